@@ -1,6 +1,7 @@
 /**
  * 通用数据提取引擎脚本
- * 根据配置规则从 API 响应中提取数据
+ * 打开登录页、检测登录成功，并捕获规则引用的 API 请求头/响应体、localStorage 和 Cookie；
+ * 提取规则由 Rust 端的 DataExtractorEngine 解释
  * 用法: node generic_extractor.js <platform_id> [output_file] [--config <config_path>]
  */
 
@@ -38,6 +39,15 @@ const GENERIC_LOGIN_TIP = `
 // 日志控制 - info始终输出，debug需要DEBUG=1
 const ENABLE_DEBUG_LOG = process.env.DEBUG === '1';
 
+// 无头模式（AMM_HEADLESS=1）：用于已登录会话的凭证刷新，看不到二维码，
+// 登录检测超时后直接失败，避免不可见的浏览器一直等待
+const HEADLESS = process.env.AMM_HEADLESS === '1';
+const HEADLESS_LOGIN_TIMEOUT_MS = 30000;
+const HEADLESS_LOGIN_REQUIRED = '无头模式下未检测到已登录状态，请使用有界面模式扫码登录';
+
+// 浏览器资料目录（AMM_USER_DATA_DIR）：指定时使用持久化上下文，登录状态保存在该目录中
+const USER_DATA_DIR = process.env.AMM_USER_DATA_DIR || '';
+
 function log(...args) {
     if (ENABLE_DEBUG_LOG) console.error('[DEBUG]', ...args);
 }
@@ -286,18 +296,38 @@ async function main() {
     let browser = null;
 
     try {
-        info('启动浏览器...');
-        browser = await chromium.launch({
-            headless: false,
-            args: ['--no-sandbox', '--disable-setuid-sandbox', '--disable-dev-shm-usage']
-        });
-
-        const context = await browser.newContext({
-            viewport: { width: 1280, height: 800 },
-            userAgent: 'Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36'
-        });
-
-        const page = await context.newPage();
+        // 账号指纹（User-Agent / 窗口大小）：由应用通过环境变量传入，未指定时使用默认值
+        let fingerprint = {};
+        if (process.env.AMM_FINGERPRINT) {
+            try {
+                fingerprint = JSON.parse(process.env.AMM_FINGERPRINT) || {};
+            } catch (e) {
+                error(`解析指纹配置失败: ${e.message}`);
+            }
+        }
+        const viewport = fingerprint.viewport || { width: 1280, height: 800 };
+        const userAgent = fingerprint.user_agent || 'Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36';
+        info(`浏览器指纹: ${viewport.width}x${viewport.height}, ${userAgent}`);
+
+        info(`启动浏览器...${HEADLESS ? ' (无头模式)' : ''}`);
+        const launchArgs = ['--no-sandbox', '--disable-setuid-sandbox', '--disable-dev-shm-usage'];
+        let context;
+        if (USER_DATA_DIR) {
+            // 账号独立的浏览器资料目录：保留上次登录的会话，重新授权无需再次扫码
+            info(`使用浏览器资料目录: ${USER_DATA_DIR}`);
+            context = await chromium.launchPersistentContext(USER_DATA_DIR, {
+                headless: HEADLESS,
+                args: launchArgs,
+                viewport,
+                userAgent
+            });
+            browser = context;
+        } else {
+            browser = await chromium.launch({ headless: HEADLESS, args: launchArgs });
+            context = await browser.newContext({ viewport, userAgent });
+        }
+
+        const page = context.pages()[0] || await context.newPage();
         info('浏览器启动成功');
 
         // 存储捕获的 API 数据
@@ -305,39 +335,11 @@ async function main() {
 
         // 解析提取规则，获取需要监听的 API 路径
         const rules = config.extract_rules || {};
-        const userInfoRules = rules.user_info || {};  // 使用 snake_case
-        const headerRules = rules.request_headers || {};  // 使用 snake_case
-
-        const localStorageKeys = rules.local_storage || [];
-        // 收集所有需要捕获的 API 路径
-        const apiPaths = new Set();
+        // 需要捕获的接口路径和 localStorage key 由 Rust 提取引擎根据规则计算后传入
+        const localStorageKeys = config.local_storage_keys || rules.local_storage || [];
+        const apiPaths = new Set(config.capture_api_paths || []);
         info('');
         info('========== 解析 API 路径 ==========');
-        info('userInfo 规则解析:');
-        for (const [key, rule] of Object.entries(userInfoRules)) {
-            info(`  ${key}: "${rule}"`);
-            const parsed = RuleParser.parse(rule);
-            if (parsed.type === 'api') {
-                apiPaths.add(parsed.apiPath);
-                // info(`    -> ✅ 匹配 API 路径: "${parsed.apiPath}"`);
-            } else {
-                // info(`    -> ❌ 非 API 规则 (type=${parsed.type})`);
-            }
-        }
-        info('');
-        info('requestHeaders 规则解析:');
-        for (const [key, rule] of Object.entries(headerRules)) {
-            info(`  ${key}: "${rule}"`);
-            const parsed = RuleParser.parse(rule);
-            if (parsed.type === 'api') {
-                apiPaths.add(parsed.apiPath);
-                // info(`    -> ✅ 匹配 API 路径: "${parsed.apiPath}"`);
-            } else {
-                // info(`    -> ❌ 非 API 规则 (type=${parsed.type})`);
-            }
-        }
-        info('');
-        info(`==================================`);
         info(`需要监听的 API 路径列表: ${Array.from(apiPaths).join(', ')}`);
         info(`共 ${apiPaths.size} 个 API 路径`);
         info('');
@@ -432,7 +434,7 @@ async function main() {
             let loginVerified = false;
             let retryCount = 0;
             let browserClosed = false;
-            const MAX_RETRIES = 100; // 最多尝试100次，避免无限循环
+            const MAX_RETRIES = HEADLESS ? 1 : 100; // 最多尝试100次，避免无限循环；无头模式只等一次
 
             // 使用 Promise.race 来同时等待浏览器关闭和 API 响应
             const checkBrowserClosed = async () => {
@@ -497,6 +499,8 @@ async function main() {
                 info(`[等待登录] 用户取消了操作`);
                 // 抛出错误表示取消
                 throw new Error('操作被取消');
+            } else if (!loginVerified && HEADLESS) {
+                throw new Error(HEADLESS_LOGIN_REQUIRED);
             } else if (!loginVerified) {
                 error(`[等待登录] 多次尝试后仍未检测到登录成功，请检查配置或网络状态`);
                 // 不抛出错误，让用户看到提取结果是空的，便于调试
@@ -510,12 +514,12 @@ async function main() {
                 }
             }
         } else {
-            // URL 匹配模式：等待 URL 变化
-            if (config.login_success_pattern) {
-                await page.waitForURL(config.login_success_pattern, { timeout: 0 });
-            } else {
-                // 默认等待 creator-micro 页面
-                await page.waitForURL('**/creator-micro/**', { timeout: 0 });
+            // URL 匹配模式：等待 URL 变化（默认等待 creator-micro 页面）
+            const loginPattern = config.login_success_pattern || '**/creator-micro/**';
+            try {
+                await page.waitForURL(loginPattern, { timeout: HEADLESS ? HEADLESS_LOGIN_TIMEOUT_MS : 0 });
+            } catch (e) {
+                throw HEADLESS ? new Error(HEADLESS_LOGIN_REQUIRED) : e;
             }
             info('✅ 登录成功 (URL 匹配)');
         }
@@ -552,127 +556,30 @@ async function main() {
         }
         
 
-        // 构建 requestHeaders（用于 cookie 提取）
-        const requestHeaders = capturedApiData[Object.keys(capturedApiData)[0]]?.requestHeaders || {};
-
-        // 使用规则提取数据
-        const extractionRules = config.extract_rules || {};
-        const extractedData = {};
-
-        info('--- 提取用户信息 (user_info) ---');
-        // 提取用户信息
-        if (extractionRules.user_info) {
-            for (const [key, rule] of Object.entries(extractionRules.user_info)) {
-                extractedData[key] = evaluateRule(rule, capturedApiData, requestHeaders);
-                // info(`  ${key}: "${rule}"`);
-                // info(`    -> 提取结果: "${extractedData[key]}"`);
-                // if (extractedData[key] === '') {
-                //     info(`    -> ⚠️ 结果为空! 检查 API 数据是否已捕获`);
-                // }
-            }
-        }
-
-        info('--- 提取请求头 (requestHeaders) ---');
-        // 提取请求头
-        if (extractionRules.request_headers) {
-            for (const [key, rule] of Object.entries(extractionRules.request_headers)) {
-                extractedData[key] = evaluateRule(rule, capturedApiData, requestHeaders);
-                // info(`  ${key}: "${rule}"`);
-                // info(`    -> 提取结果: "${extractedData[key]}"`);
-            }
-        }
-
-
-        info('--- 提取cookie ---');
-        // 处理 cookie 提取规则
-        let cookie = cookieString;
-        if (extractionRules.cookie) {
-            const cookieRule = extractionRules.cookie;
-            if (cookieRule.source === 'from_api' && cookieRule.apiPath) {
-                for (const [url, data] of Object.entries(capturedApiData)) {
-                    if (url.includes(cookieRule.apiPath)) {
-                        const headerName = cookieRule.headerName || 'cookie';
-                        cookie = data.requestHeaders[headerName] || cookieString;
-                        break;
-                    }
-                }
-            }
-        }
-
-        // 打印提取结果
+        // 打印捕获汇总
         info('');
-        info('========== 提取结果汇总 ==========');
+        info('========== 捕获结果汇总 ==========');
         info(`capturedApiData 接口数: ${Object.keys(capturedApiData).length}`);
-        for (const [url, data] of Object.entries(capturedApiData)) {
+        for (const url of Object.keys(capturedApiData)) {
             info(`  ${url}`);
         }
-        info('');
-        info('extractedData 空字段检查:');
-        for (const [key, value] of Object.entries(extractedData)) {
-            if (value === '') {
-                info(`  ⚠️ ${key} 为空`);
-            } else {
-                info(`  ✅ ${key}: ${value.substring(0, 50)}${value.length > 50 ? '...' : ''}`);
-            }
-        }
-        info('');
         info(`localStorageItems长度: ${localStorageItems.length}`);
-        info(`cookie 长度: ${cookie.length}`);
-        info(`cookie 前100字符: ${cookie.substring(0, 100)}...`);
+        info(`cookie 长度: ${cookieString.length}`);
         info('===================================');
 
-        // 构建结果 - 保持配置结构，只替换规则
+        // 构建结果 - 只输出捕获的原始数据，规则由 Rust 提取引擎解释
         const result = {
             step: 'completed',
-            message: `授权成功！账号: ${extractedData.nickname || config.platform_name + '用户'}`,
+            message: '登录成功，数据已捕获',
             url: page.url(),
-            // cookie - 优先从 API 提取，失败则回退到浏览器 cookie
-            cookie: (() => {
-                const cookieRule = extractionRules.cookie;
-                // 如果没有配置规则，使用浏览器 cookie
-                if (!cookieRule) {
-                    return cookieString;
-                }
-                if (typeof cookieRule === 'string') return cookieRule;
-                if (cookieRule.source === 'from_api') {
-                    for (const [url, data] of Object.entries(capturedApiData)) {
-                        if (cookieRule.apiPath && url.includes(cookieRule.apiPath)) {
-                            const headerName = cookieRule.headerName || 'cookie';
-                            const apiCookie = data.requestHeaders[headerName];
-                            if (apiCookie) {
-                                return apiCookie;
-                            }
-                        }
-                    }
-                }
-                return cookieString;
-            })(),
-            // local_storage - 保持配置结构
+            // 浏览器 cookie（规则未取到 cookie 时的回退值）
+            cookie: cookieString,
             local_storage: localStorageItems,
-            // request_headers - 保持配置结构，替换规则
-            request_headers: (() => {
-                const headers = {};
-                for (const [key, rule] of Object.entries(extractionRules.request_headers || {})) {
-                    if (typeof rule === 'string' && rule.startsWith('${api:')) {
-                        headers[key] = evaluateRule(rule, capturedApiData, requestHeaders);
-                    } else {
-                        headers[key] = rule;
-                    }
-                }
-                return headers;
-            })(),
-            // user_info - 保持配置结构，替换规则
-            user_info: (() => {
-                const userInfo = {};
-                for (const [key, rule] of Object.entries(extractionRules.user_info || {})) {
-                    if (typeof rule === 'string' && rule.startsWith('${api:')) {
-                        userInfo[key] = evaluateRule(rule, capturedApiData, requestHeaders);
-                    } else {
-                        userInfo[key] = rule;
-                    }
-                }
-                return userInfo;
-            })(),
+            captures: Object.entries(capturedApiData).map(([url, data]) => ({
+                url,
+                request_headers: data.requestHeaders,
+                response_body: data.responseBody,
+            })),
         };
 
         if (outputFile) {
@@ -684,7 +591,7 @@ async function main() {
         console.log('RESULT_JSON_START');
         console.log(JSON.stringify(result));
         console.log('RESULT_JSON_END');
-        info(`授权完成: ${result.user_info.nickname}`);
+        info(`数据捕获完成: ${result.captures.length} 个接口`);
 
     } catch (err) {
         error(`错误: ${err.message}`);
@@ -0,0 +1,160 @@
+// Browser auth sessions - several login windows at the same time
+// 浏览器授权会话 - 每次授权是一个独立会话（会话ID → 状态），多个账号可同时打开登录窗口：
+// - 授权在后台任务中运行，start_browser_auth 立即返回会话ID，前端按会话ID轮询状态
+// - 会话结束（完成/失败/取消）后保留一段时间供前端取回最终状态，之后自动清理
+// - 取消时先结束会话的浏览器进程，之后才释放并发名额；已取消会话的授权结果不会保存
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use crate::browser::{BrowserAuthResult, BrowserAuthStep, BrowserProcessHandle};
+
+/// 同时进行的授权会话上限（每个会话一个浏览器进程）
+pub const MAX_BROWSER_AUTH_SESSIONS: usize = 5;
+
+/// 已结束的会话保留时间
+const FINISHED_SESSION_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Snapshot of one browser auth session
+/// 授权会话状态
+#[derive(Debug, Clone, Serialize)]
+pub struct BrowserAuthSession {
+    pub session_id: String,
+    pub platform_id: String,
+    /// 重新授权的账号ID（新账号为空）
+    pub account_id: Option<String>,
+    /// 授权完成后保存的账号ID
+    pub saved_account_id: Option<String>,
+    pub result: BrowserAuthResult,
+    /// 会话是否已结束（完成、失败或取消）
+    pub finished: bool,
+    pub cancelled: bool,
+    #[serde(skip)]
+    finished_at: Option<Instant>,
+    #[serde(skip)]
+    process: BrowserProcessHandle,
+}
+
+impl BrowserAuthSession {
+    /// 是否仍在授权中
+    pub fn is_active(&self) -> bool {
+        !self.finished
+    }
+}
+
+/// Browser auth session manager
+/// 授权会话管理器（AppState 中共享一个实例）
+#[derive(Debug, Default)]
+pub struct BrowserAuthSessions {
+    sessions: Mutex<HashMap<String, BrowserAuthSession>>,
+}
+
+impl BrowserAuthSessions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, BrowserAuthSession>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 创建会话；同一账号已有进行中的会话或超过并发上限时返回错误
+    pub fn create(&self, platform_id: &str, account_id: Option<&str>) -> Result<BrowserAuthSession, String> {
+        let mut sessions = self.lock();
+        sessions.retain(|_, s| s.finished_at.map(|at| at.elapsed() < FINISHED_SESSION_TTL).unwrap_or(true));
+
+        if let Some(account_id) = account_id {
+            if sessions.values().any(|s| s.is_active() && s.account_id.as_deref() == Some(account_id)) {
+                return Err("该账号正在授权中".to_string());
+            }
+        }
+        if sessions.values().filter(|s| s.is_active()).count() >= MAX_BROWSER_AUTH_SESSIONS {
+            return Err(format!("同时进行的授权不能超过 {} 个，请先完成或取消其他授权", MAX_BROWSER_AUTH_SESSIONS));
+        }
+
+        let session = BrowserAuthSession {
+            session_id: uuid::Uuid::new_v4().to_string(),
+            platform_id: platform_id.to_string(),
+            account_id: account_id.map(|id| id.to_string()),
+            saved_account_id: None,
+            result: BrowserAuthResult {
+                step: BrowserAuthStep::LaunchingBrowser,
+                message: format!("正在启动浏览器 for {}...", platform_id),
+                need_poll: true,
+                ..BrowserAuthResult::default()
+            },
+            finished: false,
+            cancelled: false,
+            finished_at: None,
+            process: BrowserProcessHandle::new(),
+        };
+        sessions.insert(session.session_id.clone(), session.clone());
+        Ok(session)
+    }
+
+    /// 获取会话状态
+    pub fn get(&self, session_id: &str) -> Option<BrowserAuthSession> {
+        self.lock().get(session_id).cloned()
+    }
+
+    /// 所有会话（进行中的在前）
+    pub fn list(&self) -> Vec<BrowserAuthSession> {
+        let mut sessions: Vec<BrowserAuthSession> = self.lock().values().cloned().collect();
+        sessions.sort_by_key(|s| s.finished);
+        sessions
+    }
+
+    /// 会话的浏览器进程句柄，授权时交给 BrowserAutomator
+    pub fn process(&self, session_id: &str) -> Option<BrowserProcessHandle> {
+        self.lock().get(session_id).map(|s| s.process.clone())
+    }
+
+    /// 会话是否已被取消
+    pub fn is_cancelled(&self, session_id: &str) -> bool {
+        self.lock().get(session_id).is_some_and(|s| s.cancelled)
+    }
+
+    /// 记录会话的最终结果；已取消的会话保持取消状态，返回 false
+    pub fn finish(&self, session_id: &str, result: BrowserAuthResult, saved_account_id: Option<String>) -> bool {
+        let mut sessions = self.lock();
+        let Some(session) = sessions.get_mut(session_id) else { return false };
+        if session.cancelled {
+            return false;
+        }
+        session.result = BrowserAuthResult { need_poll: false, ..result };
+        session.saved_account_id = saved_account_id;
+        session.finished = true;
+        session.finished_at = Some(Instant::now());
+        true
+    }
+
+    /// 取消会话（已结束的会话不受影响），返回是否取消了进行中的会话
+    ///
+    /// 先结束会话的浏览器进程，浏览器关闭后才把会话标记为结束、释放并发名额
+    pub fn cancel(&self, session_id: &str) -> bool {
+        let process = {
+            let mut sessions = self.lock();
+            match sessions.get_mut(session_id) {
+                Some(session) if session.is_active() && !session.cancelled => {
+                    session.cancelled = true;
+                    session.result.message = "正在取消授权...".to_string();
+                    session.process.clone()
+                }
+                _ => return false,
+            }
+        };
+
+        process.kill();
+
+        let mut sessions = self.lock();
+        if let Some(session) = sessions.get_mut(session_id) {
+            session.finished = true;
+            session.finished_at = Some(Instant::now());
+            session.result.step = BrowserAuthStep::Idle;
+            session.result.message = "已取消授权".to_string();
+            session.result.need_poll = false;
+        }
+        true
+    }
+}
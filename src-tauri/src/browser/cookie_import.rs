@@ -0,0 +1,393 @@
+// Browser Cookie Import
+// 从本地 Chrome/Edge 配置文件导入 Cookie（免扫码授权）
+//
+// Chromium 系浏览器将 Cookie 存储在配置文件目录下的 `Cookies` SQLite 数据库中，
+// 值经过 AES-128-CBC 加密（前缀 v10/v11）：
+// - macOS: 密钥来自钥匙串中的 "<Browser> Safe Storage"，PBKDF2 迭代 1003 次
+// - Linux: v10 使用默认密码 "peanuts"，v11 使用 Secret Service（GNOME 钥匙串）中保存的密码，PBKDF2 迭代 1 次
+// - Windows: 使用 DPAPI 及应用绑定加密，无法在浏览器外解密，返回不支持的提示（可改用 cookies.txt 导入）
+// 无法解密的 Cookie 不会被静默丢弃，读取结果中会列出并提示用户
+
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
+
+/// PBKDF2 盐值（Chromium 固定值）
+const KEY_SALT: &[u8] = b"saltysalt";
+
+/// AES-CBC 初始向量（16 个空格）
+const KEY_IV: &[u8; 16] = b"                ";
+
+/// 未指定时使用的 User-Agent（与 macOS 版 Chrome 一致）
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36";
+
+/// 数据库 meta.version 达到该值后，解密结果前 32 字节为 host_key 的 SHA256
+const META_VERSION_HOST_DIGEST: i64 = 24;
+
+/// 支持的浏览器
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BrowserKind {
+    Chrome,
+    Edge,
+}
+
+impl BrowserKind {
+    /// 浏览器显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BrowserKind::Chrome => "Google Chrome",
+            BrowserKind::Edge => "Microsoft Edge",
+        }
+    }
+
+    /// 钥匙串中的 Safe Storage 服务名（macOS）
+    fn safe_storage_service(&self) -> &'static str {
+        match self {
+            BrowserKind::Chrome => "Chrome Safe Storage",
+            BrowserKind::Edge => "Microsoft Edge Safe Storage",
+        }
+    }
+
+    /// Secret Service 中保存 Safe Storage 密码的 application 属性（Linux）
+    fn secret_service_application(&self) -> &'static str {
+        match self {
+            BrowserKind::Chrome => "chrome",
+            BrowserKind::Edge => "microsoft-edge",
+        }
+    }
+
+    /// 用户数据目录
+    fn user_data_dir(&self) -> PathBuf {
+        let home = PathBuf::from(std::env::var("HOME").unwrap_or_else(|_| ".".to_string()));
+        if cfg!(target_os = "macos") {
+            let base = home.join("Library").join("Application Support");
+            match self {
+                BrowserKind::Chrome => base.join("Google").join("Chrome"),
+                BrowserKind::Edge => base.join("Microsoft Edge"),
+            }
+        } else {
+            let base = home.join(".config");
+            match self {
+                BrowserKind::Chrome => base.join("google-chrome"),
+                BrowserKind::Edge => base.join("microsoft-edge"),
+            }
+        }
+    }
+}
+
+/// 本地浏览器配置文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserProfile {
+    pub browser: BrowserKind,
+    /// 配置文件目录名（Default / Profile 1 ...）
+    pub name: String,
+    /// 配置文件目录
+    pub path: String,
+}
+
+/// 从浏览器读取的 Cookie
+#[derive(Debug, Clone)]
+pub struct BrowserCookie {
+    pub host: String,
+    pub name: String,
+    pub value: String,
+}
+
+/// 读取浏览器 Cookie 的结果
+#[derive(Debug, Clone, Default)]
+pub struct BrowserCookies {
+    pub cookies: Vec<BrowserCookie>,
+    /// 无法解密的 Cookie 名称
+    pub undecryptable: Vec<String>,
+}
+
+/// 当前系统是否支持直接读取浏览器 Cookie，不支持时返回提示
+fn ensure_supported_platform() -> Result<(), String> {
+    if cfg!(target_os = "macos") || cfg!(target_os = "linux") {
+        Ok(())
+    } else if cfg!(target_os = "windows") {
+        Err("Windows 版 Chrome/Edge 的 Cookie 使用 DPAPI 和应用绑定加密，暂不支持直接读取，\
+             请使用浏览器扩展导出 cookies.txt 后导入".to_string())
+    } else {
+        Err("当前系统暂不支持导入浏览器 Cookie".to_string())
+    }
+}
+
+/// 列出本机已安装浏览器的所有配置文件（仅包含存在 Cookies 数据库的目录；不支持的系统返回空列表）
+pub fn list_browser_profiles() -> Vec<BrowserProfile> {
+    let mut profiles = Vec::new();
+    if ensure_supported_platform().is_err() {
+        return profiles;
+    }
+
+    for browser in [BrowserKind::Chrome, BrowserKind::Edge] {
+        let entries = match std::fs::read_dir(browser.user_data_dir()) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name != "Default" && !name.starts_with("Profile ") {
+                continue;
+            }
+            if find_cookie_db(&entry.path()).is_some() {
+                profiles.push(BrowserProfile {
+                    browser,
+                    name,
+                    path: entry.path().to_string_lossy().to_string(),
+                });
+            }
+        }
+    }
+
+    profiles
+}
+
+/// 读取指定配置文件中属于 `domain` 的 Cookie，并拼接为 Cookie 请求头
+///
+/// 有 Cookie 无法解密时返回错误并列出这些 Cookie：缺少部分 Cookie 时登录状态不完整，不应静默导入
+pub fn read_cookie_header(browser: BrowserKind, profile_path: &Path, domain: &str) -> Result<String, String> {
+    let BrowserCookies { cookies, undecryptable } = read_cookies(browser, profile_path, domain)?;
+    if !undecryptable.is_empty() {
+        return Err(format!(
+            "{} 中有 {} 条 {} 的 Cookie 无法解密（{}），请确认已允许访问系统钥匙串，或改用 cookies.txt 导入",
+            browser.display_name(), undecryptable.len(), domain, undecryptable.join(", ")
+        ));
+    }
+    if cookies.is_empty() {
+        return Err(format!("{} 中未找到 {} 的登录 Cookie", browser.display_name(), domain));
+    }
+
+    Ok(cookies.iter()
+        .map(|c| format!("{}={}", c.name, c.value))
+        .collect::<Vec<_>>()
+        .join("; "))
+}
+
+/// 解析 Netscape 格式的 cookies.txt（浏览器扩展、curl 等导出）
+///
+/// 每行 7 列以制表符分隔：域名、子域名标记、路径、secure、过期时间、名称、值；
+/// `#HttpOnly_` 前缀的行是 HttpOnly Cookie，其余 `#` 开头的行为注释
+pub fn parse_netscape_cookies(content: &str) -> Vec<BrowserCookie> {
+    content.lines()
+        .filter_map(|line| {
+            let line = line.trim_end_matches('\r');
+            let line = line.strip_prefix("#HttpOnly_").unwrap_or(line);
+            if line.trim().is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 7 {
+                return None;
+            }
+            Some(BrowserCookie {
+                host: fields[0].to_string(),
+                name: fields[5].to_string(),
+                value: fields[6].to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 筛选属于 `domain`（包含子域名）的 Cookie 并拼接为 Cookie 请求头；没有匹配时返回 None
+pub fn cookie_header_for_domain(cookies: &[BrowserCookie], domain: &str) -> Option<String> {
+    let header = cookies.iter()
+        .filter(|c| {
+            let host = c.host.trim_start_matches('.');
+            host == domain || host.ends_with(&format!(".{}", domain))
+        })
+        .map(|c| format!("{}={}", c.name, c.value))
+        .collect::<Vec<_>>()
+        .join("; ");
+    if header.is_empty() { None } else { Some(header) }
+}
+
+/// 读取指定配置文件中属于 `domain` 的 Cookie（包含子域名）
+pub fn read_cookies(browser: BrowserKind, profile_path: &Path, domain: &str) -> Result<BrowserCookies, String> {
+    ensure_supported_platform()?;
+    let db_path = find_cookie_db(profile_path)
+        .ok_or_else(|| format!("配置文件中不存在 Cookies 数据库: {}", profile_path.display()))?;
+
+    // 浏览器运行时会锁定数据库，复制一份再读取
+    let temp_path = std::env::temp_dir().join(format!("amm_cookies_{}.db", uuid::Uuid::new_v4()));
+    std::fs::copy(&db_path, &temp_path)
+        .map_err(|e| format!("复制 Cookies 数据库失败: {}", e))?;
+
+    let result = read_cookies_from_db(browser, &temp_path, domain);
+    std::fs::remove_file(&temp_path).ok();
+    result
+}
+
+/// 从复制后的数据库读取并解密 Cookie
+fn read_cookies_from_db(browser: BrowserKind, db_path: &Path, domain: &str) -> Result<BrowserCookies, String> {
+    let conn = Connection::open(db_path)
+        .map_err(|e| format!("打开 Cookies 数据库失败: {}", e))?;
+
+    let meta_version: i64 = conn.query_row(
+        "SELECT value FROM meta WHERE key = 'version'",
+        [],
+        |row| row.get::<_, String>(0),
+    ).ok().and_then(|v| v.parse().ok()).unwrap_or(0);
+
+    let mut keys = CookieKeys::new(browser);
+
+    let mut stmt = conn.prepare(
+        "SELECT host_key, name, value, encrypted_value FROM cookies WHERE host_key = ? OR host_key LIKE ?"
+    ).map_err(|e| format!("查询 Cookies 失败: {}", e))?;
+
+    let rows = stmt.query_map(
+        [domain.to_string(), format!("%.{}", domain)],
+        |row| Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Vec<u8>>(3)?,
+        )),
+    ).map_err(|e| format!("查询 Cookies 失败: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("读取 Cookies 失败: {}", e))?;
+
+    let mut result = BrowserCookies::default();
+    for (host, name, value, encrypted) in rows {
+        let value = if !value.is_empty() || encrypted.is_empty() {
+            value
+        } else {
+            let decrypted = keys.key_for(&encrypted)
+                .and_then(|key| decrypt_value(&key, &encrypted, meta_version >= META_VERSION_HOST_DIGEST));
+            match decrypted {
+                Ok(v) => v,
+                Err(e) => {
+                    tracing::warn!("[CookieImport] 解密 Cookie {} 失败: {}", name, e);
+                    result.undecryptable.push(name);
+                    continue;
+                }
+            }
+        };
+        result.cookies.push(BrowserCookie { host, name, value });
+    }
+
+    tracing::info!("[CookieImport] 从 {} 读取到 {} 条 {} 的 Cookie，{} 条无法解密",
+        browser.display_name(), result.cookies.len(), domain, result.undecryptable.len());
+    Ok(result)
+}
+
+/// 查找配置文件中的 Cookies 数据库（新版本位于 Network 子目录）
+fn find_cookie_db(profile_path: &Path) -> Option<PathBuf> {
+    [profile_path.join("Network").join("Cookies"), profile_path.join("Cookies")]
+        .into_iter()
+        .find(|p| p.exists())
+}
+
+/// 按加密前缀派生并缓存 AES 密钥（读取钥匙串可能弹窗，每种密钥只读取一次）
+struct CookieKeys {
+    browser: BrowserKind,
+    v10: Option<Result<[u8; 16], String>>,
+    v11: Option<Result<[u8; 16], String>>,
+}
+
+impl CookieKeys {
+    fn new(browser: BrowserKind) -> Self {
+        Self { browser, v10: None, v11: None }
+    }
+
+    fn key_for(&mut self, encrypted: &[u8]) -> Result<[u8; 16], String> {
+        let browser = self.browser;
+        let slot = if encrypted.starts_with(b"v10") {
+            &mut self.v10
+        } else if encrypted.starts_with(b"v11") {
+            &mut self.v11
+        } else {
+            return Err("未知的加密格式".to_string());
+        };
+        let v11 = encrypted.starts_with(b"v11");
+        slot.get_or_insert_with(|| derive_key(browser, v11)).clone()
+    }
+}
+
+/// 派生 AES 密钥；`v11` 为 Linux 上使用 Secret Service 密码加密的值
+fn derive_key(browser: BrowserKind, v11: bool) -> Result<[u8; 16], String> {
+    let (password, iterations) = if cfg!(target_os = "macos") {
+        (read_keychain_password(browser)?, 1003)
+    } else if v11 {
+        (read_secret_service_password(browser)?, 1)
+    } else {
+        ("peanuts".to_string(), 1)
+    };
+
+    let mut key = [0u8; 16];
+    pbkdf2::pbkdf2_hmac::<sha1::Sha1>(password.as_bytes(), KEY_SALT, iterations, &mut key);
+    Ok(key)
+}
+
+/// 从 Secret Service（GNOME 钥匙串）读取 Safe Storage 密码（Linux，需要安装 secret-tool）
+fn read_secret_service_password(browser: BrowserKind) -> Result<String, String> {
+    let output = std::process::Command::new("secret-tool")
+        .args(["lookup", "application", browser.secret_service_application()])
+        .output()
+        .map_err(|e| format!("读取系统钥匙串失败（需要 secret-tool）: {}", e))?;
+
+    let password = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || password.is_empty() {
+        return Err(format!("系统钥匙串中未找到 {} 的 Safe Storage 密码", browser.display_name()));
+    }
+    Ok(password)
+}
+
+/// 从 macOS 钥匙串读取 Safe Storage 密码（系统会弹窗请求用户授权）
+fn read_keychain_password(browser: BrowserKind) -> Result<String, String> {
+    let output = std::process::Command::new("security")
+        .args(["find-generic-password", "-w", "-s", browser.safe_storage_service()])
+        .output()
+        .map_err(|e| format!("读取钥匙串失败: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("未获得 {} 的钥匙串访问授权", browser.display_name()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 解密单个 Cookie 值
+fn decrypt_value(key: &[u8; 16], encrypted: &[u8], has_host_digest: bool) -> Result<String, String> {
+    if encrypted.len() < 3 || !(encrypted.starts_with(b"v10") || encrypted.starts_with(b"v11")) {
+        return Err("未知的加密格式".to_string());
+    }
+
+    let plain = Aes128CbcDec::new(key.into(), KEY_IV.into())
+        .decrypt_padded_vec_mut::<Pkcs7>(&encrypted[3..])
+        .map_err(|e| format!("AES 解密失败: {}", e))?;
+
+    let plain = if has_host_digest && plain.len() >= 32 { &plain[32..] } else { &plain[..] };
+    String::from_utf8(plain.to_vec()).map_err(|e| format!("Cookie 不是有效的 UTF-8: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aes::cipher::BlockEncryptMut;
+
+    fn encrypt_value(key: &[u8; 16], plain: &[u8]) -> Vec<u8> {
+        let mut data = b"v10".to_vec();
+        data.extend(cbc::Encryptor::<aes::Aes128>::new(key.into(), KEY_IV.into()).encrypt_padded_vec_mut::<Pkcs7>(plain));
+        data
+    }
+
+    #[test]
+    fn test_decrypt_value() {
+        let mut key = [0u8; 16];
+        pbkdf2::pbkdf2_hmac::<sha1::Sha1>(b"peanuts", KEY_SALT, 1, &mut key);
+
+        assert_eq!(decrypt_value(&key, &encrypt_value(&key, b"abc123"), false).unwrap(), "abc123");
+
+        let mut with_digest = vec![0u8; 32];
+        with_digest.extend_from_slice(b"abc123");
+        assert_eq!(decrypt_value(&key, &encrypt_value(&key, &with_digest), true).unwrap(), "abc123");
+
+        assert!(decrypt_value(&[1u8; 16], &encrypt_value(&key, b"abc123"), false).is_err());
+        assert!(decrypt_value(&key, b"v20xxxx", false).is_err());
+    }
+}
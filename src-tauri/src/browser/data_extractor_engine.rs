@@ -1,30 +1,29 @@
 // Data Extractor Engine
-// 通用数据提取引擎 - 根据配置自动提取用户数据
-
-use crate::browser::BrowserAuthResult;
+// 通用数据提取引擎 - 根据平台配置的 extract_rules 从授权会话捕获的数据中提取用户信息和凭证
+//
+// 浏览器脚本只负责登录检测和捕获原始数据（规则引用的接口的请求头/响应体、localStorage、浏览器 Cookie），
+// 规则统一在这里解释，新增平台授权只需要一份提取配置：
+// - `${api:<路径>:request:headers:<请求头>}`  接口请求头
+// - `${api:<路径>:response:body:<字段>:<字段>...}`  接口响应 JSON（数组用下标）
+// - `${localStorage:<key>}`  localStorage 值
+// - 其他字符串原样作为固定值
+// cookie 规则：{ source: "from_api", api_path, header_name } 或 { source: "from_browser" }，
+// 接口中取不到时回退到浏览器 Cookie
+
+use crate::browser::{BrowserAuthResult, BrowserAuthStep};
 use crate::storage::DatabaseManager;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::Arc;
-
-/// 浏览器引擎配置
-pub struct DataExtractorEngine {
-    /// 数据库管理器
-    db_manager: Arc<DatabaseManager>,
-    /// 当前使用的配置
-    current_config: Option<ExtractorConfig>,
-    /// 捕获的 API 响应数据
-    captured_api_data: HashMap<String, ApiCaptureData>,
-    /// 捕获的请求头
-    captured_request_headers: HashMap<String, HashMap<String, String>>,
-}
 
-#[derive(Debug, Clone)]
 /// 捕获的 API 数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiCaptureData {
     pub url: String,
+    /// 请求头（键为小写）
+    #[serde(default)]
     pub request_headers: HashMap<String, String>,
+    #[serde(default)]
     pub response_body: Option<Value>,
 }
 
@@ -45,6 +44,55 @@ pub struct LocalStorageItem {
     pub value: String,
 }
 
+/// Outcome of one rule in a config test
+/// 配置测试中单条规则的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleStatus {
+    /// 取到非空值
+    Matched,
+    /// 接口已捕获（或 localStorage 已读取），但按路径取到的值为空，通常是字段路径写错
+    Empty,
+    /// 规则引用的接口在会话中没有被请求
+    NotCaptured,
+    /// 规则格式错误
+    Invalid,
+    /// 固定值
+    Fixed,
+}
+
+/// 单条规则的测试结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleCheck {
+    /// user_info / request_headers / local_storage / cookie
+    pub section: String,
+    pub key: String,
+    pub rule: String,
+    pub status: RuleStatus,
+    /// 提取到的值（过长时截断）
+    pub value: String,
+    /// 状态说明
+    pub message: String,
+}
+
+/// Result of testing an extractor config against a live login session
+/// 提取配置测试结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractorTestReport {
+    pub platform_id: String,
+    /// 会话结束时的页面地址
+    pub current_url: String,
+    /// 会话中捕获到的接口 URL
+    pub captured_urls: Vec<String>,
+    pub rules: Vec<RuleCheck>,
+    pub matched: usize,
+    /// 未取到值的规则数（Empty / NotCaptured / Invalid）
+    pub failed: usize,
+}
+
+/// 测试报告中值的最大显示长度
+const REPORT_VALUE_MAX_CHARS: usize = 120;
+
 /// 简化的配置结构
 #[derive(Debug, Clone)]
 pub struct ExtractorConfig {
@@ -60,25 +108,9 @@ pub struct ExtractorConfig {
     pub extract_rules: Value,
 }
 
-impl DataExtractorEngine {
-    /// 创建新的引擎实例
-    pub fn new(db_manager: Arc<DatabaseManager>) -> Self {
+impl From<crate::storage::ExtractorConfig> for ExtractorConfig {
+    fn from(config: crate::storage::ExtractorConfig) -> Self {
         Self {
-            db_manager,
-            current_config: None,
-            captured_api_data: HashMap::new(),
-            captured_request_headers: HashMap::new(),
-        }
-    }
-
-    /// 加载平台配置
-    pub async fn load_config(&mut self, platform_id: &str) -> Result<(), String> {
-        let config = self.db_manager.get_extractor_config(platform_id)
-            .map_err(|e| e.to_string())?
-            .ok_or_else(|| format!("Platform config not found: {}", platform_id))?;
-
-        // 转换为简化结构
-        self.current_config = Some(ExtractorConfig {
             platform_id: config.platform_id,
             platform_name: config.platform_name,
             login_url: config.login_url,
@@ -89,141 +121,126 @@ impl DataExtractorEngine {
             login_success_api_value: config.login_success_api_value,
             redirect_url: config.redirect_url,
             extract_rules: config.extract_rules,
-        });
-        Ok(())
+        }
+    }
+}
+
+/// 规则解释器：一次授权会话一个实例
+pub struct DataExtractorEngine {
+    /// 当前使用的配置
+    config: ExtractorConfig,
+    /// 捕获的 API 数据（按捕获顺序，同一接口以最后一次为准）
+    captured_api_data: Vec<ApiCaptureData>,
+    /// 页面 localStorage
+    local_storage: Vec<LocalStorageItem>,
+    /// 浏览器上下文中的 Cookie
+    browser_cookie: String,
+}
+
+impl DataExtractorEngine {
+    /// 创建新的引擎实例
+    pub fn new(config: ExtractorConfig) -> Self {
+        Self {
+            config,
+            captured_api_data: Vec::new(),
+            local_storage: Vec::new(),
+            browser_cookie: String::new(),
+        }
+    }
+
+    /// 从数据库加载平台配置并创建引擎
+    pub fn load(db_manager: &DatabaseManager, platform_id: &str) -> Result<Self, String> {
+        let config = db_manager.get_extractor_config(platform_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("未找到平台配置: {}", platform_id))?;
+        Ok(Self::new(config.into()))
     }
 
     /// 获取当前配置
-    pub fn get_config(&self) -> Option<&ExtractorConfig> {
-        self.current_config.as_ref()
+    pub fn get_config(&self) -> &ExtractorConfig {
+        &self.config
     }
 
-    /// 捕获 API 请求
-    pub fn capture_api_request(&mut self, url: &str, headers: &HashMap<String, String>) {
-        // 查找匹配的 API 路径
-        if let Some(config) = &self.current_config {
-            if let Some(rules) = config.extract_rules.get("request_headers") {
-                if let Some(headers_map) = rules.as_object() {
-                    for (_key, rule_val) in headers_map {
-                        if let Some(rule) = rule_val.as_str() {
-                            if let Some(api_path) = extract_api_path(rule) {
-                                if url.contains(&api_path) {
-                                    self.captured_request_headers.insert(url.to_string(), headers.clone());
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    /// 规则引用的全部接口路径（浏览器脚本据此决定捕获哪些接口）
+    pub fn api_paths(&self) -> Vec<String> {
+        let rules = &self.config.extract_rules;
+        let mut paths: Vec<String> = rule_strings(rules)
+            .filter_map(extract_api_path)
+            .collect();
+        if let Some(path) = cookie_rule_field(rules, "api_path", "apiPath") {
+            paths.push(path.to_string());
         }
+        paths.sort();
+        paths.dedup();
+        paths
     }
 
-    /// 捕获 API 响应
-    pub fn capture_api_response(&mut self, url: &str, body: &Value) {
-        // 查找匹配的 API 路径
-        if let Some(config) = &self.current_config {
-            if let Some(rules) = config.extract_rules.get("user_info") {
-                if let Some(user_info_map) = rules.as_object() {
-                    for (_key, rule_val) in user_info_map {
-                        if let Some(rule) = rule_val.as_str() {
-                            if let Some(api_path) = extract_api_path(rule) {
-                                if url.contains(&api_path) {
-                                    self.captured_api_data.insert(url.to_string(), ApiCaptureData {
-                                        url: url.to_string(),
-                                        request_headers: self.captured_request_headers.get(url).cloned().unwrap_or_default(),
-                                        response_body: Some(body.clone()),
-                                    });
-                                    break;
-                                }
-                            }
-                        }
-                    }
+    /// 需要读取的 localStorage key（local_storage 列表 + `${localStorage:key}` 规则）
+    pub fn local_storage_keys(&self) -> Vec<String> {
+        let rules = &self.config.extract_rules;
+        let mut keys: Vec<String> = rules.get("local_storage")
+            .and_then(|l| l.as_array())
+            .map(|keys| keys.iter().filter_map(|k| k.as_str()).map(|k| k.to_string()).collect())
+            .unwrap_or_default();
+        for rule in rule_strings(rules) {
+            if let Some(key) = local_storage_key(rule) {
+                if !keys.iter().any(|k| k == key) {
+                    keys.push(key.to_string());
                 }
             }
         }
+        keys
+    }
+
+    /// 捕获 API 请求/响应（请求头键统一转小写）
+    pub fn capture(&mut self, mut data: ApiCaptureData) {
+        data.request_headers = data.request_headers.into_iter()
+            .map(|(k, v)| (k.to_lowercase(), v))
+            .collect();
+        self.captured_api_data.push(data);
+    }
+
+    /// 设置页面 localStorage
+    pub fn set_local_storage(&mut self, items: Vec<LocalStorageItem>) {
+        self.local_storage = items;
+    }
+
+    /// 设置浏览器 Cookie
+    pub fn set_browser_cookie(&mut self, cookie: &str) {
+        self.browser_cookie = cookie.to_string();
+    }
+
+    /// 评估单条规则
+    pub fn evaluate(&self, rule: &str) -> String {
+        evaluate_rule(rule, &self.captured_api_data, &self.local_storage)
     }
 
     /// 执行数据提取
     pub fn extract(&self) -> ExtractResult {
-        let config = match &self.current_config {
-            Some(c) => c,
-            None => {
-                return ExtractResult {
-                    success: false,
-                    user_info: HashMap::new(),
-                    request_headers: HashMap::new(),
-                    local_storage: Vec::new(),
-                    cookie: String::new(),
-                    message: "No configuration loaded".to_string(),
-                };
-            }
+        let rules = &self.config.extract_rules;
+
+        let evaluate_map = |section: &str| -> HashMap<String, String> {
+            rules.get(section)
+                .and_then(|s| s.as_object())
+                .map(|map| map.iter()
+                    .filter_map(|(key, rule)| rule.as_str().map(|rule| (key.clone(), self.evaluate(rule))))
+                    .filter(|(_, value)| !value.is_empty())
+                    .collect())
+                .unwrap_or_default()
         };
+        let user_info = evaluate_map("user_info");
+        let request_headers = evaluate_map("request_headers");
 
-        let mut user_info = HashMap::new();
-        let mut request_headers = HashMap::new();
-
-        // 提取用户信息
-        if let Some(rules) = config.extract_rules.get("user_info") {
-            if let Some(user_info_map) = rules.as_object() {
-                for (key, rule_val) in user_info_map {
-                    if let Some(rule) = rule_val.as_str() {
-                        let value = evaluate_rule(rule, &self.captured_api_data, &self.captured_request_headers);
-                        if !value.is_empty() {
-                            user_info.insert(key.clone(), value);
-                        }
-                    }
-                }
-            }
-        }
+        let local_storage = self.local_storage_keys().into_iter()
+            .filter_map(|key| {
+                self.local_storage.iter()
+                    .find(|item| item.key == key && !item.value.is_empty())
+                    .cloned()
+            })
+            .collect();
 
-        // 提取请求头
-        if let Some(rules) = config.extract_rules.get("request_headers") {
-            if let Some(headers_map) = rules.as_object() {
-                for (key, rule_val) in headers_map {
-                    if let Some(rule) = rule_val.as_str() {
-                        let value = evaluate_rule(rule, &self.captured_api_data, &self.captured_request_headers);
-                        if !value.is_empty() {
-                            request_headers.insert(key.clone(), value);
-                        }
-                    }
-                }
-            }
-        }
+        let cookie = self.extract_cookie();
 
-        // Cookie 处理
-        let cookie = if let Some(cookie_rule) = config.extract_rules.get("cookie") {
-            if let Some(source) = cookie_rule.get("source").and_then(|s| s.as_str()) {
-                if source == "from_browser" {
-                    self.captured_request_headers.values()
-                        .flat_map(|h| h.iter())
-                        .find(|(k, _)| k.to_lowercase() == "cookie")
-                        .map(|(_, v)| v.clone())
-                        .unwrap_or_default()
-                } else if source == "from_api" {
-                    if let Some(api_path) = cookie_rule.get("api_path").and_then(|p| p.as_str()) {
-                        if let Some(headers) = self.captured_request_headers.get(api_path) {
-                            let header_name = cookie_rule.get("header_name")
-                                .and_then(|h| h.as_str())
-                                .unwrap_or("cookie");
-                            headers.get(header_name).cloned().unwrap_or_default()
-                        } else {
-                            String::new()
-                        }
-                    } else {
-                        String::new()
-                    }
-                } else {
-                    String::new()
-                }
-            } else {
-                String::new()
-            }
-        } else {
-            String::new()
-        };
-
-        // 先计算 message，再移动值
         let message = if user_info.is_empty() && cookie.is_empty() {
             "No data extracted".to_string()
         } else {
@@ -234,51 +251,65 @@ impl DataExtractorEngine {
             success: !user_info.is_empty() || !cookie.is_empty(),
             user_info,
             request_headers,
-            local_storage: Vec::new(),
+            local_storage,
             cookie,
             message,
         }
     }
 
-    /// 生成认证结果
-    pub fn build_auth_result(&self, _nickname: &str, _avatar_url: &str, current_url: &str) -> BrowserAuthResult {
-        let extract_result = self.extract();
+    /// 按 cookie 规则提取 Cookie，取不到时回退到浏览器 Cookie
+    fn extract_cookie(&self) -> String {
+        let rules = &self.config.extract_rules;
+        let from_rule = match rules.get("cookie") {
+            Some(Value::String(rule)) => self.evaluate(rule),
+            Some(rule) if rule.get("source").and_then(|s| s.as_str()) == Some("from_api") => {
+                let header_name = cookie_rule_field(rules, "header_name", "headerName")
+                    .unwrap_or("cookie")
+                    .to_lowercase();
+                cookie_rule_field(rules, "api_path", "apiPath")
+                    .and_then(|api_path| find_capture(&self.captured_api_data, api_path))
+                    .and_then(|data| data.request_headers.get(&header_name).cloned())
+                    .unwrap_or_default()
+            }
+            _ => String::new(),
+        };
 
-        // 构建 third_param JSON
-        let third_param = serde_json::json!({
-            "request_headers": extract_result.request_headers,
-            "local_data": [],
-            "cookie": extract_result.cookie,
-        });
+        if from_rule.is_empty() {
+            self.browser_cookie.clone()
+        } else {
+            from_rule
+        }
+    }
 
-        // 从 user_info HashMap 中提取4个字段
-        let nickname = extract_result.user_info.get("nickname")
-            .cloned()
-            .unwrap_or_default();
-        let avatar_url: String = extract_result.user_info.get("avatar_url")
-            .cloned()
-            .unwrap_or_default();
-        let third_id = extract_result.user_info.get("third_id")
-            .cloned()
-            .unwrap_or_default();
-        let sec_uid = extract_result.user_info.get("sec_uid")
-            .cloned()
-            .unwrap_or_default();
+    /// 生成认证结果：request_headers / local_storage 为 JSON 字符串，与保存账号时的 third_param 结构一致
+    pub fn build_auth_result(&self, current_url: &str) -> BrowserAuthResult {
+        let extract_result = self.extract();
+        let field = |key: &str| extract_result.user_info.get(key).cloned().unwrap_or_default();
+
+        let nickname = field("nickname");
+        let message = if extract_result.success {
+            format!(
+                "授权成功！账号: {}",
+                if nickname.is_empty() { format!("{}用户", self.config.platform_name) } else { nickname.clone() }
+            )
+        } else {
+            extract_result.message.clone()
+        };
 
         BrowserAuthResult {
             step: if extract_result.success {
-                crate::browser::BrowserAuthStep::Completed
+                BrowserAuthStep::Completed
             } else {
-                crate::browser::BrowserAuthStep::Failed(extract_result.message.clone())
+                BrowserAuthStep::Failed(extract_result.message.clone())
             },
-            message: extract_result.message.clone(),
+            message,
+            avatar_url: field("avatar_url"),
+            third_id: field("third_id"),
+            sec_uid: field("sec_uid"),
             nickname,
-            avatar_url,
-            third_id,
-            sec_uid,
             cookie: extract_result.cookie,
             local_storage: serde_json::to_string(&extract_result.local_storage).unwrap_or_default(),
-            request_headers: third_param.to_string(),
+            request_headers: serde_json::to_string(&extract_result.request_headers).unwrap_or_default(),
             current_url: current_url.to_string(),
             need_poll: false,
             screenshot: None,
@@ -286,34 +317,177 @@ impl DataExtractorEngine {
         }
     }
 
+    /// 逐条检查规则在本次会话中的提取结果
+    pub fn check_rules(&self) -> Vec<RuleCheck> {
+        let rules = &self.config.extract_rules;
+        let mut checks = Vec::new();
+
+        for section in ["user_info", "request_headers"] {
+            let Some(map) = rules.get(section).and_then(|s| s.as_object()) else { continue };
+            for (key, rule) in map {
+                let rule = rule.as_str().unwrap_or_default();
+                checks.push(self.check_rule(section, key, rule));
+            }
+        }
+
+        for key in rules.get("local_storage").and_then(|l| l.as_array()).into_iter().flatten().filter_map(|k| k.as_str()) {
+            let rule = format!("${{localStorage:{}}}", key);
+            checks.push(self.check_rule("local_storage", key, &rule));
+        }
+
+        if let Some(cookie_rule) = rules.get("cookie") {
+            let rule = cookie_rule.as_str().map(|r| r.to_string()).unwrap_or_else(|| cookie_rule.to_string());
+            let source = cookie_rule.get("source").and_then(|s| s.as_str());
+            let check = match (cookie_rule.as_str(), source) {
+                (Some(rule), _) => self.check_rule("cookie", "cookie", rule),
+                (None, Some("from_browser")) => self.make_check("cookie", "cookie", &rule, RuleStatus::Matched, &self.browser_cookie, "使用浏览器 Cookie"),
+                (None, Some("from_api")) => match cookie_rule_field(rules, "api_path", "apiPath") {
+                    Some(api_path) => {
+                        let header_name = cookie_rule_field(rules, "header_name", "headerName").unwrap_or("cookie");
+                        self.check_rule("cookie", "cookie", &format!("${{api:{}:request:headers:{}}}", api_path, header_name))
+                    }
+                    None => self.make_check("cookie", "cookie", &rule, RuleStatus::Invalid, "", "from_api 缺少 api_path"),
+                },
+                _ => self.make_check("cookie", "cookie", &rule, RuleStatus::Invalid, "", "source 应为 from_api 或 from_browser"),
+            };
+            checks.push(RuleCheck { rule, ..check });
+        }
+
+        checks
+    }
+
+    /// 检查单条规则
+    fn check_rule(&self, section: &str, key: &str, rule: &str) -> RuleCheck {
+        let check = |status, value: &str, message: &str| self.make_check(section, key, rule, status, value, message);
+
+        if let Err(e) = validate_rule(rule) {
+            return check(RuleStatus::Invalid, "", &e);
+        }
+
+        let value = self.evaluate(rule);
+        if let Some(api_path) = extract_api_path(rule) {
+            if find_capture(&self.captured_api_data, &api_path).is_none() {
+                return check(RuleStatus::NotCaptured, "", &format!("会话中没有请求接口 {}", api_path));
+            }
+        } else if let Some(key) = local_storage_key(rule) {
+            if !self.local_storage.iter().any(|item| item.key == key) {
+                return check(RuleStatus::NotCaptured, "", &format!("localStorage 中没有 {}", key));
+            }
+        } else {
+            return check(RuleStatus::Fixed, &value, "固定值");
+        }
+
+        if value.is_empty() {
+            check(RuleStatus::Empty, "", "已捕获数据，但按路径取到的值为空，请检查字段路径")
+        } else {
+            check(RuleStatus::Matched, &value, "")
+        }
+    }
+
+    fn make_check(&self, section: &str, key: &str, rule: &str, status: RuleStatus, value: &str, message: &str) -> RuleCheck {
+        let value = if value.chars().count() > REPORT_VALUE_MAX_CHARS {
+            format!("{}...", value.chars().take(REPORT_VALUE_MAX_CHARS).collect::<String>())
+        } else {
+            value.to_string()
+        };
+        RuleCheck {
+            section: section.to_string(),
+            key: key.to_string(),
+            rule: rule.to_string(),
+            status,
+            value,
+            message: message.to_string(),
+        }
+    }
+
+    /// 生成配置测试报告
+    pub fn build_test_report(&self, current_url: &str) -> ExtractorTestReport {
+        let rules = self.check_rules();
+        let matched = rules.iter().filter(|c| c.status == RuleStatus::Matched).count();
+        let failed = rules.iter()
+            .filter(|c| matches!(c.status, RuleStatus::Empty | RuleStatus::NotCaptured | RuleStatus::Invalid))
+            .count();
+        ExtractorTestReport {
+            platform_id: self.config.platform_id.clone(),
+            current_url: current_url.to_string(),
+            captured_urls: self.captured_api_data.iter().map(|d| d.url.clone()).collect(),
+            rules,
+            matched,
+            failed,
+        }
+    }
+
     /// 清空捕获的数据
     pub fn clear(&mut self) {
         self.captured_api_data.clear();
-        self.captured_request_headers.clear();
+        self.local_storage.clear();
+        self.browser_cookie.clear();
     }
 }
 
-/// 从规则中提取 API 路径
-fn extract_api_path(rule: &str) -> Option<String> {
-    if rule.starts_with("${api:") {
-        let content = rule.trim_start_matches("${api:").trim_end_matches('}');
-        if let Some(first_colon) = content.find(':') {
-            return Some(content[..first_colon].to_string());
+/// user_info / request_headers 中的全部字符串规则
+fn rule_strings(rules: &Value) -> impl Iterator<Item = &str> {
+    ["user_info", "request_headers"].into_iter()
+        .filter_map(|section| rules.get(section).and_then(|s| s.as_object()))
+        .flat_map(|map| map.values().filter_map(|v| v.as_str()))
+}
+
+/// cookie 规则中的字段（兼容旧配置的驼峰写法）
+fn cookie_rule_field<'a>(rules: &'a Value, name: &str, camel_name: &str) -> Option<&'a str> {
+    let cookie_rule = rules.get("cookie")?;
+    cookie_rule.get(name)
+        .or_else(|| cookie_rule.get(camel_name))
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.is_empty())
+}
+
+/// 最后一次捕获的匹配接口
+fn find_capture<'a>(api_data: &'a [ApiCaptureData], api_path: &str) -> Option<&'a ApiCaptureData> {
+    api_data.iter().rev().find(|data| data.url.contains(api_path))
+}
+
+/// 检查规则格式（`${...}` 规则之外的字符串视为固定值）
+pub fn validate_rule(rule: &str) -> Result<(), String> {
+    if let Some(content) = rule.strip_prefix("${api:") {
+        let content = content.strip_suffix('}').ok_or("规则缺少结尾的 }")?;
+        let parts: Vec<&str> = content.split(':').collect();
+        if parts.len() < 4 || parts[0].is_empty() || parts[3..].iter().any(|p| p.is_empty()) {
+            return Err("格式应为 ${api:<接口路径>:request:headers:<请求头>} 或 ${api:<接口路径>:response:body:<字段路径>}".to_string());
         }
+        return match (parts[1], parts[2]) {
+            ("request", "headers") if parts.len() == 4 => Ok(()),
+            ("request", "headers") => Err("请求头规则只能有一个请求头名".to_string()),
+            ("response", "body") => Ok(()),
+            (request_type, extract_type) => Err(format!("不支持的提取位置 {}:{}", request_type, extract_type)),
+        };
     }
-    None
+    if let Some(content) = rule.strip_prefix("${localStorage:") {
+        return match content.strip_suffix('}') {
+            Some(key) if !key.is_empty() => Ok(()),
+            _ => Err("格式应为 ${localStorage:<key>}".to_string()),
+        };
+    }
+    if rule.starts_with("${") {
+        return Err("未知的规则类型，支持 ${api:...} 和 ${localStorage:...}".to_string());
+    }
+    Ok(())
+}
+
+/// 从规则中提取 API 路径
+fn extract_api_path(rule: &str) -> Option<String> {
+    let content = rule.strip_prefix("${api:")?.strip_suffix('}')?;
+    content.find(':').map(|i| content[..i].to_string())
+}
+
+/// 从规则中提取 localStorage key
+fn local_storage_key(rule: &str) -> Option<&str> {
+    rule.strip_prefix("${localStorage:")?.strip_suffix('}')
 }
 
 /// 评估规则并提取值
-fn evaluate_rule(
-    rule: &str,
-    api_data: &HashMap<String, ApiCaptureData>,
-    request_headers: &HashMap<String, HashMap<String, String>>,
-) -> String {
-    if rule.starts_with("${api:") {
-        let content = rule.trim_start_matches("${api:").trim_end_matches('}');
+fn evaluate_rule(rule: &str, api_data: &[ApiCaptureData], local_storage: &[LocalStorageItem]) -> String {
+    if let Some(content) = rule.strip_prefix("${api:").and_then(|r| r.strip_suffix('}')) {
         let parts: Vec<&str> = content.split(':').collect();
-
         if parts.len() < 3 {
             return String::new();
         }
@@ -321,33 +495,25 @@ fn evaluate_rule(
         let api_path = parts[0];
         let request_type = parts[1];
         let extract_type = parts[2];
-        let field_path = if parts.len() > 3 {
-            parts[3..].join(":")
-        } else {
-            String::new()
-        };
+        let field_path = parts[3..].join(":");
 
-        // 查找匹配的 API 数据
-        for (url, data) in api_data {
-            if url.contains(api_path) {
-                if request_type == "request" && extract_type == "headers" {
-                    return data.request_headers.get(&field_path).cloned().unwrap_or_default();
-                } else if request_type == "response" && extract_type == "body" {
-                    if let Some(body) = &data.response_body {
-                        return extract_json_path(body, &field_path);
-                    }
-                }
-            }
-        }
+        let Some(data) = find_capture(api_data, api_path) else {
+            return String::new();
+        };
+        return match (request_type, extract_type) {
+            ("request", "headers") => data.request_headers.get(&field_path.to_lowercase()).cloned().unwrap_or_default(),
+            ("response", "body") => data.response_body.as_ref()
+                .map(|body| extract_json_path(body, &field_path))
+                .unwrap_or_default(),
+            _ => String::new(),
+        };
+    }
 
-        // 从 request_headers 直接查找
-        for headers in request_headers.values() {
-            if headers.get("url").map(|u| u.contains(api_path)).unwrap_or(false) {
-                return headers.get(&field_path).cloned().unwrap_or_default();
-            }
-        }
-    } else if rule.starts_with("${localStorage:") {
-        return format!("${{localStorage:{}}}", rule.trim_start_matches("${localStorage:").trim_end_matches('}'));
+    if let Some(key) = local_storage_key(rule) {
+        return local_storage.iter()
+            .find(|item| item.key == key)
+            .map(|item| item.value.clone())
+            .unwrap_or_default();
     }
 
     // 固定值
@@ -356,39 +522,134 @@ fn evaluate_rule(
 
 /// 从 JSON 中按路径提取值
 fn extract_json_path(json: &Value, path: &str) -> String {
-    let parts: Vec<&str> = path.split(':').collect();
-    let mut current = json.clone();
-
-    for part in parts {
-        match current {
-            Value::Object(obj) => {
-                if let Some(value) = obj.get(part) {
-                    current = value.clone();
-                } else {
-                    return String::new();
-                }
-            }
-            Value::Array(arr) => {
-                if let Ok(index) = part.parse::<usize>() {
-                    if index < arr.len() {
-                        current = arr[index].clone();
-                    } else {
-                        return String::new();
-                    }
-                } else {
-                    return String::new();
-                }
+    let mut current = json;
+
+    if !path.is_empty() {
+        for part in path.split(':') {
+            let next = match current {
+                Value::Object(obj) => obj.get(part),
+                Value::Array(arr) => part.parse::<usize>().ok().and_then(|i| arr.get(i)),
+                _ => None,
+            };
+            match next {
+                Some(value) => current = value,
+                None => return String::new(),
             }
-            _ => return String::new(),
         }
     }
 
     match current {
-        Value::String(s) => s,
+        Value::String(s) => s.clone(),
         Value::Number(n) => n.to_string(),
         Value::Bool(b) => b.to_string(),
-        Value::Array(arr) => serde_json::to_string(&arr).unwrap_or_default(),
-        Value::Object(obj) => serde_json::to_string(&obj).unwrap_or_default(),
-        _ => String::new(),
+        Value::Array(_) | Value::Object(_) => current.to_string(),
+        Value::Null => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine(extract_rules: Value) -> DataExtractorEngine {
+        DataExtractorEngine::new(ExtractorConfig {
+            platform_id: "test".to_string(),
+            platform_name: "测试".to_string(),
+            login_url: String::new(),
+            login_success_mode: "url_match".to_string(),
+            login_success_pattern: String::new(),
+            login_success_api_rule: None,
+            login_success_api_operator: None,
+            login_success_api_value: None,
+            redirect_url: None,
+            extract_rules,
+        })
+    }
+
+    #[test]
+    fn test_rules_evaluated_against_captures() {
+        let mut engine = engine(serde_json::json!({
+            "user_info": {
+                "nickname": "${api:/user/info:response:body:user:nickname}",
+                "avatar_url": "${api:/user/info:response:body:user:avatars:0}",
+                "third_id": "${api:/user/info:response:body:user:uid}",
+                "sec_uid": "${localStorage:sec_uid}"
+            },
+            "request_headers": {
+                "cookie": "${api:/user/info:request:headers:Cookie}",
+                "x-fixed": "1"
+            },
+            "local_storage": ["token"],
+            "cookie": { "source": "from_api", "api_path": "/user/info", "header_name": "cookie" }
+        }));
+        assert_eq!(engine.api_paths(), vec!["/user/info".to_string()]);
+        assert_eq!(engine.local_storage_keys(), vec!["token".to_string(), "sec_uid".to_string()]);
+
+        engine.capture(ApiCaptureData {
+            url: "https://example.com/user/info?a=1".to_string(),
+            request_headers: HashMap::from([("Cookie".to_string(), "sid=1".to_string())]),
+            response_body: Some(serde_json::json!({
+                "user": { "nickname": "小明", "avatars": ["https://a/1.png"], "uid": 42 }
+            })),
+        });
+        engine.set_local_storage(vec![
+            LocalStorageItem { key: "token".to_string(), value: "t".to_string() },
+            LocalStorageItem { key: "sec_uid".to_string(), value: "MS4".to_string() },
+        ]);
+        engine.set_browser_cookie("browser=1");
+
+        let result = engine.build_auth_result("https://example.com/");
+        assert_eq!(result.step, BrowserAuthStep::Completed);
+        assert_eq!(result.nickname, "小明");
+        assert_eq!(result.avatar_url, "https://a/1.png");
+        assert_eq!(result.third_id, "42");
+        assert_eq!(result.sec_uid, "MS4");
+        assert_eq!(result.cookie, "sid=1");
+
+        let headers: HashMap<String, String> = serde_json::from_str(&result.request_headers).unwrap();
+        assert_eq!(headers["cookie"], "sid=1");
+        assert_eq!(headers["x-fixed"], "1");
+        assert!(result.local_storage.contains("\"token\""));
+    }
+
+    #[test]
+    fn test_check_rules_reports_typos() {
+        let mut engine = engine(serde_json::json!({
+            "user_info": {
+                "nickname": "${api:/user/info:response:body:user:nick_name}",
+                "third_id": "${api:/user/info:response:body:user:uid}",
+                "avatar_url": "${api:/other:response:body:avatar}",
+                "sec_uid": "${api:/user/info:response}"
+            },
+            "cookie": { "source": "from_api", "api_path": "/user/info", "header_name": "cookie" }
+        }));
+        engine.capture(ApiCaptureData {
+            url: "https://example.com/user/info".to_string(),
+            request_headers: HashMap::from([("cookie".to_string(), "sid=1".to_string())]),
+            response_body: Some(serde_json::json!({ "user": { "nickname": "小明", "uid": "42" } })),
+        });
+
+        let report = engine.build_test_report("https://example.com/");
+        let status = |key: &str| report.rules.iter().find(|c| c.key == key).map(|c| c.status);
+        assert_eq!(status("nickname"), Some(RuleStatus::Empty));
+        assert_eq!(status("third_id"), Some(RuleStatus::Matched));
+        assert_eq!(status("avatar_url"), Some(RuleStatus::NotCaptured));
+        assert_eq!(status("sec_uid"), Some(RuleStatus::Invalid));
+        assert_eq!(status("cookie"), Some(RuleStatus::Matched));
+        assert_eq!((report.matched, report.failed), (2, 3));
+    }
+
+    #[test]
+    fn test_cookie_falls_back_to_browser_cookie() {
+        let mut engine = engine(serde_json::json!({
+            "user_info": { "nickname": "${api:/missing:response:body:nickname}" },
+            "cookie": { "source": "from_api", "apiPath": "/missing", "headerName": "cookie" }
+        }));
+        engine.set_browser_cookie("browser=1");
+
+        let result = engine.extract();
+        assert!(result.success);
+        assert!(result.user_info.is_empty());
+        assert_eq!(result.cookie, "browser=1");
     }
 }
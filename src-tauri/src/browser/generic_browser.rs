@@ -1,16 +1,93 @@
 // Generic Browser Implementation - 通用规则引擎浏览器
 // 使用配置规则从数据库中提取任意平台的用户信息
 
-use crate::browser::{BrowserAuthResult, BrowserAuthStep};
+use crate::browser::data_extractor_engine::{ApiCaptureData, ExtractorTestReport, LocalStorageItem};
+use crate::browser::{BrowserAuthResult, BrowserAuthStep, BrowserFingerprint, DataExtractorEngine};
 use crate::storage::DatabaseManager;
+use serde::Deserialize;
 use std::io::BufRead;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+
+/// 脚本输出（RESULT_JSON_START / RESULT_JSON_END 之间的 JSON）
+#[derive(Debug, Deserialize)]
+struct ScriptOutput {
+    step: String,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    url: String,
+    /// 浏览器上下文中的 Cookie
+    #[serde(default)]
+    cookie: String,
+    #[serde(default)]
+    local_storage: Vec<LocalStorageItem>,
+    /// 规则引用的接口的请求头和响应体
+    #[serde(default)]
+    captures: Vec<ApiCaptureData>,
+}
+
+/// Handle to the browser process of one session
+/// 浏览器进程句柄：取消授权时结束 Node.js 脚本进程，Playwright 启动的浏览器随之关闭
+#[derive(Debug, Clone, Default)]
+pub struct BrowserProcessHandle(Arc<Mutex<BrowserProcessSlot>>);
+
+#[derive(Debug, Default)]
+struct BrowserProcessSlot {
+    child: Option<Child>,
+    killed: bool,
+}
+
+impl BrowserProcessHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BrowserProcessSlot> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 结束浏览器进程并等待其退出；进程尚未启动时，之后也不会再启动
+    pub fn kill(&self) {
+        let mut slot = self.lock();
+        slot.killed = true;
+        if let Some(mut child) = slot.child.take() {
+            if let Err(e) = child.kill() {
+                tracing::warn!("[GenericBrowser] 结束浏览器进程失败: pid={}, error={}", child.id(), e);
+            }
+            let _ = child.wait();
+        }
+    }
+
+    /// 登记已启动的进程；已被取消时立即结束该进程
+    fn register(&self, mut child: Child) -> Result<(), String> {
+        let mut slot = self.lock();
+        if slot.killed {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("授权已取消".to_string());
+        }
+        slot.child = Some(child);
+        Ok(())
+    }
+
+    /// 取回进程等待其退出；已被 kill 时返回 None
+    fn take(&self) -> Option<Child> {
+        self.lock().child.take()
+    }
+}
 
 /// 通用浏览器实现（使用规则引擎）
 pub struct GenericBrowser {
     result: BrowserAuthResult,
     db_manager: Option<Arc<DatabaseManager>>,
+    /// 无头模式（已登录会话的凭证刷新；首次扫码登录需要有界面）
+    headless: bool,
+    /// 账号的浏览器资料目录（持久化登录会话）
+    profile_dir: Option<PathBuf>,
+    /// 脚本进程句柄（用于取消时关闭浏览器）
+    process: BrowserProcessHandle,
 }
 
 impl GenericBrowser {
@@ -18,6 +95,9 @@ impl GenericBrowser {
         Self {
             result: BrowserAuthResult::default(),
             db_manager: None,
+            headless: false,
+            profile_dir: None,
+            process: BrowserProcessHandle::new(),
         }
     }
 
@@ -26,6 +106,21 @@ impl GenericBrowser {
         self.db_manager = Some(db_manager);
     }
 
+    /// 设置是否以无头模式启动浏览器
+    pub fn set_headless(&mut self, headless: bool) {
+        self.headless = headless;
+    }
+
+    /// 设置浏览器资料目录，为空时每次使用全新的浏览器上下文
+    pub fn set_profile_dir(&mut self, profile_dir: Option<PathBuf>) {
+        self.profile_dir = profile_dir;
+    }
+
+    /// 设置脚本进程句柄，外部可通过它结束浏览器
+    pub fn set_process_handle(&mut self, process: BrowserProcessHandle) {
+        self.process = process;
+    }
+
     /// 获取 Playwright 目录
     fn get_playwright_dir() -> PathBuf {
         let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
@@ -42,62 +137,67 @@ impl GenericBrowser {
     }
 
     /// 启动通用授权流程
-    pub async fn start_authorize(&mut self, platform_id: &str) -> Result<BrowserAuthResult, String> {
+    pub async fn start_authorize(&mut self, platform_id: &str, fingerprint: &BrowserFingerprint) -> Result<BrowserAuthResult, String> {
         self.result.step = BrowserAuthStep::LaunchingBrowser;
         self.result.message = format!("正在启动浏览器 for {}...", platform_id);
 
         tracing::info!("[GenericBrowser] start_authorize: 开始执行");
 
-        // 在阻塞线程中运行 Playwright 脚本
+        let outcome = self.run_session(platform_id, fingerprint).await.and_then(|(engine, current_url)| {
+            let result = engine.build_auth_result(&current_url);
+            if result.step == BrowserAuthStep::Completed {
+                Ok(result)
+            } else {
+                Err(result.message)
+            }
+        });
+
+        match outcome {
+            Ok(auth_result) => {
+                tracing::info!("[GenericBrowser] 脚本执行成功");
+                self.result = auth_result;
+                Ok(self.result.clone())
+            }
+            Err(e) => {
+                tracing::info!("[GenericBrowser] {}", e);
+                self.result.step = BrowserAuthStep::Failed(e.clone());
+                self.result.message = e.clone();
+                self.result.error = Some(e.clone());
+                Err(e)
+            }
+        }
+    }
+
+    /// Run a sandbox login session and report how each extract rule performed
+    /// 测试提取配置：打开登录页，登录后按规则提取，返回每条规则的结果，不保存账号
+    pub async fn test_config(&mut self, platform_id: &str, fingerprint: &BrowserFingerprint) -> Result<ExtractorTestReport, String> {
+        tracing::info!("[GenericBrowser] test_config: {}", platform_id);
+        let (engine, current_url) = self.run_session(platform_id, fingerprint).await?;
+        Ok(engine.build_test_report(&current_url))
+    }
+
+    /// 在阻塞线程中运行 Playwright 脚本（60 秒超时），返回装入捕获数据的提取引擎和最终页面地址
+    async fn run_session(&self, platform_id: &str, fingerprint: &BrowserFingerprint) -> Result<(DataExtractorEngine, String), String> {
         let db_manager = self.db_manager.clone();
         let platform_id = platform_id.to_string();
+        let fingerprint_json = serde_json::to_string(fingerprint).unwrap_or_default();
+        let headless = self.headless;
+        let profile_dir = self.profile_dir.clone();
+        let process = self.process.clone();
 
-        // 使用超时
         let timeout_result = tokio::time::timeout(
             std::time::Duration::from_secs(60),
             tokio::task::spawn_blocking(move || {
-                Self::run_script(db_manager, &platform_id)
+                Self::run_script(db_manager, &platform_id, &fingerprint_json, headless, profile_dir.as_deref(), &process)
             })
         ).await;
 
-        // 处理超时
-        let join_handle = match timeout_result {
-            Ok(handle) => handle,
+        match timeout_result {
+            Ok(Ok(script_result)) => script_result,
+            Ok(Err(e)) => Err(format!("任务执行失败: {}", e)),
             Err(_) => {
-                let err_msg = "脚本执行超时 (60秒)".to_string();
-                tracing::info!("[GenericBrowser] {}", err_msg);
-                self.result.step = BrowserAuthStep::Failed(err_msg.clone());
-                self.result.message = err_msg.clone();
-                self.result.error = Some(err_msg.clone());
-                return Err(err_msg);
-            }
-        };
-
-        // 处理 join 结果
-        match join_handle {
-            Ok(script_result) => {
-                match script_result {
-                    Ok(auth_result) => {
-                        tracing::info!("[GenericBrowser] 脚本执行成功");
-                        self.result = auth_result;
-                        Ok(self.result.clone())
-                    }
-                    Err(e) => {
-                        tracing::info!("[GenericBrowser] 脚本执行错误: {}", e);
-                        self.result.step = BrowserAuthStep::Failed(e.clone());
-                        self.result.message = e.clone();
-                        self.result.error = Some(e.clone());
-                        Err(e)
-                    }
-                }
-            }
-            Err(e) => {
-                let err_msg = format!("任务执行失败: {}", e);
-                tracing::info!("[GenericBrowser] {}", err_msg);
-                self.result.step = BrowserAuthStep::Failed(err_msg.clone());
-                self.result.message = err_msg.clone();
-                self.result.error = Some(err_msg.clone());
-                Err(err_msg)
+                self.process.kill();
+                Err("脚本执行超时 (60秒)".to_string())
             }
         }
     }
@@ -117,46 +217,49 @@ impl GenericBrowser {
         }
     }
 
-    /// 从数据库加载配置
-    fn load_config(db_manager: &Arc<DatabaseManager>, platform_id: &str) -> Result<String, String> {
+    /// 从数据库加载配置，生成传给脚本的配置 JSON（附带需要捕获的接口路径和 localStorage key）
+    fn load_config(db_manager: &Arc<DatabaseManager>, platform_id: &str) -> Result<(DataExtractorEngine, String), String> {
         tracing::info!("[GenericBrowser] 正在查询平台配置: {}", platform_id);
 
-        match db_manager.get_extractor_config(platform_id) {
-            Ok(Some(config)) => {
-                tracing::info!("[GenericBrowser] 找到配置: platform_name={}, login_url={}",
-                    config.platform_name, config.login_url);
-
-                let config_obj = serde_json::json!({
-                    "platform_id": config.platform_id,
-                    "platform_name": config.platform_name,
-                    "login_url": config.login_url,
-                    "login_success_mode": config.login_success_mode,
-                    "login_success_pattern": config.login_success_pattern,
-                    "login_success_api_rule": config.login_success_api_rule,
-                    "login_success_api_operator": config.login_success_api_operator,
-                    "login_success_api_value": config.login_success_api_value,
-                    "redirect_url": config.redirect_url,
-                    "extract_rules": config.extract_rules
-                });
-                Ok(config_obj.to_string())
-            }
-            Ok(None) => {
-                tracing::info!("[GenericBrowser] 未找到平台配置: {}", platform_id);
-                Err(format!("未找到平台配置: {}", platform_id))
-            }
-            Err(e) => {
-                tracing::info!("[GenericBrowser] 读取配置失败: {}", e);
-                Err(format!("读取配置失败: {}", e))
-            }
-        }
+        let engine = DataExtractorEngine::load(db_manager, platform_id).map_err(|e| {
+            tracing::info!("[GenericBrowser] 读取配置失败: {}", e);
+            e
+        })?;
+        let config = engine.get_config();
+        tracing::info!("[GenericBrowser] 找到配置: platform_name={}, login_url={}",
+            config.platform_name, config.login_url);
+
+        let config_obj = serde_json::json!({
+            "platform_id": config.platform_id,
+            "platform_name": config.platform_name,
+            "login_url": config.login_url,
+            "login_success_mode": config.login_success_mode,
+            "login_success_pattern": config.login_success_pattern,
+            "login_success_api_rule": config.login_success_api_rule,
+            "login_success_api_operator": config.login_success_api_operator,
+            "login_success_api_value": config.login_success_api_value,
+            "redirect_url": config.redirect_url,
+            "extract_rules": config.extract_rules,
+            "capture_api_paths": engine.api_paths(),
+            "local_storage_keys": engine.local_storage_keys(),
+        });
+        Ok((engine, config_obj.to_string()))
     }
 
-    /// 在阻塞线程中运行 Playwright 脚本
-    fn run_script(db_manager: Option<Arc<DatabaseManager>>, platform_id: &str) -> Result<BrowserAuthResult, String> {
+    /// 在阻塞线程中运行 Playwright 脚本，指纹通过 AMM_FINGERPRINT、无头模式通过 AMM_HEADLESS、
+    /// 资料目录通过 AMM_USER_DATA_DIR 环境变量传入
+    fn run_script(
+        db_manager: Option<Arc<DatabaseManager>>,
+        platform_id: &str,
+        fingerprint_json: &str,
+        headless: bool,
+        profile_dir: Option<&std::path::Path>,
+        process: &BrowserProcessHandle,
+    ) -> Result<(DataExtractorEngine, String), String> {
         tracing::info!("[GenericBrowser] run_script called for platform: {}", platform_id);
 
         // 从数据库加载配置
-        let config_json = if let Some(ref db) = db_manager {
+        let (mut engine, config_json) = if let Some(ref db) = db_manager {
             Self::load_config(db, platform_id)?
         } else {
             return Err("数据库管理器未设置".to_string());
@@ -174,12 +277,40 @@ impl GenericBrowser {
             return Err(format!("浏览器目录不存在: {}", browsers_dir.display()));
         }
 
-        // 写入脚本到 Playwright 目录
-        let script_path = playwright_dir.join("generic_extractor.js");
+        let profile_dir = match profile_dir {
+            Some(dir) => {
+                std::fs::create_dir_all(dir).map_err(|e| format!("创建浏览器资料目录失败: {}", e))?;
+                dir.to_string_lossy().into_owned()
+            }
+            None => String::new(),
+        };
+
+        // 写入脚本到 Playwright 目录（每次授权一个文件，多个授权会话并行时互不覆盖）
+        let script_path = playwright_dir.join(format!("generic_extractor_{}.js", uuid::Uuid::new_v4().simple()));
         if let Err(e) = std::fs::write(&script_path, &script) {
             return Err(format!("无法写入临时脚本: {}", e));
         }
+        let env = [
+            ("AMM_CONFIG", config_json.as_str()),
+            ("AMM_FINGERPRINT", fingerprint_json),
+            ("AMM_HEADLESS", if headless { "1" } else { "0" }),
+            ("AMM_USER_DATA_DIR", profile_dir.as_str()),
+        ];
+        let result = Self::run_node(&script_path, &playwright_dir, &browsers_dir, platform_id, &env, process);
+        std::fs::remove_file(&script_path).ok();
+        let current_url = Self::parse_result(&result?, &mut engine)?;
+        Ok((engine, current_url))
+    }
 
+    /// 运行 Node.js 脚本，返回 stdout 中 RESULT_JSON_START / RESULT_JSON_END 之间的结果
+    fn run_node(
+        script_path: &std::path::Path,
+        playwright_dir: &std::path::Path,
+        browsers_dir: &std::path::Path,
+        platform_id: &str,
+        env: &[(&str, &str)],
+        process: &BrowserProcessHandle,
+    ) -> Result<String, String> {
         // 执行脚本，通过环境变量传递配置
         tracing::info!("[GenericBrowser] 启动 Node.js 脚本...");
 
@@ -187,8 +318,8 @@ impl GenericBrowser {
             .arg(&script_path)
             .arg(platform_id)
             .env("PLAYWRIGHT_BROWSERS_PATH", browsers_dir.to_string_lossy().as_ref())
-            .env("AMM_CONFIG", &config_json)
-            .current_dir(&playwright_dir)
+            .envs(env.iter().copied())
+            .current_dir(playwright_dir)
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::inherit())  // stderr 直接输出，实时打印
             .spawn()
@@ -197,6 +328,7 @@ impl GenericBrowser {
         tracing::info!("[GenericBrowser] Node.js 进程已启动, pid: {}", child.id());
 
         let stdout = child.stdout.take().unwrap();
+        process.register(child)?;
 
         // 读取 stdout，提取结果
         let reader = std::io::BufReader::new(stdout);
@@ -232,7 +364,10 @@ impl GenericBrowser {
         }
 
         tracing::info!("[GenericBrowser] 等待进程结束...");
-        // 等待进程结束
+        // 等待进程结束（已被取消时进程已结束并回收）
+        let Some(mut child) = process.take() else {
+            return Err("授权已取消".to_string());
+        };
         let status = child.wait()
             .map_err(|e| format!("等待脚本结束失败: {}", e))?;
 
@@ -250,85 +385,31 @@ impl GenericBrowser {
             return Err("未获取到结果".to_string());
         }
 
-        Self::parse_result(&result_json)
+        Ok(result_json)
     }
 
-    /// 解析认证结果 - 直接解析 JS 返回的新格式（保持配置结构，只替换规则）
-    fn parse_result(content: &str) -> Result<BrowserAuthResult, String> {
-        match serde_json::from_str::<serde_json::Value>(content) {
-            Ok(json) => {
-                let mut result = BrowserAuthResult::default();
-
-                if let Some(step) = json.get("step").and_then(|s| s.as_str()) {
-                    result.step = match step {
-                        "completed" => BrowserAuthStep::Completed,
-                        "failed" => BrowserAuthStep::Failed(
-                            json.get("message").and_then(|m| m.as_str()).unwrap_or("未知错误").to_string()
-                        ),
-                        _ => BrowserAuthStep::Failed("未知状态".to_string()),
-                    };
-                }
+    /// 解析脚本结果：脚本只返回捕获的原始数据，装入提取引擎后由引擎按配置规则解释；返回最终页面地址
+    fn parse_result(content: &str, engine: &mut DataExtractorEngine) -> Result<String, String> {
+        let output: ScriptOutput = serde_json::from_str(content)
+            .map_err(|e| format!("解析结果失败: {}", e))?;
 
-                result.message = json.get("message")
-                    .and_then(|m| m.as_str())
-                    .unwrap_or("完成")
-                    .to_string();
-
-                result.current_url = json.get("url")
-                    .and_then(|u| u.as_str())
-                    .unwrap_or("")
-                    .to_string();
-
-                // 从 user_info 对象中提取4个字段
-                if let Some(user_info) = json.get("user_info").and_then(|u| u.as_object()) {
-                    result.nickname = user_info.get("nickname")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    result.avatar_url = user_info.get("avatar_url")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    result.third_id = user_info.get("third_id")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    result.sec_uid = user_info.get("sec_uid")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                }
-
-                // cookie - 直接从顶层获取
-                result.cookie = json.get("cookie")
-                    .and_then(|c| c.as_str())
-                    .unwrap_or("")
-                    .to_string();
-
-                // local_storage - 转为字符串存储
-                result.local_storage = json.get("local_storage")
-                    .map(|l: &serde_json::Value| l.to_string())
-                    .unwrap_or_else(|| "[]".to_string());
-
-                // request_headers - 转为字符串存储
-                result.request_headers = json.get("request_headers")
-                    .map(|r| r.to_string())
-                    .unwrap_or_else(|| "{}".to_string());
-
-                result.need_poll = false;
+        if output.step != "completed" {
+            return Err(output.message.unwrap_or_else(|| "未知错误".to_string()));
+        }
 
-                if result.step == BrowserAuthStep::Completed {
-                    Ok(result)
-                } else {
-                    Err(result.message.clone())
-                }
-            }
-            Err(e) => Err(format!("解析结果失败: {}", e))
+        tracing::info!("[GenericBrowser] 捕获接口 {} 个, localStorage {} 项",
+            output.captures.len(), output.local_storage.len());
+        for capture in output.captures {
+            engine.capture(capture);
         }
+        engine.set_local_storage(output.local_storage);
+        engine.set_browser_cookie(&output.cookie);
+        Ok(output.url)
     }
 
-    /// 取消授权
+    /// 取消授权（结束正在运行的浏览器）
     pub async fn cancel(&mut self) {
+        self.process.kill();
         self.result.step = BrowserAuthStep::Idle;
         self.result.message = "已取消授权".to_string();
         self.result.need_poll = false;
@@ -1,15 +1,23 @@
 // Browser Automation Module
 // 浏览器自动化模块 - 使用通用规则引擎
 
+pub mod auth_sessions;
 pub mod data_extractor_engine;
 pub mod generic_browser;
 pub mod playwright_env;
+pub mod cookie_import;
+pub mod platforms;
+pub mod profiles;
 
-pub use generic_browser::GenericBrowser;
+pub use auth_sessions::{BrowserAuthSession, BrowserAuthSessions, MAX_BROWSER_AUTH_SESSIONS};
+pub use generic_browser::{BrowserProcessHandle, GenericBrowser};
 pub use playwright_env::{check_playwright_env, ensure_playwright_env};
-pub use data_extractor_engine::DataExtractorEngine;
+pub use data_extractor_engine::{DataExtractorEngine, ExtractorTestReport, RuleCheck, RuleStatus};
+pub use cookie_import::{BrowserKind, BrowserProfile};
+pub use profiles::{AccountBrowserProfile, BrowserProfileStore, ProfileCleanupResult};
 
 use std::fmt;
+use std::path::PathBuf;
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use crate::storage::DatabaseManager;
@@ -90,12 +98,57 @@ impl Default for BrowserAuthResult {
     }
 }
 
+/// Browser viewport size
+/// 浏览器窗口大小
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Viewport {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Browser fingerprint of an account
+/// 账号的浏览器指纹：授权会话使用的 User-Agent 和窗口大小，随账号凭证保存，
+/// 之后的接口请求沿用同一个 User-Agent
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BrowserFingerprint {
+    pub user_agent: Option<String>,
+    pub viewport: Option<Viewport>,
+}
+
+impl BrowserFingerprint {
+    /// 从账号 params JSON 的 third_param 中读取已保存的指纹
+    pub fn from_account_params(params: &str) -> Self {
+        let value: serde_json::Value = serde_json::from_str(params).unwrap_or_default();
+        let third_param = &value["third_param"];
+        Self {
+            user_agent: third_param["user-agent"].as_str()
+                .filter(|ua| !ua.is_empty())
+                .map(|ua| ua.to_string()),
+            viewport: serde_json::from_value(third_param["viewport"].clone()).ok(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.user_agent.is_none() && self.viewport.is_none()
+    }
+}
+
 /// 浏览器自动化器（使用通用规则引擎）
 pub struct BrowserAutomator {
     browser: Option<GenericBrowser>,
     result: BrowserAuthResult,
     /// 重新授权时需要更新的账号ID
     pub account_id: Option<String>,
+    /// 正在授权的平台ID
+    pub platform_id: String,
+    /// 本次授权使用的浏览器指纹
+    pub fingerprint: BrowserFingerprint,
+    /// 是否以无头模式授权（只适用于已登录会话的凭证刷新）
+    pub headless: bool,
+    /// 浏览器资料目录（为空时使用一次性的浏览器上下文）
+    pub profile_dir: Option<PathBuf>,
+    /// 浏览器进程句柄（授权会话取消时用它关闭浏览器）
+    pub process: BrowserProcessHandle,
 }
 
 impl BrowserAutomator {
@@ -104,18 +157,43 @@ impl BrowserAutomator {
             browser: None,
             result: BrowserAuthResult::default(),
             account_id: None,
+            platform_id: String::new(),
+            fingerprint: BrowserFingerprint::default(),
+            headless: false,
+            profile_dir: None,
+            process: BrowserProcessHandle::new(),
         }
     }
 
     /// 启动通用授权流程
-    /// 如果传入了 account_id，则在授权完成后会更新该账号而不是创建新账号
-    pub async fn start_authorize(&mut self, db_manager: &Arc<DatabaseManager>, platform_id: &str, account_id: Option<&str>) -> Result<(), String> {
+    /// 如果传入了 account_id，则在授权完成后会更新该账号而不是创建新账号；
+    /// 未指定指纹时沿用该账号上次保存的指纹
+    pub async fn start_authorize(
+        &mut self,
+        db_manager: &Arc<DatabaseManager>,
+        platform_id: &str,
+        account_id: Option<&str>,
+        fingerprint: BrowserFingerprint,
+    ) -> Result<(), String> {
         // 保存需要更新的账号ID
         self.account_id = account_id.map(|s| s.to_string());
+        self.platform_id = platform_id.to_string();
+
+        let stored = account_id
+            .and_then(|id| db_manager.get_account(id).ok().flatten())
+            .map(|account| BrowserFingerprint::from_account_params(&account.params))
+            .unwrap_or_default();
+        self.fingerprint = BrowserFingerprint {
+            user_agent: fingerprint.user_agent.or(stored.user_agent),
+            viewport: fingerprint.viewport.or(stored.viewport),
+        };
 
         let mut browser = GenericBrowser::new();
         browser.set_db_manager(db_manager.clone());
-        let result = browser.start_authorize(platform_id).await?;
+        browser.set_headless(self.headless);
+        browser.set_profile_dir(self.profile_dir.clone());
+        browser.set_process_handle(self.process.clone());
+        let result = browser.start_authorize(platform_id, &self.fingerprint).await?;
         self.browser = Some(browser);
         self.result = result;
         Ok(())
@@ -0,0 +1,67 @@
+// Douyin Browser - 抖音浏览器授权
+//
+// 登录 creator.douyin.com（创作者中心）后跳转到发布页，
+// 从创作者用户信息接口提取昵称/头像/sec_uid，从账号信息接口提取 uid 和 Cookie，
+// 并保存发布接口签名所需的 security-sdk localStorage
+
+use super::{DefaultExtractorConfig, PlatformBrowser};
+
+/// 创作者用户信息接口
+const MEDIA_USER_INFO_API: &str = "/web/api/media/user/info";
+
+/// 账号信息接口
+const ACCOUNT_INFO_API: &str = "/account/api/v1/user/account/info";
+
+/// 抖音浏览器授权
+pub struct DouyinBrowser;
+
+impl PlatformBrowser for DouyinBrowser {
+    fn default_config() -> DefaultExtractorConfig {
+        DefaultExtractorConfig {
+            platform_id: "douyin",
+            platform_name: "抖音",
+            login_url: "https://creator.douyin.com/",
+            login_success_mode: "url_match",
+            login_success_pattern: "**/creator-micro/**",
+            redirect_url: Some("https://creator.douyin.com/creator-micro/content/post"),
+            extract_rules: serde_json::json!({
+                "user_info": {
+                    "nickname": format!("${{api:{}:response:body:user:nickname}}", MEDIA_USER_INFO_API),
+                    "avatar_url": format!("${{api:{}:response:body:user:avatar_thumb:url_list:0}}", MEDIA_USER_INFO_API),
+                    "third_id": format!("${{api:{}:response:body:user:uid}}", ACCOUNT_INFO_API),
+                    "sec_uid": format!("${{api:{}:response:body:user:sec_uid}}", MEDIA_USER_INFO_API)
+                },
+                "request_headers": {
+                    "cookie": format!("${{api:{}:request:headers:cookie}}", ACCOUNT_INFO_API)
+                },
+                "local_storage": [
+                    "security-sdk/s_sdk_cert_key",
+                    "security-sdk/s_sdk_crypt_sdk",
+                    "security-sdk/s_sdk_pri_key",
+                    "security-sdk/s_sdk_pub_key"
+                ],
+                "cookie": {
+                    "source": "from_api",
+                    "api_path": ACCOUNT_INFO_API,
+                    "header_name": "cookie"
+                }
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_point_at_creator_apis() {
+        let config = DouyinBrowser::default_config();
+        assert_eq!(config.platform_id, "douyin");
+        assert_eq!(
+            config.extract_rules["user_info"]["avatar_url"],
+            "${api:/web/api/media/user/info:response:body:user:avatar_thumb:url_list:0}"
+        );
+        assert_eq!(config.extract_rules["local_storage"].as_array().map(|l| l.len()), Some(4));
+    }
+}
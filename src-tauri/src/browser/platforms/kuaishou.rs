@@ -0,0 +1,59 @@
+// Kuaishou Browser - 快手浏览器授权
+//
+// 登录 cp.kuaishou.com（创作者服务平台）后跳转到发布页，
+// 从账号信息接口提取用户信息，并从该接口的请求头中取 Cookie
+// （发布接口依赖 Cookie 中的 kuaishou.web.cp.api_ph）
+
+use super::{DefaultExtractorConfig, PlatformBrowser};
+
+/// 账号信息接口
+const ACCOUNT_INFO_API: &str = "/rest/v2/creator/pc/authority/account/current";
+
+/// 快手浏览器授权
+pub struct KuaishouBrowser;
+
+impl PlatformBrowser for KuaishouBrowser {
+    fn default_config() -> DefaultExtractorConfig {
+        DefaultExtractorConfig {
+            platform_id: "kuaishou",
+            platform_name: "快手",
+            login_url: "https://cp.kuaishou.com/profile",
+            login_success_mode: "url_match",
+            login_success_pattern: "**/cp.kuaishou.com/profile**",
+            redirect_url: Some("https://cp.kuaishou.com/article/publish/video"),
+            extract_rules: serde_json::json!({
+                "user_info": {
+                    "nickname": format!("${{api:{}:response:body:data:userName}}", ACCOUNT_INFO_API),
+                    "avatar_url": format!("${{api:{}:response:body:data:userHead}}", ACCOUNT_INFO_API),
+                    "third_id": format!("${{api:{}:response:body:data:userId}}", ACCOUNT_INFO_API),
+                    "sec_uid": ""
+                },
+                "request_headers": {
+                    "cookie": format!("${{api:{}:request:headers:cookie}}", ACCOUNT_INFO_API),
+                    "user-agent": format!("${{api:{}:request:headers:user-agent}}", ACCOUNT_INFO_API)
+                },
+                "local_storage": [],
+                "cookie": {
+                    "source": "from_api",
+                    "api_path": ACCOUNT_INFO_API,
+                    "header_name": "cookie"
+                }
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_point_at_account_api() {
+        let config = KuaishouBrowser::default_config();
+        assert_eq!(config.platform_id, "kuaishou");
+        assert_eq!(
+            config.extract_rules["user_info"]["third_id"],
+            "${api:/rest/v2/creator/pc/authority/account/current:response:body:data:userId}"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+// Platform browsers - 各平台浏览器授权配置
+//
+// 授权流程统一由 GenericBrowser 执行（脚本捕获数据，DataExtractorEngine 解释规则），
+// 各平台只需提供默认提取配置：
+// 登录页、登录成功判定、用户信息/Cookie 提取规则。默认配置在建库时写入
+// extractor_configs 表（INSERT OR IGNORE，不覆盖用户修改过的配置）
+
+pub mod douyin;
+pub mod kuaishou;
+
+pub use douyin::DouyinBrowser;
+pub use kuaishou::KuaishouBrowser;
+
+/// 平台默认提取配置
+#[derive(Debug, Clone)]
+pub struct DefaultExtractorConfig {
+    pub platform_id: &'static str,
+    pub platform_name: &'static str,
+    pub login_url: &'static str,
+    /// "url_match" 或 "api_match"
+    pub login_success_mode: &'static str,
+    pub login_success_pattern: &'static str,
+    pub redirect_url: Option<&'static str>,
+    pub extract_rules: serde_json::Value,
+}
+
+/// 平台浏览器授权
+pub trait PlatformBrowser {
+    /// 平台默认提取配置
+    fn default_config() -> DefaultExtractorConfig;
+}
+
+/// 所有平台浏览器的默认提取配置
+pub fn default_extractor_configs() -> Vec<DefaultExtractorConfig> {
+    vec![DouyinBrowser::default_config(), KuaishouBrowser::default_config()]
+}
@@ -0,0 +1,200 @@
+// Browser profiles - per-account persistent browser user-data directories
+// 账号浏览器资料目录 - 每个账号一个独立的浏览器 user-data 目录，放在 <app_data>/browser_profiles 下：
+// - 授权时使用持久化上下文，登录会话保存在目录中，重新授权/凭证刷新无需再次扫码
+// - 新账号授权时先使用 pending_<会话ID> 临时目录，账号保存后改名为账号ID
+// - 账号删除后目录由清理命令回收；授权失败留下的临时目录超过一段时间后一并清理
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// 资料目录根目录名（位于应用数据目录下）
+const PROFILES_DIR: &str = "browser_profiles";
+
+/// 新账号授权期间使用的临时目录前缀
+const PENDING_PREFIX: &str = "pending_";
+
+/// 临时目录保留时间（超过后视为授权中断留下的目录）
+const PENDING_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Browser profile of an account
+/// 账号的浏览器资料目录
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountBrowserProfile {
+    pub account_id: String,
+    pub path: String,
+    pub size_bytes: u64,
+    /// 最后使用时间（目录修改时间）
+    pub last_used: Option<String>,
+}
+
+/// 资料目录清理结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProfileCleanupResult {
+    pub removed_profiles: usize,
+    pub reclaimed_bytes: u64,
+}
+
+/// 资料目录管理
+#[derive(Debug, Clone)]
+pub struct BrowserProfileStore {
+    root: PathBuf,
+}
+
+/// 目录名只保留字母、数字、`-`、`_`，避免账号ID中出现路径分隔符
+fn sanitize(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+/// 目录总大小（字节）
+fn dir_size(path: &Path) -> u64 {
+    std::fs::read_dir(path)
+        .map(|entries| entries.filter_map(|e| e.ok())
+            .map(|entry| match entry.metadata() {
+                Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+                Ok(meta) => meta.len(),
+                Err(_) => 0,
+            })
+            .sum())
+        .unwrap_or(0)
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl BrowserProfileStore {
+    pub fn new(base_path: &Path) -> Self {
+        Self { root: base_path.join(PROFILES_DIR) }
+    }
+
+    /// 账号的资料目录（不保证已存在）
+    pub fn account_dir(&self, account_id: &str) -> PathBuf {
+        self.root.join(sanitize(account_id))
+    }
+
+    /// 新账号授权会话的临时资料目录
+    pub fn pending_dir(&self, session_id: &str) -> PathBuf {
+        self.root.join(format!("{}{}", PENDING_PREFIX, sanitize(session_id)))
+    }
+
+    /// 新账号保存后，把临时目录改名为账号目录（覆盖账号已有的目录）
+    pub fn adopt(&self, pending: &Path, account_id: &str) -> std::io::Result<PathBuf> {
+        let target = self.account_dir(account_id);
+        if target.exists() {
+            std::fs::remove_dir_all(&target)?;
+        }
+        std::fs::rename(pending, &target)?;
+        Ok(target)
+    }
+
+    /// 删除账号的资料目录，返回释放的字节数
+    pub fn remove(&self, account_id: &str) -> std::io::Result<u64> {
+        self.remove_dir(&self.account_dir(account_id))
+    }
+
+    /// 删除指定资料目录（只允许删除根目录下的子目录）
+    pub fn remove_dir(&self, dir: &Path) -> std::io::Result<u64> {
+        if dir.parent() != Some(self.root.as_path()) || !dir.exists() {
+            return Ok(0);
+        }
+        let size = dir_size(dir);
+        std::fs::remove_dir_all(dir)?;
+        Ok(size)
+    }
+
+    /// 所有账号资料目录（不含临时目录）
+    pub fn list(&self) -> Vec<AccountBrowserProfile> {
+        let Ok(entries) = std::fs::read_dir(&self.root) else { return Vec::new() };
+        let mut profiles: Vec<AccountBrowserProfile> = entries.filter_map(|e| e.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| {
+                let account_id = entry.file_name().to_string_lossy().into_owned();
+                if account_id.starts_with(PENDING_PREFIX) {
+                    return None;
+                }
+                let path = entry.path();
+                Some(AccountBrowserProfile {
+                    account_id,
+                    size_bytes: dir_size(&path),
+                    last_used: modified_at(&path).map(|t| {
+                        chrono::DateTime::<chrono::Local>::from(t).format("%Y-%m-%d %H:%M:%S").to_string()
+                    }),
+                    path: path.to_string_lossy().into_owned(),
+                })
+            })
+            .collect();
+        profiles.sort_by(|a, b| a.account_id.cmp(&b.account_id));
+        profiles
+    }
+
+    /// 清理不属于 `account_ids` 中任何账号的资料目录，以及过期的临时目录
+    pub fn cleanup(&self, account_ids: &HashSet<String>) -> ProfileCleanupResult {
+        let mut result = ProfileCleanupResult::default();
+        let keep: HashSet<String> = account_ids.iter().map(|id| sanitize(id)).collect();
+        let Ok(entries) = std::fs::read_dir(&self.root) else { return result };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let stale = if name.starts_with(PENDING_PREFIX) {
+                modified_at(&path)
+                    .and_then(|t| t.elapsed().ok())
+                    .map(|age| age > PENDING_TTL)
+                    .unwrap_or(true)
+            } else {
+                !keep.contains(&name)
+            };
+            if !stale {
+                continue;
+            }
+            match self.remove_dir(&path) {
+                Ok(size) => {
+                    result.removed_profiles += 1;
+                    result.reclaimed_bytes += size;
+                }
+                Err(e) => tracing::warn!("[BrowserProfiles] 删除资料目录失败 {}: {}", path.display(), e),
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_keeps_existing_accounts() {
+        let base = std::env::temp_dir().join(format!("amm-profiles-{}", uuid::Uuid::new_v4()));
+        let store = BrowserProfileStore::new(&base);
+
+        for id in ["kept", "deleted"] {
+            let dir = store.account_dir(id);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("Cookies"), b"data").unwrap();
+        }
+        let pending = store.pending_dir("session/1");
+        std::fs::create_dir_all(&pending).unwrap();
+        assert_eq!(pending.file_name().unwrap(), "pending_session_1");
+
+        let result = store.cleanup(&HashSet::from(["kept".to_string()]));
+        assert_eq!(result.removed_profiles, 1);
+        assert_eq!(result.reclaimed_bytes, 4);
+        // 未过期的临时目录保留，且不出现在账号列表中
+        assert!(pending.exists());
+        let ids: Vec<String> = store.list().into_iter().map(|p| p.account_id).collect();
+        assert_eq!(ids, vec!["kept".to_string()]);
+
+        store.adopt(&pending, "new").unwrap();
+        assert!(store.account_dir("new").exists());
+
+        let _ = std::fs::remove_dir_all(base);
+    }
+}
@@ -0,0 +1,370 @@
+// Command line mode - publish without launching the UI
+// 命令行模式：不启动界面直接发布，供服务器部署时由 cron 定时执行
+//
+//   auto-matrix-manager publish --task-file tasks.json [--data-dir DIR]
+//   auto-matrix-manager publish --task-id ID [--data-dir DIR]
+//
+// `--task-file` 为任务数组（或单个任务）的 JSON 文件，每个任务先写入数据库（界面中可以看到），再逐个账号发布；
+// `--task-id` 发布数据库中已有的任务（如在界面中创建好的任务），只处理未发布成功的账号。
+// 默认使用与桌面应用相同的数据目录，全部账号发布成功时退出码为 0，否则为 1
+
+use std::path::PathBuf;
+use serde::Deserialize;
+use crate::commands::{merge_hashtags, normalize_mentions, publish_url_for, publish_with_retry, record_error_code, with_mentions};
+use crate::core::{
+    MusicInfo, PlatformType, PublicationAccountDetail, PublicationStats, PublicationStatus, PublicationTask,
+    PublishCancelToken, PublishErrorCode, PublishMode, PublishRequest, check_publish_risk,
+};
+use crate::storage::DatabaseManager;
+
+/// 与 tauri.conf.json 中的 identifier 一致，默认数据目录与桌面应用共用
+const APP_IDENTIFIER: &str = "com.yzg.matrix";
+
+const USAGE: &str = "\
+用法:
+  auto-matrix-manager publish --task-file <tasks.json> [--data-dir <目录>]
+  auto-matrix-manager publish --task-id <任务ID> [--data-dir <目录>]
+
+任务文件为 JSON 数组（或单个对象），字段:
+  title, description, video_path, cover_path, hashtags, mentions,
+  account_ids, visibility_type, download_allowed";
+
+/// One task in a `--task-file`
+/// 任务文件中的一个任务
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TaskSpec {
+    title: String,
+    #[serde(default)]
+    description: String,
+    video_path: String,
+    cover_path: Option<String>,
+    #[serde(default)]
+    hashtags: Vec<String>,
+    #[serde(default)]
+    mentions: Vec<String>,
+    account_ids: Vec<String>,
+    #[serde(default)]
+    visibility_type: i32,
+    #[serde(default)]
+    download_allowed: bool,
+}
+
+/// 任务文件可以是任务数组，也可以是单个任务
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TaskFile {
+    Many(Vec<TaskSpec>),
+    One(TaskSpec),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Target {
+    TaskFile(PathBuf),
+    TaskId(String),
+}
+
+#[derive(Debug)]
+struct PublishArgs {
+    target: Target,
+    data_dir: Option<PathBuf>,
+}
+
+/// Run the command line mode if the process was started with a subcommand
+/// 启动参数为命令行子命令时执行并返回退出码；没有子命令时返回 None，继续启动桌面应用
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("publish") => {}
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            return Some(0);
+        }
+        _ => return None,
+    }
+
+    let publish_args = match parse_publish_args(&args[1..]) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return Some(2);
+        }
+    };
+
+    init_cli_tracing();
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("创建运行时失败: {}", e);
+            return Some(1);
+        }
+    };
+
+    Some(match runtime.block_on(publish(publish_args)) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) => {
+            eprintln!("发布失败: {}", e);
+            1
+        }
+    })
+}
+
+fn parse_publish_args(args: &[String]) -> Result<PublishArgs, String> {
+    let mut target = None;
+    let mut data_dir = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| iter.next().cloned().ok_or_else(|| format!("{} 缺少参数值", name));
+        match arg.as_str() {
+            "--task-file" => target = Some(Target::TaskFile(PathBuf::from(value(arg)?))),
+            "--task-id" => target = Some(Target::TaskId(value(arg)?)),
+            "--data-dir" => data_dir = Some(PathBuf::from(value(arg)?)),
+            other => return Err(format!("未知参数: {}", other)),
+        }
+    }
+
+    let target = target.ok_or_else(|| "需要 --task-file 或 --task-id".to_string())?;
+    Ok(PublishArgs { target, data_dir })
+}
+
+/// 桌面应用的数据目录（与 Tauri 的 app_data_dir 相同）
+fn default_data_dir() -> PathBuf {
+    let env_path = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    let home = || env_path("HOME").unwrap_or_else(|| PathBuf::from("."));
+
+    let base = if cfg!(target_os = "windows") {
+        env_path("APPDATA").unwrap_or_else(home)
+    } else if cfg!(target_os = "macos") {
+        home().join("Library").join("Application Support")
+    } else {
+        env_path("XDG_DATA_HOME").unwrap_or_else(|| home().join(".local").join("share"))
+    };
+    base.join(APP_IDENTIFIER)
+}
+
+/// 命令行模式只输出到控制台
+fn init_cli_tracing() {
+    use tracing_subscriber::prelude::*;
+    use tracing_subscriber::filter::LevelFilter;
+
+    tracing_subscriber::registry()
+        .with(LevelFilter::INFO)
+        .with(tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_target(false))
+        .init();
+}
+
+/// 执行发布，全部账号发布成功时返回 true
+async fn publish(args: PublishArgs) -> Result<bool, String> {
+    let data_dir = args.data_dir.unwrap_or_else(default_data_dir);
+    std::fs::create_dir_all(&data_dir).map_err(|e| format!("创建数据目录失败: {}: {}", data_dir.display(), e))?;
+    let db_manager = DatabaseManager::new(data_dir);
+
+    let task_ids = match args.target {
+        Target::TaskId(task_id) => vec![task_id],
+        Target::TaskFile(path) => {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("读取任务文件失败: {}: {}", path.display(), e))?;
+            let specs = match serde_json::from_str(&content).map_err(|e| format!("任务文件格式错误: {}", e))? {
+                TaskFile::Many(specs) => specs,
+                TaskFile::One(spec) => vec![spec],
+            };
+            let mut task_ids = Vec::new();
+            for spec in specs {
+                task_ids.push(save_task(&db_manager, spec)?);
+            }
+            task_ids
+        }
+    };
+
+    let mut all_succeeded = true;
+    for task_id in task_ids {
+        all_succeeded &= publish_task(&db_manager, &task_id).await?;
+    }
+    Ok(all_succeeded)
+}
+
+/// 把任务文件中的任务写入数据库，返回任务 ID
+fn save_task(db_manager: &DatabaseManager, spec: TaskSpec) -> Result<String, String> {
+    if spec.title.trim().is_empty() {
+        return Err("任务标题不能为空".to_string());
+    }
+    if !std::path::Path::new(&spec.video_path).is_file() {
+        return Err(format!("视频文件不存在: {}", spec.video_path));
+    }
+    if spec.account_ids.is_empty() {
+        return Err(format!("任务「{}」没有指定账号", spec.title));
+    }
+
+    let task_id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let task = PublicationTask {
+        id: task_id.clone(),
+        title: spec.title.trim().to_string(),
+        description: Some(spec.description),
+        video_path: spec.video_path,
+        cover_path: spec.cover_path.filter(|p| !p.is_empty()),
+        hashtags: merge_hashtags([&spec.hashtags]),
+        status: PublicationStatus::Draft,
+        created_at: now.clone(),
+        published_at: None,
+        hashtag_set_id: None,
+        vertical_video_path: None,
+        horizontal_video_path: None,
+        watermark_enabled: false,
+        visibility_type: spec.visibility_type,
+        download_allowed: spec.download_allowed,
+        poi_id: None,
+        poi_name: None,
+        music_id: None,
+        music_end_time: None,
+        mentions: normalize_mentions(spec.mentions),
+    };
+
+    let mut details = Vec::new();
+    for account_id in spec.account_ids {
+        let account = db_manager.get_account(&account_id).map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("账号不存在: {}", account_id))?;
+        details.push(PublicationAccountDetail {
+            id: uuid::Uuid::new_v4().to_string(),
+            publication_task_id: task_id.clone(),
+            account_id,
+            account_name: account.nickname,
+            platform: account.platform,
+            status: PublicationStatus::Draft,
+            created_at: now.clone(),
+            published_at: None,
+            publish_url: None,
+            stats: PublicationStats::default(),
+            message: None,
+            item_id: None,
+            strategy_version: None,
+            title_override: None,
+            description_override: None,
+            hashtags_override: None,
+            attempts: 0,
+            error_code: None,
+            variant: None,
+        });
+    }
+
+    db_manager.save_publication_with_accounts(&task, &details).map_err(|e| e.to_string())?;
+    println!("已创建任务「{}」: {}", task.title, task_id);
+    Ok(task_id)
+}
+
+/// 逐个账号发布任务（已发布成功的账号跳过），返回是否全部成功
+async fn publish_task(db_manager: &DatabaseManager, task_id: &str) -> Result<bool, String> {
+    let task = db_manager.get_publication_task(task_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("任务不存在: {}", task_id))?;
+    let details = db_manager.get_publication_task_with_accounts(task_id).map_err(|e| e.to_string())?
+        .map(|t| t.accounts)
+        .unwrap_or_default();
+
+    let pending: Vec<_> = details.into_iter()
+        .filter(|d| d.status != PublicationStatus::Completed)
+        .collect();
+    if pending.is_empty() {
+        println!("任务「{}」的账号均已发布", task.title);
+        return Ok(true);
+    }
+
+    db_manager.update_publication_task_status(task_id, PublicationStatus::Publishing).map_err(|e| e.to_string())?;
+
+    let settings = db_manager.get_app_settings().unwrap_or_default();
+    let cancel_token = PublishCancelToken::default();
+    let music_info = task.music_id.clone().map(|music_id| MusicInfo {
+        music_id,
+        music_end_time: task.music_end_time.clone().unwrap_or_default(),
+    });
+
+    let mut failed = 0;
+    for detail in &pending {
+        let platform: PlatformType = detail.platform.clone();
+        let hashtags = detail.hashtags_override.clone()
+            .or_else(|| task.hashtag_set_id.as_deref()
+                .and_then(|set_id| db_manager.get_hashtag_set(set_id).ok().flatten())
+                .map(|set| set.tags_for(&platform)))
+            .unwrap_or_else(|| task.hashtags.clone());
+        let request = PublishRequest {
+            account_id: detail.account_id.clone(),
+            video_path: task.video_path_for(&platform).into(),
+            cover_path: task.cover_path.clone().map(|p| p.into()),
+            image_paths: Vec::new(),
+            title: detail.title_override.clone().unwrap_or_else(|| task.title.clone()),
+            description: with_mentions(detail.description_override.clone().or_else(|| task.description.clone()), &task.mentions),
+            hashtags,
+            visibility_type: task.visibility_type,
+            download_allowed: task.download_allowed as i32,
+            timeout: 0,
+            record_id: None,
+            send_time: None,
+            music_info: music_info.clone(),
+            poi_id: task.poi_id.clone(),
+            poi_name: task.poi_name.clone(),
+            anchor: None,
+            extra_info: None,
+            platform_data: None,
+            progress_info: None,
+            cancel_token: None,
+            publish_permit: None,
+            publish_mode: PublishMode::Publish,
+        };
+
+        let (status, publish_url, message, item_id) =
+            match publish_with_retry(db_manager, &platform, &detail.id, request, settings.max_attempts_for(&platform), &cancel_token).await {
+                Ok(result) if result.success => {
+                    let url = result.item_id.as_deref().map(|id| publish_url_for(&platform, id));
+                    (PublicationStatus::Completed, url, None, result.item_id)
+                }
+                Ok(result) => (PublicationStatus::Failed, None, result.error_message, None),
+                Err(e) => (PublicationStatus::Failed, None, Some(e.to_string()), None),
+            };
+
+        let succeeded = status == PublicationStatus::Completed;
+        if let Err(e) = db_manager.update_publication_account_status(&detail.id, status, publish_url.clone(), message.clone(), item_id) {
+            tracing::error!("[Cli] 更新发布状态失败: detail_id={}, error={}", detail.id, e);
+        }
+        check_publish_risk(db_manager, &detail.id);
+        if succeeded {
+            println!("✅ {}（{}）: {}", detail.account_name, platform.display_name(), publish_url.unwrap_or_default());
+        } else {
+            failed += 1;
+            let message = message.unwrap_or_else(|| "未知错误".to_string());
+            record_error_code(db_manager, &detail.id, Some(PublishErrorCode::from_message(&message)));
+            println!("❌ {}（{}）: {}", detail.account_name, platform.display_name(), message);
+        }
+    }
+
+    if let Err(e) = db_manager.update_task_status_from_accounts(task_id) {
+        tracing::error!("[Cli] 更新任务状态失败: task_id={}, error={}", task_id, e);
+    }
+    println!("任务「{}」: 成功 {}，失败 {}", task.title, pending.len() - failed, failed);
+    Ok(failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_publish_args() {
+        let parsed = parse_publish_args(&args(&["--task-file", "tasks.json", "--data-dir", "/tmp/data"])).unwrap();
+        assert_eq!(parsed.target, Target::TaskFile(PathBuf::from("tasks.json")));
+        assert_eq!(parsed.data_dir, Some(PathBuf::from("/tmp/data")));
+
+        let parsed = parse_publish_args(&args(&["--task-id", "abc"])).unwrap();
+        assert_eq!(parsed.target, Target::TaskId("abc".to_string()));
+        assert_eq!(parsed.data_dir, None);
+
+        assert!(parse_publish_args(&args(&[])).is_err());
+        assert!(parse_publish_args(&args(&["--task-file"])).is_err());
+        assert!(parse_publish_args(&args(&["--unknown", "x"])).is_err());
+    }
+}
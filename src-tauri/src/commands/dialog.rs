@@ -0,0 +1,47 @@
+// Native file dialogs via tauri-plugin-dialog
+// 系统文件对话框：统一走 dialog 插件，桌面端和移动端（Android/iOS）都可用
+
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, FilePath};
+
+/// 将对话框返回的路径转换为本地路径（移动端可能返回 content:// 等 URL）
+fn to_local_path(file: FilePath) -> Result<PathBuf, String> {
+    file.into_path().map_err(|e| format!("无法访问所选文件: {}", e))
+}
+
+/// 打开文件选择对话框，`extensions` 为空时不限制类型；用户取消时返回 None
+pub async fn pick_file(app: &AppHandle, title: &str, extensions: &[&str]) -> Result<Option<PathBuf>, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let mut dialog = app.dialog().file().set_title(title);
+    if !extensions.is_empty() {
+        dialog = dialog.add_filter("Files", extensions);
+    }
+    dialog.pick_file(move |file| {
+        let _ = tx.send(file);
+    });
+
+    match rx.await.map_err(|e| format!("文件对话框异常关闭: {}", e))? {
+        Some(file) => to_local_path(file).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// 打开保存文件对话框；用户取消时返回 None
+pub async fn save_file(app: &AppHandle, title: &str, file_name: &str, filter: (&str, &[&str])) -> Result<Option<PathBuf>, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    app.dialog().file()
+        .set_title(title)
+        .set_file_name(file_name)
+        .add_filter(filter.0, filter.1)
+        .save_file(move |file| {
+            let _ = tx.send(file);
+        });
+
+    match rx.await.map_err(|e| format!("文件对话框异常关闭: {}", e))? {
+        Some(file) => to_local_path(file).map(Some),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,264 @@
+// Embedded HTTP API for external automation (feature `http-api`)
+// HTTP API 服务：以 JSON 接口提供与 Tauri 命令相同的操作（账号列表、创建任务、发布、查询状态、提取评论），
+// 外部脚本 / CI 可在不操作界面的情况下驱动矩阵管理器。除 /api/health 外都需要 `Authorization: Bearer {token}`
+//
+// - GET  /api/health
+// - GET  /api/accounts?page=&page_size=
+// - POST /api/tasks
+// - GET  /api/tasks/:id
+// - POST /api/tasks/:id/publish       后台发布，立即返回 202，之后轮询 GET /api/tasks/:id
+// - POST /api/tasks/:id/cancel
+// - POST /api/comments/extract
+// - GET  /api/comments/:aweme_id?page=&page_size=
+
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::Duration;
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::json;
+use tauri::AppHandle;
+use tokio::sync::oneshot;
+use crate::core::{CommentExtractResult, HttpApiConfig, MusicInfo, PoiInfo, PublicationTaskWithAccounts, UserAccount, check_bearer_token};
+use super::pagination::Paginated;
+use super::{CreatePublicationTaskRequest, TaskAccountInput, TaskHashtagsInput};
+
+/// 正在运行的服务的停止信号
+static SHUTDOWN: Mutex<Option<oneshot::Sender<()>>> = Mutex::new(None);
+
+/// 重启时等待旧服务释放端口的重试次数
+const BIND_ATTEMPTS: u32 = 5;
+
+#[derive(Clone)]
+struct ApiState {
+    app: AppHandle,
+    token: String,
+}
+
+/// 错误响应：`{ "error": "..." }`
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        ApiError(StatusCode::BAD_REQUEST, message)
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PageQuery {
+    page: Option<i64>,
+    page_size: Option<i64>,
+}
+
+/// 创建任务的请求体（CreatePublicationTaskRequest 去掉旧版兼容字段）
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CreateTaskBody {
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    video_path: String,
+    cover_path: Option<String>,
+    accounts: Option<Vec<TaskAccountInput>>,
+    group_id: Option<String>,
+    #[serde(default)]
+    hashtags: Vec<String>,
+    hashtag_set_id: Option<String>,
+    vertical_video_path: Option<String>,
+    horizontal_video_path: Option<String>,
+    watermark_enabled: Option<bool>,
+    visibility_type: Option<i32>,
+    download_allowed: Option<bool>,
+    poi: Option<PoiInfo>,
+    music: Option<MusicInfo>,
+    mentions: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtractCommentsBody {
+    detail_id: String,
+    aweme_id: String,
+    #[serde(default = "default_extract_count")]
+    max_count: i64,
+    #[serde(default)]
+    cursor: i64,
+}
+
+fn default_extract_count() -> i64 {
+    50
+}
+
+/// Start (or restart) the HTTP API with the given configuration; stops it when disabled
+/// 按配置启动 HTTP API（已在运行时先停止旧服务）；未启用时只停止
+pub fn restart_http_api(app: &AppHandle, config: &HttpApiConfig) {
+    if let Some(shutdown) = SHUTDOWN.lock().unwrap().take() {
+        let _ = shutdown.send(());
+        tracing::info!("[HttpApi] 已停止 HTTP API");
+    }
+    if !config.enabled || config.token.is_empty() {
+        return;
+    }
+
+    let addr: SocketAddr = match format!("{}:{}", config.bind_address, config.port).parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            tracing::error!("[HttpApi] 监听地址无效: {}:{}: {}", config.bind_address, config.port, e);
+            return;
+        }
+    };
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    *SHUTDOWN.lock().unwrap() = Some(shutdown_tx);
+
+    let state = ApiState { app: app.clone(), token: config.token.clone() };
+    tauri::async_runtime::spawn(async move {
+        let mut listener = None;
+        for attempt in 1..=BIND_ATTEMPTS {
+            match tokio::net::TcpListener::bind(addr).await {
+                Ok(l) => {
+                    listener = Some(l);
+                    break;
+                }
+                Err(e) if attempt < BIND_ATTEMPTS => {
+                    tracing::debug!("[HttpApi] 端口暂不可用，稍后重试: {}", e);
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                }
+                Err(e) => tracing::error!("[HttpApi] 监听 {} 失败: {}", addr, e),
+            }
+        }
+        let Some(listener) = listener else {
+            return;
+        };
+
+        tracing::info!("[HttpApi] HTTP API 已启动: http://{}", addr);
+        let server = axum::serve(listener, router(state))
+            .with_graceful_shutdown(async {
+                shutdown_rx.await.ok();
+            });
+        if let Err(e) = server.await {
+            tracing::error!("[HttpApi] HTTP API 异常退出: {}", e);
+        }
+    });
+}
+
+fn router(state: ApiState) -> Router {
+    let protected = Router::new()
+        .route("/api/accounts", get(list_accounts))
+        .route("/api/tasks", post(create_task))
+        .route("/api/tasks/:id", get(get_task))
+        .route("/api/tasks/:id/publish", post(publish_task))
+        .route("/api/tasks/:id/cancel", post(cancel_task))
+        .route("/api/comments/extract", post(extract_comments))
+        .route("/api/comments/:aweme_id", get(list_comments))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
+    Router::new()
+        .route("/api/health", get(health))
+        .merge(protected)
+        .with_state(state)
+}
+
+async fn require_token(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let authorization = request.headers().get(header::AUTHORIZATION).and_then(|v| v.to_str().ok());
+    if !check_bearer_token(authorization, &state.token) {
+        return ApiError(StatusCode::UNAUTHORIZED, "令牌无效".to_string()).into_response();
+    }
+    next.run(request).await
+}
+
+async fn health() -> Json<serde_json::Value> {
+    Json(json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }))
+}
+
+/// 账号列表不返回凭证参数
+async fn list_accounts(State(state): State<ApiState>, Query(query): Query<PageQuery>) -> ApiResult<Paginated<UserAccount>> {
+    let mut accounts = super::get_all_accounts(state.app, query.page, query.page_size)?;
+    for account in &mut accounts.items {
+        account.params.clear();
+    }
+    Ok(Json(accounts))
+}
+
+async fn create_task(State(state): State<ApiState>, Json(body): Json<CreateTaskBody>) -> ApiResult<PublicationTaskWithAccounts> {
+    let request = CreatePublicationTaskRequest {
+        title: body.title,
+        description: body.description,
+        video_path: body.video_path,
+        cover_path: body.cover_path,
+        accounts: body.accounts,
+        hashtags: Some(TaskHashtagsInput::Task(body.hashtags)),
+        hashtag_set_id: body.hashtag_set_id,
+        vertical_video_path: body.vertical_video_path,
+        horizontal_video_path: body.horizontal_video_path,
+        group_id: body.group_id,
+        watermark_enabled: body.watermark_enabled,
+        visibility_type: body.visibility_type,
+        download_allowed: body.download_allowed,
+        poi: body.poi,
+        music: body.music,
+        mentions: body.mentions,
+        ..Default::default()
+    };
+    let task = super::create_publication_task(state.app, request)?;
+    Ok(Json(task))
+}
+
+async fn get_task(State(state): State<ApiState>, Path(task_id): Path<String>) -> ApiResult<PublicationTaskWithAccounts> {
+    super::get_publication_task_with_accounts(state.app, &task_id)?
+        .map(Json)
+        .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("任务不存在: {}", task_id)))
+}
+
+/// 发布耗时较长，在后台执行并立即返回
+async fn publish_task(State(state): State<ApiState>, Path(task_id): Path<String>) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    if super::get_publication_task_with_accounts(state.app.clone(), &task_id)?.is_none() {
+        return Err(ApiError(StatusCode::NOT_FOUND, format!("任务不存在: {}", task_id)));
+    }
+    if crate::core::is_publish_running(&task_id) {
+        return Err(ApiError(StatusCode::CONFLICT, "任务正在发布中".to_string()));
+    }
+
+    let app = state.app.clone();
+    let id = task_id.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = super::run_publication_task(app, &id, &[]).await {
+            tracing::error!("[HttpApi] 发布任务失败: task_id={}, error={}", id, e);
+        }
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(json!({ "task_id": task_id, "status": "Publishing" }))))
+}
+
+async fn cancel_task(Path(task_id): Path<String>) -> ApiResult<serde_json::Value> {
+    let cancelled = super::cancel_publication_task(&task_id)?;
+    Ok(Json(json!({ "task_id": task_id, "cancelled": cancelled })))
+}
+
+async fn extract_comments(State(state): State<ApiState>, Json(body): Json<ExtractCommentsBody>) -> ApiResult<CommentExtractResult> {
+    let result = super::extract_comments(state.app, &body.detail_id, &body.aweme_id, body.max_count, body.cursor).await?;
+    Ok(Json(result))
+}
+
+async fn list_comments(
+    State(state): State<ApiState>,
+    Path(aweme_id): Path<String>,
+    Query(query): Query<PageQuery>,
+) -> ApiResult<Paginated<crate::core::Comment>> {
+    let comments = super::get_comments_by_aweme_id(state.app, &aweme_id, query.page, query.page_size)?;
+    Ok(Json(comments))
+}
@@ -3,33 +3,58 @@
 
 use crate::core::*;
 use crate::platforms::douyin::DouyinPlatform;
-use crate::platforms::traits::CommentExtractor;
-use crate::storage::{DatabaseManager, ExtractorConfig};
-use crate::browser::{BrowserAutomator, BrowserAuthResult, BrowserAuthStep};
+use crate::platforms::douyin::video_uploader::clear_upload_checkpoints;
+use crate::platforms::xiaohongshu::XiaohongshuPlatform;
+use crate::platforms::kuaishou::KuaishouPlatform;
+use crate::platforms::bilibili::BilibiliPlatform;
+use crate::platforms::traits::{CommentExtractor, MessageExtractor};
+use crate::platforms::PlatformModuleVersion;
+use crate::storage::{DatabaseManager, StorageBackend, AppSettings, FailureBreakdown, HashtagPerformance, HashtagSuggestion, DEFAULT_HASHTAG_SUGGESTIONS, AccountHealth, AccountHealthLevel, RiskFlag, AccountStatsDelta, AccountStatsSnapshot, AccountTimelineEvent, DEFAULT_TIMELINE_LIMIT, StatsPeriod, PublishStatistics, CacheManager, CacheCategory, CacheCategoryUsage, CacheCleanupResult, ExtractorConfig, ReportQuery, ReportResult, ReportEntitySchema, SchemaReport, ContentionStats, BackupSummary, CommentExportItem, export_comments_csv, export_comments_xlsx, PublishCheckpoint, PublicationTaskFilter, TaskSearchHit, CommentSearchHit, DEFAULT_SEARCH_LIMIT, TrashItem, TrashPurgeResult, DEFAULT_TRASH_RETENTION_DAYS};
+use crate::browser::{AccountBrowserProfile, BrowserAutomator, BrowserAuthResult, BrowserAuthSession, BrowserAuthSessions, BrowserAuthStep, BrowserKind, BrowserProfile, BrowserProfileStore, BrowserFingerprint, ExtractorTestReport, GenericBrowser, ProfileCleanupResult, Viewport};
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
-use serde::Serialize;
-use crate::core::{PublicationTask, PublicationAccountDetail, PublicationTaskWithAccounts, Comment, CommentExtractResult};
-
-// 分页评论响应结构
-#[derive(Serialize)]
-pub struct PaginatedCommentsResponse {
-    pub comments: Vec<Comment>,
-    pub total: i64,
-}
+use tracing::Instrument;
+use serde::{Deserialize, Serialize};
+use crate::core::{PublicationTask, PublicationAccountDetail, PublicationTaskWithAccounts, Comment, CommentExtractResult, HashtagSet, TaskNote, AccountReassignment};
+use std::collections::HashMap;
+
+mod pagination;
+pub use pagination::Paginated;
+mod dialog;
+#[cfg(feature = "http-api")]
+mod http_server;
+#[cfg(feature = "http-api")]
+pub use http_server::restart_http_api;
 
 // App state
 // 应用状态
 #[derive(Clone)]
 pub struct AppState {
     pub db_manager: Arc<DatabaseManager>,
-    pub browser_automator: Arc<tokio::sync::Mutex<BrowserAutomator>>,
+    /// 账号/任务/评论/配置的存储后端（默认即 db_manager 对应的本地 SQLite）
+    pub storage: Arc<dyn StorageBackend>,
+    /// 浏览器授权会话（多个账号可同时授权）
+    pub browser_sessions: Arc<BrowserAuthSessions>,
+    /// 批量重新授权队列
+    pub reauth_queue: Arc<tokio::sync::Mutex<ReauthQueue>>,
 }
 
 // Required for Tauri state management
 unsafe impl Send for AppState {}
 unsafe impl Sync for AppState {}
 
+/// Get the database manager shared through AppState
+/// 获取 AppState 中共享的数据库管理器（数据库结构只在首次连接时初始化一次）
+pub fn shared_db_manager(app: &AppHandle) -> Arc<DatabaseManager> {
+    app.state::<AppState>().db_manager.clone()
+}
+
+/// Get the storage backend shared through AppState
+/// 获取 AppState 中共享的存储后端（账号、任务、评论、配置）
+pub fn shared_storage(app: &AppHandle) -> Arc<dyn StorageBackend> {
+    app.state::<AppState>().storage.clone()
+}
+
 #[tauri::command]
 pub fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
@@ -81,50 +106,190 @@ pub struct PlatformInfo {
 pub fn get_accounts(
     app: AppHandle,
     platform: &str,
-) -> Result<Vec<UserAccount>, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
+    page: Option<i64>,
+    page_size: Option<i64>,
+) -> Result<Paginated<UserAccount>, String> {
+    let storage = shared_storage(&app);
 
     // 如果 platform 是 "all"，获取所有平台的账号
     if platform == "all" {
-        return db_manager.get_all_accounts()
+        return storage.get_all_accounts()
+            .map(|accounts| Paginated::paginate(accounts, page, page_size))
             .map_err(|e| e.to_string());
     }
 
     // 否则按指定平台查询
-    let platform_type = match platform {
-        "douyin" => PlatformType::Douyin,
-        "xiaohongshu" => PlatformType::Xiaohongshu,
-        "kuaishou" => PlatformType::Kuaishou,
-        "bilibili" => PlatformType::Bilibili,
-        _ => return Err(format!("Unknown platform: {}", platform)),
-    };
+    let platform_type = platform.parse::<PlatformType>().map_err(|e| e.to_string())?;
 
-    db_manager.get_accounts_by_platform(platform_type)
+    storage.get_accounts_by_platform(platform_type)
+        .map(|accounts| Paginated::paginate(accounts, page, page_size))
         .map_err(|e| e.to_string())
 }
 
 /// Get all accounts across all platforms
 /// 获取所有平台的账号
 #[tauri::command]
-pub fn get_all_accounts(app: AppHandle) -> Result<Vec<UserAccount>, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-    db_manager.get_all_accounts()
+pub fn get_all_accounts(app: AppHandle, page: Option<i64>, page_size: Option<i64>) -> Result<Paginated<UserAccount>, String> {
+    let storage = shared_storage(&app);
+    storage.get_all_accounts()
+        .map(|accounts| Paginated::paginate(accounts, page, page_size))
         .map_err(|e| e.to_string())
 }
 
+/// Move an account to the trash
+/// 删除账号（移入回收站）；登录会话和头像缓存保留到清空回收站时再删除
 #[tauri::command]
 pub fn delete_account(
     app: AppHandle,
     account_id: &str,
 ) -> Result<bool, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-    db_manager.delete_account(account_id)
+    let storage = shared_storage(&app);
+    storage.delete_account(account_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Restore an account from the trash
+/// 从回收站恢复账号
+#[tauri::command]
+pub fn restore_account(app: AppHandle, account_id: &str) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.restore_account(account_id)
+        .map_err(|e| e.to_string())
+}
+
+/// List the accounts and tasks in the trash
+/// 获取回收站中的账号和任务
+#[tauri::command]
+pub fn get_trash(app: AppHandle) -> Result<Vec<TrashItem>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_trash()
+        .map_err(|e| e.to_string())
+}
+
+/// Permanently delete trashed accounts and tasks older than the given number of days
+/// 清空回收站：彻底删除超过指定天数（默认 30 天，0 为全部）的账号和任务，并删除账号的登录会话和头像缓存
+#[tauri::command]
+pub fn purge_trash(app: AppHandle, older_than_days: Option<i64>) -> Result<TrashPurgeResult, String> {
+    let db_manager = shared_db_manager(&app);
+    let result = db_manager.purge_trash(older_than_days.unwrap_or(DEFAULT_TRASH_RETENTION_DAYS))
+        .map_err(|e| e.to_string())?;
+
+    for account_id in &result.account_ids {
+        if let Err(e) = BrowserProfileStore::new(&db_manager.base_path).remove(account_id) {
+            tracing::warn!("[Account] 删除浏览器资料目录失败: account_id={}, error={}", account_id, e);
+        }
+        remove_cached_avatar(&db_manager.base_path, account_id);
+    }
+    Ok(result)
+}
+
+/// Set or clear the maintenance (do-not-publish) flag of an account
+/// 设置账号维护状态：维护中的账号不出现在发布账号选择中，发布时被跳过
+#[tauri::command]
+pub fn set_account_on_hold(
+    app: AppHandle,
+    account_id: &str,
+    on_hold: bool,
+) -> Result<bool, String> {
+    let storage = shared_storage(&app);
+    storage.set_account_on_hold(account_id, on_hold)
         .map_err(|e| e.to_string())
 }
 
+/// Create an account group
+/// 创建账号分组
+#[tauri::command]
+pub fn create_account_group(app: AppHandle, name: &str, description: Option<&str>) -> Result<AccountGroup, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("分组名称不能为空".to_string());
+    }
+
+    let group = AccountGroup {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        description: description.unwrap_or_default().trim().to_string(),
+        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    };
+
+    let db_manager = shared_db_manager(&app);
+    db_manager.save_account_group(&group).map_err(|e| e.to_string())?;
+    Ok(group)
+}
+
+/// Get all account groups
+/// 获取所有账号分组
+#[tauri::command]
+pub fn get_account_groups(app: AppHandle) -> Result<Vec<AccountGroup>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_account_groups().map_err(|e| e.to_string())
+}
+
+/// Delete an account group (its accounts become ungrouped)
+/// 删除账号分组（组内账号变为未分组）
+#[tauri::command]
+pub fn delete_account_group(app: AppHandle, group_id: &str) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.delete_account_group(group_id).map_err(|e| e.to_string())
+}
+
+/// Move an account into a group (empty / null to ungroup)
+/// 设置账号所属分组（为空时移出分组）
+#[tauri::command]
+pub fn assign_account_to_group(app: AppHandle, account_id: &str, group_id: Option<&str>) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
+    let group_id = group_id.filter(|id| !id.is_empty());
+    if let Some(group_id) = group_id {
+        db_manager.get_account_group(group_id).map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("分组不存在: {}", group_id))?;
+    }
+    db_manager.assign_account_to_group(account_id, group_id).map_err(|e| e.to_string())
+}
+
+/// Get the watermark configuration of an account group
+/// 获取账号分组的水印配置
+#[tauri::command]
+pub fn get_group_watermark(app: AppHandle, group_id: &str) -> Result<Option<WatermarkConfig>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_group_watermark(group_id).map_err(|e| e.to_string())
+}
+
+/// Set or clear the watermark configuration of an account group
+/// 设置账号分组的水印配置（null 为清除）；开启水印的任务发布前按此配置给视频叠加水印
+#[tauri::command]
+pub fn set_group_watermark(app: AppHandle, group_id: &str, config: Option<WatermarkConfig>) -> Result<bool, String> {
+    if let Some(config) = &config {
+        config.validate()?;
+    }
+
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_account_group(group_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("分组不存在: {}", group_id))?;
+    db_manager.set_group_watermark(group_id, config.as_ref()).map_err(|e| e.to_string())
+}
+
+/// Replace the tags of an account
+/// 设置账号标签
+#[tauri::command]
+pub fn set_account_tags(app: AppHandle, account_id: &str, tags: Vec<String>) -> Result<bool, String> {
+    let mut seen = std::collections::HashSet::new();
+    let tags: Vec<String> = tags.into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty() && seen.insert(t.clone()))
+        .collect();
+
+    let db_manager = shared_db_manager(&app);
+    db_manager.set_account_tags(account_id, &tags).map_err(|e| e.to_string())
+}
+
+/// Get the accounts in a group
+/// 获取分组内的账号
+#[tauri::command]
+pub fn get_accounts_by_group(app: AppHandle, group_id: &str) -> Result<Vec<UserAccount>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_accounts_by_group(group_id).map_err(|e| e.to_string())
+}
+
 /// Add a new account via JSON params
 /// 添加账号（通过JSON参数）
 #[tauri::command]
@@ -136,13 +301,7 @@ pub fn add_account(
     avatar_url: &str,
     params: &str,
 ) -> Result<UserAccount, String> {
-    let platform_type = match platform {
-        "douyin" => PlatformType::Douyin,
-        "xiaohongshu" => PlatformType::Xiaohongshu,
-        "kuaishou" => PlatformType::Kuaishou,
-        "bilibili" => PlatformType::Bilibili,
-        _ => return Err(format!("Unknown platform: {}", platform)),
-    };
+    let platform_type = platform.parse::<PlatformType>().map_err(|e| e.to_string())?;
 
     // Validate params is valid JSON
     let _: serde_json::Value = serde_json::from_str(params)
@@ -157,11 +316,13 @@ pub fn add_account(
         params: params.to_string(),
         status: AccountStatus::Active,
         created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        on_hold: false,
+        group_id: None,
+        tags: Vec::new(),
     };
 
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-    db_manager.save_account(&account)
+    let storage = shared_storage(&app);
+    storage.save_account(&account)
         .map_err(|e| e.to_string())?;
 
     Ok(account)
@@ -173,10 +334,48 @@ pub fn add_account(
 /// Get all publication tasks with their account details
 /// 获取所有作品发布任务及其账号详情
 #[tauri::command]
-pub fn get_publication_tasks(app: AppHandle) -> Result<Vec<PublicationTaskWithAccounts>, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-    db_manager.get_all_publication_tasks()
+pub fn get_publication_tasks(app: AppHandle, page: Option<i64>, page_size: Option<i64>) -> Result<Paginated<PublicationTaskWithAccounts>, String> {
+    let storage = shared_storage(&app);
+    storage.get_all_publication_tasks()
+        .map(|tasks| Paginated::paginate(tasks, page, page_size))
+        .map_err(|e| e.to_string())
+}
+
+/// Get one page of publication tasks filtered in SQL
+/// 分页获取发布任务：按状态、平台和关键字（标题、描述、账号名称）筛选，总数在数据库中统计
+#[tauri::command]
+pub fn get_publication_tasks_paginated(
+    app: AppHandle,
+    page: Option<i64>,
+    page_size: Option<i64>,
+    status_filter: Option<PublicationStatus>,
+    platform_filter: Option<PlatformType>,
+    search: Option<String>,
+) -> Result<Paginated<PublicationTaskWithAccounts>, String> {
+    let db_manager = shared_db_manager(&app);
+    let (page, page_size) = pagination::normalize_page(page, page_size);
+    let filter = PublicationTaskFilter {
+        status: status_filter,
+        platform: platform_filter,
+        search,
+    };
+
+    let offset = (page - 1) * page_size;
+    let tasks = db_manager.get_publication_tasks_paginated(&filter, offset, page_size)
+        .map_err(|e| e.to_string())?;
+
+    let total = db_manager.count_publication_tasks(&filter)
+        .map_err(|e| e.to_string())?;
+
+    Ok(Paginated::new(tasks, total, page, page_size))
+}
+
+/// Full-text search over task titles and descriptions
+/// 全文搜索发布任务（标题、描述），多个关键字以空格分隔
+#[tauri::command]
+pub fn search_tasks(app: AppHandle, query: &str, limit: Option<i64>) -> Result<Vec<TaskSearchHit>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.search_tasks(query, limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, pagination::MAX_PAGE_SIZE))
         .map_err(|e| e.to_string())
 }
 
@@ -184,18 +383,17 @@ pub fn get_publication_tasks(app: AppHandle) -> Result<Vec<PublicationTaskWithAc
 /// 获取单个作品发布任务及其账号详情
 #[tauri::command]
 pub fn get_publication_task(app: AppHandle, task_id: &str) -> Result<Option<PublicationTaskWithAccounts>, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
+    let storage = shared_storage(&app);
 
     // Get the main task
-    let task = match db_manager.get_publication_task(task_id).map_err(|e| e.to_string())? {
+    let task = match storage.get_publication_task(task_id).map_err(|e| e.to_string())? {
         Some(t) => t,
         None => return Ok(None),
     };
 
     // Get account details
     let task_id_clone = task.id.clone();
-    let all_tasks = db_manager.get_all_publication_tasks().map_err(|e| e.to_string())?;
+    let all_tasks = storage.get_all_publication_tasks().map_err(|e| e.to_string())?;
 
     // Find the task with accounts
     for t in all_tasks {
@@ -215,68 +413,437 @@ pub fn get_publication_task(app: AppHandle, task_id: &str) -> Result<Option<Publ
         status: task.status,
         created_at: task.created_at,
         published_at: task.published_at.unwrap_or_default(),
+        hashtag_set_id: task.hashtag_set_id,
+        vertical_video_path: task.vertical_video_path,
+        horizontal_video_path: task.horizontal_video_path,
+        watermark_enabled: task.watermark_enabled,
+        visibility_type: task.visibility_type,
+        download_allowed: task.download_allowed,
+        poi_id: task.poi_id,
+        poi_name: task.poi_name,
+        music_id: task.music_id,
+        music_end_time: task.music_end_time,
+        mentions: task.mentions,
         accounts: Vec::new(),
     }))
 }
 
+/// Account selected for a publication task
+/// 任务目标账号（账号与平台一一对应，替代原来的 account_ids / platforms 平行数组）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskAccountInput {
+    pub account_id: String,
+    pub platform: PlatformType,
+    #[serde(default)]
+    pub overrides: TaskAccountOverrides,
+}
+
+/// Per-account overrides on top of the task content
+/// 账号级别的内容覆盖（用于避免矩阵账号之间内容完全重复）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskAccountOverrides {
+    /// 该账号专属标题
+    #[serde(default)]
+    pub title: Option<String>,
+    /// 该账号专属描述
+    #[serde(default)]
+    pub description: Option<String>,
+    /// 该账号额外的话题（与任务话题合并去重后作为该账号的话题）
+    #[serde(default)]
+    pub hashtags: Vec<String>,
+}
+
+/// Task hashtags: a flat list, or the legacy per-account lists
+/// 任务话题：新格式为一维数组，旧格式为与 account_ids 对齐的二维数组
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum TaskHashtagsInput {
+    Task(Vec<String>),
+    PerAccount(Vec<Vec<String>>),
+}
+
+/// 将旧版 account_ids / platforms / hashtags 平行数组转换为 TaskAccountInput
+fn legacy_task_accounts(
+    account_ids: Vec<String>,
+    platforms: Vec<String>,
+    hashtags: Option<&TaskHashtagsInput>,
+) -> Result<Vec<TaskAccountInput>, String> {
+    if account_ids.len() != platforms.len() {
+        return Err(format!("account_ids 与 platforms 数量不一致: {} != {}", account_ids.len(), platforms.len()));
+    }
+
+    let per_account = match hashtags {
+        Some(TaskHashtagsInput::PerAccount(tags)) => tags.as_slice(),
+        _ => &[],
+    };
+
+    account_ids.into_iter().zip(platforms).enumerate()
+        .map(|(i, (account_id, platform))| {
+            let platform = platform.parse::<PlatformType>().map_err(|e| e.to_string())?;
+            Ok(TaskAccountInput {
+                account_id,
+                platform,
+                overrides: TaskAccountOverrides {
+                    hashtags: per_account.get(i).cloned().unwrap_or_default(),
+                    ..Default::default()
+                },
+            })
+        })
+        .collect()
+}
+
+/// 合并话题：去掉 # 前缀和空白，按首次出现顺序去重
+pub(crate) fn merge_hashtags<'a>(lists: impl IntoIterator<Item = &'a Vec<String>>) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::new();
+    for tag in lists.into_iter().flatten() {
+        let tag = tag.trim().trim_start_matches('#').to_string();
+        if !tag.is_empty() && !merged.contains(&tag) {
+            merged.push(tag);
+        }
+    }
+    merged
+}
+
+/// 校验各账号所用视频是否超出其平台的上传限制，超限时返回列出失败账号的错误
+///
+/// 视频无法读取时只记录警告（发布时会再报错）
+fn check_task_video_limits(task: &PublicationTask, details: &[PublicationAccountDetail]) -> Result<(), String> {
+    let mut probed: HashMap<String, Option<VideoInfo>> = HashMap::new();
+    let mut failures = Vec::new();
+
+    for detail in details {
+        let video_path = task.video_path_for(&detail.platform);
+        let info = probed.entry(video_path.to_string()).or_insert_with(|| {
+            probe_video(video_path)
+                .map_err(|e| tracing::warn!("[Publish] 跳过平台限制校验: {}", e))
+                .ok()
+        });
+        let Some(info) = info else { continue };
+
+        let violations = check_video_limits(info, &detail.platform.limits());
+        if !violations.is_empty() {
+            failures.push(format!("{}（{}）：{}", detail.account_name, detail.platform.display_name(), violations.join("，")));
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("视频超出平台限制，以下账号将发布失败：\n{}", failures.join("\n")))
+    }
+}
+
+/// Limit violations of a video on one platform
+/// 视频在某个平台上的超限项
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformVideoCheck {
+    pub platform: PlatformType,
+    pub violations: Vec<String>,
+}
+
+/// Video metadata plus per-platform limit checks
+/// 视频校验结果：元数据 + 各平台超限项
+#[derive(Debug, Clone, Serialize)]
+pub struct VideoValidation {
+    pub info: VideoInfo,
+    pub checks: Vec<PlatformVideoCheck>,
+    /// 需要转码的原因（容器或编码不受支持），前端据此提示调用 transcode_video
+    pub transcode_reason: Option<String>,
+}
+
+/// 读取视频元数据并按平台限制校验，供创建任务前提示；`platforms` 为空时校验所有平台
+#[tauri::command]
+pub fn validate_video(path: &str, platforms: Option<Vec<PlatformType>>) -> Result<VideoValidation, String> {
+    let info = probe_video(path)?;
+    let platforms = platforms.filter(|p| !p.is_empty())
+        .unwrap_or_else(|| get_platform_limits().into_iter().map(|l| l.platform).collect());
+
+    let checks = platforms.into_iter()
+        .map(|platform| PlatformVideoCheck {
+            violations: check_video_limits(&info, &platform.limits()),
+            platform,
+        })
+        .collect();
+
+    Ok(VideoValidation { transcode_reason: transcode_reason(path, &info), info, checks })
+}
+
+/// Transcode result
+/// 转码结果
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscodeResult {
+    /// 可用于发布的视频路径（无需转码时为原路径）
+    pub output_path: String,
+    pub transcoded: bool,
+    pub reason: Option<String>,
+}
+
+/// Transcode progress event payload
+/// 转码进度事件
+#[derive(Debug, Clone, Serialize)]
+struct TranscodeProgressEvent {
+    source_path: String,
+    progress: i32,
+}
+
+impl VersionedEvent for TranscodeProgressEvent {
+    const NAME: &'static str = TRANSCODE_PROGRESS_EVENT_V1;
+    const VERSION: u32 = 1;
+    const LEGACY_NAME: Option<&'static str> = Some("video-transcode-progress");
+}
+
+/// 将平台不接受的视频（如 MKV 容器、VP9 编码）转码为 H.264 MP4，产物存放在转码缓存目录
+///
+/// 转码过程中发送 `video-transcode-progress` 事件（0-100）；格式已兼容时直接返回原路径
+#[tauri::command]
+pub async fn transcode_video(app: AppHandle, path: String, force: Option<bool>) -> Result<TranscodeResult, String> {
+    let _timer = CommandTimer::start("transcode_video");
+    let info = probe_video(&path)?;
+    let reason = transcode_reason(&path, &info);
+    if reason.is_none() && !force.unwrap_or(false) {
+        return Ok(TranscodeResult { output_path: path, transcoded: false, reason });
+    }
+
+    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
+    let output_dir = CacheManager::new(&data_path).dir_for(CacheCategory::Transcode)
+        .map_err(|e| format!("无法创建转码缓存目录: {}", e))?;
+
+    tracing::info!("[Transcode] 开始转码: {}（{}）", path, reason.as_deref().unwrap_or("手动转码"));
+    let source_path = path.clone();
+    let output = tokio::task::spawn_blocking(move || {
+        let mut last = -1;
+        transcode_to_mp4(&source_path, info.duration_secs, &output_dir, |progress| {
+            let progress = (progress * 100.0) as i32;
+            if progress != last {
+                last = progress;
+                let event = TranscodeProgressEvent { source_path: source_path.clone(), progress };
+                if let Err(e) = emit_event(&app, &event) {
+                    tracing::warn!("[Transcode] 发送进度事件失败: {}", e);
+                }
+            }
+        })
+    })
+    .await
+    .map_err(|e| format!("转码任务异常: {}", e))??;
+
+    tracing::info!("[Transcode] 转码完成: {}", output.display());
+    Ok(TranscodeResult {
+        output_path: output.to_string_lossy().to_string(),
+        transcoded: true,
+        reason,
+    })
+}
+
+/// Known upload limits of every platform
+/// 获取各平台的视频时长 / 大小限制
+#[tauri::command]
+pub fn get_platform_limits() -> Vec<PlatformLimits> {
+    [PlatformType::Douyin, PlatformType::Kuaishou, PlatformType::Xiaohongshu, PlatformType::Bilibili]
+        .iter()
+        .map(|p| p.limits())
+        .collect()
+}
+
+/// Request body of [`create_publication_task`]
+/// 创建任务的请求参数
+///
+/// 推荐传入 `accounts`；旧版 `account_ids` + `platforms` + 二维 `hashtags` 仍然兼容。
+/// 传入 `group_id` 时分组内所有账号都加入任务（已在 `accounts` 中的账号以显式传入的为准）；
+/// `poi` 为 `search_poi` 返回的位置，发布时作为作品的位置锚点；`music` 为 `search_music` 选中的配乐；
+/// `mentions` 为要 @ 的用户昵称，发布时追加到描述末尾
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CreatePublicationTaskRequest {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub video_path: String,
+    #[serde(default)]
+    pub cover_path: Option<String>,
+    #[serde(default)]
+    pub accounts: Option<Vec<TaskAccountInput>>,
+    #[serde(default)]
+    pub account_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub platforms: Option<Vec<String>>,
+    #[serde(default)]
+    pub hashtags: Option<TaskHashtagsInput>,
+    #[serde(default)]
+    pub hashtag_set_id: Option<String>,
+    #[serde(default)]
+    pub vertical_video_path: Option<String>,
+    #[serde(default)]
+    pub horizontal_video_path: Option<String>,
+    #[serde(default)]
+    pub group_id: Option<String>,
+    #[serde(default)]
+    pub watermark_enabled: Option<bool>,
+    #[serde(default)]
+    pub visibility_type: Option<i32>,
+    #[serde(default)]
+    pub download_allowed: Option<bool>,
+    #[serde(default)]
+    pub poi: Option<PoiInfo>,
+    #[serde(default)]
+    pub music: Option<MusicInfo>,
+    #[serde(default)]
+    pub mentions: Option<Vec<String>>,
+}
+
 /// Create a publication task with account details (main + sub tables)
 /// 创建作品发布任务（主表+子表）
 #[tauri::command]
 pub fn create_publication_task(
     app: AppHandle,
-    title: &str,
-    description: &str,
-    video_path: &str,
-    cover_path: Option<&str>,
-    account_ids: Vec<String>,
-    platforms: Vec<String>,
-    hashtags: Vec<Vec<String>>,
+    request: CreatePublicationTaskRequest,
 ) -> Result<PublicationTaskWithAccounts, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
+    let CreatePublicationTaskRequest {
+        title,
+        description,
+        video_path,
+        cover_path,
+        accounts,
+        account_ids,
+        platforms,
+        hashtags,
+        hashtag_set_id,
+        vertical_video_path,
+        horizontal_video_path,
+        group_id,
+        watermark_enabled,
+        visibility_type,
+        download_allowed,
+        poi,
+        music,
+        mentions,
+    } = request;
+    let visibility_type = visibility_type.unwrap_or(VISIBILITY_PUBLIC);
+    if ![VISIBILITY_PUBLIC, VISIBILITY_PRIVATE, VISIBILITY_FRIENDS].contains(&visibility_type) {
+        return Err(format!("无效的可见范围: {}", visibility_type));
+    }
+
+    let poi = poi.filter(|p| !p.poi_id.is_empty());
+    let music = music.filter(|m| !m.music_id.is_empty());
+
+    let vertical_video_path = vertical_video_path.filter(|p| !p.is_empty());
+    let horizontal_video_path = horizontal_video_path.filter(|p| !p.is_empty());
+
+    // 只上传了竖版 / 横版时，以其作为主视频
+    let video_path = match video_path.as_str() {
+        "" => vertical_video_path.clone().or_else(|| horizontal_video_path.clone())
+            .ok_or_else(|| "请选择视频文件".to_string())?,
+        _ => video_path,
+    };
+
+    let mut accounts = match accounts {
+        Some(accounts) => accounts,
+        None => legacy_task_accounts(
+            account_ids.unwrap_or_default(),
+            platforms.unwrap_or_default(),
+            hashtags.as_ref(),
+        )?,
+    };
+
+    let db_manager = shared_db_manager(&app);
+
+    if let Some(group_id) = group_id.as_deref().filter(|id| !id.is_empty()) {
+        db_manager.get_account_group(group_id).map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("分组不存在: {}", group_id))?;
+        for account in db_manager.get_accounts_by_group(group_id).map_err(|e| e.to_string())? {
+            if !accounts.iter().any(|a| a.account_id == account.id) {
+                accounts.push(TaskAccountInput {
+                    account_id: account.id,
+                    platform: account.platform,
+                    overrides: TaskAccountOverrides::default(),
+                });
+            }
+        }
+    }
+
+    if accounts.is_empty() {
+        return Err("请至少选择一个账号".to_string());
+    }
+    for (i, account) in accounts.iter().enumerate() {
+        if accounts[..i].iter().any(|a| a.account_id == account.account_id) {
+            return Err(format!("账号重复: {}", account.account_id));
+        }
+    }
 
     let task_id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
-    // Create main task (flatten hashtags from Vec<Vec<String>> to Vec<String>)
-    let hashtags: Vec<String> = hashtags.into_iter().flatten().collect();
+    // Task hashtags, de-duplicated; each account's extra hashtags are stored as its own override
+    let mut hashtags: Vec<String> = match &hashtags {
+        Some(TaskHashtagsInput::Task(tags)) => merge_hashtags([tags]),
+        _ => Vec::new(),
+    };
+
+    // 使用话题库时，以话题库的默认话题作为任务话题
+    let hashtag_set = match hashtag_set_id.as_deref().filter(|id| !id.is_empty()) {
+        Some(set_id) => {
+            let set = db_manager.get_hashtag_set(set_id).map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("话题库不存在: {}", set_id))?;
+            hashtags = set.tags.clone();
+            Some(set)
+        }
+        None => None,
+    };
+    let hashtag_set_id = hashtag_set.as_ref().map(|set| set.id.clone());
     let task = PublicationTask {
         id: task_id.clone(),
-        title: title.to_string(),
-        description: Some(description.to_string()),
-        video_path: video_path.to_string(),
-        cover_path: cover_path.map(|s| s.to_string()),
-        hashtags,
+        title,
+        description: Some(description),
+        video_path: video_path.clone(),
+        cover_path,
+        hashtags: hashtags.clone(),
         status: PublicationStatus::Draft,
         created_at: now.clone(),
         published_at: None,
+        hashtag_set_id,
+        vertical_video_path,
+        horizontal_video_path,
+        watermark_enabled: watermark_enabled.unwrap_or(false),
+        visibility_type,
+        download_allowed: download_allowed.unwrap_or(false),
+        poi_id: poi.as_ref().map(|p| p.poi_id.clone()),
+        poi_name: poi.map(|p| p.poi_name),
+        music_id: music.as_ref().map(|m| m.music_id.clone()),
+        music_end_time: music.map(|m| m.music_end_time).filter(|t| !t.is_empty()),
+        mentions: normalize_mentions(mentions.unwrap_or_default()),
     };
 
-    // Create account details (only store account info, title/description/hashtags are in main table)
+    // Create account details (account info plus optional per-account overrides)
     // 冗余 account_name 字段便于直接显示
     let mut account_details = Vec::new();
-    for (i, account_id) in account_ids.iter().enumerate() {
-        let platform_str = platforms.get(i).map(|s| s.as_str()).unwrap_or("douyin");
-        let platform_type = match platform_str {
-            "douyin" => PlatformType::Douyin,
-            "xiaohongshu" => PlatformType::Xiaohongshu,
-            "kuaishou" => PlatformType::Kuaishou,
-            "bilibili" => PlatformType::Bilibili,
-            _ => PlatformType::Douyin,
-        };
-
+    for TaskAccountInput { account_id, platform, overrides } in accounts {
         // Get account name from database for redundancy
-        let account_name = match db_manager.get_account(account_id).map_err(|e| e.to_string())? {
+        let account_name = match db_manager.get_account(&account_id).map_err(|e| e.to_string())? {
+            Some(acc) if acc.platform != platform => {
+                return Err(format!("账号 {} 属于{}，不能发布到{}", acc.nickname, acc.platform.display_name(), platform.display_name()));
+            }
             Some(acc) => acc.nickname.clone(),
-            None => format!("账号{}", &account_id[..8]),
+            None => format!("账号{}", account_id.chars().take(8).collect::<String>()),
+        };
+
+        // 额外话题与该账号在任务中本应使用的话题合并
+        let hashtags_override = if overrides.hashtags.is_empty() {
+            None
+        } else {
+            let base = hashtag_set.as_ref()
+                .map(|set| set.tags_for(&platform))
+                .unwrap_or_else(|| hashtags.clone());
+            Some(merge_hashtags([&base, &overrides.hashtags]))
         };
+        let non_empty = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
 
         let detail = PublicationAccountDetail {
             id: uuid::Uuid::new_v4().to_string(),
             publication_task_id: task_id.clone(),
-            account_id: account_id.clone(),
+            account_id,
             account_name,  // 冗余的账号名称
-            platform: platform_type,
+            platform,
             status: PublicationStatus::Draft,
             created_at: now.clone(),
             published_at: None,
@@ -284,6 +851,13 @@ pub fn create_publication_task(
             stats: PublicationStats::default(),
             message: None,
             item_id: None,
+            strategy_version: None,
+            title_override: non_empty(overrides.title),
+            description_override: non_empty(overrides.description),
+            hashtags_override,
+            attempts: 0,
+            error_code: None,
+            variant: None,
         };
 
         account_details.push(detail);
@@ -295,1219 +869,4735 @@ pub fn create_publication_task(
 
     Ok(PublicationTaskWithAccounts {
         id: task_id,
-        title: title.to_string(),
-        description: description.to_string(),
-        video_path: video_path.to_string(),
-        cover_path: cover_path.map(|s| s.to_string()).unwrap_or_default(),
+        title: task.title,
+        description: task.description.unwrap_or_default(),
+        video_path,
+        cover_path: task.cover_path.unwrap_or_default(),
         hashtags: task.hashtags,
         status: PublicationStatus::Draft,
         created_at: now,
         published_at: String::new(),
+        hashtag_set_id: task.hashtag_set_id,
+        vertical_video_path: task.vertical_video_path,
+        horizontal_video_path: task.horizontal_video_path,
+        watermark_enabled: task.watermark_enabled,
+        visibility_type: task.visibility_type,
+        download_allowed: task.download_allowed,
+        poi_id: task.poi_id,
+        poi_name: task.poi_name,
+        music_id: task.music_id,
+        music_end_time: task.music_end_time,
+        mentions: task.mentions,
         accounts: account_details,
     })
 }
 
-/// Delete a publication task and all its account details
-/// 删除作品任务及其所有账号详情
-#[tauri::command]
-pub fn delete_publication_task(app: AppHandle, task_id: &str) -> Result<bool, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-    db_manager.delete_publication_task(task_id)
-        .map_err(|e| e.to_string())
-}
+/// 支持的视频文件扩展名
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "avi", "mkv", "webm", "3gp", "m4v", "wmv"];
 
-/// Get a publication task with all account details
-/// 获取作品任务及其所有账号详情
+/// Search the platform's locations (POI) to attach to a publication task
+/// 搜索平台位置（POI），用于给发布任务设置定位（目前支持抖音）
 #[tauri::command]
-pub fn get_publication_task_with_accounts(app: AppHandle, task_id: &str) -> Result<Option<PublicationTaskWithAccounts>, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-    db_manager.get_publication_task_with_accounts(task_id)
-        .map_err(|e| e.to_string())
+pub async fn search_poi(app: AppHandle, keyword: &str, account_id: &str) -> Result<Vec<PoiInfo>, String> {
+    let _timer = CommandTimer::start("search_poi");
+
+    let keyword = keyword.trim();
+    if keyword.is_empty() {
+        return Err("请输入位置关键词".to_string());
+    }
+
+    let db_manager = shared_db_manager(&app);
+    let account = db_manager.get_account(account_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("账号不存在: {}", account_id))?;
+
+    match account.platform {
+        PlatformType::Douyin => DouyinPlatform::with_storage((*db_manager).clone())
+            .search_poi(account_id, keyword).await
+            .map_err(|e| {
+                tracing::error!("[Publish] 搜索位置失败: keyword={}, error={:?}", keyword, e);
+                e.to_string()
+            }),
+        _ => Err(format!("{}暂不支持位置搜索", account.platform.display_name())),
+    }
 }
 
-/// Get a single publication account detail by ID
-/// 根据ID获取单个作品账号发布详情
+/// Search the platform's music library for a soundtrack
+/// 搜索平台音乐，用于给发布任务选择配乐（目前支持抖音）
 #[tauri::command]
-pub fn get_publication_account_detail(app: AppHandle, detail_id: &str) -> Result<Option<PublicationAccountDetail>, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-    db_manager.get_publication_account_detail(detail_id)
-        .map_err(|e| e.to_string())
+pub async fn search_music(app: AppHandle, keyword: &str, account_id: &str) -> Result<Vec<MusicSearchItem>, String> {
+    let _timer = CommandTimer::start("search_music");
+
+    let keyword = keyword.trim();
+    if keyword.is_empty() {
+        return Err("请输入音乐关键词".to_string());
+    }
+
+    let db_manager = shared_db_manager(&app);
+    let account = db_manager.get_account(account_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("账号不存在: {}", account_id))?;
+
+    match account.platform {
+        PlatformType::Douyin => DouyinPlatform::with_storage((*db_manager).clone())
+            .search_music(account_id, keyword).await
+            .map_err(|e| {
+                tracing::error!("[Publish] 搜索音乐失败: keyword={}, error={:?}", keyword, e);
+                e.to_string()
+            }),
+        _ => Err(format!("{}暂不支持音乐搜索", account.platform.display_name())),
+    }
 }
 
-/// Result of publishing a task
-/// 发布任务结果
-#[derive(Serialize, Clone)]
-pub struct PublishTaskResult {
-    pub success: bool,
-    pub detail_id: String,
-    pub publish_url: Option<String>,
-    pub error: Option<String>,
+/// Result of creating tasks from a folder of videos
+/// 批量创建任务结果
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkPublicationTasksResult {
+    pub created: Vec<PublicationTaskWithAccounts>,
+    pub errors: Vec<String>,
 }
 
-/// Result of publishing progress (for frontend updates)
-/// 发布进度结果（用于前端更新）
-#[derive(Serialize, Clone)]
-pub struct PublishProgressResult {
-    pub total_accounts: usize,
-    pub completed_accounts: usize,
-    pub success_count: usize,
-    pub failed_count: usize,
-    pub results: Vec<PublishTaskResult>,
+/// 由文件名生成任务标题（去掉扩展名，下划线视为空格）
+fn title_from_video_file(path: &std::path::Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().replace('_', " ").trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| "未命名视频".to_string())
 }
 
-/// Publish a publication task to all accounts (concurrent/async)
-/// 发布作品到所有账号（异步并发）
+/// Create one publication task per video found in a directory
+/// 扫描目录中的视频文件，为每个视频创建一个发布任务（标题取自文件名，账号列表共用）
+///
+/// 配置了文件名解析规则时，匹配的文件按规则提取标题、话题和日期：
+/// 话题与共用话题合并（使用话题库时以话题库为准），日期记录为任务备注
 #[tauri::command]
-pub async fn publish_publication_task(
-    window: tauri::Window,
-    task_id: &str,
-    _title: &str,
-    _description: &str,
-    _video_path: &str,
-    _hashtags: Vec<String>,
-) -> Result<PublishProgressResult, String> {
-    tracing::info!("[Publish] Starting concurrent publish for task: {}", task_id);
+pub fn create_bulk_publication_tasks(
+    app: AppHandle,
+    directory: &str,
+    description: Option<&str>,
+    accounts: Vec<TaskAccountInput>,
+    hashtags: Option<Vec<String>>,
+    hashtag_set_id: Option<&str>,
+    group_id: Option<&str>,
+) -> Result<BulkPublicationTasksResult, String> {
+    let entries = std::fs::read_dir(directory)
+        .map_err(|e| format!("无法读取目录 {}: {}", directory, e))?;
+
+    let mut videos: Vec<std::path::PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+                .is_some_and(|ext| VIDEO_EXTENSIONS.contains(&ext.as_str()))
+        })
+        .collect();
+    videos.sort();
 
-    // 使用 app_handle 发送进度事件到所有窗口
-    let app_handle = window.app_handle().clone();
-    let data_path = app_handle.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = Arc::new(DatabaseManager::new(data_path.clone()));
+    if videos.is_empty() {
+        return Err(format!("目录中没有视频文件: {}", directory));
+    }
+
+    let db_manager = shared_db_manager(&app);
+    let filename_regex = match db_manager.get_filename_pattern().map_err(|e| e.to_string())? {
+        Some(pattern) => Some(compile_filename_pattern(&pattern)?),
+        None => None,
+    };
+
+    let mut result = BulkPublicationTasksResult { created: Vec::new(), errors: Vec::new() };
+    for video in videos {
+        let metadata = filename_regex.as_ref()
+            .zip(video.file_stem())
+            .and_then(|(regex, stem)| parse_filename_metadata(regex, &stem.to_string_lossy()))
+            .unwrap_or_default();
+        let title = metadata.title.clone().unwrap_or_else(|| title_from_video_file(&video));
+        let task_hashtags = merge_hashtags([&hashtags.clone().unwrap_or_default(), &metadata.tags]);
+        let video_path = video.to_string_lossy().to_string();
+        let request = CreatePublicationTaskRequest {
+            title,
+            description: description.unwrap_or_default().to_string(),
+            video_path: video_path.clone(),
+            accounts: Some(accounts.clone()),
+            hashtags: Some(TaskHashtagsInput::Task(task_hashtags)),
+            hashtag_set_id: hashtag_set_id.map(|id| id.to_string()),
+            group_id: group_id.map(|id| id.to_string()),
+            ..Default::default()
+        };
+        match create_publication_task(app.clone(), request) {
+            Ok(task) => {
+                if let Some(date) = &metadata.date {
+                    let note = TaskNote {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        task_id: task.id.clone(),
+                        author: String::new(),
+                        content: format!("文件名日期: {}", date),
+                        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                    };
+                    if let Err(e) = db_manager.add_task_note(&note) {
+                        tracing::warn!("[BulkCreate] Failed to save filename date note: {}", e);
+                    }
+                }
+                result.created.push(task)
+            }
+            Err(e) => result.errors.push(format!("{}: {}", video_path, e)),
+        }
+    }
+
+    tracing::info!("[BulkCreate] {} tasks created from {}, {} failed", result.created.len(), directory, result.errors.len());
+    Ok(result)
+}
+
+/// Get the filename parsing regex used by batch import
+/// 获取批量导入的文件名解析规则
+#[tauri::command]
+pub fn get_filename_parse_rule(app: AppHandle) -> Result<Option<String>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_filename_pattern()
+        .map_err(|e| e.to_string())
+}
+
+/// Set the filename parsing regex (named groups: title / date / tags; empty disables)
+/// 设置文件名解析规则（命名分组 title / date / tags，为空则不解析）
+#[tauri::command]
+pub fn set_filename_parse_rule(app: AppHandle, pattern: Option<&str>) -> Result<Option<String>, String> {
+    let pattern = pattern.map(str::trim).filter(|p| !p.is_empty());
+    if let Some(pattern) = pattern {
+        compile_filename_pattern(pattern)?;
+    }
+
+    let db_manager = shared_db_manager(&app);
+    db_manager.set_filename_pattern(pattern.unwrap_or_default()).map_err(|e| e.to_string())?;
+    Ok(pattern.map(|p| p.to_string()))
+}
+
+/// Preview what a parsing rule extracts from a filename
+/// 预览解析规则对某个文件名的解析结果（不传 pattern 时使用已保存的规则），不匹配返回 None
+#[tauri::command]
+pub fn preview_filename_metadata(app: AppHandle, file_name: &str, pattern: Option<&str>) -> Result<Option<FilenameMetadata>, String> {
+    let pattern = match pattern.filter(|p| !p.trim().is_empty()) {
+        Some(pattern) => pattern.to_string(),
+        None => match shared_db_manager(&app).get_filename_pattern().map_err(|e| e.to_string())? {
+            Some(pattern) => pattern,
+            None => return Ok(None),
+        },
+    };
+
+    let regex = compile_filename_pattern(&pattern)?;
+    let stem = std::path::Path::new(file_name).file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(parse_filename_metadata(&regex, &stem))
+}
+
+/// Move a publication task to the trash
+/// 删除作品任务（移入回收站，可恢复）
+#[tauri::command]
+pub fn delete_publication_task(app: AppHandle, task_id: &str) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.delete_publication_task(task_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Restore a publication task from the trash
+/// 从回收站恢复作品任务（删除时取消的定时发布需重新设置）
+#[tauri::command]
+pub fn restore_task(app: AppHandle, task_id: &str) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.restore_publication_task(task_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Result of replacing a task's video
+/// 替换任务视频的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplaceTaskVideoResult {
+    pub task_id: String,
+    pub video_path: String,
+    /// 新视频的元数据
+    pub info: VideoInfo,
+    /// 重置为草稿的账号数（使用主视频且发布失败 / 跳过的账号）
+    pub reset_accounts: usize,
+    /// 清除的上传断点数
+    pub cleared_upload_sessions: usize,
+    /// 新视频超出平台限制的提示（不阻止替换）
+    pub warnings: Vec<String>,
+}
+
+/// Replace a task's main video file (e.g. an editor shipped a fixed cut) without recreating the task
+/// 替换任务的主视频：重新读取元数据并校验平台限制、清除旧文件的上传断点，
+/// 使用主视频且发布失败 / 跳过的账号重置为草稿；已发布的账号保持不变
+#[tauri::command]
+pub fn replace_task_video(app: AppHandle, task_id: &str, new_path: &str) -> Result<ReplaceTaskVideoResult, String> {
+    if is_publish_running(task_id) {
+        return Err("任务正在发布，请先取消或等待发布结束".to_string());
+    }
+
+    let db_manager = shared_db_manager(&app);
+    let task = db_manager.get_publication_task(task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+    let details = db_manager.get_publication_task_with_accounts(task_id)
+        .map_err(|e| e.to_string())?
+        .map(|t| t.accounts)
+        .unwrap_or_default();
+
+    let info = probe_video(new_path)?;
+
+    // 有对应竖版 / 横版视频的平台不受主视频替换影响
+    let affected: Vec<&PublicationAccountDetail> = details.iter()
+        .filter(|d| task.video_path_for(&d.platform) == task.video_path)
+        .collect();
+    let warnings = affected.iter()
+        .filter_map(|d| {
+            let violations = check_video_limits(&info, &d.platform.limits());
+            (!violations.is_empty())
+                .then(|| format!("{}（{}）：{}", d.account_name, d.platform.display_name(), violations.join("，")))
+        })
+        .collect();
+
+    // 旧文件（以及在同一路径被覆盖的文件）的上传会话不能再续传
+    let mut cleared_upload_sessions = clear_upload_checkpoints(&task.video_path);
+    if task.video_path != new_path {
+        cleared_upload_sessions += clear_upload_checkpoints(new_path);
+    }
+
+    let detail_ids: Vec<String> = affected.iter().map(|d| d.id.clone()).collect();
+    let reset_accounts = db_manager.replace_task_video(task_id, new_path, &detail_ids)
+        .map_err(|e| e.to_string())?;
+    db_manager.update_task_status_from_accounts(task_id).map_err(|e| e.to_string())?;
+
+    tracing::info!("[Task] 任务 {} 视频已替换: {} -> {}，重置 {} 个账号，清除 {} 个上传断点",
+        task_id, task.video_path, new_path, reset_accounts, cleared_upload_sessions);
+    Ok(ReplaceTaskVideoResult {
+        task_id: task_id.to_string(),
+        video_path: new_path.to_string(),
+        info,
+        reset_accounts,
+        cleared_upload_sessions,
+        warnings,
+    })
+}
+
+/// Get a publication task with all account details
+/// 获取作品任务及其所有账号详情
+#[tauri::command]
+pub fn get_publication_task_with_accounts(app: AppHandle, task_id: &str) -> Result<Option<PublicationTaskWithAccounts>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_publication_task_with_accounts(task_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get a single publication account detail by ID
+/// 根据ID获取单个作品账号发布详情
+#[tauri::command]
+pub fn get_publication_account_detail(app: AppHandle, detail_id: &str) -> Result<Option<PublicationAccountDetail>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_publication_account_detail(detail_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Result of publishing a task
+/// 发布任务结果
+#[derive(Serialize, Clone)]
+pub struct PublishTaskResult {
+    pub success: bool,
+    pub detail_id: String,
+    pub publish_url: Option<String>,
+    pub error: Option<String>,
+    pub error_code: Option<PublishErrorCode>,  // 失败时的结构化错误码
+    pub skipped: bool,  // 账号维护中被跳过
+    pub deferred_until: Option<String>,  // 受发布节奏限制推迟到的时间（到时由调度器自动发布）
+}
+
+/// Result of publishing progress (for frontend updates)
+/// 发布进度结果（用于前端更新）
+#[derive(Serialize, Clone)]
+pub struct PublishProgressResult {
+    pub total_accounts: usize,
+    pub completed_accounts: usize,
+    pub success_count: usize,
+    pub failed_count: usize,
+    pub skipped_count: usize,
+    pub deferred_count: usize,
+    pub results: Vec<PublishTaskResult>,
+}
+
+/// 根据平台和作品ID生成作品链接
+pub(crate) fn publish_url_for(platform: &PlatformType, item_id: &str) -> String {
+    match platform {
+        PlatformType::Xiaohongshu => format!("https://www.xiaohongshu.com/explore/{}", item_id),
+        PlatformType::Kuaishou => format!("https://www.kuaishou.com/short-video/{}", item_id),
+        PlatformType::Bilibili => format!("https://www.bilibili.com/video/{}", item_id),
+        PlatformType::Douyin => format!("https://v.douyin.com/{}", item_id),
+    }
+}
+
+/// 账号处于维护状态时将其标记为 Skipped，返回跳过结果
+fn skip_on_hold_account(db_manager: &DatabaseManager, detail_id: &str, account_id: &str) -> Option<PublishTaskResult> {
+    match db_manager.get_account(account_id) {
+        Ok(Some(account)) if account.on_hold => {}
+        _ => return None,
+    }
+
+    tracing::info!("[Publish] Account {} is on hold, skipping detail {}", account_id, detail_id);
+    Some(skip_detail(db_manager, detail_id, "账号维护中，已跳过".to_string()))
+}
+
+/// 按账号所在分组的水印配置生成带水印的视频（ffmpeg 重新编码，结果缓存在 transcode 目录）；
+/// 账号未分组或分组未配置水印时返回原视频
+async fn watermarked_video_path(
+    app_handle: &AppHandle,
+    db_manager: &DatabaseManager,
+    account_id: &str,
+    video_path: String,
+) -> Result<String, String> {
+    let group_id = db_manager.get_account(account_id).map_err(|e| e.to_string())?
+        .and_then(|account| account.group_id);
+    let config = match group_id {
+        Some(group_id) => db_manager.get_group_watermark(&group_id).map_err(|e| e.to_string())?,
+        None => None,
+    };
+    let Some(config) = config else {
+        tracing::info!("[Publish] No watermark configured for account {}, using original video", account_id);
+        return Ok(video_path);
+    };
+
+    let data_path = app_handle.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
+    let output_dir = CacheManager::new(&data_path).dir_for(CacheCategory::Transcode)
+        .map_err(|e| format!("无法创建转码缓存目录: {}", e))?;
+    let output = tokio::task::spawn_blocking(move || apply_watermark(&video_path, &config, &output_dir))
+        .await
+        .map_err(|e| format!("水印任务异常: {}", e))??;
+
+    Ok(output.to_string_lossy().to_string())
+}
+
+/// 为发布详情生成去重变体并记录变体参数，返回变体视频路径
+async fn video_variant_path(
+    app_handle: &AppHandle,
+    db_manager: &DatabaseManager,
+    detail_id: &str,
+    video_path: String,
+) -> Result<String, String> {
+    let data_path = app_handle.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
+    let output_dir = CacheManager::new(&data_path).dir_for(CacheCategory::Transcode)
+        .map_err(|e| format!("无法创建转码缓存目录: {}", e))?;
+    let variant_key = detail_id.to_string();
+    let (output, variant) = tokio::task::spawn_blocking(move || generate_video_variant(&video_path, &variant_key, &output_dir))
+        .await
+        .map_err(|e| format!("变体任务异常: {}", e))??;
+
+    tracing::info!("[Publish] Using variant for {}: trim={}ms, video={}k, audio={}k, md5={}",
+        detail_id, variant.trim_ms, variant.video_kbps, variant.audio_kbps, variant.md5);
+    if let Err(e) = db_manager.set_publication_account_variant(detail_id, &variant) {
+        tracing::warn!("[Publish] Failed to record variant for {}: {}", detail_id, e);
+    }
+
+    Ok(output.to_string_lossy().to_string())
+}
+
+/// 记录失败账号的错误码（需在更新状态之后调用）
+pub(crate) fn record_error_code(db_manager: &DatabaseManager, detail_id: &str, error_code: Option<PublishErrorCode>) {
+    let Some(code) = error_code else { return };
+    if let Err(e) = db_manager.set_publication_account_error_code(detail_id, code) {
+        tracing::warn!("[Publish] Failed to record error code for {}: {}", detail_id, e);
+    }
+}
+
+/// 检查账号的发布节奏限制；超出限制时发布详情保持草稿状态，返回推迟结果
+fn defer_on_pacing(
+    db_manager: &DatabaseManager,
+    pacing: &PublishPacingRules,
+    detail_id: &str,
+    account_id: &str,
+    platform: &PlatformType,
+) -> Option<PublishTaskResult> {
+    let rule = pacing.rule_for(platform);
+    if rule == PacingRule::default() {
+        return None;
+    }
+
+    let now = chrono::Local::now().naive_local();
+    let since = (now - chrono::Duration::hours(24)).format(SCHEDULE_TIME_FORMAT).to_string();
+    let recent: Vec<chrono::NaiveDateTime> = match db_manager.get_account_publish_times(account_id, &since) {
+        Ok(times) => times.iter().filter_map(|t| parse_send_at(t).ok()).collect(),
+        Err(e) => {
+            tracing::warn!("[Publish] Failed to load publish history of {}, pacing not applied: {}", account_id, e);
+            return None;
+        }
+    };
+    let delay = check_pacing(&rule, &recent, now)?;
+    let until = delay.until_string();
+    let message = format!("发布节奏限制：{}，将于 {} 自动发布", delay.reason, until);
+    tracing::info!("[Publish] Account {} deferred until {}: {}", account_id, until, delay.reason);
+
+    if let Err(e) = db_manager.update_publication_account_status(
+        detail_id,
+        PublicationStatus::Draft,
+        None,
+        Some(message.clone()),
+        None,
+    ) {
+        tracing::error!("[Publish] Failed to update status for {}: {}", detail_id, e);
+    }
+
+    Some(PublishTaskResult {
+        success: false,
+        detail_id: detail_id.to_string(),
+        publish_url: None,
+        error: Some(message),
+        error_code: None,
+        skipped: false,
+        deferred_until: Some(until),
+    })
+}
+
+/// 发布结束后发送系统通知（没有账号实际发布时不通知）
+fn notify_publish_finished(db_manager: &DatabaseManager, task_title: &str, mode: PublishMode, success_count: usize, failed_count: usize) {
+    if success_count + failed_count == 0 {
+        return;
+    }
+
+    let heading = match (mode, failed_count) {
+        (PublishMode::Draft, 0) => "草稿保存完成",
+        (PublishMode::Draft, _) => "草稿保存失败",
+        (PublishMode::Publish, 0) => "发布完成",
+        (PublishMode::Publish, _) if success_count == 0 => "发布失败",
+        (PublishMode::Publish, _) => "发布部分失败",
+    };
+    notify(db_manager, NotificationKind::Publish, heading,
+        &format!("{}：成功 {}，失败 {}", task_title, success_count, failed_count));
+}
+
+/// 为发布结束的账号发送回调通知（跳过和推迟的账号不通知）
+fn notify_publish_webhooks(db_manager: &DatabaseManager, results: &[PublishTaskResult]) {
+    for result in results.iter().filter(|r| !r.skipped && r.deferred_until.is_none() && !r.detail_id.is_empty()) {
+        notify_publish_result(db_manager, &result.detail_id);
+    }
+}
+
+/// 发布结束后重新评估账号风险（跳过和推迟的账号不评估）
+fn check_publish_risks(db_manager: &DatabaseManager, results: &[PublishTaskResult]) {
+    for result in results.iter().filter(|r| !r.skipped && r.deferred_until.is_none() && !r.detail_id.is_empty()) {
+        check_publish_risk(db_manager, &result.detail_id);
+    }
+}
+
+/// 为推迟发布的账号创建定时发布（取最早的推迟时间，到时重新执行任务；
+/// 已有不晚于该时间的待执行定时任务时不重复创建）
+fn schedule_deferred_publish(db_manager: &DatabaseManager, task_id: &str, results: &[PublishTaskResult]) {
+    let Some(send_at) = results.iter().filter_map(|r| r.deferred_until.clone()).min() else { return };
+
+    let already_scheduled = db_manager.get_scheduled_jobs(Some(task_id))
+        .map(|jobs| jobs.iter().any(|job| job.status == ScheduledJobStatus::Pending && job.send_at <= send_at))
+        .unwrap_or(false);
+    if already_scheduled {
+        return;
+    }
+
+    let now = chrono::Local::now().format(SCHEDULE_TIME_FORMAT).to_string();
+    let job = ScheduledJob {
+        id: uuid::Uuid::new_v4().to_string(),
+        task_id: task_id.to_string(),
+        send_at,
+        status: ScheduledJobStatus::Pending,
+        message: None,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    match db_manager.save_scheduled_job(&job) {
+        Ok(()) => tracing::info!("[Scheduler] 已为推迟的账号创建定时发布: task={}, send_at={}", task_id, job.send_at),
+        Err(e) => tracing::error!("[Scheduler] 创建推迟发布的定时任务失败: {}", e),
+    }
+}
+
+/// 将发布详情标记为 Skipped，返回跳过结果
+fn skip_detail(db_manager: &DatabaseManager, detail_id: &str, message: String) -> PublishTaskResult {
+    if let Err(e) = db_manager.update_publication_account_status(
+        detail_id,
+        PublicationStatus::Skipped,
+        None,
+        Some(message.clone()),
+        None,
+    ) {
+        tracing::error!("[Publish] Failed to update status for {}: {}", detail_id, e);
+    }
+
+    PublishTaskResult {
+        success: false,
+        detail_id: detail_id.to_string(),
+        publish_url: None,
+        error: Some(message),
+        error_code: None,
+        skipped: true,
+        deferred_until: None,
+    }
+}
+
+/// 将发布前处理失败的发布详情标记为 Failed，返回失败结果
+fn fail_detail(db_manager: &DatabaseManager, detail_id: &str, message: String) -> PublishTaskResult {
+    tracing::error!("[Publish] Preparing publish failed for {}: {}", detail_id, message);
+    if let Err(e) = db_manager.update_publication_account_status(
+        detail_id,
+        PublicationStatus::Failed,
+        None,
+        Some(message.clone()),
+        None,
+    ) {
+        tracing::error!("[Publish] Failed to update status for {}: {}", detail_id, e);
+    }
+
+    PublishTaskResult {
+        success: false,
+        detail_id: detail_id.to_string(),
+        publish_url: None,
+        error: Some(message),
+        error_code: None,
+        skipped: false,
+        deferred_until: None,
+    }
+}
+
+/// 将被用户取消的发布详情标记为 Failed，返回取消结果
+fn cancel_detail(db_manager: &DatabaseManager, detail_id: &str) -> PublishTaskResult {
+    if let Err(e) = db_manager.update_publication_account_status(
+        detail_id,
+        PublicationStatus::Failed,
+        None,
+        Some(PUBLISH_CANCELLED_MESSAGE.to_string()),
+        None,
+    ) {
+        tracing::error!("[Publish] Failed to update status for {}: {}", detail_id, e);
+    }
+
+    PublishTaskResult {
+        success: false,
+        detail_id: detail_id.to_string(),
+        publish_url: None,
+        error: Some(PUBLISH_CANCELLED_MESSAGE.to_string()),
+        error_code: None,
+        skipped: false,
+        deferred_until: None,
+    }
+}
+
+/// Cancel an in-flight publish of a task
+/// 取消正在执行的发布：排队中的账号立即中止，已开始的账号在当前步骤（或当前分片）结束后停止，
+/// 均标记为失败；任务没有在发布时返回 false
+#[tauri::command]
+pub fn cancel_publication_task(task_id: &str) -> Result<bool, String> {
+    match cancel_publish(task_id) {
+        Some(aborted) => {
+            tracing::info!("[Publish] 已取消任务 {} 的发布，中止 {} 个排队中的账号", task_id, aborted);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// List the publishes paused for a captcha / security verification
+/// 正在等待验证的账号（界面重新打开时恢复验证提示）
+#[tauri::command]
+pub fn get_pending_publish_verifications() -> Vec<PublishCaptchaEvent> {
+    list_pending_verifications()
+}
+
+/// Open the verification page in the account's browser profile so the user can pass the captcha
+/// 在账号的浏览器资料目录中打开有界面的浏览器（保留登录会话），用户在其中完成验证；
+/// 返回授权会话，可用 `check_browser_auth_status` 查询，浏览器登录完成后同时刷新账号凭证
+#[tauri::command]
+pub fn open_publish_verification(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    detail_id: &str,
+) -> Result<BrowserAuthStatusResult, String> {
+    if cfg!(mobile) {
+        return Err("移动端不支持浏览器验证".to_string());
+    }
+    let pending = pending_verification(detail_id).ok_or_else(|| "该账号没有在等待验证".to_string())?;
+
+    let session = state.browser_sessions.create(&pending.platform, Some(&pending.account_id))?;
+    tracing::info!("[Verification] 打开验证浏览器: detail_id={}, account_id={}, session_id={}", detail_id, pending.account_id, session.session_id);
+
+    tauri::async_runtime::spawn(run_browser_auth_session(
+        app,
+        session.session_id.clone(),
+        pending.platform,
+        Some(pending.account_id),
+        BrowserFingerprint::default(),
+        false,
+    ));
+
+    Ok(BrowserAuthStatusResult::from_session(session))
+}
+
+/// Resume a publish once the user has passed the verification
+/// 用户完成验证后继续发布（重新提交发布请求）；该账号没有在等待验证时返回错误
+#[tauri::command]
+pub fn resume_publish_after_verification(detail_id: &str) -> Result<bool, String> {
+    if pending_verification(detail_id).is_none() {
+        return Err("该账号没有在等待验证（可能已超时或已取消）".to_string());
+    }
+    let resumed = resume_verification(detail_id);
+    tracing::info!("[Verification] 继续发布: detail_id={}, resumed={}", detail_id, resumed);
+    Ok(resumed)
+}
+
+/// Get the publish checkpoint of an account publish
+/// 获取账号发布的断点（已上传的视频、BD 凭证），没有断点时返回 None
+#[tauri::command]
+pub fn get_publish_checkpoint(app: AppHandle, detail_id: &str) -> Result<Option<PublishCheckpoint>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_publish_checkpoint(detail_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Resume an interrupted account publish from its last completed step
+/// 从断点继续发布一个账号：有断点时跳过已完成的视频上传和凭证获取，没有断点时重新发布该账号
+#[tauri::command]
+pub async fn resume_publication(app: AppHandle, detail_id: &str) -> Result<PublishProgressResult, String> {
+    let _timer = CommandTimer::start("resume_publication");
+    let db_manager = shared_db_manager(&app);
+
+    let detail = db_manager.get_publication_account_detail(detail_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "发布记录不存在".to_string())?;
+    if detail.status == PublicationStatus::Completed {
+        return Err("该账号已发布成功".to_string());
+    }
+    if is_publish_running(&detail.publication_task_id) {
+        return Err("任务正在发布中".to_string());
+    }
+
+    let checkpoint = db_manager.get_publish_checkpoint(detail_id).map_err(|e| e.to_string())?;
+    tracing::info!(
+        "[Publish] 断点续传: detail_id={}, step={:?}",
+        detail_id,
+        checkpoint.as_ref().map(|c| c.step)
+    );
+
+    let account_ids = [detail.account_id];
+    run_publication_accounts(app, &detail.publication_task_id, &[], Some(&account_ids), PublishMode::Publish).await
+}
+
+/// Publish a publication task to all accounts (concurrent/async)
+/// 发布作品到所有账号（异步并发）
+#[tauri::command]
+pub async fn publish_publication_task(
+    window: tauri::Window,
+    task_id: &str,
+    _title: &str,
+    _description: &str,
+    _video_path: &str,
+    _hashtags: Vec<String>,
+    skip_detail_ids: Option<Vec<String>>,  // 预检后用户选择跳过的账号（凭证失效）
+) -> Result<PublishProgressResult, String> {
+    let _timer = CommandTimer::start("publish_publication_task");
+    // 使用 app_handle 发送进度事件到所有窗口
+    run_publication_task(window.app_handle().clone(), task_id, &skip_detail_ids.unwrap_or_default()).await
+}
+
+/// Upload a task's video to the given accounts and save it as a platform draft instead of publishing
+/// 上传作品到指定账号并保存到平台草稿箱（不发布，目前支持抖音）；`account_ids` 为空时处理所有未发布的账号
+#[tauri::command]
+pub async fn save_as_platform_draft(
+    app: AppHandle,
+    task_id: &str,
+    account_ids: Vec<String>,
+) -> Result<PublishProgressResult, String> {
+    let _timer = CommandTimer::start("save_as_platform_draft");
+    let account_ids = (!account_ids.is_empty()).then_some(account_ids.as_slice());
+    run_publication_accounts(app, task_id, &[], account_ids, PublishMode::Draft).await
+}
+
+/// Credential check result of one target account
+/// 单个目标账号的凭证预检结果
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialCheckResult {
+    pub detail_id: String,
+    pub account_id: String,
+    pub account_name: String,
+    pub platform: PlatformType,
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// 按平台校验账号凭证（params JSON）
+async fn verify_account_credentials(platform: &PlatformType, params: &str) -> Result<(), PlatformError> {
+    match platform {
+        PlatformType::Douyin => DouyinPlatform::new().verify_credentials(params).await,
+        PlatformType::Kuaishou => KuaishouPlatform::new().verify_credentials(params).await,
+        PlatformType::Bilibili => BilibiliPlatform::new().verify_credentials(params).await,
+        PlatformType::Xiaohongshu => XiaohongshuPlatform::new().verify_credentials(params).await,
+    }
+}
+
+/// Check credentials of every account a publish would target
+/// 发布前预检：并发校验任务中所有待发布账号的凭证
+///
+/// 前端据此列出凭证失效的账号，由用户选择跳过（把 detail_id 传给 `skip_detail_ids`）或放弃发布；
+/// 维护中的账号不做校验（发布时本就会跳过）
+#[tauri::command]
+pub async fn verify_publication_credentials(app: AppHandle, task_id: &str) -> Result<Vec<CredentialCheckResult>, String> {
+    let _timer = CommandTimer::start("verify_publication_credentials");
+    let db_manager = shared_db_manager(&app);
+    let task = db_manager.get_publication_task_with_accounts(task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+
+    let mut handles = Vec::new();
+    for detail in task.accounts.into_iter().filter(|acc| acc.status != PublicationStatus::Completed) {
+        let db_manager = db_manager.clone();
+        handles.push(tokio::spawn(async move {
+            let account = db_manager.get_account(&detail.account_id).ok().flatten();
+            if account.as_ref().is_some_and(|a| a.on_hold) {
+                return None;
+            }
+
+            let result = match &account {
+                Some(account) => verify_account_credentials(&detail.platform, &account.params).await,
+                None => Err(PlatformError::AccountNotFound(detail.account_id.clone())),
+            };
+            if let Err(e) = &result {
+                tracing::warn!("[Publish] 凭证预检失败: account_id={}, error={}", detail.account_id, e);
+            }
+
+            Some(CredentialCheckResult {
+                detail_id: detail.id,
+                account_id: detail.account_id,
+                account_name: account.map(|a| a.nickname).unwrap_or_default(),
+                platform: detail.platform,
+                valid: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            })
+        }));
+    }
+
+    let mut results = Vec::new();
+    for handle in handles {
+        match handle.await {
+            Ok(Some(result)) => results.push(result),
+            Ok(None) => {}
+            Err(e) => tracing::error!("[Publish] 凭证预检任务异常: {}", e),
+        }
+    }
+
+    let invalid = results.iter().filter(|r| !r.valid).count();
+    tracing::info!("[Publish] 凭证预检完成: {} 个账号，{} 个失效", results.len(), invalid);
+    Ok(results)
+}
+
+/// Publish dry-run result of one target account
+/// 单个目标账号的发布预演结果
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicationPreviewResult {
+    pub detail_id: String,
+    pub account_id: String,
+    pub account_name: String,
+    pub platform: PlatformType,
+    pub preview: PublishPreview,
+}
+
+/// 整理任务的 @ 昵称：去掉开头的 `@` 和空白，过滤空值和重复
+pub(crate) fn normalize_mentions(mentions: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for mention in mentions {
+        let nickname = mention.trim().trim_start_matches('@').trim().to_string();
+        if !nickname.is_empty() && !result.contains(&nickname) {
+            result.push(nickname);
+        }
+    }
+    result
+}
+
+/// 把任务的 @ 昵称追加到描述末尾（描述中已 @ 过的不重复追加），由平台发布策略解析为提及
+pub(crate) fn with_mentions(description: Option<String>, mentions: &[String]) -> Option<String> {
+    let existing = description.as_deref().unwrap_or_default();
+    let missing: Vec<String> = mentions.iter()
+        .map(|nickname| format!("@{}", nickname))
+        .filter(|mention| !existing.split_whitespace().any(|word| word == mention))
+        .collect();
+    if missing.is_empty() {
+        return description;
+    }
+
+    let suffix = missing.join(" ");
+    Some(if existing.trim().is_empty() { suffix } else { format!("{} {}", existing.trim_end(), suffix) })
+}
+
+/// 发布预演：按实际发布时的内容（账号覆盖、话题库变体）解析文案和话题，不上传也不发布
+///
+/// 目前仅抖音支持完整预演，其他平台返回“暂不支持预览”
+#[tauri::command]
+pub async fn preview_publication_task(app: AppHandle, task_id: &str) -> Result<Vec<PublicationPreviewResult>, String> {
+    let _timer = CommandTimer::start("preview_publication_task");
+    let db_manager = shared_db_manager(&app);
+    let main_task = db_manager.get_publication_task(task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+    let task = db_manager.get_publication_task_with_accounts(task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+
+    let cover_path = resolve_cover_path(&app, &db_manager, &main_task);
+    let hashtag_set = main_task.hashtag_set_id.as_deref()
+        .and_then(|set_id| db_manager.get_hashtag_set(set_id).ok().flatten());
+
+    let mut results = Vec::new();
+    for detail in task.accounts.into_iter().filter(|acc| acc.status != PublicationStatus::Completed) {
+        let hashtags = match detail.hashtags_override.clone() {
+            Some(tags) => tags,
+            None => hashtag_set.as_ref()
+                .map(|set| set.tags_for(&detail.platform))
+                .unwrap_or_else(|| main_task.hashtags.clone()),
+        };
+        let request = PublishRequest {
+            account_id: detail.account_id.clone(),
+            video_path: main_task.video_path_for(&detail.platform).into(),
+            cover_path: cover_path.clone().map(|p| p.into()),
+            image_paths: Vec::new(),
+            title: detail.title_override.clone().unwrap_or_else(|| main_task.title.clone()),
+            description: with_mentions(
+                detail.description_override.clone().or_else(|| main_task.description.clone()),
+                &main_task.mentions,
+            ),
+            hashtags,
+            ..Default::default()
+        };
+
+        let preview = match detail.platform {
+            PlatformType::Douyin => DouyinPlatform::with_storage((*db_manager).clone())
+                .preview_publish(request)
+                .await
+                .unwrap_or_else(|e| PublishPreview { errors: vec![e.to_string()], ..Default::default() }),
+            _ => PublishPreview {
+                errors: vec![format!("{}暂不支持预览", detail.platform.display_name())],
+                ..Default::default()
+            },
+        };
+
+        let account_name = db_manager.get_account(&detail.account_id).ok().flatten()
+            .map(|a| a.nickname)
+            .unwrap_or_default();
+        results.push(PublicationPreviewResult {
+            detail_id: detail.id,
+            account_id: detail.account_id,
+            account_name,
+            platform: detail.platform,
+            preview,
+        });
+    }
+
+    Ok(results)
+}
+
+/// 通过抖音 ImageX 接口上传封面（无需浏览器），返回图片URI
+///
+/// 传入 `frame_seconds` 时 `image_path` 为视频，先截取该时间点的画面再上传
+#[tauri::command]
+pub async fn upload_douyin_cover(app: AppHandle, account_id: &str, image_path: &str, frame_seconds: Option<f64>) -> Result<String, String> {
+    let _timer = CommandTimer::start("upload_douyin_cover");
+    let db_manager = shared_db_manager(&app);
+
+    let image_path = match frame_seconds {
+        Some(seconds) => {
+            let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
+            let output_dir = CacheManager::new(&data_path).dir_for(CacheCategory::Thumbnails)
+                .map_err(|e| format!("无法创建封面缓存目录: {}", e))?;
+            extract_video_frame(image_path, seconds, &output_dir)?.to_string_lossy().to_string()
+        }
+        None => image_path.to_string(),
+    };
+
+    DouyinPlatform::with_storage((*db_manager).clone())
+        .upload_cover(account_id, &image_path)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 任务未指定封面时按默认封面策略截帧；策略为平台自动或截帧失败时返回 None
+fn resolve_cover_path(app_handle: &AppHandle, db_manager: &DatabaseManager, task: &PublicationTask) -> Option<String> {
+    if let Some(cover) = task.cover_path.as_ref().filter(|p| !p.is_empty()) {
+        return Some(cover.clone());
+    }
+
+    let strategy = db_manager.get_cover_strategy().unwrap_or_default();
+    let seconds = strategy.frame_seconds()?;
+
+    let data_path = app_handle.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
+    let output_dir = match CacheManager::new(&data_path).dir_for(CacheCategory::Thumbnails) {
+        Ok(dir) => dir,
+        Err(e) => {
+            tracing::warn!("[Publish] 无法创建封面缓存目录: {}", e);
+            return None;
+        }
+    };
+
+    let source = [Some(&task.video_path), task.vertical_video_path.as_ref(), task.horizontal_video_path.as_ref()]
+        .into_iter()
+        .flatten()
+        .find(|p| !p.is_empty())?;
+
+    match extract_video_frame(source, seconds, &output_dir) {
+        Ok(path) => {
+            tracing::info!("[Publish] 按默认封面策略 {:?} 截取封面: {}", strategy, path.display());
+            Some(path.to_string_lossy().to_string())
+        }
+        Err(e) => {
+            tracing::warn!("[Publish] 截取默认封面失败，交由平台自动选择: {}", e);
+            None
+        }
+    }
+}
+
+/// 按平台发布一次
+async fn publish_to_platform(
+    db_manager: &DatabaseManager,
+    platform: &PlatformType,
+    request: PublishRequest,
+) -> Result<PublishResult, PlatformError> {
+    match platform {
+        PlatformType::Douyin => {
+            let douyin_platform = DouyinPlatform::with_storage(db_manager.clone());
+            douyin_platform.publish_video(request).await
+        }
+        PlatformType::Kuaishou => {
+            let kuaishou_platform = KuaishouPlatform::with_storage(db_manager.clone());
+            kuaishou_platform.publish_video(request).await
+        }
+        PlatformType::Bilibili => {
+            let bilibili_platform = BilibiliPlatform::with_storage(db_manager.clone());
+            bilibili_platform.publish_video(request).await
+        }
+        PlatformType::Xiaohongshu => {
+            let xiaohongshu_platform = XiaohongshuPlatform::with_storage(db_manager.clone());
+            xiaohongshu_platform.publish_video(request).await
+        }
+    }
+}
+
+/// 发布流水线自动重试的首次等待时间（之后每次翻倍）
+const PUBLISH_RETRY_BASE_DELAY: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// 自动重试的最长等待时间
+const PUBLISH_RETRY_MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// 第 `attempt` 次失败后的重试等待时间（指数退避，attempt 从 1 开始）
+fn publish_retry_delay(attempt: u32) -> std::time::Duration {
+    PUBLISH_RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(PUBLISH_RETRY_MAX_DELAY)
+}
+
+/// 发布并按重试策略处理临时性失败：每次尝试计入 `attempts` 列，
+/// 网络异常、服务端 5xx 等失败在本次发布的 `max_attempts` 次以内按指数退避自动重试。
+/// 这是发布的唯一重试层，各平台发布策略每次调用只发布一次
+pub(crate) async fn publish_with_retry(
+    db_manager: &DatabaseManager,
+    platform: &PlatformType,
+    detail_id: &str,
+    request: PublishRequest,
+    max_attempts: u32,
+    cancel_token: &PublishCancelToken,
+) -> Result<PublishResult, PlatformError> {
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        if let Err(e) = db_manager.record_publication_attempt(detail_id) {
+            tracing::warn!("[Publish] Failed to record attempt for {}: {}", detail_id, e);
+        }
+        let result = publish_to_platform(db_manager, platform, request.clone()).await;
+        if attempt >= max_attempts || !is_transient_publish_failure(&result) {
+            return result;
+        }
+        let delay = publish_retry_delay(attempt);
+        tracing::warn!("[Publish] Transient failure for {} (attempt {}/{}), retrying in {}s",
+            detail_id, attempt, max_attempts, delay.as_secs());
+        if !wait_for_publish_retry(cancel_token, delay).await {
+            return Err(PlatformError::Cancelled(PUBLISH_CANCELLED_MESSAGE.to_string()));
+        }
+    }
+}
+
+/// 发布失败是否为临时性错误（网络异常、服务端 5xx、超时），统一按 `PlatformError::is_transient` 判断；取消不重试
+fn is_transient_publish_failure(result: &Result<PublishResult, PlatformError>) -> bool {
+    match result {
+        Ok(result) if result.success => false,
+        Ok(result) => result.error_message.as_ref()
+            .map(|msg| PlatformError::PublicationFailed(msg.clone()).is_transient())
+            .unwrap_or(false),
+        Err(e) => e.is_transient(),
+    }
+}
+
+/// 等待重试间隔；期间任务被取消时返回 false
+async fn wait_for_publish_retry(cancel_token: &PublishCancelToken, delay: std::time::Duration) -> bool {
+    let deadline = tokio::time::Instant::now() + delay;
+    while tokio::time::Instant::now() < deadline {
+        if cancel_token.is_cancelled() {
+            return false;
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(1).min(deadline - tokio::time::Instant::now())).await;
+    }
+    !cancel_token.is_cancelled()
+}
+
+/// 保存为平台草稿后账号发布记录上的提示
+const PLATFORM_DRAFT_MESSAGE: &str = "已保存到平台草稿箱";
+
+/// Publish a task to all of its accounts (shared by the command and the scheduler)
+/// 发布任务到所有账号（发布命令和定时调度器共用）
+///
+/// `skip_detail_ids` 中的账号不上传，直接标记为 Skipped（发布前凭证预检失效、用户选择跳过）
+pub async fn run_publication_task(app_handle: AppHandle, task_id: &str, skip_detail_ids: &[String]) -> Result<PublishProgressResult, String> {
+    run_publication_accounts(app_handle, task_id, skip_detail_ids, None, PublishMode::Publish).await
+}
+
+/// 按发布方式处理任务中的账号；`account_ids` 为 None 时处理所有未发布的账号
+///
+/// 草稿模式下上传后保存到平台草稿箱：不受发布节奏限制，成功后账号仍为 Draft，
+/// 之后正常发布时会重新上传发布
+async fn run_publication_accounts(
+    app_handle: AppHandle,
+    task_id: &str,
+    skip_detail_ids: &[String],
+    account_ids: Option<&[String]>,
+    mode: PublishMode,
+) -> Result<PublishProgressResult, String> {
+    tracing::info!("[Publish] Starting concurrent publish for task: {}, mode: {:?}", task_id, mode);
+
+    let db_manager = shared_db_manager(&app_handle);
 
     // Get the task with accounts
     let task = match db_manager.get_publication_task_with_accounts(task_id).map_err(|e| e.to_string())? {
         Some(t) => t,
         None => {
-            tracing::error!("[Publish] Task not found: {}", task_id);
-            return Err("Task not found".to_string());
+            tracing::error!("[Publish] Task not found: {}", task_id);
+            return Err("Task not found".to_string());
+        }
+    };
+    tracing::info!("[Publish] Found task with {} accounts", task.accounts.len());
+
+    // Get main task for video path and title
+    let main_task = match db_manager.get_publication_task(task_id).map_err(|e| e.to_string())? {
+        Some(t) => t,
+        None => return Err("Task not found".to_string()),
+    };
+
+    // Filter accounts that need publishing (not already completed)
+    let accounts_to_publish: Vec<_> = task.accounts.iter()
+        .filter(|acc| acc.status != PublicationStatus::Completed)
+        .filter(|acc| account_ids.is_none_or(|ids| ids.contains(&acc.account_id)))
+        .cloned()
+        .collect();
+
+    if accounts_to_publish.is_empty() {
+        tracing::info!("[Publish] All accounts already published, skipping");
+        return Ok(PublishProgressResult {
+            total_accounts: task.accounts.len(),
+            completed_accounts: task.accounts.len(),
+            success_count: task.accounts.len(),
+            failed_count: 0,
+            skipped_count: 0,
+            deferred_count: 0,
+            results: task.accounts.iter().map(|acc| PublishTaskResult {
+                success: true,
+                detail_id: acc.id.clone(),
+                publish_url: acc.publish_url.clone(),
+                error: None,
+                error_code: None,
+                skipped: false,
+                deferred_until: None,
+            }).collect(),
+        });
+    }
+
+    // Update main task status to Publishing
+    db_manager.update_publication_task_status(task_id, PublicationStatus::Publishing)
+        .map_err(|e| e.to_string())?;
+
+    Ok(publish_task_accounts(&app_handle, &db_manager, main_task, accounts_to_publish, skip_detail_ids, mode, task.accounts.len()).await)
+}
+
+/// Content and settings shared by every account of one publish run
+/// 同一次发布中所有账号共用的任务内容和设置
+struct AccountPublishContext {
+    app_handle: AppHandle,
+    db_manager: Arc<DatabaseManager>,
+    task: PublicationTask,
+    cover_path: Option<String>,
+    hashtag_set: Option<HashtagSet>,
+    music_info: Option<MusicInfo>,
+    settings: AppSettings,
+    pacing: PublishPacingRules,
+    mode: PublishMode,
+    cancel_token: PublishCancelToken,
+}
+
+/// Publish a set of a task's accounts concurrently and settle the task
+/// 并发发布任务中的一组账号（发布、保存草稿、重发共用）：每个账号走同一条发布流水线（见 publish_account），
+/// 结束后安排推迟的账号、发送通知，并根据各账号状态更新任务状态
+async fn publish_task_accounts(
+    app_handle: &AppHandle,
+    db_manager: &Arc<DatabaseManager>,
+    main_task: PublicationTask,
+    details: Vec<PublicationAccountDetail>,
+    skip_detail_ids: &[String],
+    mode: PublishMode,
+    total_accounts: usize,
+) -> PublishProgressResult {
+    let task_id = main_task.id.clone();
+
+    // Limit concurrency to avoid overwhelming the system
+    let settings = db_manager.get_app_settings().unwrap_or_default();
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(settings.max_concurrent_publishes as usize));
+    let cancel_token = register_publish(&task_id);
+    let context = Arc::new(AccountPublishContext {
+        app_handle: app_handle.clone(),
+        db_manager: db_manager.clone(),
+        cover_path: resolve_cover_path(app_handle, db_manager, &main_task),
+        // 关联了话题库时，按各账号平台取话题变体
+        hashtag_set: main_task.hashtag_set_id.as_deref()
+            .and_then(|set_id| db_manager.get_hashtag_set(set_id).ok().flatten()),
+        music_info: main_task.music_id.clone().map(|music_id| MusicInfo {
+            music_id,
+            music_end_time: main_task.music_end_time.clone().unwrap_or_default(),
+        }),
+        pacing: db_manager.get_publish_pacing_rules().unwrap_or_default(),
+        settings,
+        mode,
+        cancel_token: cancel_token.clone(),
+        task: main_task,
+    });
+
+    // Use tokio::spawn for concurrent publishing
+    let mut handles = Vec::new();
+    for account_detail in details {
+        let detail_id = account_detail.id.clone();
+        let skip_requested = skip_detail_ids.contains(&detail_id);
+        // 发布日志按 detail_id 归属，供前端实时订阅
+        let publish_span = tracing::info_span!("publish", detail_id = %detail_id);
+        let handle = tokio::spawn(
+            publish_account(context.clone(), semaphore.clone(), account_detail, skip_requested).instrument(publish_span)
+        );
+        cancel_token.track_pending(&detail_id, handle.abort_handle());
+        handles.push((detail_id, handle));
+    }
+
+    // Wait for all publishes to complete
+    let mut results: Vec<PublishTaskResult> = Vec::new();
+    for (detail_id, handle) in handles {
+        match handle.await {
+            Ok(result) => results.push(result),
+            // 排队中被取消而中止的账号
+            Err(e) if e.is_cancelled() => results.push(cancel_detail(db_manager, &detail_id)),
+            Err(e) => {
+                tracing::error!("[Publish] Task panicked: {}", e);
+                results.push(PublishTaskResult {
+                    success: false,
+                    detail_id: String::new(),
+                    publish_url: None,
+                    error: Some(format!("Publish task error: {}", e)),
+                    error_code: None,
+                    skipped: false,
+                    deferred_until: None,
+                });
+            }
+        }
+    }
+
+    unregister_publish(&task_id, &cancel_token);
+    schedule_deferred_publish(db_manager, &task_id, &results);
+    notify_publish_webhooks(db_manager, &results);
+    check_publish_risks(db_manager, &results);
+
+    // Calculate statistics
+    let success_count = results.iter().filter(|r| r.success).count();
+    let skipped_count = results.iter().filter(|r| r.skipped).count();
+    let deferred_count = results.iter().filter(|r| r.deferred_until.is_some()).count();
+    let failed_count = results.len() - success_count - skipped_count - deferred_count;
+
+    tracing::info!("[Publish] Results: {} success, {} failed, detail_ids: {:?}",
+        success_count, failed_count, results.iter().map(|r| &r.detail_id).collect::<Vec<_>>());
+
+    // Update main task status based on all account statuses
+    if let Err(e) = db_manager.update_task_status_from_accounts(&task_id) {
+        tracing::error!("[Publish] Failed to update task status: {}", e);
+    } else {
+        tracing::info!("[Publish] Task status updated successfully");
+    }
+
+    tracing::info!("[Publish] Publish completed: {} success, {} failed", success_count, failed_count);
+    notify_publish_finished(db_manager, &context.task.title, mode, success_count, failed_count);
+
+    PublishProgressResult {
+        total_accounts,
+        // All accounts accounted for
+        completed_accounts: total_accounts,
+        success_count,
+        failed_count,
+        skipped_count,
+        deferred_count,
+        results,
+    }
+}
+
+/// Run the publish pipeline for one account of a task
+/// 单个账号的发布流水线：排队 → 维护 / 跳过 / 节奏检查 → 水印与去重变体 → 发布（临时性失败自动重试）→ 记录结果
+async fn publish_account(
+    context: Arc<AccountPublishContext>,
+    semaphore: Arc<tokio::sync::Semaphore>,
+    account_detail: PublicationAccountDetail,
+    skip_requested: bool,
+) -> PublishTaskResult {
+    // Acquire permit before publishing
+    let permit = PublishPermit::acquire(semaphore).await;
+
+    let db_manager = &context.db_manager;
+    let cancel_token = &context.cancel_token;
+    let app_handle = &context.app_handle;
+    let task = &context.task;
+    let mode = context.mode;
+
+    let detail_id = account_detail.id.clone();
+    if !cancel_token.start(&detail_id) {
+        return cancel_detail(db_manager, &detail_id);
+    }
+    let platform = account_detail.platform.clone();
+    let account_id = account_detail.account_id.clone();
+
+    tracing::info!("[Publish] Starting publish for account: {}, platform: {:?}", account_id, platform);
+
+    // 维护中的账号直接跳过
+    if let Some(skipped) = skip_on_hold_account(db_manager, &detail_id, &account_id) {
+        return skipped;
+    }
+
+    // 预检凭证失效且用户选择跳过
+    if skip_requested {
+        return skip_detail(db_manager, &detail_id, "凭证失效，已跳过".to_string());
+    }
+
+    // 平台草稿目前只有抖音支持
+    if mode == PublishMode::Draft && platform != PlatformType::Douyin {
+        return skip_detail(db_manager, &detail_id, format!("{}暂不支持保存草稿", platform.display_name()));
+    }
+
+    // 超出发布节奏限制的账号推迟发布（保存草稿不计入发布次数）
+    if mode == PublishMode::Publish {
+        if let Some(deferred) = defer_on_pacing(db_manager, &context.pacing, &detail_id, &account_id, &platform) {
+            return deferred;
+        }
+    }
+
+    // 记录执行本次发布的策略模块版本
+    if let Some(version) = crate::platforms::platform_module_version(&platform) {
+        if let Err(e) = db_manager.set_publication_account_strategy_version(&detail_id, version) {
+            tracing::warn!("[Publish] Failed to record strategy version for {}: {}", detail_id, e);
+        }
+    }
+
+    // 按平台选择竖版 / 横版视频
+    let video_path = task.video_path_for(&platform).to_string();
+
+    // 开启水印时，上传按账号所在分组配置叠加水印后的视频
+    let video_path = if task.watermark_enabled {
+        match watermarked_video_path(app_handle, db_manager, &account_id, video_path).await {
+            Ok(path) => path,
+            Err(e) => return fail_detail(db_manager, &detail_id, format!("水印处理失败: {}", e)),
+        }
+    } else {
+        video_path
+    };
+
+    // 开启去重变体时，每个账号上传各自生成的变体
+    let video_path = if context.settings.video_variants_enabled {
+        match video_variant_path(app_handle, db_manager, &detail_id, video_path).await {
+            Ok(path) => path,
+            Err(e) => return fail_detail(db_manager, &detail_id, format!("生成去重变体失败: {}", e)),
+        }
+    } else {
+        video_path
+    };
+
+    // 账号级覆盖优先于任务内容，避免矩阵账号内容完全重复
+    let title = account_detail.title_override.clone().unwrap_or_else(|| task.title.clone());
+    let description = with_mentions(
+        account_detail.description_override.clone().or_else(|| task.description.clone()),
+        &task.mentions,
+    );
+    let hashtags = match account_detail.hashtags_override.clone() {
+        Some(tags) => tags,
+        None => context.hashtag_set.as_ref()
+            .map(|set| set.tags_for(&platform))
+            .unwrap_or_else(|| task.hashtags.clone()),
+    };
+
+    // Build publish request with progress info
+    let request = PublishRequest {
+        account_id: account_id.clone(),
+        video_path: video_path.into(),
+        cover_path: context.cover_path.clone().map(|p| p.into()),
+        image_paths: Vec::new(),
+        title,
+        description,
+        hashtags: hashtags.clone(),
+        visibility_type: task.visibility_type,
+        download_allowed: task.download_allowed as i32,
+        timeout: 0,
+        record_id: None,
+        send_time: None,
+        music_info: context.music_info.clone(),
+        poi_id: task.poi_id.clone(),
+        poi_name: task.poi_name.clone(),
+        anchor: None,
+        extra_info: None,
+        platform_data: None,
+        progress_info: Some((task.id.clone(), detail_id.clone(), account_id.clone(), app_handle.clone())),
+        cancel_token: Some(cancel_token.clone()),
+        publish_permit: Some(permit.clone()),
+        publish_mode: mode,
+    };
+
+    // Publish based on platform; transient failures (network, 5xx) retry with exponential backoff
+    let max_attempts = context.settings.max_attempts_for(&platform);
+    let publish_result = publish_with_retry(db_manager, &platform, &detail_id, request, max_attempts, cancel_token).await;
+
+    // 进度事件由 strategy.rs 中的 emit_progress 发送
+    match publish_result {
+        Ok(result) => {
+            let publish_url = result.item_id.as_deref()
+                .map(|id| publish_url_for(&platform, id));
+
+            // 保存为平台草稿的账号仍为 Draft，之后可正常发布
+            let new_status = match (result.success, mode) {
+                (true, PublishMode::Publish) => PublicationStatus::Completed,
+                (true, PublishMode::Draft) => PublicationStatus::Draft,
+                (false, _) => PublicationStatus::Failed,
+            };
+
+            // Update account status with message and item_id
+            let message = match (result.success, mode) {
+                (true, PublishMode::Draft) => Some(PLATFORM_DRAFT_MESSAGE.to_string()),
+                _ => result.error_message.clone(),
+            };
+            let item_id = result.item_id.clone();
+
+            if let Err(e) = db_manager.update_publication_account_status(
+                &detail_id,
+                new_status.clone(),
+                publish_url.clone(),
+                message.clone(),
+                item_id.clone(),
+            ) {
+                tracing::error!("[Publish] Failed to update status for {}: {}", detail_id, e);
+            } else {
+                tracing::info!("[Publish] ✅ Account status updated: detail_id={}, status={:?}, item_id={:?}",
+                    detail_id, new_status, item_id);
+            }
+
+            let error_code = (!result.success).then(|| {
+                message.as_deref().map(PublishErrorCode::from_message).unwrap_or(PublishErrorCode::Unknown)
+            });
+            record_error_code(db_manager, &detail_id, error_code);
+
+            // 记录话题使用，供话题库联想和表现统计
+            if result.success && mode == PublishMode::Publish {
+                if let Err(e) = db_manager.record_hashtag_usage(&detail_id, &hashtags) {
+                    tracing::warn!("[Publish] Failed to record hashtag usage for {}: {}", detail_id, e);
+                }
+            }
+
+            PublishTaskResult {
+                success: result.success,
+                detail_id,
+                publish_url,
+                error: message.filter(|_| !result.success),
+                error_code,
+                skipped: false,
+                deferred_until: None,
+            }
+        }
+        Err(e) => {
+            let error_msg = match &e {
+                PlatformError::Cancelled(msg) => msg.clone(),
+                _ => e.to_string(),
+            };
+            tracing::error!("[Publish] Publish failed for {}: {}", detail_id, error_msg);
+            let error_code = (!matches!(e, PlatformError::Cancelled(_))).then(|| e.error_code());
+
+            // Update account status with error message
+            if let Err(e2) = db_manager.update_publication_account_status(
+                &detail_id,
+                PublicationStatus::Failed,
+                None,
+                Some(error_msg.clone()),
+                None,
+            ) {
+                tracing::error!("[Publish] Failed to update status for {}: {}", detail_id, e2);
+            } else {
+                tracing::info!("[Publish] ✅ Account status updated to Failed: detail_id={}", detail_id);
+            }
+
+            record_error_code(db_manager, &detail_id, error_code);
+
+            PublishTaskResult {
+                success: false,
+                detail_id,
+                publish_url: None,
+                error: Some(error_msg),
+                error_code,
+                skipped: false,
+                deferred_until: None,
+            }
+        }
+    }
+}
+
+/// Retry publishing for failed or pending accounts
+/// 重发失败的或未发布的账号
+#[tauri::command]
+pub async fn retry_publication_task(
+    window: tauri::Window,
+    task_id: &str,
+) -> Result<PublishProgressResult, String> {
+    let _timer = CommandTimer::start("retry_publication_task");
+    tracing::info!("[Retry] Starting retry publish for task: {}", task_id);
+
+    // 使用 app_handle 发送进度事件到所有窗口
+    let app_handle = window.app_handle().clone();
+    let db_manager = shared_db_manager(&app_handle);
+
+    if is_publish_running(task_id) {
+        return Err("任务正在发布中".to_string());
+    }
+
+    // Get the task with all accounts (for the final count) and the main task for video path and title
+    let task = db_manager.get_publication_task_with_accounts(task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+    let main_task = db_manager.get_publication_task(task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+
+    // Get accounts that need retry (Draft or Failed status)
+    let accounts_to_retry = db_manager.get_accounts_for_retry(task_id)
+        .map_err(|e| e.to_string())?;
+
+    if accounts_to_retry.is_empty() {
+        tracing::info!("[Retry] No accounts need retry");
+        return Err("没有需要重发的账号".to_string());
+    }
+
+    tracing::info!("[Retry] Found {} accounts to retry", accounts_to_retry.len());
+
+    // Reset account statuses to Draft for retry
+    for account in &accounts_to_retry {
+        if let Err(e) = db_manager.reset_account_for_retry(&account.id) {
+            tracing::error!("[Retry] Failed to reset account {}: {}", account.id, e);
+        }
+    }
+
+    // Update main task status to Publishing
+    db_manager.update_publication_task_status(task_id, PublicationStatus::Publishing)
+        .map_err(|e| e.to_string())?;
+
+    Ok(publish_task_accounts(&app_handle, &db_manager, main_task, accounts_to_retry, &[], PublishMode::Publish, task.accounts.len()).await)
+}
+
+#[tauri::command]
+pub fn publish_video(
+    app: AppHandle,
+    platform: &str,
+    account_id: &str,
+    video_path: &str,
+    title: &str,
+    description: &str,
+    hashtags: Vec<String>,
+) -> Result<PublishResult, String> {
+    let platform_type = platform.parse::<PlatformType>().map_err(|e| e.to_string())?;
+    if platform_type != PlatformType::Douyin {
+        return Err(format!("Unsupported platform: {}", platform));
+    }
+
+    let db_manager = shared_db_manager(&app);
+
+    let _account = match db_manager.get_account(account_id) {
+        Ok(Some(acc)) => acc,
+        Ok(None) => return Err("Account not found".to_string()),
+        Err(e) => return Err(format!("Database error: {}", e)),
+    };
+
+    let request = PublishRequest {
+        account_id: account_id.to_string(),
+        video_path: video_path.into(),
+        cover_path: None,
+        image_paths: Vec::new(),
+        title: title.to_string(),
+        description: Some(description.to_string()),
+        hashtags,
+        visibility_type: 0,
+        download_allowed: 0,
+        timeout: 0,
+        record_id: None,
+        send_time: None,
+        music_info: None,
+        poi_id: None,
+        poi_name: None,
+        anchor: None,
+        extra_info: None,
+        platform_data: None,
+        progress_info: None,
+        cancel_token: None,
+        publish_permit: None,
+        publish_mode: PublishMode::Publish,
+    };
+
+    match platform_type {
+        PlatformType::Douyin => {
+            let rt = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+            let douyin_platform = DouyinPlatform::with_storage((*db_manager).clone());
+            rt.block_on(async {
+                douyin_platform.publish_video(request).await
+            })
+            .map_err(|e| e.to_string())
+        }
+        _ => Err("Unsupported platform".to_string()),
+    }
+}
+
+/// Publish an image post (图文 carousel) to a Douyin account
+/// 发布抖音图文作品（图片按传入顺序展示）
+#[tauri::command]
+pub async fn publish_image_post(
+    app: AppHandle,
+    account_id: String,
+    image_paths: Vec<String>,
+    title: String,
+    description: Option<String>,
+    hashtags: Vec<String>,
+) -> Result<PublishResult, String> {
+    let _timer = CommandTimer::start("publish_image_post");
+    let db_manager = shared_db_manager(&app);
+
+    let account = db_manager.get_account(&account_id)
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| "Account not found".to_string())?;
+    if account.platform != PlatformType::Douyin {
+        return Err(format!("Unsupported platform: {}", account.platform));
+    }
+    if image_paths.is_empty() {
+        return Err("图文作品至少需要一张图片".to_string());
+    }
+
+    let request = PublishRequest {
+        account_id,
+        image_paths: image_paths.into_iter().map(|p| p.into()).collect(),
+        title,
+        description,
+        hashtags,
+        ..Default::default()
+    };
+
+    DouyinPlatform::with_storage((*db_manager).clone())
+        .publish_video(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Browser automation authentication commands
+// 浏览器自动化授权命令
+// ============================================================================
+
+/// 浏览器认证状态查询结果
+#[derive(Serialize, Clone)]
+pub struct BrowserAuthStatusResult {
+    /// 授权会话ID（查询/取消时传入）
+    pub session_id: String,
+    pub platform_id: String,
+    pub account_id: Option<String>,
+    pub step: String,
+    pub message: String,
+    pub current_url: String,
+    pub screenshot: Option<String>,
+    pub need_poll: bool,
+    pub cookie: String,
+    pub local_storage: String,
+    pub nickname: String,
+    pub avatar_url: String,
+    pub third_id: String,
+    pub sec_uid: String,
+    pub error: Option<String>,
+}
+
+impl BrowserAuthStatusResult {
+    fn from_session(session: BrowserAuthSession) -> Self {
+        let result = session.result;
+        Self {
+            session_id: session.session_id,
+            platform_id: session.platform_id,
+            account_id: session.saved_account_id.or(session.account_id),
+            step: format!("{:?}", result.step),
+            message: result.message,
+            current_url: result.current_url,
+            screenshot: result.screenshot,
+            need_poll: !session.finished,
+            cookie: result.cookie,
+            local_storage: result.local_storage,
+            nickname: result.nickname,
+            avatar_url: result.avatar_url,
+            third_id: result.third_id,
+            sec_uid: result.sec_uid,
+            error: result.error,
+        }
+    }
+}
+
+/// 启动浏览器授权流程
+/// 如果传入了 account_id，则会更新现有账号而不是创建新账号；
+/// `user_agent` / `viewport_width` / `viewport_height` 指定本次会话的浏览器指纹，随账号保存。
+/// `headless` 指定是否无头运行（用于已登录会话的凭证刷新，看不到二维码），
+/// 未指定时重新授权已有账号沿用设置中的 `browser_auth_headless`，新账号首次登录始终有界面。
+///
+/// 授权在后台运行，立即返回会话ID，之后用 `check_browser_auth_status(session_id)` 轮询；
+/// 多个账号可同时授权（上限 MAX_BROWSER_AUTH_SESSIONS 个）
+#[tauri::command]
+pub async fn start_browser_auth(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    platform: &str,
+    account_id: Option<&str>,
+    _chrome_path: Option<&str>,
+    user_agent: Option<&str>,
+    viewport_width: Option<u32>,
+    viewport_height: Option<u32>,
+    headless: Option<bool>,
+) -> Result<BrowserAuthStatusResult, String> {
+    let _timer = CommandTimer::start("start_browser_auth");
+    if cfg!(mobile) {
+        return Err("移动端不支持浏览器授权，请使用粘贴 Cookie 导入".to_string());
+    }
+
+    let viewport = match (viewport_width, viewport_height) {
+        (Some(width), Some(height)) if width > 0 && height > 0 => Some(Viewport { width, height }),
+        (None, None) => None,
+        _ => return Err("窗口宽高需同时指定且大于 0".to_string()),
+    };
+    let fingerprint = BrowserFingerprint {
+        user_agent: user_agent.map(str::trim).filter(|ua| !ua.is_empty()).map(|ua| ua.to_string()),
+        viewport,
+    };
+
+    let headless = match headless {
+        Some(true) if account_id.is_none() => return Err("新账号需要扫码登录，请使用有界面模式授权".to_string()),
+        Some(headless) => headless,
+        None => account_id.is_some() && state.db_manager.get_app_settings()
+            .map(|s| s.browser_auth_headless)
+            .unwrap_or(false),
+    };
+
+    let session = state.browser_sessions.create(platform, account_id)?;
+    tracing::info!("[BrowserAuth] 创建授权会话: session_id={}, platform={}, account_id={:?}", session.session_id, platform, account_id);
+
+    tauri::async_runtime::spawn(run_browser_auth_session(
+        app,
+        session.session_id.clone(),
+        platform.to_string(),
+        account_id.map(|id| id.to_string()),
+        fingerprint,
+        headless,
+    ));
+
+    Ok(BrowserAuthStatusResult::from_session(session))
+}
+
+/// 在后台运行一个授权会话：启动浏览器等待登录，完成后保存凭证并记录最终状态
+async fn run_browser_auth_session(
+    app: AppHandle,
+    session_id: String,
+    platform: String,
+    account_id: Option<String>,
+    fingerprint: BrowserFingerprint,
+    headless: bool,
+) {
+    let (db_manager, sessions) = {
+        let state = app.state::<AppState>();
+        (state.db_manager.clone(), state.browser_sessions.clone())
+    };
+
+    // 已有账号直接使用其资料目录（保留登录会话）；新账号先用临时目录，保存后改名为账号ID
+    let profiles = BrowserProfileStore::new(&db_manager.base_path);
+    let profile_dir = match &account_id {
+        Some(id) => profiles.account_dir(id),
+        None => profiles.pending_dir(&session_id),
+    };
+
+    // 每个会话使用独立的自动化器，会话之间互不阻塞
+    let mut automator = BrowserAutomator::new();
+    automator.headless = headless;
+    automator.profile_dir = Some(profile_dir.clone());
+    automator.process = sessions.process(&session_id).unwrap_or_default();
+    let outcome = automator.start_authorize(&db_manager, &platform, account_id.as_deref(), fingerprint).await;
+    let mut result = automator.get_result().clone();
+    let mut saved_account_id = None;
+
+    match outcome {
+        Ok(()) if matches!(result.step, BrowserAuthStep::Completed) && !result.cookie.is_empty() => {
+            if sessions.is_cancelled(&session_id) {
+                tracing::info!("[BrowserAuth] 会话已取消，不保存授权结果: session_id={}", session_id);
+                if account_id.is_none() {
+                    let _ = profiles.remove_dir(&profile_dir);
+                }
+                return;
+            }
+            match save_browser_credentials(&app, &result, &platform, account_id.as_deref(), &automator.fingerprint) {
+                Ok(account) => {
+                    tracing::info!("[BrowserAuth] Account saved successfully: session_id={}, id={}, nickname={}", session_id, account.id, account.nickname);
+                    result.message = format!("授权成功！账号: {}", account.nickname);
+                    if account_id.is_none() {
+                        if let Err(e) = profiles.adopt(&profile_dir, &account.id) {
+                            tracing::warn!("[BrowserAuth] 保存浏览器资料目录失败: account_id={}, error={}", account.id, e);
+                        }
+                    }
+                    saved_account_id = Some(account.id);
+                }
+                Err(e) => {
+                    tracing::error!("[BrowserAuth] Failed to save account: session_id={}, error={}", session_id, e);
+                    result.message = format!("授权完成但保存失败: {}", e);
+                    result.error = Some(e);
+                }
+            }
+        }
+        Ok(()) => {}
+        Err(e) => {
+            tracing::warn!("[BrowserAuth] 授权失败: session_id={}, error={}", session_id, e);
+            result.step = BrowserAuthStep::Failed(e.clone());
+            result.message = format!("启动浏览器失败: {}", e);
+            result.error = Some(e);
+        }
+    }
+
+    if account_id.is_none() && saved_account_id.is_none() {
+        let _ = profiles.remove_dir(&profile_dir);
+    }
+    sessions.finish(&session_id, result, saved_account_id);
+}
+
+/// 查询授权会话状态（`need_poll` 为 false 时会话已结束）
+#[tauri::command]
+pub fn check_browser_auth_status(state: tauri::State<'_, AppState>, session_id: &str) -> Result<BrowserAuthStatusResult, String> {
+    state.browser_sessions.get(session_id)
+        .map(BrowserAuthStatusResult::from_session)
+        .ok_or_else(|| "授权会话不存在或已过期".to_string())
+}
+
+/// List all browser auth sessions (active ones first)
+/// 列出所有授权会话（进行中的在前，已结束的会话保留一段时间）
+#[tauri::command]
+pub fn get_browser_auth_sessions(state: tauri::State<'_, AppState>) -> Vec<BrowserAuthStatusResult> {
+    state.browser_sessions.list()
+        .into_iter()
+        .map(BrowserAuthStatusResult::from_session)
+        .collect()
+}
+
+/// 取消授权会话；返回是否取消了进行中的会话（已结束的会话不受影响）
+#[tauri::command]
+pub fn cancel_browser_auth(state: tauri::State<'_, AppState>, session_id: &str) -> Result<bool, String> {
+    let cancelled = state.browser_sessions.cancel(session_id);
+    if cancelled {
+        tracing::info!("[BrowserAuth] 已取消授权会话: session_id={}", session_id);
+    }
+    Ok(cancelled)
+}
+
+/// Start re-authorizing accounts one after another
+/// 启动批量重新授权队列
+///
+/// 未指定 account_ids 时先校验所有未维护账号的凭证（可按平台过滤），
+/// 状态已是 Expired 或校验失败的账号进入队列，校验失败的同时标记为 Expired；
+/// 队列在后台逐个打开浏览器授权，每个账号结束后推送 `reauth-queue-progress` 事件
+#[tauri::command]
+pub async fn start_reauth_queue(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    platform: Option<PlatformType>,
+    account_ids: Option<Vec<String>>,
+) -> Result<ReauthQueue, String> {
+    let _timer = CommandTimer::start("start_reauth_queue");
+    if cfg!(mobile) {
+        return Err("移动端不支持浏览器授权，请使用粘贴 Cookie 导入".to_string());
+    }
+
+    let mut queue = state.reauth_queue.lock().await;
+    if queue.is_running() {
+        return Err("已有正在进行的重新授权队列".to_string());
+    }
+
+    let accounts = match account_ids {
+        Some(ids) => ids.iter()
+            .filter_map(|id| state.db_manager.get_account(id).ok().flatten())
+            .collect(),
+        None => collect_expired_accounts(&state.db_manager, platform.as_ref()).await?,
+    };
+    tracing::info!("[Reauth] 重新授权队列: {} 个账号", accounts.len());
+
+    *queue = ReauthQueue::new(accounts);
+    let snapshot = queue.clone();
+    drop(queue);
+
+    if snapshot.is_running() {
+        tauri::async_runtime::spawn(run_reauth_queue(app));
+    }
+    Ok(snapshot)
+}
+
+/// 获取重新授权队列的当前状态
+#[tauri::command]
+pub async fn get_reauth_queue(state: tauri::State<'_, AppState>) -> Result<ReauthQueue, String> {
+    let _timer = CommandTimer::start("get_reauth_queue");
+    Ok(state.reauth_queue.lock().await.clone())
+}
+
+/// 取消重新授权队列（正在授权的账号结束后停止）
+#[tauri::command]
+pub async fn cancel_reauth_queue(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<ReauthQueue, String> {
+    let _timer = CommandTimer::start("cancel_reauth_queue");
+    let mut queue = state.reauth_queue.lock().await;
+    queue.cancel();
+    let _ = emit_event(&app, &*queue);
+    Ok(queue.clone())
+}
+
+/// 找出凭证失效的账号（跳过维护中的账号）
+async fn collect_expired_accounts(db_manager: &Arc<DatabaseManager>, platform: Option<&PlatformType>) -> Result<Vec<UserAccount>, String> {
+    let accounts: Vec<UserAccount> = db_manager.get_all_accounts()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|account| !account.on_hold && (platform.is_none() || platform == Some(&account.platform)))
+        .collect();
+
+    let mut handles = Vec::new();
+    for account in accounts {
+        let db_manager = db_manager.clone();
+        handles.push(tokio::spawn(async move {
+            if account.status == AccountStatus::Expired {
+                return Some(account);
+            }
+            let error = verify_account_credentials(&account.platform, &account.params).await.err()?;
+            tracing::warn!("[Reauth] 凭证失效: account_id={}, error={}", account.id, error);
+            if let Err(e) = db_manager.set_account_status(&account.id, &AccountStatus::Expired) {
+                tracing::error!("[Reauth] 标记账号失效失败: {}", e);
+            }
+            Some(account)
+        }));
+    }
+
+    let mut expired = Vec::new();
+    for handle in handles {
+        match handle.await {
+            Ok(Some(account)) => expired.push(account),
+            Ok(None) => {}
+            Err(e) => tracing::error!("[Reauth] 凭证校验任务异常: {}", e),
+        }
+    }
+    Ok(expired)
+}
+
+/// 后台依次授权队列中的账号，直到全部结束或被取消
+async fn run_reauth_queue(app: AppHandle) {
+    let (queue, db_manager) = {
+        let state = app.state::<AppState>();
+        (state.reauth_queue.clone(), state.db_manager.clone())
+    };
+
+    loop {
+        let item = {
+            let mut queue = queue.lock().await;
+            let item = queue.start_next();
+            let _ = emit_event(&app, &*queue);
+            match item {
+                Some(item) => item,
+                None => break,
+            }
+        };
+        let platform = item.platform.as_str();
+        tracing::info!("[Reauth] 开始授权: account_id={}, platform={}", item.account_id, platform);
+
+        let outcome = {
+            let mut automator = BrowserAutomator::new();
+            automator.profile_dir = Some(BrowserProfileStore::new(&db_manager.base_path).account_dir(&item.account_id));
+            match automator.start_authorize(&db_manager, platform, Some(&item.account_id), BrowserFingerprint::default()).await {
+                Ok(()) => {
+                    let result = automator.get_result().clone();
+                    if matches!(result.step, BrowserAuthStep::Completed) && !result.cookie.is_empty() {
+                        save_browser_credentials(&app, &result, platform, Some(&item.account_id), &automator.fingerprint)
+                            .map(|account| format!("授权成功！账号: {}", account.nickname))
+                            .map_err(|e| format!("授权完成但保存失败: {}", e))
+                    } else {
+                        Err(result.error.unwrap_or(result.message))
+                    }
+                }
+                Err(e) => Err(format!("启动浏览器失败: {}", e)),
+            }
+        };
+        if let Err(e) = &outcome {
+            tracing::warn!("[Reauth] 授权失败: account_id={}, error={}", item.account_id, e);
+        }
+
+        let mut queue = queue.lock().await;
+        queue.finish(&item.account_id, outcome);
+        let _ = emit_event(&app, &*queue);
+    }
+
+    tracing::info!("[Reauth] 重新授权队列结束");
+}
+
+/// 保存从浏览器提取的凭证到数据库
+/// 如果传入了 account_id，则更新现有账号而不是创建新账号；
+/// 会话使用的指纹写入 third_param，之后的接口请求沿用同一个 User-Agent
+fn save_browser_credentials(app: &AppHandle, result: &BrowserAuthResult, platform: &str, account_id: Option<&str>, fingerprint: &BrowserFingerprint) -> Result<UserAccount, String> {
+    let platform_type = platform.parse::<PlatformType>().map_err(|e| e.to_string())?;
+
+    // 构建 third_param - 直接使用 request_headers (JSON string)
+    let third_param: serde_json::Value = serde_json::from_str(&result.request_headers)
+        .unwrap_or(serde_json::json!({}));
+
+    // 添加 cookie 和 local_storage 到 third_param
+    let mut third_param_obj = third_param.as_object()
+        .cloned()
+        .unwrap_or_else(|| serde_json::Map::new());
+
+    third_param_obj.insert("cookie".to_string(), serde_json::json!(result.cookie));
+    third_param_obj.insert("local_data".to_string(), serde_json::json!(result.local_storage));
+    if let Some(user_agent) = &fingerprint.user_agent {
+        third_param_obj.insert("user-agent".to_string(), serde_json::json!(user_agent));
+    }
+    if let Some(viewport) = &fingerprint.viewport {
+        third_param_obj.insert("viewport".to_string(), serde_json::json!(viewport));
+    }
+
+    // 直接从 result 读取字段
+    let third_id = result.third_id.clone();
+    let nickname = if !result.nickname.is_empty() {
+        result.nickname.clone()
+    } else {
+        format!("{}用户", platform_type.display_name())
+    };
+    let avatar_url = result.avatar_url.clone();
+
+    // 构建 params JSON
+    let params = serde_json::json!({
+        "third_id": third_id,
+        "sec_uid": result.sec_uid,
+        "third_param": serde_json::Value::Object(third_param_obj)
+    });
+
+    // 使用传入的account_id或生成新的UUID
+    let account_id = account_id.map(|s| s.to_string())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let account = UserAccount {
+        id: account_id,
+        username: nickname.clone(),
+        nickname,
+        avatar_url,
+        platform: platform_type,
+        params: params.to_string(),
+        status: AccountStatus::Active,
+        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        on_hold: false,
+        group_id: None,
+        tags: Vec::new(),
+    };
+
+    // 保存到数据库
+    let db_manager = shared_db_manager(&app);
+    db_manager.save_account(&account)
+        .map_err(|e| e.to_string())?;
+    spawn_avatar_refresh(&db_manager, &account);
+
+    Ok(account)
+}
+
+/// 后台下载账号头像到本地缓存（授权/刷新账号时调用，原头像链接会过期）
+fn spawn_avatar_refresh(db_manager: &DatabaseManager, account: &UserAccount) {
+    if account.avatar_url.is_empty() {
+        return;
+    }
+    let base_path = db_manager.base_path.clone();
+    let account = account.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = cache_account_avatar(&base_path, &account).await {
+            tracing::warn!("[Avatar] 缓存头像失败: account_id={}, error={}", account.id, e);
+        }
+    });
+}
+
+/// Get an account's cached avatar as a base64 data URL
+/// 获取账号头像（base64 data URL）：优先读取本地缓存，没有缓存时按账号的头像地址下载一次；
+/// 都取不到时返回 null，前端显示默认头像
+#[tauri::command]
+pub async fn get_account_avatar(app: AppHandle, account_id: String) -> Result<Option<String>, String> {
+    let _timer = CommandTimer::start("get_account_avatar");
+    let db_manager = shared_db_manager(&app);
+    if let Some(data_url) = cached_avatar_data_url(&db_manager.base_path, &account_id) {
+        return Ok(Some(data_url));
+    }
+
+    let Some(account) = db_manager.get_account(&account_id).map_err(|e| e.to_string())? else {
+        return Err(format!("账号不存在: {}", account_id));
+    };
+    if account.avatar_url.is_empty() {
+        return Ok(None);
+    }
+    if let Err(e) = cache_account_avatar(&db_manager.base_path, &account).await {
+        tracing::info!("[Avatar] 头像下载失败: account_id={}, error={}", account_id, e);
+        return Ok(None);
+    }
+    Ok(cached_avatar_data_url(&db_manager.base_path, &account_id))
+}
+
+// ============================================================================
+// Browser cookie import commands
+// 浏览器 Cookie 导入命令
+// ============================================================================
+
+/// List the persistent browser profiles kept for accounts
+/// 列出账号的浏览器资料目录（保存登录会话，重新授权无需扫码）
+#[tauri::command]
+pub fn get_account_browser_profiles(app: AppHandle) -> Vec<AccountBrowserProfile> {
+    BrowserProfileStore::new(&shared_db_manager(&app).base_path).list()
+}
+
+/// Delete an account's browser profile; its next authorization needs a QR login again
+/// 删除账号的浏览器资料目录（下次授权需要重新扫码），返回释放的字节数
+#[tauri::command]
+pub fn delete_account_browser_profile(app: AppHandle, account_id: &str) -> Result<u64, String> {
+    BrowserProfileStore::new(&shared_db_manager(&app).base_path)
+        .remove(account_id)
+        .map_err(|e| format!("删除浏览器资料目录失败: {}", e))
+}
+
+/// Remove browser profiles of deleted accounts and interrupted authorizations
+/// 清理已删除账号的浏览器资料目录和授权中断留下的临时目录
+#[tauri::command]
+pub fn cleanup_account_browser_profiles(app: AppHandle) -> Result<ProfileCleanupResult, String> {
+    let db_manager = shared_db_manager(&app);
+    let account_ids: std::collections::HashSet<String> = db_manager.get_all_accounts()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|account| account.id)
+        .collect();
+    let result = BrowserProfileStore::new(&db_manager.base_path).cleanup(&account_ids);
+    tracing::info!("[BrowserProfiles] 清理 {} 个资料目录, 释放 {} 字节", result.removed_profiles, result.reclaimed_bytes);
+    Ok(result)
+}
+
+/// 列出本机可导入 Cookie 的浏览器配置文件
+#[tauri::command]
+pub fn list_browser_profiles() -> Vec<BrowserProfile> {
+    crate::browser::cookie_import::list_browser_profiles()
+}
+
+/// 从本地浏览器配置文件导入抖音账号（无需扫码）
+/// 读取 creator.douyin.com 的登录 Cookie，校验登录状态后保存账号；
+/// 若已存在相同 third_id 的账号则更新其凭证
+#[tauri::command]
+pub async fn import_account_from_browser(
+    app: AppHandle,
+    browser: BrowserKind,
+    profile_path: &str,
+    consent: bool,
+    user_agent: Option<&str>,
+) -> Result<UserAccount, String> {
+    let _timer = CommandTimer::start("import_account_from_browser");
+    if cfg!(mobile) {
+        return Err("移动端不支持读取浏览器 Cookie，请使用粘贴 Cookie 导入".to_string());
+    }
+    if !consent {
+        return Err("需要用户同意后才能读取浏览器 Cookie".to_string());
+    }
+
+    let profile_path = std::path::PathBuf::from(profile_path);
+    let cookie = tokio::task::spawn_blocking(move || {
+        crate::browser::cookie_import::read_cookie_header(browser, &profile_path, "douyin.com")
+    }).await.map_err(|e| format!("读取 Cookie 任务失败: {}", e))??;
+
+    let account = save_cookie_account(&shared_db_manager(&app), PlatformType::Douyin, &cookie, user_agent, None).await?;
+
+    tracing::info!("[CookieImport] 已从 {} 导入账号: {}", browser.display_name(), account.nickname);
+    Ok(account)
+}
+
+/// 各平台 Cookie 中携带用户 ID 的字段（抖音通过接口获取）
+fn cookie_user_id_field(platform: &PlatformType) -> Option<&'static str> {
+    match platform {
+        PlatformType::Kuaishou => Some("userId"),
+        PlatformType::Bilibili => Some("DedeUserID"),
+        PlatformType::Douyin | PlatformType::Xiaohongshu => None,
+    }
+}
+
+/// 用登录 Cookie 创建或更新账号：校验登录状态后保存，
+/// 若已存在相同 third_id 的账号则只更新凭证，保留原 ID、分组和创建时间
+async fn save_cookie_account(
+    db_manager: &DatabaseManager,
+    platform: PlatformType,
+    cookie: &str,
+    user_agent: Option<&str>,
+    nickname: Option<&str>,
+) -> Result<UserAccount, String> {
+    use crate::platforms::douyin::account_params::AccountParams;
+
+    let cookie = cookie.trim();
+    if cookie.is_empty() {
+        return Err("Cookie 不能为空".to_string());
+    }
+    let user_agent = user_agent
+        .filter(|ua| !ua.is_empty())
+        .unwrap_or(crate::browser::cookie_import::DEFAULT_USER_AGENT)
+        .to_string();
+    let platform_name = platform.display_name();
+
+    let mut third_id = cookie_user_id_field(&platform)
+        .and_then(|field| crate::platforms::kuaishou::client::cookie_value(cookie, field))
+        .unwrap_or_default();
+    let mut sec_uid = String::new();
+    let mut nickname = nickname.unwrap_or("").trim().to_string();
+    let mut avatar_url = String::new();
+
+    if platform == PlatformType::Douyin {
+        // 抖音 Cookie 不含用户 ID，通过用户信息接口获取（同时校验登录状态）
+        let client = crate::platforms::douyin::douyin_client::DouyinClient::new(
+            cookie.to_string(), user_agent.clone(), String::new(), Vec::new(),
+        );
+        let user = client.get_user_info().await?;
+
+        let field = |key: &str| user.get(key).and_then(|v| v.as_str()).unwrap_or("").to_string();
+        third_id = field("uid");
+        if third_id.is_empty() {
+            return Err("抖音登录已失效，请重新登录创作者中心后再导入 Cookie".to_string());
+        }
+        sec_uid = field("sec_uid");
+        if nickname.is_empty() {
+            nickname = field("nickname");
+        }
+        avatar_url = user.get("avatar_thumb")
+            .and_then(|v| v.get("url_list"))
+            .and_then(|v| v.get(0))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+    }
+    if nickname.is_empty() {
+        nickname = format!("{}用户", platform_name);
+    }
+
+    let params = serde_json::json!({
+        "third_id": third_id,
+        "sec_uid": sec_uid,
+        "third_param": {
+            "cookie": cookie,
+            "user-agent": user_agent,
+            "local_data": "[]",
+        }
+    }).to_string();
+
+    // 其他平台用一次轻量的登录态请求校验
+    if platform != PlatformType::Douyin {
+        verify_account_credentials(&platform, &params).await
+            .map_err(|e| format!("{}登录已失效，请重新登录后再导入 Cookie: {}", platform_name, e))?;
+    }
+
+    let existing = if third_id.is_empty() {
+        None
+    } else {
+        db_manager.get_accounts_by_platform(platform.clone())
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .find(|acc| AccountParams::from_json(&acc.params).get_third_id() == third_id)
+    };
+
+    let account = UserAccount {
+        id: existing.as_ref().map(|a| a.id.clone()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        username: nickname.clone(),
+        nickname,
+        avatar_url,
+        platform,
+        params,
+        status: AccountStatus::Active,
+        on_hold: existing.as_ref().map(|a| a.on_hold).unwrap_or(false),
+        group_id: existing.as_ref().and_then(|a| a.group_id.clone()),
+        tags: existing.as_ref().map(|a| a.tags.clone()).unwrap_or_default(),
+        created_at: existing.map(|a| a.created_at)
+            .unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+    };
+
+    db_manager.save_account(&account)
+        .map_err(|e| e.to_string())?;
+    spawn_avatar_refresh(db_manager, &account);
+
+    Ok(account)
+}
+
+/// 粘贴 Cookie 导入账号（无需浏览器，移动端可用）
+/// 校验登录状态后保存账号；若已存在相同 third_id 的账号则更新其凭证
+#[tauri::command]
+pub async fn import_account_from_cookie(
+    app: AppHandle,
+    platform: &str,
+    cookie: &str,
+    user_agent: Option<&str>,
+    nickname: Option<&str>,
+) -> Result<UserAccount, String> {
+    let _timer = CommandTimer::start("import_account_from_cookie");
+    let platform_type = platform.parse::<PlatformType>().map_err(|e| e.to_string())?;
+
+    let account = save_cookie_account(&shared_db_manager(&app), platform_type, cookie, user_agent, nickname).await?;
+
+    tracing::info!("[CookieImport] 已通过粘贴 Cookie 导入{}账号: {}", account.platform.display_name(), account.nickname);
+    Ok(account)
+}
+
+/// Authorization modes available on the current target
+/// 当前运行平台可用的授权方式
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthCapabilities {
+    pub browser_auth: bool,     // Playwright 浏览器扫码授权（仅桌面端）
+    pub browser_import: bool,   // 读取本机浏览器 Cookie（仅桌面端）
+    pub cookie_paste: bool,     // 粘贴 Cookie 导入
+    pub mobile: bool,
+}
+
+/// 获取当前平台可用的授权方式，前端据此隐藏移动端不支持的入口
+#[tauri::command]
+pub fn get_auth_capabilities() -> AuthCapabilities {
+    let mobile = cfg!(mobile);
+    AuthCapabilities {
+        browser_auth: !mobile,
+        browser_import: !mobile,
+        cookie_paste: true,
+        mobile,
+    }
+}
+
+// ============================================================================
+// Account import/export commands
+// 账号批量导入/导出命令（在不同安装之间迁移账号，无需重新扫码）
+// ============================================================================
+
+/// 账号文件格式标识与版本
+const ACCOUNT_FILE_FORMAT: &str = "auto-matrix-manager-accounts";
+const ACCOUNT_FILE_VERSION: u32 = 1;
+
+/// Account import/export file
+/// 账号导入/导出文件（JSON）：
+///
+/// ```json
+/// {
+///   "format": "auto-matrix-manager-accounts",
+///   "version": 1,
+///   "exported_at": "2024-03-01 12:00:00",
+///   "accounts": [
+///     { "platform": "douyin", "nickname": "账号A", "params": { "third_id": "...", "third_param": { "cookie": "..." } } },
+///     { "platform": "kuaishou", "cookie": "userId=...; kuaishou.server.web_st=...", "user_agent": "Mozilla/5.0 ..." }
+///   ]
+/// }
+/// ```
+///
+/// 每个账号提供完整凭证 `params`（本程序导出的格式），或只提供 `cookie`（可选 `user_agent`），
+/// 后者与粘贴 Cookie 导入相同，由程序请求平台接口补全用户信息。也可以直接是账号数组
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountFile {
+    pub format: String,
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at: String,
+    pub accounts: Vec<AccountFileEntry>,
+}
+
+/// 账号文件中的一个账号
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountFileEntry {
+    pub platform: PlatformType,
+    #[serde(default)]
+    pub nickname: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub avatar_url: String,
+    /// 完整凭证（JSON 对象，或其字符串形式）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+    /// 仅 Cookie 时使用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cookie: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub on_hold: bool,
+}
+
+/// Result of importing one account entry
+/// 单个账号的导入结果
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountImportItem {
+    /// 在文件中的序号（从 0 开始）
+    pub index: usize,
+    pub platform: PlatformType,
+    pub nickname: String,
+    /// 导入成功时的账号 ID（已存在相同 third_id 的账号时为原账号）
+    pub account_id: Option<String>,
+    /// 凭证是否校验通过（未通过仍会导入，状态标记为 Expired，可加入重新授权队列）
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// 各平台登录 Cookie 所在的域名
+fn platform_cookie_domain(platform: &PlatformType) -> &'static str {
+    match platform {
+        PlatformType::Douyin => "douyin.com",
+        PlatformType::Xiaohongshu => "xiaohongshu.com",
+        PlatformType::Kuaishou => "kuaishou.com",
+        PlatformType::Bilibili => "bilibili.com",
+    }
+}
+
+/// 解析账号文件：JSON（完整格式或账号数组），否则按 Netscape cookies.txt 解析，每个平台一个账号
+fn parse_account_file(content: &str) -> Result<Vec<AccountFileEntry>, String> {
+    let trimmed = content.trim_start_matches('\u{feff}').trim();
+
+    if trimmed.starts_with('[') {
+        return serde_json::from_str(trimmed).map_err(|e| format!("账号文件格式错误: {}", e));
+    }
+    if trimmed.starts_with('{') {
+        let file: AccountFile = serde_json::from_str(trimmed).map_err(|e| format!("账号文件格式错误: {}", e))?;
+        if file.format != ACCOUNT_FILE_FORMAT {
+            return Err(format!("不支持的账号文件格式: {}", file.format));
+        }
+        if file.version > ACCOUNT_FILE_VERSION {
+            return Err(format!("账号文件版本({})高于当前程序支持的版本({})，请先升级程序", file.version, ACCOUNT_FILE_VERSION));
+        }
+        return Ok(file.accounts);
+    }
+
+    let cookies = crate::browser::cookie_import::parse_netscape_cookies(trimmed);
+    let entries: Vec<AccountFileEntry> = [PlatformType::Douyin, PlatformType::Xiaohongshu, PlatformType::Kuaishou, PlatformType::Bilibili]
+        .into_iter()
+        .filter_map(|platform| {
+            let cookie = crate::browser::cookie_import::cookie_header_for_domain(&cookies, platform_cookie_domain(&platform))?;
+            Some(AccountFileEntry {
+                platform,
+                nickname: String::new(),
+                username: String::new(),
+                avatar_url: String::new(),
+                params: None,
+                cookie: Some(cookie),
+                user_agent: None,
+                tags: Vec::new(),
+                on_hold: false,
+            })
+        })
+        .collect();
+    if entries.is_empty() {
+        return Err("文件中没有可识别的账号（支持账号 JSON 文件和 Netscape 格式的 cookies.txt）".to_string());
+    }
+    Ok(entries)
+}
+
+/// 导入带完整凭证的账号：校验凭证，已存在相同 third_id 的账号时更新其凭证并保留 ID、分组和标签
+async fn import_account_entry(db_manager: &DatabaseManager, entry: &AccountFileEntry, params: &serde_json::Value) -> Result<(UserAccount, bool), String> {
+    use crate::platforms::douyin::account_params::AccountParams;
+
+    let params = match params {
+        serde_json::Value::String(params) => params.clone(),
+        params => params.to_string(),
+    };
+    let third_id = AccountParams::from_json(&params).get_third_id();
+
+    let verified = verify_account_credentials(&entry.platform, &params).await;
+    if let Err(e) = &verified {
+        tracing::warn!("[AccountImport] 凭证校验未通过: platform={}, nickname={}, error={}", entry.platform, entry.nickname, e);
+    }
+
+    let existing = if third_id.is_empty() {
+        None
+    } else {
+        db_manager.get_accounts_by_platform(entry.platform.clone())
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .find(|acc| AccountParams::from_json(&acc.params).get_third_id() == third_id)
+    };
+
+    let nickname = if entry.nickname.trim().is_empty() {
+        format!("{}用户", entry.platform.display_name())
+    } else {
+        entry.nickname.trim().to_string()
+    };
+    let account = UserAccount {
+        id: existing.as_ref().map(|a| a.id.clone()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        username: if entry.username.is_empty() { nickname.clone() } else { entry.username.clone() },
+        nickname,
+        avatar_url: entry.avatar_url.clone(),
+        platform: entry.platform.clone(),
+        params,
+        status: if verified.is_ok() { AccountStatus::Active } else { AccountStatus::Expired },
+        on_hold: existing.as_ref().map(|a| a.on_hold).unwrap_or(entry.on_hold),
+        group_id: existing.as_ref().and_then(|a| a.group_id.clone()),
+        tags: existing.as_ref().map(|a| a.tags.clone()).unwrap_or_else(|| entry.tags.clone()),
+        created_at: existing.map(|a| a.created_at)
+            .unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+    };
+
+    db_manager.save_account(&account).map_err(|e| e.to_string())?;
+    Ok((account, verified.is_ok()))
+}
+
+/// Import accounts from a JSON account file or a Netscape cookies.txt
+/// 批量导入账号：支持本程序导出的账号 JSON（格式见 [`AccountFile`]）和 Netscape 格式的 cookies.txt。
+///
+/// 逐个校验凭证：带完整凭证的账号校验未通过时仍导入并标记为 Expired；仅 Cookie 的账号校验未通过时不导入。
+/// 未指定 `path` 时弹出文件选择对话框，用户取消返回空列表
+#[tauri::command]
+pub async fn import_accounts(app: AppHandle, path: Option<String>) -> Result<Vec<AccountImportItem>, String> {
+    let _timer = CommandTimer::start("import_accounts");
+    let db_manager = shared_db_manager(&app);
+
+    let path = match path.filter(|p| !p.is_empty()) {
+        Some(path) => std::path::PathBuf::from(path),
+        None => match dialog::pick_file(&app, "导入账号", &["json", "txt"]).await? {
+            Some(file) => file,
+            None => return Ok(Vec::new()),
+        },
+    };
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("读取账号文件失败: {}", e))?;
+    let entries = parse_account_file(&content)?;
+
+    // 逐个导入，避免同一文件中重复的账号并发写入时各自新建
+    let mut results = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let outcome = match (&entry.params, &entry.cookie) {
+            (Some(params), _) => import_account_entry(&db_manager, entry, params).await,
+            (None, Some(cookie)) => {
+                let nickname = Some(entry.nickname.as_str()).filter(|n| !n.trim().is_empty());
+                save_cookie_account(&db_manager, entry.platform.clone(), cookie, entry.user_agent.as_deref(), nickname).await
+                    .map(|account| (account, true))
+            }
+            (None, None) => Err("缺少凭证（需要 params 或 cookie）".to_string()),
+        };
+
+        results.push(match outcome {
+            Ok((account, valid)) => AccountImportItem {
+                index,
+                platform: account.platform,
+                nickname: account.nickname,
+                account_id: Some(account.id),
+                valid,
+                error: None,
+            },
+            Err(e) => AccountImportItem {
+                index,
+                platform: entry.platform.clone(),
+                nickname: entry.nickname.clone(),
+                account_id: None,
+                valid: false,
+                error: Some(e),
+            },
+        });
+    }
+
+    let imported = results.iter().filter(|r| r.account_id.is_some()).count();
+    let invalid = results.iter().filter(|r| r.account_id.is_some() && !r.valid).count();
+    tracing::info!(
+        "[AccountImport] 从 {} 导入账号: 共 {} 个，成功 {} 个（其中凭证失效 {} 个），失败 {} 个",
+        path.display(), results.len(), imported, invalid, results.len() - imported,
+    );
+    Ok(results)
+}
+
+/// Export accounts (with credentials) to a JSON account file
+/// 导出账号到 JSON 文件（格式见 [`AccountFile`]），`account_ids` 为空时导出全部账号。
+///
+/// 文件包含明文凭证，请妥善保管；未指定 `path` 时弹出保存对话框，用户取消返回 None，否则返回导出数量
+#[tauri::command]
+pub async fn export_accounts(app: AppHandle, path: Option<String>, account_ids: Vec<String>) -> Result<Option<usize>, String> {
+    let _timer = CommandTimer::start("export_accounts");
+    let db_manager = shared_db_manager(&app);
+
+    let accounts: Vec<UserAccount> = db_manager.get_all_accounts()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|a| account_ids.is_empty() || account_ids.contains(&a.id))
+        .collect();
+    if accounts.is_empty() {
+        return Err("没有可导出的账号".to_string());
+    }
+
+    let path = match path.filter(|p| !p.is_empty()) {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let file_name = format!("账号_{}.json", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+            match dialog::save_file(&app, "导出账号", &file_name, ("JSON", &["json"])).await? {
+                Some(file) => file,
+                None => return Ok(None),
+            }
+        }
+    };
+
+    let file = AccountFile {
+        format: ACCOUNT_FILE_FORMAT.to_string(),
+        version: ACCOUNT_FILE_VERSION,
+        exported_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        accounts: accounts.into_iter()
+            .map(|account| AccountFileEntry {
+                params: Some(serde_json::from_str(&account.params)
+                    .unwrap_or(serde_json::Value::String(account.params))),
+                platform: account.platform,
+                nickname: account.nickname,
+                username: account.username,
+                avatar_url: account.avatar_url,
+                cookie: None,
+                user_agent: None,
+                tags: account.tags,
+                on_hold: account.on_hold,
+            })
+            .collect(),
+    };
+    let count = file.accounts.len();
+
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("写入账号文件失败: {}", e))?;
+
+    tracing::info!("[AccountExport] 已导出 {} 个账号到 {}", count, path.display());
+    Ok(Some(count))
+}
+
+// ============================================================================
+// Extractor Config Management Commands
+// 提取引擎配置管理命令
+// ============================================================================
+
+/// 获取所有平台提取引擎配置
+#[tauri::command]
+pub fn get_extractor_configs(app: AppHandle) -> Result<Vec<serde_json::Value>, String> {
+    let storage = shared_storage(&app);
+
+    storage.get_all_extractor_configs()
+        .map_err(|e| e.to_string())
+        .map(|configs| {
+            configs.into_iter().map(|c| {
+                serde_json::json!({
+                    "id": c.id,
+                    "platform_id": c.platform_id,
+                    "platform_name": c.platform_name,
+                    "login_url": c.login_url,
+                    "login_success_mode": c.login_success_mode,
+                    "login_success_pattern": c.login_success_pattern,
+                    "login_success_api_rule": c.login_success_api_rule,
+                    "login_success_api_operator": c.login_success_api_operator,
+                    "login_success_api_value": c.login_success_api_value,
+                    "redirect_url": c.redirect_url,
+                    "extract_rules": c.extract_rules,
+                    "is_default": c.is_default,
+                    "created_at": c.created_at,
+                    "updated_at": c.updated_at,
+                })
+            }).collect()
+        })
+}
+
+/// 获取指定平台的提取引擎配置
+#[tauri::command]
+pub fn get_extractor_config(app: AppHandle, platform_id: &str) -> Result<Option<serde_json::Value>, String> {
+    let storage = shared_storage(&app);
+
+    storage.get_extractor_config(platform_id)
+        .map_err(|e| e.to_string())
+        .map(|config| {
+            config.map(|c| {
+                serde_json::json!({
+                    "id": c.id,
+                    "platform_id": c.platform_id,
+                    "platform_name": c.platform_name,
+                    "login_url": c.login_url,
+                    "login_success_mode": c.login_success_mode,
+                    "login_success_pattern": c.login_success_pattern,
+                    "login_success_api_rule": c.login_success_api_rule,
+                    "login_success_api_operator": c.login_success_api_operator,
+                    "login_success_api_value": c.login_success_api_value,
+                    "redirect_url": c.redirect_url,
+                    "extract_rules": c.extract_rules,
+                    "is_default": c.is_default,
+                    "created_at": c.created_at,
+                    "updated_at": c.updated_at,
+                })
+            })
+        })
+}
+
+/// 保存提取引擎配置
+#[tauri::command]
+pub fn save_extractor_config(
+    app: AppHandle,
+    platform_id: &str,
+    platform_name: &str,
+    login_url: &str,
+    login_success_mode: &str,
+    login_success_pattern: &str,
+    login_success_api_rule: Option<&str>,
+    login_success_api_operator: Option<&str>,
+    login_success_api_value: Option<&str>,
+    redirect_url: Option<&str>,
+    extract_rules: &str,
+) -> Result<bool, String> {
+    let storage = shared_storage(&app);
+
+    // 解析 extract_rules JSON
+    let rules: serde_json::Value = serde_json::from_str(extract_rules)
+        .map_err(|e| format!("Invalid extract_rules JSON: {}", e))?;
+
+    let config = ExtractorConfig {
+        id: format!("config_{}", platform_id),
+        platform_id: platform_id.to_string(),
+        platform_name: platform_name.to_string(),
+        login_url: login_url.to_string(),
+        login_success_mode: login_success_mode.to_string(),
+        login_success_pattern: login_success_pattern.to_string(),
+        login_success_api_rule: login_success_api_rule.map(|s| s.to_string()),
+        login_success_api_operator: login_success_api_operator.map(|s| s.to_string()),
+        login_success_api_value: login_success_api_value.map(|s| s.to_string()),
+        redirect_url: redirect_url.map(|s| s.to_string()),
+        extract_rules: rules,
+        is_default: false,
+        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        updated_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    };
+
+    storage.save_extractor_config(&config)
+        .map_err(|e| e.to_string())?;
+
+    Ok(true)
+}
+
+/// Test an extractor config against a live login session
+/// 测试提取引擎配置：打开配置的登录页，登录后按 extract_rules 提取，
+/// 返回每条规则是否取到值（接口未请求 / 字段路径取到空值 / 规则格式错误）。
+/// 使用全新的浏览器会话和默认指纹，不创建或更新账号
+#[tauri::command]
+pub async fn test_extractor_config(app: AppHandle, platform_id: String) -> Result<ExtractorTestReport, String> {
+    let _timer = CommandTimer::start("test_extractor_config");
+
+    let mut browser = GenericBrowser::new();
+    browser.set_db_manager(shared_db_manager(&app));
+    let report = browser.test_config(&platform_id, &BrowserFingerprint::default()).await?;
+
+    tracing::info!("[ExtractorConfig] 测试 {}: {} 条规则命中, {} 条未取到值", platform_id, report.matched, report.failed);
+    Ok(report)
+}
+
+// ============================================================================
+// File selection commands
+// 文件选择命令
+// ============================================================================
+
+/// Select a file and return its path
+/// 选择文件并返回路径
+#[tauri::command]
+pub fn select_file(
+    _app: AppHandle,
+    title: &str,
+    filter_extensions: Option<Vec<&str>>,
+) -> Result<Option<String>, String> {
+    // Since we can't use tauri::api::dialog directly in commands,
+    // we'll return the filter info for frontend to handle
+    // Or we can use webview window to open dialog
+    let extensions = filter_extensions.unwrap_or_default();
+    Ok(Some(format!(
+        "FILE_DIALOG:{}:{}",
+        title,
+        extensions.join(",")
+    )))
+}
+
+/// Result of file selection
+#[derive(Serialize, Clone)]
+pub struct FileSelectionResult {
+    pub path: String,
+    pub name: String,
+}
+
+/// Result of file selection with content for preview
+#[derive(Serialize, Clone)]
+pub struct FileSelectionWithContentResult {
+    pub path: String,
+    pub name: String,
+    pub content: String, // Base64 encoded content
+    pub mime_type: String,
+}
+
+/// Open native file dialog and return selected file path
+/// 打开系统文件对话框并返回选中的文件路径
+#[tauri::command]
+pub async fn open_file_dialog(
+    app: AppHandle,
+    title: &str,
+    _multiple: bool,
+    filters: Option<Vec<String>>,
+) -> Result<Option<FileSelectionResult>, String> {
+    let _timer = CommandTimer::start("open_file_dialog");
+    let filters = filters.unwrap_or_default();
+    let extensions: Vec<&str> = filters
+        .iter()
+        .flat_map(|f| f.split(','))
+        .map(|s| s.trim().trim_start_matches('.'))
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    // Single file selection for now
+    let result = dialog::pick_file(&app, title, &extensions).await?;
+
+    Ok(result.map(|path| FileSelectionResult {
+        name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        path: path.to_string_lossy().to_string(),
+    }))
+}
+
+/// Open native file dialog and return file with content for preview
+/// 打开系统文件对话框并返回文件内容（用于前端预览）
+#[tauri::command]
+pub async fn select_file_with_content(
+    app: AppHandle,
+    title: &str,
+    file_type: &str, // "video" or "image"
+    _filters: Option<Vec<String>>,
+) -> Result<Option<FileSelectionWithContentResult>, String> {
+    let _timer = CommandTimer::start("select_file_with_content");
+    // Add filters based on file type
+    let extensions: Vec<&str> = match file_type {
+        "video" => VIDEO_EXTENSIONS.to_vec(),
+        "image" => vec!["png", "jpg", "jpeg", "webp", "gif", "bmp", "tiff", "heic"],
+        _ => Vec::new(),
+    };
+
+    let result = dialog::pick_file(&app, title, &extensions).await?;
+
+    match result {
+        Some(file) => {
+            let name = file.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let path = file.to_string_lossy().to_string();
+
+            // Read file content
+            let bytes = tokio::fs::read(&path).await
+                .map_err(|e| format!("Failed to read file: {}", e))?;
+
+            // Determine mime type
+            let mime_type = match file_type {
+                "video" => {
+                    let ext = std::path::Path::new(&path)
+                        .extension()
+                        .and_then(|e| e.to_str())
+                        .unwrap_or("");
+                    match ext.to_lowercase().as_str() {
+                        "mp4" => "video/mp4",
+                        "mov" => "video/quicktime",
+                        "avi" => "video/x-msvideo",
+                        "mkv" => "video/x-matroska",
+                        "webm" => "video/webm",
+                        "3gp" => "video/3gpp",
+                        "m4v" => "video/x-m4v",
+                        "wmv" => "video/x-ms-wmv",
+                        _ => "application/octet-stream",
+                    }.to_string()
+                }
+                "image" => {
+                    let ext = std::path::Path::new(&path)
+                        .extension()
+                        .and_then(|e| e.to_str())
+                        .unwrap_or("");
+                    match ext.to_lowercase().as_str() {
+                        "png" => "image/png",
+                        "jpg" | "jpeg" => "image/jpeg",
+                        "webp" => "image/webp",
+                        "gif" => "image/gif",
+                        "bmp" => "image/bmp",
+                        "tiff" | "tif" => "image/tiff",
+                        "heic" => "image/heic",
+                        _ => "application/octet-stream",
+                    }.to_string()
+                }
+                _ => "application/octet-stream".to_string(),
+            };
+
+            // Encode to base64
+            let content = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
+
+            Ok(Some(FileSelectionWithContentResult {
+                path,
+                name,
+                content,
+                mime_type,
+            }))
+        }
+        None => Ok(None),
+    }
+}
+
+// ============================================================================
+// Comment extraction commands
+// 评论提取命令
+// ============================================================================
+
+/// Extract comments from a video
+/// 从视频提取评论
+#[tauri::command]
+pub async fn extract_comments(
+    app: AppHandle,
+    detail_id: &str,  // publication_accounts 表的 id
+    aweme_id: &str,
+    max_count: i64,
+    cursor: i64,  // 分页游标，用于增量提取
+) -> Result<CommentExtractResult, String> {
+    let _timer = CommandTimer::start("extract_comments");
+
+    let db_manager = shared_db_manager(&app);
+
+    // 先从 publication_accounts 表获取真正的 account_id（未通过本应用发布的作品从 remote_videos 表获取）
+    let account_id = match db_manager.get_publication_account_detail(detail_id) {
+        Ok(Some(acc)) => acc.account_id,
+        Ok(None) => match db_manager.get_remote_video(detail_id) {
+            Ok(Some(video)) => video.account_id,
+            Ok(None) => {
+                tracing::error!("[Comment] 发布账号详情不存在: {}", detail_id);
+                return Err(format!("Publication account not found: {}", detail_id));
+            }
+            Err(e) => {
+                tracing::error!("[Comment] 查询平台作品失败: {:?}", e);
+                return Err(format!("Failed to query remote video: {:?}", e));
+            }
+        },
+        Err(e) => {
+            tracing::error!("[Comment] 查询发布账号详情失败: {:?}", e);
+            return Err(format!("Failed to query publication account: {:?}", e));
+        }
+    };
+
+    // Get account info to determine platform
+    let account = match db_manager.get_account(&account_id).map_err(|e| e.to_string())? {
+        Some(acc) => acc,
+        None => {
+            tracing::error!("[Comment] 账号不存在: {}", account_id);
+            return Err(format!("Account not found: {}", account_id));
+        }
+    };
+
+    // Extract based on platform
+    match account.platform {
+        PlatformType::Douyin => {
+            let douyin_platform = DouyinPlatform::with_storage((*db_manager).clone());
+            match douyin_platform.extract_comments(&account_id, aweme_id, max_count, cursor).await {
+                Ok(result) => {
+                    tracing::info!("[Comment] 提取成功: {} 条评论", result.comments.len());
+
+                    // 提取成功后，更新 publication_accounts 表中的评论数
+                    if result.success {
+                        if let Err(e) = db_manager.update_publication_account_comment_count(aweme_id) {
+                            tracing::error!("[Comment] 更新评论数失败: {:?}", e);
+                        } else if let Err(e) = db_manager.record_account_stats_snapshots() {
+                            tracing::error!("[Stats] 记录账号数据快照失败: {:?}", e);
+                        }
+                    }
+
+                    Ok(result)
+                }
+                Err(e) => {
+                    tracing::error!("[Comment] 提取失败: {:?}", e);
+                    Err(e.to_string())
+                }
+            }
+        }
+        _ => {
+            tracing::error!("[Comment] 不支持的平台: {:?}", account.platform);
+            Err(format!("Unsupported platform for comment extraction: {:?}", account.platform))
+        }
+    }
+}
+
+/// Full-text search over comment text
+/// 全文搜索评论内容，结果附带发布该作品的任务（查找“哪个视频提到了 X”）
+#[tauri::command]
+pub fn search_comments(app: AppHandle, query: &str, limit: Option<i64>) -> Result<Vec<CommentSearchHit>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.search_comments(query, limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, pagination::MAX_PAGE_SIZE))
+        .map_err(|e| e.to_string())
+}
+
+/// Get comments by aweme_id with pagination
+/// 根据作品ID获取评论（分页）
+#[tauri::command]
+pub fn get_comments_by_aweme_id(
+    app: AppHandle,
+    aweme_id: &str,
+    page: Option<i64>,
+    page_size: Option<i64>,
+) -> Result<Paginated<Comment>, String> {
+    let db_manager = shared_db_manager(&app);
+    let (page, page_size) = pagination::normalize_page(page, page_size);
+
+    tracing::info!("[Comment] 分页查询: aweme_id={}, page={}, page_size={}", aweme_id, page, page_size);
+
+    let offset = (page - 1) * page_size;
+    let comments = db_manager.get_comments_by_aweme_id_paginated(aweme_id, offset, page_size)
+        .map_err(|e| e.to_string())?;
+
+    let total = db_manager.get_comment_count(aweme_id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(Paginated::new(comments, total, page, page_size))
+}
+
+/// 未指定时单次提取的私信数
+const DEFAULT_MESSAGE_EXTRACT_COUNT: i64 = 200;
+
+/// Extract private messages from an account's creator inbox
+/// 提取账号收件箱私信并保存（目前支持抖音）
+#[tauri::command]
+pub async fn extract_messages(
+    app: AppHandle,
+    account_id: &str,
+    max_count: Option<i64>,
+) -> Result<MessageExtractResult, String> {
+    let _timer = CommandTimer::start("extract_messages");
+
+    let db_manager = shared_db_manager(&app);
+    let account = db_manager.get_account(account_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("账号不存在: {}", account_id))?;
+
+    match account.platform {
+        PlatformType::Douyin => {
+            let douyin_platform = DouyinPlatform::with_storage((*db_manager).clone());
+            douyin_platform.extract_messages(account_id, max_count.unwrap_or(DEFAULT_MESSAGE_EXTRACT_COUNT)).await
+                .map_err(|e| {
+                    tracing::error!("[Message] 提取私信失败: {:?}", e);
+                    e.to_string()
+                })
+        }
+        _ => Err(format!("{}暂不支持私信提取", account.platform.display_name())),
+    }
+}
+
+/// Get an account's stored private messages with pagination
+/// 分页获取账号的私信（按消息时间倒序）
+#[tauri::command]
+pub fn get_messages(
+    app: AppHandle,
+    account_id: &str,
+    page: Option<i64>,
+    page_size: Option<i64>,
+) -> Result<Paginated<PrivateMessage>, String> {
+    let db_manager = shared_db_manager(&app);
+    let (page, page_size) = pagination::normalize_page(page, page_size);
+
+    let offset = (page - 1) * page_size;
+    let messages = db_manager.get_messages_paginated(account_id, offset, page_size)
+        .map_err(|e| e.to_string())?;
+    let total = db_manager.get_message_count(account_id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(Paginated::new(messages, total, page, page_size))
+}
+
+/// 未指定时单次同步的作品数
+const DEFAULT_VIDEO_SYNC_COUNT: i64 = 500;
+
+/// Sync an account's published video list from the platform
+/// 同步账号在平台上的作品列表（含未通过本应用发布的作品），并关联作品ID相同的发布记录（目前支持抖音）
+#[tauri::command]
+pub async fn sync_account_videos(
+    app: AppHandle,
+    account_id: &str,
+    max_count: Option<i64>,
+) -> Result<VideoSyncResult, String> {
+    let _timer = CommandTimer::start("sync_account_videos");
+
+    let db_manager = shared_db_manager(&app);
+    let account = db_manager.get_account(account_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("账号不存在: {}", account_id))?;
+
+    let videos = match account.platform {
+        PlatformType::Douyin => DouyinPlatform::with_storage((*db_manager).clone())
+            .fetch_work_list(account_id, max_count.unwrap_or(DEFAULT_VIDEO_SYNC_COUNT)).await
+            .map_err(|e| {
+                tracing::error!("[VideoSync] 同步作品列表失败: {:?}", e);
+                e.to_string()
+            })?,
+        _ => return Err(format!("{}暂不支持作品同步", account.platform.display_name())),
+    };
+
+    let new_videos = db_manager.save_remote_videos(&videos).map_err(|e| e.to_string())?;
+    let linked = db_manager.link_remote_videos(account_id).map_err(|e| e.to_string())?;
+    tracing::info!("[VideoSync] 账号 {} 同步完成: 共 {} 个作品，新增 {}，已关联 {}",
+        account_id, videos.len(), new_videos, linked);
+
+    Ok(VideoSyncResult {
+        total: videos.len() as i64,
+        new_videos: new_videos as i64,
+        linked: linked as i64,
+    })
+}
+
+/// Get an account's synced videos with pagination
+/// 分页获取账号已同步的平台作品（按发布时间倒序）
+#[tauri::command]
+pub fn get_remote_videos(
+    app: AppHandle,
+    account_id: &str,
+    page: Option<i64>,
+    page_size: Option<i64>,
+) -> Result<Paginated<RemoteVideo>, String> {
+    let db_manager = shared_db_manager(&app);
+    let (page, page_size) = pagination::normalize_page(page, page_size);
+
+    let offset = (page - 1) * page_size;
+    let videos = db_manager.get_remote_videos_paginated(account_id, offset, page_size)
+        .map_err(|e| e.to_string())?;
+    let total = db_manager.get_remote_video_count(account_id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(Paginated::new(videos, total, page, page_size))
+}
+
+/// Delete a published video from the platform
+/// 从平台删除已发布的作品并将发布记录标记为 Removed；`delete_comments` 为 true 时同时删除本地保存的评论（目前支持抖音）
+#[tauri::command]
+pub async fn delete_remote_publication(
+    app: AppHandle,
+    detail_id: &str,
+    delete_comments: Option<bool>,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("delete_remote_publication");
+
+    let db_manager = shared_db_manager(&app);
+    let detail = db_manager.get_publication_account_detail(detail_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Publication account not found: {}", detail_id))?;
+
+    let item_id = match (&detail.status, detail.item_id.as_deref()) {
+        (PublicationStatus::Completed, Some(item_id)) if !item_id.is_empty() => item_id.to_string(),
+        _ => return Err("只能删除已发布成功的作品".to_string()),
+    };
+
+    match detail.platform {
+        PlatformType::Douyin => DouyinPlatform::with_storage((*db_manager).clone())
+            .delete_item(&detail.account_id, &item_id).await
+            .map_err(|e| {
+                tracing::error!("[Publish] 删除平台作品失败: detail_id={}, error={:?}", detail_id, e);
+                e.to_string()
+            })?,
+        _ => return Err(format!("{}暂不支持删除已发布作品", detail.platform.display_name())),
+    }
+
+    db_manager.mark_publication_account_removed(detail_id, "已从平台删除")
+        .map_err(|e| e.to_string())?;
+    db_manager.update_task_status_from_accounts(&detail.publication_task_id)
+        .map_err(|e| e.to_string())?;
+    if delete_comments.unwrap_or(false) {
+        shared_storage(&app).delete_comments_by_aweme_id(&item_id)
+            .map_err(|e| e.to_string())?;
+    }
+    tracing::info!("[Publish] 已从平台删除作品: detail_id={}, item_id={}", detail_id, item_id);
+
+    Ok(())
+}
+
+/// Delete comments by aweme_id
+/// 根据作品ID删除评论
+#[tauri::command]
+pub fn delete_comments(app: AppHandle, aweme_id: &str) -> Result<bool, String> {
+    let storage = shared_storage(&app);
+    storage.delete_comments_by_aweme_id(aweme_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get comment count by aweme_id
+/// 根据作品ID获取评论数量
+#[tauri::command]
+pub fn get_comment_count(app: AppHandle, aweme_id: &str) -> Result<i64, String> {
+    let storage = shared_storage(&app);
+    storage.get_comment_count(aweme_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Result of tagging the comments of an item
+/// 评论分类结果
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentClassifyResult {
+    pub total: usize,
+    pub tagged: usize,
+    pub tag_counts: std::collections::BTreeMap<String, usize>,
+}
+
+/// Get the keyword lists used to tag comments
+/// 获取评论分类关键词配置
+#[tauri::command]
+pub fn get_comment_tag_rules(app: AppHandle) -> Result<CommentTagRules, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_comment_tag_rules()
+        .map_err(|e| e.to_string())
+}
+
+/// Set the keyword lists used to tag comments
+/// 设置评论分类关键词配置（标签 → 关键词列表）
+#[tauri::command]
+pub fn set_comment_tag_rules(app: AppHandle, rules: CommentTagRules) -> Result<CommentTagRules, String> {
+    let rules = rules.normalized();
+    let db_manager = shared_db_manager(&app);
+    db_manager.set_comment_tag_rules(&rules).map_err(|e| e.to_string())?;
+    Ok(rules)
+}
+
+/// Tag every comment of an item by the configured keyword lists
+/// 按关键词配置给作品下的所有评论打标签（会覆盖之前的标签）
+#[tauri::command]
+pub fn classify_comments(app: AppHandle, aweme_id: &str) -> Result<CommentClassifyResult, String> {
+    let db_manager = shared_db_manager(&app);
+    let rules = db_manager.get_comment_tag_rules().map_err(|e| e.to_string())?;
+    let comments = db_manager.get_comments_by_aweme_id(aweme_id).map_err(|e| e.to_string())?;
+
+    let mut result = CommentClassifyResult { total: comments.len(), tagged: 0, tag_counts: Default::default() };
+    let tags: Vec<(String, Vec<String>)> = comments.into_iter()
+        .map(|comment| {
+            let tags = rules.classify(&comment.content);
+            if !tags.is_empty() {
+                result.tagged += 1;
+            }
+            for tag in &tags {
+                *result.tag_counts.entry(tag.clone()).or_default() += 1;
+            }
+            (comment.id, tags)
+        })
+        .collect();
+
+    db_manager.set_comment_tags(&tags).map_err(|e| e.to_string())?;
+    tracing::info!("[Comment] 分类完成: aweme_id={}, {}/{} 条评论命中标签", aweme_id, result.tagged, result.total);
+    Ok(result)
+}
+
+/// Get comments carrying a tag with pagination
+/// 按标签分页查询评论（可限定作品）
+#[tauri::command]
+pub fn get_comments_by_tag(
+    app: AppHandle,
+    tag: &str,
+    aweme_id: Option<&str>,
+    page: Option<i64>,
+    page_size: Option<i64>,
+) -> Result<Paginated<Comment>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_comments_by_tag(tag, aweme_id.filter(|id| !id.is_empty()))
+        .map(|comments| Paginated::paginate(comments, page, page_size))
+        .map_err(|e| e.to_string())
+}
+
+/// Comment export progress payload
+/// 评论导出进度
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentExportProgress {
+    pub aweme_id: String,
+    pub written: usize,
+    pub total: usize,
+}
+
+impl VersionedEvent for CommentExportProgress {
+    const NAME: &'static str = COMMENT_EXPORT_PROGRESS_EVENT_V1;
+    const VERSION: u32 = 1;
+    const LEGACY_NAME: Option<&'static str> = Some("comment-export-progress");
+}
+
+/// Export comments to an Excel workbook (summary sheet + one sheet per item)
+/// 导出评论为 Excel：汇总表（每个作品一行）+ 每个作品一个明细表
+///
+/// 传入 `task_id` 时导出该任务下所有已发布作品的评论；未指定 `output_path` 时弹出保存对话框，
+/// 用户取消返回 None。只导出一个作品且 `output_path` 以 `.csv` 结尾时写为 CSV。
+/// 写入过程中发送 `comment-export-progress` 事件
+#[tauri::command]
+pub async fn export_comments_to_xlsx(
+    app: AppHandle,
+    aweme_ids: Option<Vec<String>>,
+    task_id: Option<String>,
+    output_path: Option<String>,
+) -> Result<Option<String>, String> {
+    let _timer = CommandTimer::start("export_comments_to_xlsx");
+    let db_manager = shared_db_manager(&app);
+
+    let mut aweme_ids = aweme_ids.unwrap_or_default();
+    if let Some(task_id) = task_id.filter(|id| !id.is_empty()) {
+        let task = db_manager.get_publication_task_with_accounts(&task_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Task not found".to_string())?;
+        aweme_ids.extend(task.accounts.into_iter().filter_map(|a| a.item_id));
+    }
+    let mut seen = std::collections::HashSet::new();
+    aweme_ids.retain(|id| !id.is_empty() && seen.insert(id.clone()));
+    if aweme_ids.is_empty() {
+        return Err("没有可导出的作品".to_string());
+    }
+
+    let mut items = Vec::new();
+    for aweme_id in aweme_ids {
+        let comments = db_manager.get_comments_by_aweme_id(&aweme_id).map_err(|e| e.to_string())?;
+        let (title, account_name, published_at) = db_manager.get_comment_export_item(&aweme_id)
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default();
+        items.push(CommentExportItem { aweme_id, title, account_name, published_at, comments });
+    }
+
+    let output_path = match output_path.filter(|p| !p.is_empty()) {
+        Some(path) => path,
+        None => {
+            let file_name = format!("评论导出_{}.xlsx", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+            match dialog::save_file(&app, "导出评论", &file_name, ("Excel", &["xlsx"])).await? {
+                Some(file) => file.to_string_lossy().to_string(),
+                None => return Ok(None),
+            }
         }
     };
-    tracing::info!("[Publish] Found task with {} accounts", task.accounts.len());
+    let as_csv = std::path::Path::new(&output_path).extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    if as_csv && items.len() != 1 {
+        return Err("CSV 仅支持导出单个作品，请使用 .xlsx".to_string());
+    }
 
-    // Get main task for video path and title
-    let main_task = match db_manager.get_publication_task(task_id).map_err(|e| e.to_string())? {
-        Some(t) => t,
-        None => return Err("Task not found".to_string()),
+    let totals: HashMap<String, usize> = items.iter()
+        .map(|item| (item.aweme_id.clone(), item.comments.len()))
+        .collect();
+    let handle = app.clone();
+    let report = move |aweme_id: &str, written: usize| {
+        let total = totals.get(aweme_id).copied().unwrap_or(written);
+        let progress = CommentExportProgress { aweme_id: aweme_id.to_string(), written, total };
+        if let Err(e) = emit_event(&handle, &progress) {
+            tracing::warn!("[Comment] 发送导出进度失败: {}", e);
+        }
     };
+    let path = output_path.clone();
+    let count = items.len();
+    tokio::task::spawn_blocking(move || {
+        let path = std::path::Path::new(&path);
+        if as_csv {
+            let item = &items[0];
+            export_comments_csv(path, &item.comments, |written| report(&item.aweme_id, written))
+        } else {
+            export_comments_xlsx(path, &items, report)
+        }
+    }).await.map_err(|e| format!("导出任务失败: {}", e))??;
 
-    // Filter accounts that need publishing (not already completed)
-    let accounts_to_publish: Vec<_> = task.accounts.iter()
-        .filter(|acc| acc.status != PublicationStatus::Completed)
+    tracing::info!("[Comment] 已导出 {} 个作品的评论到 {}", count, output_path);
+    Ok(Some(output_path))
+}
+
+/// Reply to a comment as the publishing account
+/// 以账号身份回复评论（目前支持抖音）
+#[tauri::command]
+pub async fn reply_comment(
+    app: AppHandle,
+    account_id: String,
+    aweme_id: String,
+    comment_id: String,
+    text: String,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("reply_comment");
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("回复内容不能为空".to_string());
+    }
+
+    let db_manager = shared_db_manager(&app);
+    let account = db_manager.get_account(&account_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Account not found: {}", account_id))?;
+
+    match account.platform {
+        PlatformType::Douyin => {
+            DouyinPlatform::with_storage((*db_manager).clone())
+                .reply_comment(&account_id, &aweme_id, &comment_id, text).await
+                .map_err(|e| e.to_string())
+        }
+        other => Err(format!("Unsupported platform for comment reply: {:?}", other)),
+    }
+}
+
+// ============================================================================
+// Auto-Reply Commands
+// 评论自动回复命令
+// ============================================================================
+
+/// Get auto-reply rules, optionally for a single account
+/// 获取自动回复规则
+#[tauri::command]
+pub fn get_auto_reply_rules(app: AppHandle, account_id: Option<&str>) -> Result<Vec<AutoReplyRule>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_auto_reply_rules(account_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Create or update an auto-reply rule (no id creates a new rule)
+/// 新建或更新自动回复规则（id 为空时新建）
+#[tauri::command]
+pub fn save_auto_reply_rule(
+    app: AppHandle,
+    id: Option<&str>,
+    account_id: &str,
+    name: &str,
+    keywords: Vec<String>,
+    reply_template: &str,
+    enabled: Option<bool>,
+    max_replies_per_hour: Option<i64>,
+) -> Result<AutoReplyRule, String> {
+    let keywords: Vec<String> = keywords.iter()
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty())
         .collect();
+    if keywords.is_empty() {
+        return Err("请至少填写一个关键词".to_string());
+    }
+    if reply_template.trim().is_empty() {
+        return Err("回复模板不能为空".to_string());
+    }
 
-    if accounts_to_publish.is_empty() {
-        tracing::info!("[Publish] All accounts already published, skipping");
-        return Ok(PublishProgressResult {
-            total_accounts: task.accounts.len(),
-            completed_accounts: task.accounts.len(),
-            success_count: task.accounts.len(),
-            failed_count: 0,
-            results: task.accounts.iter().map(|acc| PublishTaskResult {
-                success: true,
-                detail_id: acc.id.clone(),
-                publish_url: acc.publish_url.clone(),
-                error: None,
-            }).collect(),
-        });
+    let db_manager = shared_db_manager(&app);
+    if db_manager.get_account(account_id).map_err(|e| e.to_string())?.is_none() {
+        return Err(format!("账号不存在: {}", account_id));
+    }
+
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let existing = match id.filter(|id| !id.is_empty()) {
+        Some(id) => Some(db_manager.get_auto_reply_rule(id).map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("规则不存在: {}", id))?),
+        None => None,
+    };
+
+    let rule = AutoReplyRule {
+        id: existing.as_ref().map(|r| r.id.clone()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        account_id: account_id.to_string(),
+        name: name.trim().to_string(),
+        keywords,
+        reply_template: reply_template.trim().to_string(),
+        enabled: enabled.unwrap_or(true),
+        max_replies_per_hour: max_replies_per_hour.unwrap_or(DEFAULT_REPLIES_PER_HOUR).max(1),
+        created_at: existing.map(|r| r.created_at).unwrap_or_else(|| now.clone()),
+        updated_at: now,
+    };
+
+    db_manager.save_auto_reply_rule(&rule).map_err(|e| e.to_string())?;
+    Ok(rule)
+}
+
+/// Delete an auto-reply rule
+/// 删除自动回复规则
+#[tauri::command]
+pub fn delete_auto_reply_rule(app: AppHandle, rule_id: &str) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.delete_auto_reply_rule(rule_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get sent auto-replies, newest first
+/// 获取自动回复记录
+#[tauri::command]
+pub fn get_auto_reply_logs(app: AppHandle, account_id: Option<&str>, limit: Option<usize>) -> Result<Vec<AutoReplyLog>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_auto_reply_logs(account_id, limit.unwrap_or(100))
+        .map_err(|e| e.to_string())
+}
+
+/// Get incremental comment sync progress of all items
+/// 获取各作品的评论增量同步进度
+#[tauri::command]
+pub fn get_comment_sync_states(app: AppHandle) -> Result<Vec<CommentSyncState>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_comment_sync_states().map_err(|e| e.to_string())
+}
+
+/// 作品最近的评论同步记录（最新的在前），前端记住已读到的同步ID
+#[tauri::command]
+pub fn get_comment_sync_history(app: AppHandle, aweme_id: &str, limit: Option<usize>) -> Result<Vec<CommentSyncRecord>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_comment_sync_history(aweme_id, limit.unwrap_or(20)).map_err(|e| e.to_string())
+}
+
+/// Comments first seen after `since_sync_id` (0 = everything since the first sync)
+/// 获取指定同步之后的新评论，用于"上次查看以来的新评论"视图和未读角标
+#[tauri::command]
+pub fn get_new_comments(app: AppHandle, aweme_id: &str, since_sync_id: i64) -> Result<Vec<Comment>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_new_comments(aweme_id, since_sync_id).map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Remote Sync Commands
+// 远程同步命令
+// ============================================================================
+
+/// Get the remote sync configuration
+/// 获取远程同步配置
+#[tauri::command]
+pub fn get_remote_sync_config(app: AppHandle) -> Result<RemoteSyncConfig, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_remote_sync_config().map_err(|e| e.to_string())
+}
+
+/// Save the remote sync configuration (device id is generated on first save)
+/// 保存远程同步配置，首次保存时生成本机标识
+#[tauri::command]
+pub fn save_remote_sync_config(app: AppHandle, config: RemoteSyncConfig) -> Result<RemoteSyncConfig, String> {
+    let db_manager = shared_db_manager(&app);
+    let previous = db_manager.get_remote_sync_config().map_err(|e| e.to_string())?;
+
+    let endpoint = config.endpoint.trim().trim_end_matches('/').to_string();
+    if !endpoint.is_empty() {
+        url::Url::parse(&endpoint).map_err(|e| format!("同步服务地址无效: {}", e))?;
+    }
+    if config.enabled && (endpoint.is_empty() || config.passphrase.is_empty()) {
+        return Err("启用同步需要填写同步服务地址和同步密码".to_string());
+    }
+
+    let config = RemoteSyncConfig {
+        endpoint,
+        interval_minutes: config.interval_minutes.max(1),
+        device_id: if previous.device_id.is_empty() {
+            uuid::Uuid::new_v4().to_string()
+        } else {
+            previous.device_id
+        },
+        ..config
+    };
+    db_manager.set_remote_sync_config(&config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+/// Run one remote sync round now
+/// 立即执行一轮远程同步
+#[tauri::command]
+pub async fn run_remote_sync_now(app: AppHandle) -> Result<RemoteSyncResult, String> {
+    let _timer = CommandTimer::start("run_remote_sync_now");
+    let db_manager = shared_db_manager(&app);
+    run_remote_sync(&db_manager).await
+}
+
+// ============================================================================
+// HTTP API Commands
+// HTTP API 命令
+// ============================================================================
+
+/// Get the HTTP API configuration
+/// 获取 HTTP API 配置
+#[tauri::command]
+pub fn get_http_api_config(app: AppHandle) -> Result<HttpApiConfig, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_http_api_config().map_err(|e| e.to_string())
+}
+
+/// Save the HTTP API configuration and restart the server (token is generated on first save)
+/// 保存 HTTP API 配置并按新配置重启服务；未填写令牌时自动生成
+#[tauri::command]
+pub fn save_http_api_config(app: AppHandle, config: HttpApiConfig) -> Result<HttpApiConfig, String> {
+    if config.enabled && !cfg!(feature = "http-api") {
+        return Err("当前版本未包含 HTTP API（需以 --features http-api 编译）".to_string());
+    }
+    let bind_address = match config.bind_address.trim() {
+        "" => DEFAULT_HTTP_API_BIND_ADDRESS.to_string(),
+        address => address.parse::<std::net::IpAddr>()
+            .map_err(|e| format!("监听地址无效: {}: {}", address, e))?
+            .to_string(),
+    };
+    if config.port < 1024 {
+        return Err("端口需在 1024 ~ 65535 之间".to_string());
+    }
+
+    let token = config.token.trim().to_string();
+    let config = HttpApiConfig {
+        bind_address,
+        token: if token.is_empty() { uuid::Uuid::new_v4().simple().to_string() } else { token },
+        ..config
+    };
+    let db_manager = shared_db_manager(&app);
+    db_manager.set_http_api_config(&config).map_err(|e| e.to_string())?;
+
+    #[cfg(feature = "http-api")]
+    restart_http_api(&app, &config);
+    Ok(config)
+}
+
+// ============================================================================
+// Notification Commands
+// 系统通知命令
+// ============================================================================
+
+/// Get the desktop notification settings
+/// 获取系统通知设置
+#[tauri::command]
+pub fn get_notification_settings(app: AppHandle) -> Result<NotificationSettings, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_notification_settings().map_err(|e| e.to_string())
+}
+
+/// Save the desktop notification settings (a switch per event type)
+/// 保存系统通知设置（总开关及发布、定时发布、评论同步各自的开关）
+#[tauri::command]
+pub fn save_notification_settings(app: AppHandle, settings: NotificationSettings) -> Result<NotificationSettings, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.set_notification_settings(&settings).map_err(|e| e.to_string())?;
+    Ok(settings)
+}
+
+// ============================================================================
+// Webhook Commands
+// 发布回调命令
+// ============================================================================
+
+/// Get the publish webhook configuration
+/// 获取发布回调配置
+#[tauri::command]
+pub fn get_webhook_config(app: AppHandle) -> Result<WebhookConfig, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_webhook_config().map_err(|e| e.to_string())
+}
+
+/// Save the publish webhook configuration
+/// 保存发布回调配置（地址需为 http / https，空地址和重复地址会被去掉）
+#[tauri::command]
+pub fn save_webhook_config(app: AppHandle, config: WebhookConfig) -> Result<WebhookConfig, String> {
+    let mut urls: Vec<String> = Vec::new();
+    for url in config.urls.iter().map(|u| u.trim()).filter(|u| !u.is_empty()) {
+        let parsed = url::Url::parse(url).map_err(|e| format!("回调地址无效: {}: {}", url, e))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(format!("回调地址只支持 http / https: {}", url));
+        }
+        if !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+    }
+    if config.enabled && urls.is_empty() {
+        return Err("启用发布回调需要填写至少一个回调地址".to_string());
     }
 
-    // Update main task status to Publishing
-    db_manager.update_publication_task_status(task_id, PublicationStatus::Publishing)
+    let config = WebhookConfig {
+        urls,
+        max_attempts: config.max_attempts.clamp(1, MAX_WEBHOOK_ATTEMPTS_LIMIT),
+        ..config
+    };
+    let db_manager = shared_db_manager(&app);
+    db_manager.set_webhook_config(&config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+/// Send a test notification to every configured webhook URL
+/// 向所有回调地址发送测试通知（不要求已启用），返回各地址的投递结果
+#[tauri::command]
+pub async fn test_webhook(app: AppHandle) -> Result<Vec<WebhookDelivery>, String> {
+    let _timer = CommandTimer::start("test_webhook");
+    let db_manager = shared_db_manager(&app);
+    let config = db_manager.get_webhook_config().map_err(|e| e.to_string())?;
+    if config.urls.is_empty() {
+        return Err("未配置回调地址".to_string());
+    }
+
+    Ok(send_webhooks(&db_manager, &config, &WebhookPayload::test()).await)
+}
+
+/// Get webhook delivery records with pagination
+/// 分页获取发布回调投递记录（按时间倒序，`failed_only` 为 true 时只看失败的记录）
+#[tauri::command]
+pub fn get_webhook_deliveries(
+    app: AppHandle,
+    failed_only: Option<bool>,
+    page: Option<i64>,
+    page_size: Option<i64>,
+) -> Result<Paginated<WebhookDelivery>, String> {
+    let db_manager = shared_db_manager(&app);
+    let (page, page_size) = pagination::normalize_page(page, page_size);
+    let failed_only = failed_only.unwrap_or(false);
+
+    let offset = (page - 1) * page_size;
+    let deliveries = db_manager.get_webhook_deliveries_paginated(failed_only, offset, page_size)
+        .map_err(|e| e.to_string())?;
+    let total = db_manager.get_webhook_delivery_count(failed_only)
         .map_err(|e| e.to_string())?;
 
-    // Prepare shared data for concurrent publishing
-    let video_path = main_task.video_path.clone();
-    let cover_path = main_task.cover_path.clone();
-    let title = main_task.title.clone();
-    let description = main_task.description.clone();
-    let hashtags = main_task.hashtags.clone();
+    Ok(Paginated::new(deliveries, total, page, page_size))
+}
 
-    // Use tokio::spawn for concurrent publishing
-    // Limit concurrency to avoid overwhelming the system
-    let semaphore = Arc::new(tokio::sync::Semaphore::new(3));
-    let app_handle_for_progress = app_handle.clone();
-    let task_id_str = task_id.to_string();
-    let mut handles = Vec::new();
+// ============================================================================
+// AI Caption Commands
+// AI 文案生成命令
+// ============================================================================
 
-    for account_detail in accounts_to_publish {
-        let db_manager = db_manager.clone();
-        let semaphore = semaphore.clone();
-        let video_path = video_path.clone();
-        let cover_path = cover_path.clone();
-        let title = title.clone();
-        let description = description.clone();
-        let hashtags = hashtags.clone();
-        let account_detail = account_detail.clone();
-        let app_handle_for_progress = app_handle_for_progress.clone();
-        let task_id = task_id_str.clone();
-
-        let handle = tokio::spawn(async move {
-            // Acquire permit before publishing
-            let _permit = semaphore.acquire().await.unwrap();
-
-            let detail_id = account_detail.id.clone();
-            let platform = account_detail.platform;
-            let account_id = account_detail.account_id.clone();
-
-            tracing::info!("[Publish] Starting publish for account: {}, platform: {:?}", account_id, platform);
-
-            // Build publish request with progress info
-            let request = PublishRequest {
-                account_id: account_id.clone(),
-                video_path: video_path.clone().into(),
-                cover_path: cover_path.clone().map(|p| p.into()),
-                title: title.clone(),
-                description: description.clone(),
-                hashtags: hashtags.clone(),
-                visibility_type: 0,
-                download_allowed: 0,
-                timeout: 0,
-                record_id: None,
-                send_time: None,
-                music_info: None,
-                poi_id: None,
-                poi_name: None,
-                anchor: None,
-                extra_info: None,
-                platform_data: None,
-                progress_info: Some((task_id.clone(), detail_id.clone(), account_id.clone(), app_handle_for_progress.clone())),
-            };
+/// Get the AI caption generation configuration
+/// 获取 AI 文案生成配置
+#[tauri::command]
+pub fn get_ai_config(app: AppHandle) -> Result<AiConfig, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_ai_config().map_err(|e| e.to_string())
+}
 
-            // Publish based on platform
-            let publish_result = match platform {
-                PlatformType::Douyin => {
-                    let douyin_platform = DouyinPlatform::with_storage((*db_manager).clone());
-                    douyin_platform.publish_video(request).await
-                }
-                _ => {
-                    return PublishTaskResult {
-                        success: false,
-                        detail_id,
-                        publish_url: None,
-                        error: Some(format!("Unsupported platform: {:?}", platform)),
-                    };
-                }
-            };
+/// Save the AI caption generation configuration
+/// 保存 AI 文案生成配置（接口地址需为兼容 OpenAI 的 API 根地址）
+#[tauri::command]
+pub fn save_ai_config(app: AppHandle, config: AiConfig) -> Result<AiConfig, String> {
+    let endpoint = config.endpoint.trim().trim_end_matches('/').to_string();
+    if !endpoint.is_empty() {
+        url::Url::parse(&endpoint).map_err(|e| format!("AI 接口地址无效: {}", e))?;
+    }
+    if config.enabled && (endpoint.is_empty() || config.api_key.trim().is_empty()) {
+        return Err("启用 AI 文案生成需要填写接口地址和 API Key".to_string());
+    }
 
-            // Process result
-            match publish_result {
-                Ok(result) => {
-                    let publish_url = result.item_id.clone()
-                        .map(|id| format!("https://v.douyin.com/{}", id));
+    let model = config.model.trim().to_string();
+    let config = AiConfig {
+        endpoint,
+        api_key: config.api_key.trim().to_string(),
+        model: if model.is_empty() { DEFAULT_AI_MODEL.to_string() } else { model },
+        ..config
+    };
+    let db_manager = shared_db_manager(&app);
+    db_manager.set_ai_config(&config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
 
-                    let new_status = if result.success {
-                        PublicationStatus::Completed
-                    } else {
-                        PublicationStatus::Failed
-                    };
+/// Generate title/description/hashtag candidates from a video file name or a topic
+/// 根据视频文件名或主题生成标题 / 描述 / 话题候选，供创建任务时挑选
+#[tauri::command]
+pub async fn generate_captions(
+    app: AppHandle,
+    video_path_or_topic: &str,
+    platform: &str,
+    count: Option<usize>,
+) -> Result<Vec<CaptionCandidate>, String> {
+    let _timer = CommandTimer::start("generate_captions");
+    let platform_type = platform.parse::<PlatformType>().map_err(|e| e.to_string())?;
+    let config = shared_db_manager(&app).get_ai_config().map_err(|e| e.to_string())?;
+    crate::core::generate_captions(&config, video_path_or_topic, &platform_type, count.unwrap_or(DEFAULT_CAPTION_CANDIDATES)).await
+}
 
-                    // Update account status with message and item_id
-                    let message = result.error_message.clone();
-                    let item_id = result.item_id.clone();
-
-                    if let Err(e) = db_manager.update_publication_account_status(
-                        &detail_id,
-                        new_status.clone(),
-                        publish_url.clone(),
-                        message.clone(),
-                        item_id.clone(),
-                    ) {
-                        tracing::error!("[Publish] Failed to update status for {}: {}", detail_id, e);
-                    } else {
-                        tracing::info!("[Publish] ✅ Account status updated: detail_id={}, status={:?}, item_id={:?}",
-                            detail_id, new_status, item_id);
-                    }
+// ============================================================================
+// App Settings Commands
+// 应用设置命令
+// ============================================================================
 
-                    // 进度事件由 strategy.rs 中的 emit_progress 发送
+/// Get application settings
+/// 获取应用设置
+#[tauri::command]
+pub fn get_app_settings(app: AppHandle) -> Result<AppSettings, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_app_settings()
+        .map_err(|e| e.to_string())
+}
 
-                    PublishTaskResult {
-                        success: result.success,
-                        detail_id,
-                        publish_url,
-                        error: message,
-                    }
-                }
-                Err(e) => {
-                    let error_msg = e.to_string();
-                    tracing::error!("[Publish] Publish failed for {}: {}", detail_id, error_msg);
-
-                    // Update account status with error message
-                    if let Err(e2) = db_manager.update_publication_account_status(
-                        &detail_id,
-                        PublicationStatus::Failed,
-                        None,
-                        Some(error_msg.clone()),
-                        None,
-                    ) {
-                        tracing::error!("[Publish] Failed to update status for {}: {}", detail_id, e2);
-                    } else {
-                        tracing::info!("[Publish] ✅ Account status updated to Failed: detail_id={}", detail_id);
-                    }
+/// Save application settings, returns the effective values
+/// 保存应用设置，返回实际生效的设置
+#[tauri::command]
+pub fn set_app_settings(app: AppHandle, settings: AppSettings) -> Result<AppSettings, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.save_app_settings(&settings)
+        .map_err(|e| e.to_string())
+}
 
-                    // 进度事件由 strategy.rs 中的 emit_progress 发送
+// ============================================================================
+// Cover Strategy Commands
+// 默认封面策略命令
+// ============================================================================
 
-                    PublishTaskResult {
-                        success: false,
-                        detail_id,
-                        publish_url: None,
-                        error: Some(error_msg),
-                    }
-                }
-            }
-        });
+/// Get the default cover strategy applied when a task has no cover
+/// 获取默认封面策略
+#[tauri::command]
+pub fn get_cover_strategy(app: AppHandle) -> Result<CoverStrategy, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_cover_strategy()
+        .map_err(|e| e.to_string())
+}
 
-        handles.push(handle);
+/// Set the default cover strategy
+/// 设置默认封面策略（platform_auto / first_frame / frame_at）
+#[tauri::command]
+pub fn set_cover_strategy(app: AppHandle, strategy: CoverStrategy) -> Result<(), String> {
+    if let CoverStrategy::FrameAt { seconds } = strategy {
+        if !seconds.is_finite() || seconds < 0.0 {
+            return Err("截帧时间必须是非负数".to_string());
+        }
     }
 
-    // Wait for all publishes to complete
-    let mut results: Vec<PublishTaskResult> = Vec::new();
-    for handle in handles {
-        match handle.await {
-            Ok(result) => results.push(result),
-            Err(e) => {
-                tracing::error!("[Publish] Task panicked: {}", e);
-                results.push(PublishTaskResult {
-                    success: false,
-                    detail_id: String::new(),
-                    publish_url: None,
-                    error: Some(format!("Publish task error: {}", e)),
-                });
-            }
-        }
+    let db_manager = shared_db_manager(&app);
+    db_manager.set_cover_strategy(&strategy)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the publish pacing rules
+/// 获取发布节奏规则（每账号 24 小时发布上限、最小发布间隔，可按平台覆盖）
+#[tauri::command]
+pub fn get_publish_pacing_rules(app: AppHandle) -> Result<PublishPacingRules, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_publish_pacing_rules()
+        .map_err(|e| e.to_string())
+}
+
+/// Set the publish pacing rules
+/// 设置发布节奏规则，0 表示不限
+#[tauri::command]
+pub fn set_publish_pacing_rules(app: AppHandle, rules: PublishPacingRules) -> Result<(), String> {
+    // 平台键统一为小写平台ID
+    let platforms = rules.platforms.into_iter()
+        .map(|(platform, rule)| platform.parse::<PlatformType>()
+            .map(|p| (p.as_str().to_string(), rule))
+            .map_err(|e| e.to_string()))
+        .collect::<Result<_, _>>()?;
+    let rules = PublishPacingRules { account: rules.account, platforms };
+    let max_interval = 24 * 60;
+    if std::iter::once(&rules.account).chain(rules.platforms.values()).any(|r| r.min_interval_minutes > max_interval) {
+        return Err(format!("最小发布间隔不能超过 {} 分钟", max_interval));
     }
 
-    // Calculate statistics
-    let success_count = results.iter().filter(|r| r.success).count();
-    let failed_count = results.len() - success_count;
-    let completed_accounts = task.accounts.len(); // All accounts accounted for
+    let db_manager = shared_db_manager(&app);
+    db_manager.set_publish_pacing_rules(&rules)
+        .map_err(|e| e.to_string())
+}
 
-    tracing::info!("[Publish] Results: {} success, {} failed, detail_ids: {:?}",
-        success_count, failed_count, results.iter().map(|r| &r.detail_id).collect::<Vec<_>>());
+// ============================================================================
+// Task Note Commands
+// 任务备注命令
+// ============================================================================
 
-    // Update main task status based on all account statuses
-    if let Err(e) = db_manager.update_task_status_from_accounts(task_id) {
-        tracing::error!("[Publish] Failed to update task status: {}", e);
-    } else {
-        tracing::info!("[Publish] Task status updated successfully");
+/// Add an internal note to a publication task
+/// 添加任务备注（如"客户要求周五重发"），仅供团队内部查看
+#[tauri::command]
+pub fn add_task_note(app: AppHandle, task_id: &str, content: &str, author: Option<&str>) -> Result<TaskNote, String> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Err("备注内容不能为空".to_string());
     }
 
-    tracing::info!("[Publish] Publish completed: {} success, {} failed", success_count, failed_count);
+    let db_manager = shared_db_manager(&app);
 
-    Ok(PublishProgressResult {
-        total_accounts: task.accounts.len(),
-        completed_accounts,
-        success_count,
-        failed_count,
-        results,
-    })
+    if db_manager.get_publication_task(task_id).map_err(|e| e.to_string())?.is_none() {
+        return Err("Task not found".to_string());
+    }
+
+    let note = TaskNote {
+        id: uuid::Uuid::new_v4().to_string(),
+        task_id: task_id.to_string(),
+        author: author.map(|a| a.trim().to_string()).unwrap_or_default(),
+        content: content.to_string(),
+        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    };
+
+    db_manager.add_task_note(&note).map_err(|e| e.to_string())?;
+    Ok(note)
 }
 
-/// Retry publishing for failed or pending accounts
-/// 重发失败的或未发布的账号
+/// Get all notes of a publication task
+/// 获取任务的所有备注
 #[tauri::command]
-pub async fn retry_publication_task(
-    window: tauri::Window,
-    task_id: &str,
-) -> Result<PublishProgressResult, String> {
-    tracing::info!("[Retry] Starting retry publish for task: {}", task_id);
+pub fn get_task_notes(app: AppHandle, task_id: &str) -> Result<Vec<TaskNote>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_task_notes(task_id)
+        .map_err(|e| e.to_string())
+}
 
-    // 使用 app_handle 发送进度事件到所有窗口
-    let app_handle = window.app_handle().clone();
-    let data_path = app_handle.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = Arc::new(DatabaseManager::new(data_path.clone()));
+/// Reassign a pending account publication to another account of the same platform
+/// 将未发布的账号详情改派到同平台的其他账号（原账号被删除或授权失效时使用），改派记录会保留
+#[tauri::command]
+pub fn reassign_publication_account(
+    app: AppHandle,
+    detail_id: &str,
+    account_id: &str,
+    reason: Option<&str>,
+) -> Result<PublicationAccountDetail, String> {
+    let db_manager = shared_db_manager(&app);
 
-    // Get accounts that need retry (Draft or Failed status)
-    let accounts_to_retry = db_manager.get_accounts_for_retry(task_id)
-        .map_err(|e| e.to_string())?;
+    let detail = db_manager.get_publication_account_detail(detail_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Publication account not found: {}", detail_id))?;
 
-    if accounts_to_retry.is_empty() {
-        tracing::info!("[Retry] No accounts need retry");
-        return Err("没有需要重发的账号".to_string());
+    if matches!(detail.status, PublicationStatus::Completed | PublicationStatus::Publishing) {
+        return Err("已发布或正在发布的账号不能改派".to_string());
+    }
+    if detail.account_id == account_id {
+        return Err("新账号与原账号相同".to_string());
     }
 
-    tracing::info!("[Retry] Found {} accounts to retry", accounts_to_retry.len());
+    let account = db_manager.get_account(account_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("账号不存在: {}", account_id))?;
+    if account.platform != detail.platform {
+        return Err(format!("账号 {} 属于{}，不能改派到{}任务", account.nickname, account.platform.display_name(), detail.platform.display_name()));
+    }
 
-    // Reset account statuses to Draft for retry
-    for account in &accounts_to_retry {
-        if let Err(e) = db_manager.reset_account_for_retry(&account.id) {
-            tracing::error!("[Retry] Failed to reset account {}: {}", account.id, e);
-        }
+    let task = db_manager.get_publication_task_with_accounts(&detail.publication_task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+    if task.accounts.iter().any(|a| a.account_id == account_id) {
+        return Err(format!("账号 {} 已在该任务中", account.nickname));
     }
 
-    // Update main task status to Publishing
-    db_manager.update_publication_task_status(task_id, PublicationStatus::Publishing)
+    let record = AccountReassignment {
+        id: uuid::Uuid::new_v4().to_string(),
+        detail_id: detail.id.clone(),
+        task_id: detail.publication_task_id.clone(),
+        from_account_id: detail.account_id.clone(),
+        from_account_name: detail.account_name.clone(),
+        to_account_id: account.id.clone(),
+        to_account_name: account.nickname.clone(),
+        reason: reason.map(|r| r.trim().to_string()).unwrap_or_default(),
+        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    };
+
+    if !db_manager.reassign_publication_account(&record).map_err(|e| e.to_string())? {
+        return Err("账号状态已变化，无法改派".to_string());
+    }
+    db_manager.update_task_status_from_accounts(&detail.publication_task_id)
         .map_err(|e| e.to_string())?;
 
-    // Get main task for video path and title
-    let main_task = match db_manager.get_publication_task(task_id).map_err(|e| e.to_string())? {
-        Some(t) => t,
-        None => return Err("Task not found".to_string()),
+    tracing::info!("[Reassign] detail_id={} {} -> {}", detail_id, record.from_account_name, record.to_account_name);
+
+    db_manager.get_publication_account_detail(detail_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Publication account not found: {}", detail_id))
+}
+
+/// Get the account reassignment history of a publication task
+/// 获取任务的账号改派记录
+#[tauri::command]
+pub fn get_account_reassignments(app: AppHandle, task_id: &str) -> Result<Vec<AccountReassignment>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_account_reassignments(task_id)
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Hashtag Set Commands
+// 话题库命令
+// ============================================================================
+
+/// Get all hashtag sets
+/// 获取所有话题库
+#[tauri::command]
+pub fn get_hashtag_sets(app: AppHandle) -> Result<Vec<HashtagSet>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_all_hashtag_sets()
+        .map_err(|e| e.to_string())
+}
+
+/// Create or update a hashtag set (no id creates a new set)
+/// 新建或更新话题库（id 为空时新建）
+#[tauri::command]
+pub fn save_hashtag_set(
+    app: AppHandle,
+    id: Option<&str>,
+    name: &str,
+    tags: Vec<String>,
+    platform_tags: HashMap<String, Vec<String>>,
+) -> Result<HashtagSet, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("话题库名称不能为空".to_string());
+    }
+
+    let db_manager = shared_db_manager(&app);
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    let existing = match id.filter(|id| !id.is_empty()) {
+        Some(id) => Some(db_manager.get_hashtag_set(id).map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("话题库不存在: {}", id))?),
+        None => None,
     };
 
-    // Prepare shared data for concurrent publishing
-    let video_path = main_task.video_path.clone();
-    let cover_path = main_task.cover_path.clone();
-    let title = main_task.title.clone();
-    let description = main_task.description.clone();
-    let hashtags = main_task.hashtags.clone();
+    // 去掉空白话题和 # 前缀，保留顺序
+    let normalize = |tags: Vec<String>| -> Vec<String> {
+        tags.into_iter()
+            .map(|t| t.trim().trim_start_matches('#').to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    };
 
-    // Use tokio::spawn for concurrent publishing
-    let semaphore = Arc::new(tokio::sync::Semaphore::new(3));
-    let app_handle_for_progress = app_handle.clone();
-    let task_id_str = task_id.to_string();
-    let mut handles = Vec::new();
+    let set = HashtagSet {
+        id: existing.as_ref().map(|s| s.id.clone()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name: name.to_string(),
+        tags: normalize(tags),
+        platform_tags: platform_tags.into_iter()
+            .map(|(platform, tags)| (platform.to_lowercase(), normalize(tags)))
+            .filter(|(_, tags)| !tags.is_empty())
+            .collect(),
+        created_at: existing.map(|s| s.created_at).unwrap_or_else(|| now.clone()),
+        updated_at: now,
+    };
 
-    for account_detail in accounts_to_retry {
-        let db_manager = db_manager.clone();
-        let semaphore = semaphore.clone();
-        let video_path = video_path.clone();
-        let cover_path = cover_path.clone();
-        let title = title.clone();
-        let description = description.clone();
-        let hashtags = hashtags.clone();
-        let app_handle_for_progress = app_handle_for_progress.clone();
-        let task_id = task_id_str.clone();
-
-        let handle = tokio::spawn(async move {
-            let _permit = semaphore.acquire().await.unwrap();
-
-            let detail_id = account_detail.id.clone();
-            let platform = account_detail.platform;
-            let account_id = account_detail.account_id.clone();
-
-            tracing::info!("[Retry] Retrying publish for account: {}, platform: {:?}", account_id, platform);
-
-            // Build publish request with progress info
-            let request = PublishRequest {
-                account_id: account_id.clone(),
-                video_path: video_path.clone().into(),
-                cover_path: cover_path.clone().map(|p| p.into()),
-                title: title.clone(),
-                description: description.clone(),
-                hashtags: hashtags.clone(),
-                visibility_type: 0,
-                download_allowed: 0,
-                timeout: 0,
-                record_id: None,
-                send_time: None,
-                music_info: None,
-                poi_id: None,
-                poi_name: None,
-                anchor: None,
-                extra_info: None,
-                platform_data: None,
-                progress_info: Some((task_id.clone(), detail_id.clone(), account_id.clone(), app_handle_for_progress.clone())),
-            };
+    db_manager.save_hashtag_set(&set).map_err(|e| e.to_string())?;
+    Ok(set)
+}
 
-            // Publish based on platform
-            let publish_result = match platform {
-                PlatformType::Douyin => {
-                    let douyin_platform = DouyinPlatform::with_storage((*db_manager).clone());
-                    douyin_platform.publish_video(request).await
-                }
-                _ => {
-                    return PublishTaskResult {
-                        success: false,
-                        detail_id,
-                        publish_url: None,
-                        error: Some(format!("Unsupported platform: {:?}", platform)),
-                    };
-                }
-            };
+/// Delete a hashtag set
+/// 删除话题库
+#[tauri::command]
+pub fn delete_hashtag_set(app: AppHandle, set_id: &str) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.delete_hashtag_set(set_id)
+        .map_err(|e| e.to_string())
+}
 
-            // Process result
-            match publish_result {
-                Ok(result) => {
-                    let publish_url = result.item_id.clone()
-                        .map(|id| format!("https://v.douyin.com/{}", id));
+/// Attach a hashtag set to a publication task (None to detach)
+/// 将话题库关联到发布任务（传 None 解除关联）
+#[tauri::command]
+pub fn attach_hashtag_set_to_task(app: AppHandle, task_id: &str, set_id: Option<&str>) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
 
-                    let new_status = if result.success {
-                        PublicationStatus::Completed
-                    } else {
-                        PublicationStatus::Failed
-                    };
+    let set = match set_id.filter(|id| !id.is_empty()) {
+        Some(set_id) => Some(db_manager.get_hashtag_set(set_id).map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("话题库不存在: {}", set_id))?),
+        None => None,
+    };
 
-                    let message = result.error_message.clone();
-                    let item_id = result.item_id.clone();
-
-                    if let Err(e) = db_manager.update_publication_account_status(
-                        &detail_id,
-                        new_status,
-                        publish_url.clone(),
-                        message.clone(),
-                        item_id.clone(),
-                    ) {
-                        tracing::error!("[Retry] Failed to update status for {}: {}", detail_id, e);
-                    }
+    db_manager.attach_hashtag_set_to_task(task_id, set.as_ref())
+        .map_err(|e| e.to_string())
+}
 
-                    // 进度事件由 strategy.rs 中的 emit_progress 发送
+/// Suggest previously used hashtags starting with the prefix
+/// 按前缀联想发布过的话题（使用次数多的在前）
+#[tauri::command]
+pub fn get_hashtag_suggestions(app: AppHandle, prefix: &str, limit: Option<usize>) -> Result<Vec<HashtagSuggestion>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_hashtag_suggestions(prefix, limit.unwrap_or(DEFAULT_HASHTAG_SUGGESTIONS))
+        .map_err(|e| e.to_string())
+}
 
-                    PublishTaskResult {
-                        success: result.success,
-                        detail_id,
-                        publish_url,
-                        error: message,
-                    }
-                }
-                Err(e) => {
-                    let error_msg = e.to_string();
-                    tracing::error!("[Retry] Publish failed for {}: {}", detail_id, error_msg);
-
-                    if let Err(e2) = db_manager.update_publication_account_status(
-                        &detail_id,
-                        PublicationStatus::Failed,
-                        None,
-                        Some(error_msg.clone()),
-                        None,
-                    ) {
-                        tracing::error!("[Retry] Failed to update status for {}: {}", detail_id, e2);
-                    }
+/// Usage count and aggregate publication stats of every hashtag
+/// 获取话题表现：使用次数及使用该话题的发布数据汇总
+#[tauri::command]
+pub fn get_hashtag_performance(app: AppHandle) -> Result<Vec<HashtagPerformance>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_hashtag_performance()
+        .map_err(|e| e.to_string())
+}
 
-                    // 进度事件由 strategy.rs 中的 emit_progress 发送
+// ============================================================================
+// Publication Template Commands
+// 发布模板命令
+// ============================================================================
 
-                    PublishTaskResult {
-                        success: false,
-                        detail_id,
-                        publish_url: None,
-                        error: Some(error_msg),
-                    }
-                }
-            }
-        });
+/// Get all publication templates
+/// 获取所有发布模板
+#[tauri::command]
+pub fn get_publication_templates(app: AppHandle) -> Result<Vec<PublicationTemplate>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_publication_templates()
+        .map_err(|e| e.to_string())
+}
 
-        handles.push(handle);
+/// Save a task's title/description/hashtags/accounts as a named template
+/// 将任务的标题、描述、话题和目标账号保存为模板（传入 template_id 时覆盖该模板）
+#[tauri::command]
+pub fn save_task_as_template(
+    app: AppHandle,
+    task_id: &str,
+    name: &str,
+    template_id: Option<&str>,
+) -> Result<PublicationTemplate, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("模板名称不能为空".to_string());
     }
 
-    // Wait for all publishes to complete
-    let mut results: Vec<PublishTaskResult> = Vec::new();
-    for handle in handles {
-        match handle.await {
-            Ok(result) => results.push(result),
-            Err(e) => {
-                tracing::error!("[Retry] Task panicked: {}", e);
-                results.push(PublishTaskResult {
-                    success: false,
-                    detail_id: String::new(),
-                    publish_url: None,
-                    error: Some(format!("Retry task error: {}", e)),
-                });
-            }
+    let db_manager = shared_db_manager(&app);
+    let task = db_manager.get_publication_task_with_accounts(task_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("任务不存在: {}", task_id))?;
+
+    let existing = match template_id.filter(|id| !id.is_empty()) {
+        Some(id) => Some(db_manager.get_publication_template(id).map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("模板不存在: {}", id))?),
+        None => None,
+    };
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+    let template = PublicationTemplate {
+        id: existing.as_ref().map(|t| t.id.clone()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name: name.to_string(),
+        title: task.title,
+        description: task.description,
+        hashtags: task.hashtags,
+        hashtag_set_id: task.hashtag_set_id.filter(|id| !id.is_empty()),
+        accounts: task.accounts.into_iter()
+            .map(|detail| TemplateAccount {
+                account_id: detail.account_id,
+                platform: detail.platform,
+                title_override: detail.title_override,
+                description_override: detail.description_override,
+                hashtags_override: detail.hashtags_override,
+            })
+            .collect(),
+        created_at: existing.map(|t| t.created_at).unwrap_or_else(|| now.clone()),
+        updated_at: now,
+    };
+
+    db_manager.save_publication_template(&template).map_err(|e| e.to_string())?;
+    tracing::info!("[Template] Saved template '{}' from task {}", template.name, task_id);
+    Ok(template)
+}
+
+/// Delete a publication template
+/// 删除发布模板
+#[tauri::command]
+pub fn delete_publication_template(app: AppHandle, template_id: &str) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.delete_publication_template(template_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Create a new draft task from a template with the given video
+/// 按模板创建新的草稿任务，只需提供视频；已删除的账号会被跳过
+#[tauri::command]
+pub fn create_task_from_template(
+    app: AppHandle,
+    template_id: &str,
+    video_path: &str,
+) -> Result<PublicationTaskWithAccounts, String> {
+    let db_manager = shared_db_manager(&app);
+    let template = db_manager.get_publication_template(template_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("模板不存在: {}", template_id))?;
+
+    let mut accounts = Vec::new();
+    for account in template.accounts {
+        if db_manager.get_account(&account.account_id).map_err(|e| e.to_string())?.is_none() {
+            tracing::warn!("[Template] Account {} no longer exists, skipped", account.account_id);
+            continue;
         }
+        accounts.push(TaskAccountInput {
+            account_id: account.account_id,
+            platform: account.platform,
+            overrides: TaskAccountOverrides {
+                title: account.title_override,
+                description: account.description_override,
+                hashtags: account.hashtags_override.unwrap_or_default(),
+            },
+        });
     }
-
-    // Calculate statistics
-    let success_count = results.iter().filter(|r| r.success).count();
-    let failed_count = results.len() - success_count;
-
-    // Get task with all accounts for final count
-    let task = db_manager.get_publication_task_with_accounts(task_id)
-        .map_err(|e| e.to_string())?
-        .unwrap_or_else(|| panic!("Task not found after retry"));
-
-    // Update main task status based on all account statuses
-    if let Err(e) = db_manager.update_task_status_from_accounts(task_id) {
-        tracing::error!("[Retry] Failed to update task status: {}", e);
+    if accounts.is_empty() {
+        return Err(format!("模板 {} 中的账号均已删除", template.name));
     }
 
-    tracing::info!("[Retry] Retry completed: {} success, {} failed", success_count, failed_count);
+    // 话题库已被删除时退回模板保存的话题
+    let hashtag_set_id = match template.hashtag_set_id {
+        Some(set_id) if db_manager.get_hashtag_set(&set_id).map_err(|e| e.to_string())?.is_some() => Some(set_id),
+        _ => None,
+    };
 
-    Ok(PublishProgressResult {
-        total_accounts: task.accounts.len(),
-        completed_accounts: task.accounts.len(),
-        success_count,
-        failed_count,
-        results,
+    create_publication_task(app.clone(), CreatePublicationTaskRequest {
+        title: template.title,
+        description: template.description,
+        video_path: video_path.to_string(),
+        accounts: Some(accounts),
+        hashtags: Some(TaskHashtagsInput::Task(template.hashtags)),
+        hashtag_set_id,
+        ..Default::default()
     })
 }
 
-#[tauri::command]
-pub fn publish_video(
-    app: AppHandle,
-    platform: &str,
-    account_id: &str,
-    video_path: &str,
-    title: &str,
-    description: &str,
-    hashtags: Vec<String>,
-) -> Result<PublishResult, String> {
-    let platform_type = match platform {
-        "douyin" => PlatformType::Douyin,
-        _ => return Err(format!("Unsupported platform: {}", platform)),
-    };
+// ============================================================================
+// Platform Module Version Commands
+// 平台模块版本命令
+// ============================================================================
 
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path.clone());
+/// Get the version and changelog of each platform publish strategy module
+/// 获取各平台发布策略模块的版本及变更记录
+#[tauri::command]
+pub fn get_platform_module_versions() -> Vec<PlatformModuleVersion> {
+    crate::platforms::platform_module_versions()
+}
 
-    let _account = match db_manager.get_account(account_id) {
-        Ok(Some(acc)) => acc,
-        Ok(None) => return Err("Account not found".to_string()),
-        Err(e) => return Err(format!("Database error: {}", e)),
-    };
+/// Per-command call counts and timings, slowest first
+/// 获取各命令的调用次数和耗时统计（按平均耗时倒序），用于定位拖慢界面的操作
+#[tauri::command]
+pub fn get_command_metrics() -> Vec<CommandMetric> {
+    command_metrics()
+}
 
-    let request = PublishRequest {
-        account_id: account_id.to_string(),
-        video_path: video_path.into(),
-        cover_path: None,
-        title: title.to_string(),
-        description: Some(description.to_string()),
-        hashtags,
-        visibility_type: 0,
-        download_allowed: 0,
-        timeout: 0,
-        record_id: None,
-        send_time: None,
-        music_info: None,
-        poi_id: None,
-        poi_name: None,
-        anchor: None,
-        extra_info: None,
-        platform_data: None,
-        progress_info: None,
+/// Recent log entries from the log files, for attaching to failure reports
+/// 获取最近的日志（`level` 为最低级别，默认 INFO；按时间正序），用于反馈发布失败时附带日志
+#[tauri::command]
+pub fn get_recent_logs(level: Option<&str>, limit: Option<usize>) -> Result<Vec<LogEntry>, String> {
+    let level = match level.filter(|l| !l.trim().is_empty()) {
+        Some(level) => level.trim().parse::<tracing::Level>()
+            .map_err(|_| format!("无效的日志级别: {}（可选 error / warn / info / debug / trace）", level))?,
+        None => tracing::Level::INFO,
     };
+    recent_logs(level, limit.unwrap_or(DEFAULT_RECENT_LOGS))
+}
 
-    match platform_type {
-        PlatformType::Douyin => {
-            let rt = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
-            let douyin_platform = DouyinPlatform::with_storage(db_manager);
-            rt.block_on(async {
-                douyin_platform.publish_video(request).await
-            })
-            .map_err(|e| e.to_string())
-        }
-        _ => Err("Unsupported platform".to_string()),
-    }
+/// Describe the versioned events pushed to the frontend
+/// 获取后端推送的事件清单（事件名、载荷版本、旧事件名、字段）
+#[tauri::command]
+pub fn get_event_schema() -> Vec<EventSchema> {
+    EVENT_SCHEMAS.to_vec()
 }
 
 // ============================================================================
-// Browser automation authentication commands
-// 浏览器自动化授权命令
+// Database Maintenance Commands
+// 数据库维护命令
 // ============================================================================
 
-/// 浏览器认证状态查询结果
-#[derive(Serialize, Clone)]
-pub struct BrowserAuthStatusResult {
-    pub step: String,
-    pub message: String,
-    pub current_url: String,
-    pub screenshot: Option<String>,
-    pub need_poll: bool,
-    pub cookie: String,
-    pub local_storage: String,
-    pub nickname: String,
-    pub avatar_url: String,
-    pub third_id: String,
-    pub sec_uid: String,
-    pub error: Option<String>,
+/// Verify the database schema and repair missing columns
+/// 校验数据库结构并补齐缺失字段，返回漂移报告
+#[tauri::command]
+pub fn check_database_schema(app: AppHandle) -> Result<SchemaReport, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.verify_schema()
+        .map_err(|e| e.to_string())
 }
 
-/// 启动浏览器授权流程
-/// 如果传入了 account_id，则会更新现有账号而不是创建新账号
+/// Get database lock contention counters since app start
+/// 获取数据库锁竞争统计（busy 次数、重试恢复/失败次数、是否处于串行写入）
 #[tauri::command]
-pub async fn start_browser_auth(_app: AppHandle, state: tauri::State<'_, AppState>, platform: &str, account_id: Option<&str>, _chrome_path: Option<&str>) -> Result<BrowserAuthStatusResult, String> {
+pub fn get_db_contention_stats() -> ContentionStats {
+    crate::storage::contention_stats()
+}
 
-    let mut automator = state.browser_automator.lock().await;
+/// Export an anonymized copy of the database for bug reports
+/// 导出匿名数据快照（去除凭证、昵称哈希化、素材路径脱敏），可直接附在问题反馈中。
+///
+/// 未指定 `output_path` 时弹出保存对话框，用户取消返回 None
+#[tauri::command]
+pub async fn export_anonymized_snapshot(app: AppHandle, output_path: Option<String>) -> Result<Option<String>, String> {
+    let _timer = CommandTimer::start("export_anonymized_snapshot");
+    let db_manager = shared_db_manager(&app);
 
-    // 使用通用规则引擎启动授权
-    automator.start_authorize(&state.db_manager, platform, account_id)
-        .await
-        .map_err(|e| format!("启动浏览器失败: {}", e))?;
-
-    let result = automator.get_result().clone();
-
-    // 如果在初始化阶段就完成了授权，保存到数据库
-
-    if matches!(result.step, BrowserAuthStep::Completed) && !result.cookie.is_empty() {
-
-        match save_browser_credentials(&_app, &result, platform, account_id) {
-            Ok(account) => {
-                tracing::info!("[Command] Account saved successfully: {}", account.nickname);
-                return Ok(BrowserAuthStatusResult {
-                    step: "Completed".to_string(),
-                    message: format!("授权完成！账号: {}", account.nickname),
-                    current_url: result.current_url,
-                    screenshot: result.screenshot,
-                    need_poll: false,
-                    cookie: result.cookie,
-                    local_storage: result.local_storage,
-                    nickname: result.nickname,
-                    avatar_url: result.avatar_url,
-                    third_id: result.third_id,
-                    sec_uid: result.sec_uid,
-                    error: None,
-                });
-            },
-            Err(e) => {
-                tracing::error!("[Command] Failed to save account: {}", e);
-                return Ok(BrowserAuthStatusResult {
-                    step: "Completed".to_string(),
-                    message: format!("授权完成但保存失败: {}", e),
-                    current_url: result.current_url,
-                    screenshot: result.screenshot,
-                    need_poll: false,
-                    cookie: result.cookie,
-                    local_storage: result.local_storage,
-                    nickname: result.nickname,
-                    avatar_url: result.avatar_url,
-                    third_id: result.third_id,
-                    sec_uid: result.sec_uid,
-                    error: Some(e),
-                });
+    let output_path = match output_path.filter(|p| !p.is_empty()) {
+        Some(path) => path,
+        None => {
+            let file_name = format!("匿名快照_{}.db", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+            match dialog::save_file(&app, "导出匿名数据快照", &file_name, ("SQLite", &["db"])).await? {
+                Some(file) => file.to_string_lossy().to_string(),
+                None => return Ok(None),
             }
         }
+    };
+
+    // VACUUM INTO 不会覆盖已有文件，保存对话框已确认覆盖
+    let output = std::path::Path::new(&output_path);
+    if output.exists() {
+        std::fs::remove_file(output).map_err(|e| format!("无法覆盖文件 {}: {}", output_path, e))?;
     }
 
-    Ok(BrowserAuthStatusResult {
-        step: format!("{:?}", result.step),
-        message: result.message,
-        current_url: result.current_url,
-        screenshot: result.screenshot,
-        need_poll: result.need_poll,
-        cookie: result.cookie,
-        local_storage: result.local_storage,
-        nickname: result.nickname,
-        avatar_url: result.avatar_url,
-        third_id: result.third_id,
-        sec_uid: result.sec_uid,
-        error: result.error,
-    })
+    let summary = db_manager.export_anonymized_snapshot(output)
+        .map_err(|e| e.to_string())?;
+    tracing::info!(
+        "[Storage] 已导出匿名数据快照到 {}: 账号 {}, 任务 {}, 发布明细 {}, 评论 {}, 移除设置 {}",
+        output_path, summary.accounts, summary.publication_tasks, summary.publication_details,
+        summary.comments, summary.removed_settings,
+    );
+    Ok(Some(output_path))
 }
 
-/// 检查浏览器授权状态并提取凭证
+/// Export accounts (with credentials), tasks, details, extractor configs and comments as an encrypted backup
+/// 导出加密备份（账号含凭证、发布任务与明细、提取引擎配置、评论），用于迁移到其他电脑。
+///
+/// 未指定 `path` 时弹出保存对话框，用户取消返回 None
 #[tauri::command]
-pub async fn check_browser_auth_status(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<BrowserAuthStatusResult, String> {
-    
-    let mut automator = state.browser_automator.lock().await;
+pub async fn export_backup(app: AppHandle, path: Option<String>, passphrase: String) -> Result<Option<BackupSummary>, String> {
+    let _timer = CommandTimer::start("export_backup");
+    let db_manager = shared_db_manager(&app);
 
-    // 检查登录状态并提取凭证
-    let need_poll = automator.check_and_extract()
-        .await
-        .map_err(|e| format!("检查状态失败: {}", e))?;
-
-    let result = automator.get_result().clone();
-
-    // 如果已完成，保存凭证到数据库
-    if !need_poll && !result.cookie.is_empty() {
-
-        // 从automator获取account_id
-        let account_id = automator.account_id.as_deref();
-        match save_browser_credentials(&app, &result, "douyin", account_id) {
-            Ok(account) => {
-                tracing::info!("[Command] Account saved successfully: id={}, nickname={}", account.id, account.nickname);
-                // 返回完成的账号信息
-                return Ok(BrowserAuthStatusResult {
-                    step: "Completed".to_string(),
-                    message: format!("授权成功！账号: {}", account.nickname),
-                    current_url: result.current_url,
-                    screenshot: result.screenshot,
-                    need_poll: false,
-                    cookie: result.cookie,
-                    local_storage: result.local_storage,
-                    nickname: result.nickname,
-                    avatar_url: result.avatar_url,
-                    third_id: result.third_id,
-                    sec_uid: result.sec_uid,
-                    error: None,
-                });
-            }
-            Err(e) => {
-                tracing::error!("[Command] Failed to save account: {}", e);
-                return Err(format!("保存凭证失败: {}", e));
+    let path = match path.filter(|p| !p.is_empty()) {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let file_name = format!("备份_{}.{}", chrono::Local::now().format("%Y%m%d_%H%M%S"), BACKUP_FILE_EXTENSION);
+            match dialog::save_file(&app, "导出备份", &file_name, ("备份文件", &[BACKUP_FILE_EXTENSION])).await? {
+                Some(file) => file,
+                None => return Ok(None),
             }
         }
-    }
+    };
 
-    Ok(BrowserAuthStatusResult {
-        step: format!("{:?}", result.step),
-        message: result.message,
-        current_url: result.current_url,
-        screenshot: result.screenshot,
-        need_poll,
-        cookie: result.cookie,
-        local_storage: result.local_storage,
-        nickname: result.nickname,
-        avatar_url: result.avatar_url,
-        third_id: result.third_id,
-        sec_uid: result.sec_uid,
-        error: result.error,
-    })
+    let summary = crate::core::export_backup(&db_manager, &path, &passphrase)?;
+    tracing::info!(
+        "[Backup] 已导出备份到 {}: 账号 {}, 任务 {}, 发布明细 {}, 评论 {}",
+        path.display(), summary.accounts, summary.publication_tasks, summary.publication_details, summary.comments,
+    );
+    Ok(Some(summary))
 }
 
-/// 取消浏览器授权
+/// Restore an encrypted backup created by `export_backup`
+/// 导入加密备份：同 id 的账号/任务等被备份中的版本覆盖，本机其余数据保留。
+///
+/// 未指定 `path` 时弹出文件选择对话框，用户取消返回 None
 #[tauri::command]
-pub async fn cancel_browser_auth(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut automator = state.browser_automator.lock().await;
-    automator.cancel().await;
-    Ok(())
-}
-
-/// 保存从浏览器提取的凭证到数据库
-/// 如果传入了 account_id，则更新现有账号而不是创建新账号
-fn save_browser_credentials(app: &AppHandle, result: &BrowserAuthResult, platform: &str, account_id: Option<&str>) -> Result<UserAccount, String> {
-    
-    // 构建 third_param - 直接使用 request_headers (JSON string)
-    let third_param: serde_json::Value = serde_json::from_str(&result.request_headers)
-        .unwrap_or(serde_json::json!({}));
-
-    // 添加 cookie 和 local_storage 到 third_param
-    let mut third_param_obj = third_param.as_object()
-        .cloned()
-        .unwrap_or_else(|| serde_json::Map::new());
-
-    third_param_obj.insert("cookie".to_string(), serde_json::json!(result.cookie));
-    third_param_obj.insert("local_data".to_string(), serde_json::json!(result.local_storage));
-
-    // 直接从 result 读取字段
-    let third_id = result.third_id.clone();
-    let nickname = if !result.nickname.is_empty() {
-        result.nickname.clone()
-    } else {
-        format!("{}用户", get_platform_name(platform))
-    };
-    let avatar_url = result.avatar_url.clone();
-
-    // 构建 params JSON
-    let params = serde_json::json!({
-        "third_id": third_id,
-        "sec_uid": result.sec_uid,
-        "third_param": serde_json::Value::Object(third_param_obj)
-    });
-
-    // 使用传入的account_id或生成新的UUID
-    let account_id = account_id.map(|s| s.to_string())
-        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-
-    let account = UserAccount {
-        id: account_id,
-        username: nickname.clone(),
-        nickname,
-        avatar_url,
-        platform: match platform {
-            "douyin" => PlatformType::Douyin,
-            "xiaohongshu" => PlatformType::Xiaohongshu,
-            "kuaishou" => PlatformType::Kuaishou,
-            "bilibili" => PlatformType::Bilibili,
-            _ => PlatformType::Douyin,
+pub async fn import_backup(app: AppHandle, path: Option<String>, passphrase: String) -> Result<Option<BackupSummary>, String> {
+    let _timer = CommandTimer::start("import_backup");
+    let db_manager = shared_db_manager(&app);
+
+    let path = match path.filter(|p| !p.is_empty()) {
+        Some(path) => std::path::PathBuf::from(path),
+        None => match dialog::pick_file(&app, "导入备份", &[BACKUP_FILE_EXTENSION]).await? {
+            Some(file) => file,
+            None => return Ok(None),
         },
-        params: params.to_string(),
-        status: AccountStatus::Active,
-        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
     };
 
-    // 保存到数据库
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path.clone());
-    db_manager.save_account(&account)
-        .map_err(|e| e.to_string())?;
-
-    Ok(account)
+    let summary = crate::core::import_backup(&db_manager, &path, &passphrase)?;
+    tracing::info!(
+        "[Backup] 已从 {} 导入备份（{} 导出）: 账号 {}, 任务 {}, 发布明细 {}, 评论 {}",
+        path.display(), summary.created_at, summary.accounts, summary.publication_tasks,
+        summary.publication_details, summary.comments,
+    );
+    Ok(Some(summary))
 }
 
-/// 获取平台名称
-fn get_platform_name(platform: &str) -> &'static str {
-    match platform {
-        "douyin" => "抖音",
-        "xiaohongshu" => "小红书",
-        "kuaishou" => "快手",
-        "bilibili" => "B站",
-        _ => "未知",
-    }
+/// Run the publish load test against a mock platform (developer build only)
+/// 开发用发布压测：模拟平台 + 临时数据库，报告吞吐量和锁竞争（需 `--features load-test` 编译）
+#[cfg(feature = "load-test")]
+#[tauri::command]
+pub async fn run_publish_load_test(config: LoadTestConfig) -> Result<LoadTestReport, String> {
+    let _timer = CommandTimer::start("run_publish_load_test");
+    run_load_test(config).await
 }
 
 // ============================================================================
-// Extractor Config Management Commands
-// 提取引擎配置管理命令
+// Scheduled Publishing Commands
+// 定时发布命令
 // ============================================================================
 
-/// 获取所有平台提取引擎配置
+/// Schedule a publication task to be published at a future time
+/// 定时发布任务（send_at 为本地时间 YYYY-MM-DD HH:MM:SS），应用重启后仍会执行
 #[tauri::command]
-pub fn get_extractor_configs(app: AppHandle) -> Result<Vec<serde_json::Value>, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
+pub fn schedule_publication_task(app: AppHandle, task_id: &str, send_at: &str) -> Result<ScheduledJob, String> {
+    let send_at = parse_send_at(send_at)?;
+    if send_at <= chrono::Local::now().naive_local() {
+        return Err("发布时间必须晚于当前时间".to_string());
+    }
 
-    db_manager.get_all_extractor_configs()
-        .map_err(|e| e.to_string())
-        .map(|configs| {
-            configs.into_iter().map(|c| {
-                serde_json::json!({
-                    "id": c.id,
-                    "platform_id": c.platform_id,
-                    "platform_name": c.platform_name,
-                    "login_url": c.login_url,
-                    "login_success_mode": c.login_success_mode,
-                    "login_success_pattern": c.login_success_pattern,
-                    "login_success_api_rule": c.login_success_api_rule,
-                    "login_success_api_operator": c.login_success_api_operator,
-                    "login_success_api_value": c.login_success_api_value,
-                    "redirect_url": c.redirect_url,
-                    "extract_rules": c.extract_rules,
-                    "is_default": c.is_default,
-                    "created_at": c.created_at,
-                    "updated_at": c.updated_at,
-                })
-            }).collect()
-        })
+    let db_manager = shared_db_manager(&app);
+
+    if db_manager.get_publication_task(task_id).map_err(|e| e.to_string())?.is_none() {
+        return Err("Task not found".to_string());
+    }
+
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let job = ScheduledJob {
+        id: uuid::Uuid::new_v4().to_string(),
+        task_id: task_id.to_string(),
+        send_at: send_at.format(SCHEDULE_TIME_FORMAT).to_string(),
+        status: ScheduledJobStatus::Pending,
+        message: None,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    db_manager.save_scheduled_job(&job).map_err(|e| e.to_string())?;
+    tracing::info!("[Scheduler] 已创建定时发布: task={}, send_at={}", job.task_id, job.send_at);
+    Ok(job)
 }
 
-/// 获取指定平台的提取引擎配置
+/// Get scheduled jobs, optionally for a single task
+/// 获取定时发布任务（可按任务过滤）
 #[tauri::command]
-pub fn get_extractor_config(app: AppHandle, platform_id: &str) -> Result<Option<serde_json::Value>, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-
-    db_manager.get_extractor_config(platform_id)
+pub fn get_scheduled_jobs(app: AppHandle, task_id: Option<&str>, page: Option<i64>, page_size: Option<i64>) -> Result<Paginated<ScheduledJob>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_scheduled_jobs(task_id)
+        .map(|jobs| Paginated::paginate(jobs, page, page_size))
         .map_err(|e| e.to_string())
-        .map(|config| {
-            config.map(|c| {
-                serde_json::json!({
-                    "id": c.id,
-                    "platform_id": c.platform_id,
-                    "platform_name": c.platform_name,
-                    "login_url": c.login_url,
-                    "login_success_mode": c.login_success_mode,
-                    "login_success_pattern": c.login_success_pattern,
-                    "login_success_api_rule": c.login_success_api_rule,
-                    "login_success_api_operator": c.login_success_api_operator,
-                    "login_success_api_value": c.login_success_api_value,
-                    "redirect_url": c.redirect_url,
-                    "extract_rules": c.extract_rules,
-                    "is_default": c.is_default,
-                    "created_at": c.created_at,
-                    "updated_at": c.updated_at,
-                })
-            })
-        })
 }
 
-/// 保存提取引擎配置
+/// Cancel a scheduled job that has not fired yet
+/// 取消尚未执行的定时发布
 #[tauri::command]
-pub fn save_extractor_config(
-    app: AppHandle,
-    platform_id: &str,
-    platform_name: &str,
-    login_url: &str,
-    login_success_mode: &str,
-    login_success_pattern: &str,
-    login_success_api_rule: Option<&str>,
-    login_success_api_operator: Option<&str>,
-    login_success_api_value: Option<&str>,
-    redirect_url: Option<&str>,
-    extract_rules: &str,
-) -> Result<bool, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
+pub fn cancel_scheduled_job(app: AppHandle, job_id: &str) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.cancel_scheduled_job(job_id)
+        .map_err(|e| e.to_string())
+}
 
-    // 解析 extract_rules JSON
-    let rules: serde_json::Value = serde_json::from_str(extract_rules)
-        .map_err(|e| format!("Invalid extract_rules JSON: {}", e))?;
+// ============================================================================
+// Publication Stats Commands
+// 作品数据刷新命令
+// ============================================================================
 
-    let config = ExtractorConfig {
-        id: format!("config_{}", platform_id),
-        platform_id: platform_id.to_string(),
-        platform_name: platform_name.to_string(),
-        login_url: login_url.to_string(),
-        login_success_mode: login_success_mode.to_string(),
-        login_success_pattern: login_success_pattern.to_string(),
-        login_success_api_rule: login_success_api_rule.map(|s| s.to_string()),
-        login_success_api_operator: login_success_api_operator.map(|s| s.to_string()),
-        login_success_api_value: login_success_api_value.map(|s| s.to_string()),
-        redirect_url: redirect_url.map(|s| s.to_string()),
-        extract_rules: rules,
-        is_default: false,
-        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-        updated_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+/// Result of refreshing the stats of a whole task
+/// 批量刷新作品数据结果
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicationStatsRefreshResult {
+    pub refreshed: usize,
+    pub failed: usize,
+    pub skipped: usize,  // 未完成发布、无作品ID或平台暂不支持
+    pub errors: Vec<String>,
+}
+
+/// 查询单个账号发布的最新互动数据并写回 publication_accounts
+async fn refresh_detail_stats(db_manager: &Arc<DatabaseManager>, detail: &PublicationAccountDetail) -> Result<PublicationStats, String> {
+    let item_id = detail.item_id.as_deref()
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| "该发布没有作品ID，无法刷新数据".to_string())?;
+
+    let stats = match &detail.platform {
+        PlatformType::Douyin => {
+            DouyinPlatform::with_storage((**db_manager).clone())
+                .fetch_item_stats(&detail.account_id, item_id).await
+                .map_err(|e| e.to_string())?
+        }
+        other => return Err(format!("{:?} 暂不支持刷新作品数据", other)),
     };
 
-    db_manager.save_extractor_config(&config)
+    db_manager.update_publication_account_stats(&detail.id, &stats)
         .map_err(|e| e.to_string())?;
 
-    Ok(true)
+    Ok(stats)
+}
+
+/// Refresh likes/comments/favorites/shares of one account publication from the platform
+/// 从平台刷新单个账号发布的互动数据（目前支持抖音）
+#[tauri::command]
+pub async fn refresh_publication_stats(app: AppHandle, detail_id: String) -> Result<PublicationAccountDetail, String> {
+    let _timer = CommandTimer::start("refresh_publication_stats");
+    let db_manager = shared_db_manager(&app);
+
+    let mut detail = db_manager.get_publication_account_detail(&detail_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Publication account not found: {}", detail_id))?;
+
+    detail.stats = refresh_detail_stats(&db_manager, &detail).await?;
+    tracing::info!("[Stats] 已刷新作品数据: detail_id={}, stats={:?}", detail_id, detail.stats);
+    Ok(detail)
+}
+
+/// Refresh the stats of every completed publication in a task
+/// 批量刷新任务下所有已发布成功的作品数据
+#[tauri::command]
+pub async fn refresh_task_publication_stats(app: AppHandle, task_id: String) -> Result<PublicationStatsRefreshResult, String> {
+    let _timer = CommandTimer::start("refresh_task_publication_stats");
+    let db_manager = shared_db_manager(&app);
+
+    let task = db_manager.get_publication_task_with_accounts(&task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Task not found".to_string())?;
+
+    let mut result = PublicationStatsRefreshResult { refreshed: 0, failed: 0, skipped: 0, errors: Vec::new() };
+    for detail in &task.accounts {
+        let has_item = detail.item_id.as_deref().is_some_and(|id| !id.is_empty());
+        if detail.status != PublicationStatus::Completed || !has_item || detail.platform != PlatformType::Douyin {
+            result.skipped += 1;
+            continue;
+        }
+
+        match refresh_detail_stats(&db_manager, detail).await {
+            Ok(_) => result.refreshed += 1,
+            Err(e) => {
+                tracing::warn!("[Stats] 刷新作品数据失败: detail_id={}, error={}", detail.id, e);
+                result.failed += 1;
+                result.errors.push(format!("{}: {}", detail.account_name, e));
+            }
+        }
+    }
+
+    tracing::info!("[Stats] 任务 {} 作品数据刷新完成: 成功 {}，失败 {}，跳过 {}",
+        task_id, result.refreshed, result.failed, result.skipped);
+    Ok(result)
 }
 
 // ============================================================================
-// File selection commands
-// 文件选择命令
+// Publish Log Commands
+// 发布日志实时查看命令
 // ============================================================================
 
-/// Select a file and return its path
-/// 选择文件并返回路径
+/// Start streaming the logs of one account publish as `publish-log` events
+/// 订阅指定发布明细的实时日志（前端监听 publish-log 事件）
 #[tauri::command]
-pub fn select_file(
-    _app: AppHandle,
-    title: &str,
-    filter_extensions: Option<Vec<&str>>,
-) -> Result<Option<String>, String> {
-    // Since we can't use tauri::api::dialog directly in commands,
-    // we'll return the filter info for frontend to handle
-    // Or we can use webview window to open dialog
-    let extensions = filter_extensions.unwrap_or_default();
-    Ok(Some(format!(
-        "FILE_DIALOG:{}:{}",
-        title,
-        extensions.join(",")
-    )))
+pub fn subscribe_publish_logs(app: AppHandle, detail_id: &str) -> Result<(), String> {
+    crate::core::subscribe_publish_logs(app, detail_id);
+    Ok(())
 }
 
-/// Result of file selection
-#[derive(Serialize, Clone)]
-pub struct FileSelectionResult {
-    pub path: String,
-    pub name: String,
+/// Stop streaming the logs of one account publish
+/// 取消订阅发布日志
+#[tauri::command]
+pub fn unsubscribe_publish_logs(detail_id: &str) -> Result<bool, String> {
+    Ok(crate::core::unsubscribe_publish_logs(detail_id))
 }
 
-/// Result of file selection with content for preview
-#[derive(Serialize, Clone)]
-pub struct FileSelectionWithContentResult {
-    pub path: String,
-    pub name: String,
-    pub content: String, // Base64 encoded content
-    pub mime_type: String,
-}
+// ============================================================================
+// Account Stats Commands
+// 账号数据统计命令
+// ============================================================================
 
-/// Open native file dialog and return selected file path
-/// 打开系统文件对话框并返回选中的文件路径
+/// Compare an account's engagement growth between two periods (e.g. week over week)
+/// 对比账号在两个周期内的互动增长（如周环比）
 #[tauri::command]
-pub async fn open_file_dialog(
-    _window: tauri::Window,
-    title: &str,
-    _multiple: bool,
-    filters: Option<Vec<String>>,
-) -> Result<Option<FileSelectionResult>, String> {
-    // Use rfd for native file dialog
-    let mut dialog = rfd::AsyncFileDialog::new()
-        .set_title(title);
+pub fn get_account_stats_delta(app: AppHandle, account_id: &str, period_a: StatsPeriod, period_b: StatsPeriod) -> Result<AccountStatsDelta, String> {
+    let db_manager = shared_db_manager(&app);
 
-    // Add filters
-    if let Some(filters_str) = filters {
-        let extensions: Vec<&str> = filters_str
-            .iter()
-            .flat_map(|f| f.split(','))
-            .map(|s| s.trim().trim_start_matches('.'))
-            .filter(|s| !s.is_empty())
-            .collect();
-        if !extensions.is_empty() {
-            dialog = dialog.add_filter("Files", &extensions);
-        }
+    if db_manager.get_account(account_id).map_err(|e| e.to_string())?.is_none() {
+        return Err("Account not found".to_string());
     }
 
-    // Single file selection for now
-    let result = dialog
-        .pick_file()
-        .await;
+    // 先记录当天快照，保证包含今天的周期能拿到最新数据
+    db_manager.record_account_stats_snapshots().map_err(|e| e.to_string())?;
+    db_manager.get_account_stats_delta(account_id, &period_a, &period_b)
+}
 
-    match result {
-        Some(file) => Ok(Some(FileSelectionResult {
-            path: file.path().to_string_lossy().to_string(),
-            name: file.file_name().to_string(),
-        })),
-        None => Ok(None),
-    }
+/// 未指定时趋势图查询的天数
+const DEFAULT_STATS_HISTORY_DAYS: u32 = 30;
+
+/// Refresh an account's follower count, total likes and video count from the platform
+/// 从平台刷新账号的粉丝数、获赞数、作品数，并写入当天的数据快照（目前支持抖音）
+#[tauri::command]
+pub async fn refresh_account_stats(app: AppHandle, account_id: &str) -> Result<AccountProfileStats, String> {
+    let _timer = CommandTimer::start("refresh_account_stats");
+
+    let db_manager = shared_db_manager(&app);
+    let account = db_manager.get_account(account_id).map_err(|e| e.to_string())?
+        .ok_or_else(|| "Account not found".to_string())?;
+
+    let stats = match account.platform {
+        PlatformType::Douyin => DouyinPlatform::with_storage((*db_manager).clone())
+            .get_account_stats(account_id).await,
+        PlatformType::Kuaishou => KuaishouPlatform::new().get_account_stats(account_id).await,
+        PlatformType::Bilibili => BilibiliPlatform::new().get_account_stats(account_id).await,
+        PlatformType::Xiaohongshu => XiaohongshuPlatform::new().get_account_stats(account_id).await,
+    }.map_err(|e| e.to_string())?;
+
+    // 先记录当天的互动快照，再写入主页数据，保证当天快照的各项指标完整
+    db_manager.record_account_stats_snapshots().map_err(|e| e.to_string())?;
+    db_manager.record_account_profile_stats(account_id, &stats).map_err(|e| e.to_string())?;
+    tracing::info!("[Stats] 已刷新账号数据: account_id={}, followers={}, total_likes={}, videos={}",
+        account_id, stats.followers, stats.total_likes, stats.video_count);
+
+    Ok(stats)
 }
 
-/// Open native file dialog and return file with content for preview
-/// 打开系统文件对话框并返回文件内容（用于前端预览）
+/// Get an account's daily stats snapshots for trend charts
+/// 获取账号最近 `days` 天（默认 30 天）的每日数据快照，按日期升序
 #[tauri::command]
-pub async fn select_file_with_content(
-    _window: tauri::Window,
-    title: &str,
-    file_type: &str, // "video" or "image"
-    _filters: Option<Vec<String>>,
-) -> Result<Option<FileSelectionWithContentResult>, String> {
-    // Use rfd for native file dialog
-    let mut dialog = rfd::AsyncFileDialog::new()
-        .set_title(title);
+pub fn get_account_stats_history(app: AppHandle, account_id: &str, days: Option<u32>) -> Result<Vec<AccountStatsSnapshot>, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_account_stats_history(account_id, days.unwrap_or(DEFAULT_STATS_HISTORY_DAYS).max(1))
+        .map_err(|e| e.to_string())
+}
 
-    // Add filters based on file type
-    let extensions: Vec<&str> = match file_type {
-        "video" => vec!["mp4", "mov", "avi", "mkv", "webm", "3gp", "m4v", "wmv"],
-        "image" => vec!["png", "jpg", "jpeg", "webp", "gif", "bmp", "tiff", "heic"],
-        _ => vec!["*"],
-    };
+/// Get an account's activity timeline: publishes, publish results, auth refreshes and stat milestones
+/// 获取账号动态时间线（发布、发布结果、授权刷新、数据里程碑），按时间倒序
+#[tauri::command]
+pub fn get_account_timeline(app: AppHandle, account_id: &str, limit: Option<usize>) -> Result<Vec<AccountTimelineEvent>, String> {
+    let db_manager = shared_db_manager(&app);
 
-    if !extensions.is_empty() {
-        dialog = dialog.add_filter("Files", &extensions);
+    if db_manager.get_account(account_id).map_err(|e| e.to_string())?.is_none() {
+        return Err("Account not found".to_string());
     }
 
-    let result = dialog
-        .pick_file()
-        .await;
+    db_manager.get_account_timeline(account_id, limit.unwrap_or(DEFAULT_TIMELINE_LIMIT).max(1))
+        .map_err(|e| e.to_string())
+}
 
-    match result {
-        Some(file) => {
-            let path = file.path().to_string_lossy().to_string();
-            let name = file.file_name().to_string();
+/// Account together with its rolling health score
+/// 账号及其健康度
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountOverview {
+    pub account: UserAccount,
+    pub health: AccountHealth,
+}
 
-            // Read file content
-            let bytes = tokio::fs::read(&path).await
-                .map_err(|e| format!("Failed to read file: {}", e))?;
+/// List accounts with a health score derived from recent publish outcomes
+/// 账号概览：账号信息 + 根据最近发布结果计算的健康分
+#[tauri::command]
+pub fn get_account_overview(app: AppHandle, page: Option<i64>, page_size: Option<i64>) -> Result<Paginated<AccountOverview>, String> {
+    let db_manager = shared_db_manager(&app);
+    let accounts = db_manager.get_all_accounts().map_err(|e| e.to_string())?;
+    let ids: Vec<String> = accounts.iter().map(|a| a.id.clone()).collect();
+    let health = db_manager.get_account_health(&ids).map_err(|e| e.to_string())?;
+
+    let overview = accounts.into_iter()
+        .zip(health)
+        .map(|(account, health)| AccountOverview { account, health })
+        .collect();
+    Ok(Paginated::paginate(overview, page, page_size))
+}
 
-            // Determine mime type
-            let mime_type = match file_type {
-                "video" => {
-                    let ext = std::path::Path::new(&path)
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .unwrap_or("");
-                    match ext.to_lowercase().as_str() {
-                        "mp4" => "video/mp4",
-                        "mov" => "video/quicktime",
-                        "avi" => "video/x-msvideo",
-                        "mkv" => "video/x-matroska",
-                        "webm" => "video/webm",
-                        "3gp" => "video/3gpp",
-                        "m4v" => "video/x-m4v",
-                        "wmv" => "video/x-ms-wmv",
-                        _ => "application/octet-stream",
-                    }.to_string()
-                }
-                "image" => {
-                    let ext = std::path::Path::new(&path)
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .unwrap_or("");
-                    match ext.to_lowercase().as_str() {
-                        "png" => "image/png",
-                        "jpg" | "jpeg" => "image/jpeg",
-                        "webp" => "image/webp",
-                        "gif" => "image/gif",
-                        "bmp" => "image/bmp",
-                        "tiff" | "tif" => "image/tiff",
-                        "heic" => "image/heic",
-                        _ => "application/octet-stream",
-                    }.to_string()
-                }
-                _ => "application/octet-stream".to_string(),
-            };
+/// Health warnings for the accounts selected for a new task
+/// 新建任务前检查所选账号，返回健康度为警告或危险的账号（健康或无记录的不返回）
+#[tauri::command]
+pub fn check_account_health(app: AppHandle, account_ids: Vec<String>) -> Result<Vec<AccountHealth>, String> {
+    if account_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let db_manager = shared_db_manager(&app);
+    Ok(db_manager.get_account_health(&account_ids)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|h| matches!(h.level, AccountHealthLevel::Warning | AccountHealthLevel::Critical))
+        .collect())
+}
 
-            // Encode to base64
-            let content = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
+/// Account health with the risk flags raised by recent publishes
+/// 账号健康报告项：健康度 + 风险原因
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountHealthReport {
+    pub account: UserAccount,
+    pub health: AccountHealth,
+    pub failure_rate: Option<u8>,  // 最近发布的失败率（百分比）
+    pub risk_flags: Vec<RiskFlag>,
+    pub risk_reasons: Vec<String>,  // 风险原因的显示名称
+}
 
-            Ok(Some(FileSelectionWithContentResult {
-                path,
-                name,
-                content,
-                mime_type,
-            }))
-        }
-        None => Ok(None),
+/// Health report of all accounts, risky accounts first
+/// 账号健康报告：已标记为风险或当前存在风险的账号排在前面，运营人员据此暂停发布或解除标记
+#[tauri::command]
+pub fn get_account_health_report(app: AppHandle) -> Result<Vec<AccountHealthReport>, String> {
+    let db_manager = shared_db_manager(&app);
+    let accounts = db_manager.get_all_accounts().map_err(|e| e.to_string())?;
+    let ids: Vec<String> = accounts.iter().map(|a| a.id.clone()).collect();
+    let health = db_manager.get_account_health(&ids).map_err(|e| e.to_string())?;
+
+    let mut report: Vec<AccountHealthReport> = accounts.into_iter()
+        .zip(health)
+        .map(|(account, health)| {
+            let risk_flags = health.risk_flags();
+            AccountHealthReport {
+                account,
+                failure_rate: health.failure_rate(),
+                risk_reasons: risk_flags.iter().map(|flag| risk_flag_label(*flag).to_string()).collect(),
+                risk_flags,
+                health,
+            }
+        })
+        .collect();
+    report.sort_by_key(|r| (r.account.status != AccountStatus::Risky, r.risk_flags.is_empty(), r.health.score.unwrap_or(100)));
+    Ok(report)
+}
+
+/// Clear the risky flag after the operator has checked the account
+/// 运营人员确认后解除账号的风险标记（恢复为正常）
+#[tauri::command]
+pub fn clear_account_risk(app: AppHandle, account_id: &str) -> Result<bool, String> {
+    let db_manager = shared_db_manager(&app);
+    match db_manager.get_account(account_id).map_err(|e| e.to_string())? {
+        Some(account) if account.status == AccountStatus::Risky => {}
+        Some(_) => return Ok(false),
+        None => return Err("Account not found".to_string()),
     }
+    db_manager.set_account_status(account_id, &AccountStatus::Active).map_err(|e| e.to_string())
 }
 
 // ============================================================================
-// Comment extraction commands
-// 评论提取命令
+// Publish Statistics Commands
+// 发布统计命令
 // ============================================================================
 
-/// Extract comments from a video
-/// 从视频提取评论
+/// Aggregate task/publication counts by status, platform, account and day for the dashboard
+/// 发布统计仪表盘：按状态、平台、账号、日期汇总任务和发布明细（日期 YYYY-MM-DD，默认最近 30 天）
 #[tauri::command]
-pub async fn extract_comments(
-    app: AppHandle,
-    detail_id: &str,  // publication_accounts 表的 id
-    aweme_id: &str,
-    max_count: i64,
-    cursor: i64,  // 分页游标，用于增量提取
-) -> Result<CommentExtractResult, String> {
-
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = Arc::new(DatabaseManager::new(data_path));
-
-    // 先从 publication_accounts 表获取真正的 account_id
-    let publication_account = match db_manager.get_publication_account_detail(detail_id) {
-        Ok(Some(acc)) => acc,
-        Ok(None) => {
-            tracing::error!("[Comment] 发布账号详情不存在: {}", detail_id);
-            return Err(format!("Publication account not found: {}", detail_id));
-        }
-        Err(e) => {
-            tracing::error!("[Comment] 查询发布账号详情失败: {:?}", e);
-            return Err(format!("Failed to query publication account: {:?}", e));
+pub fn get_publish_statistics(app: AppHandle, range: Option<StatsPeriod>) -> Result<PublishStatistics, String> {
+    let parse = |s: &str| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| format!("无效的日期: {}（格式应为 YYYY-MM-DD）", s));
+
+    let range = match range {
+        Some(range) => {
+            if parse(&range.start)? > parse(&range.end)? {
+                return Err("开始日期不能晚于结束日期".to_string());
+            }
+            range
         }
-    };
-
-    let account_id = &publication_account.account_id;
-
-    // Get account info to determine platform
-    let account = match db_manager.get_account(account_id).map_err(|e| e.to_string())? {
-        Some(acc) => acc,
         None => {
-            tracing::error!("[Comment] 账号不存在: {}", account_id);
-            return Err(format!("Account not found: {}", account_id));
+            let end = chrono::Local::now().date_naive();
+            StatsPeriod {
+                start: (end - chrono::Duration::days(29)).format("%Y-%m-%d").to_string(),
+                end: end.format("%Y-%m-%d").to_string(),
+            }
         }
     };
 
-    // Extract based on platform
-    match account.platform {
-        PlatformType::Douyin => {
-            let douyin_platform = DouyinPlatform::with_storage((*db_manager).clone());
-            match douyin_platform.extract_comments(account_id, aweme_id, max_count, cursor).await {
-                Ok(result) => {
-                    tracing::info!("[Comment] 提取成功: {} 条评论", result.comments.len());
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_publish_statistics(&range)
+        .map_err(|e| e.to_string())
+}
 
-                    // 提取成功后，更新 publication_accounts 表中的评论数
-                    if result.success {
-                        if let Err(e) = db_manager.update_publication_account_comment_count(aweme_id) {
-                            tracing::error!("[Comment] 更新评论数失败: {:?}", e);
-                        }
-                    }
+// ============================================================================
+// Failure Analytics Commands
+// 发布失败分析命令
+// ============================================================================
 
-                    Ok(result)
-                }
-                Err(e) => {
-                    tracing::error!("[Comment] 提取失败: {:?}", e);
-                    Err(e.to_string())
-                }
-            }
-        }
-        _ => {
-            tracing::error!("[Comment] 不支持的平台: {:?}", account.platform);
-            Err(format!("Unsupported platform for comment extraction: {:?}", account.platform))
-        }
+/// Break down failed publishes by normalized failure category, per day
+/// 按失败类别统计发布失败（日期 YYYY-MM-DD，默认最近 7 天）
+#[tauri::command]
+pub fn get_failure_breakdown(app: AppHandle, start: Option<String>, end: Option<String>) -> Result<FailureBreakdown, String> {
+    let parse = |s: &str| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| format!("无效的日期: {}（格式应为 YYYY-MM-DD）", s));
+
+    let end = match end {
+        Some(end) => parse(&end)?,
+        None => chrono::Local::now().date_naive(),
+    };
+    let start = match start {
+        Some(start) => parse(&start)?,
+        None => end - chrono::Duration::days(6),
+    };
+    if start > end {
+        return Err("开始日期不能晚于结束日期".to_string());
     }
+
+    let db_manager = shared_db_manager(&app);
+    db_manager.get_failure_breakdown(&start.format("%Y-%m-%d").to_string(), &end.format("%Y-%m-%d").to_string())
+        .map_err(|e| e.to_string())
 }
 
-/// Get comments by aweme_id with pagination
-/// 根据作品ID获取评论（分页）
+// ============================================================================
+// Cache Commands
+// 缓存管理命令
+// ============================================================================
+
+/// Get disk usage of each cache category
+/// 获取各缓存分类的占用情况
 #[tauri::command]
-pub fn get_comments_by_aweme_id(
-    app: AppHandle,
-    aweme_id: &str,
-    page: i64,
-    page_size: i64,
-) -> Result<PaginatedCommentsResponse, String> {
+pub fn get_cache_usage(app: AppHandle) -> Vec<CacheCategoryUsage> {
     let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-
-    tracing::info!("[Comment] 分页查询: aweme_id={}, page={}, page_size={}", aweme_id, page, page_size);
-
-    let offset = (page - 1) * page_size;
-    let comments = db_manager.get_comments_by_aweme_id_paginated(aweme_id, offset, page_size)
-        .map_err(|e| e.to_string())?;
+    CacheManager::new(&data_path).usage()
+}
 
-    let total = db_manager.get_comment_count(aweme_id)
-        .map_err(|e| e.to_string())?;
+/// Clear cached temp files and report reclaimed space
+/// 清空缓存（不传分类时清空全部），返回释放的空间
+#[tauri::command]
+pub fn clear_cache(app: AppHandle, category: Option<&str>) -> Result<CacheCleanupResult, String> {
+    let category = match category {
+        Some(c) => Some(CacheCategory::parse(c).ok_or_else(|| format!("未知的缓存分类: {}", c))?),
+        None => None,
+    };
 
-    Ok(PaginatedCommentsResponse { comments, total })
+    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
+    let result = CacheManager::new(&data_path).clear(category);
+    tracing::info!("[Cache] 清理缓存: 删除 {} 个文件, 释放 {} 字节", result.removed_files, result.reclaimed_bytes);
+    Ok(result)
 }
 
-/// Delete comments by aweme_id
-/// 根据作品ID删除评论
+// ============================================================================
+// Report Builder Commands
+// 自定义报表命令
+// ============================================================================
+
+/// Get entities and fields available to the report builder
+/// 获取报表构建器可用的实体及字段
 #[tauri::command]
-pub fn delete_comments(app: AppHandle, aweme_id: &str) -> Result<bool, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-    db_manager.delete_comments_by_aweme_id(aweme_id)
-        .map_err(|e| e.to_string())
+pub fn get_report_schema() -> Vec<ReportEntitySchema> {
+    crate::storage::report::report_schema()
 }
 
-/// Get comment count by aweme_id
-/// 根据作品ID获取评论数量
+/// Run a custom report query
+/// 执行自定义报表查询
 #[tauri::command]
-pub fn get_comment_count(app: AppHandle, aweme_id: &str) -> Result<i64, String> {
-    let data_path = app.path().app_data_dir().unwrap_or_else(|_| std::path::PathBuf::from("data"));
-    let db_manager = DatabaseManager::new(data_path);
-    db_manager.get_comment_count(aweme_id)
-        .map_err(|e| e.to_string())
+pub fn run_report(app: AppHandle, query: ReportQuery) -> Result<ReportResult, String> {
+    let db_manager = shared_db_manager(&app);
+    db_manager.run_report(&query)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish_retry_backoff() {
+        assert_eq!(publish_retry_delay(1), std::time::Duration::from_secs(10));
+        assert_eq!(publish_retry_delay(2), std::time::Duration::from_secs(20));
+        assert_eq!(publish_retry_delay(3), std::time::Duration::from_secs(40));
+        assert_eq!(publish_retry_delay(10), std::time::Duration::from_secs(120));
+    }
+}
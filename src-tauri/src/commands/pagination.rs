@@ -0,0 +1,85 @@
+// Pagination envelope shared by list commands
+// 列表命令统一的分页结构，前端可以复用同一个表格组件
+
+use serde::Serialize;
+
+/// 未指定页大小时的默认值
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+
+/// 页大小上限
+pub const MAX_PAGE_SIZE: i64 = 500;
+
+/// Paginated list response
+/// 分页响应
+#[derive(Debug, Clone, Serialize)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub page: i64,
+    pub page_size: i64,
+    pub has_more: bool,
+}
+
+/// 规范化分页参数，返回 (page, page_size)；页码从 1 开始
+pub fn normalize_page(page: Option<i64>, page_size: Option<i64>) -> (i64, i64) {
+    let page = page.unwrap_or(1).max(1);
+    let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    (page, page_size)
+}
+
+impl<T> Paginated<T> {
+    /// Wrap one page already fetched from the database
+    /// 包装数据库分页查询的结果
+    pub fn new(items: Vec<T>, total: i64, page: i64, page_size: i64) -> Self {
+        let has_more = (page - 1) * page_size + (items.len() as i64) < total;
+        Self { items, total, page, page_size, has_more }
+    }
+
+    /// Slice a fully loaded list; without a page the whole list is returned as one page
+    /// 对完整列表分页；page 和 page_size 都未指定时整个列表作为一页返回
+    pub fn paginate(all: Vec<T>, page: Option<i64>, page_size: Option<i64>) -> Self {
+        let total = all.len() as i64;
+        if page.is_none() && page_size.is_none() {
+            return Self { items: all, total, page: 1, page_size: total, has_more: false };
+        }
+
+        let (page, page_size) = normalize_page(page, page_size);
+        let items: Vec<T> = all.into_iter()
+            .skip(((page - 1) * page_size) as usize)
+            .take(page_size as usize)
+            .collect();
+        Self::new(items, total, page, page_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate_slices_and_reports_more() {
+        let page = Paginated::paginate((1..=45).collect::<Vec<i32>>(), Some(2), Some(20));
+        assert_eq!(page.items.first(), Some(&21));
+        assert_eq!(page.items.len(), 20);
+        assert_eq!(page.total, 45);
+        assert!(page.has_more);
+
+        let last = Paginated::paginate((1..=45).collect::<Vec<i32>>(), Some(3), Some(20));
+        assert_eq!(last.items.len(), 5);
+        assert!(!last.has_more);
+    }
+
+    #[test]
+    fn test_paginate_without_page_returns_all() {
+        let page = Paginated::paginate(vec![1, 2, 3], None, None);
+        assert_eq!(page.items, vec![1, 2, 3]);
+        assert_eq!(page.page_size, 3);
+        assert!(!page.has_more);
+    }
+
+    #[test]
+    fn test_normalize_page_clamps() {
+        assert_eq!(normalize_page(Some(0), Some(10_000)), (1, MAX_PAGE_SIZE));
+        assert_eq!(normalize_page(None, None), (1, DEFAULT_PAGE_SIZE));
+    }
+}
@@ -0,0 +1,68 @@
+//! Account Risk Monitoring
+//!
+//! 账号风险监控：每次账号发布结束后，根据该账号最近的发布结果（连续失败次数、失败率、
+//! 平台返回的风控 / 验证码响应）判断风险，出现风险的正常账号标记为 `Risky` 并发送通知，
+//! 运营人员在账号健康报告中查看原因后暂停发布（维护中）或解除标记。
+//! 风险只会自动标记、不会自动解除，避免账号在风控期间反复被使用。
+
+use crate::storage::{AccountHealth, DatabaseManager, RiskFlag};
+use super::{AccountStatus, NotificationKind, notify};
+
+/// 风险原因的显示名称
+pub fn risk_flag_label(flag: RiskFlag) -> &'static str {
+    match flag {
+        RiskFlag::ConsecutiveFailures => "连续发布失败",
+        RiskFlag::HighFailureRate => "发布失败率过高",
+        RiskFlag::RiskControl => "触发平台风控",
+    }
+}
+
+/// Re-evaluate the risk of the account behind a finished publication
+/// 账号发布结束后重新评估风险，正常账号出现风险时标记为 Risky（已标记、已过期的账号不变）
+pub fn check_publish_risk(db_manager: &DatabaseManager, detail_id: &str) {
+    let account_id = match db_manager.get_publication_account_detail(detail_id) {
+        Ok(Some(detail)) => detail.account_id,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!("[AccountMonitor] 读取发布明细失败: detail_id={}, error={}", detail_id, e);
+            return;
+        }
+    };
+
+    let account = match db_manager.get_account(&account_id) {
+        Ok(Some(account)) if account.status == AccountStatus::Active => account,
+        Ok(_) => return,
+        Err(e) => {
+            tracing::warn!("[AccountMonitor] 读取账号失败: account_id={}, error={}", account_id, e);
+            return;
+        }
+    };
+
+    let health: AccountHealth = match db_manager.get_account_health(std::slice::from_ref(&account_id)) {
+        Ok(mut health) if !health.is_empty() => health.remove(0),
+        Ok(_) => return,
+        Err(e) => {
+            tracing::warn!("[AccountMonitor] 计算账号健康度失败: account_id={}, error={}", account_id, e);
+            return;
+        }
+    };
+
+    let flags = health.risk_flags();
+    if flags.is_empty() {
+        return;
+    }
+
+    if let Err(e) = db_manager.set_account_status(&account_id, &AccountStatus::Risky) {
+        tracing::error!("[AccountMonitor] 标记风险账号失败: account_id={}, error={}", account_id, e);
+        return;
+    }
+
+    let reasons: Vec<&str> = flags.iter().map(|flag| risk_flag_label(*flag)).collect();
+    tracing::warn!("[AccountMonitor] 账号 {}（{}）已标记为风险账号: {}", account.nickname, account_id, reasons.join("、"));
+    notify(
+        db_manager,
+        NotificationKind::Publish,
+        "账号存在风险",
+        &format!("{}：{}，建议暂停发布", account.nickname, reasons.join("、")),
+    );
+}
@@ -0,0 +1,194 @@
+//! AI Caption Generation
+//!
+//! 可选的大模型集成：调用兼容 OpenAI 的 `POST {endpoint}/chat/completions` 接口，
+//! 根据视频文件名或主题为指定平台生成若干组标题 / 描述 / 话题候选，创建任务时供运营挑选后再发布。
+//!
+//! 接口地址、模型和 API Key 保存在设置中（`ai.config`），未启用时不会发出任何请求
+
+use std::path::Path;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use super::PlatformType;
+
+/// 默认模型
+pub const DEFAULT_AI_MODEL: &str = "gpt-4o-mini";
+
+/// 未指定数量时生成的候选数
+pub const DEFAULT_CAPTION_CANDIDATES: usize = 3;
+
+/// 单次最多生成的候选数
+const MAX_CAPTION_CANDIDATES: usize = 10;
+
+/// 请求超时（生成较慢，比普通接口宽松）
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// AI integration configuration
+/// 大模型接口配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AiConfig {
+    pub enabled: bool,
+    pub endpoint: String,          // 兼容 OpenAI 的接口地址，如 https://api.openai.com/v1
+    pub api_key: String,
+    pub model: String,
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: String::new(),
+            api_key: String::new(),
+            model: DEFAULT_AI_MODEL.to_string(),
+        }
+    }
+}
+
+/// Generated title/description/hashtags candidate
+/// 生成的文案候选
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptionCandidate {
+    pub title: String,
+    pub description: String,
+    pub hashtags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    #[serde(default)]
+    content: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CandidateList {
+    candidates: Vec<CaptionCandidate>,
+}
+
+/// 各平台标题字数上限（超出时平台会截断或拒绝）
+fn title_max_chars(platform: &PlatformType) -> usize {
+    match platform {
+        PlatformType::Douyin => 30,
+        PlatformType::Xiaohongshu => 20,
+        PlatformType::Kuaishou => 30,
+        PlatformType::Bilibili => 80,
+    }
+}
+
+/// 传入的是存在的视频文件时以文件名作为主题，否则直接作为主题
+fn caption_subject(video_path_or_topic: &str) -> String {
+    let path = Path::new(video_path_or_topic);
+    if path.is_file() {
+        if let Some(stem) = path.file_stem() {
+            return stem.to_string_lossy().replace('_', " ").trim().to_string();
+        }
+    }
+    video_path_or_topic.trim().to_string()
+}
+
+fn build_prompt(subject: &str, platform: &PlatformType, count: usize) -> String {
+    let platform_name = platform.display_name();
+    let title_max = title_max_chars(platform);
+    format!(
+        "请为一条发布到{platform_name}的短视频生成 {count} 组不同风格的文案。\n\
+         视频主题：{subject}\n\
+         要求：标题不超过 {title_max} 个字；描述 1~3 句话；每组 3~5 个话题，话题不带 # 号。\n\
+         只输出 JSON，格式为 {{\"candidates\": [{{\"title\": \"\", \"description\": \"\", \"hashtags\": []}}]}}"
+    )
+}
+
+/// 解析模型输出：兼容 ```json 代码块包裹以及直接返回数组的情况
+fn parse_candidates(content: &str) -> Result<Vec<CaptionCandidate>, String> {
+    let json = content.trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+
+    let candidates = serde_json::from_str::<CandidateList>(json)
+        .map(|list| list.candidates)
+        .or_else(|_| serde_json::from_str::<Vec<CaptionCandidate>>(json))
+        .map_err(|e| format!("无法解析模型返回的文案: {}", e))?;
+
+    Ok(candidates.into_iter()
+        .map(|c| CaptionCandidate {
+            title: c.title.trim().to_string(),
+            description: c.description.trim().to_string(),
+            hashtags: c.hashtags.iter()
+                .map(|t| t.trim().trim_start_matches('#').to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+        })
+        .filter(|c| !c.title.is_empty())
+        .collect())
+}
+
+/// Generate title/description/hashtag candidates for a platform
+/// 调用大模型生成文案候选
+pub async fn generate_captions(
+    config: &AiConfig,
+    video_path_or_topic: &str,
+    platform: &PlatformType,
+    count: usize,
+) -> Result<Vec<CaptionCandidate>, String> {
+    if !config.enabled {
+        return Err("未启用 AI 文案生成，请先在设置中配置接口".to_string());
+    }
+    if config.endpoint.trim().is_empty() || config.api_key.is_empty() {
+        return Err("AI 接口地址或 API Key 未配置".to_string());
+    }
+    let subject = caption_subject(video_path_or_topic);
+    if subject.is_empty() {
+        return Err("请提供视频文件或主题".to_string());
+    }
+    let count = count.clamp(1, MAX_CAPTION_CANDIDATES);
+
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("创建 AI 客户端失败: {}", e))?;
+    let url = format!("{}/chat/completions", config.endpoint.trim().trim_end_matches('/'));
+    let body = serde_json::json!({
+        "model": config.model,
+        "temperature": 0.8,
+        "messages": [
+            { "role": "system", "content": "你是短视频运营，擅长为国内短视频平台撰写吸引人的标题、描述和话题。" },
+            { "role": "user", "content": build_prompt(&subject, platform, count) },
+        ],
+    });
+
+    tracing::info!("[AI] 生成文案: platform={}, subject={}, count={}", platform, subject, count);
+    let response = client.post(&url)
+        .bearer_auth(&config.api_key)
+        .json(&body)
+        .send().await
+        .map_err(|e| format!("请求 AI 接口失败: {}", e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("AI 接口返回错误: HTTP {} {}", status, text.chars().take(200).collect::<String>()));
+    }
+    let chat: ChatResponse = response.json().await
+        .map_err(|e| format!("解析 AI 接口响应失败: {}", e))?;
+    let content = chat.choices.into_iter().next()
+        .map(|c| c.message.content)
+        .ok_or_else(|| "AI 接口没有返回内容".to_string())?;
+
+    let mut candidates = parse_candidates(&content)?;
+    if candidates.is_empty() {
+        return Err("AI 没有生成可用的文案".to_string());
+    }
+    candidates.truncate(count);
+    Ok(candidates)
+}
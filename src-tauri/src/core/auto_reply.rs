@@ -0,0 +1,212 @@
+//! Comment Auto-Reply
+//!
+//! 评论自动回复：用户为每个账号配置"关键词 → 回复模板"规则，
+//! 后台任务定期拉取已发布作品的新评论，命中规则的评论自动回复，
+//! 每条规则有每小时回复上限，所有回复记录写入 auto_reply_logs 表
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::core::Comment;
+use crate::storage::DatabaseManager;
+
+/// 两轮处理之间的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 只处理最近 N 天内发布的作品
+const RECENT_DAYS: i64 = 7;
+
+/// 每轮最多处理的作品数
+const BATCH_LIMIT: usize = 30;
+
+/// 相邻两次回复之间的间隔（避免短时间内连续回复触发风控）
+const REPLY_DELAY: Duration = Duration::from_secs(8);
+
+/// 每条规则默认每小时最多回复数
+pub const DEFAULT_REPLIES_PER_HOUR: i64 = 20;
+
+/// Auto-reply rule
+/// 自动回复规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoReplyRule {
+    pub id: String,
+    pub account_id: String,
+    pub name: String,
+    pub keywords: Vec<String>,     // 评论包含任一关键词即命中（不区分大小写）
+    pub reply_template: String,    // 支持 {nickname}、{content} 占位符
+    pub enabled: bool,
+    pub max_replies_per_hour: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl AutoReplyRule {
+    /// 评论内容是否命中规则
+    pub fn matches(&self, content: &str) -> bool {
+        let content = content.to_lowercase();
+        self.keywords.iter()
+            .map(|k| k.trim().to_lowercase())
+            .any(|k| !k.is_empty() && content.contains(&k))
+    }
+
+    /// 根据评论生成回复内容
+    pub fn render_reply(&self, comment: &Comment) -> String {
+        self.reply_template
+            .replace("{nickname}", &comment.user_nickname)
+            .replace("{content}", &comment.content)
+            .trim()
+            .to_string()
+    }
+}
+
+/// Sent auto-reply log entry
+/// 自动回复记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoReplyLog {
+    pub id: String,
+    pub rule_id: String,
+    pub account_id: String,
+    pub aweme_id: String,
+    pub comment_id: String,
+    pub comment_content: String,
+    pub reply_content: String,
+    pub success: bool,
+    pub error: Option<String>,
+    pub created_at: String,
+}
+
+/// A reply the worker wants to post
+/// 待发送的回复
+#[derive(Debug, Clone)]
+pub struct AutoReplyRequest {
+    pub account_id: String,
+    pub aweme_id: String,
+    pub comment_id: String,
+    pub text: String,
+}
+
+/// 启动自动回复后台任务
+///
+/// 每隔 POLL_INTERVAL：对有启用规则的账号，取其最近 RECENT_DAYS 天内发布成功的作品，
+/// 先调用 `sync_comments(detail_id, aweme_id)` 拉取新评论入库，再对尚未处理过的评论逐条匹配规则，
+/// 命中且未超过该规则每小时上限时调用 `send_reply` 发送回复。
+/// 只处理规则创建之后入库的评论，避免新建规则时批量回复历史评论。
+pub fn start_auto_reply_worker<S, SFut, R, RFut>(db_manager: Arc<DatabaseManager>, sync_comments: S, send_reply: R)
+where
+    S: Fn(String, String) -> SFut + Send + Sync + 'static,
+    SFut: Future<Output = Result<(), String>> + Send + 'static,
+    R: Fn(AutoReplyRequest) -> RFut + Send + Sync + 'static,
+    RFut: Future<Output = Result<(), String>> + Send + 'static,
+{
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let rules = match db_manager.get_enabled_auto_reply_rules() {
+                Ok(rules) => rules,
+                Err(e) => {
+                    tracing::error!("[AutoReply] 查询规则失败: {}", e);
+                    continue;
+                }
+            };
+            if rules.is_empty() {
+                continue;
+            }
+
+            let since = (chrono::Local::now() - chrono::Duration::days(RECENT_DAYS))
+                .format("%Y-%m-%d %H:%M:%S").to_string();
+            let detail_ids = match db_manager.get_recently_published_detail_ids(&since, BATCH_LIMIT) {
+                Ok(ids) => ids,
+                Err(e) => {
+                    tracing::error!("[AutoReply] 查询最近发布失败: {}", e);
+                    continue;
+                }
+            };
+
+            let mut sent = 0;
+            for detail_id in detail_ids {
+                let detail = match db_manager.get_publication_account_detail(&detail_id) {
+                    Ok(Some(detail)) => detail,
+                    _ => continue,
+                };
+                let account_rules: Vec<&AutoReplyRule> = rules.iter()
+                    .filter(|r| r.account_id == detail.account_id)
+                    .collect();
+                let Some(aweme_id) = detail.item_id.clone().filter(|_| !account_rules.is_empty()) else {
+                    continue;
+                };
+
+                if let Err(e) = sync_comments(detail_id.clone(), aweme_id.clone()).await {
+                    tracing::warn!("[AutoReply] 拉取评论失败: detail_id={}, error={}", detail_id, e);
+                    continue;
+                }
+
+                let comments = match db_manager.get_unreplied_comments(&detail.account_id, &aweme_id) {
+                    Ok(comments) => comments,
+                    Err(e) => {
+                        tracing::error!("[AutoReply] 查询待处理评论失败: {}", e);
+                        continue;
+                    }
+                };
+
+                for comment in comments {
+                    let Some(rule) = account_rules.iter()
+                        .find(|r| comment.created_at >= r.created_at && r.matches(&comment.content))
+                    else {
+                        continue;
+                    };
+
+                    let hour_ago = (chrono::Local::now() - chrono::Duration::hours(1))
+                        .format("%Y-%m-%d %H:%M:%S").to_string();
+                    match db_manager.count_auto_replies_since(&rule.id, &hour_ago) {
+                        Ok(count) if count >= rule.max_replies_per_hour => continue,
+                        Ok(_) => {}
+                        Err(e) => {
+                            tracing::error!("[AutoReply] 查询回复次数失败: {}", e);
+                            continue;
+                        }
+                    }
+
+                    let text = rule.render_reply(&comment);
+                    if text.is_empty() {
+                        continue;
+                    }
+
+                    let result = send_reply(AutoReplyRequest {
+                        account_id: detail.account_id.clone(),
+                        aweme_id: aweme_id.clone(),
+                        comment_id: comment.comment_id.clone(),
+                        text: text.clone(),
+                    }).await;
+
+                    if result.is_ok() {
+                        sent += 1;
+                    }
+                    let log = AutoReplyLog {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        rule_id: rule.id.clone(),
+                        account_id: detail.account_id.clone(),
+                        aweme_id: aweme_id.clone(),
+                        comment_id: comment.comment_id.clone(),
+                        comment_content: comment.content.clone(),
+                        reply_content: text,
+                        success: result.is_ok(),
+                        error: result.err(),
+                        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                    };
+                    if let Err(e) = db_manager.save_auto_reply_log(&log) {
+                        tracing::error!("[AutoReply] 保存回复记录失败: {}", e);
+                    }
+
+                    tokio::time::sleep(REPLY_DELAY).await;
+                }
+            }
+
+            if sent > 0 {
+                tracing::info!("[AutoReply] 本轮自动回复 {} 条评论", sent);
+            }
+        }
+    });
+}
@@ -0,0 +1,96 @@
+//! Avatar Cache
+//!
+//! 授权时提取的头像 URL 是带签名的 CDN 链接，过一段时间就会失效。
+//! 账号授权或刷新时把头像下载到 `<app_data>/avatars/<账号ID>`，
+//! 前端通过 `get_account_avatar` 以 base64 data URL 读取本地副本，不再依赖原链接。
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use base64::Engine;
+use crate::browser::BrowserFingerprint;
+use super::UserAccount;
+
+/// 头像目录名（位于应用数据目录下）
+const AVATARS_DIR: &str = "avatars";
+
+/// 头像大小上限
+const MAX_AVATAR_BYTES: usize = 2 * 1024 * 1024;
+
+/// 下载超时
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 账号头像的本地路径（账号ID中路径分隔符等字符替换为 `_`）
+fn avatar_path(base_path: &Path, account_id: &str) -> PathBuf {
+    let name: String = account_id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    base_path.join(AVATARS_DIR).join(name)
+}
+
+/// 根据文件头识别图片类型
+fn sniff_image_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// Download an account's avatar into the local cache
+/// 下载账号头像到本地缓存（覆盖旧文件），使用账号保存的 User-Agent
+pub async fn cache_account_avatar(base_path: &Path, account: &UserAccount) -> Result<PathBuf, String> {
+    if account.avatar_url.is_empty() {
+        return Err("账号没有头像地址".to_string());
+    }
+
+    let user_agent = BrowserFingerprint::from_account_params(&account.params).user_agent
+        .unwrap_or_else(|| crate::browser::cookie_import::DEFAULT_USER_AGENT.to_string());
+    let client = reqwest::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .user_agent(user_agent)
+        .build()
+        .map_err(|e| format!("创建下载客户端失败: {}", e))?;
+
+    let response = client.get(&account.avatar_url).send().await
+        .map_err(|e| format!("下载头像失败: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("下载头像失败: HTTP {}", response.status()));
+    }
+    let bytes = response.bytes().await.map_err(|e| format!("下载头像失败: {}", e))?;
+    if bytes.len() > MAX_AVATAR_BYTES {
+        return Err(format!("头像文件过大: {} 字节", bytes.len()));
+    }
+    if sniff_image_mime(&bytes).is_none() {
+        return Err("头像地址返回的不是图片（链接可能已过期）".to_string());
+    }
+
+    let path = avatar_path(base_path, &account.id);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("创建头像目录失败: {}", e))?;
+    }
+    // 先写临时文件再改名，读取方不会看到写了一半的图片
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, &bytes).map_err(|e| format!("保存头像失败: {}", e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("保存头像失败: {}", e))?;
+
+    tracing::info!("[Avatar] 已缓存账号头像: account_id={}, {} 字节", account.id, bytes.len());
+    Ok(path)
+}
+
+/// 读取已缓存的头像，返回 `data:<mime>;base64,...`
+pub fn cached_avatar_data_url(base_path: &Path, account_id: &str) -> Option<String> {
+    let bytes = std::fs::read(avatar_path(base_path, account_id)).ok()?;
+    let mime = sniff_image_mime(&bytes)?;
+    Some(format!("data:{};base64,{}", mime, base64::engine::general_purpose::STANDARD.encode(&bytes)))
+}
+
+/// 删除账号的头像缓存
+pub fn remove_cached_avatar(base_path: &Path, account_id: &str) {
+    let _ = std::fs::remove_file(avatar_path(base_path, account_id));
+}
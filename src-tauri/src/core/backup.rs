@@ -0,0 +1,84 @@
+//! Encrypted Backup
+//!
+//! 把账号（含 Cookie 等凭证）、发布任务、发布明细、提取引擎配置和评论导出为一个加密备份文件，
+//! 在另一台电脑上用同一备份密码导入，完成迁移。
+//!
+//! 文件为 JSON：`{ format, version, salt, payload }`，
+//! payload 为 [`BackupData`] 的 JSON 经备份密码派生密钥（每个文件随机盐）AES-256-GCM 加密后的 base64
+
+use std::path::Path;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use crate::storage::{BackupData, BackupSummary, DatabaseManager, BACKUP_VERSION};
+use super::crypto;
+
+/// 备份文件扩展名
+pub const BACKUP_FILE_EXTENSION: &str = "ammbak";
+
+/// 备份文件格式标识
+const BACKUP_FORMAT: &str = "auto-matrix-manager-backup";
+
+/// 备份密码最短长度
+const MIN_PASSPHRASE_LEN: usize = 6;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupArchive {
+    format: String,
+    version: u32,
+    salt: String,
+    payload: String,
+}
+
+fn check_passphrase(passphrase: &str) -> Result<(), String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!("备份密码至少需要{}个字符", MIN_PASSPHRASE_LEN));
+    }
+    Ok(())
+}
+
+/// Export an encrypted backup to `path`
+/// 导出加密备份到 `path`（已存在时覆盖）
+pub fn export_backup(db_manager: &DatabaseManager, path: &Path, passphrase: &str) -> Result<BackupSummary, String> {
+    check_passphrase(passphrase)?;
+
+    let data = db_manager.dump_backup_data().map_err(|e| e.to_string())?;
+    let counts = data.tables.iter().map(|(table, rows)| (table.clone(), rows.len())).collect();
+    let json = serde_json::to_string(&data).map_err(|e| e.to_string())?;
+
+    let salt = crypto::random_salt()?;
+    let key = crypto::derive_key(passphrase, &salt)?;
+    let archive = BackupArchive {
+        format: BACKUP_FORMAT.to_string(),
+        version: BACKUP_VERSION,
+        salt: base64::engine::general_purpose::STANDARD.encode(salt),
+        payload: crypto::encrypt_payload(&key, &json)?,
+    };
+
+    let content = serde_json::to_string(&archive).map_err(|e| e.to_string())?;
+    std::fs::write(path, content).map_err(|e| format!("写入备份文件失败: {}", e))?;
+
+    Ok(BackupSummary::from_counts(&data.created_at, &counts))
+}
+
+/// Import an encrypted backup from `path`
+/// 导入加密备份：同 id 的数据被备份中的版本覆盖，本机其余数据保留
+pub fn import_backup(db_manager: &DatabaseManager, path: &Path, passphrase: &str) -> Result<BackupSummary, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("读取备份文件失败: {}", e))?;
+    let archive: BackupArchive = serde_json::from_str(&content)
+        .ok()
+        .filter(|a: &BackupArchive| a.format == BACKUP_FORMAT)
+        .ok_or_else(|| "不是有效的备份文件".to_string())?;
+    if archive.version > BACKUP_VERSION {
+        return Err(format!("备份文件版本({})高于当前程序支持的版本({})，请先升级程序", archive.version, BACKUP_VERSION));
+    }
+
+    let salt = base64::engine::general_purpose::STANDARD.decode(&archive.salt)
+        .map_err(|_| "不是有效的备份文件".to_string())?;
+    let key = crypto::derive_key(passphrase, &salt)?;
+    let json = crypto::decrypt_payload(&key, &archive.payload)
+        .map_err(|e| if e == "解密失败" { "解密失败，请检查备份密码".to_string() } else { e })?;
+    let data: BackupData = serde_json::from_str(&json).map_err(|e| format!("备份数据格式错误: {}", e))?;
+
+    let counts = db_manager.restore_backup_data(&data).map_err(|e| e.to_string())?;
+    Ok(BackupSummary::from_counts(&data.created_at, &counts))
+}
@@ -0,0 +1,69 @@
+//! Platform Capabilities
+//!
+//! 各平台已知的上传限制（视频时长、文件大小），创建发布任务时据此提前拦截必然失败的账号。
+//! 视频信息由 [`super::media`] 读取；时长未知（ffprobe 不可用）时只校验文件大小
+
+use serde::Serialize;
+use super::{PlatformType, VideoInfo};
+
+const GB: u64 = 1024 * 1024 * 1024;
+
+/// Known upload limits of a platform
+/// 平台上传限制
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformLimits {
+    pub platform: PlatformType,
+    /// 最长视频时长（秒）
+    pub max_duration_secs: u64,
+    /// 最大文件大小（字节）
+    pub max_size_bytes: u64,
+}
+
+impl PlatformType {
+    /// Known upload limits (creator web upload)
+    /// 平台已知的网页端上传限制
+    pub fn limits(&self) -> PlatformLimits {
+        let (max_duration_secs, max_size_bytes) = match self {
+            PlatformType::Douyin => (60 * 60, 16 * GB),
+            PlatformType::Kuaishou => (15 * 60, 4 * GB),
+            PlatformType::Xiaohongshu => (60 * 60, 20 * GB),
+            PlatformType::Bilibili => (10 * 60 * 60, 16 * GB),
+        };
+        PlatformLimits { platform: self.clone(), max_duration_secs, max_size_bytes }
+    }
+}
+
+/// 按 mm:ss / h:mm:ss 格式化时长
+fn format_duration(secs: u64) -> String {
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// 校验视频是否超出平台限制，返回超限原因（未超限时为空）
+pub fn check_video_limits(info: &VideoInfo, limits: &PlatformLimits) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    // 能读到时长却没有视频流，多半是音频或损坏的文件
+    if info.duration_secs.is_some() && info.video_codec.is_none() {
+        violations.push("文件中没有视频流".to_string());
+    }
+    if info.size_bytes > limits.max_size_bytes {
+        violations.push(format!(
+            "文件大小 {:.2} GB 超过 {} GB 上限",
+            info.size_bytes as f64 / GB as f64,
+            limits.max_size_bytes / GB,
+        ));
+    }
+    if let Some(duration) = info.duration_secs.filter(|d| *d > limits.max_duration_secs as f64) {
+        violations.push(format!(
+            "时长 {} 超过 {} 上限",
+            format_duration(duration.ceil() as u64),
+            format_duration(limits.max_duration_secs),
+        ));
+    }
+
+    violations
+}
@@ -0,0 +1,176 @@
+//! Command Metrics
+//!
+//! 记录每个 Tauri 命令的调用次数和耗时，超过 [`SLOW_COMMAND_THRESHOLD`] 的调用以 WARN 级别写日志（附参数摘要），
+//! 通过 `get_command_metrics` 命令查看，用于定位让界面发卡的操作。
+//!
+//! - 同步命令在主线程上执行，[`timed_invoke_handler`] 包裹整个命令分发，记录的就是完整耗时；
+//! - 异步命令的分发只是把任务丢到后台，需要在命令开头创建 [`CommandTimer`]，命令结束（drop）时记录实际耗时。
+//!   出现第一条 CommandTimer 记录后，该命令改以执行耗时为准，分发耗时不再计入。
+//!
+//! 数据只保存在内存中，应用重启后清零
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::Runtime;
+
+/// 超过该耗时的命令视为慢命令
+pub const SLOW_COMMAND_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// 参数摘要中单个值的最大长度
+const ARG_VALUE_MAX_CHARS: usize = 40;
+
+/// 参数名包含这些词时不记录参数值
+const SENSITIVE_ARG_KEYS: [&str; 7] = ["cookie", "password", "passphrase", "token", "apikey", "params", "config"];
+
+/// Timing statistics of one command
+/// 单个命令的耗时统计
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CommandMetric {
+    pub command: String,
+    pub calls: u64,
+    pub total_ms: u64,
+    pub avg_ms: f64,
+    pub max_ms: u64,
+    pub last_ms: u64,
+    /// 超过慢命令阈值的调用次数
+    pub slow_calls: u64,
+    /// 最近一次调用的参数摘要（敏感参数已隐藏）
+    pub last_args: String,
+    pub last_called_at: String,
+    /// 是否为异步命令（耗时来自 CommandTimer）
+    pub asynchronous: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Dispatch,
+    Execution,
+}
+
+static METRICS: OnceLock<Mutex<HashMap<String, CommandMetric>>> = OnceLock::new();
+
+fn metrics() -> &'static Mutex<HashMap<String, CommandMetric>> {
+    METRICS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn with_entry<T>(command: &str, f: impl FnOnce(&mut CommandMetric) -> T) -> T {
+    let mut metrics = metrics().lock().unwrap_or_else(|e| e.into_inner());
+    let entry = metrics.entry(command.to_string())
+        .or_insert_with(|| CommandMetric { command: command.to_string(), ..Default::default() });
+    f(entry)
+}
+
+fn record(command: &str, elapsed: Duration, source: Source) {
+    with_entry(command, |entry| record_entry(entry, elapsed, source));
+}
+
+fn record_entry(entry: &mut CommandMetric, elapsed: Duration, source: Source) {
+    match source {
+        Source::Dispatch if entry.asynchronous => return,
+        // 第一条执行耗时：丢弃之前只包含分发耗时的统计
+        Source::Execution if !entry.asynchronous => {
+            *entry = CommandMetric {
+                command: entry.command.clone(),
+                last_args: std::mem::take(&mut entry.last_args),
+                asynchronous: true,
+                ..Default::default()
+            };
+        }
+        _ => {}
+    }
+
+    let ms = elapsed.as_millis() as u64;
+    entry.calls += 1;
+    entry.total_ms += ms;
+    entry.max_ms = entry.max_ms.max(ms);
+    entry.last_ms = ms;
+    entry.last_called_at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    if elapsed > SLOW_COMMAND_THRESHOLD {
+        entry.slow_calls += 1;
+        tracing::warn!("[Command] 慢命令 {} 耗时 {}ms, 参数: {}", entry.command, ms, entry.last_args);
+    }
+}
+
+/// 参数摘要：`name=value, ...`，值过长时截断，敏感参数只记录参数名
+fn summarize_args(body: &InvokeBody) -> String {
+    let args = match body {
+        InvokeBody::Json(serde_json::Value::Object(args)) => args,
+        InvokeBody::Json(_) => return String::new(),
+        InvokeBody::Raw(bytes) => return format!("<{} bytes>", bytes.len()),
+    };
+
+    args.iter()
+        .map(|(name, value)| {
+            let lower = name.to_lowercase();
+            if SENSITIVE_ARG_KEYS.iter().any(|key| lower.contains(key)) {
+                return format!("{}=***", name);
+            }
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            if value.chars().count() > ARG_VALUE_MAX_CHARS {
+                format!("{}={}…", name, value.chars().take(ARG_VALUE_MAX_CHARS).collect::<String>())
+            } else {
+                format!("{}={}", name, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Wrap the generated invoke handler to time every command dispatch
+/// 包裹 `generate_handler!` 生成的处理器，记录每次命令分发的耗时和参数摘要
+pub fn timed_invoke_handler<R, F>(handler: F) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        let command = invoke.message.command().to_string();
+        let args = summarize_args(invoke.message.payload());
+        // 先记下参数，异步命令可能在分发返回前就已执行完
+        with_entry(&command, |entry| entry.last_args = args);
+        let started = Instant::now();
+        let handled = handler(invoke);
+        record(&command, started.elapsed(), Source::Dispatch);
+        handled
+    }
+}
+
+/// Timer for an async command, recorded when dropped
+/// 异步命令计时器：在命令开头创建，命令返回时记录耗时
+pub struct CommandTimer {
+    command: &'static str,
+    started: Instant,
+}
+
+impl CommandTimer {
+    pub fn start(command: &'static str) -> Self {
+        Self { command, started: Instant::now() }
+    }
+}
+
+impl Drop for CommandTimer {
+    fn drop(&mut self) {
+        record(self.command, self.started.elapsed(), Source::Execution);
+    }
+}
+
+/// Timing statistics of all commands called so far, slowest (by average) first
+/// 获取所有已调用命令的耗时统计（按平均耗时倒序）
+pub fn command_metrics() -> Vec<CommandMetric> {
+    let metrics = metrics().lock().unwrap_or_else(|e| e.into_inner());
+    let mut list: Vec<CommandMetric> = metrics.values()
+        .cloned()
+        .map(|mut metric| {
+            metric.avg_ms = if metric.calls > 0 { metric.total_ms as f64 / metric.calls as f64 } else { 0.0 };
+            metric
+        })
+        .collect();
+    list.sort_by(|a, b| b.avg_ms.total_cmp(&a.avg_ms));
+    list
+}
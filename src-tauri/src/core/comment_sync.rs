@@ -0,0 +1,200 @@
+//! Incremental Comment Sync
+//!
+//! 后台定期为已发布成功的作品增量拉取评论：从上次保存的游标继续翻页，
+//! 只追加新评论（同一作品下已存在的 comment_id 跳过），进度记录在 comment_sync_state 表。
+//! 某个作品翻到最后一页后，下一轮从第一页重新扫描，遇到没有新评论的页即停止
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::core::{CommentExtractResult, NotificationKind, notify};
+use crate::storage::DatabaseManager;
+use super::stats_refresher::{is_rate_limited, jittered};
+
+/// 两轮同步之间的基础间隔
+const SYNC_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 限流时的最大退避间隔
+const MAX_BACKOFF: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 每轮最多同步的作品数
+const BATCH_LIMIT: usize = 30;
+
+/// 每个作品每轮最多翻的页数
+const MAX_PAGES_PER_ITEM: usize = 5;
+
+/// 每页拉取的评论数
+pub const COMMENT_SYNC_PAGE_SIZE: i64 = 50;
+
+/// 相邻两次请求之间的基础间隔
+const PAGE_DELAY: Duration = Duration::from_secs(4);
+
+/// Comment sync progress of one item
+/// 单个作品的评论同步进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentSyncState {
+    pub aweme_id: String,
+    pub detail_id: String,          // 最近一次同步使用的 publication_accounts.id
+    pub cursor: i64,                // 下一次同步的起始游标
+    pub has_more: bool,             // false 表示已翻到最后一页
+    pub synced_count: i64,          // 累计新增评论数
+    pub last_error: Option<String>,
+    pub last_synced_at: String,
+}
+
+/// One finished comment sync of an item
+/// 一次评论同步记录；new_count 为本次首次看到的评论数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentSyncRecord {
+    pub id: i64,
+    pub aweme_id: String,
+    pub new_count: i64,
+    pub synced_at: String,
+}
+
+/// 同步单个作品：返回本轮新增评论数
+async fn sync_item<F, Fut>(
+    db_manager: &DatabaseManager,
+    extract: &F,
+    detail_id: &str,
+    aweme_id: &str,
+) -> Result<i64, String>
+where
+    F: Fn(String, String, i64) -> Fut,
+    Fut: Future<Output = Result<CommentExtractResult, String>>,
+{
+    let previous = db_manager.get_comment_sync_state(aweme_id).map_err(|e| e.to_string())?;
+    let synced_count = previous.as_ref().map(|s| s.synced_count).unwrap_or(0);
+
+    // 已翻到最后一页则从头重新扫描新评论
+    let rescan = matches!(&previous, Some(state) if !state.has_more);
+    let mut cursor = previous.as_ref().filter(|_| !rescan).map(|s| s.cursor).unwrap_or(0);
+    let mut has_more = true;
+    let mut added = 0;
+    let mut error = None;
+
+    for page in 0..MAX_PAGES_PER_ITEM {
+        if page > 0 {
+            tokio::time::sleep(jittered(PAGE_DELAY, 0.5)).await;
+        }
+
+        let before = db_manager.get_comment_count(aweme_id).map_err(|e| e.to_string())?;
+        let result = match extract(detail_id.to_string(), aweme_id.to_string(), cursor).await {
+            Ok(result) if result.success => result,
+            Ok(result) => {
+                error = Some(result.error_message.unwrap_or_else(|| "提取评论失败".to_string()));
+                break;
+            }
+            Err(e) => {
+                error = Some(e);
+                break;
+            }
+        };
+        let new_comments = db_manager.get_comment_count(aweme_id).map_err(|e| e.to_string())? - before;
+
+        added += new_comments;
+        cursor = result.next_cursor;
+        has_more = result.has_more;
+
+        if !has_more || (rescan && new_comments == 0) {
+            break;
+        }
+    }
+
+    // 重新扫描时没有翻到末尾，保持"已同步完"状态，下一轮继续从头扫描
+    let has_more = has_more && !rescan;
+
+    let state = CommentSyncState {
+        aweme_id: aweme_id.to_string(),
+        detail_id: detail_id.to_string(),
+        cursor,
+        has_more,
+        synced_count: synced_count + added,
+        last_error: error.clone(),
+        last_synced_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    };
+    db_manager.save_comment_sync_state(&state).map_err(|e| e.to_string())?;
+
+    // 本轮（以及两轮之间手动提取）新入库的评论归入这次同步，供"上次同步以来的新评论"查询
+    if let Err(e) = db_manager.record_comment_sync(aweme_id) {
+        tracing::warn!("[CommentSync] 记录同步批次失败 {}: {}", aweme_id, e);
+    }
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(added),
+    }
+}
+
+/// 启动评论增量同步
+///
+/// 每轮取最多 BATCH_LIMIT 个已发布作品（从未同步或最久未同步的优先），
+/// 调用 `extract(detail_id, aweme_id, cursor)` 拉取一页评论并入库。
+/// 遇到平台限流时结束本轮并把间隔翻倍（不超过 MAX_BACKOFF）。
+pub fn start_comment_sync<F, Fut>(db_manager: Arc<DatabaseManager>, extract: F)
+where
+    F: Fn(String, String, i64) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<CommentExtractResult, String>> + Send + 'static,
+{
+    tauri::async_runtime::spawn(async move {
+        // 错开应用启动时的其他请求
+        tokio::time::sleep(jittered(Duration::from_secs(300), 0.5)).await;
+
+        let mut interval = SYNC_INTERVAL;
+        loop {
+            let targets = match db_manager.get_comment_sync_targets(BATCH_LIMIT) {
+                Ok(targets) => targets,
+                Err(e) => {
+                    tracing::error!("[CommentSync] 查询待同步作品失败: {}", e);
+                    Vec::new()
+                }
+            };
+
+            let mut added = 0;
+            let mut failed = 0;
+            let mut rate_limited = false;
+            for (i, (detail_id, aweme_id)) in targets.iter().enumerate() {
+                if i > 0 {
+                    tokio::time::sleep(jittered(PAGE_DELAY, 0.5)).await;
+                }
+
+                match sync_item(&db_manager, &extract, detail_id, aweme_id).await {
+                    Ok(count) => added += count,
+                    Err(e) if is_rate_limited(&e) => {
+                        tracing::warn!("[CommentSync] 平台限流，暂停本轮同步: {}", e);
+                        rate_limited = true;
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::warn!("[CommentSync] 同步失败: aweme_id={}, error={}", aweme_id, e);
+                        failed += 1;
+                    }
+                }
+            }
+
+            interval = if rate_limited {
+                (interval * 2).min(MAX_BACKOFF)
+            } else {
+                SYNC_INTERVAL
+            };
+
+            if !targets.is_empty() {
+                tracing::info!("[CommentSync] 本轮同步 {} 个作品，新增 {} 条评论，下一轮约 {} 分钟后",
+                    targets.len(), added, interval.as_secs() / 60);
+            }
+
+            // 没有新评论也没有失败时不打扰
+            if rate_limited {
+                notify(&db_manager, NotificationKind::CommentSync, "评论同步已暂停",
+                    &format!("平台限流，约 {} 分钟后重试；本轮新增 {} 条评论", interval.as_secs() / 60, added));
+            } else if added > 0 || failed > 0 {
+                let heading = if failed > 0 { "评论同步部分失败" } else { "评论同步完成" };
+                notify(&db_manager, NotificationKind::CommentSync, heading,
+                    &format!("新增 {} 条评论，{} 个作品同步失败", added, failed));
+            }
+
+            tokio::time::sleep(jittered(interval, 0.2)).await;
+        }
+    });
+}
@@ -0,0 +1,53 @@
+//! Comment Tagging
+//!
+//! 按可配置的关键词列表给评论打标签（如 negative / lead / spam），
+//! 便于运营在大量评论中快速筛出差评、意向客户和垃圾评论
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+/// Keyword lists per tag
+/// 标签 → 关键词列表，评论包含任一关键词即打上该标签（不区分大小写）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CommentTagRules(pub BTreeMap<String, Vec<String>>);
+
+impl Default for CommentTagRules {
+    fn default() -> Self {
+        let rule = |keywords: &[&str]| keywords.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+        Self(BTreeMap::from([
+            ("negative".to_string(), rule(&["差评", "垃圾", "骗人", "假货", "退款", "失望", "难用", "不好用"])),
+            ("lead".to_string(), rule(&["多少钱", "价格", "怎么买", "在哪买", "链接", "购买", "联系方式", "怎么联系"])),
+            ("spam".to_string(), rule(&["加微", "加v", "兼职", "刷单", "免费领", "看我主页", "私我"])),
+        ]))
+    }
+}
+
+impl CommentTagRules {
+    /// 评论内容命中的标签（按标签名排序）
+    pub fn classify(&self, content: &str) -> Vec<String> {
+        let content = content.to_lowercase();
+        self.0.iter()
+            .filter(|(_, keywords)| {
+                keywords.iter()
+                    .map(|k| k.trim().to_lowercase())
+                    .any(|k| !k.is_empty() && content.contains(&k))
+            })
+            .map(|(tag, _)| tag.clone())
+            .collect()
+    }
+
+    /// 去掉空标签和空关键词
+    pub fn normalized(self) -> Self {
+        Self(self.0.into_iter()
+            .map(|(tag, keywords)| {
+                let keywords = keywords.into_iter()
+                    .map(|k| k.trim().to_string())
+                    .filter(|k| !k.is_empty())
+                    .collect::<Vec<_>>();
+                (tag.trim().to_string(), keywords)
+            })
+            .filter(|(tag, keywords)| !tag.is_empty() && !keywords.is_empty())
+            .collect())
+    }
+}
@@ -0,0 +1,63 @@
+//! Default Cover Selection
+//!
+//! 任务未指定封面时的默认封面策略：交给平台自动选择、取视频首帧、或取第 N 秒的画面。
+//! 截帧通过 ffmpeg 完成，产物写入缓存的 thumbnails 分类目录
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+
+/// Default cover strategy
+/// 默认封面策略
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum CoverStrategy {
+    /// 不上传封面，由平台自动选择
+    #[default]
+    PlatformAuto,
+    /// 视频首帧
+    FirstFrame,
+    /// 指定秒数处的画面
+    FrameAt { seconds: f64 },
+}
+
+impl CoverStrategy {
+    /// 截帧时间点（秒），PlatformAuto 返回 None
+    pub fn frame_seconds(&self) -> Option<f64> {
+        match self {
+            CoverStrategy::PlatformAuto => None,
+            CoverStrategy::FirstFrame => Some(0.0),
+            CoverStrategy::FrameAt { seconds } => Some(seconds.max(0.0)),
+        }
+    }
+}
+
+/// 从视频中截取一帧作为封面
+///
+/// 输出文件名由视频路径和时间点决定，同一视频重复发布时直接复用已截取的封面
+pub fn extract_video_frame(video_path: &str, seconds: f64, output_dir: &Path) -> Result<PathBuf, String> {
+    if !Path::new(video_path).exists() {
+        return Err(format!("视频文件不存在: {}", video_path));
+    }
+
+    let key = format!("{:x}", md5::compute(format!("{}@{:.3}", video_path, seconds)));
+    let output = output_dir.join(format!("cover_{}.jpg", key));
+    if output.exists() {
+        return Ok(output);
+    }
+
+    let result = Command::new("ffmpeg")
+        .args(["-hide_banner", "-loglevel", "error", "-y"])
+        .args(["-ss", &format!("{:.3}", seconds)])
+        .args(["-i", video_path])
+        .args(["-frames:v", "1", "-q:v", "2"])
+        .arg(&output)
+        .output()
+        .map_err(|e| format!("无法启动 ffmpeg（请确认已安装并在 PATH 中）: {}", e))?;
+
+    if !result.status.success() || !output.exists() {
+        return Err(format!("截取封面失败: {}", String::from_utf8_lossy(&result.stderr).trim()));
+    }
+
+    Ok(output)
+}
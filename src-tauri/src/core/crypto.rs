@@ -0,0 +1,85 @@
+//! Crypto
+//!
+//! 口令派生密钥 + AES-256-GCM 加解密，供远程同步（账号数据）和加密备份共用。
+//!
+//! 密文格式：base64(nonce || tag || ciphertext)
+
+use aes_gcm::aead::{AeadInPlace, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce, Tag};
+use base64::Engine;
+use rand::RngCore;
+
+/// 密钥派生迭代次数（PBKDF2-HMAC-SHA256）
+const KEY_ITERATIONS: u32 = 100_000;
+
+/// AES-GCM nonce / tag 长度
+pub const NONCE_LEN: usize = 12;
+pub const TAG_LEN: usize = 16;
+
+/// 随机盐长度
+pub const SALT_LEN: usize = 16;
+
+/// 从口令派生 AES-256 密钥
+pub fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(passphrase.as_bytes(), salt, KEY_ITERATIONS, &mut key);
+    Ok(key)
+}
+
+/// 生成随机盐
+pub fn random_salt() -> Result<[u8; SALT_LEN], String> {
+    let mut salt = [0u8; SALT_LEN];
+    rand::rngs::OsRng.try_fill_bytes(&mut salt).map_err(|e| format!("生成随机数失败: {}", e))?;
+    Ok(salt)
+}
+
+/// 加密为 base64(nonce || tag || ciphertext)
+pub fn encrypt_payload(key: &[u8; 32], plain: &str) -> Result<String, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rngs::OsRng.try_fill_bytes(&mut nonce).map_err(|e| format!("生成随机数失败: {}", e))?;
+
+    let mut ciphertext = plain.as_bytes().to_vec();
+    let tag = Aes256Gcm::new(key.into())
+        .encrypt_in_place_detached(Nonce::from_slice(&nonce), &[], &mut ciphertext)
+        .map_err(|e| format!("加密失败: {}", e))?;
+
+    let mut data = Vec::with_capacity(NONCE_LEN + TAG_LEN + ciphertext.len());
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&tag);
+    data.extend_from_slice(&ciphertext);
+    Ok(base64::engine::general_purpose::STANDARD.encode(data))
+}
+
+/// 解密 [`encrypt_payload`] 的结果；口令错误或密文被篡改时返回 "解密失败"
+pub fn decrypt_payload(key: &[u8; 32], encoded: &str) -> Result<String, String> {
+    let data = base64::engine::general_purpose::STANDARD.decode(encoded)
+        .map_err(|e| format!("解码失败: {}", e))?;
+    if data.len() < NONCE_LEN + TAG_LEN {
+        return Err("密文长度不正确".to_string());
+    }
+
+    let (nonce, rest) = data.split_at(NONCE_LEN);
+    let (tag, ciphertext) = rest.split_at(TAG_LEN);
+    let mut plain = ciphertext.to_vec();
+    Aes256Gcm::new(key.into())
+        .decrypt_in_place_detached(Nonce::from_slice(nonce), &[], &mut plain, Tag::from_slice(tag))
+        .map_err(|_| "解密失败".to_string())?;
+
+    String::from_utf8(plain).map_err(|e| format!("解密结果不是有效文本: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_round_trip() {
+        let key = derive_key("口令", b"salt").unwrap();
+        let encrypted = encrypt_payload(&key, "账号数据").unwrap();
+        assert_ne!(encrypted, encrypt_payload(&key, "账号数据").unwrap());
+        assert_eq!(decrypt_payload(&key, &encrypted).unwrap(), "账号数据");
+
+        let wrong = derive_key("其他口令", b"salt").unwrap();
+        assert_eq!(decrypt_payload(&wrong, &encrypted), Err("解密失败".to_string()));
+    }
+}
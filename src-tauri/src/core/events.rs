@@ -0,0 +1,135 @@
+//! Versioned Frontend Events
+//!
+//! 推送到前端的事件统一使用带命名空间和版本号的事件名（如 `amm://publish/progress/v2`），
+//! 载荷带 `version` 字段，前后端可以各自演进：载荷不兼容地变更时递增版本并换用新事件名，
+//! 旧界面继续监听原事件名即可。Tauri 事件名只允许字母数字和 `-` `/` `:` `_`，因此版本号写作路径的最后一段。
+//!
+//! 迁移期间 [`emit_event`] 同时发送旧的无版本事件（如 `publish-progress`），
+//! 事件清单通过 `get_event_schema` 命令提供给前端
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use super::{PublishCaptchaEvent, PublishLogEntry, PublishProgressEvent, ReauthQueue};
+
+/// 发布进度（v1 为旧事件 `publish-progress` 的无版本载荷）
+pub const PUBLISH_PROGRESS_EVENT_V2: &str = "amm://publish/progress/v2";
+/// 发布日志
+pub const PUBLISH_LOG_EVENT_V1: &str = "amm://publish/log/v1";
+/// 发布等待验证（验证码 / 安全验证）
+pub const PUBLISH_CAPTCHA_EVENT_V1: &str = "amm://publish/captcha-required/v1";
+/// 视频转码进度
+pub const TRANSCODE_PROGRESS_EVENT_V1: &str = "amm://media/transcode-progress/v1";
+/// 批量重新授权队列
+pub const REAUTH_QUEUE_EVENT_V1: &str = "amm://account/reauth-queue/v1";
+/// 评论导出进度
+pub const COMMENT_EXPORT_PROGRESS_EVENT_V1: &str = "amm://comment/export-progress/v1";
+
+/// Payload of a namespaced, versioned event
+/// 可推送到前端的版本化事件载荷
+pub trait VersionedEvent: Serialize {
+    /// 带命名空间和版本号的事件名
+    const NAME: &'static str;
+    /// 载荷版本（写入载荷的 `version` 字段）
+    const VERSION: u32;
+    /// 迁移期间同时发送的旧事件名
+    const LEGACY_NAME: Option<&'static str> = None;
+}
+
+/// 版本化载荷：`{ "version": N, ...载荷字段 }`
+#[derive(Serialize)]
+struct Versioned<'a, T: Serialize> {
+    version: u32,
+    #[serde(flatten)]
+    payload: &'a T,
+}
+
+/// Emit a versioned event (and its legacy counterpart, if any)
+/// 发送版本化事件；有旧事件名时先以原载荷发送旧事件
+pub fn emit_event<E: VersionedEvent>(app: &AppHandle, event: &E) -> tauri::Result<()> {
+    if let Some(legacy) = E::LEGACY_NAME {
+        app.emit(legacy, event)?;
+    }
+    app.emit(E::NAME, Versioned { version: E::VERSION, payload: event })
+}
+
+/// Description of one frontend event
+/// 事件说明（供前端检查自己依赖的事件版本是否仍然存在）
+#[derive(Debug, Clone, Serialize)]
+pub struct EventSchema {
+    pub name: &'static str,
+    pub version: u32,
+    pub legacy_name: Option<&'static str>,
+    pub description: &'static str,
+    /// 载荷字段（不含 `version`）
+    pub fields: &'static [&'static str],
+}
+
+/// 当前发送的全部事件
+pub const EVENT_SCHEMAS: &[EventSchema] = &[
+    EventSchema {
+        name: PUBLISH_PROGRESS_EVENT_V2,
+        version: 2,
+        legacy_name: Some("publish-progress"),
+        description: "单个账号的发布进度",
+        fields: &["task_id", "detail_id", "account_id", "platform", "status", "message", "progress", "timestamp"],
+    },
+    EventSchema {
+        name: PUBLISH_LOG_EVENT_V1,
+        version: 1,
+        legacy_name: Some(super::PUBLISH_LOG_EVENT),
+        description: "已订阅账号的发布日志",
+        fields: &["detail_id", "level", "message", "timestamp"],
+    },
+    EventSchema {
+        name: PUBLISH_CAPTCHA_EVENT_V1,
+        version: 1,
+        legacy_name: Some("publish-captcha-required"),
+        description: "发布等待用户完成验证码 / 安全验证",
+        fields: &["task_id", "detail_id", "account_id", "platform", "verify_url", "message", "timestamp"],
+    },
+    EventSchema {
+        name: TRANSCODE_PROGRESS_EVENT_V1,
+        version: 1,
+        legacy_name: Some("video-transcode-progress"),
+        description: "视频转码进度（0-100）",
+        fields: &["source_path", "progress"],
+    },
+    EventSchema {
+        name: REAUTH_QUEUE_EVENT_V1,
+        version: 1,
+        legacy_name: Some(super::REAUTH_QUEUE_EVENT),
+        description: "批量重新授权队列状态",
+        fields: &["items", "cancelled"],
+    },
+    EventSchema {
+        name: COMMENT_EXPORT_PROGRESS_EVENT_V1,
+        version: 1,
+        legacy_name: Some("comment-export-progress"),
+        description: "单个作品评论导出进度",
+        fields: &["aweme_id", "written", "total"],
+    },
+];
+
+impl VersionedEvent for PublishProgressEvent {
+    const NAME: &'static str = PUBLISH_PROGRESS_EVENT_V2;
+    const VERSION: u32 = 2;
+    const LEGACY_NAME: Option<&'static str> = Some("publish-progress");
+}
+
+impl VersionedEvent for PublishLogEntry {
+    const NAME: &'static str = PUBLISH_LOG_EVENT_V1;
+    const VERSION: u32 = 1;
+    const LEGACY_NAME: Option<&'static str> = Some(super::PUBLISH_LOG_EVENT);
+}
+
+impl VersionedEvent for PublishCaptchaEvent {
+    const NAME: &'static str = PUBLISH_CAPTCHA_EVENT_V1;
+    const VERSION: u32 = 1;
+    const LEGACY_NAME: Option<&'static str> = Some("publish-captcha-required");
+}
+
+impl VersionedEvent for ReauthQueue {
+    const NAME: &'static str = REAUTH_QUEUE_EVENT_V1;
+    const VERSION: u32 = 1;
+    const LEGACY_NAME: Option<&'static str> = Some(super::REAUTH_QUEUE_EVENT);
+}
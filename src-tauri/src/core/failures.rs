@@ -0,0 +1,155 @@
+// Failure classification - the single keyword taxonomy for publish failure messages
+// 发布失败归类 - 发布失败信息的唯一一套关键字规则：
+// 同一条信息同时得到失败类别（失败分析、账号健康度）、错误码（前端处理建议）和是否临时性错误（自动重试），
+// 三者不会互相矛盾
+
+use super::PublishErrorCode;
+use serde::{Deserialize, Serialize};
+
+/// Failure category
+/// 失败类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureCategory {
+    /// 凭证过期 / 未登录
+    CredentialExpired,
+    /// 账号风控
+    RiskControl,
+    /// 超时
+    Timeout,
+    /// 网络或服务端错误
+    Network,
+    /// 视频 / 封面上传失败
+    UploadFailed,
+    /// 内容审核未通过
+    ContentRejected,
+    /// 操作频繁 / 限流
+    RateLimited,
+    /// 本地文件或参数问题
+    InvalidInput,
+    /// 其他
+    Other,
+}
+
+impl FailureCategory {
+    pub const ALL: [FailureCategory; 9] = [
+        FailureCategory::CredentialExpired,
+        FailureCategory::RiskControl,
+        FailureCategory::Timeout,
+        FailureCategory::Network,
+        FailureCategory::UploadFailed,
+        FailureCategory::ContentRejected,
+        FailureCategory::RateLimited,
+        FailureCategory::InvalidInput,
+        FailureCategory::Other,
+    ];
+
+    /// 显示名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            FailureCategory::CredentialExpired => "凭证过期",
+            FailureCategory::RiskControl => "账号风控",
+            FailureCategory::Timeout => "请求超时",
+            FailureCategory::Network => "网络异常",
+            FailureCategory::UploadFailed => "上传失败",
+            FailureCategory::ContentRejected => "内容审核",
+            FailureCategory::RateLimited => "操作频繁",
+            FailureCategory::InvalidInput => "文件或参数错误",
+            FailureCategory::Other => "其他",
+        }
+    }
+
+    /// 对应的发布错误码
+    fn error_code(&self) -> PublishErrorCode {
+        match self {
+            FailureCategory::CredentialExpired => PublishErrorCode::CookieExpired,
+            FailureCategory::UploadFailed => PublishErrorCode::UploadFailed,
+            FailureCategory::RiskControl => PublishErrorCode::RiskControl,
+            FailureCategory::RateLimited => PublishErrorCode::RateLimited,
+            FailureCategory::Network | FailureCategory::Timeout => PublishErrorCode::Network,
+            FailureCategory::ContentRejected | FailureCategory::InvalidInput | FailureCategory::Other => PublishErrorCode::Unknown,
+        }
+    }
+}
+
+/// Classification of a failure message
+/// 一条失败信息的归类结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FailureClass {
+    pub category: FailureCategory,
+    pub code: PublishErrorCode,
+    /// 临时性失败（网络异常、服务端 5xx、超时），稍后重试可能成功
+    pub transient: bool,
+}
+
+/// Classify a failure message by keyword (first match wins)
+/// 按关键字归类失败信息（按优先级匹配，先命中先返回）。
+/// 超时和网络错误排在上传失败之前：上传时连接中断归为网络异常，可以自动重试
+pub fn classify_failure(message: &str) -> FailureClass {
+    static SERVER_ERROR: once_cell::sync::Lazy<regex::Regex> = once_cell::sync::Lazy::new(|| {
+        regex::Regex::new(r"(?i)(HTTP|响应码:?)\s*5\d{2}").unwrap()
+    });
+
+    let lower = message.to_lowercase();
+    let has = |keywords: &[&str]| keywords.iter().any(|k| lower.contains(k));
+
+    let category = if has(&["风控", "risk"]) {
+        FailureCategory::RiskControl
+    } else if has(&["频繁", "限流", "too many", "rate limit", "429"]) {
+        FailureCategory::RateLimited
+    } else if has(&["authentication failed", "invalid credentials", "cookie", "未登录", "重新登录", "重新授权", "登录失效", "过期", "csrf"]) {
+        FailureCategory::CredentialExpired
+    } else if has(&["违规", "敏感", "审核", "不合规"]) {
+        FailureCategory::ContentRejected
+    } else if has(&["timed out", "timeout", "超时"]) {
+        FailureCategory::Timeout
+    } else if has(&["network error", "connection", "连接", "error sending request"]) || SERVER_ERROR.is_match(message) {
+        FailureCategory::Network
+    } else if has(&["invalid input", "不存在", "不能为空", "为空", "格式"]) {
+        FailureCategory::InvalidInput
+    } else if has(&["video upload failed", "上传"]) {
+        FailureCategory::UploadFailed
+    } else {
+        FailureCategory::Other
+    };
+
+    FailureClass {
+        category,
+        code: category.error_code(),
+        transient: matches!(category, FailureCategory::Network | FailureCategory::Timeout),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_failure() {
+        let category = |message: &str| classify_failure(message).category;
+        assert_eq!(category("Invalid credentials: 快手账号Cookie为空，请重新登录"), FailureCategory::CredentialExpired);
+        assert_eq!(category("Publication failed: 请去账号管理列表中解除风控[抖音]"), FailureCategory::RiskControl);
+        assert_eq!(category("Video upload failed: 上传视频失败: operation timed out"), FailureCategory::Timeout);
+        assert_eq!(category("Video upload failed: 所有分片上传失败"), FailureCategory::UploadFailed);
+        assert_eq!(category("Invalid input: 视频文件不存在: /tmp/a.mp4"), FailureCategory::InvalidInput);
+        assert_eq!(category("something odd"), FailureCategory::Other);
+    }
+
+    #[test]
+    fn test_code_and_transient_agree_with_category() {
+        let network = classify_failure("Network error: error sending request");
+        assert_eq!((network.code, network.transient), (PublishErrorCode::Network, true));
+
+        let server = classify_failure("Publication failed: 发布失败，HTTP 502");
+        assert_eq!((server.category, server.transient), (FailureCategory::Network, true));
+
+        let upload_reset = classify_failure("Video upload failed: connection reset by peer");
+        assert_eq!((upload_reset.category, upload_reset.transient), (FailureCategory::Network, true));
+
+        let rejected = classify_failure("Publication failed: 内容违规");
+        assert_eq!((rejected.code, rejected.transient), (PublishErrorCode::Unknown, false));
+
+        let expired = classify_failure("Authentication failed: 登录失效");
+        assert_eq!((expired.code, expired.transient), (PublishErrorCode::CookieExpired, false));
+    }
+}
@@ -0,0 +1,198 @@
+//! File Logging
+//!
+//! 将 tracing 日志按天写入应用数据目录下的 `logs/app-YYYY-MM-DD.log`，保留最近 [`LOG_RETENTION_DAYS`] 天，
+//! 用户反馈发布失败时可通过 `get_recent_logs` 取回日志附在问题中。
+//!
+//! 每行格式：`2024-03-01 12:00:00.123  INFO ThreadId(01) 消息`（本地时间，无颜色）；
+//! 多行消息的后续行归入上一条日志
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use serde::Serialize;
+use tracing::Level;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// 日志文件保留天数
+pub const LOG_RETENTION_DAYS: usize = 7;
+
+/// 未指定条数时返回的日志数
+pub const DEFAULT_RECENT_LOGS: usize = 200;
+
+/// 单次最多返回的日志数
+const MAX_RECENT_LOGS: usize = 5000;
+
+const LOG_FILE_PREFIX: &str = "app-";
+const LOG_FILE_SUFFIX: &str = ".log";
+
+/// 时间戳格式（与 `TIMESTAMP_LEN` 保持一致）
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+const TIMESTAMP_LEN: usize = 23;
+
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// One line (entry) of the log file
+/// 单条日志
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub message: String,
+}
+
+/// 以本地时间输出时间戳
+pub struct LocalTimer;
+
+impl FormatTime for LocalTimer {
+    fn format_time(&self, w: &mut Writer<'_>) -> std::fmt::Result {
+        write!(w, "{}", chrono::Local::now().format(TIMESTAMP_FORMAT))
+    }
+}
+
+/// Daily rotated log file writer for the tracing fmt layer
+/// 按天滚动的日志文件写入器（日期变化时切换文件并清理过期日志）
+pub struct RollingFileWriter {
+    dir: PathBuf,
+    current: Mutex<Option<(String, File)>>,
+}
+
+impl RollingFileWriter {
+    /// 创建写入器并记录日志目录，供 [`recent_logs`] 读取
+    pub fn new(dir: &Path) -> Self {
+        let _ = LOG_DIR.set(dir.to_path_buf());
+        Self { dir: dir.to_path_buf(), current: Mutex::new(None) }
+    }
+
+    fn write_all(&self, buf: &[u8]) -> io::Result<()> {
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+
+        if current.as_ref().map(|(date, _)| date != &today).unwrap_or(true) {
+            std::fs::create_dir_all(&self.dir)?;
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.dir.join(format!("{}{}{}", LOG_FILE_PREFIX, today, LOG_FILE_SUFFIX)))?;
+            *current = Some((today, file));
+            remove_expired_logs(&self.dir);
+        }
+
+        match current.as_mut() {
+            Some((_, file)) => file.write_all(buf),
+            None => Ok(()),
+        }
+    }
+}
+
+/// fmt layer 每条事件取一次写入器
+pub struct RollingFileGuard<'a>(&'a RollingFileWriter);
+
+impl Write for RollingFileGuard<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for RollingFileWriter {
+    type Writer = RollingFileGuard<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RollingFileGuard(self)
+    }
+}
+
+/// 日志文件按文件名（日期）倒序
+fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.file_name()
+                .map(|n| n.to_string_lossy())
+                .is_some_and(|n| n.starts_with(LOG_FILE_PREFIX) && n.ends_with(LOG_FILE_SUFFIX)))
+            .collect())
+        .unwrap_or_default();
+    files.sort();
+    files.reverse();
+    files
+}
+
+/// 删除超出保留天数的日志文件
+///
+/// 在写入器持有锁时调用，不能再输出 tracing 日志，删除失败留到下次切换文件时重试
+fn remove_expired_logs(dir: &Path) {
+    for path in log_files(dir).into_iter().skip(LOG_RETENTION_DAYS) {
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+/// 解析一行日志；不是以时间戳开头的行返回 None（属于上一条日志的续行）
+fn parse_log_line(line: &str) -> Option<LogEntry> {
+    let timestamp = line.get(..TIMESTAMP_LEN)?;
+    chrono::NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+
+    let rest = line[TIMESTAMP_LEN..].trim_start();
+    let (level, message) = rest.split_once(' ').unwrap_or((rest, ""));
+    level.parse::<Level>().ok()?;
+
+    // 去掉线程ID前缀
+    let message = message.trim_start();
+    let message = match message.strip_prefix("ThreadId(").and_then(|m| m.split_once(") ")) {
+        Some((_, message)) => message,
+        None => message,
+    };
+
+    Some(LogEntry {
+        timestamp: timestamp.to_string(),
+        level: level.to_string(),
+        message: message.to_string(),
+    })
+}
+
+fn read_log_file(path: &Path) -> Vec<LogEntry> {
+    let Ok(file) = File::open(path) else { return Vec::new() };
+
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        match parse_log_line(&line) {
+            Some(entry) => entries.push(entry),
+            None => {
+                if let Some(last) = entries.last_mut() {
+                    last.message.push('\n');
+                    last.message.push_str(&line);
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// Most recent log entries at or above `min_level`, oldest first
+/// 读取最近的日志（不低于 `min_level` 级别，按时间正序），从最新的日志文件往前读，够数即止
+pub fn recent_logs(min_level: Level, limit: usize) -> Result<Vec<LogEntry>, String> {
+    let dir = LOG_DIR.get().ok_or_else(|| "文件日志未启用".to_string())?;
+    let limit = limit.clamp(1, MAX_RECENT_LOGS);
+
+    // Level 的比较方向：TRACE > DEBUG > INFO > WARN > ERROR
+    let mut collected: Vec<LogEntry> = Vec::new();
+    for path in log_files(dir) {
+        let mut entries: Vec<LogEntry> = read_log_file(&path).into_iter()
+            .filter(|e| e.level.parse::<Level>().is_ok_and(|level| level <= min_level))
+            .collect();
+        entries.append(&mut collected);
+        collected = entries;
+        if collected.len() >= limit {
+            break;
+        }
+    }
+
+    let skip = collected.len().saturating_sub(limit);
+    Ok(collected.split_off(skip))
+}
@@ -0,0 +1,64 @@
+//! Filename Metadata Parsing
+//!
+//! 批量导入时按用户配置的正则从视频文件名中提取标题、日期和话题，
+//! 正则使用命名分组 `title` / `date` / `tags`，例如文件名
+//! `20240610_产品介绍_#美妆#护肤.mp4` 可用
+//! `^(?P<date>\d{8})_(?P<title>[^_#]+)_?(?P<tags>#.*)?$` 解析
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// 支持的命名分组
+const GROUP_NAMES: &[&str] = &["title", "date", "tags"];
+
+/// Metadata extracted from a filename
+/// 从文件名中提取的元数据
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FilenameMetadata {
+    pub title: Option<String>,
+    pub date: Option<String>,  // 能识别时规范为 YYYY-MM-DD，否则保留原文
+    pub tags: Vec<String>,
+}
+
+/// 编译并校验解析规则：必须是合法正则，且至少包含一个支持的命名分组
+pub fn compile_filename_pattern(pattern: &str) -> Result<Regex, String> {
+    let regex = Regex::new(pattern).map_err(|e| format!("无效的正则表达式: {}", e))?;
+    if !regex.capture_names().flatten().any(|name| GROUP_NAMES.contains(&name)) {
+        return Err("正则需包含命名分组 title、date 或 tags 之一，如 (?P<title>...)".to_string());
+    }
+    Ok(regex)
+}
+
+/// 按规则解析文件名（不含扩展名），不匹配时返回 None
+pub fn parse_filename_metadata(regex: &Regex, file_stem: &str) -> Option<FilenameMetadata> {
+    let caps = regex.captures(file_stem)?;
+    let group = |name: &str| {
+        caps.name(name)
+            .map(|m| m.as_str().trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    let tags = group("tags")
+        .map(|raw| {
+            raw.split(|c: char| c == '#' || c == ',' || c == '，' || c.is_whitespace())
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(FilenameMetadata {
+        title: group("title").map(|t| t.replace('_', " ")),
+        date: group("date").map(|d| normalize_date(&d)),
+        tags,
+    })
+}
+
+/// 常见日期写法统一为 YYYY-MM-DD
+fn normalize_date(raw: &str) -> String {
+    ["%Y%m%d", "%Y-%m-%d", "%Y.%m.%d", "%Y_%m_%d"]
+        .iter()
+        .find_map(|fmt| chrono::NaiveDate::parse_from_str(raw, fmt).ok())
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| raw.to_string())
+}
@@ -0,0 +1,46 @@
+//! HTTP API Settings
+//!
+//! 外部自动化用的 HTTP API 配置。服务本身在 `commands::http_server` 中实现，
+//! 只有以 `--features http-api` 编译时才包含；未包含时配置可以保存，但不会启动服务。
+
+use serde::{Deserialize, Serialize};
+
+/// 默认监听端口
+pub const DEFAULT_HTTP_API_PORT: u16 = 17890;
+
+/// 默认只监听本机
+pub const DEFAULT_HTTP_API_BIND_ADDRESS: &str = "127.0.0.1";
+
+/// HTTP API configuration
+/// HTTP API 配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpApiConfig {
+    pub enabled: bool,
+    pub bind_address: String,  // 监听地址，局域网内其他机器调用时改为 0.0.0.0
+    pub port: u16,
+    pub token: String,         // 访问令牌，请求需携带 `Authorization: Bearer {token}`，首次保存时生成
+}
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: DEFAULT_HTTP_API_BIND_ADDRESS.to_string(),
+            port: DEFAULT_HTTP_API_PORT,
+            token: String::new(),
+        }
+    }
+}
+
+/// 校验 `Authorization` 请求头中的 Bearer 令牌（逐字节比较全部内容，耗时与不匹配的位置无关）
+pub fn check_bearer_token(authorization: Option<&str>, token: &str) -> bool {
+    let Some(provided) = authorization.and_then(|v| v.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    if token.is_empty() || provided.len() != token.len() {
+        return false;
+    }
+
+    provided.bytes().zip(token.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
@@ -0,0 +1,328 @@
+//! Publish Load Test Harness
+//!
+//! 开发用发布压测（需以 `--features load-test` 编译）：在临时数据库中创建 N 个模拟账号和一个发布任务，
+//! 按与 `run_publication_task` 相同的方式并发发布（信号量限流、逐账号写发布结果、最后汇总任务状态），
+//! 平台调用由 [`MockPlatform`] 按配置的延迟和失败率模拟，
+//! 报告吞吐量、耗时分布以及测试期间的数据库锁竞争，用于发版前验证并发相关改动。
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use super::{
+    AccountStatus, Platform, PlatformCredentials, PlatformError, PlatformPublication, PlatformType,
+    PublicationAccountDetail, PublicationStats, PublicationStatus, PublicationTask, PublishRequest,
+    PublishResult, UserAccount,
+};
+use crate::storage::{contention_stats, DatabaseManager, DEFAULT_MAX_CONCURRENT_PUBLISHES};
+
+/// Load test configuration
+/// 压测配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoadTestConfig {
+    /// 模拟账号数
+    pub accounts: usize,
+    /// 最大并发发布数（与设置中的 max_concurrent_publishes 含义相同）
+    pub max_concurrent: u32,
+    /// 单次模拟发布的最短 / 最长耗时（毫秒），在两者之间均匀随机
+    pub min_latency_ms: u64,
+    pub max_latency_ms: u64,
+    /// 模拟发布失败的概率（0.0 ~ 1.0）
+    pub failure_rate: f64,
+}
+
+impl Default for LoadTestConfig {
+    fn default() -> Self {
+        Self {
+            accounts: 50,
+            max_concurrent: DEFAULT_MAX_CONCURRENT_PUBLISHES,
+            min_latency_ms: 200,
+            max_latency_ms: 2000,
+            failure_rate: 0.1,
+        }
+    }
+}
+
+/// Load test report
+/// 压测报告
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadTestReport {
+    pub accounts: usize,
+    pub max_concurrent: u32,
+    pub success_count: usize,
+    pub failed_count: usize,
+    /// 状态写入失败的次数（发布结果未能落库）
+    pub write_errors: usize,
+    pub elapsed_ms: u64,
+    /// 每秒完成的发布数
+    pub throughput_per_sec: f64,
+    /// 单个账号从拿到并发许可到结果落库的耗时
+    pub latency_p50_ms: u64,
+    pub latency_p95_ms: u64,
+    pub latency_max_ms: u64,
+    /// 压测期间新增的数据库锁竞争
+    pub busy_errors: u64,
+    pub recovered: u64,
+    pub exhausted: u64,
+    pub serialized_writes: u64,
+}
+
+/// Mock platform with configurable latency and failure rate
+/// 模拟平台：发布时等待随机延迟，按失败率返回失败
+#[derive(Debug, Clone)]
+pub struct MockPlatform {
+    min_latency: Duration,
+    max_latency: Duration,
+    failure_rate: f64,
+}
+
+impl MockPlatform {
+    pub fn new(config: &LoadTestConfig) -> Self {
+        let min_latency = Duration::from_millis(config.min_latency_ms);
+        Self {
+            min_latency,
+            max_latency: Duration::from_millis(config.max_latency_ms).max(min_latency),
+            failure_rate: config.failure_rate.clamp(0.0, 1.0),
+        }
+    }
+}
+
+#[async_trait]
+impl Platform for MockPlatform {
+    fn platform_type(&self) -> PlatformType {
+        PlatformType::Douyin
+    }
+
+    fn platform_name(&self) -> String {
+        "模拟平台".to_string()
+    }
+
+    async fn authenticate_account(&self) -> Result<UserAccount, PlatformError> {
+        Err(PlatformError::AuthenticationFailed("模拟平台不支持授权".to_string()))
+    }
+
+    async fn refresh_credentials(&self, _account_id: &str) -> Result<UserAccount, PlatformError> {
+        Err(PlatformError::AuthenticationFailed("模拟平台不支持刷新凭证".to_string()))
+    }
+
+    async fn verify_credentials(&self, _params: &str) -> Result<(), PlatformError> {
+        Ok(())
+    }
+
+    async fn publish_video(&self, request: PublishRequest) -> Result<PublishResult, PlatformError> {
+        // ThreadRng 不能跨 await，先取好随机值
+        let (latency, failed) = {
+            let mut rng = rand::thread_rng();
+            let latency = rng.gen_range(self.min_latency..=self.max_latency);
+            (latency, rng.gen_bool(self.failure_rate))
+        };
+        tokio::time::sleep(latency).await;
+
+        if failed {
+            return Err(PlatformError::PublicationFailed(format!("模拟发布失败: {}", request.account_id)));
+        }
+        Ok(PublishResult {
+            success: true,
+            publication_id: String::new(),
+            item_id: Some(uuid::Uuid::new_v4().simple().to_string()),
+            error_message: None,
+        })
+    }
+
+    async fn get_publication_status(&self, _publication_id: &str) -> Result<PlatformPublication, PlatformError> {
+        Err(PlatformError::PublicationFailed("模拟平台不支持获取发布状态".to_string()))
+    }
+
+    async fn get_account_stats(&self, _account_id: &str) -> Result<PublicationStats, PlatformError> {
+        Ok(PublicationStats::default())
+    }
+
+    fn get_credentials_from_params(&self, _params: &str) -> Result<PlatformCredentials, PlatformError> {
+        Err(PlatformError::InvalidCredentials("模拟平台没有凭证".to_string()))
+    }
+}
+
+/// 在临时数据库中创建模拟账号和发布任务，返回任务ID
+fn seed_task(db_manager: &DatabaseManager, accounts: usize) -> Result<String, String> {
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let task_id = uuid::Uuid::new_v4().to_string();
+
+    let mut details = Vec::with_capacity(accounts);
+    for i in 0..accounts {
+        let account = UserAccount {
+            id: uuid::Uuid::new_v4().to_string(),
+            username: format!("load_test_{}", i),
+            nickname: format!("压测账号{}", i),
+            avatar_url: String::new(),
+            platform: PlatformType::Douyin,
+            params: "{}".to_string(),
+            status: AccountStatus::Active,
+            created_at: now.clone(),
+            on_hold: false,
+            group_id: None,
+            tags: Vec::new(),
+        };
+        db_manager.save_account(&account).map_err(|e| e.to_string())?;
+
+        details.push(PublicationAccountDetail {
+            id: uuid::Uuid::new_v4().to_string(),
+            publication_task_id: task_id.clone(),
+            account_id: account.id,
+            account_name: account.nickname,
+            platform: PlatformType::Douyin,
+            status: PublicationStatus::Pending,
+            created_at: now.clone(),
+            published_at: None,
+            publish_url: None,
+            stats: PublicationStats::default(),
+            message: None,
+            item_id: None,
+            strategy_version: None,
+            title_override: None,
+            description_override: None,
+            hashtags_override: None,
+            attempts: 0,
+            error_code: None,
+            variant: None,
+        });
+    }
+
+    let task = PublicationTask {
+        id: task_id.clone(),
+        title: "压测任务".to_string(),
+        description: None,
+        video_path: String::new(),
+        cover_path: None,
+        hashtags: Vec::new(),
+        status: PublicationStatus::Pending,
+        created_at: now,
+        published_at: None,
+        hashtag_set_id: None,
+        vertical_video_path: None,
+        horizontal_video_path: None,
+        watermark_enabled: false,
+        visibility_type: 0,
+        download_allowed: false,
+        poi_id: None,
+        poi_name: None,
+        music_id: None,
+        music_end_time: None,
+        mentions: Vec::new(),
+    };
+    db_manager.save_publication_with_accounts(&task, &details).map_err(|e| e.to_string())?;
+
+    Ok(task_id)
+}
+
+/// 按升序排列的耗时取百分位
+fn percentile(sorted: &[Duration], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let index = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[index.min(sorted.len() - 1)].as_millis() as u64
+}
+
+/// Run the publish load test against a throwaway database
+/// 执行发布压测（使用临时目录中的独立数据库，结束后删除）
+pub async fn run_load_test(config: LoadTestConfig) -> Result<LoadTestReport, String> {
+    if config.accounts == 0 {
+        return Err("模拟账号数必须大于 0".to_string());
+    }
+
+    let base_path: PathBuf = std::env::temp_dir().join(format!("amm-load-test-{}", uuid::Uuid::new_v4().simple()));
+    let db_manager = Arc::new(DatabaseManager::new(base_path.clone()));
+    let task_id = seed_task(&db_manager, config.accounts)?;
+    let details = db_manager.get_publication_task_with_accounts(&task_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "压测任务创建失败".to_string())?
+        .accounts;
+
+    tracing::info!("[LoadTest] 开始压测: {} 个账号，并发 {}，延迟 {}-{} ms，失败率 {}",
+        config.accounts, config.max_concurrent, config.min_latency_ms, config.max_latency_ms, config.failure_rate);
+
+    let before = contention_stats();
+    let platform = Arc::new(MockPlatform::new(&config));
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(config.max_concurrent.max(1) as usize));
+    let started = Instant::now();
+
+    let mut handles = Vec::new();
+    for detail in details {
+        let db_manager = db_manager.clone();
+        let platform = platform.clone();
+        let semaphore = semaphore.clone();
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire().await.unwrap();
+            let begin = Instant::now();
+
+            let request = PublishRequest {
+                account_id: detail.account_id.clone(),
+                title: "压测任务".to_string(),
+                ..Default::default()
+            };
+            let (status, item_id, message) = match platform.publish_video(request).await {
+                Ok(result) => (PublicationStatus::Completed, result.item_id, None),
+                Err(e) => (PublicationStatus::Failed, None, Some(e.to_string())),
+            };
+            let success = status == PublicationStatus::Completed;
+            let written = db_manager
+                .update_publication_account_status(&detail.id, status, None, message, item_id)
+                .is_ok();
+
+            (success, written, begin.elapsed())
+        }));
+    }
+
+    let mut success_count = 0;
+    let mut write_errors = 0;
+    let mut latencies = Vec::with_capacity(handles.len());
+    for handle in handles {
+        match handle.await {
+            Ok((success, written, latency)) => {
+                if success {
+                    success_count += 1;
+                }
+                if !written {
+                    write_errors += 1;
+                }
+                latencies.push(latency);
+            }
+            Err(e) => tracing::error!("[LoadTest] 模拟发布任务异常: {}", e),
+        }
+    }
+    if let Err(e) = db_manager.update_task_status_from_accounts(&task_id) {
+        tracing::error!("[LoadTest] 更新任务状态失败: {}", e);
+        write_errors += 1;
+    }
+
+    let elapsed = started.elapsed();
+    let after = contention_stats();
+    latencies.sort();
+
+    if let Err(e) = std::fs::remove_dir_all(&base_path) {
+        tracing::warn!("[LoadTest] 清理临时数据库失败: {}", e);
+    }
+
+    let report = LoadTestReport {
+        accounts: config.accounts,
+        max_concurrent: config.max_concurrent,
+        success_count,
+        failed_count: latencies.len() - success_count,
+        write_errors,
+        elapsed_ms: elapsed.as_millis() as u64,
+        throughput_per_sec: latencies.len() as f64 / elapsed.as_secs_f64().max(0.001),
+        latency_p50_ms: percentile(&latencies, 0.5),
+        latency_p95_ms: percentile(&latencies, 0.95),
+        latency_max_ms: latencies.last().map(|d| d.as_millis() as u64).unwrap_or(0),
+        busy_errors: after.busy_errors - before.busy_errors,
+        recovered: after.recovered - before.recovered,
+        exhausted: after.exhausted - before.exhausted,
+        serialized_writes: after.serialized_writes - before.serialized_writes,
+    };
+
+    tracing::info!("[LoadTest] 压测完成: {:?}", report);
+    Ok(report)
+}
@@ -0,0 +1,160 @@
+//! Media Probing & Transcoding
+//!
+//! 读取本地视频的时长、分辨率、编码和文件大小，并在格式不被平台接受时转码为 H.264 MP4。
+//! 元数据通过 ffprobe 读取；ffprobe 不可用或解析失败时只返回文件大小，其余字段为 None
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Basic properties of a local video file
+/// 本地视频的基本信息
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VideoInfo {
+    pub size_bytes: u64,
+    /// 时长（秒）
+    pub duration_secs: Option<f64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// 视频编码，如 h264、hevc
+    pub video_codec: Option<String>,
+    /// 音频编码，如 aac；无音轨时为 None
+    pub audio_codec: Option<String>,
+}
+
+impl VideoInfo {
+    /// 是否为竖版视频（分辨率未知时返回 None）
+    pub fn is_vertical(&self) -> Option<bool> {
+        Some(self.height? > self.width?)
+    }
+}
+
+/// 运行 ffprobe 获取容器和流信息（JSON）
+fn run_ffprobe(video_path: &str) -> Result<Value, String> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "error", "-print_format", "json", "-show_format", "-show_streams"])
+        .arg(video_path)
+        .output()
+        .map_err(|e| format!("无法启动 ffprobe（请确认已安装并在 PATH 中）: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("ffprobe 解析失败: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("ffprobe 输出解析失败: {}", e))
+}
+
+/// 从 ffprobe 的 JSON 输出中提取视频信息
+fn parse_ffprobe(info: &mut VideoInfo, probe: &Value) {
+    info.duration_secs = probe.pointer("/format/duration")
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse().ok());
+
+    let streams = probe.get("streams").and_then(|v| v.as_array()).map(|v| v.as_slice()).unwrap_or_default();
+    let stream = |kind: &str| streams.iter().find(|s| s.get("codec_type").and_then(|v| v.as_str()) == Some(kind));
+
+    if let Some(video) = stream("video") {
+        info.width = video.get("width").and_then(|v| v.as_u64()).map(|v| v as u32);
+        info.height = video.get("height").and_then(|v| v.as_u64()).map(|v| v as u32);
+        info.video_codec = video.get("codec_name").and_then(|v| v.as_str()).map(|s| s.to_string());
+    }
+    if let Some(audio) = stream("audio") {
+        info.audio_codec = audio.get("codec_name").and_then(|v| v.as_str()).map(|s| s.to_string());
+    }
+}
+
+/// 读取视频大小、时长、分辨率和编码
+///
+/// 文件不存在时返回错误；ffprobe 失败只记录警告
+pub fn probe_video(video_path: &str) -> Result<VideoInfo, String> {
+    let size_bytes = std::fs::metadata(Path::new(video_path))
+        .map_err(|e| format!("无法读取视频文件 {}: {}", video_path, e))?
+        .len();
+
+    let mut info = VideoInfo { size_bytes, ..Default::default() };
+    match run_ffprobe(video_path) {
+        Ok(probe) => parse_ffprobe(&mut info, &probe),
+        Err(e) => tracing::warn!("[Media] {}，跳过时长 / 分辨率检测", e),
+    }
+
+    Ok(info)
+}
+
+/// 各平台普遍接受的容器格式
+const COMPATIBLE_CONTAINERS: &[&str] = &["mp4", "mov"];
+
+/// 各平台普遍接受的视频编码
+const COMPATIBLE_VIDEO_CODECS: &[&str] = &["h264", "hevc"];
+
+/// 判断视频是否需要转码，返回原因（如 "容器格式 mkv 不受支持"）；无需转码时返回 None
+///
+/// 编码未知（ffprobe 不可用）时只按扩展名判断
+pub fn transcode_reason(video_path: &str, info: &VideoInfo) -> Option<String> {
+    let extension = Path::new(video_path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    if !COMPATIBLE_CONTAINERS.contains(&extension.as_str()) {
+        return Some(format!("容器格式 {} 不受支持", if extension.is_empty() { "未知" } else { &extension }));
+    }
+    match info.video_codec.as_deref() {
+        Some(codec) if !COMPATIBLE_VIDEO_CODECS.contains(&codec) => Some(format!("视频编码 {} 不受支持", codec)),
+        _ => None,
+    }
+}
+
+/// 转码为 H.264 + AAC 的 MP4
+///
+/// 输出文件名由源文件路径和修改时间决定，同一文件重复转码时直接复用；
+/// `on_progress` 接收 0.0 ~ 1.0 的进度（时长未知时不回调）
+pub fn transcode_to_mp4(
+    video_path: &str,
+    duration_secs: Option<f64>,
+    output_dir: &Path,
+    mut on_progress: impl FnMut(f64),
+) -> Result<PathBuf, String> {
+    let modified = std::fs::metadata(video_path)
+        .and_then(|m| m.modified())
+        .map_err(|e| format!("无法读取视频文件 {}: {}", video_path, e))?;
+    let key = format!("{:x}", md5::compute(format!("{}@{:?}", video_path, modified)));
+    let output = output_dir.join(format!("transcode_{}.mp4", key));
+    if output.exists() {
+        return Ok(output);
+    }
+
+    // 先写临时文件，成功后再改名，避免中断的转码被当作缓存复用
+    let partial = output_dir.join(format!("transcode_{}.part.mp4", key));
+    let mut child = Command::new("ffmpeg")
+        .args(["-hide_banner", "-loglevel", "error", "-nostats", "-y"])
+        .args(["-i", video_path])
+        .args(["-c:v", "libx264", "-preset", "medium", "-crf", "20", "-pix_fmt", "yuv420p"])
+        .args(["-c:a", "aac", "-b:a", "192k"])
+        .args(["-movflags", "+faststart", "-progress", "pipe:1"])
+        .arg(&partial)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("无法启动 ffmpeg（请确认已安装并在 PATH 中）: {}", e))?;
+
+    // -progress 每隔约 0.5 秒输出一组 key=value，out_time_us 为已处理的时长（微秒）
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let Some(total) = duration_secs.filter(|d| *d > 0.0) else { continue };
+            if let Some(done) = line.strip_prefix("out_time_us=").and_then(|v| v.trim().parse::<f64>().ok()) {
+                on_progress((done / 1_000_000.0 / total).clamp(0.0, 1.0));
+            }
+        }
+    }
+
+    let result = child.wait_with_output().map_err(|e| format!("等待 ffmpeg 结束失败: {}", e))?;
+    if !result.status.success() || !partial.exists() {
+        let _ = std::fs::remove_file(&partial);
+        return Err(format!("视频转码失败: {}", String::from_utf8_lossy(&result.stderr).trim()));
+    }
+    std::fs::rename(&partial, &output).map_err(|e| format!("保存转码文件失败: {}", e))?;
+    on_progress(1.0);
+
+    Ok(output)
+}
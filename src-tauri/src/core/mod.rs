@@ -1,7 +1,75 @@
 // Core module - Platform trait and factory
 
+mod account_monitor;
+mod ai;
+mod auto_reply;
+mod avatar_cache;
+mod backup;
+mod capabilities;
+mod command_metrics;
+mod comment_sync;
+mod comment_tags;
+mod cover;
+mod crypto;
+mod events;
+mod failures;
+mod file_log;
+mod filename_meta;
+mod http_api;
+#[cfg(feature = "load-test")]
+mod load_test;
+mod media;
+mod notifications;
+mod publish_cancel;
+mod publish_logs;
+mod publish_pacing;
 mod publish_progress;
+mod publish_verification;
+mod reauth_queue;
+mod remote_sync;
+mod scheduler;
+mod stats_refresher;
+mod video_variant;
+mod watermark;
+mod webhook;
+pub use account_monitor::{check_publish_risk, risk_flag_label};
+pub use ai::{AiConfig, CaptionCandidate, DEFAULT_AI_MODEL, DEFAULT_CAPTION_CANDIDATES, generate_captions};
+pub use auto_reply::{AutoReplyRule, AutoReplyLog, AutoReplyRequest, DEFAULT_REPLIES_PER_HOUR, start_auto_reply_worker};
+pub use avatar_cache::{cache_account_avatar, cached_avatar_data_url, remove_cached_avatar};
+pub use backup::{BACKUP_FILE_EXTENSION, export_backup, import_backup};
+pub use command_metrics::{CommandMetric, CommandTimer, SLOW_COMMAND_THRESHOLD, command_metrics, timed_invoke_handler};
+pub use capabilities::{PlatformLimits, check_video_limits};
+pub use comment_sync::{CommentSyncRecord, CommentSyncState, COMMENT_SYNC_PAGE_SIZE, start_comment_sync};
+pub use comment_tags::CommentTagRules;
+pub use cover::{CoverStrategy, extract_video_frame};
+pub use crypto::{decrypt_payload, encrypt_payload};
+pub use events::{
+    EventSchema, VersionedEvent, EVENT_SCHEMAS, emit_event, PUBLISH_PROGRESS_EVENT_V2, PUBLISH_LOG_EVENT_V1, PUBLISH_CAPTCHA_EVENT_V1,
+    TRANSCODE_PROGRESS_EVENT_V1, REAUTH_QUEUE_EVENT_V1, COMMENT_EXPORT_PROGRESS_EVENT_V1,
+};
+pub use failures::{FailureCategory, FailureClass, classify_failure};
+pub use file_log::{LogEntry, LocalTimer, RollingFileWriter, DEFAULT_RECENT_LOGS, LOG_RETENTION_DAYS, recent_logs};
+pub use http_api::{HttpApiConfig, DEFAULT_HTTP_API_BIND_ADDRESS, DEFAULT_HTTP_API_PORT, check_bearer_token};
+pub use filename_meta::{FilenameMetadata, compile_filename_pattern, parse_filename_metadata};
+#[cfg(feature = "load-test")]
+pub use load_test::{LoadTestConfig, LoadTestReport, MockPlatform, run_load_test};
+pub use media::{VideoInfo, probe_video, transcode_reason, transcode_to_mp4};
+pub use notifications::{NotificationKind, NotificationSettings, init_notifications, notify};
+pub use publish_cancel::{PublishCancelToken, PUBLISH_CANCELLED_MESSAGE, cancel_publish, is_publish_running, register_publish, unregister_publish};
+pub use publish_logs::{PublishLogEntry, PublishLogLayer, PUBLISH_LOG_EVENT, subscribe_publish_logs, unsubscribe_publish_logs};
+pub use publish_pacing::{PacingDelay, PacingRule, PublishPacingRules, check_pacing};
 pub use publish_progress::{ProgressEmitter, get_progress_emitter};
+pub use publish_verification::{PublishCaptchaEvent, PublishPermit, VERIFICATION_TIMEOUT, list_pending_verifications, pending_verification, resume_verification, wait_for_verification};
+pub use reauth_queue::{ReauthItemStatus, ReauthQueue, ReauthQueueItem, REAUTH_QUEUE_EVENT};
+pub use remote_sync::{RemoteSyncConfig, RemoteSyncResult, SyncEntity, SyncChange, DEFAULT_REMOTE_SYNC_INTERVAL_MINUTES, run_remote_sync, start_remote_sync};
+pub use scheduler::{ScheduledJob, ScheduledJobStatus, SCHEDULE_TIME_FORMAT, parse_send_at, start_scheduler};
+pub use stats_refresher::start_stats_refresher;
+pub use video_variant::{VideoVariant, generate_video_variant};
+pub use watermark::{WatermarkConfig, WatermarkContent, WatermarkPosition, apply_watermark};
+pub use webhook::{
+    WebhookConfig, WebhookDelivery, WebhookPayload, DEFAULT_WEBHOOK_MAX_ATTEMPTS, MAX_WEBHOOK_ATTEMPTS_LIMIT,
+    notify_publish_result, send_webhooks,
+};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -11,9 +79,13 @@ use thiserror::Error;
 /// Platform type enumeration
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PlatformType {
+    #[serde(alias = "douyin")]
     Douyin,
+    #[serde(alias = "xiaohongshu")]
     Xiaohongshu,
+    #[serde(alias = "kuaishou")]
     Kuaishou,
+    #[serde(alias = "bilibili")]
     Bilibili,
 }
 
@@ -27,6 +99,56 @@ impl PlatformType {
             PlatformType::Bilibili => "B站",
         }.to_string()
     }
+
+    /// Whether the platform's feed favours vertical (9:16) video
+    /// 平台是否以竖版视频为主（B站以横版为主）
+    pub fn prefers_vertical_video(&self) -> bool {
+        !matches!(self, PlatformType::Bilibili)
+    }
+
+    /// All supported platforms
+    /// 所有支持的平台
+    pub const ALL: [PlatformType; 4] = [
+        PlatformType::Douyin,
+        PlatformType::Xiaohongshu,
+        PlatformType::Kuaishou,
+        PlatformType::Bilibili,
+    ];
+
+    /// Platform id used by the frontend, browser auth rules and settings keys
+    /// 平台ID（小写，与前端参数、浏览器授权规则、话题库平台变体的键一致）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlatformType::Douyin => "douyin",
+            PlatformType::Xiaohongshu => "xiaohongshu",
+            PlatformType::Kuaishou => "kuaishou",
+            PlatformType::Bilibili => "bilibili",
+        }
+    }
+}
+
+impl std::fmt::Display for PlatformType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Unknown platform string
+/// 无法识别的平台字符串（不再默认当作抖音处理）
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Unknown platform: {0}")]
+pub struct UnknownPlatformError(pub String);
+
+impl std::str::FromStr for PlatformType {
+    type Err = UnknownPlatformError;
+
+    /// 解析平台ID，不区分大小写（兼容数据库中的 "Douyin" 写法）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().to_lowercase();
+        PlatformType::ALL.into_iter()
+            .find(|p| p.as_str() == id)
+            .ok_or_else(|| UnknownPlatformError(s.to_string()))
+    }
 }
 
 /// Account status
@@ -35,6 +157,7 @@ pub enum AccountStatus {
     Active,
     Expired,
     Pending,
+    Risky,  // 连续失败、失败率过高或触发风控，建议暂停发布（见 account_monitor）
 }
 
 /// User information stored in database
@@ -49,6 +172,22 @@ pub struct UserAccount {
     pub params: String,            // 爬取的用户参数JSON
     pub status: AccountStatus,     // 状态
     pub created_at: String,        // 保存时间
+    #[serde(default)]
+    pub on_hold: bool,             // 维护中（暂停发布）
+    #[serde(default)]
+    pub group_id: Option<String>,  // 所属分组
+    #[serde(default)]
+    pub tags: Vec<String>,         // 账号标签
+}
+
+/// Account group
+/// 账号分组（发布任务可直接以整个分组为目标）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountGroup {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub created_at: String,
 }
 
 /// Platform credentials (retrieved from params when needed)
@@ -76,6 +215,8 @@ pub enum PublicationStatus {
     Publishing,
     Completed,
     Failed,
+    Skipped,
+    Removed,     // 已从平台删除
 }
 
 /// Platform publication record
@@ -104,6 +245,18 @@ pub struct PublicationStats {
     pub shares: i64,
 }
 
+/// Account profile statistics reported by the platform
+/// 平台返回的账号主页数据（粉丝、获赞、作品数）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountProfileStats {
+    pub followers: i64,
+    pub following: i64,
+    /// 账号累计获赞数
+    pub total_likes: i64,
+    /// 平台上的作品数（含非本工具发布的作品）
+    pub video_count: i64,
+}
+
 // ============================================================================
 // Comment types (评论表结构)
 // ============================================================================
@@ -135,6 +288,8 @@ pub struct Comment {
     pub status: CommentStatus,     // 状态
     #[serde(alias = "created_at")]
     pub created_at: String,        // 入库时间
+    #[serde(default)]
+    pub tags: Vec<String>,         // 分类标签（见 CommentTagRules）
 }
 
 /// Comment status
@@ -153,6 +308,78 @@ pub struct CommentExtractResult {
     pub total_in_aweme: i64,      // 作品总评论数
     pub comments: Vec<Comment>,
     pub error_message: Option<String>,
+    #[serde(default)]
+    pub next_cursor: i64,         // 下一页游标，用于增量同步
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+// ============================================================================
+// Private message types (私信表结构)
+// ============================================================================
+
+/// Private message - 私信表（创作者收件箱中的消息，用于线索跟进）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateMessage {
+    pub id: String,
+    pub account_id: String,        // 收件账号ID
+    pub conversation_id: String,   // 平台会话ID
+    pub message_id: String,        // 平台消息ID（同一账号下唯一）
+    pub sender_id: String,         // 发送者ID
+    pub sender_nickname: String,
+    pub sender_avatar: String,
+    pub content: String,           // 消息文本（非文本消息为类型说明，如 [图片]）
+    pub is_outgoing: bool,         // 是否为本账号发出的消息
+    pub create_time: String,       // 消息时间
+    pub created_at: String,        // 入库时间
+}
+
+/// Private message extraction result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageExtractResult {
+    pub success: bool,
+    pub total_extracted: i64,
+    /// 新入库的消息数（已存在的消息不重复保存）
+    #[serde(default)]
+    pub new_messages: i64,
+    pub messages: Vec<PrivateMessage>,
+    pub error_message: Option<String>,
+}
+
+// ============================================================================
+// Remote video types (平台作品列表)
+// ============================================================================
+
+/// Remote video - 账号在平台上的作品（包括未通过本应用发布的作品）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteVideo {
+    pub id: String,
+    pub account_id: String,
+    pub item_id: String,           // 平台作品ID（同一账号下唯一）
+    pub title: String,
+    pub cover_url: String,
+    pub share_url: String,
+    pub create_time: String,       // 作品发布时间
+    pub plays: i64,
+    pub likes: i64,
+    pub comments: i64,
+    pub favorites: i64,
+    pub shares: i64,
+    /// 通过本应用发布的作品关联的发布详情ID（publication_accounts.id）
+    pub detail_id: Option<String>,
+    pub synced_at: String,
+}
+
+/// Result of syncing an account's video list
+/// 作品列表同步结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoSyncResult {
+    /// 本次拉取到的作品数
+    pub total: i64,
+    /// 首次入库的作品数
+    pub new_videos: i64,
+    /// 关联到本应用发布记录的作品数
+    pub linked: i64,
 }
 
 /// Publication result
@@ -164,6 +391,27 @@ pub struct PublishResult {
     pub error_message: Option<String>,
 }
 
+/// Hashtag resolved during a publish preview
+/// 发布预演中的话题解析结果（hashtag_id 为空表示平台未匹配到话题）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewHashtag {
+    pub name: String,
+    pub hashtag_id: String,
+    pub matched: bool,
+}
+
+/// Publish dry-run result: resolved caption and validation errors, nothing uploaded
+/// 发布预演结果：解析后的文案、话题和校验错误，不上传也不发布
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PublishPreview {
+    pub item_title: String,
+    pub caption: String,
+    pub text: String,
+    pub hashtags: Vec<PreviewHashtag>,
+    pub text_extra: serde_json::Value,
+    pub errors: Vec<String>,
+}
+
 // ============================================================================
 // Publication Task types (主表+子表结构)
 // ============================================================================
@@ -180,10 +428,48 @@ pub struct PublicationTask {
     pub status: PublicationStatus,
     pub created_at: String,
     pub published_at: Option<String>,
+    #[serde(default)]
+    pub hashtag_set_id: Option<String>,  // 关联的话题库，发布时按平台取话题
+    #[serde(default)]
+    pub vertical_video_path: Option<String>,  // 竖版（9:16）视频
+    #[serde(default)]
+    pub horizontal_video_path: Option<String>,  // 横版（16:9）视频
+    #[serde(default)]
+    pub watermark_enabled: bool,  // 发布前按账号所在分组的配置叠加水印
+    #[serde(default)]
+    pub visibility_type: i32,  // 可见范围：0 公开，1 仅自己可见，2 好友可见
+    #[serde(default)]
+    pub download_allowed: bool,  // 是否允许下载
+    #[serde(default)]
+    pub poi_id: Option<String>,  // 位置锚点（目前仅抖音）
+    #[serde(default)]
+    pub poi_name: Option<String>,
+    #[serde(default)]
+    pub music_id: Option<String>,  // 作品配乐（目前仅抖音）
+    #[serde(default)]
+    pub music_end_time: Option<String>,  // 配乐截止时间（毫秒）
+    #[serde(default)]
+    pub mentions: Vec<String>,  // 发布时追加到描述的 @ 昵称
+}
+
+impl PublicationTask {
+    /// 按平台选择视频：竖版平台优先竖版、B站优先横版，缺少对应版本时使用 video_path
+    pub fn video_path_for(&self, platform: &PlatformType) -> &str {
+        let (preferred, fallback) = if platform.prefers_vertical_video() {
+            (&self.vertical_video_path, &self.horizontal_video_path)
+        } else {
+            (&self.horizontal_video_path, &self.vertical_video_path)
+        };
+
+        preferred.as_deref().filter(|p| !p.is_empty())
+            .or_else(|| Some(self.video_path.as_str()).filter(|p| !p.is_empty()))
+            .or_else(|| fallback.as_deref().filter(|p| !p.is_empty()))
+            .unwrap_or_default()
+    }
 }
 
 /// Publication account detail - 子表（账号发布详情）
-/// 注意：title/description/hashtags 只在主表存储，子表只存储关联信息和发布结果
+/// 注意：title/description/hashtags 在主表存储，子表只存储关联信息、发布结果和账号级覆盖
 /// 冗余 account_name 字段便于直接显示
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublicationAccountDetail {
@@ -199,6 +485,20 @@ pub struct PublicationAccountDetail {
     pub stats: PublicationStats,
     pub message: Option<String>,  // 发布失败原因（记录到哪一步失败及错误信息）
     pub item_id: Option<String>,  // 发布成功的视频ID
+    #[serde(default)]
+    pub strategy_version: Option<String>,  // 执行发布的策略模块版本
+    #[serde(default)]
+    pub title_override: Option<String>,  // 账号专属标题（为空时使用任务标题）
+    #[serde(default)]
+    pub description_override: Option<String>,  // 账号专属描述
+    #[serde(default)]
+    pub hashtags_override: Option<Vec<String>>,  // 账号专属话题（优先于任务话题和话题库）
+    #[serde(default)]
+    pub attempts: u32,  // 发布尝试次数（含临时性失败后的自动重试）
+    #[serde(default)]
+    pub error_code: Option<PublishErrorCode>,  // 发布失败错误码
+    #[serde(default)]
+    pub variant: Option<VideoVariant>,  // 该账号上传的去重变体（未启用时为 None）
 }
 
 /// Publication task with all account details - 包含账号详情的任务
@@ -213,9 +513,117 @@ pub struct PublicationTaskWithAccounts {
     pub status: PublicationStatus,
     pub created_at: String,
     pub published_at: String,
+    #[serde(default)]
+    pub hashtag_set_id: Option<String>,
+    #[serde(default)]
+    pub vertical_video_path: Option<String>,
+    #[serde(default)]
+    pub horizontal_video_path: Option<String>,
+    #[serde(default)]
+    pub watermark_enabled: bool,
+    #[serde(default)]
+    pub visibility_type: i32,
+    #[serde(default)]
+    pub download_allowed: bool,
+    #[serde(default)]
+    pub poi_id: Option<String>,
+    #[serde(default)]
+    pub poi_name: Option<String>,
+    #[serde(default)]
+    pub music_id: Option<String>,
+    #[serde(default)]
+    pub music_end_time: Option<String>,
+    #[serde(default)]
+    pub mentions: Vec<String>,
     pub accounts: Vec<PublicationAccountDetail>,
 }
 
+/// Publication task note - 任务备注（团队协作用的内部记录，不会发布到平台）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskNote {
+    pub id: String,
+    pub task_id: String,
+    pub author: String,
+    pub content: String,
+    pub created_at: String,
+}
+
+/// Account reassignment record - 发布账号改派记录（原账号被删除/失效时改派到同平台的其他账号）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountReassignment {
+    pub id: String,
+    pub detail_id: String,
+    pub task_id: String,
+    pub from_account_id: String,
+    pub from_account_name: String,
+    pub to_account_id: String,
+    pub to_account_name: String,
+    pub reason: String,
+    pub created_at: String,
+}
+
+// ============================================================================
+// Hashtag set types (话题库)
+// ============================================================================
+
+/// Reusable hashtag set - 可复用的话题库
+/// platform_tags 存放各平台的变体（键为小写平台名，如 "douyin"），未配置的平台使用 tags
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashtagSet {
+    pub id: String,
+    pub name: String,
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub platform_tags: std::collections::HashMap<String, Vec<String>>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl HashtagSet {
+    /// Get the ordered tags for a platform, falling back to the default tags
+    /// 获取指定平台的话题（有平台变体时优先使用变体）
+    pub fn tags_for(&self, platform: &PlatformType) -> Vec<String> {
+        match self.platform_tags.get(platform.as_str()) {
+            Some(tags) if !tags.is_empty() => tags.clone(),
+            _ => self.tags.clone(),
+        }
+    }
+}
+
+// ============================================================================
+// Publication template types (发布模板)
+// ============================================================================
+
+/// Reusable task structure - 发布模板
+/// 保存任务的标题、描述、话题和目标账号，每天只需换视频即可生成新任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicationTemplate {
+    pub id: String,
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub hashtags: Vec<String>,
+    /// 关联的话题库（为空表示直接使用 hashtags）
+    pub hashtag_set_id: Option<String>,
+    pub accounts: Vec<TemplateAccount>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Target account of a template, with its per-account overrides
+/// 模板中的目标账号及账号级别的内容覆盖
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateAccount {
+    pub account_id: String,
+    pub platform: PlatformType,
+    #[serde(default)]
+    pub title_override: Option<String>,
+    #[serde(default)]
+    pub description_override: Option<String>,
+    #[serde(default)]
+    pub hashtags_override: Option<Vec<String>>,
+}
+
 /// Platform errors
 #[derive(Debug, Error)]
 pub enum PlatformError {
@@ -245,6 +653,89 @@ pub enum PlatformError {
 
     #[error("Storage error: {0}")]
     StorageError(String),
+
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+}
+
+impl PlatformError {
+    /// Whether the error is transient (timeout, connection reset, HTTP 5xx) and worth retrying
+    /// 是否为临时性错误（超时、连接中断、5xx），可自动重试；鉴权、参数等错误不重试
+    pub fn is_transient(&self) -> bool {
+        match self {
+            PlatformError::NetworkError(_) => true,
+            PlatformError::VideoUploadFailed(msg) | PlatformError::PublicationFailed(msg) => {
+                classify_failure(msg).transient
+            }
+            _ => false,
+        }
+    }
+
+    /// Structured error code shown to the frontend
+    /// 归类为发布错误码：先按错误类型，再按错误信息关键字
+    pub fn error_code(&self) -> PublishErrorCode {
+        match self {
+            PlatformError::AuthenticationFailed(_) | PlatformError::InvalidCredentials(_) => PublishErrorCode::CookieExpired,
+            PlatformError::NetworkError(_) => PublishErrorCode::Network,
+            PlatformError::VideoUploadFailed(msg) => match PublishErrorCode::from_message(msg) {
+                PublishErrorCode::Unknown => PublishErrorCode::UploadFailed,
+                code => code,
+            },
+            PlatformError::PublicationFailed(msg) => PublishErrorCode::from_message(msg),
+            PlatformError::Cancelled(_) => PublishErrorCode::Unknown,
+            other => PublishErrorCode::from_message(&other.to_string()),
+        }
+    }
+}
+
+/// Structured publish error code
+/// 发布失败错误码，前端据此给出针对性的处理建议（如凭证过期时引导重新授权）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishErrorCode {
+    /// 凭证过期 / 登录失效
+    CookieExpired,
+    /// 视频 / 封面上传失败
+    UploadFailed,
+    /// 账号风控
+    RiskControl,
+    /// 操作频繁 / 限流
+    RateLimited,
+    /// 网络异常、超时或服务端 5xx
+    Network,
+    /// 其他
+    Unknown,
+}
+
+impl PublishErrorCode {
+    /// 存储用的字符串（与序列化结果一致）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PublishErrorCode::CookieExpired => "cookie_expired",
+            PublishErrorCode::UploadFailed => "upload_failed",
+            PublishErrorCode::RiskControl => "risk_control",
+            PublishErrorCode::RateLimited => "rate_limited",
+            PublishErrorCode::Network => "network",
+            PublishErrorCode::Unknown => "unknown",
+        }
+    }
+
+    /// 从存储的字符串解析，空值或无法识别时返回 None
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            PublishErrorCode::CookieExpired,
+            PublishErrorCode::UploadFailed,
+            PublishErrorCode::RiskControl,
+            PublishErrorCode::RateLimited,
+            PublishErrorCode::Network,
+            PublishErrorCode::Unknown,
+        ].into_iter().find(|code| code.as_str() == value)
+    }
+
+    /// 按错误信息关键字归类（与失败分析、自动重试使用同一套规则，见 classify_failure）
+    pub fn from_message(message: &str) -> Self {
+        classify_failure(message).code
+    }
 }
 
 impl std::convert::From<rusqlite::Error> for PlatformError {
@@ -259,6 +750,28 @@ impl std::convert::From<std::io::Error> for PlatformError {
     }
 }
 
+/// POI (location) search result
+/// 位置（POI）搜索结果，发布时作为作品的位置锚点
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PoiInfo {
+    pub poi_id: String,
+    pub poi_name: String,
+    pub address: String,
+    pub city: String,
+}
+
+/// Music search result
+/// 音乐搜索结果，选中后以 `music_id` 作为作品配乐
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MusicSearchItem {
+    pub music_id: String,
+    pub title: String,
+    pub author: String,
+    pub duration: i64,  // 时长（秒）
+    pub cover_url: String,
+    pub play_url: String,  // 试听地址
+}
+
 /// 音乐信息
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MusicInfo {
@@ -272,6 +785,22 @@ pub struct ExtraInfo {
     pub self_declaration: Option<serde_json::Value>,
 }
 
+/// 可见范围：公开
+pub const VISIBILITY_PUBLIC: i32 = 0;
+/// 可见范围：仅自己可见（适合先发布私密作品测试）
+pub const VISIBILITY_PRIVATE: i32 = 1;
+/// 可见范围：好友可见
+pub const VISIBILITY_FRIENDS: i32 = 2;
+
+/// Publish mode
+/// 发布方式：直接发布，或上传后保存到平台草稿箱
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PublishMode {
+    #[default]
+    Publish,
+    Draft,
+}
+
 /// Publish request
 ///
 /// 包含视频发布所需的公共信息
@@ -281,10 +810,14 @@ pub struct PublishRequest {
     pub account_id: String,
     pub video_path: PathBuf,
     pub cover_path: Option<PathBuf>,
+    /// 图文作品的图片（按展示顺序；非空时发布图文而不是视频，忽略 video_path）
+    pub image_paths: Vec<PathBuf>,
     pub title: String,
     pub description: Option<String>,
     pub hashtags: Vec<String>,
+    /// 可见范围（VISIBILITY_PUBLIC / VISIBILITY_PRIVATE / VISIBILITY_FRIENDS）
     pub visibility_type: i32,
+    /// 是否允许下载（1 允许，0 不允许）
     pub download_allowed: i32,
     pub timeout: i64,
     /// 记录ID（用于回调）
@@ -308,9 +841,23 @@ pub struct PublishRequest {
     /// 进度跟踪信息（task_id, detail_id, account_id, app_handle 用于事件推送）
     #[doc(hidden)]
     pub progress_info: Option<(String, String, String, tauri::AppHandle)>,
+    /// 取消令牌（发布策略在步骤之间检查）
+    pub cancel_token: Option<PublishCancelToken>,
+    /// 并发许可（等待验证期间暂时归还）
+    pub publish_permit: Option<PublishPermit>,
+    /// 发布方式（草稿仅抖音支持）
+    pub publish_mode: PublishMode,
 }
 
 impl PublishRequest {
+    /// 发布已被用户取消时返回 Cancelled 错误
+    pub fn check_cancelled(&self) -> Result<(), PlatformError> {
+        match &self.cancel_token {
+            Some(token) if token.is_cancelled() => Err(PlatformError::Cancelled(PUBLISH_CANCELLED_MESSAGE.to_string())),
+            _ => Ok(()),
+        }
+    }
+
     /// 从平台数据获取指定字段
     pub fn get_platform_field<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
         self.platform_data
@@ -350,14 +897,18 @@ pub trait Platform: Send + Sync {
     /// Refresh account credentials
     async fn refresh_credentials(&self, account_id: &str) -> Result<UserAccount, PlatformError>;
 
+    /// Verify account params (credentials) with a cheap authenticated request
+    /// 用一次轻量的登录态请求校验账号凭证（params JSON）是否仍然有效
+    async fn verify_credentials(&self, params: &str) -> Result<(), PlatformError>;
+
     /// Publish video to the platform
     async fn publish_video(&self, request: PublishRequest) -> Result<PublishResult, PlatformError>;
 
     /// Get publication status
     async fn get_publication_status(&self, publication_id: &str) -> Result<PlatformPublication, PlatformError>;
 
-    /// Get account statistics
-    async fn get_account_stats(&self, account_id: &str) -> Result<PublicationStats, PlatformError>;
+    /// Get account statistics (followers, total likes, video count)
+    async fn get_account_stats(&self, account_id: &str) -> Result<AccountProfileStats, PlatformError>;
 
     /// Get platform credentials from user params
     fn get_credentials_from_params(&self, params: &str) -> Result<PlatformCredentials, PlatformError>;
@@ -379,6 +930,8 @@ pub enum ProgressStatus {
     BuildingData,  // 构建数据中
     #[serde(rename = "publishing")]
     Publishing,    // 发布中
+    #[serde(rename = "verification_required")]
+    VerificationRequired,  // 等待用户完成验证码 / 安全验证
     #[serde(rename = "completed")]
     Completed,     // 完成
     #[serde(rename = "failed")]
@@ -0,0 +1,78 @@
+//! Desktop Notifications
+//!
+//! 耗时操作结束后发送系统通知（窗口最小化或在后台时也能看到）：
+//! 发布完成 / 失败、定时发布执行结果、后台评论同步的新增评论和失败。
+//! 每类通知可在设置中单独关闭，总开关关闭时不发送任何通知。
+
+use std::sync::OnceLock;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+use crate::storage::DatabaseManager;
+
+/// 应用启动时保存的 AppHandle（后台任务没有 AppHandle，通过它发送通知）
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Kind of operation a notification reports
+/// 通知类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Publish,
+    ScheduledJob,
+    CommentSync,
+}
+
+/// Desktop notification settings
+/// 系统通知设置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub enabled: bool,          // 总开关
+    pub publish: bool,          // 发布完成 / 失败
+    pub scheduled_job: bool,    // 定时发布执行结果
+    pub comment_sync: bool,     // 后台评论同步的新增评论和失败
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            publish: true,
+            scheduled_job: true,
+            comment_sync: true,
+        }
+    }
+}
+
+impl NotificationSettings {
+    /// 该类通知是否开启
+    pub fn allows(&self, kind: NotificationKind) -> bool {
+        self.enabled && match kind {
+            NotificationKind::Publish => self.publish,
+            NotificationKind::ScheduledJob => self.scheduled_job,
+            NotificationKind::CommentSync => self.comment_sync,
+        }
+    }
+}
+
+/// 保存 AppHandle，之后才能发送通知（应用启动时调用一次）
+pub fn init_notifications(app: AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// Show a desktop notification if its kind is enabled
+/// 按设置发送系统通知（未开启该类通知或尚未初始化时忽略）
+pub fn notify(db_manager: &DatabaseManager, kind: NotificationKind, title: &str, body: &str) {
+    let Some(app) = APP_HANDLE.get() else {
+        return;
+    };
+
+    let settings = db_manager.get_notification_settings().unwrap_or_default();
+    if !settings.allows(kind) {
+        return;
+    }
+
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        tracing::warn!("[Notification] 发送系统通知失败: {}", e);
+    }
+}
@@ -0,0 +1,103 @@
+//! Publish Cancellation
+//!
+//! 取消进行中的发布：每次执行发布任务时登记一个 [`PublishCancelToken`]，`cancel_publication_task` 触发后
+//! - 仍在排队等待并发许可的账号直接中止（abort）；
+//! - 已开始的账号由发布策略在流程步骤之间、分片上传之间检查令牌后自行停止。
+//!
+//! 两种情况下账号都标记为失败，消息为 [`PUBLISH_CANCELLED_MESSAGE`]
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::task::AbortHandle;
+
+/// 被取消账号的发布结果消息
+pub const PUBLISH_CANCELLED_MESSAGE: &str = "用户已取消发布";
+
+#[derive(Debug, Default)]
+struct TrackedTasks {
+    /// 尚未拿到并发许可的账号（detail_id → 中止句柄）
+    pending: HashMap<String, AbortHandle>,
+    /// 已开始发布的账号，不再中止
+    started: HashSet<String>,
+}
+
+/// Cancellation token of one running publish
+/// 一次发布的取消令牌（克隆后共享同一状态）
+#[derive(Debug, Clone, Default)]
+pub struct PublishCancelToken {
+    cancelled: Arc<AtomicBool>,
+    tasks: Arc<Mutex<TrackedTasks>>,
+}
+
+impl PublishCancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 登记排队中的账号发布任务，取消时直接中止
+    pub fn track_pending(&self, detail_id: &str, handle: AbortHandle) {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        // 任务可能在登记前就已拿到许可开始执行
+        if !tasks.started.contains(detail_id) {
+            tasks.pending.insert(detail_id.to_string(), handle);
+        }
+    }
+
+    /// 账号拿到并发许可、开始发布时调用；已取消时返回 false
+    pub fn start(&self, detail_id: &str) -> bool {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        tasks.pending.remove(detail_id);
+        tasks.started.insert(detail_id.to_string());
+        !self.is_cancelled()
+    }
+
+    /// 取消发布，返回被中止的排队任务数
+    pub fn cancel(&self) -> usize {
+        self.cancelled.store(true, Ordering::SeqCst);
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        let aborted = tasks.pending.len();
+        for (_, handle) in tasks.pending.drain() {
+            handle.abort();
+        }
+        aborted
+    }
+}
+
+static RUNNING_PUBLISHES: OnceLock<Mutex<HashMap<String, PublishCancelToken>>> = OnceLock::new();
+
+fn running_publishes() -> &'static Mutex<HashMap<String, PublishCancelToken>> {
+    RUNNING_PUBLISHES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Register a running publish of a task and get its cancellation token
+/// 登记正在执行的发布任务，返回取消令牌
+pub fn register_publish(task_id: &str) -> PublishCancelToken {
+    let token = PublishCancelToken::default();
+    running_publishes().lock().unwrap_or_else(|e| e.into_inner())
+        .insert(task_id.to_string(), token.clone());
+    token
+}
+
+/// 发布结束后注销（同一任务已被重新登记时保留新的令牌）
+pub fn unregister_publish(task_id: &str, token: &PublishCancelToken) {
+    let mut running = running_publishes().lock().unwrap_or_else(|e| e.into_inner());
+    if running.get(task_id).is_some_and(|t| Arc::ptr_eq(&t.cancelled, &token.cancelled)) {
+        running.remove(task_id);
+    }
+}
+
+/// 任务是否正在发布
+pub fn is_publish_running(task_id: &str) -> bool {
+    running_publishes().lock().unwrap_or_else(|e| e.into_inner())
+        .contains_key(task_id)
+}
+
+/// Cancel the running publish of a task
+/// 取消任务正在执行的发布；任务没有在发布时返回 None，否则返回被中止的排队任务数
+pub fn cancel_publish(task_id: &str) -> Option<usize> {
+    let token = running_publishes().lock().unwrap_or_else(|e| e.into_inner())
+        .get(task_id)
+        .cloned()?;
+    Some(token.cancel())
+}
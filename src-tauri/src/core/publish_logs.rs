@@ -0,0 +1,174 @@
+//! Publish Log Tail
+//!
+//! 每个账号的发布流程运行在名为 `publish` 的 span 中（携带 detail_id 字段），
+//! PublishLogLayer 捕获该 span 内的 tracing 事件并广播出去，
+//! 前端订阅某个 detail_id 后即可实时查看该账号发布的每一步日志
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::{Mutex, OnceLock};
+use serde::Serialize;
+use tauri::AppHandle;
+use super::emit_event;
+use tokio::sync::broadcast;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// 发布流程 span 名称
+pub const PUBLISH_SPAN: &str = "publish";
+
+/// 推送到前端的事件名
+pub const PUBLISH_LOG_EVENT: &str = "publish-log";
+
+/// 广播缓冲区大小（订阅方处理不过来时丢弃最旧的日志）
+const LOG_CHANNEL_CAPACITY: usize = 1024;
+
+/// Publish log entry
+/// 单条发布日志
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishLogEntry {
+    pub detail_id: String,
+    pub level: String,
+    pub message: String,
+    pub timestamp: i64,
+}
+
+static LOG_CHANNEL: OnceLock<broadcast::Sender<PublishLogEntry>> = OnceLock::new();
+
+static SUBSCRIPTIONS: OnceLock<Mutex<HashMap<String, tokio::task::JoinHandle<()>>>> = OnceLock::new();
+
+fn log_channel() -> &'static broadcast::Sender<PublishLogEntry> {
+    LOG_CHANNEL.get_or_init(|| broadcast::channel(LOG_CHANNEL_CAPACITY).0)
+}
+
+fn subscriptions() -> &'static Mutex<HashMap<String, tokio::task::JoinHandle<()>>> {
+    SUBSCRIPTIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 记录在 publish span 上的 detail_id
+struct PublishScope(String);
+
+/// 提取 detail_id 字段
+#[derive(Default)]
+struct DetailIdVisitor(Option<String>);
+
+impl Visit for DetailIdVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "detail_id" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "detail_id" {
+            self.0 = Some(format!("{:?}", value).trim_matches('"').to_string());
+        }
+    }
+}
+
+/// 将事件格式化为一行文本：message 在前，其余字段以 key=value 追加
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// tracing layer：捕获 publish span 内的事件
+pub struct PublishLogLayer;
+
+impl<S> Layer<S> for PublishLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &tracing::span::Attributes<'_>, id: &tracing::span::Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != PUBLISH_SPAN {
+            return;
+        }
+
+        let mut visitor = DetailIdVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(detail_id), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(PublishScope(detail_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        // 没有订阅方时不做格式化
+        let sender = log_channel();
+        if sender.receiver_count() == 0 {
+            return;
+        }
+        // 本模块转发日志时产生的事件不再广播，避免推送失败的警告被反复推送
+        if event.metadata().target() == module_path!() {
+            return;
+        }
+
+        let detail_id = ctx.event_scope(event).and_then(|scope| {
+            scope.from_root()
+                .find_map(|span| span.extensions().get::<PublishScope>().map(|s| s.0.clone()))
+        });
+        let Some(detail_id) = detail_id else {
+            return;
+        };
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let _ = sender.send(PublishLogEntry {
+            detail_id,
+            level: event.metadata().level().to_string(),
+            message: visitor.0,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        });
+    }
+}
+
+/// Start forwarding the logs of one publish to the frontend as `publish-log` events
+/// 订阅指定 detail_id 的发布日志（重复订阅不会重复推送）
+pub fn subscribe_publish_logs(app_handle: AppHandle, detail_id: &str) {
+    let mut subs = subscriptions().lock().unwrap_or_else(|e| e.into_inner());
+    if subs.get(detail_id).is_some_and(|h| !h.is_finished()) {
+        return;
+    }
+
+    let mut receiver = log_channel().subscribe();
+    let target = detail_id.to_string();
+    let handle = tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(entry) if entry.detail_id == target => {
+                    if let Err(e) = emit_event(&app_handle, &entry) {
+                        tracing::warn!("[PublishLog] Failed to emit log event: {}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+
+    subs.insert(detail_id.to_string(), handle);
+}
+
+/// Stop forwarding the logs of one publish
+/// 取消订阅指定 detail_id 的发布日志
+pub fn unsubscribe_publish_logs(detail_id: &str) -> bool {
+    let mut subs = subscriptions().lock().unwrap_or_else(|e| e.into_inner());
+    match subs.remove(detail_id) {
+        Some(handle) => {
+            handle.abort();
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,80 @@
+//! Publish Pacing
+//!
+//! 发布节奏限制：限制同一账号 24 小时内的发布次数和两次发布的最小间隔，可按平台单独配置。
+//! 每个账号发布前检查，超出限制的账号不算失败，而是推迟到最早允许的时间由调度器自动发布
+
+use std::collections::BTreeMap;
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use super::{PlatformType, SCHEDULE_TIME_FORMAT};
+
+/// Pacing limits of one account
+/// 单个账号的发布节奏限制（0 表示不限）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PacingRule {
+    /// 24 小时内最多发布次数
+    pub max_per_day: u32,
+    /// 两次发布的最小间隔（分钟）
+    pub min_interval_minutes: u32,
+}
+
+/// Publish pacing rules
+/// 发布节奏规则：默认规则作用于所有账号，平台规则覆盖该平台账号的默认规则
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PublishPacingRules {
+    pub account: PacingRule,
+    /// 平台ID（douyin / kuaishou / ...）→ 规则
+    pub platforms: BTreeMap<String, PacingRule>,
+}
+
+impl PublishPacingRules {
+    /// 账号所在平台生效的规则
+    pub fn rule_for(&self, platform: &PlatformType) -> PacingRule {
+        self.platforms.get(platform.as_str()).copied().unwrap_or(self.account)
+    }
+}
+
+/// Why and until when a publish is delayed
+/// 发布推迟信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacingDelay {
+    pub until: NaiveDateTime,
+    pub reason: String,
+}
+
+impl PacingDelay {
+    /// 推迟到的时间（SCHEDULE_TIME_FORMAT）
+    pub fn until_string(&self) -> String {
+        self.until.format(SCHEDULE_TIME_FORMAT).to_string()
+    }
+}
+
+/// 检查账号现在能否发布；`recent` 为该账号最近 24 小时内的发布时间，超出限制时返回推迟信息
+pub fn check_pacing(rule: &PacingRule, recent: &[NaiveDateTime], now: NaiveDateTime) -> Option<PacingDelay> {
+    let window_start = now - Duration::hours(24);
+    let mut in_window: Vec<NaiveDateTime> = recent.iter().copied().filter(|t| *t > window_start).collect();
+    in_window.sort();
+
+    let mut delays = Vec::new();
+    if rule.max_per_day > 0 && in_window.len() >= rule.max_per_day as usize {
+        // 最早的几次发布滑出 24 小时窗口后才有余量
+        let oldest = in_window[in_window.len() - rule.max_per_day as usize];
+        delays.push(PacingDelay {
+            until: oldest + Duration::hours(24),
+            reason: format!("24 小时内已发布 {} 次（上限 {} 次）", in_window.len(), rule.max_per_day),
+        });
+    }
+    if let Some(last) = in_window.last().filter(|_| rule.min_interval_minutes > 0) {
+        let next = *last + Duration::minutes(rule.min_interval_minutes as i64);
+        if next > now {
+            delays.push(PacingDelay {
+                until: next,
+                reason: format!("距上次发布不足 {} 分钟", rule.min_interval_minutes),
+            });
+        }
+    }
+
+    delays.into_iter().max_by_key(|d| d.until)
+}
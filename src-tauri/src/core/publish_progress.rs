@@ -4,8 +4,8 @@
 
 use std::sync::{Arc, RwLock, OnceLock};
 use chrono::Utc;
-use crate::core::{PublishProgressEvent, ProgressStatus};
-use tauri::{AppHandle, Emitter};
+use crate::core::{PublishProgressEvent, ProgressStatus, emit_event};
+use tauri::AppHandle;
 
 /// 进度事件发送器
 #[derive(Clone)]
@@ -40,7 +40,7 @@ impl ProgressEmitter {
 
         if let Some(handle) = app_handle {
             // 使用 AppHandle 发送事件到所有窗口
-            if let Err(e) = handle.emit("publish-progress", event) {
+            if let Err(e) = emit_event(&handle, event) {
                 tracing::warn!("[Progress] Failed to emit progress event: {}", e);
             } else {
                 tracing::info!("[Progress] ✅ Emitted: detail_id={}, status={:?}, progress={}%, message={}",
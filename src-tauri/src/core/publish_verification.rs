@@ -0,0 +1,150 @@
+//! Publish Verification (captcha / risk-control challenges)
+//!
+//! 发布过程中平台要求滑块验证码或安全验证时，发布策略不直接失败，而是暂停等待用户处理：
+//! 1. 登记待验证的账号并发送 `publish-captcha-required` 事件（带验证页地址和账号）；
+//! 2. 用户通过 `open_publish_verification` 在该账号的浏览器资料目录中打开验证页完成验证；
+//! 3. `resume_publish_after_verification(detail_id)` 通知发布策略继续，重新提交发布。
+//!
+//! 等待期间归还该账号的并发许可（[`PublishPermit`]），其他账号可以继续发布；用户完成验证后重新获取许可再提交。
+//! 等待超过 [`VERIFICATION_TIMEOUT`] 或任务被取消时按原错误失败
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use serde::Serialize;
+use tauri::AppHandle;
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+use super::{PublishCancelToken, emit_event};
+
+/// 等待用户完成验证的最长时间
+pub const VERIFICATION_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Event sent when a publish is waiting for the user to pass a verification
+/// 发布等待验证事件
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishCaptchaEvent {
+    pub task_id: String,
+    pub detail_id: String,
+    pub account_id: String,
+    pub platform: String,
+    pub verify_url: String,   // 需要在浏览器中打开完成验证的页面
+    pub message: String,      // 平台返回的提示
+    pub timestamp: i64,
+}
+
+/// Concurrency permit held by one account publish
+/// 账号发布占用的并发许可（克隆后共享同一许可），等待验证时可暂时归还
+#[derive(Debug, Clone)]
+pub struct PublishPermit {
+    semaphore: Arc<Semaphore>,
+    permit: Arc<Mutex<Option<OwnedSemaphorePermit>>>,
+}
+
+impl PublishPermit {
+    /// 等待并获取一个并发许可
+    pub async fn acquire(semaphore: Arc<Semaphore>) -> Self {
+        let permit = semaphore.clone().acquire_owned().await.ok();
+        Self { semaphore, permit: Arc::new(Mutex::new(permit)) }
+    }
+
+    fn slot(&self) -> std::sync::MutexGuard<'_, Option<OwnedSemaphorePermit>> {
+        self.permit.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 归还许可，排队的账号可以开始发布
+    fn release(&self) {
+        self.slot().take();
+    }
+
+    /// 重新获取许可（仍持有时直接返回）
+    async fn reacquire(&self) {
+        if self.slot().is_some() {
+            return;
+        }
+        let permit = self.semaphore.clone().acquire_owned().await.ok();
+        *self.slot() = permit;
+    }
+}
+
+struct PendingVerification {
+    event: PublishCaptchaEvent,
+    resume: oneshot::Sender<()>,
+}
+
+static PENDING_VERIFICATIONS: OnceLock<Mutex<HashMap<String, PendingVerification>>> = OnceLock::new();
+
+fn pending_verifications() -> &'static Mutex<HashMap<String, PendingVerification>> {
+    PENDING_VERIFICATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Pause a publish until the user passes the verification
+/// 发送验证事件并等待用户完成验证；用户确认后返回 true，超时或任务被取消时返回 false。
+/// 等待期间归还 `permit`，用户确认后重新获取许可再返回
+pub async fn wait_for_verification(
+    app: &AppHandle,
+    event: PublishCaptchaEvent,
+    cancel_token: Option<&PublishCancelToken>,
+    permit: Option<&PublishPermit>,
+) -> bool {
+    let detail_id = event.detail_id.clone();
+    let (resume_tx, mut resume_rx) = oneshot::channel();
+    pending_verifications().lock().unwrap_or_else(|e| e.into_inner())
+        .insert(detail_id.clone(), PendingVerification { event: event.clone(), resume: resume_tx });
+
+    tracing::warn!("[Verification] 发布需要验证，等待用户处理: detail_id={}, url={}", detail_id, event.verify_url);
+    if let Err(e) = emit_event(app, &event) {
+        tracing::warn!("[Verification] 发送验证事件失败: {}", e);
+    }
+    if let Some(permit) = permit {
+        permit.release();
+    }
+
+    let deadline = tokio::time::Instant::now() + VERIFICATION_TIMEOUT;
+    let resumed = loop {
+        if cancel_token.is_some_and(|token| token.is_cancelled()) {
+            break false;
+        }
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            tracing::warn!("[Verification] 等待验证超时: detail_id={}", detail_id);
+            break false;
+        }
+        match tokio::time::timeout(Duration::from_secs(1).min(deadline - now), &mut resume_rx).await {
+            Ok(result) => break result.is_ok(),
+            Err(_) => continue,
+        }
+    };
+
+    pending_verifications().lock().unwrap_or_else(|e| e.into_inner()).remove(&detail_id);
+
+    if let Some(permit) = permit.filter(|_| resumed) {
+        tracing::info!("[Verification] 验证完成，等待并发许可后继续发布: detail_id={}", detail_id);
+        permit.reacquire().await;
+    }
+    resumed
+}
+
+/// Resume a publish waiting for verification
+/// 用户完成验证后继续发布；该账号没有在等待验证时返回 false
+pub fn resume_verification(detail_id: &str) -> bool {
+    let pending = pending_verifications().lock().unwrap_or_else(|e| e.into_inner()).remove(detail_id);
+    match pending {
+        Some(pending) => pending.resume.send(()).is_ok(),
+        None => false,
+    }
+}
+
+/// 账号正在等待的验证
+pub fn pending_verification(detail_id: &str) -> Option<PublishCaptchaEvent> {
+    pending_verifications().lock().unwrap_or_else(|e| e.into_inner())
+        .get(detail_id)
+        .map(|pending| pending.event.clone())
+}
+
+/// 所有正在等待验证的账号（界面重新打开时恢复提示）
+pub fn list_pending_verifications() -> Vec<PublishCaptchaEvent> {
+    pending_verifications().lock().unwrap_or_else(|e| e.into_inner())
+        .values()
+        .map(|pending| pending.event.clone())
+        .collect()
+}
@@ -0,0 +1,95 @@
+//! Batch Re-authorization Queue
+//!
+//! 批量重新授权：把凭证失效的账号排成队列，逐个打开浏览器授权会话，
+//! 一个账号授权完成（或失败）后自动切到下一个。浏览器自动化器同一时间只有一个会话，
+//! 因此队列按顺序执行；队列只保存在内存中，进度通过 [`REAUTH_QUEUE_EVENT`] 推送到前端
+
+use serde::Serialize;
+use super::{PlatformType, UserAccount};
+
+/// 推送到前端的事件名（载荷为整个队列）
+pub const REAUTH_QUEUE_EVENT: &str = "reauth-queue-progress";
+
+/// Status of one account in the re-authorization queue
+/// 队列中单个账号的授权状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReauthItemStatus {
+    Pending,
+    InProgress,
+    Completed,
+    Failed,
+    /// 队列被取消，未授权
+    Skipped,
+}
+
+/// Account waiting for re-authorization
+/// 待重新授权的账号
+#[derive(Debug, Clone, Serialize)]
+pub struct ReauthQueueItem {
+    pub account_id: String,
+    pub nickname: String,
+    pub platform: PlatformType,
+    pub status: ReauthItemStatus,
+    /// 失败原因或授权结果说明
+    pub message: Option<String>,
+}
+
+/// Re-authorization queue
+/// 重新授权队列
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReauthQueue {
+    pub items: Vec<ReauthQueueItem>,
+    /// 用户已取消（当前账号授权结束后不再继续）
+    pub cancelled: bool,
+}
+
+impl ReauthQueue {
+    pub fn new(accounts: Vec<UserAccount>) -> Self {
+        let items = accounts.into_iter()
+            .map(|account| ReauthQueueItem {
+                account_id: account.id,
+                nickname: account.nickname,
+                platform: account.platform,
+                status: ReauthItemStatus::Pending,
+                message: None,
+            })
+            .collect();
+        Self { items, cancelled: false }
+    }
+
+    /// 是否还有未结束的账号
+    pub fn is_running(&self) -> bool {
+        self.items.iter().any(|item| matches!(item.status, ReauthItemStatus::Pending | ReauthItemStatus::InProgress))
+    }
+
+    /// 取出下一个待授权账号并标记为进行中；已取消或全部结束时返回 None
+    pub fn start_next(&mut self) -> Option<ReauthQueueItem> {
+        if self.cancelled {
+            return None;
+        }
+        let item = self.items.iter_mut().find(|item| item.status == ReauthItemStatus::Pending)?;
+        item.status = ReauthItemStatus::InProgress;
+        Some(item.clone())
+    }
+
+    /// 记录账号的授权结果
+    pub fn finish(&mut self, account_id: &str, result: Result<String, String>) {
+        if let Some(item) = self.items.iter_mut().find(|item| item.account_id == account_id) {
+            let (status, message) = match result {
+                Ok(message) => (ReauthItemStatus::Completed, message),
+                Err(message) => (ReauthItemStatus::Failed, message),
+            };
+            item.status = status;
+            item.message = Some(message);
+        }
+    }
+
+    /// 取消队列：尚未开始的账号标记为跳过，进行中的账号授权结束后停止
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+        for item in self.items.iter_mut().filter(|item| item.status == ReauthItemStatus::Pending) {
+            item.status = ReauthItemStatus::Skipped;
+        }
+    }
+}
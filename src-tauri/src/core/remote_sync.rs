@@ -0,0 +1,322 @@
+//! Remote Sync
+//!
+//! 可选的多机同步：把账号（加密）、发布任务和发布结果推送到自建的同步服务，
+//! 并拉取其他电脑的变更，两位运营在不同电脑上共享同一套账号矩阵。
+//!
+//! - 本地变更由 sync_changes 触发器记录，每轮推送所有未推送的变更
+//! - 冲突按 updated_at（UTC）解决：远端变更只有比本地更新时才会应用（后写者胜）
+//! - 账号数据包含 Cookie 等凭证，用同步密码派生的密钥做 AES-256-GCM 加密后再上传，
+//!   服务端只能看到密文
+//!
+//! 服务端接口：
+//! - `GET  {endpoint}/sync/pull?cursor=&device_id=` 返回 `{ changes, cursor }`
+//! - `POST {endpoint}/sync/push` 提交 `{ device_id, changes }`
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::core::{PublicationAccountDetail, PublicationTask, UserAccount};
+use crate::storage::{DatabaseManager, PendingSyncChange};
+use super::crypto::{self, encrypt_payload};
+use super::stats_refresher::jittered;
+
+/// 默认同步间隔（分钟）
+pub const DEFAULT_REMOTE_SYNC_INTERVAL_MINUTES: u32 = 15;
+
+/// 未启用同步时重新检查配置的间隔
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 请求超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 密钥派生盐（各台电脑使用同一同步密码即可得到相同密钥）
+const KEY_SALT: &[u8] = b"auto-matrix-manager/remote-sync";
+
+/// 同一时间只允许一轮同步
+static SYNC_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Synced entity kind
+/// 同步的实体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncEntity {
+    Account,
+    Task,
+    Detail,
+}
+
+impl SyncEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncEntity::Account => "account",
+            SyncEntity::Task => "task",
+            SyncEntity::Detail => "detail",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "account" => Some(SyncEntity::Account),
+            "task" => Some(SyncEntity::Task),
+            "detail" => Some(SyncEntity::Detail),
+            _ => None,
+        }
+    }
+}
+
+/// Remote sync configuration
+/// 远程同步配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteSyncConfig {
+    pub enabled: bool,
+    pub endpoint: String,          // 同步服务地址，如 https://sync.example.com
+    pub token: String,             // 访问令牌（Bearer）
+    pub passphrase: String,        // 同步密码，用于加密账号凭证（各台电脑需一致）
+    pub interval_minutes: u32,
+    pub device_id: String,         // 本机标识，首次保存时生成
+}
+
+impl Default for RemoteSyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: String::new(),
+            token: String::new(),
+            passphrase: String::new(),
+            interval_minutes: DEFAULT_REMOTE_SYNC_INTERVAL_MINUTES,
+            device_id: String::new(),
+        }
+    }
+}
+
+/// One change exchanged with the sync server
+/// 与服务端交换的一条变更
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncChange {
+    pub entity: SyncEntity,
+    pub id: String,
+    pub updated_at: String,        // UTC，格式 %Y-%m-%d %H:%M:%S
+    #[serde(default)]
+    pub deleted: bool,
+    #[serde(default)]
+    pub payload: Option<String>,   // 实体 JSON；账号为加密后的 base64
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PullResponse {
+    changes: Vec<SyncChange>,
+    cursor: String,
+}
+
+#[derive(Debug, Serialize)]
+struct PushRequest<'a> {
+    device_id: &'a str,
+    changes: &'a [SyncChange],
+}
+
+/// Result of one sync round
+/// 一轮同步的结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RemoteSyncResult {
+    pub pulled: usize,     // 拉取到的远端变更数
+    pub applied: usize,    // 实际应用的远端变更数
+    pub skipped: usize,    // 本地更新而跳过的远端变更数
+    pub pushed: usize,     // 推送的本地变更数
+    pub finished_at: String,
+}
+
+/// 从同步密码派生 AES-256 密钥
+fn derive_key(passphrase: &str) -> Result<[u8; 32], String> {
+    crypto::derive_key(passphrase, KEY_SALT)
+}
+
+fn decrypt_payload(key: &[u8; 32], encoded: &str) -> Result<String, String> {
+    crypto::decrypt_payload(key, encoded)
+        .map_err(|e| if e == "解密失败" { "解密失败，请检查各台电脑的同步密码是否一致".to_string() } else { e })
+}
+
+/// 根据本地变更记录构建推送内容；实体已不存在时视为删除
+fn build_change(db_manager: &DatabaseManager, key: &[u8; 32], pending: &PendingSyncChange) -> Result<SyncChange, String> {
+    let payload = if pending.deleted {
+        None
+    } else {
+        match pending.entity {
+            SyncEntity::Account => db_manager.get_account(&pending.entity_id).map_err(|e| e.to_string())?
+                .map(|account| serde_json::to_string(&account).map_err(|e| e.to_string()))
+                .transpose()?
+                .map(|json| encrypt_payload(key, &json))
+                .transpose()?,
+            SyncEntity::Task => db_manager.get_publication_task(&pending.entity_id).map_err(|e| e.to_string())?
+                .map(|task| serde_json::to_string(&task).map_err(|e| e.to_string()))
+                .transpose()?,
+            SyncEntity::Detail => db_manager.get_publication_account_detail(&pending.entity_id).map_err(|e| e.to_string())?
+                .map(|detail| serde_json::to_string(&detail).map_err(|e| e.to_string()))
+                .transpose()?,
+        }
+    };
+
+    Ok(SyncChange {
+        entity: pending.entity,
+        id: pending.entity_id.clone(),
+        updated_at: pending.updated_at.clone(),
+        deleted: payload.is_none(),
+        payload,
+    })
+}
+
+/// 应用一条远端变更；本地同一实体更新（或同时）修改过时跳过，返回是否已应用
+fn apply_change(db_manager: &DatabaseManager, key: &[u8; 32], change: &SyncChange) -> Result<bool, String> {
+    let local_updated_at = db_manager.get_sync_updated_at(change.entity, &change.id).map_err(|e| e.to_string())?;
+    if local_updated_at.is_some_and(|local| local >= change.updated_at) {
+        return Ok(false);
+    }
+
+    match (change.entity, change.payload.as_deref().filter(|_| !change.deleted)) {
+        (SyncEntity::Account, Some(payload)) => {
+            let account: UserAccount = serde_json::from_str(&decrypt_payload(key, payload)?)
+                .map_err(|e| format!("账号数据格式错误: {}", e))?;
+            db_manager.save_account(&account).map_err(|e| e.to_string())?;
+            db_manager.set_account_on_hold(&account.id, account.on_hold).map_err(|e| e.to_string())?;
+            db_manager.set_account_tags(&account.id, &account.tags).map_err(|e| e.to_string())?;
+        }
+        (SyncEntity::Task, Some(payload)) => {
+            let task: PublicationTask = serde_json::from_str(payload)
+                .map_err(|e| format!("任务数据格式错误: {}", e))?;
+            db_manager.save_publication_task(&task).map_err(|e| e.to_string())?;
+        }
+        (SyncEntity::Detail, Some(payload)) => {
+            let detail: PublicationAccountDetail = serde_json::from_str(payload)
+                .map_err(|e| format!("发布结果数据格式错误: {}", e))?;
+            db_manager.save_publication_account_detail(&detail).map_err(|e| e.to_string())?;
+        }
+        (SyncEntity::Account, None) => {
+            db_manager.delete_account(&change.id).map_err(|e| e.to_string())?;
+        }
+        (SyncEntity::Task, None) => {
+            db_manager.delete_publication_task(&change.id).map_err(|e| e.to_string())?;
+        }
+        (SyncEntity::Detail, None) => {
+            db_manager.delete_publication_account_detail(&change.id).map_err(|e| e.to_string())?;
+        }
+    }
+
+    // 触发器会把刚写入的数据记为本地变更，这里改回远端时间戳并标记为已推送
+    db_manager.record_remote_sync_change(change.entity, &change.id, &change.updated_at, change.deleted)
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+fn sync_url(config: &RemoteSyncConfig, path: &str) -> String {
+    format!("{}/sync/{}", config.endpoint.trim_end_matches('/'), path)
+}
+
+async fn sync_round(db_manager: &DatabaseManager, config: &RemoteSyncConfig) -> Result<RemoteSyncResult, String> {
+    if config.endpoint.trim().is_empty() {
+        return Err("未配置同步服务地址".to_string());
+    }
+    if config.passphrase.is_empty() {
+        return Err("未设置同步密码，账号凭证无法加密".to_string());
+    }
+
+    let key = derive_key(&config.passphrase)?;
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("创建同步客户端失败: {}", e))?;
+    let mut result = RemoteSyncResult::default();
+
+    // 1. 拉取远端变更（账号 → 任务 → 发布结果的顺序应用）
+    let cursor = db_manager.get_remote_sync_cursor().map_err(|e| e.to_string())?;
+    let response = client.get(sync_url(config, "pull"))
+        .bearer_auth(&config.token)
+        .query(&[("cursor", cursor.as_str()), ("device_id", config.device_id.as_str())])
+        .send().await
+        .map_err(|e| format!("拉取远端变更失败: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("拉取远端变更失败: HTTP {}", response.status()));
+    }
+    let mut pulled: PullResponse = response.json().await
+        .map_err(|e| format!("解析远端变更失败: {}", e))?;
+    pulled.changes.sort_by_key(|c| (c.entity as u8, c.updated_at.clone()));
+
+    result.pulled = pulled.changes.len();
+    for change in &pulled.changes {
+        match apply_change(db_manager, &key, change) {
+            Ok(true) => result.applied += 1,
+            Ok(false) => result.skipped += 1,
+            Err(e) => return Err(format!("应用远端变更失败 ({} {}): {}", change.entity.as_str(), change.id, e)),
+        }
+    }
+    if !pulled.cursor.is_empty() {
+        db_manager.set_remote_sync_cursor(&pulled.cursor).map_err(|e| e.to_string())?;
+    }
+
+    // 2. 推送本地变更
+    let pending = db_manager.get_pending_sync_changes().map_err(|e| e.to_string())?;
+    if !pending.is_empty() {
+        let changes = pending.iter()
+            .map(|p| build_change(db_manager, &key, p))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let response = client.post(sync_url(config, "push"))
+            .bearer_auth(&config.token)
+            .json(&PushRequest { device_id: &config.device_id, changes: &changes })
+            .send().await
+            .map_err(|e| format!("推送本地变更失败: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("推送本地变更失败: HTTP {}", response.status()));
+        }
+
+        db_manager.mark_sync_changes_pushed(&pending).map_err(|e| e.to_string())?;
+        result.pushed = pending.len();
+    }
+
+    result.finished_at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    Ok(result)
+}
+
+/// Run one sync round now (pull, then push)
+/// 立即执行一轮同步（先拉取后推送）；已有同步在进行时返回错误
+pub async fn run_remote_sync(db_manager: &DatabaseManager) -> Result<RemoteSyncResult, String> {
+    if SYNC_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("同步正在进行中".to_string());
+    }
+
+    let result = match db_manager.get_remote_sync_config() {
+        Ok(config) => sync_round(db_manager, &config).await,
+        Err(e) => Err(e.to_string()),
+    };
+    SYNC_RUNNING.store(false, Ordering::SeqCst);
+
+    if let Ok(result) = &result {
+        tracing::info!("[RemoteSync] 同步完成: 拉取 {} 条（应用 {}，跳过 {}），推送 {} 条",
+            result.pulled, result.applied, result.skipped, result.pushed);
+    }
+    result
+}
+
+/// 启动后台同步：启用时按配置的间隔同步，未启用时每分钟检查一次配置
+pub fn start_remote_sync(db_manager: Arc<DatabaseManager>) {
+    tauri::async_runtime::spawn(async move {
+        // 错开应用启动时的其他请求
+        tokio::time::sleep(jittered(Duration::from_secs(60), 0.5)).await;
+
+        loop {
+            let config = db_manager.get_remote_sync_config().unwrap_or_default();
+            if !config.enabled {
+                tokio::time::sleep(IDLE_CHECK_INTERVAL).await;
+                continue;
+            }
+
+            if let Err(e) = run_remote_sync(&db_manager).await {
+                tracing::warn!("[RemoteSync] 同步失败: {}", e);
+            }
+
+            let interval = Duration::from_secs(config.interval_minutes.max(1) as u64 * 60);
+            tokio::time::sleep(jittered(interval, 0.1)).await;
+        }
+    });
+}
@@ -0,0 +1,144 @@
+//! Publish Scheduler
+//!
+//! 定时发布调度器：定时任务持久化在 scheduled_jobs 表中，
+//! 后台 tokio 任务轮询到期的任务并触发发布，应用重启后继续调度
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::storage::DatabaseManager;
+use super::notifications::{NotificationKind, notify};
+
+/// 轮询间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 定时时间格式（本地时间，与 created_at 一致）
+pub const SCHEDULE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Scheduled job status
+/// 定时任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledJobStatus {
+    Pending,     // 等待执行
+    Running,     // 执行中
+    Completed,   // 已执行（发布结果见任务详情）
+    Failed,      // 执行失败
+    Cancelled,   // 已取消
+}
+
+impl ScheduledJobStatus {
+    /// 数据库中存储的状态字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduledJobStatus::Pending => "pending",
+            ScheduledJobStatus::Running => "running",
+            ScheduledJobStatus::Completed => "completed",
+            ScheduledJobStatus::Failed => "failed",
+            ScheduledJobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// 解析状态字符串
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "running" => ScheduledJobStatus::Running,
+            "completed" => ScheduledJobStatus::Completed,
+            "failed" => ScheduledJobStatus::Failed,
+            "cancelled" => ScheduledJobStatus::Cancelled,
+            _ => ScheduledJobStatus::Pending,
+        }
+    }
+}
+
+/// Scheduled publish job - 定时发布任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    pub id: String,
+    pub task_id: String,
+    pub send_at: String,  // 计划发布时间（本地时间 YYYY-MM-DD HH:MM:SS）
+    pub status: ScheduledJobStatus,
+    pub message: Option<String>,  // 执行结果说明
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 解析计划发布时间
+pub fn parse_send_at(send_at: &str) -> Result<chrono::NaiveDateTime, String> {
+    chrono::NaiveDateTime::parse_from_str(send_at.trim(), SCHEDULE_TIME_FORMAT)
+        .map_err(|_| format!("无效的发布时间: {}（格式应为 YYYY-MM-DD HH:MM:SS）", send_at))
+}
+
+/// 启动定时发布调度器
+///
+/// 启动时先把上次退出时仍在执行中的任务恢复为等待状态（发布会跳过已完成的账号，
+/// 重新执行是安全的），之后每隔 POLL_INTERVAL 检查一次到期任务并调用 `runner` 发布。
+/// 应用关闭期间错过的任务会在下次启动后立即执行。
+pub fn start_scheduler<F, Fut>(db_manager: Arc<DatabaseManager>, runner: F)
+where
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<String, String>> + Send + 'static,
+{
+    match db_manager.requeue_running_scheduled_jobs() {
+        Ok(n) if n > 0 => tracing::info!("[Scheduler] 恢复 {} 个中断的定时任务", n),
+        Ok(_) => {}
+        Err(e) => tracing::error!("[Scheduler] 恢复中断的定时任务失败: {}", e),
+    }
+
+    let runner = Arc::new(runner);
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let now = chrono::Local::now().format(SCHEDULE_TIME_FORMAT).to_string();
+            let due_jobs = match db_manager.get_due_scheduled_jobs(&now) {
+                Ok(jobs) => jobs,
+                Err(e) => {
+                    tracing::error!("[Scheduler] 查询到期任务失败: {}", e);
+                    continue;
+                }
+            };
+
+            for job in due_jobs {
+                // 抢占任务，防止同一任务被重复触发
+                match db_manager.claim_scheduled_job(&job.id) {
+                    Ok(true) => {}
+                    Ok(false) => continue,
+                    Err(e) => {
+                        tracing::error!("[Scheduler] 更新任务状态失败: {}", e);
+                        continue;
+                    }
+                }
+
+                tracing::info!("[Scheduler] 触发定时发布: job={}, task={}, send_at={}", job.id, job.task_id, job.send_at);
+
+                let db_manager = db_manager.clone();
+                let runner = runner.clone();
+                tauri::async_runtime::spawn(async move {
+                    let (status, message) = match runner(job.task_id.clone()).await {
+                        Ok(summary) => (ScheduledJobStatus::Completed, summary),
+                        Err(e) => {
+                            tracing::error!("[Scheduler] 定时发布失败: job={}, error={}", job.id, e);
+                            (ScheduledJobStatus::Failed, e)
+                        }
+                    };
+
+                    if let Err(e) = db_manager.finish_scheduled_job(&job.id, status, &message) {
+                        tracing::error!("[Scheduler] 更新任务状态失败: {}", e);
+                    }
+
+                    let title = db_manager.get_publication_task(&job.task_id).ok().flatten()
+                        .map(|task| task.title)
+                        .unwrap_or_else(|| job.task_id.clone());
+                    let heading = match status {
+                        ScheduledJobStatus::Failed => "定时发布失败",
+                        _ => "定时发布已执行",
+                    };
+                    notify(&db_manager, NotificationKind::ScheduledJob, heading, &format!("{}：{}", title, message));
+                });
+            }
+        }
+    });
+}
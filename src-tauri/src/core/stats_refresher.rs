@@ -0,0 +1,101 @@
+//! Publication Stats Refresher
+//!
+//! 后台定期刷新最近发布作品的互动数据（评论数、点赞数等），无需手动提取评论。
+//! 每轮之间、每个作品之间都加入随机抖动，避免请求过于规律；
+//! 遇到平台限流时暂停本轮并指数退避
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use rand::Rng;
+use crate::storage::DatabaseManager;
+
+/// 两轮刷新之间的基础间隔
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// 限流时的最大退避间隔
+const MAX_BACKOFF: Duration = Duration::from_secs(4 * 60 * 60);
+
+/// 只刷新最近 N 天内发布的作品
+const RECENT_DAYS: i64 = 7;
+
+/// 每轮最多刷新的作品数
+const BATCH_LIMIT: usize = 50;
+
+/// 相邻两个作品之间的基础间隔
+const ITEM_DELAY: Duration = Duration::from_secs(5);
+
+/// 在 base 的基础上随机浮动 ±spread（0.0 ~ 1.0）
+pub(super) fn jittered(base: Duration, spread: f64) -> Duration {
+    let factor = rand::thread_rng().gen_range((1.0 - spread)..=(1.0 + spread));
+    base.mul_f64(factor.max(0.0))
+}
+
+/// 判断错误是否为平台限流
+pub(super) fn is_rate_limited(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    ["429", "rate limit", "too many requests", "频繁", "限流"]
+        .iter()
+        .any(|marker| lower.contains(marker))
+}
+
+/// 启动互动数据刷新器
+///
+/// 每轮取最近 RECENT_DAYS 天内发布成功的作品，逐个调用 `refresher` 刷新。
+/// 某个作品失败只记录日志；遇到限流则结束本轮，并把下一轮的间隔翻倍（不超过 MAX_BACKOFF），
+/// 一轮顺利完成后恢复为 REFRESH_INTERVAL。
+pub fn start_stats_refresher<F, Fut>(db_manager: Arc<DatabaseManager>, refresher: F)
+where
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+{
+    tauri::async_runtime::spawn(async move {
+        // 错开应用启动时的其他请求
+        tokio::time::sleep(jittered(Duration::from_secs(120), 0.5)).await;
+
+        let mut interval = REFRESH_INTERVAL;
+        loop {
+            let since = (chrono::Local::now() - chrono::Duration::days(RECENT_DAYS))
+                .format("%Y-%m-%d %H:%M:%S").to_string();
+
+            let detail_ids = match db_manager.get_recently_published_detail_ids(&since, BATCH_LIMIT) {
+                Ok(ids) => ids,
+                Err(e) => {
+                    tracing::error!("[StatsRefresher] 查询最近发布失败: {}", e);
+                    Vec::new()
+                }
+            };
+
+            let mut refreshed = 0;
+            let mut rate_limited = false;
+            for (i, detail_id) in detail_ids.iter().enumerate() {
+                if i > 0 {
+                    tokio::time::sleep(jittered(ITEM_DELAY, 0.6)).await;
+                }
+
+                match refresher(detail_id.clone()).await {
+                    Ok(()) => refreshed += 1,
+                    Err(e) if is_rate_limited(&e) => {
+                        tracing::warn!("[StatsRefresher] 平台限流，暂停本轮刷新: {}", e);
+                        rate_limited = true;
+                        break;
+                    }
+                    Err(e) => tracing::warn!("[StatsRefresher] 刷新失败: detail_id={}, error={}", detail_id, e),
+                }
+            }
+
+            interval = if rate_limited {
+                (interval * 2).min(MAX_BACKOFF)
+            } else {
+                REFRESH_INTERVAL
+            };
+
+            if !detail_ids.is_empty() {
+                tracing::info!("[StatsRefresher] 本轮刷新 {}/{} 个作品，下一轮约 {} 分钟后",
+                    refreshed, detail_ids.len(), interval.as_secs() / 60);
+            }
+
+            tokio::time::sleep(jittered(interval, 0.2)).await;
+        }
+    });
+}
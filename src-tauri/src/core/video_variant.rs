@@ -0,0 +1,123 @@
+//! Video Variants
+//!
+//! 去重变体：发布前为每个账号生成一份略有差异的视频副本（裁掉开头几帧、微调视频码率、
+//! 重新编码音频、写入不同的元数据），各账号上传的文件 MD5 和编码特征互不相同，
+//! 降低矩阵账号因重复内容被下架的概率。变体参数由发布详情ID确定，重试时得到相同的变体并复用缓存
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+use super::{VideoInfo, probe_video};
+
+/// 裁掉开头的时长范围（毫秒，约 1 ~ 5 帧）
+const TRIM_MS_RANGE: (u32, u32) = (40, 200);
+
+/// 视频码率相对原视频的系数范围（千分比）
+const BITRATE_PERMILLE_RANGE: (u32, u32) = (900, 1100);
+
+/// 可选的音频码率
+const AUDIO_BITRATES_KBPS: &[u32] = &[128, 160, 192];
+
+/// 无法估算原视频码率时使用的基准码率
+const FALLBACK_VIDEO_KBPS: u32 = 4000;
+
+/// Variant of a video generated for one account
+/// 为单个账号生成的视频变体
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VideoVariant {
+    pub seed: u32,
+    /// 裁掉开头的毫秒数
+    pub trim_ms: u32,
+    pub video_kbps: u32,
+    pub audio_kbps: u32,
+    /// 变体文件的 MD5
+    #[serde(default)]
+    pub md5: String,
+}
+
+impl VideoVariant {
+    /// 由变体标识（发布详情ID）确定变体参数；`base_kbps` 为原视频的视频码率
+    pub fn plan(variant_key: &str, base_kbps: u32) -> Self {
+        let digest = md5::compute(variant_key.as_bytes());
+        let seed = u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]]);
+        let pick = |value: u32, (min, max): (u32, u32)| min + value % (max - min + 1);
+
+        let permille = pick(seed >> 8, BITRATE_PERMILLE_RANGE);
+        Self {
+            seed,
+            trim_ms: pick(seed, TRIM_MS_RANGE),
+            video_kbps: (base_kbps as u64 * permille as u64 / 1000).max(1) as u32,
+            audio_kbps: AUDIO_BITRATES_KBPS[(seed >> 16) as usize % AUDIO_BITRATES_KBPS.len()],
+            md5: String::new(),
+        }
+    }
+}
+
+/// 由文件大小和时长估算视频码率（kbps，含音轨，作为变体的基准码率足够）
+fn estimate_video_kbps(info: &VideoInfo) -> Option<u32> {
+    let duration = info.duration_secs.filter(|d| *d > 0.0)?;
+    let kbps = (info.size_bytes as f64 * 8.0 / duration / 1000.0) as u32;
+    (kbps > 0).then_some(kbps)
+}
+
+/// 计算文件 MD5（分块读取，避免整个视频读入内存）
+fn file_md5(path: &Path) -> Result<String, String> {
+    let mut file = std::fs::File::open(path).map_err(|e| format!("无法读取变体文件: {}", e))?;
+    let mut context = md5::Context::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let n = file.read(&mut buffer).map_err(|e| format!("无法读取变体文件: {}", e))?;
+        if n == 0 {
+            break;
+        }
+        context.consume(&buffer[..n]);
+    }
+    Ok(format!("{:x}", context.compute()))
+}
+
+/// 生成视频的去重变体，返回变体文件路径和变体参数（含文件 MD5）
+pub fn generate_video_variant(video_path: &str, variant_key: &str, output_dir: &Path) -> Result<(PathBuf, VideoVariant), String> {
+    let info = probe_video(video_path)?;
+    let modified = std::fs::metadata(video_path)
+        .and_then(|m| m.modified())
+        .map_err(|e| format!("无法读取视频文件 {}: {}", video_path, e))?;
+    let mut variant = VideoVariant::plan(variant_key, estimate_video_kbps(&info).unwrap_or(FALLBACK_VIDEO_KBPS));
+
+    let key = format!("{:x}", md5::compute(format!(
+        "{}@{:?}@{}:{}:{}:{}", video_path, modified, variant.seed, variant.trim_ms, variant.video_kbps, variant.audio_kbps,
+    )));
+    let output = output_dir.join(format!("variant_{}.mp4", key));
+
+    if !output.exists() {
+        // 先写临时文件，成功后再改名，避免中断的处理被当作缓存复用
+        let partial = output_dir.join(format!("variant_{}.part.mp4", key));
+        let mut command = Command::new("ffmpeg");
+        command.args(["-hide_banner", "-loglevel", "error", "-nostats", "-y"])
+            .args(["-ss", &format!("{:.3}", variant.trim_ms as f64 / 1000.0)])
+            .args(["-i", video_path])
+            .args(["-c:v", "libx264", "-preset", "medium", "-pix_fmt", "yuv420p"])
+            .args(["-b:v", &format!("{}k", variant.video_kbps)])
+            .args(["-maxrate", &format!("{}k", variant.video_kbps * 3 / 2)])
+            .args(["-bufsize", &format!("{}k", variant.video_kbps * 2)]);
+        if info.audio_codec.is_some() {
+            command.args(["-c:a", "aac", "-b:a", &format!("{}k", variant.audio_kbps)]);
+        }
+        let result = command
+            .args(["-map_metadata", "-1", "-metadata", &format!("comment=v{:08x}", variant.seed)])
+            .args(["-movflags", "+faststart"])
+            .arg(&partial)
+            .output()
+            .map_err(|e| format!("无法启动 ffmpeg（请确认已安装并在 PATH 中）: {}", e))?;
+
+        if !result.status.success() || !partial.exists() {
+            let _ = std::fs::remove_file(&partial);
+            return Err(format!("生成视频变体失败: {}", String::from_utf8_lossy(&result.stderr).trim()));
+        }
+        std::fs::rename(&partial, &output).map_err(|e| format!("保存视频变体失败: {}", e))?;
+        tracing::info!("[VideoVariant] 已生成视频变体: {}", output.display());
+    }
+
+    variant.md5 = file_md5(&output)?;
+    Ok((output, variant))
+}
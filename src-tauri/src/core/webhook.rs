@@ -0,0 +1,232 @@
+//! Publish Webhooks
+//!
+//! 发布回调：账号发布成功或失败后，向设置中配置的地址逐个 POST 一条 JSON 通知，
+//! 请求失败（网络错误或非 2xx）时按指数退避重试，每个地址的最终投递结果写入 webhook_deliveries 表。
+//! 投递在后台进行，不阻塞发布流程。
+//!
+//! 载荷：`{ event, task_id, detail_id, account_id, platform, status, item_id, publish_url, error, timestamp }`，
+//! `event` 为 `publish.completed` / `publish.failed`（测试通知为 `webhook.test`）
+
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::storage::DatabaseManager;
+use super::{PublicationAccountDetail, PublicationStatus};
+
+/// 默认每个地址的最大尝试次数
+pub const DEFAULT_WEBHOOK_MAX_ATTEMPTS: u32 = 3;
+
+/// 最大尝试次数上限
+pub const MAX_WEBHOOK_ATTEMPTS_LIMIT: u32 = 10;
+
+/// 请求超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 首次重试前的等待时间（之后每次翻倍）
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+
+/// 发布成功事件
+pub const WEBHOOK_EVENT_PUBLISH_COMPLETED: &str = "publish.completed";
+/// 发布失败事件
+pub const WEBHOOK_EVENT_PUBLISH_FAILED: &str = "publish.failed";
+/// 测试通知事件
+pub const WEBHOOK_EVENT_TEST: &str = "webhook.test";
+
+/// Webhook configuration
+/// 发布回调配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub enabled: bool,
+    pub urls: Vec<String>,     // 回调地址，每个地址都会收到通知
+    pub max_attempts: u32,     // 每个地址的最大尝试次数（1 表示不重试）
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            urls: Vec::new(),
+            max_attempts: DEFAULT_WEBHOOK_MAX_ATTEMPTS,
+        }
+    }
+}
+
+/// Body of a webhook notification
+/// 回调通知载荷
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub event: String,
+    pub task_id: String,
+    pub detail_id: String,
+    pub account_id: String,
+    pub platform: String,
+    pub status: String,
+    pub item_id: Option<String>,
+    pub publish_url: Option<String>,
+    pub error: Option<String>,
+    pub timestamp: String,
+}
+
+impl WebhookPayload {
+    /// 由发布明细生成通知；只有发布成功或失败的明细需要通知
+    pub fn from_detail(detail: &PublicationAccountDetail) -> Option<Self> {
+        let event = match detail.status {
+            PublicationStatus::Completed => WEBHOOK_EVENT_PUBLISH_COMPLETED,
+            PublicationStatus::Failed => WEBHOOK_EVENT_PUBLISH_FAILED,
+            _ => return None,
+        };
+
+        Some(Self {
+            event: event.to_string(),
+            task_id: detail.publication_task_id.clone(),
+            detail_id: detail.id.clone(),
+            account_id: detail.account_id.clone(),
+            platform: detail.platform.as_str().to_string(),
+            status: format!("{:?}", detail.status),
+            item_id: detail.item_id.clone().filter(|id| !id.is_empty()),
+            publish_url: detail.publish_url.clone().filter(|url| !url.is_empty()),
+            error: detail.message.clone().filter(|_| detail.status == PublicationStatus::Failed),
+            timestamp: now(),
+        })
+    }
+
+    /// 测试通知，用于检查回调地址是否可用
+    pub fn test() -> Self {
+        Self {
+            event: WEBHOOK_EVENT_TEST.to_string(),
+            status: "Test".to_string(),
+            timestamp: now(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Final result of delivering one notification to one URL
+/// 一条通知投递到一个地址的最终结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub url: String,
+    pub event: String,
+    pub task_id: String,
+    pub detail_id: String,
+    pub payload: String,               // 发送的 JSON
+    pub success: bool,
+    pub attempts: u32,
+    pub response_code: Option<u16>,    // 最后一次请求的 HTTP 状态码（网络错误时为空）
+    pub error: Option<String>,
+    pub created_at: String,
+}
+
+fn now() -> String {
+    chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Notify the configured webhooks that a publication finished
+/// 账号发布结束后在后台发送回调通知（未启用回调、没有配置地址或明细未结束时不发送）
+pub fn notify_publish_result(db_manager: &DatabaseManager, detail_id: &str) {
+    let config = match db_manager.get_webhook_config() {
+        Ok(config) if config.enabled && !config.urls.is_empty() => config,
+        Ok(_) => return,
+        Err(e) => {
+            tracing::warn!("[Webhook] 读取回调配置失败: {}", e);
+            return;
+        }
+    };
+
+    let payload = match db_manager.get_publication_account_detail(detail_id) {
+        Ok(Some(detail)) => match WebhookPayload::from_detail(&detail) {
+            Some(payload) => payload,
+            None => return,
+        },
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!("[Webhook] 读取发布明细失败: detail_id={}, error={}", detail_id, e);
+            return;
+        }
+    };
+
+    let db_manager = db_manager.clone();
+    tokio::spawn(async move {
+        send_webhooks(&db_manager, &config, &payload).await;
+    });
+}
+
+/// Deliver a notification to every configured URL and record the results
+/// 把通知投递到所有回调地址并记录投递结果
+pub async fn send_webhooks(db_manager: &DatabaseManager, config: &WebhookConfig, payload: &WebhookPayload) -> Vec<WebhookDelivery> {
+    let body = serde_json::to_string(payload).unwrap_or_default();
+    let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!("[Webhook] 创建回调客户端失败: {}", e);
+            return Vec::new();
+        }
+    };
+    let max_attempts = config.max_attempts.clamp(1, MAX_WEBHOOK_ATTEMPTS_LIMIT);
+
+    let mut deliveries = Vec::new();
+    for url in &config.urls {
+        let delivery = deliver(&client, url, payload, &body, max_attempts).await;
+        if delivery.success {
+            tracing::info!("[Webhook] 已发送 {} 到 {}（第 {} 次尝试）", payload.event, url, delivery.attempts);
+        } else {
+            tracing::warn!("[Webhook] 发送 {} 到 {} 失败: {:?}", payload.event, url, delivery.error);
+        }
+        if let Err(e) = db_manager.save_webhook_delivery(&delivery) {
+            tracing::warn!("[Webhook] 记录投递结果失败: {}", e);
+        }
+        deliveries.push(delivery);
+    }
+
+    deliveries
+}
+
+/// 向单个地址发送通知，失败时重试
+async fn deliver(client: &reqwest::Client, url: &str, payload: &WebhookPayload, body: &str, max_attempts: u32) -> WebhookDelivery {
+    let mut delivery = WebhookDelivery {
+        id: uuid::Uuid::new_v4().to_string(),
+        url: url.to_string(),
+        event: payload.event.clone(),
+        task_id: payload.task_id.clone(),
+        detail_id: payload.detail_id.clone(),
+        payload: body.to_string(),
+        success: false,
+        attempts: 0,
+        response_code: None,
+        error: None,
+        created_at: now(),
+    };
+
+    while delivery.attempts < max_attempts {
+        if delivery.attempts > 0 {
+            tokio::time::sleep(RETRY_BASE_DELAY.saturating_mul(2u32.saturating_pow(delivery.attempts - 1))).await;
+        }
+        delivery.attempts += 1;
+
+        let response = client.post(url)
+            .header("Content-Type", "application/json")
+            .header("X-Webhook-Event", payload.event.as_str())
+            .body(body.to_string())
+            .send()
+            .await;
+        match response {
+            Ok(response) if response.status().is_success() => {
+                delivery.response_code = Some(response.status().as_u16());
+                delivery.success = true;
+                delivery.error = None;
+                break;
+            }
+            Ok(response) => {
+                delivery.response_code = Some(response.status().as_u16());
+                delivery.error = Some(format!("HTTP {}", response.status()));
+            }
+            Err(e) => {
+                delivery.response_code = None;
+                delivery.error = Some(e.to_string());
+            }
+        }
+    }
+
+    delivery
+}
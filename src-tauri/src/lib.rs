@@ -5,6 +5,7 @@ pub mod storage;
 pub mod browser;
 pub mod platforms;
 pub mod commands;
+pub mod cli;
 
 // Re-export browser types for easy access
 pub use browser::{BrowserAutomator, BrowserAuthResult, BrowserAuthStep};
@@ -19,106 +20,373 @@ pub use commands::AppState;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-// Initialize tracing for logging
-// 初始化 tracing 用于日志输出
-fn init_tracing() {
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .with_thread_ids(true)
-        .with_target(false)
+// Initialize tracing for logging (console + daily rotated files under `{data_path}/logs`)
+// 初始化 tracing 用于日志输出（控制台 + 按天滚动的日志文件）
+fn init_tracing(data_path: &std::path::Path) {
+    use tracing_subscriber::prelude::*;
+    use tracing_subscriber::filter::LevelFilter;
+    use crate::core::{LocalTimer, RollingFileWriter};
+
+    tracing_subscriber::registry()
+        .with(LevelFilter::INFO)
+        .with(tracing_subscriber::fmt::layer()
+            .with_thread_ids(true)
+            .with_target(false))
+        // 日志文件，供 get_recent_logs 读取
+        .with(tracing_subscriber::fmt::layer()
+            .with_writer(RollingFileWriter::new(&data_path.join("logs")))
+            .with_timer(LocalTimer)
+            .with_ansi(false)
+            .with_thread_ids(true)
+            .with_target(false))
+        // 发布日志实时推送（按 detail_id 订阅）
+        .with(crate::core::PublishLogLayer)
         .init();
 }
 
+// Check the Playwright environment without blocking startup
+// 检查 Playwright 环境（非阻塞方式），缺失时在后台安装
+#[cfg(desktop)]
+fn prepare_playwright_env() {
+    use crate::browser::{check_playwright_env, ensure_playwright_env};
+
+    match check_playwright_env() {
+        Ok(_) => {
+            tracing::info!("[App] Playwright 环境检查通过");
+        }
+        Err(e) => {
+            tracing::error!("[App] Playwright 环境检查失败: {}，正在后台安装...", e);
+
+            // 在后台线程安装
+            std::thread::spawn(move || {
+                if let Err(e) = ensure_playwright_env() {
+                    tracing::error!("[App] Playwright 环境安装失败: {}", e);
+                } else {
+                    tracing::info!("[App] Playwright 环境安装完成");
+                }
+            });
+        }
+    }
+}
+
 // Run the Tauri application
 // 运行 Tauri 应用
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    use crate::storage::DatabaseManager;
-    use crate::commands::AppState;
-    use crate::browser::{BrowserAutomator, check_playwright_env, ensure_playwright_env};
+    use crate::storage::{DatabaseManager, CacheManager};
+    use crate::core::{start_scheduler, start_stats_refresher, start_auto_reply_worker, start_comment_sync, start_remote_sync, init_notifications, timed_invoke_handler, ReauthQueue, COMMENT_SYNC_PAGE_SIZE};
+    use crate::commands::{AppState, run_publication_task, refresh_publication_stats, extract_comments, reply_comment};
+    use crate::browser::BrowserAuthSessions;
     use tauri::Manager;
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
-            // 初始化 tracing
-            init_tracing();
-            tracing::info!("[App] 应用启动");
-            // 使用 Tauri 的应用数据目录
+            // 使用 Tauri 的应用数据目录（桌面端和移动端沙盒内都可写）
             let data_path = app.path()
                 .app_data_dir()
+                .or_else(|_| app.path().app_local_data_dir())
                 .unwrap_or_else(|_| {
-                    // 如果获取失败，使用默认路径
-                    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-                    std::path::PathBuf::from(home)
-                        .join("Library")
-                        .join("Application Support")
-                        .join("com.tauri.auto-matrix-manager")
+                    // 如果获取失败，使用临时目录
+                    std::env::temp_dir().join("auto-matrix-manager")
                 });
 
             // Create data directory if needed
             std::fs::create_dir_all(&data_path).ok();
 
-            // 检查 Playwright 环境（非阻塞方式）
-            match check_playwright_env() {
-                Ok(_) => {
-                    tracing::info!("[App] Playwright 环境检查通过");
+            // 初始化 tracing（日志文件写在数据目录下）
+            init_tracing(&data_path);
+            tracing::info!("[App] 应用启动");
+
+            // 移动端没有桌面 Chrome，不支持浏览器授权，跳过 Playwright 检查
+            #[cfg(desktop)]
+            prepare_playwright_env();
+
+            // 按容量上限淘汰过旧的缓存文件
+            let reclaimed = CacheManager::new(&data_path).enforce_limits();
+            if reclaimed.removed_files > 0 {
+                tracing::info!("[App] 缓存超出上限，已清理 {} 个文件，释放 {} 字节", reclaimed.removed_files, reclaimed.reclaimed_bytes);
+            }
+
+            let db_manager = Arc::new(DatabaseManager::new(data_path.clone()));
+
+            // 校验数据库结构，缺失字段自动补齐
+            match db_manager.verify_schema() {
+                Ok(report) if report.issues.is_empty() => {
+                    tracing::info!("[App] 数据库结构校验通过");
+                }
+                Ok(report) if report.is_healthy() => {
+                    tracing::info!("[App] 数据库结构已自动修复 {} 处", report.issues.len());
+                }
+                Ok(report) => {
+                    tracing::error!("[App] 数据库结构存在无法自动修复的问题: {:?}", report.issues);
                 }
                 Err(e) => {
-                    tracing::error!("[App] Playwright 环境检查失败: {}，正在后台安装...", e);
-
-                    // 在后台线程安装
-                    std::thread::spawn(move || {
-                        if let Err(e) = ensure_playwright_env() {
-                            tracing::error!("[App] Playwright 环境安装失败: {}", e);
-                        } else {
-                            tracing::info!("[App] Playwright 环境安装完成");
-                        }
-                    });
+                    tracing::error!("[App] 数据库结构校验失败: {}", e);
                 }
             }
+            match db_manager.get_schema_version() {
+                Ok(version) => tracing::info!("[App] 数据库迁移版本: {}/{}", version, crate::storage::migrations::latest_schema_version()),
+                Err(e) => tracing::error!("[App] 读取数据库迁移版本失败: {}", e),
+            }
+
+            // 记录当天的账号数据快照（用于周期对比）
+            if let Err(e) = db_manager.record_account_stats_snapshots() {
+                tracing::error!("[App] 记录账号数据快照失败: {}", e);
+            }
 
-            let db_manager = Arc::new(DatabaseManager::new(data_path.clone()));
-            let browser_automator = Arc::new(Mutex::new(BrowserAutomator::new()));
             let app_state = AppState {
-                db_manager,
-                browser_automator,
+                db_manager: db_manager.clone(),
+                storage: db_manager.clone(),
+                browser_sessions: Arc::new(BrowserAuthSessions::new()),
+                reauth_queue: Arc::new(Mutex::new(ReauthQueue::default())),
             };
+            tracing::info!("[App] 存储后端: {}", app_state.storage.backend_name());
 
+            // 命令统一通过 AppState 共享同一个数据库管理器
             app.manage(app_state);
+
+            // 后台任务通过保存的 AppHandle 发送系统通知
+            init_notifications(app.handle().clone());
+
+            // 启用时启动 HTTP API（供外部脚本 / CI 调用）
+            #[cfg(feature = "http-api")]
+            match db_manager.get_http_api_config() {
+                Ok(config) => crate::commands::restart_http_api(app.handle(), &config),
+                Err(e) => tracing::error!("[App] 读取 HTTP API 配置失败: {}", e),
+            }
+
+            // 启动互动数据刷新器（定期更新最近发布作品的评论/点赞等计数）
+            let refresher_handle = app.handle().clone();
+            start_stats_refresher(db_manager.clone(), move |detail_id| {
+                let app_handle = refresher_handle.clone();
+                async move {
+                    refresh_publication_stats(app_handle, detail_id).await.map(|_| ())
+                }
+            });
+
+            // 启动评论自动回复（拉取新评论并按规则回复）
+            let sync_handle = app.handle().clone();
+            let reply_handle = app.handle().clone();
+            start_auto_reply_worker(
+                db_manager.clone(),
+                move |detail_id, aweme_id| {
+                    let app_handle = sync_handle.clone();
+                    async move {
+                        extract_comments(app_handle, &detail_id, &aweme_id, 50, 0).await.map(|_| ())
+                    }
+                },
+                move |request| {
+                    let app_handle = reply_handle.clone();
+                    async move {
+                        reply_comment(app_handle, request.account_id, request.aweme_id, request.comment_id, request.text).await
+                    }
+                },
+            );
+
+            // 启动评论增量同步（从上次游标继续拉取已发布作品的新评论）
+            let comment_sync_handle = app.handle().clone();
+            start_comment_sync(db_manager.clone(), move |detail_id, aweme_id, cursor| {
+                let app_handle = comment_sync_handle.clone();
+                async move {
+                    extract_comments(app_handle, &detail_id, &aweme_id, COMMENT_SYNC_PAGE_SIZE, cursor).await
+                }
+            });
+
+            // 启动远程同步（未启用时只定期检查配置）
+            start_remote_sync(db_manager.clone());
+
+            // 启动定时发布调度器（到期任务在后台触发发布）
+            let scheduler_handle = app.handle().clone();
+            start_scheduler(db_manager, move |task_id| {
+                let app_handle = scheduler_handle.clone();
+                async move {
+                    let result = run_publication_task(app_handle, &task_id, &[]).await?;
+                    Ok(format!("成功 {}，失败 {}，跳过 {}", result.success_count, result.failed_count, result.skipped_count))
+                }
+            });
+
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(timed_invoke_handler(tauri::generate_handler![
             greet,
             get_supported_platforms,
             get_accounts,
             get_all_accounts,
             add_account,
             delete_account,
+            restore_account,
+            get_trash,
+            purge_trash,
+            set_account_on_hold,
+            create_account_group,
+            get_account_groups,
+            delete_account_group,
+            assign_account_to_group,
+            get_group_watermark,
+            set_group_watermark,
+            set_account_tags,
+            get_accounts_by_group,
             open_file_dialog,
             select_file_with_content,
             // Publication task commands (new main + sub table structure)
             get_publication_tasks,
+            get_publication_tasks_paginated,
+            search_tasks,
             get_publication_task,
             get_publication_task_with_accounts,
             get_publication_account_detail,
             create_publication_task,
+            search_poi,
+            search_music,
+            create_bulk_publication_tasks,
+            get_filename_parse_rule,
+            set_filename_parse_rule,
+            preview_filename_metadata,
             delete_publication_task,
+            restore_task,
+            replace_task_video,
             publish_publication_task,
+            save_as_platform_draft,
+            verify_publication_credentials,
+            get_platform_limits,
+            validate_video,
+            transcode_video,
+            upload_douyin_cover,
+            preview_publication_task,
             retry_publication_task,
+            cancel_publication_task,
+            get_pending_publish_verifications,
+            open_publish_verification,
+            resume_publish_after_verification,
+            get_publish_checkpoint,
+            resume_publication,
+            // Scheduled publishing
+            schedule_publication_task,
+            get_scheduled_jobs,
+            cancel_scheduled_job,
+            subscribe_publish_logs,
+            unsubscribe_publish_logs,
+            refresh_publication_stats,
+            refresh_task_publication_stats,
             publish_video,
+            publish_image_post,
             start_browser_auth,
             check_browser_auth_status,
             cancel_browser_auth,
+            get_browser_auth_sessions,
+            start_reauth_queue,
+            get_reauth_queue,
+            cancel_reauth_queue,
+            list_browser_profiles,
+            get_account_browser_profiles,
+            delete_account_browser_profile,
+            cleanup_account_browser_profiles,
+            import_account_from_browser,
+            import_account_from_cookie,
+            import_accounts,
+            export_accounts,
+            get_auth_capabilities,
+            get_account_avatar,
             get_extractor_configs,
             get_extractor_config,
             save_extractor_config,
+            test_extractor_config,
             // Comment extraction commands
             extract_comments,
             get_comments_by_aweme_id,
+            search_comments,
             get_comment_count,
+            export_comments_to_xlsx,
+            get_comment_tag_rules,
+            set_comment_tag_rules,
+            classify_comments,
+            get_comments_by_tag,
+            reply_comment,
+            // Private message extraction
+            extract_messages,
+            get_messages,
+            sync_account_videos,
+            get_remote_videos,
+            delete_remote_publication,
+            // Auto-reply rules
+            get_auto_reply_rules,
+            save_auto_reply_rule,
+            delete_auto_reply_rule,
+            get_auto_reply_logs,
+            get_comment_sync_states,
+            get_comment_sync_history,
+            get_new_comments,
+            get_remote_sync_config,
+            save_remote_sync_config,
+            run_remote_sync_now,
+            get_http_api_config,
+            save_http_api_config,
+            get_notification_settings,
+            save_notification_settings,
+            get_webhook_config,
+            save_webhook_config,
+            test_webhook,
+            get_webhook_deliveries,
+            get_ai_config,
+            save_ai_config,
+            generate_captions,
             delete_comments,
-        ])
+            // Report builder commands
+            get_report_schema,
+            run_report,
+            check_database_schema,
+            get_db_contention_stats,
+            export_anonymized_snapshot,
+            export_backup,
+            import_backup,
+            #[cfg(feature = "load-test")]
+            run_publish_load_test,
+            // Account stats
+            get_account_stats_delta,
+            refresh_account_stats,
+            get_account_stats_history,
+            get_account_timeline,
+            get_account_overview,
+            check_account_health,
+            get_account_health_report,
+            clear_account_risk,
+            get_publish_statistics,
+            get_failure_breakdown,
+            // Cache management
+            get_cache_usage,
+            clear_cache,
+            // Publish retry settings
+            get_app_settings,
+            set_app_settings,
+            get_cover_strategy,
+            set_cover_strategy,
+            get_publish_pacing_rules,
+            set_publish_pacing_rules,
+            // Task notes
+            add_task_note,
+            get_task_notes,
+            reassign_publication_account,
+            get_account_reassignments,
+            // Hashtag sets
+            get_hashtag_sets,
+            save_hashtag_set,
+            delete_hashtag_set,
+            attach_hashtag_set_to_task,
+            get_hashtag_suggestions,
+            get_hashtag_performance,
+            get_publication_templates,
+            save_task_as_template,
+            delete_publication_template,
+            create_task_from_template,
+            // Platform module versions
+            get_platform_module_versions,
+            get_event_schema,
+            get_command_metrics,
+            get_recent_logs,
+        ]))
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
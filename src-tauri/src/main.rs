@@ -4,6 +4,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // 带子命令启动时以命令行模式运行（如 `publish --task-file tasks.json`），不启动界面
+    if let Some(code) = auto_matrix_manager::cli::run_from_args() {
+        std::process::exit(code);
+    }
+
     // 注意：Tauri 内部已经初始化了 tracing，无需再次初始化
     // 日志会通过 Tauri 的日志系统自动输出
     auto_matrix_manager::run();
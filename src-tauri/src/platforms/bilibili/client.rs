@@ -0,0 +1,281 @@
+//! B站API客户端
+//!
+//! 负责与B站创作中心（member.bilibili.com）进行HTTP通信
+//!
+//! # 主要功能
+//!
+//! - 预上传（`preupload`）：获取 UPOS 上传地址和鉴权
+//! - 上传封面
+//! - 投稿（`x/vu/web/add/v3`）
+//!
+//! 写接口需要携带 Cookie 中的 `bili_jct` 作为 csrf 参数，接口以 `code == 0` 表示成功
+
+use base64::Engine;
+use serde_json::Value;
+
+/// 创作中心URL
+pub const MEMBER_URL: &str = "https://member.bilibili.com";
+
+/// 主站API URL
+const API_URL: &str = "https://api.bilibili.com";
+
+/// csrf 参数对应的 Cookie 名
+const CSRF_COOKIE: &str = "bili_jct";
+
+/// UPOS 上传配置
+const UPOS_PROFILE: &str = "ugcupos/bup";
+
+/// 共享的异步HTTP客户端
+static ASYNC_CLIENT: std::sync::LazyLock<reqwest::Client> = std::sync::LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .expect("Failed to create async HTTP client")
+});
+
+/// 预上传结果
+#[derive(Debug, Clone)]
+pub struct PreUpload {
+    /// UPOS 鉴权（请求头 `X-Upos-Auth`）
+    pub auth: String,
+    /// 上传服务地址（不含协议，如 `//upos-cs-upcdnbda2.bilivideo.com`）
+    pub endpoint: String,
+    /// 上传路径（如 `upos://ugcboss/n230101abc.mp4`）
+    pub upos_uri: String,
+    /// 业务ID（合并分片时回传）
+    pub biz_id: i64,
+    /// 服务端建议的分片大小
+    pub chunk_size: u64,
+}
+
+impl PreUpload {
+    /// 完整的上传地址
+    pub fn upload_url(&self) -> String {
+        format!("https:{}/{}", self.endpoint, self.upos_uri.trim_start_matches("upos://"))
+    }
+
+    /// 投稿时使用的文件名（上传路径去掉目录和扩展名）
+    pub fn file_name(&self) -> String {
+        upos_file_name(&self.upos_uri)
+    }
+}
+
+/// 投稿结果
+#[derive(Debug, Clone, Default)]
+pub struct SubmitResult {
+    pub aid: i64,
+    pub bvid: String,
+}
+
+/// B站API客户端
+#[derive(Debug, Clone)]
+pub struct BilibiliClient {
+    /// 用户Cookie
+    pub cookie: String,
+    /// User-Agent
+    pub user_agent: String,
+    /// csrf（取自 Cookie 中的 bili_jct）
+    csrf: String,
+}
+
+impl BilibiliClient {
+    /// 创建新的客户端实例
+    pub fn new(cookie: String, user_agent: String) -> Self {
+        let csrf = cookie_value(&cookie, CSRF_COOKIE).unwrap_or_default();
+        Self { cookie, user_agent, csrf }
+    }
+
+    /// Cookie 中是否包含 csrf（未登录时为空）
+    pub fn has_csrf(&self) -> bool {
+        !self.csrf.is_empty()
+    }
+
+    /// 校验登录态（`x/web-interface/nav`，未登录时 isLogin 为 false）
+    pub async fn check_login(&self) -> Result<(), String> {
+        let response = ASYNC_CLIENT
+            .get(format!("{}/x/web-interface/nav", API_URL))
+            .header("Cookie", &self.cookie)
+            .header("User-Agent", &self.user_agent)
+            .header("Referer", MEMBER_URL)
+            .send()
+            .await
+            .map_err(|e| format!("登录态校验请求失败: {}", e))?;
+
+        // 未登录时接口返回 code=-101，这里统一按 isLogin 判断
+        let text = response.text().await.unwrap_or_default();
+        let json: Value = serde_json::from_str(&text)
+            .map_err(|e| format!("解析登录态响应失败: {}", e))?;
+        if json.pointer("/data/isLogin").and_then(|v| v.as_bool()) != Some(true) {
+            return Err("B站账号未登录或登录已过期".to_string());
+        }
+        Ok(())
+    }
+
+    /// 预上传，获取 UPOS 上传信息
+    pub async fn preupload(&self, file_name: &str, file_size: u64) -> Result<PreUpload, String> {
+        let url = format!(
+            "{}/preupload?name={}&size={}&r=upos&profile={}&ssl=0&version=2.14.0&build=2140000&upcdn=bda2&probe_version=20221109",
+            MEMBER_URL,
+            urlencoding::encode(file_name),
+            file_size,
+            urlencoding::encode(UPOS_PROFILE),
+        );
+        tracing::info!("[Bilibili] GET {}", url);
+
+        let response = ASYNC_CLIENT
+            .get(&url)
+            .header("Cookie", &self.cookie)
+            .header("User-Agent", &self.user_agent)
+            .header("Referer", format!("{}/platform/upload/video/frame", MEMBER_URL))
+            .send()
+            .await
+            .map_err(|e| format!("预上传请求失败: {}", e))?;
+
+        let text = response.text().await.unwrap_or_default();
+        let json: Value = serde_json::from_str(&text)
+            .map_err(|e| format!("解析预上传响应失败: {}, 响应: {}", e, &text[..text.len().min(500)]))?;
+
+        // 预上传接口以 OK == 1 表示成功
+        if json.get("OK").and_then(|v| v.as_i64()) != Some(1) {
+            return Err(format!("预上传失败: {}", json));
+        }
+
+        let str_field = |key: &str| json.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let pre = PreUpload {
+            auth: str_field("auth"),
+            endpoint: str_field("endpoint"),
+            upos_uri: str_field("upos_uri"),
+            biz_id: json.get("biz_id").and_then(|v| v.as_i64()).unwrap_or_default(),
+            chunk_size: json.get("chunk_size").and_then(|v| v.as_u64()).unwrap_or_default(),
+        };
+
+        if pre.auth.is_empty() || pre.endpoint.is_empty() || pre.upos_uri.is_empty() {
+            return Err(format!("预上传失败：缺少上传信息 {}", json));
+        }
+        Ok(pre)
+    }
+
+    /// 上传封面，返回封面URL
+    pub async fn upload_cover(&self, content_type: &str, content: &[u8]) -> Result<String, String> {
+        let data_uri = format!(
+            "data:{};base64,{}",
+            content_type,
+            base64::engine::general_purpose::STANDARD.encode(content)
+        );
+        let body = format!(
+            "cover={}&csrf={}",
+            urlencoding::encode(&data_uri),
+            urlencoding::encode(&self.csrf)
+        );
+
+        let url = format!("{}/x/vu/web/cover/up?ts={}", MEMBER_URL, chrono::Utc::now().timestamp_millis());
+        let response = ASYNC_CLIENT
+            .post(&url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .header("Cookie", &self.cookie)
+            .header("User-Agent", &self.user_agent)
+            .header("Referer", format!("{}/platform/upload/video/frame", MEMBER_URL))
+            .header("Origin", MEMBER_URL)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("上传封面请求失败: {}", e))?;
+
+        let json = Self::parse_response(response).await?;
+        json.pointer("/data/url")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("上传封面失败：未返回url {}", json))
+    }
+
+    /// 投稿，返回 aid / bvid
+    pub async fn submit(&self, mut data: Value) -> Result<SubmitResult, String> {
+        if let Value::Object(map) = &mut data {
+            map.insert("csrf".to_string(), Value::String(self.csrf.clone()));
+        }
+
+        let url = format!("{}/x/vu/web/add/v3?csrf={}", MEMBER_URL, urlencoding::encode(&self.csrf));
+        tracing::info!("[Bilibili] POST {}/x/vu/web/add/v3", MEMBER_URL);
+
+        let response = ASYNC_CLIENT
+            .post(&url)
+            .header("Content-Type", "application/json;charset=UTF-8")
+            .header("Cookie", &self.cookie)
+            .header("User-Agent", &self.user_agent)
+            .header("Referer", format!("{}/platform/upload/video/frame", MEMBER_URL))
+            .header("Origin", MEMBER_URL)
+            .body(data.to_string())
+            .send()
+            .await
+            .map_err(|e| format!("投稿请求失败: {}", e))?;
+
+        let json = Self::parse_response(response).await?;
+        Ok(SubmitResult {
+            aid: json.pointer("/data/aid").and_then(|v| v.as_i64()).unwrap_or_default(),
+            bvid: json.pointer("/data/bvid").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        })
+    }
+
+    /// 解析响应：HTTP 状态异常或 code != 0 时返回错误
+    pub async fn parse_response(response: reqwest::Response) -> Result<Value, String> {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        tracing::debug!("[Bilibili] 响应: HTTP {}, {}", status, &text[..text.len().min(2000)]);
+
+        if !status.is_success() {
+            return Err(format!("HTTP {}, 响应: {}", status.as_u16(), &text[..text.len().min(500)]));
+        }
+
+        let json: Value = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        let code = json.get("code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if code != 0 {
+            let msg = json.get("message").and_then(|v| v.as_str()).unwrap_or("未知错误");
+            return Err(format!("接口返回错误: code={}, message={}", code, msg));
+        }
+
+        Ok(json)
+    }
+}
+
+/// 从 Cookie 字符串中读取指定字段
+pub fn cookie_value(cookie: &str, name: &str) -> Option<String> {
+    cookie.split(';')
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
+
+/// 从 UPOS 路径中提取投稿文件名：`upos://ugcboss/n230101abc.mp4` → `n230101abc`
+pub fn upos_file_name(upos_uri: &str) -> String {
+    let name = upos_uri.rsplit('/').next().unwrap_or(upos_uri);
+    match name.rsplit_once('.') {
+        Some((stem, _)) => stem.to_string(),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upos_file_name() {
+        assert_eq!(upos_file_name("upos://ugcboss/n230101abc.mp4"), "n230101abc");
+        assert_eq!(upos_file_name("upos://ugcboss/n230101abc"), "n230101abc");
+    }
+
+    #[test]
+    fn test_upload_url() {
+        let pre = PreUpload {
+            auth: "a".to_string(),
+            endpoint: "//upos-cs-upcdnbda2.bilivideo.com".to_string(),
+            upos_uri: "upos://ugcboss/n1.mp4".to_string(),
+            biz_id: 1,
+            chunk_size: 0,
+        };
+        assert_eq!(pre.upload_url(), "https://upos-cs-upcdnbda2.bilivideo.com/ugcboss/n1.mp4");
+        assert_eq!(pre.file_name(), "n1");
+    }
+}
@@ -0,0 +1,378 @@
+//! B站视频发布策略
+//!
+//! 实现策略模式，支持将视频投稿到B站
+//!
+//! # 发布流程（5步）
+//!
+//! 1. **参数校验** - 检查视频路径和账号参数
+//! 2. **创建客户端** - 从账号参数解析 Cookie / User-Agent，读取 csrf
+//! 3. **上传视频** - 预上传 → 初始化 → 分片上传 → 合并（UPOS 协议）
+//! 4. **上传封面** - B站投稿必须带封面，未设置封面时无法投稿
+//! 5. **投稿** - 组装标题、简介、标签后调用 add/v3 接口
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crate::platforms::factory::PublishStrategyFactory;
+//! use crate::core::PlatformType;
+//!
+//! let strategy = PublishStrategyFactory::get_service(PlatformType::Bilibili).await;
+//! if let Some(s) = strategy {
+//!     let result = s.publish(request).await;
+//! }
+//! ```
+
+use crate::core::{PlatformError, PublishResult, PublishRequest as CorePublishRequest, ProgressStatus, PublishProgressEvent, emit_event};
+use crate::platforms::traits::{ModuleChangelogEntry, PublishStrategy};
+use crate::platforms::douyin::account_params::AccountParams;
+use crate::platforms::bilibili::client::BilibiliClient;
+use crate::platforms::bilibili::uploader::BilibiliUploader;
+use serde_json::{json, Value};
+use chrono::Utc;
+
+/// 平台类型标识
+/// 4 = B站
+pub const PLATFORM_TYPE_BILIBILI: i64 = 4;
+
+/// 策略模块版本（B站接口调整时递增，并在 CHANGELOG 中记录）
+pub const STRATEGY_VERSION: &str = "1.0.0";
+
+/// 策略模块变更记录（新版本在前）
+pub const CHANGELOG: &[ModuleChangelogEntry] = &[
+    ModuleChangelogEntry {
+        version: "1.0.0",
+        notes: "5 步发布流程：UPOS 分片上传、封面上传、add/v3 投稿",
+    },
+];
+
+/// 标题最大长度（字符）
+const MAX_TITLE_CHARS: usize = 80;
+
+/// 简介最大长度（字符）
+const MAX_DESC_CHARS: usize = 2000;
+
+/// 标签最多个数
+const MAX_TAGS: usize = 10;
+
+/// 默认投稿分区（生活 > 日常）
+const DEFAULT_TID: i64 = 21;
+
+/// 定时发布最早时间：当前时间 2 小时后（秒）
+const MIN_SCHEDULE_DELAY_SECS: i64 = 2 * 60 * 60;
+
+/// B站视频发布策略
+///
+/// 实现 `PublishStrategy` 接口，提供B站视频投稿功能
+#[derive(Debug, Clone)]
+pub struct BilibiliPublishStrategy {
+    /// 任务ID
+    task_id: String,
+    /// 详情ID
+    detail_id: String,
+    /// 账号ID
+    account_id: String,
+    /// AppHandle 用于发送进度事件
+    app_handle: Option<tauri::AppHandle>,
+}
+
+impl BilibiliPublishStrategy {
+    /// 创建新的发布策略实例（无进度跟踪）
+    pub fn new() -> Self {
+        Self {
+            task_id: String::new(),
+            detail_id: String::new(),
+            account_id: String::new(),
+            app_handle: None,
+        }
+    }
+
+    /// 创建带进度跟踪的发布策略实例
+    pub fn with_progress(
+        task_id: &str,
+        detail_id: &str,
+        account_id: &str,
+        app_handle: &tauri::AppHandle,
+    ) -> Self {
+        Self {
+            task_id: task_id.to_string(),
+            detail_id: detail_id.to_string(),
+            account_id: account_id.to_string(),
+            app_handle: Some(app_handle.clone()),
+        }
+    }
+
+    /// 发送进度事件
+    fn emit_progress(&self, status: &ProgressStatus, message: &str, progress: i32) {
+        if let Some(handle) = &self.app_handle {
+            let event = PublishProgressEvent {
+                task_id: self.task_id.clone(),
+                detail_id: self.detail_id.clone(),
+                account_id: self.account_id.clone(),
+                platform: "bilibili".to_string(),
+                status: status.clone(),
+                message: message.to_string(),
+                progress,
+                timestamp: Utc::now().timestamp_millis(),
+            };
+            if let Err(e) = emit_event(handle, &event) {
+                tracing::warn!("[Progress] Failed to emit progress event: {}", e);
+            }
+        }
+    }
+}
+
+impl Default for BilibiliPublishStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PublishStrategy for BilibiliPublishStrategy {
+    /// 投稿视频到B站
+    ///
+    /// # 平台数据格式
+    ///
+    /// ```json
+    /// {
+    ///   "params": "数据库中的账号参数JSON",
+    ///   "send_time": 1234567890,
+    ///   "tid": 21
+    /// }
+    /// ```
+    async fn publish(&self, request: CorePublishRequest) -> Result<PublishResult, PlatformError> {
+        // 临时性错误的重试由发布流水线统一处理（publish_with_retry），策略内只发布一次
+        let result = self.publish_attempt(&request).await;
+        if let Err(e) = &result {
+            self.emit_progress(&ProgressStatus::Failed, &e.to_string(), 0);
+        }
+        result
+    }
+
+    /// 获取平台类型
+    fn get_platform_type(&self) -> i64 {
+        PLATFORM_TYPE_BILIBILI
+    }
+
+    /// 获取策略模块版本
+    fn get_version(&self) -> &'static str {
+        STRATEGY_VERSION
+    }
+
+    /// 获取策略模块变更记录
+    fn get_changelog(&self) -> &'static [ModuleChangelogEntry] {
+        CHANGELOG
+    }
+}
+
+impl BilibiliPublishStrategy {
+    /// 执行一次完整的发布流程（步骤1-5）
+    async fn publish_attempt(&self, request: &CorePublishRequest) -> Result<PublishResult, PlatformError> {
+        request.check_cancelled()?;
+
+        // ========== 步骤1: 参数校验 ==========
+        tracing::info!("[Bilibili Publish] ====== 步骤1: 参数校验 ======");
+        self.emit_progress(&ProgressStatus::Starting, "开始发布", 0);
+
+        if request.video_path.as_os_str().is_empty() {
+            return Err(PlatformError::InvalidInput("视频路径不能为空".to_string()));
+        }
+        if !request.video_path.exists() {
+            return Err(PlatformError::InvalidInput(format!(
+                "视频文件不存在: {}",
+                request.video_path.display()
+            )));
+        }
+        let cover_path = request.cover_path.as_ref()
+            .filter(|p| p.exists())
+            .ok_or_else(|| PlatformError::InvalidInput("B站投稿必须设置封面".to_string()))?;
+
+        let platform_data = request.platform_data.as_ref()
+            .ok_or_else(|| PlatformError::InvalidInput("platform_data不能为空".to_string()))?;
+
+        let params = platform_data.get("params")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| PlatformError::InvalidInput("params不能为空".to_string()))?;
+
+        // ========== 步骤2: 创建客户端 ==========
+        tracing::info!("[Bilibili Publish] ====== 步骤2: 创建客户端 ======");
+
+        let account_params = AccountParams::from_json(params);
+        let cookie = account_params.get_cookie();
+        if cookie.is_empty() {
+            return Err(PlatformError::InvalidCredentials("B站账号Cookie为空，请重新登录".to_string()));
+        }
+        let client = BilibiliClient::new(cookie, account_params.get_user_agent());
+        if !client.has_csrf() {
+            return Err(PlatformError::InvalidCredentials("B站账号Cookie缺少bili_jct，请重新授权".to_string()));
+        }
+
+        request.check_cancelled()?;
+
+        // ========== 步骤3: 上传视频 ==========
+        tracing::info!("[Bilibili Publish] ====== 步骤3: 上传视频 ======");
+        self.emit_progress(&ProgressStatus::UploadingVideo, "上传视频中...", 10);
+
+        let file_name = BilibiliUploader::new(&client)
+            .upload_video(&request.video_path)
+            .await
+            .map_err(PlatformError::VideoUploadFailed)?;
+
+        request.check_cancelled()?;
+
+        // ========== 步骤4: 上传封面 ==========
+        tracing::info!("[Bilibili Publish] ====== 步骤4: 上传封面 ======");
+        self.emit_progress(&ProgressStatus::Uploading, "上传封面中...", 60);
+
+        let content = std::fs::read(cover_path)
+            .map_err(|e| PlatformError::InvalidInput(format!("读取封面失败: {}", e)))?;
+        let content_type = if cover_path.to_string_lossy().to_lowercase().ends_with(".png") { "image/png" } else { "image/jpeg" };
+        let cover_url = client.upload_cover(content_type, &content)
+            .await
+            .map_err(PlatformError::VideoUploadFailed)?;
+
+        request.check_cancelled()?;
+
+        // ========== 步骤5: 投稿 ==========
+        tracing::info!("[Bilibili Publish] ====== 步骤5: 投稿 ======");
+        self.emit_progress(&ProgressStatus::Publishing, "发布中...", 80);
+
+        let send_time = platform_data.get("send_time").and_then(|v| v.as_i64())
+            .or(request.send_time);
+        let tid = platform_data.get("tid").and_then(|v| v.as_i64()).unwrap_or(DEFAULT_TID);
+        let submit_data = build_submit_data(&SubmitInput {
+            file_name: &file_name,
+            cover_url: &cover_url,
+            title: &request.title,
+            description: request.description.as_deref().unwrap_or(""),
+            hashtags: &request.hashtags,
+            tid,
+            visibility_type: request.visibility_type,
+            send_time,
+            now: Utc::now().timestamp(),
+        });
+
+        let submitted = client
+            .submit(submit_data)
+            .await
+            .map_err(PlatformError::PublicationFailed)?;
+
+        tracing::info!("[Bilibili Publish] B站投稿成功, aid: {}, bvid: {}", submitted.aid, submitted.bvid);
+        self.emit_progress(&ProgressStatus::Completed, "发布成功", 100);
+
+        Ok(PublishResult {
+            success: true,
+            publication_id: platform_data.get("record_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            item_id: if submitted.bvid.is_empty() { None } else { Some(submitted.bvid) },
+            error_message: None,
+        })
+    }
+}
+
+/// 投稿请求参数
+struct SubmitInput<'a> {
+    file_name: &'a str,
+    cover_url: &'a str,
+    title: &'a str,
+    description: &'a str,
+    hashtags: &'a [String],
+    tid: i64,
+    visibility_type: i32,
+    send_time: Option<i64>,
+    /// 当前时间（秒），用于校验定时发布时间
+    now: i64,
+}
+
+/// 构建标签：去掉 `#`、去重，最多 10 个，逗号分隔
+fn build_tags(hashtags: &[String]) -> String {
+    let mut tags: Vec<&str> = Vec::new();
+    for tag in hashtags {
+        let tag = tag.trim().trim_start_matches('#').trim();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags.truncate(MAX_TAGS);
+    tags.join(",")
+}
+
+/// 构建投稿请求数据
+fn build_submit_data(input: &SubmitInput) -> Value {
+    let title: String = input.title.trim().chars().take(MAX_TITLE_CHARS).collect();
+    let desc: String = input.description.trim().chars().take(MAX_DESC_CHARS).collect();
+
+    // B站定时发布需在 2 小时之后，过早的时间改为立即发布
+    let dtime = input.send_time
+        .filter(|t| *t >= input.now + MIN_SCHEDULE_DELAY_SECS)
+        .unwrap_or(0);
+
+    let mut data = json!({
+        "copyright": 1,
+        "source": "",
+        "tid": input.tid,
+        "cover": input.cover_url,
+        "title": title,
+        "desc_format_id": 0,
+        "desc": desc,
+        "dynamic": "",
+        "tag": build_tags(input.hashtags),
+        "videos": [{
+            "filename": input.file_name,
+            "title": "",
+            "desc": "",
+        }],
+        "no_reprint": 1,
+        "open_elec": 0,
+        "up_selection_reply": false,
+        "up_close_reply": false,
+        "up_close_danmu": false,
+        // is_only_self: 1=仅自己可见
+        "is_only_self": if input.visibility_type == 0 { 0 } else { 1 },
+    });
+    if dtime > 0 {
+        data["dtime"] = json!(dtime);
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input<'a>(hashtags: &'a [String], send_time: Option<i64>) -> SubmitInput<'a> {
+        SubmitInput {
+            file_name: "n1",
+            cover_url: "https://i0.hdslb.com/cover.jpg",
+            title: "标题",
+            description: "简介",
+            hashtags,
+            tid: DEFAULT_TID,
+            visibility_type: 0,
+            send_time,
+            now: 1_000_000,
+        }
+    }
+
+    #[test]
+    fn test_build_tags() {
+        let tags = vec!["#美食".to_string(), " 探店 ".to_string(), "美食".to_string(), String::new()];
+        assert_eq!(build_tags(&tags), "美食,探店");
+
+        let many: Vec<String> = (0..15).map(|i| format!("t{}", i)).collect();
+        assert_eq!(build_tags(&many).split(',').count(), MAX_TAGS);
+    }
+
+    #[test]
+    fn test_build_submit_data_schedule() {
+        let data = build_submit_data(&input(&[], Some(1_000_000 + MIN_SCHEDULE_DELAY_SECS)));
+        assert_eq!(data["dtime"], 1_000_000 + MIN_SCHEDULE_DELAY_SECS);
+        assert_eq!(data["videos"][0]["filename"], "n1");
+
+        // 不足 2 小时的定时发布改为立即发布
+        let data = build_submit_data(&input(&[], Some(1_000_000 + 60)));
+        assert!(data.get("dtime").is_none());
+    }
+}
@@ -0,0 +1,191 @@
+//! B站视频分片上传器
+//!
+//! B站使用 UPOS 协议上传，与抖音的 VOD V4 签名上传不同
+//!
+//! # 上传流程
+//!
+//! 1. **预上传** (`preupload`) - 获取上传地址、`X-Upos-Auth` 鉴权、建议分片大小
+//! 2. **初始化** (`POST {url}?uploads&output=json`) - 获取 uploadId
+//! 3. **分片上传** (`PUT {url}?partNumber=..&uploadId=..`) - 按分片依次上传，记录 ETag
+//! 4. **合并分片** (`POST {url}?output=json&uploadId=..`) - 提交分片列表
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crate::platforms::bilibili::uploader::BilibiliUploader;
+//!
+//! let uploader = BilibiliUploader::new(&client);
+//! let file_name = uploader.upload_video(Path::new("/path/to/video.mp4")).await?;
+//! ```
+
+use crate::platforms::bilibili::client::{BilibiliClient, PreUpload};
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// 默认分片大小 (10MB)，预上传未返回建议值时使用
+const DEFAULT_CHUNK_SIZE: u64 = 10 * 1024 * 1024;
+
+/// 上传请求超时（秒）
+const UPLOAD_TIMEOUT_SECS: u64 = 300;
+
+/// B站视频分片上传器
+pub struct BilibiliUploader<'a> {
+    client: &'a BilibiliClient,
+    http: reqwest::Client,
+}
+
+impl<'a> BilibiliUploader<'a> {
+    /// 创建上传器
+    pub fn new(client: &'a BilibiliClient) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(UPLOAD_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        Self { client, http }
+    }
+
+    /// 上传视频，返回投稿使用的文件名
+    pub async fn upload_video(&self, path: &Path) -> Result<String, String> {
+        let file_size = std::fs::metadata(path)
+            .map_err(|e| format!("读取文件信息失败: {}", e))?
+            .len();
+        if file_size == 0 {
+            return Err(format!("文件为空: {}", path.display()));
+        }
+        let file_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "video.mp4".to_string());
+
+        // 步骤1: 预上传
+        let pre = self.client.preupload(&file_name, file_size).await?;
+        let url = pre.upload_url();
+        tracing::info!("[Bilibili Upload] 预上传成功, url: {}", url);
+
+        // 步骤2: 初始化分片上传
+        let upload_id = self.init_upload(&pre, &url).await?;
+
+        // 步骤3: 分片上传
+        let chunk_size = if pre.chunk_size > 0 { pre.chunk_size } else { DEFAULT_CHUNK_SIZE };
+        let chunks = chunk_count(file_size, chunk_size);
+        let mut file = File::open(path).map_err(|e| format!("打开文件失败: {}", e))?;
+        let mut buffer = vec![0u8; chunk_size as usize];
+        let mut parts = Vec::with_capacity(chunks as usize);
+
+        for index in 0..chunks {
+            let start = index * chunk_size;
+            let size = read_chunk(&mut file, &mut buffer).map_err(|e| format!("读取文件失败: {}", e))?;
+            if size == 0 {
+                break;
+            }
+
+            let part_url = format!(
+                "{}?partNumber={}&uploadId={}&chunk={}&chunks={}&size={}&start={}&end={}&total={}",
+                url, index + 1, upload_id, index, chunks, size, start, start + size as u64, file_size
+            );
+            let response = self.http.put(&part_url)
+                .header("X-Upos-Auth", &pre.auth)
+                .header("User-Agent", &self.client.user_agent)
+                .header("Content-Type", "application/octet-stream")
+                .body(buffer[..size].to_vec())
+                .send()
+                .await
+                .map_err(|e| format!("上传分片{}失败: {}", index + 1, e))?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let text = response.text().await.unwrap_or_default();
+                return Err(format!("上传分片{}失败: HTTP {}, 响应: {}", index + 1, status.as_u16(), text));
+            }
+
+            parts.push(json!({ "partNumber": index + 1, "eTag": "etag" }));
+            tracing::debug!("[Bilibili Upload] 分片 {}/{} 上传成功 ({} 字节)", index + 1, chunks, size);
+        }
+
+        // 步骤4: 合并分片
+        self.complete_upload(&pre, &url, &upload_id, &file_name, parts).await?;
+
+        let name = pre.file_name();
+        tracing::info!("[Bilibili Upload] 上传完成, filename: {}, 分片数: {}", name, chunks);
+        Ok(name)
+    }
+
+    /// 初始化分片上传，返回 uploadId
+    async fn init_upload(&self, pre: &PreUpload, url: &str) -> Result<String, String> {
+        let response = self.http.post(format!("{}?uploads&output=json", url))
+            .header("X-Upos-Auth", &pre.auth)
+            .header("User-Agent", &self.client.user_agent)
+            .send()
+            .await
+            .map_err(|e| format!("初始化上传失败: {}", e))?;
+
+        let json = parse_upos_response(response, "初始化上传").await?;
+        json.get("upload_id")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("初始化上传失败：未返回upload_id {}", json))
+    }
+
+    /// 合并分片
+    async fn complete_upload(&self, pre: &PreUpload, url: &str, upload_id: &str, file_name: &str, parts: Vec<Value>) -> Result<(), String> {
+        let complete_url = format!(
+            "{}?output=json&name={}&profile=ugcupos%2Fbup&uploadId={}&biz_id={}",
+            url, urlencoding::encode(file_name), upload_id, pre.biz_id
+        );
+        let response = self.http.post(&complete_url)
+            .header("X-Upos-Auth", &pre.auth)
+            .header("User-Agent", &self.client.user_agent)
+            .header("Content-Type", "application/json")
+            .body(json!({ "parts": parts }).to_string())
+            .send()
+            .await
+            .map_err(|e| format!("合并分片失败: {}", e))?;
+
+        let json = parse_upos_response(response, "合并分片").await?;
+        if json.get("OK").and_then(|v| v.as_i64()) != Some(1) {
+            return Err(format!("合并分片失败: {}", json));
+        }
+        Ok(())
+    }
+}
+
+/// 分片数量（向上取整）
+fn chunk_count(file_size: u64, chunk_size: u64) -> u64 {
+    file_size.div_ceil(chunk_size)
+}
+
+/// 读满一个分片（文件末尾可能不足一个分片）
+fn read_chunk(file: &mut File, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let n = file.read(&mut buffer[filled..])?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(filled)
+}
+
+/// 解析 UPOS 响应
+async fn parse_upos_response(response: reqwest::Response, action: &str) -> Result<Value, String> {
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(format!("{}失败: HTTP {}, 响应: {}", action, status.as_u16(), text));
+    }
+    serde_json::from_str(&text).map_err(|e| format!("{}失败: 解析响应失败 {}, 响应: {}", action, e, text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_count() {
+        assert_eq!(chunk_count(1, DEFAULT_CHUNK_SIZE), 1);
+        assert_eq!(chunk_count(DEFAULT_CHUNK_SIZE, DEFAULT_CHUNK_SIZE), 1);
+        assert_eq!(chunk_count(DEFAULT_CHUNK_SIZE + 1, DEFAULT_CHUNK_SIZE), 2);
+    }
+}
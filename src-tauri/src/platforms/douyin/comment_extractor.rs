@@ -7,6 +7,9 @@
 //! - API端点: `https://www.douyin.com/aweme/v1/web/comment/list/`
 //! - 参数: aweme_id, cursor, count
 //! - 响应: total, comments数组
+//!
+//! - 回复端点: `https://www.douyin.com/aweme/v1/web/comment/publish`
+//! - 参数: aweme_id, text, reply_id（被回复的评论ID）
 
 use crate::core::{Comment, CommentStatus, CommentExtractResult, PlatformError, LocalDataItem};
 use crate::platforms::douyin::account_params::AccountParams;
@@ -20,6 +23,9 @@ use std::time::Duration;
 /// 抖音评论API基础URL
 const COMMENT_API_URL: &str = "https://www.douyin.com/aweme/v1/web/comment/list/";
 
+/// 抖音评论发布API（回复评论）
+const COMMENT_PUBLISH_URL: &str = "https://www.douyin.com/aweme/v1/web/comment/publish";
+
 /// 最大提取评论数
 const MAX_COMMENTS: i64 = 500;
 
@@ -120,6 +126,8 @@ impl DouyinCommentExtractor {
                     total_in_aweme: page_result.total,
                     comments: page_comments,
                     error_message: None,
+                    next_cursor: page_result.cursor,
+                    has_more: page_result.has_more,
                 })
             }
             Err(e) => {
@@ -130,11 +138,64 @@ impl DouyinCommentExtractor {
                     total_in_aweme: 0,
                     comments: Vec::new(),
                     error_message: Some(format!("提取评论失败: {}", e)),
+                    next_cursor: cursor,
+                    has_more: false,
                 })
             }
         }
     }
 
+    /// 回复一条评论
+    ///
+    /// 需要账号登录态，使用完整的账号 Cookie 请求
+    pub async fn reply(&self, aweme_id: &str, comment_id: &str, text: &str) -> Result<(), PlatformError> {
+        let (_, webid) = self.parse_cookies_from_db();
+        let ms_token = self.generate_ms_token();
+
+        let req_url = format!(
+            "{}?device_platform=webapp&aid=6383&channel=channel_pc_web&pc_client_type=1&version_code=170400&version_name=17.4.0&cookie_enabled=true&browser_language=zh-CN&browser_platform=Win32&browser_name=Chrome&browser_version=123.0.0.0&browser_online=true&engine_name=Blink&engine_version=123.0.0.0&os_name=Windows&os_version=10&platform=PC&webid={}&msToken={}",
+            COMMENT_PUBLISH_URL,
+            webid,
+            ms_token
+        );
+        let a_bogus = self.calculate_a_bogus(&req_url);
+        let final_url = format!("{}&a_bogus={}", req_url, a_bogus);
+
+        let form = [
+            ("aweme_id", aweme_id),
+            ("comment_send_type", "0"),
+            ("channel_id", "0"),
+            ("city", ""),
+            ("text", text),
+            ("reply_id", comment_id),
+            ("paste_edit_method", "non_paste"),
+        ];
+
+        let response = ASYNC_CLIENT
+            .post(&final_url)
+            .header("Accept", "application/json, text/plain, */*")
+            .header("User-Agent", &self.user_agent)
+            .header("Accept-Language", "zh-CN,zh;q=0.9,en;q=0.8")
+            .header("Referer", format!("https://www.douyin.com/video/{}", aweme_id))
+            .header("Cookie", &self.cookie)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| PlatformError::NetworkError(format!("回复评论请求失败: {}", e)))?;
+
+        let response_json: Value = response.json().await
+            .map_err(|e| PlatformError::NetworkError(format!("解析回复响应失败: {}", e)))?;
+
+        let status_code = response_json.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if status_code != 0 {
+            let msg = response_json.get("status_msg").and_then(|v| v.as_str()).unwrap_or("未知错误");
+            return Err(PlatformError::PublicationFailed(format!("回复评论失败: {} (code: {})", msg, status_code)));
+        }
+
+        tracing::info!("[Comment] 已回复评论: aweme_id={}, comment_id={}", aweme_id, comment_id);
+        Ok(())
+    }
+
     /// 从Cookie中解析ttwid和webid
     fn parse_cookies_from_db(&self) -> (String, String) {
         let mut ttwid = String::new();
@@ -370,9 +431,18 @@ impl DouyinCommentExtractor {
             .cloned()
             .unwrap_or_default();
 
-        tracing::info!("[Comment] total: {}, comments_count: {}", total, comments.len());
+        // 下一页游标：接口未返回时按本页数量推算
+        let next_cursor = response_json.get("cursor")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(cursor + comments.len() as i64);
+        let has_more = response_json.get("has_more")
+            .and_then(|v| v.as_i64())
+            .map(|v| v == 1)
+            .unwrap_or(false);
+
+        tracing::info!("[Comment] total: {}, comments_count: {}, next_cursor: {}, has_more: {}", total, comments.len(), next_cursor, has_more);
 
-        Ok(PageResult { total, comments })
+        Ok(PageResult { total, comments, cursor: next_cursor, has_more })
     }
 
     /// 解析评论数据
@@ -437,6 +507,7 @@ impl DouyinCommentExtractor {
             create_time,
             status: CommentStatus::Completed,
             created_at: now.to_string(),
+            tags: Vec::new(),
         })
     }
 
@@ -495,4 +566,6 @@ impl DouyinCommentExtractor {
 struct PageResult {
     total: i64,
     comments: Vec<Value>,
+    cursor: i64,      // 下一页游标
+    has_more: bool,
 }
@@ -9,7 +9,11 @@
 //! - 获取BD Ticket Guard Client Data（安全凭证）
 //! - 获取视频上传配置
 //! - 搜索话题建议
+//! - 搜索位置（POI）
+//! - 搜索音乐
+//! - 搜索用户（@ 提及）
 //! - 发布视频（V2接口）
+//! - 保存草稿
 //!
 //! # 使用示例
 //!
@@ -30,7 +34,8 @@
 //! let result = client.get_public_video_v2(publish_data, csrf_token, bd_ticket).await;
 //! ```
 
-use crate::core::LocalDataItem;
+use crate::core::{LocalDataItem, PublishErrorCode};
+use crate::platforms::douyin::video_uploader::VideoUploader;
 use serde_json::Value;
 use std::collections::HashMap;
 
@@ -40,6 +45,9 @@ const BASE_URL: &str = "https://creator.douyin.com";
 /// MSSDK URL
 const MSSDK_URL: &str = "https://mssdk.bytedance.com";
 
+/// 保存草稿接口路径（请求体与 create_v2 相同）
+pub const DRAFT_CREATE_PATH: &str = "/web/api/media/aweme/draft/create_v2/";
+
 /// BD Ticket API URL
 const BD_TICKET_API_URL: &str = "https://sssj-acibpxtpbg.cn-beijing.fcapp.run/douyin/bd-ticket-guard-client-data";
 
@@ -482,8 +490,7 @@ impl DouyinClient {
 
         let status_code = response.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
         if status_code != 0 {
-            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("获取失败");
-            return Err(format!("获取上传配置失败: {}", msg));
+            return Err(format!("获取上传配置失败: {}", status_message(&response, "获取失败")));
         }
 
         // 调试：检查auth字段
@@ -510,6 +517,238 @@ impl DouyinClient {
         Ok(response)
     }
 
+    /// 获取上传授权（VOD / ImageX V4 签名所需的临时凭证）
+    ///
+    /// # 返回
+    ///
+    /// `auth` 对象中的非空字段（AccessKeyID、SecretAccessKey、SessionToken 等）
+    ///
+    /// # 错误
+    ///
+    /// 获取上传配置失败或 auth 为空时返回错误信息
+    pub async fn get_upload_auth(&self) -> Result<HashMap<String, Value>, String> {
+        let upload_options = self.get_upload_options().await?;
+
+        // get_upload_options 已把字符串形式的 auth 解析为对象
+        let upload_auth: HashMap<String, Value> = match upload_options.get("auth") {
+            Some(Value::Object(map)) => map.iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            other => {
+                tracing::warn!("[UploadOptions] auth类型未知: {:?}", other);
+                HashMap::new()
+            }
+        };
+
+        if upload_auth.is_empty() {
+            return Err("获取上传配置失败：auth为空".to_string());
+        }
+        Ok(upload_auth)
+    }
+
+    /// 上传封面图片（不依赖视频上传会话）
+    ///
+    /// # 参数
+    ///
+    /// * `image_path` - 本地图片路径（JPG / PNG，不超过10MB）
+    ///
+    /// # 返回
+    ///
+    /// ImageX 图片URI（如 `tos-cn-i-jm8ajry58r/xxx`），发布时写入 create_v2 的 poster 字段
+    pub async fn upload_cover(&self, image_path: &str) -> Result<String, String> {
+        let upload_auth = self.get_upload_auth().await?;
+        VideoUploader::new(upload_auth, self.third_id.clone(), self.user_agent.clone())
+            .upload_image(image_path)
+            .await
+    }
+
+    /// 获取当前登录用户信息
+    ///
+    /// # 返回
+    ///
+    /// 响应中的 `user` 对象（包含 uid、sec_uid、nickname、avatar_thumb）
+    ///
+    /// # 错误
+    ///
+    /// Cookie 无效或未登录时返回错误信息
+    pub async fn get_user_info(&self) -> Result<Value, String> {
+        let response = self.request_get("/web/api/media/user/info/", None).await;
+
+        let status_code = response.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if status_code != 0 {
+            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("未登录");
+            return Err(format!("获取用户信息失败: {}", msg));
+        }
+
+        response.get("user")
+            .cloned()
+            .ok_or_else(|| "获取用户信息失败: user为空".to_string())
+    }
+
+    /// 获取作品互动数据
+    ///
+    /// # 参数
+    ///
+    /// * `item_id` - 发布成功后返回的作品ID
+    ///
+    /// # 返回
+    ///
+    /// 响应中的 `statistics` 对象（digg_count、comment_count、collect_count、share_count）
+    ///
+    /// # 错误
+    ///
+    /// Cookie 无效或作品不存在时返回错误信息
+    pub async fn get_item_statistics(&self, item_id: &str) -> Result<Value, String> {
+        let mut params = HashMap::new();
+        params.insert("aweme_id".to_string(), item_id.to_string());
+        params.insert("aid".to_string(), "2906".to_string());
+
+        let response = self.request_get("/web/api/media/aweme/detail/", Some(params)).await;
+
+        let status_code = response.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if status_code != 0 {
+            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("请求失败");
+            return Err(format!("获取作品数据失败: {}", msg));
+        }
+
+        response.get("aweme_detail")
+            .or_else(|| response.get("aweme"))
+            .and_then(|aweme| aweme.get("statistics"))
+            .cloned()
+            .ok_or_else(|| "获取作品数据失败: statistics为空".to_string())
+    }
+
+    /// 获取账号已发布的作品列表（一页）
+    ///
+    /// # 参数
+    ///
+    /// * `max_cursor` - 分页游标（首页为0，之后取上一页响应中的 `max_cursor`）
+    /// * `count` - 每页数量
+    ///
+    /// # 返回
+    ///
+    /// 完整响应（`aweme_list` 作品数组、`max_cursor`、`has_more`）
+    ///
+    /// # 错误
+    ///
+    /// Cookie 无效或未登录时返回错误信息
+    pub async fn get_work_list(&self, max_cursor: i64, count: i64) -> Result<Value, String> {
+        let mut params = HashMap::new();
+        params.insert("status".to_string(), "0".to_string());
+        params.insert("count".to_string(), count.to_string());
+        params.insert("max_cursor".to_string(), max_cursor.to_string());
+        params.insert("scene".to_string(), "star_atlas".to_string());
+        params.insert("aid".to_string(), "1128".to_string());
+
+        let response = self.request_get("/janus/douyin/creator/pc/work_list", Some(params)).await;
+
+        let status_code = response.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if status_code != 0 {
+            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("请求失败");
+            return Err(format!("获取作品列表失败: {}", msg));
+        }
+
+        Ok(response)
+    }
+
+    /// 搜索位置（POI）
+    ///
+    /// # 参数
+    ///
+    /// * `keyword` - 搜索关键词（地点名称）
+    /// * `count` - 返回数量
+    ///
+    /// # 返回
+    ///
+    /// 完整响应（`poi_list` 位置数组）
+    ///
+    /// # 错误
+    ///
+    /// Cookie 无效或接口返回错误时返回错误信息
+    pub async fn search_poi(&self, keyword: &str, count: i64) -> Result<Value, String> {
+        let mut params = HashMap::new();
+        params.insert("keywords".to_string(), keyword.to_string());
+        params.insert("search_type".to_string(), "1".to_string());
+        params.insert("page".to_string(), "1".to_string());
+        params.insert("count".to_string(), count.to_string());
+        params.insert("aid".to_string(), "1128".to_string());
+
+        let response = self.request_get("/aweme/v1/life/video_api/search/poi/", Some(params)).await;
+
+        let status_code = response.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if status_code != 0 {
+            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("请求失败");
+            return Err(format!("搜索位置失败: {}", msg));
+        }
+
+        Ok(response)
+    }
+
+    /// 搜索音乐
+    ///
+    /// # 参数
+    ///
+    /// * `keyword` - 搜索关键词（歌名或歌手）
+    /// * `count` - 返回数量
+    ///
+    /// # 返回
+    ///
+    /// 完整响应（`music_list` 音乐数组）
+    ///
+    /// # 错误
+    ///
+    /// Cookie 无效或接口返回错误时返回错误信息
+    pub async fn search_music(&self, keyword: &str, count: i64) -> Result<Value, String> {
+        let mut params = HashMap::new();
+        params.insert("keyword".to_string(), keyword.to_string());
+        params.insert("cursor".to_string(), "0".to_string());
+        params.insert("count".to_string(), count.to_string());
+        params.insert("aid".to_string(), "1128".to_string());
+
+        let response = self.request_get("/web/api/media/music/search/", Some(params)).await;
+
+        let status_code = response.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if status_code != 0 {
+            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("请求失败");
+            return Err(format!("搜索音乐失败: {}", msg));
+        }
+
+        Ok(response)
+    }
+
+    /// 搜索可 @ 的用户
+    ///
+    /// # 参数
+    ///
+    /// * `keyword` - 用户昵称
+    /// * `count` - 返回数量
+    ///
+    /// # 返回
+    ///
+    /// 完整响应（`user_list` 用户数组）
+    ///
+    /// # 错误
+    ///
+    /// Cookie 无效或接口返回错误时返回错误信息
+    pub async fn search_user(&self, keyword: &str, count: i64) -> Result<Value, String> {
+        let mut params = HashMap::new();
+        params.insert("keyword".to_string(), keyword.to_string());
+        params.insert("cursor".to_string(), "0".to_string());
+        params.insert("count".to_string(), count.to_string());
+        params.insert("aid".to_string(), "1128".to_string());
+
+        let response = self.request_get("/aweme/v1/creator/at/search/", Some(params)).await;
+
+        let status_code = response.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if status_code != 0 {
+            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("请求失败");
+            return Err(format!("搜索用户失败: {}", msg));
+        }
+
+        Ok(response)
+    }
+
     /// 搜索话题建议
     ///
     /// # 参数
@@ -585,7 +824,83 @@ impl DouyinClient {
             self.get_csrf_token("/web/api/media/aweme/create_v2/").await?
         };
 
-        // 构建查询参数（与Java成功的请求一致）
+        let params = Self::create_v2_params();
+        let mut headers = Self::bd_ticket_headers(&bd_ticket);
+        headers.insert("X-Secsdk-Csrf-Token".to_string(), csrf_token.clone());
+        headers.insert(
+            "Referer".to_string(),
+            format!("{}/creator-micro/content/post/video?enter_from=publish_page", BASE_URL),
+        );
+
+        // ========== 打印发布API请求信息 ==========
+
+        let response = self.request_post(
+            "/web/api/media/aweme/create_v2/",
+            Some(params),
+            Some(publish_data),
+            Some(headers),
+        ).await;
+
+        if response == Value::Null {
+            return Err("请去账号管理列表中解除风控[抖音]".to_string());
+        }
+
+        let status_code = response.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if status_code != 0 {
+            return Err(format!("{} [视频发布V2]", status_message(&response, "未知错误")));
+        }
+
+        let item_id = response.get("item_id").and_then(|v| v.as_str()).unwrap_or("");
+
+        if item_id.is_empty() {
+            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("未知错误");
+            return Err(format!("{} [视频发布V2结果]", msg));
+        }
+
+        Ok(response)
+    }
+
+    /// 保存作品到创作者中心草稿箱（不发布）
+    ///
+    /// 请求体与 create_v2 发布相同，作品上传完成后保存为平台草稿，可在创作者中心继续编辑后发布
+    ///
+    /// # 返回
+    ///
+    /// 完整响应（包含 `draft_id`）
+    ///
+    /// # 错误
+    ///
+    /// 如果保存失败，返回错误信息
+    pub async fn save_draft_v2(
+        &mut self,
+        publish_data: HashMap<String, Value>,
+        csrf_token: String,
+        bd_ticket: HashMap<String, String>,
+    ) -> Result<Value, String> {
+        let params = Self::create_v2_params();
+        let mut headers = Self::bd_ticket_headers(&bd_ticket);
+        headers.insert("X-Secsdk-Csrf-Token".to_string(), csrf_token);
+        headers.insert(
+            "Referer".to_string(),
+            format!("{}/creator-micro/content/post/video?enter_from=publish_page", BASE_URL),
+        );
+
+        let response = self.request_post(DRAFT_CREATE_PATH, Some(params), Some(publish_data), Some(headers)).await;
+
+        if response == Value::Null {
+            return Err("请去账号管理列表中解除风控[抖音]".to_string());
+        }
+
+        let status_code = response.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if status_code != 0 {
+            return Err(format!("{} [保存草稿]", status_message(&response, "未知错误")));
+        }
+
+        Ok(response)
+    }
+
+    /// create_v2 / 草稿接口的查询参数（与Java成功的请求一致）
+    fn create_v2_params() -> HashMap<String, String> {
         let mut params = HashMap::new();
         params.insert("read_aid".to_string(), "2906".to_string());
         params.insert("cookie_enabled".to_string(), "true".to_string()); // Java用"true"
@@ -596,8 +911,11 @@ impl DouyinClient {
         params.insert("browser_online".to_string(), "true".to_string()); // Java有
         params.insert("timezone_name".to_string(), "Asia/Shanghai".to_string()); // Java有
         params.insert("aid".to_string(), "1128".to_string()); // Java有
+        params
+    }
 
-        // 构建请求头（使用Java成功的6个BD ticket字段）
+    /// 构建请求头（使用Java成功的6个BD ticket字段）
+    fn bd_ticket_headers(bd_ticket: &HashMap<String, String>) -> HashMap<String, String> {
         let mut headers = HashMap::new();
 
         // 提取所有6个BD ticket字段（与Java一致）
@@ -622,39 +940,89 @@ impl DouyinClient {
             headers.insert("bd-ticket-guard-iteration-version".to_string(), v.clone());
         }
 
-        headers.insert("X-Secsdk-Csrf-Token".to_string(), csrf_token.clone());
+        headers
+    }
+
+    /// 删除已发布的作品
+    ///
+    /// # 参数
+    ///
+    /// * `item_id` - 发布成功后返回的作品ID
+    ///
+    /// # 错误
+    ///
+    /// Cookie 无效、作品不存在或无权删除时返回错误信息
+    pub async fn delete_item(&mut self, item_id: &str) -> Result<(), String> {
+        let csrf_token = self.get_csrf_token("/web/api/media/aweme/delete/").await?;
+
+        let mut params = HashMap::new();
+        params.insert("aid".to_string(), "2906".to_string());
+
+        let mut data = HashMap::new();
+        data.insert("item_id".to_string(), Value::String(item_id.to_string()));
+
+        let mut headers = HashMap::new();
+        headers.insert("X-Secsdk-Csrf-Token".to_string(), csrf_token);
         headers.insert(
             "Referer".to_string(),
-            format!("{}/creator-micro/content/post/video?enter_from=publish_page", BASE_URL),
+            format!("{}/creator-micro/content/manage", BASE_URL),
         );
 
-        // ========== 打印发布API请求信息 ==========
-
-        let response = self.request_post(
-            "/web/api/media/aweme/create_v2/",
-            Some(params),
-            Some(publish_data),
-            Some(headers),
-        ).await;
-
-        if response == Value::Null {
-            return Err("请去账号管理列表中解除风控[抖音]".to_string());
-        }
+        let response = self.request_post("/web/api/media/aweme/delete/", Some(params), Some(data), Some(headers)).await;
 
         let status_code = response.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
         if status_code != 0 {
-            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("未知错误");
-            return Err(format!("{} [视频发布V2]", msg));
+            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("请求失败");
+            return Err(format!("删除作品失败: {}", msg));
         }
 
-        let item_id = response.get("item_id").and_then(|v| v.as_str()).unwrap_or("");
+        Ok(())
+    }
+}
 
-        if item_id.is_empty() {
-            let msg = response.get("status_msg").and_then(|v| v.as_str()).unwrap_or("未知错误");
-            return Err(format!("{} [视频发布V2结果]", msg));
-        }
+/// 风控验证失败时错误信息中的标记
+pub const RISK_CONTROL_MARKER: &str = "（触发风控验证）";
 
-        Ok(response)
+/// 完成风控验证的页面（在账号的浏览器资料目录中打开，登录状态下平台会弹出验证）
+pub const VERIFY_PAGE_URL: &str = "https://creator.douyin.com/creator-micro/content/upload";
+
+/// 风控验证响应中的字段（需要在创作者中心完成滑块 / 短信等安全验证）
+const RISK_CONTROL_KEYS: &[&str] = &["verify_center_decision_conf", "verify_type", "nlp_verify"];
+
+/// Whether a Douyin API response asks for a captcha / security verification
+/// 接口响应是否为风控验证（验证码、安全验证）
+pub fn is_risk_control_response(response: &Value) -> bool {
+    if RISK_CONTROL_KEYS.iter().any(|key| response.get(key).is_some()) {
+        return true;
+    }
+    response.get("status_msg")
+        .and_then(|v| v.as_str())
+        .is_some_and(|msg| msg.contains("验证码") || msg.contains("安全验证"))
+}
+
+/// 接口失败时的错误信息；风控验证响应附加"触发风控"，发布失败归类和账号风险监控据此识别
+fn status_message(response: &Value, default: &str) -> String {
+    let msg = response.get("status_msg").and_then(|v| v.as_str()).filter(|m| !m.is_empty()).unwrap_or(default);
+    if is_risk_control_response(response) {
+        format!("{}{}", msg, RISK_CONTROL_MARKER)
+    } else {
+        msg.to_string()
+    }
+}
+
+/// 错误信息是否来自风控验证响应（可由用户完成验证后重试）
+pub fn is_risk_control_error(message: &str) -> bool {
+    message.contains(RISK_CONTROL_MARKER)
+}
+
+/// Classify a Douyin API error message into a publish error code
+/// 归类抖音接口的错误信息：抖音特有的登录离线提示（BD 凭证 3602、发布接口"用户未登录"）归为凭证过期，
+/// 其余按通用关键字归类（风控、频繁、5xx 等）
+pub fn douyin_error_code(message: &str) -> PublishErrorCode {
+    if ["登录离线", "(3602)", "用户未登录"].iter().any(|k| message.contains(k)) {
+        PublishErrorCode::CookieExpired
+    } else {
+        PublishErrorCode::from_message(message)
     }
 }
 
@@ -662,6 +1030,27 @@ impl DouyinClient {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_risk_control_response() {
+        let verify = serde_json::json!({
+            "status_code": 2483,
+            "status_msg": "",
+            "verify_center_decision_conf": "{\"type\":\"verify\"}",
+        });
+        assert!(is_risk_control_response(&verify));
+        assert_eq!(status_message(&verify, "未知错误"), "未知错误（触发风控验证）");
+        assert!(is_risk_control_error(&format!("{} [视频发布V2]", status_message(&verify, "未知错误"))));
+        assert_eq!(douyin_error_code(&status_message(&verify, "未知错误")), PublishErrorCode::RiskControl);
+
+        let captcha = serde_json::json!({ "status_code": 7, "status_msg": "请完成安全验证" });
+        assert!(is_risk_control_response(&captcha));
+
+        let other = serde_json::json!({ "status_code": 8, "status_msg": "内容违规" });
+        assert!(!is_risk_control_response(&other));
+        assert_eq!(status_message(&other, "未知错误"), "内容违规");
+        assert!(!is_risk_control_error("内容违规 [视频发布V2]"));
+    }
+
     #[test]
     fn test_build_url() {
         let client = DouyinClient::new(
@@ -681,4 +1070,13 @@ mod tests {
         assert!(url.contains("key1=value1"));
         assert!(url.contains("key2=value2"));
     }
+
+    #[test]
+    fn test_douyin_error_code() {
+        assert_eq!(douyin_error_code("登录离线[获取抖音发布服务BD(3602)]"), PublishErrorCode::CookieExpired);
+        assert_eq!(douyin_error_code("请去账号管理列表中解除风控[抖音]"), PublishErrorCode::RiskControl);
+        assert_eq!(douyin_error_code("操作太频繁，请稍后再试 [视频发布V2]"), PublishErrorCode::RateLimited);
+        assert_eq!(douyin_error_code("获取上传配置失败: HTTP 503"), PublishErrorCode::Network);
+        assert_eq!(douyin_error_code("未知错误 [视频发布V2]"), PublishErrorCode::Unknown);
+    }
 }
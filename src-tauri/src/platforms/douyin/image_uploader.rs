@@ -0,0 +1,197 @@
+//! 图文图片上传器
+//!
+//! 负责上传图文（图片轮播）作品的图片
+//!
+//! # 上传流程
+//!
+//! 每张图片按顺序走 ImageX 上传（与封面相同：申请上传 → 上传图片 → 提交），
+//! 并从文件头读取宽高，发布时写入 create_v2 的 `images` 字段
+//!
+//! 支持 JPG / PNG / WebP，单张不超过10MB，每个作品最多 [`MAX_POST_IMAGES`] 张
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crate::platforms::douyin::image_uploader::ImageUploader;
+//!
+//! let uploader = ImageUploader::new(video_uploader);
+//! let images = uploader.upload_images(&image_paths, |done, total| {}).await?;
+//! ```
+
+use crate::core::{PublishCancelToken, PUBLISH_CANCELLED_MESSAGE};
+use crate::platforms::douyin::video_uploader::VideoUploader;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// 单个图文作品的图片数量上限
+pub const MAX_POST_IMAGES: usize = 35;
+
+/// 已上传的图片
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadedImage {
+    /// 图片URI（如 `tos-cn-i-jm8ajry58r/xxx`）
+    pub uri: String,
+    /// 宽度（像素）
+    pub width: u32,
+    /// 高度（像素）
+    pub height: u32,
+}
+
+/// 图文图片上传器
+///
+/// 复用 [`VideoUploader`] 的 ImageX 上传，批量上传图文作品的图片
+#[derive(Debug, Clone)]
+pub struct ImageUploader {
+    /// 上传器（持有 ImageX 上传凭证）
+    uploader: VideoUploader,
+    /// 发布取消令牌（每张图片上传前检查）
+    cancel_token: Option<PublishCancelToken>,
+}
+
+impl ImageUploader {
+    /// 创建新的图片上传器
+    pub fn new(uploader: VideoUploader) -> Self {
+        Self { uploader, cancel_token: None }
+    }
+
+    /// 设置取消令牌，取消后不再上传剩余图片
+    pub fn with_cancel_token(mut self, cancel_token: Option<PublishCancelToken>) -> Self {
+        self.cancel_token = cancel_token;
+        self
+    }
+
+    /// 按顺序上传图片
+    ///
+    /// # 参数
+    ///
+    /// * `image_paths` - 本地图片路径（顺序即作品中的展示顺序）
+    /// * `on_progress` - 每上传完一张回调一次（已上传张数 / 总张数）
+    ///
+    /// # 返回
+    ///
+    /// 与 `image_paths` 顺序一致的图片URI和宽高
+    pub async fn upload_images(
+        &self,
+        image_paths: &[PathBuf],
+        on_progress: impl Fn(usize, usize),
+    ) -> Result<Vec<UploadedImage>, String> {
+        if image_paths.is_empty() {
+            return Err("图文作品至少需要一张图片".to_string());
+        }
+        if image_paths.len() > MAX_POST_IMAGES {
+            return Err(format!("图片数量超过上限: {}张，最多{}张", image_paths.len(), MAX_POST_IMAGES));
+        }
+
+        let total = image_paths.len();
+        let mut images = Vec::with_capacity(total);
+        for (index, path) in image_paths.iter().enumerate() {
+            if self.cancel_token.as_ref().is_some_and(|t| t.is_cancelled()) {
+                return Err(PUBLISH_CANCELLED_MESSAGE.to_string());
+            }
+
+            let bytes = std::fs::read(path)
+                .map_err(|e| format!("读取图片失败: {}: {}", path.display(), e))?;
+            let (width, height) = image_dimensions(&bytes)
+                .ok_or_else(|| format!("无法识别图片格式（仅支持 JPG/PNG/WebP）: {}", path.display()))?;
+
+            tracing::info!("[UploadImages] 上传第 {}/{} 张图片: {} ({}x{})", index + 1, total, path.display(), width, height);
+            let uri = self.uploader.upload_image(&path.to_string_lossy()).await
+                .map_err(|e| format!("第{}张图片上传失败: {}", index + 1, e))?;
+
+            images.push(UploadedImage { uri, width, height });
+            on_progress(index + 1, total);
+        }
+
+        Ok(images)
+    }
+}
+
+/// 从文件头读取图片宽高（JPG / PNG / WebP），无法识别时返回 None
+pub fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |i: usize| Some(u16::from_be_bytes([*bytes.get(i)?, *bytes.get(i + 1)?]) as u32);
+    let le16 = |i: usize| Some(u16::from_le_bytes([*bytes.get(i)?, *bytes.get(i + 1)?]) as u32);
+    let le24 = |i: usize| Some(u32::from_le_bytes([*bytes.get(i)?, *bytes.get(i + 1)?, *bytes.get(i + 2)?, 0]));
+
+    // PNG: 签名 + IHDR，宽高为大端 u32
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+        let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+        return Some((width, height));
+    }
+
+    // JPEG: 逐个段查找 SOF 段（0xC0-0xCF，排除 DHT/JPG/DAC）
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        let mut i = 2;
+        while i + 4 <= bytes.len() {
+            if bytes[i] != 0xFF {
+                return None;
+            }
+            let marker = bytes[i + 1];
+            if marker == 0xFF {
+                i += 1;
+                continue;
+            }
+            let length = be16(i + 2)? as usize;
+            if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+                return Some((be16(i + 7)?, be16(i + 5)?));
+            }
+            i += 2 + length;
+        }
+        return None;
+    }
+
+    // WebP: RIFF 容器，按 VP8X / VP8L / VP8 分别解析
+    if bytes.len() >= 30 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        return match &bytes[12..16] {
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+                Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+            }
+            b"VP8 " => Some((le16(26)? & 0x3FFF, le16(28)? & 0x3FFF)),
+            _ => None,
+        };
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_dimensions() {
+        let mut png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        png.extend_from_slice(&1080u32.to_be_bytes());
+        png.extend_from_slice(&1440u32.to_be_bytes());
+        assert_eq!(image_dimensions(&png), Some((1080, 1440)));
+    }
+
+    #[test]
+    fn test_jpeg_dimensions_skips_non_sof_segments() {
+        let jpeg = [
+            0xFF, 0xD8,
+            // APP0，长度 4
+            0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00,
+            // SOF0：长度、精度、高 1440、宽 1080
+            0xFF, 0xC0, 0x00, 0x11, 0x08, 0x05, 0xA0, 0x04, 0x38,
+        ];
+        assert_eq!(image_dimensions(&jpeg), Some((1080, 1440)));
+    }
+
+    #[test]
+    fn test_webp_vp8x_dimensions() {
+        let mut webp = b"RIFF\x00\x00\x00\x00WEBPVP8X".to_vec();
+        webp.extend_from_slice(&[0u8; 8]);
+        webp.extend_from_slice(&[0x37, 0x04, 0x00]); // 1079 + 1
+        webp.extend_from_slice(&[0x9F, 0x05, 0x00]); // 1439 + 1
+        assert_eq!(image_dimensions(&webp), Some((1080, 1440)));
+    }
+
+    #[test]
+    fn test_unknown_format() {
+        assert_eq!(image_dimensions(b"GIF89a"), None);
+        assert_eq!(image_dimensions(&[]), None);
+    }
+}
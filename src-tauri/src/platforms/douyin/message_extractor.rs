@@ -0,0 +1,247 @@
+//! 抖音私信提取器
+//!
+//! 使用账号凭证拉取创作者中心收件箱中的私信，用于线索跟进
+//!
+//! # API 参考
+//!
+//! - 会话列表: `https://creator.douyin.com/web/api/media/im/conversation/list/`
+//! - 参数: cursor, count
+//! - 响应: conversations 数组（会话ID、对方用户信息）, cursor, has_more
+//!
+//! - 消息列表: `https://creator.douyin.com/web/api/media/im/message/list/`
+//! - 参数: conversation_id, cursor, count
+//! - 响应: messages 数组（消息ID、发送者ID、内容、时间）, cursor, has_more
+
+use crate::core::{PrivateMessage, MessageExtractResult, PlatformError};
+use crate::platforms::douyin::account_params::AccountParams;
+use serde_json::Value;
+use uuid::Uuid;
+use chrono::Local;
+use std::time::Duration;
+
+/// 会话列表API
+const CONVERSATION_LIST_URL: &str = "https://creator.douyin.com/web/api/media/im/conversation/list/";
+
+/// 消息列表API
+const MESSAGE_LIST_URL: &str = "https://creator.douyin.com/web/api/media/im/message/list/";
+
+/// 私信页面（作为 Referer）
+const CHAT_PAGE_URL: &str = "https://creator.douyin.com/creator-micro/data/following/chat";
+
+/// 单次提取的最大消息数
+const MAX_MESSAGES: i64 = 1000;
+
+/// 会话列表每页数量
+const CONVERSATION_PAGE_SIZE: i64 = 20;
+
+/// 每个会话提取的最近消息数
+const MESSAGES_PER_CONVERSATION: i64 = 50;
+
+/// 共享的异步HTTP客户端
+static ASYNC_CLIENT: std::sync::LazyLock<reqwest::Client> = std::sync::LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .expect("Failed to create async HTTP client")
+});
+
+/// 抖音私信提取器
+#[derive(Debug, Clone)]
+pub struct DouyinMessageExtractor {
+    /// 用户Cookie
+    cookie: String,
+    /// User-Agent
+    user_agent: String,
+}
+
+/// 会话（对方用户信息）
+struct Conversation {
+    id: String,
+    peer_id: String,
+    peer_nickname: String,
+    peer_avatar: String,
+}
+
+/// 单页结果
+struct Page {
+    items: Vec<Value>,
+    cursor: i64,
+    has_more: bool,
+}
+
+/// 读取字符串或数字字段（平台的ID字段两种格式都有）
+fn value_string(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// 消息时间戳（秒或毫秒）转为本地时间字符串
+fn format_timestamp(ts: i64) -> Option<String> {
+    let secs = if ts > 1_000_000_000_000 { ts / 1000 } else { ts };
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|dt| dt.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+/// 解析消息内容：content 为 JSON 字符串时取其中的 text，非文本消息返回类型说明
+fn parse_content(message: &Value) -> String {
+    let raw = message.get("content").and_then(|v| v.as_str()).unwrap_or_default();
+    match serde_json::from_str::<Value>(raw) {
+        Ok(content) => content.get("text")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| match message.get("message_type").and_then(|v| v.as_i64()) {
+                Some(27) => "[图片]".to_string(),
+                Some(8) => "[视频]".to_string(),
+                Some(5) => "[表情]".to_string(),
+                _ => "[非文本消息]".to_string(),
+            }),
+        Err(_) => raw.to_string(),
+    }
+}
+
+impl DouyinMessageExtractor {
+    /// 从账号参数创建提取器
+    pub fn from_params(params_json: &str) -> Result<Self, PlatformError> {
+        let account_params = AccountParams::from_json(params_json);
+
+        let cookie = account_params.get_cookie();
+        let user_agent = account_params.get_user_agent();
+        if cookie.is_empty() || user_agent.is_empty() {
+            return Err(PlatformError::InvalidCredentials(
+                "账号参数不完整，缺少cookie或user_agent".to_string()
+            ));
+        }
+
+        Ok(Self { cookie, user_agent })
+    }
+
+    /// 提取收件箱私信：遍历会话列表，每个会话取最近的消息，直到达到 `max_count`
+    pub async fn extract(&self, max_count: i64) -> Result<MessageExtractResult, PlatformError> {
+        let max_count = max_count.clamp(1, MAX_MESSAGES) as usize;
+        let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let mut messages = Vec::new();
+
+        let mut cursor = 0;
+        'conversations: loop {
+            let page = self.fetch_page(CONVERSATION_LIST_URL, "conversations", &[
+                ("cursor", cursor.to_string()),
+                ("count", CONVERSATION_PAGE_SIZE.to_string()),
+            ]).await?;
+
+            for conversation in page.items.iter().filter_map(Self::parse_conversation) {
+                let message_page = match self.fetch_page(MESSAGE_LIST_URL, "messages", &[
+                    ("conversation_id", conversation.id.clone()),
+                    ("cursor", "0".to_string()),
+                    ("count", MESSAGES_PER_CONVERSATION.to_string()),
+                ]).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        tracing::warn!("[Message] 获取会话 {} 的消息失败: {}", conversation.id, e);
+                        continue;
+                    }
+                };
+
+                for item in &message_page.items {
+                    if let Some(message) = Self::parse_message(item, &conversation, &now) {
+                        messages.push(message);
+                        if messages.len() >= max_count {
+                            break 'conversations;
+                        }
+                    }
+                }
+            }
+
+            if !page.has_more || page.items.is_empty() {
+                break;
+            }
+            cursor = page.cursor;
+        }
+
+        tracing::info!("[Message] 提取 {} 条私信", messages.len());
+        Ok(MessageExtractResult {
+            success: true,
+            total_extracted: messages.len() as i64,
+            new_messages: 0,
+            messages,
+            error_message: None,
+        })
+    }
+
+    /// 请求一页列表数据
+    async fn fetch_page(&self, url: &str, list_key: &str, query: &[(&str, String)]) -> Result<Page, PlatformError> {
+        let response = ASYNC_CLIENT
+            .get(url)
+            .query(&[("aid", "2906"), ("device_platform", "webapp")])
+            .query(query)
+            .header("Accept", "application/json, text/plain, */*")
+            .header("User-Agent", &self.user_agent)
+            .header("Accept-Language", "zh-CN,zh;q=0.9,en;q=0.8")
+            .header("Referer", CHAT_PAGE_URL)
+            .header("Cookie", &self.cookie)
+            .send()
+            .await
+            .map_err(|e| PlatformError::NetworkError(format!("请求私信接口失败: {}", e)))?;
+
+        let json: Value = response.json().await
+            .map_err(|e| PlatformError::NetworkError(format!("解析私信响应失败: {}", e)))?;
+
+        let status_code = json.get("status_code").and_then(|v| v.as_i64()).unwrap_or(-1);
+        if status_code != 0 {
+            let msg = json.get("status_msg").and_then(|v| v.as_str()).unwrap_or("未知错误");
+            return Err(PlatformError::PublicationFailed(format!("私信接口错误: {} (code: {})", msg, status_code)));
+        }
+
+        let items = json.get(list_key).and_then(|v| v.as_array()).cloned().unwrap_or_default();
+        Ok(Page {
+            cursor: json.get("cursor").and_then(|v| v.as_i64()).unwrap_or(0),
+            has_more: json.get("has_more").map(|v| v.as_bool().unwrap_or(v.as_i64() == Some(1))).unwrap_or(false),
+            items,
+        })
+    }
+
+    /// 解析会话
+    fn parse_conversation(value: &Value) -> Option<Conversation> {
+        let user = value.get("user")?;
+        Some(Conversation {
+            id: value_string(value.get("conversation_id"))?,
+            peer_id: value_string(user.get("uid"))?,
+            peer_nickname: user.get("nickname").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            peer_avatar: user.get("avatar_thumb")
+                .and_then(|v| v.get("url_list"))
+                .and_then(|v| v.as_array())
+                .and_then(|v| v.first())
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        })
+    }
+
+    /// 解析单条消息（对方发来的消息带对方的昵称头像，本账号发出的消息标记为 outgoing）
+    fn parse_message(value: &Value, conversation: &Conversation, now: &str) -> Option<PrivateMessage> {
+        let message_id = value_string(value.get("server_message_id"))
+            .or_else(|| value_string(value.get("message_id")))?;
+        let sender_id = value_string(value.get("sender"))?;
+        let is_outgoing = sender_id != conversation.peer_id;
+        let create_time = value.get("create_time")
+            .and_then(|v| v.as_i64())
+            .and_then(format_timestamp)
+            .unwrap_or_else(|| now.to_string());
+
+        Some(PrivateMessage {
+            id: Uuid::new_v4().to_string(),
+            account_id: String::new(),
+            conversation_id: conversation.id.clone(),
+            message_id,
+            sender_nickname: if is_outgoing { String::new() } else { conversation.peer_nickname.clone() },
+            sender_avatar: if is_outgoing { String::new() } else { conversation.peer_avatar.clone() },
+            sender_id,
+            content: parse_content(value),
+            is_outgoing,
+            create_time,
+            created_at: now.to_string(),
+        })
+    }
+}
@@ -9,13 +9,16 @@
 //! - [`signature_v4`] - AWS Signature V4 签名
 //! - [`douyin_client`] - HTTP客户端
 //! - [`video_uploader`] - 视频上传器
+//! - [`image_uploader`] - 图文图片上传器
 //! - [`strategy`] - 发布策略（主入口）
 //! - [`comment_extractor`] - 评论提取器
+//! - [`message_extractor`] - 私信提取器
 //! - [`a_bogus`] - a_bogus签名计算
 
-use crate::core::{Platform, PlatformType, PlatformError, UserAccount, PublishRequest as CorePublishRequest, CommentExtractResult};
-use crate::platforms::traits::{PublishStrategy, CommentExtractor};
+use crate::core::{Platform, PlatformType, PlatformError, UserAccount, PublishRequest as CorePublishRequest, CommentExtractResult, MessageExtractResult, PublicationStats, PublishPreview, RemoteVideo, PoiInfo, MusicSearchItem};
+use crate::platforms::traits::{PublishStrategy, CommentExtractor, MessageExtractor};
 use crate::platforms::douyin::comment_extractor::DouyinCommentExtractor;
+use crate::platforms::douyin::message_extractor::DouyinMessageExtractor;
 use crate::storage::DatabaseManager;
 use std::sync::Arc;
 
@@ -24,8 +27,10 @@ pub mod utils;
 pub mod signature_v4;
 pub mod douyin_client;
 pub mod video_uploader;
+pub mod image_uploader;
 pub mod strategy;
 pub mod comment_extractor;
+pub mod message_extractor;
 pub mod a_bogus;
 
 // 导出主要类型
@@ -55,41 +60,245 @@ impl DouyinPlatform {
             db_manager: Some(Arc::new(db_manager)),
         }
     }
-}
 
-impl Default for DouyinPlatform {
-    fn default() -> Self {
-        Self::new()
+    /// 查询作品的点赞、评论、收藏、分享数
+    ///
+    /// 使用发布账号的凭证请求创作者平台，`item_id` 为发布成功后记录的作品ID
+    pub async fn fetch_item_stats(&self, account_id: &str, item_id: &str) -> Result<PublicationStats, PlatformError> {
+        use self::account_params::AccountParams;
+        use self::douyin_client::DouyinClient;
+
+        let db_manager = self.db_manager.as_ref().ok_or_else(|| {
+            PlatformError::InvalidInput("平台未配置数据库连接".to_string())
+        })?;
+
+        let account = db_manager.get_account(account_id)
+            .map_err(|e| PlatformError::StorageError(format!("查询账号失败: {:?}", e)))?
+            .ok_or_else(|| PlatformError::AccountNotFound(format!("账号不存在: {}", account_id)))?;
+
+        let params = AccountParams::from_json(&account.params);
+        let client = DouyinClient::new(
+            params.get_cookie(),
+            params.get_user_agent(),
+            params.get_third_id(),
+            params.get_local_data(),
+        );
+
+        let statistics = client.get_item_statistics(item_id).await
+            .map_err(PlatformError::NetworkError)?;
+        let count = |key: &str| statistics.get(key).and_then(|v| v.as_i64()).unwrap_or(0);
+
+        Ok(PublicationStats {
+            comments: count("comment_count"),
+            likes: count("digg_count"),
+            favorites: count("collect_count"),
+            shares: count("share_count"),
+        })
     }
-}
 
-#[async_trait::async_trait]
-impl Platform for DouyinPlatform {
-    fn platform_type(&self) -> PlatformType {
-        PlatformType::Douyin
+    /// 以账号身份上传封面图片，返回可写入 create_v2 poster 字段的图片URI
+    pub async fn upload_cover(&self, account_id: &str, image_path: &str) -> Result<String, PlatformError> {
+        use self::account_params::AccountParams;
+        use self::douyin_client::DouyinClient;
+
+        let db_manager = self.db_manager.as_ref().ok_or_else(|| {
+            PlatformError::InvalidInput("平台未配置数据库连接".to_string())
+        })?;
+
+        let account = db_manager.get_account(account_id)
+            .map_err(|e| PlatformError::StorageError(format!("查询账号失败: {:?}", e)))?
+            .ok_or_else(|| PlatformError::AccountNotFound(format!("账号不存在: {}", account_id)))?;
+
+        let params = AccountParams::from_json(&account.params);
+        let client = DouyinClient::new(
+            params.get_cookie(),
+            params.get_user_agent(),
+            params.get_third_id(),
+            params.get_local_data(),
+        );
+
+        client.upload_cover(image_path).await
+            .map_err(PlatformError::VideoUploadFailed)
     }
 
-    fn platform_name(&self) -> String {
-        "抖音".to_string()
+    /// 以账号身份回复作品下的一条评论
+    pub async fn reply_comment(&self, account_id: &str, aweme_id: &str, comment_id: &str, text: &str) -> Result<(), PlatformError> {
+        let db_manager = self.db_manager.as_ref().ok_or_else(|| {
+            PlatformError::InvalidInput("平台未配置数据库连接".to_string())
+        })?;
+
+        let account = db_manager.get_account(account_id)
+            .map_err(|e| PlatformError::StorageError(format!("查询账号失败: {:?}", e)))?
+            .ok_or_else(|| PlatformError::AccountNotFound(format!("账号不存在: {}", account_id)))?;
+
+        DouyinCommentExtractor::from_params(&account.params)?
+            .reply(aweme_id, comment_id, text)
+            .await
     }
 
-    async fn authenticate_account(&self) -> Result<UserAccount, PlatformError> {
-        Err(PlatformError::AuthenticationFailed(
-            "浏览器认证需要使用 start_browser_auth 命令".to_string(),
-        ))
+    /// 以账号身份搜索位置（POI），用于给作品添加位置锚点
+    pub async fn search_poi(&self, account_id: &str, keyword: &str) -> Result<Vec<PoiInfo>, PlatformError> {
+        use self::account_params::AccountParams;
+        use self::douyin_client::DouyinClient;
+
+        let db_manager = self.db_manager.as_ref().ok_or_else(|| {
+            PlatformError::InvalidInput("平台未配置数据库连接".to_string())
+        })?;
+
+        let account = db_manager.get_account(account_id)
+            .map_err(|e| PlatformError::StorageError(format!("查询账号失败: {:?}", e)))?
+            .ok_or_else(|| PlatformError::AccountNotFound(format!("账号不存在: {}", account_id)))?;
+
+        let params = AccountParams::from_json(&account.params);
+        let client = DouyinClient::new(
+            params.get_cookie(),
+            params.get_user_agent(),
+            params.get_third_id(),
+            params.get_local_data(),
+        );
+
+        let response = client.search_poi(keyword, POI_SEARCH_COUNT).await
+            .map_err(PlatformError::NetworkError)?;
+
+        let text = |poi: &serde_json::Value, key: &str| poi.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let pois = response.get("poi_list")
+            .and_then(|v| v.as_array())
+            .map(|list| list.iter()
+                .filter_map(|poi| {
+                    let poi_id = poi.get("poi_id")
+                        .and_then(|v| v.as_str().map(|s| s.to_string()).or_else(|| v.as_i64().map(|n| n.to_string())))
+                        .filter(|id| !id.is_empty())?;
+                    Some(PoiInfo {
+                        poi_id,
+                        poi_name: text(poi, "poi_name"),
+                        address: text(poi, "address"),
+                        city: text(poi, "city_name"),
+                    })
+                })
+                .collect())
+            .unwrap_or_default();
+
+        Ok(pois)
     }
 
-    async fn refresh_credentials(&self, _account_id: &str) -> Result<UserAccount, PlatformError> {
-        Err(PlatformError::AuthenticationFailed(
-            "请重新进行浏览器认证".to_string(),
-        ))
+    /// 以账号身份搜索音乐，用于给作品选择配乐
+    pub async fn search_music(&self, account_id: &str, keyword: &str) -> Result<Vec<MusicSearchItem>, PlatformError> {
+        use self::account_params::AccountParams;
+        use self::douyin_client::DouyinClient;
+
+        let db_manager = self.db_manager.as_ref().ok_or_else(|| {
+            PlatformError::InvalidInput("平台未配置数据库连接".to_string())
+        })?;
+
+        let account = db_manager.get_account(account_id)
+            .map_err(|e| PlatformError::StorageError(format!("查询账号失败: {:?}", e)))?
+            .ok_or_else(|| PlatformError::AccountNotFound(format!("账号不存在: {}", account_id)))?;
+
+        let params = AccountParams::from_json(&account.params);
+        let client = DouyinClient::new(
+            params.get_cookie(),
+            params.get_user_agent(),
+            params.get_third_id(),
+            params.get_local_data(),
+        );
+
+        let response = client.search_music(keyword, MUSIC_SEARCH_COUNT).await
+            .map_err(PlatformError::NetworkError)?;
+
+        let music = response.get("music_list")
+            .and_then(|v| v.as_array())
+            .map(|list| list.iter().filter_map(parse_music).collect())
+            .unwrap_or_default();
+
+        Ok(music)
     }
 
-    async fn publish_video(&self, request: CorePublishRequest) -> Result<crate::core::PublishResult, PlatformError> {
-        tracing::info!("[Publish] 开始抖音发布流程，账号ID: {}", request.account_id);
-        tracing::info!("[Publish] 视频路径: {:?}", request.video_path);
-        tracing::info!("[Publish] 视频标题: {}", request.title);
+    /// 以账号身份删除平台上已发布的作品
+    pub async fn delete_item(&self, account_id: &str, item_id: &str) -> Result<(), PlatformError> {
+        use self::account_params::AccountParams;
+        use self::douyin_client::DouyinClient;
+
+        let db_manager = self.db_manager.as_ref().ok_or_else(|| {
+            PlatformError::InvalidInput("平台未配置数据库连接".to_string())
+        })?;
+
+        let account = db_manager.get_account(account_id)
+            .map_err(|e| PlatformError::StorageError(format!("查询账号失败: {:?}", e)))?
+            .ok_or_else(|| PlatformError::AccountNotFound(format!("账号不存在: {}", account_id)))?;
+
+        let params = AccountParams::from_json(&account.params);
+        let mut client = DouyinClient::new(
+            params.get_cookie(),
+            params.get_user_agent(),
+            params.get_third_id(),
+            params.get_local_data(),
+        );
+
+        client.delete_item(item_id).await
+            .map_err(PlatformError::PublicationFailed)
+    }
+
+    /// 拉取账号在平台上的作品列表（含未通过本应用发布的作品），最多 `max_count` 个
+    ///
+    /// 返回的作品 `id` 为新生成的ID，`detail_id` 为空，入库时按 (account_id, item_id) 合并
+    pub async fn fetch_work_list(&self, account_id: &str, max_count: i64) -> Result<Vec<RemoteVideo>, PlatformError> {
+        use self::account_params::AccountParams;
+        use self::douyin_client::DouyinClient;
+
+        let db_manager = self.db_manager.as_ref().ok_or_else(|| {
+            PlatformError::InvalidInput("平台未配置数据库连接".to_string())
+        })?;
+
+        let account = db_manager.get_account(account_id)
+            .map_err(|e| PlatformError::StorageError(format!("查询账号失败: {:?}", e)))?
+            .ok_or_else(|| PlatformError::AccountNotFound(format!("账号不存在: {}", account_id)))?;
+
+        let params = AccountParams::from_json(&account.params);
+        let client = DouyinClient::new(
+            params.get_cookie(),
+            params.get_user_agent(),
+            params.get_third_id(),
+            params.get_local_data(),
+        );
+
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let max_count = max_count.max(1) as usize;
+        let mut videos = Vec::new();
+        let mut cursor = 0;
+        loop {
+            let response = client.get_work_list(cursor, WORK_LIST_PAGE_SIZE).await
+                .map_err(PlatformError::NetworkError)?;
+
+            let items = response.get("aweme_list").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+            for item in &items {
+                if let Some(video) = parse_work(item, account_id, &now) {
+                    videos.push(video);
+                    if videos.len() >= max_count {
+                        return Ok(videos);
+                    }
+                }
+            }
+
+            let has_more = response.get("has_more")
+                .map(|v| v.as_bool().unwrap_or(v.as_i64() == Some(1)))
+                .unwrap_or(false);
+            let next_cursor = response.get("max_cursor").and_then(|v| v.as_i64()).unwrap_or(0);
+            if !has_more || items.is_empty() || next_cursor == cursor {
+                break;
+            }
+            cursor = next_cursor;
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
 
+        tracing::info!("[VideoSync] 账号 {} 拉取到 {} 个作品", account_id, videos.len());
+        Ok(videos)
+    }
+
+    /// 查询账号参数并构造带抖音平台数据（params / third_id / 音乐 / 位置等）的发布请求
+    fn build_platform_request(&self, request: &CorePublishRequest) -> Result<CorePublishRequest, PlatformError> {
         // 检查 db_manager 是否可用
         if self.db_manager.is_none() {
             tracing::error!("[Publish] db_manager 未初始化，无法获取账号参数");
@@ -161,13 +370,6 @@ impl Platform for DouyinPlatform {
             }
         }
 
-        // 使用发布策略（带进度跟踪）
-        let strategy = match &request.progress_info {
-            Some((task_id, detail_id, account_id, app_handle)) => {
-                DouyinPublishStrategy::with_progress(task_id, detail_id, account_id, app_handle)
-            }
-            None => DouyinPublishStrategy::new(),
-        };
         tracing::info!("[Publish] 开始调用发布策略，third_id前20字符: {}...", &third_id[..third_id.len().min(20)]);
 
         // 构造带有平台数据的请求
@@ -175,6 +377,7 @@ impl Platform for DouyinPlatform {
             account_id: request.account_id.clone(),
             video_path: request.video_path.clone(),
             cover_path: request.cover_path.clone(),
+            image_paths: request.image_paths.clone(),
             title: request.title.clone(),
             description: request.description.clone(),
             hashtags: request.hashtags.clone(),
@@ -190,7 +393,176 @@ impl Platform for DouyinPlatform {
             extra_info: request.extra_info.clone(),
             platform_data: Some(platform_data),
             progress_info: None,
+            cancel_token: request.cancel_token.clone(),
+            publish_permit: request.publish_permit.clone(),
+            publish_mode: request.publish_mode,
+        };
+
+
+        Ok(platform_request)
+    }
+
+    /// 发布预演：解析话题、构造文案和发布参数，但不上传视频、不发布
+    pub async fn preview_publish(&self, request: CorePublishRequest) -> Result<PublishPreview, PlatformError> {
+        let platform_request = self.build_platform_request(&request)?;
+        let strategy = match &self.db_manager {
+            Some(db_manager) => DouyinPublishStrategy::new().with_hashtag_cache(db_manager.clone()),
+            None => DouyinPublishStrategy::new(),
+        };
+        Ok(strategy.preview(platform_request).await)
+    }
+}
+
+
+/// 作品列表每页数量
+const WORK_LIST_PAGE_SIZE: i64 = 20;
+
+/// 位置搜索返回的数量
+const POI_SEARCH_COUNT: i64 = 20;
+
+/// 音乐搜索返回的数量
+const MUSIC_SEARCH_COUNT: i64 = 20;
+
+/// 解析音乐搜索结果中的单首音乐
+fn parse_music(music: &serde_json::Value) -> Option<MusicSearchItem> {
+    // 音乐ID超出 JS 安全整数范围，优先取字符串形式的 id_str
+    let music_id = music.get("id_str")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .or_else(|| music.get("id").and_then(|v| v.as_i64()).map(|n| n.to_string()))
+        .filter(|id| !id.is_empty())?;
+    let text = |key: &str| music.get(key)
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let first_url = |key: &str| music.get(key)
+        .and_then(|v| v.get("url_list"))
+        .and_then(|v| v.as_array())
+        .and_then(|urls| urls.first())
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    Some(MusicSearchItem {
+        music_id,
+        title: text("title"),
+        author: text("author"),
+        duration: music.get("duration").and_then(|v| v.as_i64()).unwrap_or(0),
+        cover_url: first_url("cover_thumb"),
+        play_url: first_url("play_url"),
+    })
+}
+
+/// 解析作品列表中的单个作品
+fn parse_work(item: &serde_json::Value, account_id: &str, now: &str) -> Option<RemoteVideo> {
+    // 作品ID和数值字段有时以字符串返回
+    let item_id = item.get("aweme_id").and_then(|v| v.as_str().map(|s| s.to_string()).or_else(|| v.as_i64().map(|n| n.to_string())))
+        .filter(|id| !id.is_empty())?;
+    let statistics = item.get("statistics");
+    let count = |key: &str| statistics.and_then(|s| s.get(key))
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(0);
+    let create_time = item.get("create_time")
+        .and_then(|v| v.as_i64())
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+        .map(|dt| dt.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+    let cover_url = item.get("video")
+        .and_then(|v| v.get("cover"))
+        .and_then(|v| v.get("url_list"))
+        .and_then(|v| v.as_array())
+        .and_then(|v| v.first())
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let share_url = item.get("share_url")
+        .or_else(|| item.get("share_info").and_then(|v| v.get("share_url")))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| format!("https://www.douyin.com/video/{}", item_id));
+
+    Some(RemoteVideo {
+        id: uuid::Uuid::new_v4().to_string(),
+        account_id: account_id.to_string(),
+        title: item.get("desc").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        cover_url,
+        share_url,
+        create_time,
+        plays: count("play_count"),
+        likes: count("digg_count"),
+        comments: count("comment_count"),
+        favorites: count("collect_count"),
+        shares: count("share_count"),
+        detail_id: None,
+        synced_at: now.to_string(),
+        item_id,
+    })
+}
+
+impl Default for DouyinPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Platform for DouyinPlatform {
+    fn platform_type(&self) -> PlatformType {
+        PlatformType::Douyin
+    }
+
+    fn platform_name(&self) -> String {
+        "抖音".to_string()
+    }
+
+    async fn authenticate_account(&self) -> Result<UserAccount, PlatformError> {
+        Err(PlatformError::AuthenticationFailed(
+            "浏览器认证需要使用 start_browser_auth 命令".to_string(),
+        ))
+    }
+
+    async fn refresh_credentials(&self, _account_id: &str) -> Result<UserAccount, PlatformError> {
+        Err(PlatformError::AuthenticationFailed(
+            "请重新进行浏览器认证".to_string(),
+        ))
+    }
+
+    async fn verify_credentials(&self, params: &str) -> Result<(), PlatformError> {
+        use self::account_params::AccountParams;
+        use self::douyin_client::DouyinClient;
+
+        let params = AccountParams::from_json(params);
+        let client = DouyinClient::new(
+            params.get_cookie(),
+            params.get_user_agent(),
+            params.get_third_id(),
+            params.get_local_data(),
+        );
+
+        client.get_user_info().await
+            .map(|_| ())
+            .map_err(PlatformError::InvalidCredentials)
+    }
+
+    async fn publish_video(&self, request: CorePublishRequest) -> Result<crate::core::PublishResult, PlatformError> {
+        tracing::info!("[Publish] 开始抖音发布流程，账号ID: {}", request.account_id);
+        tracing::info!("[Publish] 视频路径: {:?}", request.video_path);
+        tracing::info!("[Publish] 视频标题: {}", request.title);
+
+        let platform_request = self.build_platform_request(&request)?;
+        let db_manager = self.db_manager.as_ref().unwrap();
+
+        // 使用发布策略（带进度跟踪）
+        let strategy = match &request.progress_info {
+            Some((task_id, detail_id, account_id, app_handle)) => {
+                DouyinPublishStrategy::with_progress(task_id, detail_id, account_id, app_handle)
+            }
+            None => DouyinPublishStrategy::new(),
         };
+        let strategy = strategy
+            .with_hashtag_cache(db_manager.clone())
+            .with_checkpoints(db_manager.clone());
 
         let result = strategy.publish(platform_request).await;
 
@@ -207,10 +579,39 @@ impl Platform for DouyinPlatform {
         ))
     }
 
-    async fn get_account_stats(&self, _account_id: &str) -> Result<crate::core::PublicationStats, PlatformError> {
-        Err(PlatformError::PublicationFailed(
-            "暂不支持获取账号统计".to_string(),
-        ))
+    async fn get_account_stats(&self, account_id: &str) -> Result<crate::core::AccountProfileStats, PlatformError> {
+        use self::account_params::AccountParams;
+        use self::douyin_client::DouyinClient;
+
+        let db_manager = self.db_manager.as_ref().ok_or_else(|| {
+            PlatformError::InvalidInput("平台未配置数据库连接".to_string())
+        })?;
+
+        let account = db_manager.get_account(account_id)
+            .map_err(|e| PlatformError::StorageError(format!("查询账号失败: {:?}", e)))?
+            .ok_or_else(|| PlatformError::AccountNotFound(format!("账号不存在: {}", account_id)))?;
+
+        let params = AccountParams::from_json(&account.params);
+        let client = DouyinClient::new(
+            params.get_cookie(),
+            params.get_user_agent(),
+            params.get_third_id(),
+            params.get_local_data(),
+        );
+
+        let user = client.get_user_info().await
+            .map_err(PlatformError::InvalidCredentials)?;
+        // 数值字段有时以字符串返回
+        let count = |key: &str| user.get(key)
+            .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+            .unwrap_or(0);
+
+        Ok(crate::core::AccountProfileStats {
+            followers: count("follower_count"),
+            following: count("following_count"),
+            total_likes: count("total_favorited"),
+            video_count: count("aweme_count"),
+        })
     }
 
     fn get_credentials_from_params(&self, params: &str) -> Result<crate::core::PlatformCredentials, PlatformError> {
@@ -286,14 +687,13 @@ impl CommentExtractor for DouyinPlatform {
 
         // 保存到数据库
         if !result.comments.is_empty() {
-            if let Err(e) = db_manager.save_comments_batch(&result.comments) {
+            let inserted = db_manager.save_comments_batch(&result.comments).map_err(|e| {
                 tracing::error!("[Comment] 保存评论失败: {:?}", e);
-                return Err(PlatformError::StorageError(
-                    format!("保存评论失败: {:?}", e),
-                ));
-            }
+                PlatformError::StorageError(format!("保存评论失败: {:?}", e))
+            })?;
             tracing::info!(
-                "[Comment] 成功保存 {} 条评论到数据库",
+                "[Comment] 成功保存 {} 条新评论到数据库（本页 {} 条）",
+                inserted,
                 result.comments.len()
             );
         }
@@ -301,3 +701,36 @@ impl CommentExtractor for DouyinPlatform {
         Ok(result)
     }
 }
+
+#[async_trait::async_trait]
+impl MessageExtractor for DouyinPlatform {
+    /// 提取创作者收件箱私信并保存（已存在的消息不重复保存）
+    async fn extract_messages(&self, account_id: &str, max_count: i64) -> Result<MessageExtractResult, PlatformError> {
+        let db_manager = self.db_manager.as_ref().ok_or_else(|| {
+            PlatformError::InvalidInput("平台未配置数据库连接".to_string())
+        })?;
+
+        let account = db_manager.get_account(account_id)
+            .map_err(|e| PlatformError::StorageError(format!("查询账号失败: {:?}", e)))?
+            .ok_or_else(|| PlatformError::AccountNotFound(format!("账号不存在: {}", account_id)))?;
+
+        let mut result = DouyinMessageExtractor::from_params(&account.params)?
+            .extract(max_count)
+            .await?;
+
+        for message in &mut result.messages {
+            message.account_id = account_id.to_string();
+        }
+
+        if !result.messages.is_empty() {
+            let inserted = db_manager.save_messages(&result.messages).map_err(|e| {
+                tracing::error!("[Message] 保存私信失败: {:?}", e);
+                PlatformError::StorageError(format!("保存私信失败: {:?}", e))
+            })?;
+            tracing::info!("[Message] 成功保存 {} 条新私信（本次 {} 条）", inserted, result.messages.len());
+            result.new_messages = inserted as i64;
+        }
+
+        Ok(result)
+    }
+}
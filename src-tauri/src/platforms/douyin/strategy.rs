@@ -12,7 +12,10 @@
 //! 6. **获取CSRF Token** - 从响应头获取CSRF Token
 //! 7. **处理文案和话题** - 处理标题、描述、话题标签
 //! 8. **构建发布数据** - 组装发布请求数据
-//! 9. **发布视频** - 调用发布接口
+//! 9. **发布视频** - 调用发布接口（要求验证码 / 安全验证时暂停，用户完成验证后重新提交）
+//!
+//! 请求中带有 `image_paths` 时发布图文作品：步骤4改为逐张上传图片（ImageX），
+//! 步骤8以 `media_type=2` 和 `images` 字段代替视频ID，其余步骤相同
 //!
 //! # 使用示例
 //!
@@ -32,25 +35,110 @@
 //!
 //! 本模块完全对应Java中的 `DouyinPublishStrategy.java`
 
-use crate::core::{PlatformError, PublishResult, PublishRequest as CorePublishRequest, ProgressStatus, PublishProgressEvent};
-use crate::platforms::traits::PublishStrategy;
+use crate::core::{PlatformError, PublishErrorCode, PublishResult, PublishRequest as CorePublishRequest, ProgressStatus, PublishProgressEvent, emit_event, PublishPreview, PreviewHashtag, PublishCancelToken, PublishMode, PublishCaptchaEvent};
+use crate::platforms::traits::{ModuleChangelogEntry, PublishStrategy};
 use crate::platforms::douyin::account_params::AccountParams;
-use crate::platforms::douyin::douyin_client::DouyinClient;
+use crate::platforms::douyin::douyin_client::{DouyinClient, douyin_error_code, is_risk_control_error, DRAFT_CREATE_PATH, VERIFY_PAGE_URL};
 use crate::platforms::douyin::utils::{
-    calculate_timing, format_poi_anchor_content, generate_creation_id, get_string_length, strip_html_tags,
-    to_json_string,
+    calculate_timing, extract_mentions, format_poi_anchor_content, generate_creation_id, get_string_length,
+    strip_html_tags, to_json_string,
 };
+use crate::platforms::douyin::image_uploader::{ImageUploader, UploadedImage, MAX_POST_IMAGES};
 use crate::platforms::douyin::video_uploader::VideoUploader;
+use crate::storage::{DatabaseManager, PublishCheckpoint};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::path::PathBuf;
-use tauri::Emitter;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use chrono::Utc;
 
 /// 平台类型标识
 /// 1 = 抖音
 pub const PLATFORM_TYPE_DOUYIN: i64 = 1;
 
+/// 策略模块版本（抖音接口调整时递增，并在 CHANGELOG 中记录）
+pub const STRATEGY_VERSION: &str = "1.9.0";
+
+/// 策略模块变更记录（新版本在前）
+pub const CHANGELOG: &[ModuleChangelogEntry] = &[
+    ModuleChangelogEntry {
+        version: "1.9.0",
+        notes: "记录发布断点（已上传的视频ID、封面、BD 凭证），中断后再次发布时跳过已完成的上传步骤",
+    },
+    ModuleChangelogEntry {
+        version: "1.8.0",
+        notes: "发布 / 保存草稿遇到风控验证时暂停并发送 publish-captcha-required 事件，用户完成验证后重新提交",
+    },
+    ModuleChangelogEntry {
+        version: "1.7.1",
+        notes: "识别风控验证响应（验证码、安全验证），错误信息标注触发风控，供账号风险监控统计",
+    },
+    ModuleChangelogEntry {
+        version: "1.7.0",
+        notes: "支持文案中的 @ 提及：按昵称搜索用户，写入 text_extra 和 mentions",
+    },
+    ModuleChangelogEntry {
+        version: "1.6.0",
+        notes: "支持保存为平台草稿：草稿模式下上传完成后调用 draft/create_v2，不直接发布",
+    },
+    ModuleChangelogEntry {
+        version: "1.5.0",
+        notes: "支持发布图文作品：图片逐张走 ImageX 上传，create_v2 以 media_type=2 和 images 字段发布",
+    },
+    ModuleChangelogEntry {
+        version: "1.4.0",
+        notes: "话题ID优先读取本地话题库缓存，未缓存时才调用 search_challenge_sug，解析结果写回缓存",
+    },
+    ModuleChangelogEntry {
+        version: "1.3.0",
+        notes: "分片上传失败时指数退避重试，任一分片最终失败即中止；中断的上传可从断点续传",
+    },
+    ModuleChangelogEntry {
+        version: "1.2.0",
+        notes: "支持上传自定义封面（ImageX），封面 URI 写入 poster 字段",
+    },
+    ModuleChangelogEntry {
+        version: "1.1.0",
+        notes: "超时、5xx 等临时性错误在同一次发布内自动重试",
+    },
+    ModuleChangelogEntry {
+        version: "1.0.0",
+        notes: "9 步发布流程：V4 签名上传、BD 凭证、CSRF Token、话题处理、create_v2 发布",
+    },
+];
+
+/// 搜索 @ 提及用户时返回的数量
+const MENTION_SEARCH_COUNT: i64 = 10;
+
+/// 一次发布中最多等待用户完成验证的次数（验证后仍要求验证则按失败处理）
+const MAX_VERIFICATIONS: u32 = 2;
+
+/// 步骤6获取 CSRF Token 的接口路径（Token 按接口路径签发）
+fn csrf_path(mode: PublishMode) -> &'static str {
+    match mode {
+        PublishMode::Publish => "/web/api/media/aweme/create_v2/",
+        PublishMode::Draft => DRAFT_CREATE_PATH,
+    }
+}
+
+/// 按抖音错误码确定步骤错误的类型：登录离线归为鉴权失败、网络异常归为网络错误，
+/// 其余保持步骤默认的错误类型，前端据此给出对应的错误码
+fn classify_step_error(message: String, default: fn(String) -> PlatformError) -> PlatformError {
+    match douyin_error_code(&message) {
+        PublishErrorCode::CookieExpired => PlatformError::AuthenticationFailed(message),
+        PublishErrorCode::Network => PlatformError::NetworkError(message),
+        _ => default(message),
+    }
+}
+
+/// 发布的作品内容
+enum PublishMedia<'a> {
+    /// 视频：VOD 视频ID和可选的自定义封面URI
+    Video { video_id: &'a str, poster_uri: Option<&'a str> },
+    /// 图文：按展示顺序排列的图片
+    Images(&'a [UploadedImage]),
+}
+
 /// 抖音发布配置
 ///
 /// 从 platform_data JSON 中解析抖音特定的发布配置
@@ -110,6 +198,10 @@ pub struct DouyinPublishStrategy {
     account_id: String,
     /// AppHandle 用于发送进度事件
     app_handle: Option<tauri::AppHandle>,
+    /// 话题ID缓存（话题库 hashtags 表）
+    hashtag_cache: Option<Arc<DatabaseManager>>,
+    /// 发布断点（publish_checkpoints 表），有详情ID时才记录
+    checkpoints: Option<Arc<DatabaseManager>>,
 }
 
 impl DouyinPublishStrategy {
@@ -120,6 +212,8 @@ impl DouyinPublishStrategy {
             detail_id: String::new(),
             account_id: String::new(),
             app_handle: None,
+            hashtag_cache: None,
+            checkpoints: None,
         }
     }
 
@@ -135,9 +229,23 @@ impl DouyinPublishStrategy {
             detail_id: detail_id.to_string(),
             account_id: account_id.to_string(),
             app_handle: Some(app_handle.clone()),
+            hashtag_cache: None,
+            checkpoints: None,
         }
     }
 
+    /// 使用话题库缓存话题ID，避免重复调用 search_challenge_sug
+    pub fn with_hashtag_cache(mut self, db_manager: Arc<DatabaseManager>) -> Self {
+        self.hashtag_cache = Some(db_manager);
+        self
+    }
+
+    /// 记录发布断点：步骤4-9 之间中断后再次发布该账号时复用已上传的视频和 BD 凭证
+    pub fn with_checkpoints(mut self, db_manager: Arc<DatabaseManager>) -> Self {
+        self.checkpoints = Some(db_manager);
+        self
+    }
+
     /// 发送进度事件
     fn emit_progress(&self, status: &ProgressStatus, message: &str, progress: i32) {
         if let Some(handle) = &self.app_handle {
@@ -151,7 +259,7 @@ impl DouyinPublishStrategy {
                 progress,
                 timestamp: Utc::now().timestamp_millis(),
             };
-            if let Err(e) = handle.emit("publish-progress", &event) {
+            if let Err(e) = emit_event(handle, &event) {
                 tracing::warn!("[Progress] Failed to emit progress event: {}", e);
             } else {
                 tracing::info!("[Progress] ✅ Emitted: status={:?}, progress={}%, message={}", status, progress, message);
@@ -191,6 +299,42 @@ impl PublishStrategy for DouyinPublishStrategy {
     ///
     /// 如果发布失败，返回错误信息
     async fn publish(&self, request: CorePublishRequest) -> Result<PublishResult, PlatformError> {
+        // 临时性错误的重试由发布流水线统一处理（publish_with_retry），策略内只发布一次
+        let result = self.publish_attempt(&request).await;
+        // 发布被平台拒绝时删除断点，下次重新上传；网络中断、取消、凭证过期和风控验证保留断点以便续传
+        if let Err(e @ PlatformError::PublicationFailed(msg)) = &result {
+            if !e.is_transient() && !is_risk_control_error(msg) {
+                self.clear_checkpoint();
+            }
+        }
+        result
+    }
+
+    /// 获取平台类型
+    fn get_platform_type(&self) -> i64 {
+        PLATFORM_TYPE_DOUYIN
+    }
+
+    /// 获取策略模块版本
+    fn get_version(&self) -> &'static str {
+        STRATEGY_VERSION
+    }
+
+    /// 获取策略模块变更记录
+    fn get_changelog(&self) -> &'static [ModuleChangelogEntry] {
+        CHANGELOG
+    }
+}
+
+impl DouyinPublishStrategy {
+    /// 执行一次完整的发布流程（步骤1-9）
+    async fn publish_attempt(&self, request: &CorePublishRequest) -> Result<PublishResult, PlatformError> {
+        request.check_cancelled()?;
+
+        if !request.image_paths.is_empty() {
+            return self.publish_image_attempt(request).await;
+        }
+
         // ========== 步骤1: 参数校验 ==========
         tracing::info!("[Publish] ====== 步骤1: 参数校验 ======");
         self.emit_progress(&ProgressStatus::Starting, "开始发布", 0);
@@ -203,39 +347,10 @@ impl PublishStrategy for DouyinPublishStrategy {
         let platform_data = request.platform_data.as_ref()
             .ok_or_else(|| PlatformError::InvalidInput("platform_data不能为空".to_string()))?;
 
-        let params = platform_data.get("params")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| PlatformError::InvalidInput("params不能为空".to_string()))?
-            .to_string();
-
-        // ========== 步骤2: 解析抖音账号参数 ==========
-        tracing::info!("[Publish] ====== 步骤2: 解析抖音账号参数 ======");
-
-        let account_params = AccountParams::from_json(&params);
-
-        let third_id = platform_data.get("third_id")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| account_params.get_third_id());
-
-        if third_id.is_empty() {
-            tracing::error!("[Publish] third_id为空，无法发布视频");
-            tracing::error!("[Publish] 解析AccountParams结果: third_id={:?}, cookie={}...",
-                account_params.third_id,
-                account_params.get_cookie().len()
-            );
-            return Err(PlatformError::InvalidInput("thirdId不能为空".to_string()));
-        }
-
-        let cookie = account_params.get_cookie();
-        let user_agent = account_params.get_user_agent();
-        let local_data = account_params.get_local_data();
-
-
-        // ========== 步骤3: 创建客户端 ==========
-        tracing::info!("[Publish] ====== 步骤3: 创建客户端 ======");
+        // ========== 步骤2-3: 解析账号参数、创建客户端 ==========
+        let mut client = self.create_client(platform_data)?;
 
-        let mut client = DouyinClient::new(cookie, user_agent, third_id.clone(), local_data);
+        request.check_cancelled()?;
 
         // ========== 步骤4: 申请上传地址和凭证 (V4签名) ==========
         tracing::info!("[Publish] ====== 步骤4: 申请上传地址和凭证 ======");
@@ -249,32 +364,88 @@ impl PublishStrategy for DouyinPublishStrategy {
             )));
         }
 
-        // 上传视频
-        let video_id = self.upload_video(&mut client, &request.video_path).await?;
+        // 有断点时复用已上传的视频和封面（视频文件未变化）
+        let (video_id, poster_uri, checkpoint) = match self.load_checkpoint(&request.video_path) {
+            Some(checkpoint) => {
+                tracing::info!("[Publish] 断点续传：复用已上传的视频, videoId: {}", checkpoint.video_id);
+                self.emit_progress(&ProgressStatus::UploadingVideo, "复用已上传的视频", 55);
+                (checkpoint.video_id.clone(), checkpoint.poster_uri.clone(), Some(checkpoint))
+            }
+            None => {
+                // 上传视频
+                let (video_id, uploader) = self.upload_video(&mut client, &request.video_path, request.cancel_token.as_ref()).await?;
+
+                tracing::info!("[Publish] 视频上传成功, videoId: {}", video_id);
+
+                // 上传自定义封面；失败时不中断发布，由抖音自动生成封面
+                let poster_uri = match request.cover_path.as_ref().filter(|p| !p.as_os_str().is_empty()) {
+                    Some(cover_path) => {
+                        self.emit_progress(&ProgressStatus::UploadingVideo, "上传封面中...", 55);
+                        match uploader.upload_image(&cover_path.to_string_lossy()).await {
+                            Ok(uri) => Some(uri),
+                            Err(e) => {
+                                tracing::warn!("[Publish] 封面上传失败，使用抖音自动封面: {}", e);
+                                None
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                let video_size = std::fs::metadata(&request.video_path).map(|m| m.len()).unwrap_or(0);
+                let checkpoint = self.save_checkpoint(PublishCheckpoint::video_uploaded(
+                    &self.detail_id,
+                    &request.video_path.to_string_lossy(),
+                    video_size,
+                    &video_id,
+                    poster_uri.as_deref(),
+                ));
+                (video_id, poster_uri, checkpoint)
+            }
+        };
 
-        tracing::info!("[Publish] 视频上传成功, videoId: {}", video_id);
+        request.check_cancelled()?;
 
         // ========== 步骤5: 获取BD凭证 ==========
         tracing::info!("[Publish] ====== 步骤5: 获取BD凭证 ======");
         self.emit_progress(&ProgressStatus::GettingTicket, "获取发布凭证...", 60);
 
-        let bd_ticket = client
-            .get_header_ticket_key("video")
-            .await
-            .map_err(|e| PlatformError::VideoUploadFailed(e))?;
+        let fresh_ticket = checkpoint.as_ref()
+            .and_then(|c| c.fresh_bd_ticket(chrono::Local::now().naive_local()))
+            .cloned();
+        let bd_ticket = match fresh_ticket {
+            Some(bd_ticket) => {
+                tracing::info!("[Publish] 断点续传：复用BD凭证");
+                bd_ticket
+            }
+            None => {
+                let bd_ticket = client
+                    .get_header_ticket_key("video")
+                    .await
+                    .map_err(|e| classify_step_error(e, PlatformError::VideoUploadFailed))?;
+                if let Some(checkpoint) = checkpoint {
+                    self.save_checkpoint(checkpoint.with_bd_ticket(&bd_ticket));
+                }
+                bd_ticket
+            }
+        };
 
         tracing::info!("[Publish] BD凭证获取成功, 包含 {} 个字段", bd_ticket.len());
 
+        request.check_cancelled()?;
+
         // ========== 步骤6: 获取CSRF Token ==========
         tracing::info!("[Publish] ====== 步骤6: 获取CSRF Token ======");
 
         let csrf_token = client
-            .get_csrf_token("/web/api/media/aweme/create_v2/")
+            .get_csrf_token(csrf_path(request.publish_mode))
             .await
             .map_err(|e| PlatformError::AuthenticationFailed(e))?;
 
         tracing::info!("[Publish] CSRF Token获取成功:{}", &csrf_token);
 
+        request.check_cancelled()?;
+
         // ========== 步骤7: 处理文案和话题标签 ==========
         tracing::info!("[Publish] ====== 步骤7: 处理文案和话题标签 ======");
 
@@ -289,27 +460,34 @@ impl PublishStrategy for DouyinPublishStrategy {
         tracing::info!("[Publish] 文案处理完成, 标题长度: {}, 话题数: {}",
             caption_result.item_title.len(), caption_result.challenges.len());
 
+        request.check_cancelled()?;
+
         // ========== 步骤8: 构建发布数据 ==========
         tracing::info!("[Publish] ====== 步骤8: 构建发布数据 ======");
 
         // 获取抖音特定配置
         let douyin_config = DouyinPublishConfig::from_platform_data(platform_data);
 
-        let publish_data = self.build_publish_data(caption_result, &video_id, &request, &douyin_config);
+        let media = PublishMedia::Video { video_id: &video_id, poster_uri: poster_uri.as_deref() };
+        let publish_data = self.build_publish_data(caption_result, media, request, &douyin_config);
+
+        request.check_cancelled()?;
+
+        if request.publish_mode == PublishMode::Draft {
+            return self.save_draft(&mut client, request, publish_data, csrf_token, bd_ticket, douyin_config.record_id).await;
+        }
 
         // ========== 步骤9: 发布视频 ==========
         tracing::info!("[Publish] ====== 步骤9: 发布视频到抖音 ======");
         self.emit_progress(&ProgressStatus::Publishing, "发布中...", 80);
 
-        let post_result = client
-            .get_public_video_v2(publish_data, Some(csrf_token), Some(bd_ticket))
-            .await
-            .map_err(|e| PlatformError::PublicationFailed(e))?;
+        let post_result = self.submit(&mut client, request, publish_data, csrf_token, bd_ticket).await?;
 
         // 构建返回结果
         let item_id = self.get_item_id_from_result(&post_result);
 
         tracing::info!("抖音视频发布成功, itemId: {}", item_id);
+        self.clear_checkpoint();
 
         self.emit_progress(&ProgressStatus::Completed, "发布成功", 100);
 
@@ -321,87 +499,414 @@ impl PublishStrategy for DouyinPublishStrategy {
         })
     }
 
-    /// 获取平台类型
-    fn get_platform_type(&self) -> i64 {
-        PLATFORM_TYPE_DOUYIN
-    }
-}
+    /// 执行一次图文作品发布：步骤4上传图片，步骤8以 images 代替视频ID
+    async fn publish_image_attempt(&self, request: &CorePublishRequest) -> Result<PublishResult, PlatformError> {
+        // ========== 步骤1: 参数校验 ==========
+        tracing::info!("[Publish] ====== 步骤1: 参数校验（图文，{}张图片） ======", request.image_paths.len());
+        self.emit_progress(&ProgressStatus::Starting, "开始发布", 0);
 
-impl DouyinPublishStrategy {
-    /// 上传视频（包含步骤4-6）
-    ///
-    /// 步骤4: 获取上传配置
-    /// 步骤5: 申请上传地址和凭证（V4签名）
-    /// 步骤6: 上传视频内容
-    /// 步骤7: 提交上传完成（V4签名）
-    async fn upload_video(&self, client: &mut DouyinClient, video_path: &PathBuf) -> Result<String, PlatformError> {
-        // 步骤4: 获取上传配置
-        tracing::info!("[Upload] ====== 步骤4: 获取上传配置 ======");
-        let upload_options = client
-            .get_upload_options()
+        if request.image_paths.len() > MAX_POST_IMAGES {
+            return Err(PlatformError::InvalidInput(format!("图片数量超过上限: 最多{}张", MAX_POST_IMAGES)));
+        }
+        if let Some(missing) = request.image_paths.iter().find(|p| !p.exists()) {
+            return Err(PlatformError::InvalidInput(format!("图片文件不存在: {}", missing.display())));
+        }
+
+        let platform_data = request.platform_data.as_ref()
+            .ok_or_else(|| PlatformError::InvalidInput("platform_data不能为空".to_string()))?;
+
+        // ========== 步骤2-3: 解析账号参数、创建客户端 ==========
+        let mut client = self.create_client(platform_data)?;
+
+        request.check_cancelled()?;
+
+        // ========== 步骤4: 上传图片 ==========
+        tracing::info!("[Publish] ====== 步骤4: 上传图片 ======");
+        self.emit_progress(&ProgressStatus::UploadingVideo, "上传图片中...", 10);
+
+        // 上传阶段占总进度的 10% ~ 55%
+        let uploader = ImageUploader::new(self.create_uploader(&mut client).await?)
+            .with_cancel_token(request.cancel_token.clone());
+        let images = uploader
+            .upload_images(&request.image_paths, |uploaded, total| {
+                self.emit_progress(
+                    &ProgressStatus::UploadingVideo,
+                    &format!("上传图片中... {}/{}", uploaded, total),
+                    10 + (uploaded * 45 / total) as i32,
+                );
+            })
             .await
-            .map_err(|e| PlatformError::VideoUploadFailed(e))?;
-
-        let auth = upload_options
-            .get("auth")
-            .cloned()
-            .ok_or_else(|| PlatformError::VideoUploadFailed("获取上传配置失败：auth为空".to_string()))?;
-
-        tracing::debug!("[Upload] auth类型: {:?}", auth);
-
-        // 将auth转换为HashMap
-        let upload_auth: HashMap<String, Value> = match auth {
-            Value::Object(map) => {
-                tracing::debug!("[Upload] auth是JSON对象，包含 {} 个字段", map.len());
-                map
-                .iter()
-                .filter(|(_, v)| !v.is_null())
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect()
-            }
-            Value::String(s) => {
-                // 如果是字符串，尝试解析JSON
-                tracing::debug!("[Upload] auth是字符串: {}...", &s[..s.len().min(200)]);
-                match serde_json::from_str(&s) {
-                    Ok(parsed) => {
-                        tracing::debug!("[Upload] auth字符串解析成功");
-                        parsed
-                    }
-                    Err(e) => {
-                        tracing::warn!("[Upload] auth字符串解析失败: {}，使用空HashMap", e);
-                        HashMap::new()
-                    }
+            .map_err(|e| match &request.cancel_token {
+                Some(token) if token.is_cancelled() => PlatformError::Cancelled(e),
+                _ => classify_step_error(e, PlatformError::VideoUploadFailed),
+            })?;
+
+        tracing::info!("[Publish] 图片上传成功, 共{}张", images.len());
+
+        request.check_cancelled()?;
+
+        // ========== 步骤5: 获取BD凭证 ==========
+        tracing::info!("[Publish] ====== 步骤5: 获取BD凭证 ======");
+        self.emit_progress(&ProgressStatus::GettingTicket, "获取发布凭证...", 60);
+
+        let bd_ticket = client
+            .get_header_ticket_key("video")
+            .await
+            .map_err(|e| classify_step_error(e, PlatformError::VideoUploadFailed))?;
+
+        request.check_cancelled()?;
+
+        // ========== 步骤6: 获取CSRF Token ==========
+        tracing::info!("[Publish] ====== 步骤6: 获取CSRF Token ======");
+
+        let csrf_token = client
+            .get_csrf_token(csrf_path(request.publish_mode))
+            .await
+            .map_err(PlatformError::AuthenticationFailed)?;
+
+        request.check_cancelled()?;
+
+        // ========== 步骤7: 处理文案和话题标签 ==========
+        tracing::info!("[Publish] ====== 步骤7: 处理文案和话题标签 ======");
+
+        let description = request.description.as_deref().unwrap_or("");
+        let caption_result = self.process_caption_and_hashtags(
+            &mut client,
+            &request.title,
+            description,
+            &request.hashtags,
+        ).await;
+
+        request.check_cancelled()?;
+
+        // ========== 步骤8: 构建发布数据 ==========
+        tracing::info!("[Publish] ====== 步骤8: 构建发布数据 ======");
+
+        let douyin_config = DouyinPublishConfig::from_platform_data(platform_data);
+        let publish_data = self.build_publish_data(caption_result, PublishMedia::Images(&images), request, &douyin_config);
+
+        request.check_cancelled()?;
+
+        if request.publish_mode == PublishMode::Draft {
+            return self.save_draft(&mut client, request, publish_data, csrf_token, bd_ticket, douyin_config.record_id).await;
+        }
+
+        // ========== 步骤9: 发布图文 ==========
+        tracing::info!("[Publish] ====== 步骤9: 发布图文到抖音 ======");
+        self.emit_progress(&ProgressStatus::Publishing, "发布中...", 80);
+
+        let post_result = self.submit(&mut client, request, publish_data, csrf_token, bd_ticket).await?;
+
+        let item_id = self.get_item_id_from_result(&post_result);
+
+        tracing::info!("抖音图文发布成功, itemId: {}", item_id);
+
+        self.emit_progress(&ProgressStatus::Completed, "发布成功", 100);
+
+        Ok(PublishResult {
+            success: true,
+            publication_id: douyin_config.record_id.unwrap_or_default(),
+            item_id: Some(item_id),
+            error_message: None,
+        })
+    }
+
+    /// 步骤9（草稿模式）：保存到创作者中心草稿箱，作品未发布，没有作品ID
+    async fn save_draft(
+        &self,
+        client: &mut DouyinClient,
+        request: &CorePublishRequest,
+        publish_data: HashMap<String, Value>,
+        csrf_token: String,
+        bd_ticket: HashMap<String, String>,
+        record_id: Option<String>,
+    ) -> Result<PublishResult, PlatformError> {
+        tracing::info!("[Publish] ====== 步骤9: 保存到抖音草稿箱 ======");
+        self.emit_progress(&ProgressStatus::Publishing, "保存草稿中...", 80);
+
+        let result = self.submit(client, request, publish_data, csrf_token, bd_ticket).await?;
+
+        let draft_id = result.get("draft_id")
+            .map(|v| v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string()))
+            .unwrap_or_default();
+        tracing::info!("抖音草稿保存成功, draftId: {}", draft_id);
+        self.clear_checkpoint();
+
+        self.emit_progress(&ProgressStatus::Completed, "已保存到草稿箱", 100);
+
+        Ok(PublishResult {
+            success: true,
+            publication_id: record_id.unwrap_or_default(),
+            item_id: None,
+            error_message: None,
+        })
+    }
+
+    /// 步骤9：提交发布（草稿模式下保存草稿）；平台要求验证码 / 安全验证时暂停，
+    /// 等用户在浏览器中完成验证后重新提交，最多等待 MAX_VERIFICATIONS 次
+    async fn submit(
+        &self,
+        client: &mut DouyinClient,
+        request: &CorePublishRequest,
+        publish_data: HashMap<String, Value>,
+        csrf_token: String,
+        bd_ticket: HashMap<String, String>,
+    ) -> Result<Value, PlatformError> {
+        let mut verifications = 0;
+        loop {
+            let result = match request.publish_mode {
+                PublishMode::Draft => client.save_draft_v2(publish_data.clone(), csrf_token.clone(), bd_ticket.clone()).await,
+                PublishMode::Publish => client.get_public_video_v2(publish_data.clone(), Some(csrf_token.clone()), Some(bd_ticket.clone())).await,
+            };
+            match result {
+                Err(e) if is_risk_control_error(&e) && verifications < MAX_VERIFICATIONS && self.wait_for_verification(request, &e).await => {
+                    verifications += 1;
+                    request.check_cancelled()?;
+                    tracing::info!("[Publish] 用户已完成验证，重新提交（第{}次）", verifications);
+                    self.emit_progress(&ProgressStatus::Publishing, "验证完成，重新提交中...", 80);
                 }
+                result => return result.map_err(|e| classify_step_error(e, PlatformError::PublicationFailed)),
             }
-            _ => {
-                tracing::warn!("[Upload] auth类型未知: {:?}，使用空HashMap", auth);
-                HashMap::new()
+        }
+    }
+
+    /// 暂停发布等待用户完成验证；没有界面（命令行发布）时不等待，直接按原错误失败
+    async fn wait_for_verification(&self, request: &CorePublishRequest, message: &str) -> bool {
+        let Some(app_handle) = &self.app_handle else {
+            return false;
+        };
+
+        self.emit_progress(&ProgressStatus::VerificationRequired, "需要完成安全验证，请在浏览器中处理", 80);
+        let event = PublishCaptchaEvent {
+            task_id: self.task_id.clone(),
+            detail_id: self.detail_id.clone(),
+            account_id: self.account_id.clone(),
+            platform: "douyin".to_string(),
+            verify_url: VERIFY_PAGE_URL.to_string(),
+            message: message.to_string(),
+            timestamp: Utc::now().timestamp_millis(),
+        };
+        crate::core::wait_for_verification(app_handle, event, request.cancel_token.as_ref(), request.publish_permit.as_ref()).await
+    }
+
+    /// 读取本次账号发布的断点；视频文件已变化（路径或大小不同）时不复用
+    fn load_checkpoint(&self, video_path: &Path) -> Option<PublishCheckpoint> {
+        let db_manager = self.checkpoints.as_ref().filter(|_| !self.detail_id.is_empty())?;
+        let checkpoint = match db_manager.get_publish_checkpoint(&self.detail_id) {
+            Ok(checkpoint) => checkpoint?,
+            Err(e) => {
+                tracing::warn!("[Publish] 读取发布断点失败: {}", e);
+                return None;
             }
         };
+        let video_size = std::fs::metadata(video_path).ok()?.len();
+        checkpoint.matches_video(&video_path.to_string_lossy(), video_size).then_some(checkpoint)
+    }
 
-        tracing::debug!("[Upload] upload_auth字段数量: {}", upload_auth.len());
-        // 调试：打印upload_auth中的key
-        tracing::debug!("[Upload] upload_auth keys: {:?}", upload_auth.keys().map(|k| k.as_str()).collect::<Vec<_>>());
+    /// 保存断点；未启用断点（没有数据库或详情ID）时返回 None
+    fn save_checkpoint(&self, checkpoint: PublishCheckpoint) -> Option<PublishCheckpoint> {
+        let db_manager = self.checkpoints.as_ref().filter(|_| !self.detail_id.is_empty())?;
+        if let Err(e) = db_manager.save_publish_checkpoint(&checkpoint) {
+            tracing::warn!("[Publish] 保存发布断点失败: {}", e);
+        }
+        Some(checkpoint)
+    }
 
-        if upload_auth.is_empty() {
-            return Err(PlatformError::VideoUploadFailed(
-                "获取上传配置失败：auth为空".to_string(),
-            ));
+    /// 发布完成后删除断点
+    fn clear_checkpoint(&self) {
+        if let Some(db_manager) = self.checkpoints.as_ref().filter(|_| !self.detail_id.is_empty()) {
+            if let Err(e) = db_manager.delete_publish_checkpoint(&self.detail_id) {
+                tracing::warn!("[Publish] 删除发布断点失败: {}", e);
+            }
         }
+    }
 
-        tracing::info!("[Upload] 上传配置获取成功");
+    /// 解析抖音账号参数并创建客户端（步骤2-3）
+    fn create_client(&self, platform_data: &Value) -> Result<DouyinClient, PlatformError> {
+        let params = platform_data.get("params")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| PlatformError::InvalidInput("params不能为空".to_string()))?;
+
+        // ========== 步骤2: 解析抖音账号参数 ==========
+        tracing::info!("[Publish] ====== 步骤2: 解析抖音账号参数 ======");
+
+        let account_params = AccountParams::from_json(params);
+
+        let third_id = platform_data.get("third_id")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| account_params.get_third_id());
+
+        if third_id.is_empty() {
+            tracing::error!("[Publish] third_id为空，无法发布");
+            tracing::error!("[Publish] 解析AccountParams结果: third_id={:?}, cookie={}...",
+                account_params.third_id,
+                account_params.get_cookie().len()
+            );
+            return Err(PlatformError::InvalidInput("thirdId不能为空".to_string()));
+        }
+
+        let cookie = account_params.get_cookie();
+        let user_agent = account_params.get_user_agent();
+        let local_data = account_params.get_local_data();
+
+        // ========== 步骤3: 创建客户端 ==========
+        tracing::info!("[Publish] ====== 步骤3: 创建客户端 ======");
+
+        Ok(DouyinClient::new(cookie, user_agent, third_id, local_data))
+    }
+
+    /// 发布预演：执行步骤1-3、7-8（参数校验、话题解析、text_extra 构建），跳过上传和发布
+    ///
+    /// 校验错误收集到 `errors` 中而不是提前返回，便于一次看到全部问题
+    pub async fn preview(&self, request: CorePublishRequest) -> PublishPreview {
+        let mut preview = PublishPreview::default();
+
+        // 步骤1: 参数校验
+        if !request.image_paths.is_empty() {
+            if request.image_paths.len() > MAX_POST_IMAGES {
+                preview.errors.push(format!("图片数量超过上限: 最多{}张", MAX_POST_IMAGES));
+            }
+            for path in request.image_paths.iter().filter(|p| !p.exists()) {
+                preview.errors.push(format!("图片文件不存在: {}", path.display()));
+            }
+        } else if request.video_path.as_os_str().is_empty() {
+            preview.errors.push("视频路径不能为空".to_string());
+        } else if !request.video_path.exists() {
+            preview.errors.push(format!("视频文件不存在: {}", request.video_path.display()));
+        }
+        if let Some(cover_path) = request.cover_path.as_ref().filter(|p| !p.as_os_str().is_empty()) {
+            if !cover_path.exists() {
+                preview.errors.push(format!("封面文件不存在，将使用抖音自动封面: {}", cover_path.display()));
+            }
+        }
+
+        let platform_data = request.platform_data.clone().unwrap_or(Value::Null);
+        let params = platform_data.get("params").and_then(|v| v.as_str()).unwrap_or("");
+        if params.is_empty() {
+            preview.errors.push("params不能为空".to_string());
+            return preview;
+        }
+
+        // 步骤2: 解析抖音账号参数
+        let account_params = AccountParams::from_json(params);
+        let third_id = platform_data.get("third_id")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| account_params.get_third_id());
+        if third_id.is_empty() {
+            preview.errors.push("thirdId不能为空".to_string());
+            return preview;
+        }
+
+        // 步骤3: 创建客户端（仅用于话题搜索）
+        let mut client = DouyinClient::new(
+            account_params.get_cookie(),
+            account_params.get_user_agent(),
+            third_id,
+            account_params.get_local_data(),
+        );
+
+        // 步骤7: 处理文案和话题标签
+        let description = request.description.as_deref().unwrap_or("");
+        let caption_result = self.process_caption_and_hashtags(
+            &mut client,
+            &request.title,
+            description,
+            &request.hashtags,
+        ).await;
+
+        if caption_result.item_title.is_empty() {
+            preview.errors.push("标题不能为空".to_string());
+        }
+        preview.hashtags = caption_result.challenges.iter()
+            .map(|c| {
+                let hashtag_id = c.get("hashtag_id").cloned().unwrap_or_default();
+                PreviewHashtag {
+                    name: c.get("hashtag_name").cloned().unwrap_or_default(),
+                    matched: !hashtag_id.is_empty() && hashtag_id != "0",
+                    hashtag_id,
+                }
+            })
+            .collect();
+        for hashtag in preview.hashtags.iter().filter(|h| !h.matched) {
+            preview.errors.push(format!("话题未匹配到抖音话题ID，将以普通文本发布: #{}", hashtag.name));
+        }
+
+        // 步骤8: 构建发布数据（视频ID / 图片留空），取其中的文案字段
+        let douyin_config = DouyinPublishConfig::from_platform_data(&platform_data);
+        let media = if request.image_paths.is_empty() {
+            PublishMedia::Video { video_id: "", poster_uri: None }
+        } else {
+            PublishMedia::Images(&[])
+        };
+        let publish_data = self.build_publish_data(caption_result, media, &request, &douyin_config);
+        let common = publish_data.get("item").and_then(|item| item.get("common")).cloned().unwrap_or(Value::Null);
+        let field = |key: &str| common.get(key).and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+        preview.item_title = field("item_title");
+        preview.caption = field("caption");
+        preview.text = field("text");
+        preview.text_extra = common.get("text_extra")
+            .and_then(|v| v.as_str())
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or(Value::Array(Vec::new()));
+
+        preview
+    }
+
+    /// 上传视频（包含步骤4-6）
+    ///
+    /// 步骤4: 获取上传配置
+    /// 步骤5: 申请上传地址和凭证（V4签名）
+    /// 步骤6: 上传视频内容
+    /// 步骤7: 提交上传完成（V4签名）
+    ///
+    /// 返回视频ID和上传器，上传器可继续用于上传封面
+    async fn upload_video(
+        &self,
+        client: &mut DouyinClient,
+        video_path: &PathBuf,
+        cancel_token: Option<&PublishCancelToken>,
+    ) -> Result<(String, VideoUploader), PlatformError> {
+        let mut uploader = self.create_uploader(client).await?
+            .with_cancel_token(cancel_token.cloned());
 
         // 步骤5-7: 通过上传器完成视频上传（V4签名上传）
         tracing::info!("[Upload] ====== 步骤5-7: V4签名上传视频到VOD ======");
         let video_path_str = video_path.to_string_lossy().to_string();
-        let mut uploader = VideoUploader::new(upload_auth, client.third_id.clone(), client.user_agent.clone());
 
+        // 上传阶段占总进度的 10% ~ 55%
         let video_id = uploader
-            .upload_video(&video_path_str)
+            .upload_video_with_progress(&video_path_str, |uploaded, total| {
+                let percent = (uploaded * 100).checked_div(total).unwrap_or(100) as i32;
+                self.emit_progress(
+                    &ProgressStatus::UploadingVideo,
+                    &format!("上传视频中... {}%", percent),
+                    10 + percent * 45 / 100,
+                );
+            })
             .await
-            .map_err(|e| PlatformError::VideoUploadFailed(e))?;
+            .map_err(|e| match cancel_token {
+                Some(token) if token.is_cancelled() => PlatformError::Cancelled(e),
+                _ => classify_step_error(e, PlatformError::VideoUploadFailed),
+            })?;
 
-        Ok(video_id)
+        Ok((video_id, uploader))
+    }
+
+    /// 获取上传配置并创建上传器（视频和封面共用同一份上传凭证）
+    async fn create_uploader(&self, client: &mut DouyinClient) -> Result<VideoUploader, PlatformError> {
+        // 步骤4: 获取上传配置
+        tracing::info!("[Upload] ====== 步骤4: 获取上传配置 ======");
+        let upload_auth = client
+            .get_upload_auth()
+            .await
+            .map_err(|e| classify_step_error(e, PlatformError::VideoUploadFailed))?;
+
+        tracing::info!("[Upload] 上传配置获取成功");
+
+        Ok(VideoUploader::new(upload_auth, client.third_id.clone(), client.user_agent.clone()))
     }
 
     /// 处理文案和话题标签
@@ -473,13 +978,44 @@ impl DouyinPublishStrategy {
 
         // 计算textExtra
         let text_start = if item_title.is_empty() { 0 } else { item_title.len() + 1 };
-        let text_extra = self.build_text_extra(&challenges, text_start);
+        let mut text_extra = self.build_text_extra(&challenges, text_start);
+
+        // 描述中的 @昵称 解析为提及，找不到对应用户时保留为普通文本
+        let mut mentions: Vec<String> = Vec::new();
+        let mut resolved: HashMap<String, Option<(String, String)>> = HashMap::new();
+        for (start, nickname) in extract_mentions(&desc) {
+            if !resolved.contains_key(&nickname) {
+                let user = self.search_mention_user(client, &nickname).await;
+                resolved.insert(nickname.clone(), user);
+            }
+            let Some((user_id, sec_uid)) = resolved.get(&nickname).cloned().flatten() else {
+                continue;
+            };
+
+            let caption_end = start + get_string_length(&format!("@{}", nickname));
+            let mut extra: HashMap<String, Value> = HashMap::new();
+            extra.insert("start".to_string(), Value::Number(serde_json::Number::from(text_start + start)));
+            extra.insert("end".to_string(), Value::Number(serde_json::Number::from(text_start + caption_end)));
+            extra.insert("type".to_string(), Value::Number(serde_json::Number::from(0)));
+            extra.insert("hashtag_name".to_string(), Value::String(String::new()));
+            extra.insert("hashtag_id".to_string(), Value::String(String::new()));
+            extra.insert("user_id".to_string(), Value::String(user_id.clone()));
+            extra.insert("sec_uid".to_string(), Value::String(sec_uid));
+            extra.insert("caption_start".to_string(), Value::Number(serde_json::Number::from(start)));
+            extra.insert("caption_end".to_string(), Value::Number(serde_json::Number::from(caption_end)));
+            text_extra.push(extra);
+
+            if !mentions.contains(&user_id) {
+                mentions.push(user_id);
+            }
+        }
 
         result.item_title = item_title;
         result.caption = caption_text;
         result.text = text;
         result.challenges = challenges;
         result.text_extra = text_extra;
+        result.mentions = mentions;
 
         result
     }
@@ -503,8 +1039,18 @@ impl DouyinPublishStrategy {
             .collect()
     }
 
-    /// 搜索话题
+    /// 搜索话题（优先使用话题库中缓存的话题ID）
     async fn search_challenge(&self, client: &mut DouyinClient, keyword: &str) -> Result<HashMap<String, String>, String> {
+        let cached = self.hashtag_cache.as_ref()
+            .and_then(|db| db.get_douyin_hashtag_id(keyword).ok().flatten());
+        if let Some(hashtag_id) = cached {
+            tracing::debug!("[Publish] 话题ID命中缓存: {} -> {}", keyword, hashtag_id);
+            let mut challenge = HashMap::new();
+            challenge.insert("hashtag_name".to_string(), keyword.to_string());
+            challenge.insert("hashtag_id".to_string(), hashtag_id);
+            return Ok(challenge);
+        }
+
         let response = client.search_challenge_sug(keyword).await;
 
         let empty_vec: Vec<Value> = Vec::new();
@@ -524,6 +1070,12 @@ impl DouyinPublishStrategy {
             }
         }
 
+        if let Some(db) = self.hashtag_cache.as_ref().filter(|_| !hashtag_id.is_empty() && hashtag_id != "0") {
+            if let Err(e) = db.cache_douyin_hashtag_id(keyword, &hashtag_id) {
+                tracing::warn!("[Publish] 缓存话题ID失败: {}: {}", keyword, e);
+            }
+        }
+
         let mut challenge = HashMap::new();
         challenge.insert("hashtag_name".to_string(), keyword.to_string());
         challenge.insert("hashtag_id".to_string(), hashtag_id);
@@ -531,6 +1083,34 @@ impl DouyinPublishStrategy {
         Ok(challenge)
     }
 
+    /// 按昵称搜索 @ 提及的用户，只接受昵称完全一致的结果
+    ///
+    /// # 返回
+    ///
+    /// `(user_id, sec_uid)`，搜索失败或没有同名用户时返回 None
+    async fn search_mention_user(&self, client: &DouyinClient, nickname: &str) -> Option<(String, String)> {
+        let response = match client.search_user(nickname, MENTION_SEARCH_COUNT).await {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::warn!("[Publish] 搜索 @ 用户失败: {}: {}", nickname, e);
+                return None;
+            }
+        };
+
+        let user = response.get("user_list")
+            .and_then(|v| v.as_array())?
+            .iter()
+            .map(|item| item.get("user_info").unwrap_or(item))
+            .find(|user| user.get("nickname").and_then(|v| v.as_str()) == Some(nickname))?;
+
+        let user_id = user.get("uid")
+            .and_then(|v| v.as_str().map(|s| s.to_string()).or_else(|| v.as_i64().map(|n| n.to_string())))
+            .filter(|id| !id.is_empty())?;
+        let sec_uid = user.get("sec_uid").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+
+        Some((user_id, sec_uid))
+    }
+
     /// 构建话题位置信息
     ///
     /// # 参数
@@ -586,7 +1166,7 @@ impl DouyinPublishStrategy {
     /// # 参数
     ///
     /// * `caption_result` - 文案处理结果
-    /// * `video_id` - 视频ID
+    /// * `media` - 作品内容（视频ID和自定义封面，或图文图片）
     /// * `request` - 公共发布请求
     /// * `config` - 抖音特定配置
     ///
@@ -596,7 +1176,7 @@ impl DouyinPublishStrategy {
     fn build_publish_data(
         &self,
         caption_result: CaptionAndHashtagsResult,
-        video_id: &str,
+        media: PublishMedia,
         request: &CorePublishRequest,
         config: &DouyinPublishConfig,
     ) -> HashMap<String, Value> {
@@ -612,7 +1192,7 @@ impl DouyinPublishStrategy {
         common_data.insert("activity".to_string(), Value::String("[]".to_string()));
         common_data.insert("text_extra".to_string(), Value::String(to_json_string(&caption_result.text_extra)));
         common_data.insert("challenges".to_string(), Value::String("[]".to_string()));
-        common_data.insert("mentions".to_string(), Value::String("[]".to_string()));
+        common_data.insert("mentions".to_string(), Value::String(to_json_string(&caption_result.mentions)));
         common_data.insert(
             "hashtag_source".to_string(),
             Value::String(if caption_result.challenges.is_empty() {
@@ -632,10 +1212,26 @@ impl DouyinPublishStrategy {
         );
         common_data.insert("timing".to_string(), Value::Number(serde_json::Number::from(0)));
         common_data.insert("creation_id".to_string(), Value::String(creation_id));
-        common_data.insert("media_type".to_string(), Value::Number(serde_json::Number::from(4)));
-        common_data.insert("video_id".to_string(), Value::String(video_id.to_string()));
         common_data.insert("source_info".to_string(), Value::String("{}".to_string()));
 
+        match media {
+            PublishMedia::Video { video_id, poster_uri } => {
+                common_data.insert("media_type".to_string(), Value::Number(serde_json::Number::from(4)));
+                common_data.insert("video_id".to_string(), Value::String(video_id.to_string()));
+
+                // 自定义封面（未上传时由抖音自动截取）
+                if let Some(uri) = poster_uri {
+                    common_data.insert("poster".to_string(), Value::String(uri.to_string()));
+                    common_data.insert("poster_delay".to_string(), Value::Number(serde_json::Number::from(0)));
+                }
+            }
+            PublishMedia::Images(images) => {
+                // 图文：media_type=2，第一张图片作为封面
+                common_data.insert("media_type".to_string(), Value::Number(serde_json::Number::from(2)));
+                common_data.insert("images".to_string(), serde_json::to_value(images).unwrap_or(Value::Array(Vec::new())));
+            }
+        }
+
         if !music_id.is_empty() {
             common_data.insert("music_source".to_string(), Value::Number(serde_json::Number::from(1)));
             common_data.insert("music_id".to_string(), Value::String(music_id));
@@ -784,5 +1380,7 @@ struct CaptionAndHashtagsResult {
     challenges: Vec<HashMap<String, String>>,
     /// 话题位置信息
     text_extra: Vec<HashMap<String, Value>>,
+    /// @ 提及的用户ID
+    mentions: Vec<String>,
 }
 
@@ -6,6 +6,7 @@
 //!
 //! - 去除HTML标签
 //! - 获取字符串长度（考虑中文字符和Emoji）
+//! - 解析文案中的 @ 提及
 //! - 截取字符串（考虑中文字符）
 //! - 生成创建ID
 //! - 计算延迟发布时间
@@ -58,6 +59,43 @@ pub fn get_string_length(input: &str) -> usize {
     length
 }
 
+/// 解析文案中的 @ 提及
+///
+/// 昵称从 `@` 之后开始，到空白、`@` 或 `#` 为止；单独的 `@` 忽略
+///
+/// # 参数
+///
+/// * `input` - 文案
+///
+/// # 返回
+///
+/// `(起始位置, 昵称)` 列表，位置按 [`get_string_length`] 计算，指向 `@`
+pub fn extract_mentions(input: &str) -> Vec<(usize, String)> {
+    let mut mentions = Vec::new();
+    let mut pos = 0;
+    let mut current: Option<(usize, String)> = None;
+
+    for c in input.chars() {
+        let is_boundary = c.is_whitespace() || c == '@' || c == '#';
+        if is_boundary {
+            if let Some((start, nickname)) = current.take().filter(|(_, n)| !n.is_empty()) {
+                mentions.push((start, nickname));
+            }
+        } else if let Some((_, nickname)) = current.as_mut() {
+            nickname.push(c);
+        }
+        if c == '@' {
+            current = Some((pos, String::new()));
+        }
+        pos += get_string_length(c.encode_utf8(&mut [0; 4]));
+    }
+    if let Some((start, nickname)) = current.filter(|(_, n)| !n.is_empty()) {
+        mentions.push((start, nickname));
+    }
+
+    mentions
+}
+
 /// 截取字符串（考虑中文字符）
 ///
 /// 按字符截取，确保不会截断中文字符或Emoji
@@ -274,6 +312,18 @@ mod tests {
         assert_eq!(length, 9);
     }
 
+    #[test]
+    fn test_extract_mentions() {
+        let mentions = extract_mentions("一起来玩 @小明 @Bob#话题 @ 结束@李四");
+        // "一起来玩 " = 4*2+1 = 9；"@小明 " = 1+4+1 = 6
+        assert_eq!(mentions, vec![
+            (9, "小明".to_string()),
+            (15, "Bob".to_string()),
+            (31, "李四".to_string()),
+        ]);
+        assert!(extract_mentions("没有提及").is_empty());
+    }
+
     #[test]
     fn test_substr() {
         let input = "你好Hello";
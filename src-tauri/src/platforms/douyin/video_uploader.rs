@@ -13,11 +13,18 @@
 //!
 //! 2. **上传视频内容**
 //!    - 小文件（<=5MB）：直接上传
-//!    - 大文件（>5MB）：分片上传
+//!    - 大文件（>5MB）：分片上传，失败的分片指数退避重试；
+//!      已上传的分片记录在本地断点文件中，中断后再次上传同一文件时跳过已完成的分片
 //!
 //! 3. **提交上传完成** (`commit_upload_inner`)
 //!    - 使用V4签名提交上传完成
 //!
+//! 上传进度通过 `upload_video_with_progress` 的回调报告（已上传字节数 / 总字节数），
+//! 分片上传时每完成一个分片回调一次
+//!
+//! 封面图片走 ImageX 上传（`upload_image`）：申请上传 → 上传图片 → 提交，
+//! 返回的图片 URI 填入发布数据的 `poster` 字段
+//!
 //! # 使用示例
 //!
 //! ```rust
@@ -32,22 +39,43 @@
 //!
 //! 本模块完全对应Java中的 `VideoUploader.java`
 
+use crate::core::{PublishCancelToken, PUBLISH_CANCELLED_MESSAGE};
 use crate::platforms::douyin::signature_v4::SignatureV4;
 use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Read, Seek};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// VOD API URL
 const VOD_API_URL: &str = "https://vod.bytedanceapi.com/";
 
+/// ImageX API URL（封面上传）
+const IMAGEX_API_URL: &str = "https://imagex.bytedanceapi.com/";
+
+/// 抖音创作者平台的 ImageX 服务ID
+const IMAGEX_SERVICE_ID: &str = "jm8ajry58r";
+
 /// 视频分片大小 (5MB)
 const VIDEO_MAX_SIZE: u64 = 5 * 1024 * 1024;
 
+/// 封面图片大小上限 (10MB)
+const IMAGE_MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+/// 单个分片的最大重试次数
+const PART_MAX_RETRIES: u32 = 3;
+
+/// 分片重试的基础等待时间（之后每次翻倍）
+const PART_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// 断点文件有效期：上传凭证过期后无法续传，超过该时间重新申请上传
+const CHECKPOINT_TTL_SECS: i64 = 60 * 60;
+
 /// 申请上传结果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct UploadApplyResult {
     /// 上传URL
     upload_url: String,
@@ -59,8 +87,21 @@ struct UploadApplyResult {
     session_key: String,
 }
 
-/// 分片信息
+/// 申请图片上传结果
 #[derive(Debug, Clone)]
+struct ImageApplyResult {
+    /// 上传URL
+    upload_url: String,
+    /// 上传凭证
+    upload_auth: String,
+    /// 图片URI（发布时作为封面）
+    store_uri: String,
+    /// 会话密钥
+    session_key: String,
+}
+
+/// 分片信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct PartInfo {
     /// 分片编号
     part_number: i32,
@@ -68,6 +109,87 @@ struct PartInfo {
     crc32: u32,
 }
 
+/// 分片上传断点
+///
+/// 以视频路径、大小和修改时间定位，保存在系统临时目录；上传完成后删除
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UploadCheckpoint {
+    /// 视频路径（按路径清除断点时使用）
+    #[serde(default)]
+    video_path: String,
+    apply_result: UploadApplyResult,
+    upload_id: String,
+    /// 已成功上传的分片
+    parts: Vec<PartInfo>,
+    /// 创建时间（Unix 秒）
+    created_at: i64,
+}
+
+impl UploadCheckpoint {
+    /// 断点文件路径
+    fn path_for(video_path: &str, file_size: u64) -> PathBuf {
+        let modified = std::fs::metadata(video_path).and_then(|m| m.modified()).ok();
+        let key = format!("{:x}", md5::compute(format!("{}@{}@{:?}", video_path, file_size, modified)));
+        Self::dir().join(format!("douyin_{}.json", key))
+    }
+
+    /// 读取未过期的断点
+    fn load(path: &Path) -> Option<Self> {
+        let checkpoint: Self = serde_json::from_slice(&std::fs::read(path).ok()?).ok()?;
+        if chrono::Utc::now().timestamp() - checkpoint.created_at > CHECKPOINT_TTL_SECS {
+            let _ = std::fs::remove_file(path);
+            return None;
+        }
+        Some(checkpoint)
+    }
+
+    fn save(&self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = std::fs::write(path, serde_json::to_vec(self).unwrap_or_default()) {
+            tracing::warn!("[UploadBig] 保存上传断点失败: {}", e);
+        }
+    }
+
+    fn remove(path: &Path) {
+        let _ = std::fs::remove_file(path);
+    }
+
+    fn dir() -> PathBuf {
+        std::env::temp_dir().join("amm-upload-checkpoints")
+    }
+}
+
+/// 清除某个视频路径的所有上传断点（文件被替换后旧的上传会话不能再续传）
+///
+/// 返回删除的断点数
+pub fn clear_upload_checkpoints(video_path: &str) -> usize {
+    let Ok(entries) = std::fs::read_dir(UploadCheckpoint::dir()) else { return 0 };
+
+    entries.filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| {
+            std::fs::read(path).ok()
+                .and_then(|bytes| serde_json::from_slice::<UploadCheckpoint>(&bytes).ok())
+                .is_some_and(|checkpoint| checkpoint.video_path == video_path)
+        })
+        .filter(|path| std::fs::remove_file(path).is_ok())
+        .count()
+}
+
+/// 计算分片 (offset, size)：按 part_size 切分，不足一片的尾部并入最后一片
+fn plan_parts(file_size: u64, part_size: u64) -> Vec<(u64, u64)> {
+    let total_parts = (file_size / part_size).max(1);
+    (0..total_parts)
+        .map(|i| {
+            let offset = i * part_size;
+            let size = if i + 1 == total_parts { file_size - offset } else { part_size };
+            (offset, size)
+        })
+        .collect()
+}
+
 /// 视频上传器
 ///
 /// 负责将视频文件上传到抖音VOD服务器
@@ -81,6 +203,8 @@ pub struct VideoUploader {
     user_agent: String,
     /// HTTP客户端
     client: Client,
+    /// 发布取消令牌（每个分片上传前检查）
+    cancel_token: Option<PublishCancelToken>,
 }
 
 impl VideoUploader {
@@ -96,9 +220,20 @@ impl VideoUploader {
             third_id,
             user_agent,
             client,
+            cancel_token: None,
         }
     }
 
+    /// 设置发布取消令牌，取消后不再上传剩余分片（断点保留，可续传）
+    pub fn with_cancel_token(mut self, cancel_token: Option<PublishCancelToken>) -> Self {
+        self.cancel_token = cancel_token;
+        self
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel_token.as_ref().is_some_and(|t| t.is_cancelled())
+    }
+
     /// 上传视频（主入口）
     ///
     /// # 步骤
@@ -108,6 +243,17 @@ impl VideoUploader {
     /// 3. 上传视频内容（小文件直接上传，大文件分片上传）
     /// 4. 提交上传完成（V4签名）
     pub async fn upload_video(&mut self, video_path: &str) -> Result<String, String> {
+        self.upload_video_with_progress(video_path, |_, _| {}).await
+    }
+
+    /// 上传视频，并通过 `on_progress(已上传字节数, 总字节数)` 报告上传进度
+    ///
+    /// 续传时首次回调即包含断点中已完成的分片
+    pub async fn upload_video_with_progress(
+        &mut self,
+        video_path: &str,
+        on_progress: impl Fn(u64, u64) + Send + Sync,
+    ) -> Result<String, String> {
         let video_file = std::path::Path::new(video_path);
 
         // 验证视频文件
@@ -119,26 +265,82 @@ impl VideoUploader {
 
         tracing::info!("[UploadVideo] 视频文件大小: {}MB", file_size / 1024 / 1024);
 
-        // 步骤2: 申请上传，获取上传地址和凭证
-        tracing::info!("[UploadVideo] ====== 步骤2: 申请上传地址和凭证 (V4签名) ======");
-        let apply_result = self.apply_upload_inner(file_size).await?;
-
-        tracing::info!("[UploadVideo] 上传地址获取成功, videoId: {}", apply_result.video_id);
-
-        // 步骤3: 上传视频内容
-        tracing::info!("[UploadVideo] ====== 步骤3: 上传视频内容 ======");
         if file_size <= VIDEO_MAX_SIZE {
+            // 步骤2: 申请上传，获取上传地址和凭证
+            tracing::info!("[UploadVideo] ====== 步骤2: 申请上传地址和凭证 (V4签名) ======");
+            let apply_result = self.apply_upload_inner(file_size).await?;
+            tracing::info!("[UploadVideo] 上传地址获取成功, videoId: {}", apply_result.video_id);
+
+            // 步骤3: 上传视频内容
+            tracing::info!("[UploadVideo] ====== 步骤3: 上传视频内容 ======");
             tracing::info!("[UploadVideo] 文件小于5MB，直接上传");
             self.upload_little_content(&apply_result, video_path).await?;
-        } else {
-            tracing::info!("[UploadVideo] 文件大于5MB，分片上传");
-            self.upload_big_content(&apply_result, video_path, file_size).await?;
+            on_progress(file_size, file_size);
+
+            // 步骤4: 提交上传完成
+            tracing::info!("[UploadVideo] ====== 步骤4: 提交上传完成 (V4签名) ======");
+            self.commit_upload_inner(&apply_result.session_key).await?;
+
+            tracing::info!("[UploadVideo] 视频上传成功, videoId: {}", apply_result.video_id);
+            return Ok(apply_result.video_id);
         }
 
-        // 步骤4: 提交上传完成
-        tracing::info!("[UploadVideo] ====== 步骤4: 提交上传完成 (V4签名) ======");
-        self.commit_upload_inner(&apply_result.session_key).await?;
+        // 大文件：有未过期的断点时沿用上次的上传会话，只补传缺失的分片
+        let checkpoint_path = UploadCheckpoint::path_for(video_path, file_size);
+        let mut checkpoint = match UploadCheckpoint::load(&checkpoint_path) {
+            Some(checkpoint) => {
+                tracing::info!("[UploadVideo] 发现上传断点，已完成 {} 个分片，继续上传 videoId: {}",
+                    checkpoint.parts.len(), checkpoint.apply_result.video_id);
+                checkpoint
+            }
+            None => {
+                tracing::info!("[UploadVideo] ====== 步骤2: 申请上传地址和凭证 (V4签名) ======");
+                let apply_result = self.apply_upload_inner(file_size).await?;
+                tracing::info!("[UploadVideo] 上传地址获取成功, videoId: {}", apply_result.video_id);
+
+                let init_response = self.init_multi_part(&apply_result).await?;
+                let upload_id = init_response.get("data")
+                    .and_then(|v| v.get("uploadid"))
+                    .and_then(|v| v.as_str())
+                    .ok_or("获取UploadId失败")?
+                    .to_string();
+
+                let checkpoint = UploadCheckpoint {
+                    video_path: video_path.to_string(),
+                    apply_result,
+                    upload_id,
+                    parts: Vec::new(),
+                    created_at: chrono::Utc::now().timestamp(),
+                };
+                checkpoint.save(&checkpoint_path);
+                checkpoint
+            }
+        };
+        let resumed_parts = checkpoint.parts.len();
 
+        // 步骤3: 分片上传
+        tracing::info!("[UploadVideo] ====== 步骤3: 上传视频内容 ======");
+        tracing::info!("[UploadVideo] 文件大于5MB，分片上传");
+        let result = match self.upload_big_content(&mut checkpoint, &checkpoint_path, video_path, file_size, &on_progress).await {
+            // 步骤4: 提交上传完成
+            Ok(()) => {
+                tracing::info!("[UploadVideo] ====== 步骤4: 提交上传完成 (V4签名) ======");
+                self.commit_upload_inner(&checkpoint.apply_result.session_key).await
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(()) => UploadCheckpoint::remove(&checkpoint_path),
+            // 续传时一个分片都没传上去，多半是上传会话已失效，丢弃断点以便下次重新申请
+            Err(_) if resumed_parts > 0 && checkpoint.parts.len() == resumed_parts => {
+                UploadCheckpoint::remove(&checkpoint_path)
+            }
+            Err(_) => {}
+        }
+        result?;
+
+        let apply_result = checkpoint.apply_result;
         tracing::info!("[UploadVideo] 视频上传成功, videoId: {}", apply_result.video_id);
         Ok(apply_result.video_id)
     }
@@ -431,106 +633,127 @@ impl VideoUploader {
     }
 
     /// 上传大文件（>5MB），分片上传
-    async fn upload_big_content(&self, apply_result: &UploadApplyResult, video_path: &str, file_size: u64) -> Result<(), String> {
-        let part_size = VIDEO_MAX_SIZE;
-
-        // 计算分片数量（与 Python 一致）
-        let last_chunk_size = file_size % part_size;
-        let mut total_parts = (file_size + part_size - 1) / part_size;
-        if last_chunk_size > 0 {
-            total_parts -= 1;
-        }
+    ///
+    /// 跳过断点中已完成的分片；每个分片失败后指数退避重试，
+    /// 重试用尽仍失败则立即返回错误（不会用不完整的分片列表完成上传）
+    async fn upload_big_content(
+        &self,
+        checkpoint: &mut UploadCheckpoint,
+        checkpoint_path: &Path,
+        video_path: &str,
+        file_size: u64,
+        on_progress: &(dyn Fn(u64, u64) + Send + Sync),
+    ) -> Result<(), String> {
+        let parts = plan_parts(file_size, VIDEO_MAX_SIZE);
+        let total_parts = parts.len();
+
+        let mut uploaded: u64 = parts.iter()
+            .enumerate()
+            .filter(|(i, _)| checkpoint.parts.iter().any(|p| p.part_number == *i as i32 + 1))
+            .map(|(_, (_, size))| size)
+            .sum();
+        on_progress(uploaded, file_size);
 
         tracing::info!("[UploadBig] ====== 开始分片上传 ======");
-        tracing::info!("[UploadBig] 文件大小: {}MB, 分片大小: {}MB, 分片数: {}",
-            file_size / 1024 / 1024, part_size / 1024 / 1024, total_parts);
-
-        // 初始化上传
-        tracing::info!("[UploadBig] 步骤1: 初始化分片上传");
-        let init_response = self.init_multi_part(apply_result).await?;
-        let upload_id = init_response.get("data")
-            .and_then(|v| v.get("uploadid"))
-            .and_then(|v| v.as_str())
-            .ok_or("获取UploadId失败")?
-            .to_string();
-
-        tracing::debug!("[UploadBig] upload_id: {}", upload_id);
+        tracing::info!("[UploadBig] 文件大小: {}MB, 分片大小: {}MB, 分片数: {}, 已完成: {}",
+            file_size / 1024 / 1024, VIDEO_MAX_SIZE / 1024 / 1024, total_parts, checkpoint.parts.len());
 
-        let mut uploaded_parts = Vec::new();
-        let mut failed = false;
-
-        // 逐个分片上传
-        tracing::info!("[UploadBig] 步骤2: 逐个上传分片");
-        for i in 0..total_parts {
+        tracing::info!("[UploadBig] 逐个上传分片");
+        for (i, (offset, size)) in parts.into_iter().enumerate() {
             let part_number = i as i32 + 1;
-            let offset = (i as u64) * part_size;
-
-            // 计算分片大小（与 Python 一致）
-            let current_part_size = std::cmp::min(part_size, file_size - offset);
-
-            tracing::info!("[UploadBig] 上传分片 {}/{}, offset: {}, size: {}",
-                part_number, total_parts, offset, current_part_size);
-
-            // 读取分片数据
-            let mut file = File::open(video_path)
-                .map_err(|e| format!("打开文件失败: {}", e))?;
-
-            let mut buffer = vec![0u8; current_part_size as usize];
-            file.seek(std::io::SeekFrom::Start(offset))
-                .map_err(|e| format!("Seek失败: {}", e))?;
-            file.read_exact(&mut buffer)
-                .map_err(|e| format!("读取分片失败: {}", e))?;
-
-            // 计算分片数据的CRC32校验值（与 Python 一致，使用 zlib.crc32）
-            let part_crc32 = crc32fast::hash(&buffer);
-
-            // 上传分片（与 Python 一致，使用 POST + query params）
-            let response = self.client.post(&apply_result.upload_url)
-                .query(&[
-                    ("phase", "transfer"),
-                    ("part_number", &part_number.to_string()),
-                    ("part_offset", &offset.to_string()),
-                    ("uploadid", &upload_id),
-                ])
-                .header("Authorization", &apply_result.upload_auth)
-                .header("Content-Type", "application/octet-stream")
-                .header("Content-Length", current_part_size)
-                .header("Content-CRC32", format!("{:08x}", part_crc32))
-                .header("X-Storage-U", &self.third_id)
-                .header("Referer", "https://creator.douyin.com/")
-                .header("User-Agent", &self.user_agent)
-                .header("X-Logical-Part-Mode", "logical_part")
-                .header("X-Storage-Mode", "gateway")
-                .body(buffer)
-                .send()
-                .await
-                .map_err(|e| format!("上传分片失败: {}", e))?;
-
-            if response.status() == StatusCode::OK || response.status() == StatusCode::CREATED {
-                uploaded_parts.push(PartInfo {
-                    part_number,
-                    crc32: part_crc32,
-                });
-                tracing::debug!("分片 {} 上传成功", part_number);
-            } else {
-                let status = response.status();
-                let text = response.text().await.unwrap_or_default();
-                tracing::error!("分片 {} 上传失败: HTTP {}, 响应: {}", part_number, status, text);
-                failed = true;
+            if checkpoint.parts.iter().any(|p| p.part_number == part_number) {
+                continue;
             }
-        }
+            if self.is_cancelled() {
+                return Err(PUBLISH_CANCELLED_MESSAGE.to_string());
+            }
+
+            tracing::info!("[UploadBig] 上传分片 {}/{}, offset: {}, size: {}", part_number, total_parts, offset, size);
+
+            let mut attempt = 0;
+            let part = loop {
+                match self.upload_part(&checkpoint.apply_result, &checkpoint.upload_id, video_path, part_number, offset, size).await {
+                    Ok(part) => break part,
+                    Err(e) if attempt < PART_MAX_RETRIES && !self.is_cancelled() => {
+                        let delay = PART_RETRY_BASE_DELAY * 2u32.pow(attempt);
+                        attempt += 1;
+                        tracing::warn!("[UploadBig] 分片 {} 上传失败（{}），{} 秒后第 {} 次重试",
+                            part_number, e, delay.as_secs(), attempt);
+                        tokio::time::sleep(delay).await;
+                    }
+                    Err(e) => {
+                        return Err(format!("分片 {}/{} 重试 {} 次后仍上传失败: {}", part_number, total_parts, PART_MAX_RETRIES, e));
+                    }
+                }
+            };
 
-        if failed && uploaded_parts.is_empty() {
-            return Err("所有分片上传失败".to_string());
+            checkpoint.parts.push(part);
+            checkpoint.save(checkpoint_path);
+
+            uploaded += size;
+            on_progress(uploaded, file_size);
         }
 
-        tracing::info!("[UploadBig] 分片上传完成, 成功 {}/{} 个", uploaded_parts.len(), total_parts);
+        tracing::info!("[UploadBig] 分片上传完成, 共 {} 个", checkpoint.parts.len());
 
         // 完成分片上传
-        tracing::info!("[UploadBig] 步骤3: 完成分片上传");
-        self.complete_multi_part(apply_result, &upload_id, &uploaded_parts).await?;
+        tracing::info!("[UploadBig] 完成分片上传");
+        self.complete_multi_part(&checkpoint.apply_result, &checkpoint.upload_id, &checkpoint.parts).await
+    }
 
-        Ok(())
+    /// 上传单个分片（一次尝试）
+    async fn upload_part(
+        &self,
+        apply_result: &UploadApplyResult,
+        upload_id: &str,
+        video_path: &str,
+        part_number: i32,
+        offset: u64,
+        size: u64,
+    ) -> Result<PartInfo, String> {
+        // 读取分片数据
+        let mut file = File::open(video_path)
+            .map_err(|e| format!("打开文件失败: {}", e))?;
+
+        let mut buffer = vec![0u8; size as usize];
+        file.seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| format!("Seek失败: {}", e))?;
+        file.read_exact(&mut buffer)
+            .map_err(|e| format!("读取分片失败: {}", e))?;
+
+        // 计算分片数据的CRC32校验值（与 Python 一致，使用 zlib.crc32）
+        let crc32 = crc32fast::hash(&buffer);
+
+        // 上传分片（与 Python 一致，使用 POST + query params）
+        let response = self.client.post(&apply_result.upload_url)
+            .query(&[
+                ("phase", "transfer"),
+                ("part_number", &part_number.to_string()),
+                ("part_offset", &offset.to_string()),
+                ("uploadid", upload_id),
+            ])
+            .header("Authorization", &apply_result.upload_auth)
+            .header("Content-Type", "application/octet-stream")
+            .header("Content-Length", size)
+            .header("Content-CRC32", format!("{:08x}", crc32))
+            .header("X-Storage-U", &self.third_id)
+            .header("Referer", "https://creator.douyin.com/")
+            .header("User-Agent", &self.user_agent)
+            .header("X-Logical-Part-Mode", "logical_part")
+            .header("X-Storage-Mode", "gateway")
+            .body(buffer)
+            .send()
+            .await
+            .map_err(|e| format!("上传分片失败: {}", e))?;
+
+        let status = response.status();
+        if status != StatusCode::OK && status != StatusCode::CREATED {
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("HTTP {}, 响应: {}", status, text));
+        }
+
+        tracing::debug!("分片 {} 上传成功", part_number);
+        Ok(PartInfo { part_number, crc32 })
     }
 
     /// 初始化分片上传
@@ -677,6 +900,167 @@ impl VideoUploader {
         Ok(())
     }
 
+    // ============ 封面上传（ImageX） ============
+
+    /// 上传封面图片
+    ///
+    /// # 步骤
+    ///
+    /// 1. 申请图片上传地址和凭证（V4签名，service=imagex）
+    /// 2. 上传图片内容
+    /// 3. 提交上传完成（V4签名）
+    ///
+    /// # 返回
+    ///
+    /// 图片URI（如 `tos-cn-i-jm8ajry58r/xxx`）
+    pub async fn upload_image(&self, image_path: &str) -> Result<String, String> {
+        let image_file = Path::new(image_path);
+        if !image_file.exists() {
+            return Err(format!("封面文件不存在: {}", image_file.display()));
+        }
+
+        let buffer = std::fs::read(image_file)
+            .map_err(|e| format!("读取封面文件失败: {}", e))?;
+        if buffer.is_empty() {
+            return Err("封面文件为空".to_string());
+        }
+        if buffer.len() as u64 > IMAGE_MAX_SIZE {
+            return Err(format!("封面文件过大: {}MB，上限10MB", buffer.len() / 1024 / 1024));
+        }
+
+        tracing::info!("[UploadImage] ====== 步骤1: 申请封面上传地址 ======");
+        let apply_result = self.apply_image_upload().await?;
+
+        tracing::info!("[UploadImage] ====== 步骤2: 上传封面内容 ======");
+        let crc32 = crc32fast::hash(&buffer);
+        let response = self.client.post(&apply_result.upload_url)
+            .header("Authorization", &apply_result.upload_auth)
+            .header("Content-Type", "application/octet-stream")
+            .header("Content-CRC32", format!("{:08x}", crc32))
+            .header("Referer", "https://creator.douyin.com/")
+            .header("User-Agent", &self.user_agent)
+            .body(buffer)
+            .send()
+            .await
+            .map_err(|e| format!("上传封面失败: {}", e))?;
+
+        let status = response.status();
+        if status != StatusCode::OK && status != StatusCode::CREATED {
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("上传封面失败: HTTP {}, 响应: {}", status, text));
+        }
+
+        tracing::info!("[UploadImage] ====== 步骤3: 提交封面上传 ======");
+        self.commit_image_upload(&apply_result.session_key).await?;
+
+        tracing::info!("[UploadImage] 封面上传成功, uri: {}", apply_result.store_uri);
+        Ok(apply_result.store_uri)
+    }
+
+    /// ImageX 请求使用的签名凭证
+    fn build_image_credentials(&self) -> HashMap<String, String> {
+        let mut credentials = self.build_credentials();
+        credentials.insert("region".to_string(), "cn-north-1".to_string());
+        credentials.insert("service".to_string(), "imagex".to_string());
+        credentials
+    }
+
+    /// 申请图片上传
+    async fn apply_image_upload(&self) -> Result<ImageApplyResult, String> {
+        let mut params: HashMap<String, String> = HashMap::new();
+        params.insert("Action".to_string(), "ApplyImageUpload".to_string());
+        params.insert("Version".to_string(), "2018-08-01".to_string());
+        params.insert("ServiceId".to_string(), IMAGEX_SERVICE_ID.to_string());
+        params.insert("app_id".to_string(), "2906".to_string());
+        params.insert("user_id".to_string(), self.third_id.clone());
+        params.insert("s".to_string(), format!("{:x}", rand::random::<i32>()));
+
+        let params_for_url = params.clone();
+
+        let mut headers: HashMap<String, String> = HashMap::new();
+        headers.insert("User-Agent".to_string(), self.user_agent.clone());
+        headers.insert("Referer".to_string(), "https://creator.douyin.com/".to_string());
+
+        let mut signer = SignatureV4::new();
+        let signed_headers = signer.sign_request_headers("GET", IMAGEX_API_URL, &params, &headers, b"", &self.build_image_credentials())
+            .map_err(|e| format!("签名失败: {}", e))?;
+
+        let url = self.build_url_with_sorted_params(IMAGEX_API_URL, params_for_url);
+        let response_body = self.send_signed_get_request(&url, &signed_headers).await?;
+
+        let result: Value = serde_json::from_str(&response_body)
+            .map_err(|e| format!("解析JSON失败: {}", e))?;
+        self.check_error(&result)?;
+
+        let upload_address = result.get("Result")
+            .and_then(|v| v.get("UploadAddress"))
+            .ok_or_else(|| "获取封面上传地址失败".to_string())?;
+
+        let upload_host = upload_address.get("UploadHosts")
+            .and_then(|v| v.as_array())
+            .and_then(|hosts| hosts.first())
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "获取UploadHosts失败".to_string())?;
+
+        let store_info = upload_address.get("StoreInfos")
+            .and_then(|v| v.as_array())
+            .and_then(|infos| infos.first())
+            .ok_or_else(|| "获取StoreInfos失败".to_string())?;
+
+        let store_uri = store_info.get("StoreUri")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "获取StoreUri失败".to_string())?
+            .to_string();
+
+        let auth = store_info.get("Auth")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "获取Auth失败".to_string())?
+            .to_string();
+
+        let session_key = upload_address.get("SessionKey")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "获取SessionKey失败".to_string())?
+            .to_string();
+
+        Ok(ImageApplyResult {
+            upload_url: format!("https://{}/upload/v1/{}", upload_host, store_uri),
+            upload_auth: auth,
+            store_uri,
+            session_key,
+        })
+    }
+
+    /// 提交图片上传完成
+    async fn commit_image_upload(&self, session_key: &str) -> Result<(), String> {
+        let mut params: HashMap<String, String> = HashMap::new();
+        params.insert("Action".to_string(), "CommitImageUpload".to_string());
+        params.insert("Version".to_string(), "2018-08-01".to_string());
+        params.insert("ServiceId".to_string(), IMAGEX_SERVICE_ID.to_string());
+        params.insert("app_id".to_string(), "2906".to_string());
+        params.insert("user_id".to_string(), self.third_id.clone());
+
+        let params_for_url = params.clone();
+
+        let body = serde_json::json!({ "SessionKey": session_key });
+        let body_bytes = serde_json::to_vec(&body).unwrap_or_default();
+
+        let mut headers: HashMap<String, String> = HashMap::new();
+        headers.insert("User-Agent".to_string(), self.user_agent.clone());
+        headers.insert("Referer".to_string(), "https://creator.douyin.com/".to_string());
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+
+        let mut signer = SignatureV4::new();
+        let signed_headers = signer.sign_request_headers("POST", IMAGEX_API_URL, &params, &headers, &body_bytes, &self.build_image_credentials())
+            .map_err(|e| format!("签名失败: {}", e))?;
+
+        let url = self.build_url_with_sorted_params(IMAGEX_API_URL, params_for_url);
+        let response_body = self.send_signed_post_request(&url, &signed_headers, &body_bytes).await?;
+
+        let result: Value = serde_json::from_str(&response_body)
+            .map_err(|e| format!("解析JSON失败: {}", e))?;
+        self.check_error(&result)
+    }
+
     // ============ 辅助方法 ============
 
     /// 验证视频文件
@@ -756,3 +1140,25 @@ impl VideoUploader {
             .map_err(|e| format!("读取响应失败: {}", e))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_parts_merges_tail_into_last_part() {
+        let parts = plan_parts(12, 5);
+        assert_eq!(parts, vec![(0, 5), (5, 7)]);
+        assert_eq!(parts.iter().map(|(_, size)| size).sum::<u64>(), 12);
+    }
+
+    #[test]
+    fn test_plan_parts_exact_multiple() {
+        assert_eq!(plan_parts(10, 5), vec![(0, 5), (5, 5)]);
+    }
+
+    #[test]
+    fn test_plan_parts_smaller_than_one_part() {
+        assert_eq!(plan_parts(3, 5), vec![(0, 3)]);
+    }
+}
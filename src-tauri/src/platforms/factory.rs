@@ -114,5 +114,23 @@ pub async fn init_default_strategies() {
     );
     PublishStrategyFactory::register(PlatformType::Douyin, douyin_strategy).await;
 
+    // 注册小红书策略
+    let xiaohongshu_strategy: Arc<dyn PublishStrategy> = Arc::new(
+        crate::platforms::xiaohongshu::XiaohongshuPublishStrategy::new()
+    );
+    PublishStrategyFactory::register(PlatformType::Xiaohongshu, xiaohongshu_strategy).await;
+
+    // 注册快手策略
+    let kuaishou_strategy: Arc<dyn PublishStrategy> = Arc::new(
+        crate::platforms::kuaishou::KuaishouPublishStrategy::new()
+    );
+    PublishStrategyFactory::register(PlatformType::Kuaishou, kuaishou_strategy).await;
+
+    // 注册B站策略
+    let bilibili_strategy: Arc<dyn PublishStrategy> = Arc::new(
+        crate::platforms::bilibili::BilibiliPublishStrategy::new()
+    );
+    PublishStrategyFactory::register(PlatformType::Bilibili, bilibili_strategy).await;
+
     tracing::info!("平台策略初始化完成，支持的平台: {:?}", PublishStrategyFactory::supported_platforms().await);
 }
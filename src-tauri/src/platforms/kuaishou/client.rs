@@ -0,0 +1,238 @@
+//! 快手API客户端
+//!
+//! 负责与快手创作者服务平台（cp.kuaishou.com）进行HTTP通信
+//!
+//! # 主要功能
+//!
+//! - 发送POST请求（JSON / 表单，自动附带 `kuaishou.web.cp.api_ph` 参数）
+//! - 申请上传 / 完成上传
+//! - 上传封面
+//! - 提交作品
+//!
+//! 快手接口以 `result == 1` 表示成功
+
+use serde_json::Value;
+
+/// 创作者服务平台URL
+pub const CP_URL: &str = "https://cp.kuaishou.com";
+
+/// 接口公共参数对应的 Cookie 名
+const API_PH_COOKIE: &str = "kuaishou.web.cp.api_ph";
+
+/// 共享的异步HTTP客户端
+static ASYNC_CLIENT: std::sync::LazyLock<reqwest::Client> = std::sync::LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .expect("Failed to create async HTTP client")
+});
+
+/// 申请上传结果
+#[derive(Debug, Clone)]
+pub struct UploadPre {
+    /// 上传令牌
+    pub token: String,
+    /// 上传服务地址
+    pub endpoint: String,
+}
+
+/// 完成上传结果
+#[derive(Debug, Clone, Default)]
+pub struct UploadFinish {
+    pub file_id: i64,
+    pub cover_key: String,
+    pub duration: i64,
+    pub width: i64,
+    pub height: i64,
+}
+
+/// 快手API客户端
+#[derive(Debug, Clone)]
+pub struct KuaishouClient {
+    /// 用户Cookie
+    pub cookie: String,
+    /// User-Agent
+    pub user_agent: String,
+    /// 接口公共参数（取自 Cookie）
+    api_ph: String,
+}
+
+impl KuaishouClient {
+    /// 创建新的客户端实例
+    pub fn new(cookie: String, user_agent: String) -> Self {
+        let api_ph = cookie_value(&cookie, API_PH_COOKIE).unwrap_or_default();
+        Self { cookie, user_agent, api_ph }
+    }
+
+    /// Cookie 中是否包含接口公共参数（未登录创作者平台时为空）
+    pub fn has_api_ph(&self) -> bool {
+        !self.api_ph.is_empty()
+    }
+
+    /// 发送POST请求（JSON）
+    pub async fn request_post(&self, endpoint: &str, data: Value) -> Result<Value, String> {
+        let mut body = data;
+        if let Value::Object(map) = &mut body {
+            map.insert(API_PH_COOKIE.to_string(), Value::String(self.api_ph.clone()));
+        }
+
+        tracing::info!("[Kuaishou] POST {}{}", CP_URL, endpoint);
+
+        let response = ASYNC_CLIENT
+            .post(format!("{}{}", CP_URL, endpoint))
+            .header("Content-Type", "application/json;charset=UTF-8")
+            .header("Cookie", &self.cookie)
+            .header("User-Agent", &self.user_agent)
+            .header("Referer", format!("{}/article/publish/video", CP_URL))
+            .header("Origin", CP_URL)
+            .header("Accept", "application/json, text/plain, */*")
+            .header("Accept-Language", "zh-CN,zh;q=0.9")
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|e| format!("POST请求失败: {}", e))?;
+
+        Self::parse_response(response).await
+    }
+
+    /// 发送POST请求（multipart 表单，单个文件字段）
+    pub async fn request_file(&self, endpoint: &str, field: &str, file_name: &str, content_type: &str, content: Vec<u8>) -> Result<Value, String> {
+        let boundary = format!("----AmmFormBoundary{}", uuid::Uuid::new_v4().simple());
+
+        let mut body = Vec::with_capacity(content.len() + 512);
+        body.extend_from_slice(format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"{k}\"\r\n\r\n{v}\r\n",
+            b = boundary, k = API_PH_COOKIE, v = self.api_ph
+        ).as_bytes());
+        body.extend_from_slice(format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"{f}\"; filename=\"{n}\"\r\nContent-Type: {t}\r\n\r\n",
+            b = boundary, f = field, n = file_name, t = content_type
+        ).as_bytes());
+        body.extend_from_slice(&content);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+        let response = ASYNC_CLIENT
+            .post(format!("{}{}", CP_URL, endpoint))
+            .header("Content-Type", format!("multipart/form-data; boundary={}", boundary))
+            .header("Cookie", &self.cookie)
+            .header("User-Agent", &self.user_agent)
+            .header("Referer", format!("{}/article/publish/video", CP_URL))
+            .header("Origin", CP_URL)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("POST请求失败: {}", e))?;
+
+        Self::parse_response(response).await
+    }
+
+    /// 解析响应：HTTP 状态异常或 result != 1 时返回错误
+    pub async fn parse_response(response: reqwest::Response) -> Result<Value, String> {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        tracing::debug!("[Kuaishou] 响应: HTTP {}, {}", status, &text[..text.len().min(2000)]);
+
+        if !status.is_success() {
+            return Err(format!("HTTP {}, 响应: {}", status.as_u16(), &text[..text.len().min(500)]));
+        }
+
+        let json: Value = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        if json.get("result").and_then(|v| v.as_i64()) != Some(1) {
+            let msg = json.get("message")
+                .or_else(|| json.get("error_msg"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("未知错误");
+            return Err(format!("接口返回错误: result={}, message={}", json.get("result").unwrap_or(&Value::Null), msg));
+        }
+
+        Ok(json)
+    }
+
+    /// 申请上传
+    pub async fn upload_pre(&self) -> Result<UploadPre, String> {
+        let json = self.request_post("/rest/cp/works/v2/video/pc/upload/pre", serde_json::json!({ "uploadType": 1 })).await?;
+
+        let token = json.pointer("/data/token").and_then(|v| v.as_str()).unwrap_or_default();
+        if token.is_empty() {
+            return Err(format!("申请上传失败：token为空 {}", json));
+        }
+
+        // 上传服务地址，未返回时使用默认地址
+        let endpoint = json.pointer("/data/endpoints/0/host")
+            .and_then(|v| v.as_str())
+            .map(|host| format!("https://{}", host))
+            .unwrap_or_else(|| "https://upload.kuaishouzt.com".to_string());
+
+        Ok(UploadPre { token: token.to_string(), endpoint })
+    }
+
+    /// 完成上传
+    pub async fn upload_finish(&self, token: &str, file_name: &str, file_length: u64) -> Result<UploadFinish, String> {
+        let json = self.request_post("/rest/cp/works/v2/video/pc/upload/finish", serde_json::json!({
+            "token": token,
+            "fileName": file_name,
+            "fileType": "video/mp4",
+            "fileLength": file_length,
+        })).await?;
+
+        let data = json.get("data").cloned().unwrap_or(Value::Null);
+        let finish = UploadFinish {
+            file_id: data.get("fileId").and_then(|v| v.as_i64()).unwrap_or_default(),
+            cover_key: data.get("coverKey").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            duration: data.get("duration").and_then(|v| v.as_i64()).unwrap_or_default(),
+            width: data.get("width").and_then(|v| v.as_i64()).unwrap_or_default(),
+            height: data.get("height").and_then(|v| v.as_i64()).unwrap_or_default(),
+        };
+
+        if finish.file_id == 0 {
+            return Err(format!("完成上传失败：未返回fileId {}", json));
+        }
+        Ok(finish)
+    }
+
+    /// 上传自定义封面，返回 coverKey
+    pub async fn upload_cover(&self, file_name: &str, content_type: &str, content: Vec<u8>) -> Result<String, String> {
+        let json = self.request_file("/rest/cp/works/v2/video/pc/upload/cover/upload", "file", file_name, content_type, content).await?;
+
+        json.pointer("/data/coverKey")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("上传封面失败：未返回coverKey {}", json))
+    }
+
+    /// 提交作品，返回作品ID
+    pub async fn submit(&self, data: Value) -> Result<String, String> {
+        let json = self.request_post("/rest/cp/works/v2/video/pc/submit", data).await?;
+
+        Ok(json.pointer("/data/photoIdStr")
+            .or_else(|| json.pointer("/data/photoId"))
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .unwrap_or_default())
+    }
+}
+
+/// 从 Cookie 字符串中读取指定字段
+pub fn cookie_value(cookie: &str, name: &str) -> Option<String> {
+    cookie.split(';')
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cookie_value() {
+        let cookie = "userId=123; kuaishou.web.cp.api_ph=abc=; did=web_1";
+        assert_eq!(cookie_value(cookie, "kuaishou.web.cp.api_ph"), Some("abc=".to_string()));
+        assert_eq!(cookie_value(cookie, "userId"), Some("123".to_string()));
+        assert_eq!(cookie_value(cookie, "missing"), None);
+    }
+}
@@ -0,0 +1,177 @@
+//! 快手平台模块
+//!
+//! 提供快手视频发布功能的相关实现
+//!
+//! # 模块结构
+//!
+//! - [`client`] - HTTP客户端
+//! - [`uploader`] - 视频分片上传器
+//! - [`strategy`] - 发布策略（主入口）
+//!
+//! 账号通过 `start_browser_auth("kuaishou")` 授权（见 `browser::platforms::kuaishou`），
+//! 账号参数与抖音相同（`third_id` + `third_param`），复用 [`AccountParams`] 解析
+
+use crate::core::{Platform, PlatformType, PlatformError, UserAccount, PublishRequest as CorePublishRequest};
+use crate::platforms::douyin::account_params::AccountParams;
+use crate::platforms::traits::PublishStrategy;
+use crate::storage::DatabaseManager;
+use std::sync::Arc;
+
+pub mod client;
+pub mod uploader;
+pub mod strategy;
+
+// 导出主要类型
+pub use self::strategy::KuaishouPublishStrategy;
+
+/// 快手平台实现
+///
+/// 包装发布策略，提供Platform trait实现
+#[derive(Debug, Clone)]
+pub struct KuaishouPlatform {
+    /// 数据库管理器（用于获取账号信息）
+    db_manager: Option<Arc<DatabaseManager>>,
+}
+
+impl KuaishouPlatform {
+    /// 创建新的平台实例
+    pub fn new() -> Self {
+        Self { db_manager: None }
+    }
+
+    /// 创建带数据库管理器的平台实例
+    pub fn with_storage(db_manager: DatabaseManager) -> Self {
+        Self {
+            db_manager: Some(Arc::new(db_manager)),
+        }
+    }
+}
+
+impl Default for KuaishouPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Platform for KuaishouPlatform {
+    fn platform_type(&self) -> PlatformType {
+        PlatformType::Kuaishou
+    }
+
+    fn platform_name(&self) -> String {
+        "快手".to_string()
+    }
+
+    async fn authenticate_account(&self) -> Result<UserAccount, PlatformError> {
+        Err(PlatformError::AuthenticationFailed(
+            "浏览器认证需要使用 start_browser_auth 命令".to_string(),
+        ))
+    }
+
+    async fn refresh_credentials(&self, _account_id: &str) -> Result<UserAccount, PlatformError> {
+        Err(PlatformError::AuthenticationFailed(
+            "请重新进行浏览器认证".to_string(),
+        ))
+    }
+
+    async fn verify_credentials(&self, params: &str) -> Result<(), PlatformError> {
+        use self::client::KuaishouClient;
+
+        let params = AccountParams::from_json(params);
+        let client = KuaishouClient::new(params.get_cookie(), params.get_user_agent());
+        if !client.has_api_ph() {
+            return Err(PlatformError::InvalidCredentials("快手账号未登录创作者平台，请重新授权".to_string()));
+        }
+
+        // 申请上传令牌需要登录态，且不会产生任何内容
+        client.upload_pre().await
+            .map(|_| ())
+            .map_err(PlatformError::InvalidCredentials)
+    }
+
+    async fn publish_video(&self, request: CorePublishRequest) -> Result<crate::core::PublishResult, PlatformError> {
+        tracing::info!("[Kuaishou Publish] 开始快手发布流程，账号ID: {}", request.account_id);
+
+        let db_manager = self.db_manager.as_ref()
+            .ok_or_else(|| PlatformError::InvalidInput("平台未配置数据库连接".to_string()))?;
+
+        let account = match db_manager.get_account(&request.account_id) {
+            Ok(Some(acc)) => acc,
+            Ok(None) => {
+                return Err(PlatformError::InvalidInput(
+                    format!("账号不存在: {}", request.account_id)
+                ));
+            }
+            Err(e) => {
+                return Err(PlatformError::InvalidInput(
+                    format!("查询账号失败: {:?}", e)
+                ));
+            }
+        };
+
+        // 构建快手特定平台数据
+        let mut platform_data = serde_json::json!({
+            "params": account.params,
+        });
+        if let Some(record_id) = &request.record_id {
+            platform_data["record_id"] = serde_json::json!(record_id);
+        }
+        if let Some(send_time) = request.send_time {
+            platform_data["send_time"] = serde_json::json!(send_time);
+        }
+
+        // 使用发布策略（带进度跟踪）
+        let strategy = match &request.progress_info {
+            Some((task_id, detail_id, account_id, app_handle)) => {
+                KuaishouPublishStrategy::with_progress(task_id, detail_id, account_id, app_handle)
+            }
+            None => KuaishouPublishStrategy::new(),
+        };
+
+        let platform_request = CorePublishRequest {
+            platform_data: Some(platform_data),
+            progress_info: None,
+            ..request
+        };
+
+        let result = strategy.publish(platform_request).await;
+
+        match &result {
+            Ok(r) => tracing::info!("[Kuaishou Publish] 发布结果: success={}, item_id={:?}", r.success, r.item_id),
+            Err(e) => tracing::error!("[Kuaishou Publish] 发布失败: {:?}", e),
+        }
+        result
+    }
+
+    async fn get_publication_status(&self, _publication_id: &str) -> Result<crate::core::PlatformPublication, PlatformError> {
+        Err(PlatformError::PublicationFailed(
+            "暂不支持获取发布状态".to_string(),
+        ))
+    }
+
+    async fn get_account_stats(&self, _account_id: &str) -> Result<crate::core::AccountProfileStats, PlatformError> {
+        Err(PlatformError::PublicationFailed(
+            "暂不支持获取账号统计".to_string(),
+        ))
+    }
+
+    fn get_credentials_from_params(&self, params: &str) -> Result<crate::core::PlatformCredentials, PlatformError> {
+        let account_params = AccountParams::from_json(params);
+
+        let cookie = account_params.get_cookie();
+        if cookie.is_empty() {
+            return Err(PlatformError::InvalidCredentials(
+                "账号参数不完整".to_string(),
+            ));
+        }
+
+        Ok(crate::core::PlatformCredentials {
+            cookie,
+            user_agent: account_params.get_user_agent(),
+            third_id: account_params.get_third_id(),
+            sec_uid: None,
+            local_data: account_params.get_local_data(),
+        })
+    }
+}
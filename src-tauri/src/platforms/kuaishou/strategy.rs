@@ -0,0 +1,340 @@
+//! 快手视频发布策略
+//!
+//! 实现策略模式，支持将视频发布到快手平台
+//!
+//! # 发布流程（5步）
+//!
+//! 1. **参数校验** - 检查视频路径和账号参数
+//! 2. **创建客户端** - 从账号参数解析 Cookie / User-Agent
+//! 3. **上传视频** - 申请上传 → 分片上传 → 合并 → 完成上传
+//! 4. **上传封面** - 有封面时上传，否则使用平台生成的默认封面
+//! 5. **提交作品** - 组装文案和话题后调用 submit 接口
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crate::platforms::factory::PublishStrategyFactory;
+//! use crate::core::PlatformType;
+//!
+//! let strategy = PublishStrategyFactory::get_service(PlatformType::Kuaishou).await;
+//! if let Some(s) = strategy {
+//!     let result = s.publish(request).await;
+//! }
+//! ```
+
+use crate::core::{PlatformError, PublishResult, PublishRequest as CorePublishRequest, ProgressStatus, PublishProgressEvent, emit_event};
+use crate::platforms::traits::{ModuleChangelogEntry, PublishStrategy};
+use crate::platforms::douyin::account_params::AccountParams;
+use crate::platforms::kuaishou::client::{KuaishouClient, UploadFinish};
+use crate::platforms::kuaishou::uploader::KuaishouUploader;
+use serde_json::{json, Value};
+use chrono::Utc;
+
+/// 平台类型标识
+/// 2 = 快手
+pub const PLATFORM_TYPE_KUAISHOU: i64 = 2;
+
+/// 策略模块版本（快手接口调整时递增，并在 CHANGELOG 中记录）
+pub const STRATEGY_VERSION: &str = "1.0.0";
+
+/// 策略模块变更记录（新版本在前）
+pub const CHANGELOG: &[ModuleChangelogEntry] = &[
+    ModuleChangelogEntry {
+        version: "1.0.0",
+        notes: "5 步发布流程：分片上传、封面上传、话题拼接、submit 发布",
+    },
+];
+
+/// 作品描述最大长度（字符，含话题）
+const MAX_CAPTION_CHARS: usize = 500;
+
+/// 快手视频发布策略
+///
+/// 实现 `PublishStrategy` 接口，提供快手视频发布功能
+#[derive(Debug, Clone)]
+pub struct KuaishouPublishStrategy {
+    /// 任务ID
+    task_id: String,
+    /// 详情ID
+    detail_id: String,
+    /// 账号ID
+    account_id: String,
+    /// AppHandle 用于发送进度事件
+    app_handle: Option<tauri::AppHandle>,
+}
+
+impl KuaishouPublishStrategy {
+    /// 创建新的发布策略实例（无进度跟踪）
+    pub fn new() -> Self {
+        Self {
+            task_id: String::new(),
+            detail_id: String::new(),
+            account_id: String::new(),
+            app_handle: None,
+        }
+    }
+
+    /// 创建带进度跟踪的发布策略实例
+    pub fn with_progress(
+        task_id: &str,
+        detail_id: &str,
+        account_id: &str,
+        app_handle: &tauri::AppHandle,
+    ) -> Self {
+        Self {
+            task_id: task_id.to_string(),
+            detail_id: detail_id.to_string(),
+            account_id: account_id.to_string(),
+            app_handle: Some(app_handle.clone()),
+        }
+    }
+
+    /// 发送进度事件
+    fn emit_progress(&self, status: &ProgressStatus, message: &str, progress: i32) {
+        if let Some(handle) = &self.app_handle {
+            let event = PublishProgressEvent {
+                task_id: self.task_id.clone(),
+                detail_id: self.detail_id.clone(),
+                account_id: self.account_id.clone(),
+                platform: "kuaishou".to_string(),
+                status: status.clone(),
+                message: message.to_string(),
+                progress,
+                timestamp: Utc::now().timestamp_millis(),
+            };
+            if let Err(e) = emit_event(handle, &event) {
+                tracing::warn!("[Progress] Failed to emit progress event: {}", e);
+            }
+        }
+    }
+}
+
+impl Default for KuaishouPublishStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PublishStrategy for KuaishouPublishStrategy {
+    /// 发布视频到快手
+    ///
+    /// # 平台数据格式
+    ///
+    /// ```json
+    /// {
+    ///   "params": "数据库中的账号参数JSON",
+    ///   "send_time": 1234567890
+    /// }
+    /// ```
+    async fn publish(&self, request: CorePublishRequest) -> Result<PublishResult, PlatformError> {
+        // 临时性错误的重试由发布流水线统一处理（publish_with_retry），策略内只发布一次
+        let result = self.publish_attempt(&request).await;
+        if let Err(e) = &result {
+            self.emit_progress(&ProgressStatus::Failed, &e.to_string(), 0);
+        }
+        result
+    }
+
+    /// 获取平台类型
+    fn get_platform_type(&self) -> i64 {
+        PLATFORM_TYPE_KUAISHOU
+    }
+
+    /// 获取策略模块版本
+    fn get_version(&self) -> &'static str {
+        STRATEGY_VERSION
+    }
+
+    /// 获取策略模块变更记录
+    fn get_changelog(&self) -> &'static [ModuleChangelogEntry] {
+        CHANGELOG
+    }
+}
+
+impl KuaishouPublishStrategy {
+    /// 执行一次完整的发布流程（步骤1-5）
+    async fn publish_attempt(&self, request: &CorePublishRequest) -> Result<PublishResult, PlatformError> {
+        request.check_cancelled()?;
+
+        // ========== 步骤1: 参数校验 ==========
+        tracing::info!("[Kuaishou Publish] ====== 步骤1: 参数校验 ======");
+        self.emit_progress(&ProgressStatus::Starting, "开始发布", 0);
+
+        if request.video_path.as_os_str().is_empty() {
+            return Err(PlatformError::InvalidInput("视频路径不能为空".to_string()));
+        }
+        if !request.video_path.exists() {
+            return Err(PlatformError::InvalidInput(format!(
+                "视频文件不存在: {}",
+                request.video_path.display()
+            )));
+        }
+
+        let platform_data = request.platform_data.as_ref()
+            .ok_or_else(|| PlatformError::InvalidInput("platform_data不能为空".to_string()))?;
+
+        let params = platform_data.get("params")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| PlatformError::InvalidInput("params不能为空".to_string()))?;
+
+        // ========== 步骤2: 创建客户端 ==========
+        tracing::info!("[Kuaishou Publish] ====== 步骤2: 创建客户端 ======");
+
+        let account_params = AccountParams::from_json(params);
+        let cookie = account_params.get_cookie();
+        if cookie.is_empty() {
+            return Err(PlatformError::InvalidCredentials("快手账号Cookie为空，请重新登录".to_string()));
+        }
+        let client = KuaishouClient::new(cookie, account_params.get_user_agent());
+        if !client.has_api_ph() {
+            return Err(PlatformError::InvalidCredentials("快手账号未登录创作者平台，请重新授权".to_string()));
+        }
+
+        request.check_cancelled()?;
+
+        // ========== 步骤3: 上传视频 ==========
+        tracing::info!("[Kuaishou Publish] ====== 步骤3: 上传视频 ======");
+        self.emit_progress(&ProgressStatus::UploadingVideo, "上传视频中...", 10);
+
+        let uploaded = KuaishouUploader::new(&client)
+            .upload_video(&request.video_path)
+            .await
+            .map_err(PlatformError::VideoUploadFailed)?;
+
+        request.check_cancelled()?;
+
+        // ========== 步骤4: 上传封面 ==========
+        tracing::info!("[Kuaishou Publish] ====== 步骤4: 上传封面 ======");
+        self.emit_progress(&ProgressStatus::Uploading, "上传封面中...", 60);
+
+        let cover_key = match request.cover_path.as_ref().filter(|p| p.exists()) {
+            Some(cover_path) => {
+                let content = std::fs::read(cover_path)
+                    .map_err(|e| PlatformError::InvalidInput(format!("读取封面失败: {}", e)))?;
+                let file_name = cover_path.file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "cover.jpg".to_string());
+                let content_type = if file_name.to_lowercase().ends_with(".png") { "image/png" } else { "image/jpeg" };
+                client.upload_cover(&file_name, content_type, content)
+                    .await
+                    .map_err(PlatformError::VideoUploadFailed)?
+            }
+            None => {
+                tracing::info!("[Kuaishou Publish] 未设置封面，使用平台默认封面");
+                uploaded.cover_key.clone()
+            }
+        };
+
+        request.check_cancelled()?;
+
+        // ========== 步骤5: 提交作品 ==========
+        tracing::info!("[Kuaishou Publish] ====== 步骤5: 提交作品 ======");
+        self.emit_progress(&ProgressStatus::Publishing, "发布中...", 80);
+
+        let caption = build_caption(
+            &request.title,
+            request.description.as_deref().unwrap_or(""),
+            &request.hashtags,
+        );
+        let send_time = platform_data.get("send_time").and_then(|v| v.as_i64())
+            .or(request.send_time);
+        let submit_data = build_submit_data(&uploaded, &cover_key, &caption, request.visibility_type, send_time);
+
+        let photo_id = client
+            .submit(submit_data)
+            .await
+            .map_err(PlatformError::PublicationFailed)?;
+
+        tracing::info!("[Kuaishou Publish] 快手作品发布成功, photoId: {}", photo_id);
+        self.emit_progress(&ProgressStatus::Completed, "发布成功", 100);
+
+        Ok(PublishResult {
+            success: true,
+            publication_id: platform_data.get("record_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            item_id: if photo_id.is_empty() { None } else { Some(photo_id) },
+            error_message: None,
+        })
+    }
+}
+
+/// 构建作品描述：标题 + 描述 + #话题
+fn build_caption(title: &str, description: &str, hashtags: &[String]) -> String {
+    let mut caption = title.trim().to_string();
+    let description = description.trim();
+    if !description.is_empty() && description != caption {
+        if !caption.is_empty() {
+            caption.push('\n');
+        }
+        caption.push_str(description);
+    }
+    for tag in hashtags {
+        let tag = tag.trim().trim_start_matches('#');
+        if !tag.is_empty() {
+            caption.push_str(&format!(" #{}", tag));
+        }
+    }
+    caption.trim().chars().take(MAX_CAPTION_CHARS).collect()
+}
+
+/// 构建提交作品请求数据
+fn build_submit_data(uploaded: &UploadFinish, cover_key: &str, caption: &str, visibility_type: i32, send_time: Option<i64>) -> Value {
+    json!({
+        "fileId": uploaded.file_id,
+        "coverKey": cover_key,
+        "coverTimeStamp": 0,
+        "caption": caption,
+        // photoStatus: 1=公开, 2=仅自己可见
+        "photoStatus": if visibility_type == 0 { 1 } else { 2 },
+        "coverType": 1,
+        "coverTitle": "",
+        "photoType": 0,
+        "collectionId": "",
+        "publishTime": send_time.map(|t| t * 1000).unwrap_or(0),
+        "longitude": "",
+        "latitude": "",
+        "notifyResult": 0,
+        "domain": "",
+        "secondDomain": "",
+        "triggerH265": false,
+        "downloadType": 1,
+        "disableNearbyShow": false,
+        "allowSameFrame": true,
+        "movieId": "",
+        "openPrePreview": false,
+        "declareInfo": {},
+        "activityIds": [],
+        "riseQuality": false,
+        "chapters": [],
+        "projectId": "",
+        "recTagIdList": [],
+        "videoInfoMeta": "",
+        "previewUrlErrorMessage": "",
+        "triggerSpliceConvert": false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_caption() {
+        let tags = vec!["#美食".to_string(), " 探店 ".to_string(), String::new()];
+        assert_eq!(build_caption("周末", "好吃", &tags), "周末\n好吃 #美食 #探店");
+        // 描述与标题相同时不重复
+        assert_eq!(build_caption("周末", "周末", &[]), "周末");
+    }
+
+    #[test]
+    fn test_build_submit_data_visibility() {
+        let uploaded = UploadFinish { file_id: 42, ..Default::default() };
+        let data = build_submit_data(&uploaded, "cover", "caption", 1, None);
+        assert_eq!(data["fileId"], 42);
+        assert_eq!(data["photoStatus"], 2);
+        assert_eq!(data["publishTime"], 0);
+    }
+}
@@ -0,0 +1,129 @@
+//! 快手视频上传器
+//!
+//! # 上传流程
+//!
+//! 1. **申请上传** (`upload/pre`) - 获取上传令牌和上传服务地址
+//! 2. **分片上传** (`api/upload/fragment`) - 按 4MB 分片依次上传
+//! 3. **合并分片** (`api/upload/complete`)
+//! 4. **完成上传** (`upload/finish`) - 获取 fileId、默认封面等信息
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crate::platforms::kuaishou::uploader::KuaishouUploader;
+//!
+//! let uploader = KuaishouUploader::new(&client);
+//! let finish = uploader.upload_video(Path::new("/path/to/video.mp4")).await?;
+//! ```
+
+use crate::platforms::kuaishou::client::{KuaishouClient, UploadFinish};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// 分片大小 (4MB)
+const FRAGMENT_SIZE: usize = 4 * 1024 * 1024;
+
+/// 上传请求超时（秒）
+const UPLOAD_TIMEOUT_SECS: u64 = 300;
+
+/// 快手视频上传器
+pub struct KuaishouUploader<'a> {
+    client: &'a KuaishouClient,
+    http: reqwest::Client,
+}
+
+impl<'a> KuaishouUploader<'a> {
+    /// 创建上传器
+    pub fn new(client: &'a KuaishouClient) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(UPLOAD_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        Self { client, http }
+    }
+
+    /// 上传视频，返回完成上传结果
+    pub async fn upload_video(&self, path: &Path) -> Result<UploadFinish, String> {
+        let file_size = std::fs::metadata(path)
+            .map_err(|e| format!("读取文件信息失败: {}", e))?
+            .len();
+        if file_size == 0 {
+            return Err(format!("文件为空: {}", path.display()));
+        }
+        let file_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "video.mp4".to_string());
+
+        // 步骤1: 申请上传
+        let pre = self.client.upload_pre().await?;
+        tracing::info!("[Kuaishou Upload] 申请上传成功, endpoint: {}", pre.endpoint);
+
+        // 步骤2: 分片上传
+        let mut file = File::open(path).map_err(|e| format!("打开文件失败: {}", e))?;
+        let mut buffer = vec![0u8; FRAGMENT_SIZE];
+        let mut fragment_id = 0;
+        loop {
+            let size = read_fragment(&mut file, &mut buffer).map_err(|e| format!("读取文件失败: {}", e))?;
+            if size == 0 {
+                break;
+            }
+
+            let url = format!("{}/api/upload/fragment?upload_token={}&fragment_id={}", pre.endpoint, pre.token, fragment_id);
+            let response = self.http.post(&url)
+                .header("Content-Type", "application/octet-stream")
+                .header("User-Agent", &self.client.user_agent)
+                .body(buffer[..size].to_vec())
+                .send()
+                .await
+                .map_err(|e| format!("上传分片{}失败: {}", fragment_id, e))?;
+            check_upload_response(response, &format!("上传分片{}", fragment_id)).await?;
+
+            tracing::debug!("[Kuaishou Upload] 分片 {} 上传成功 ({} 字节)", fragment_id, size);
+            fragment_id += 1;
+        }
+
+        // 步骤3: 合并分片
+        let url = format!("{}/api/upload/complete?fragment_count={}&upload_token={}", pre.endpoint, fragment_id, pre.token);
+        let response = self.http.post(&url)
+            .header("User-Agent", &self.client.user_agent)
+            .send()
+            .await
+            .map_err(|e| format!("合并分片失败: {}", e))?;
+        check_upload_response(response, "合并分片").await?;
+
+        // 步骤4: 完成上传
+        let finish = self.client.upload_finish(&pre.token, &file_name, file_size).await?;
+        tracing::info!("[Kuaishou Upload] 上传完成, fileId: {}, 分片数: {}", finish.file_id, fragment_id);
+        Ok(finish)
+    }
+}
+
+/// 读满一个分片（文件末尾可能不足一个分片）
+fn read_fragment(file: &mut File, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let n = file.read(&mut buffer[filled..])?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(filled)
+}
+
+/// 上传服务以 `result == 1` 表示成功
+async fn check_upload_response(response: reqwest::Response, action: &str) -> Result<(), String> {
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(format!("{}失败: HTTP {}, 响应: {}", action, status.as_u16(), text));
+    }
+
+    let result = serde_json::from_str::<serde_json::Value>(&text).ok()
+        .and_then(|json| json.get("result").and_then(|v| v.as_i64()));
+    if result != Some(1) {
+        return Err(format!("{}失败: {}", action, text));
+    }
+    Ok(())
+}
@@ -7,13 +7,73 @@
 // # 模块结构
 //
 // - [douyin](douyin/index.html) - 抖音平台发布策略
+// - [xiaohongshu](xiaohongshu/index.html) - 小红书平台发布策略
+// - [kuaishou](kuaishou/index.html) - 快手平台发布策略
+// - [bilibili](bilibili/index.html) - B站平台发布策略
 // - [traits](traits/index.html) - 发布策略 trait 定义
 // - [factory](factory/index.html) - 发布策略工厂
 
 pub mod douyin;
+pub mod xiaohongshu;
+pub mod kuaishou;
+pub mod bilibili;
 pub mod traits;
 pub mod factory;
 
 // 重新导出主要类型，方便使用
-pub use crate::platforms::traits::PublishStrategy;
+pub use crate::platforms::traits::{ModuleChangelogEntry, PublishStrategy};
 pub use crate::platforms::factory::{PublishStrategyFactory, init_default_strategies};
+
+use crate::core::PlatformType;
+use serde::Serialize;
+
+/// 平台发布模块版本信息
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformModuleVersion {
+    pub platform: PlatformType,
+    pub module: &'static str,
+    pub version: &'static str,
+    pub changelog: &'static [ModuleChangelogEntry],
+}
+
+/// 获取所有已实现平台的发布模块版本
+pub fn platform_module_versions() -> Vec<PlatformModuleVersion> {
+    let douyin = douyin::DouyinPublishStrategy::new();
+    let xiaohongshu = xiaohongshu::XiaohongshuPublishStrategy::new();
+    let kuaishou = kuaishou::KuaishouPublishStrategy::new();
+    let bilibili = bilibili::BilibiliPublishStrategy::new();
+
+    vec![
+        PlatformModuleVersion {
+            platform: PlatformType::Douyin,
+            module: "douyin::strategy",
+            version: douyin.get_version(),
+            changelog: douyin.get_changelog(),
+        },
+        PlatformModuleVersion {
+            platform: PlatformType::Xiaohongshu,
+            module: "xiaohongshu::strategy",
+            version: xiaohongshu.get_version(),
+            changelog: xiaohongshu.get_changelog(),
+        },
+        PlatformModuleVersion {
+            platform: PlatformType::Kuaishou,
+            module: "kuaishou::strategy",
+            version: kuaishou.get_version(),
+            changelog: kuaishou.get_changelog(),
+        },
+        PlatformModuleVersion {
+            platform: PlatformType::Bilibili,
+            module: "bilibili::strategy",
+            version: bilibili.get_version(),
+            changelog: bilibili.get_changelog(),
+        },
+    ]
+}
+
+/// 获取指定平台当前的发布模块版本（未实现的平台返回 None）
+pub fn platform_module_version(platform: &PlatformType) -> Option<&'static str> {
+    platform_module_versions().into_iter()
+        .find(|m| &m.platform == platform)
+        .map(|m| m.version)
+}
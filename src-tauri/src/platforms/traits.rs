@@ -3,7 +3,17 @@
 //! 定义各平台发布策略的通用接口
 //! 遵循策略模式，支持不同平台（抖音、快手、小红书等）的视频发布
 
-use crate::core::{PlatformError, PublishResult, PublishRequest, CommentExtractResult};
+use crate::core::{PlatformError, PublishResult, PublishRequest, CommentExtractResult, MessageExtractResult};
+use serde::Serialize;
+
+/// 平台模块版本变更记录
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleChangelogEntry {
+    /// 版本号
+    pub version: &'static str,
+    /// 变更说明
+    pub notes: &'static str,
+}
 
 /// 发布策略 trait
 ///
@@ -28,6 +38,16 @@ pub trait PublishStrategy: Send + Sync {
     ///
     /// 平台类型标识：1=抖音; 2=快手; 3=小红书
     fn get_platform_type(&self) -> i64;
+
+    /// 获取策略模块版本
+    ///
+    /// 平台接口变化导致策略调整时递增，每次发布都会记录执行时的版本
+    fn get_version(&self) -> &'static str;
+
+    /// 获取策略模块变更记录（新版本在前）
+    fn get_changelog(&self) -> &'static [ModuleChangelogEntry] {
+        &[]
+    }
 }
 
 /// 评论提取策略 trait
@@ -50,3 +70,22 @@ pub trait CommentExtractor: Send + Sync {
     async fn extract_comments(&self, account_id: &str, aweme_id: &str, max_count: i64, cursor: i64)
         -> Result<CommentExtractResult, PlatformError>;
 }
+
+/// 私信提取策略 trait
+///
+/// 支持私信的平台实现此接口，用于拉取创作者收件箱中的消息
+#[async_trait::async_trait]
+pub trait MessageExtractor: Send + Sync {
+    /// 提取账号收件箱中的私信
+    ///
+    /// # 参数
+    ///
+    /// * `account_id` - 账号ID（用于获取凭证）
+    /// * `max_count` - 最大提取消息数
+    ///
+    /// # 返回
+    ///
+    /// 私信提取结果（消息的 account_id 已填写）
+    async fn extract_messages(&self, account_id: &str, max_count: i64)
+        -> Result<MessageExtractResult, PlatformError>;
+}
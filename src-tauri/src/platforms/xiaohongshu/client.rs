@@ -0,0 +1,300 @@
+//! 小红书API客户端
+//!
+//! 负责与小红书创作服务平台进行HTTP通信
+//!
+//! # 主要功能
+//!
+//! - 发送GET/POST请求（自动附带 x-s / x-t 签名）
+//! - 申请上传凭证（视频/封面）
+//! - 搜索话题
+//! - 创建视频笔记
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crate::platforms::xiaohongshu::client::XiaohongshuClient;
+//!
+//! let client = XiaohongshuClient::new("cookie".to_string(), "user-agent".to_string());
+//!
+//! // 申请视频上传凭证
+//! let permit = client.get_upload_permit(UploadScene::Video).await?;
+//!
+//! // 创建视频笔记
+//! let result = client.create_note(note_data).await?;
+//! ```
+
+use serde_json::Value;
+
+/// 创作服务平台URL
+const CREATOR_URL: &str = "https://creator.xiaohongshu.com";
+
+/// 笔记接口URL
+const EDITH_URL: &str = "https://edith.xiaohongshu.com";
+
+/// 签名使用的自定义 Base64 字母表
+const SIGN_ALPHABET: &[u8] = b"A4NjFqYu5wPHsO0XTdDgMa2r1ZQocVte9UJBvk6/7=yRnhISGKblCWi+LpfE8xzm3";
+
+/// 共享的异步HTTP客户端
+static ASYNC_CLIENT: std::sync::LazyLock<reqwest::Client> = std::sync::LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .expect("Failed to create async HTTP client")
+});
+
+/// 上传场景
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadScene {
+    /// 视频
+    Video,
+    /// 图片（封面）
+    Image,
+}
+
+impl UploadScene {
+    fn as_str(&self) -> &'static str {
+        match self {
+            UploadScene::Video => "video",
+            UploadScene::Image => "image",
+        }
+    }
+}
+
+/// 上传凭证
+#[derive(Debug, Clone)]
+pub struct UploadPermit {
+    /// 文件ID
+    pub file_id: String,
+    /// 上传凭证
+    pub token: String,
+    /// 上传地址（域名）
+    pub upload_addr: String,
+}
+
+impl UploadPermit {
+    /// 完整上传URL
+    pub fn upload_url(&self) -> String {
+        format!("https://{}/{}", self.upload_addr, self.file_id)
+    }
+}
+
+/// 话题信息
+#[derive(Debug, Clone)]
+pub struct Topic {
+    pub id: String,
+    pub name: String,
+    pub link: String,
+}
+
+/// 小红书API客户端
+#[derive(Debug, Clone)]
+pub struct XiaohongshuClient {
+    /// 用户Cookie
+    pub cookie: String,
+    /// User-Agent
+    pub user_agent: String,
+}
+
+impl XiaohongshuClient {
+    /// 创建新的客户端实例
+    pub fn new(cookie: String, user_agent: String) -> Self {
+        Self { cookie, user_agent }
+    }
+
+    /// 发送GET请求（创作服务平台）
+    ///
+    /// # 参数
+    ///
+    /// * `endpoint` - API端点路径（含查询参数）
+    pub async fn request_get(&self, base_url: &str, endpoint: &str) -> Result<Value, String> {
+        let (x_s, x_t) = sign(endpoint, None, chrono::Utc::now().timestamp_millis());
+
+        let response = ASYNC_CLIENT
+            .get(format!("{}{}", base_url, endpoint))
+            .header("Cookie", &self.cookie)
+            .header("User-Agent", &self.user_agent)
+            .header("Referer", CREATOR_URL)
+            .header("Origin", CREATOR_URL)
+            .header("Accept", "application/json, text/plain, */*")
+            .header("Accept-Language", "zh-CN,zh;q=0.9")
+            .header("x-s", x_s)
+            .header("x-t", x_t)
+            .send()
+            .await
+            .map_err(|e| format!("GET请求失败: {}", e))?;
+
+        Self::parse_response(response).await
+    }
+
+    /// 发送POST请求
+    ///
+    /// # 参数
+    ///
+    /// * `base_url` - 域名
+    /// * `endpoint` - API端点路径
+    /// * `data` - JSON请求体
+    pub async fn request_post(&self, base_url: &str, endpoint: &str, data: &Value) -> Result<Value, String> {
+        let (x_s, x_t) = sign(endpoint, Some(data), chrono::Utc::now().timestamp_millis());
+        let body = serde_json::to_string(data).unwrap_or_default();
+
+        tracing::info!("[XHS] POST {}{}", base_url, endpoint);
+        tracing::debug!("[XHS] Body: {}", &body);
+
+        let response = ASYNC_CLIENT
+            .post(format!("{}{}", base_url, endpoint))
+            .header("Content-Type", "application/json;charset=UTF-8")
+            .header("Cookie", &self.cookie)
+            .header("User-Agent", &self.user_agent)
+            .header("Referer", CREATOR_URL)
+            .header("Origin", CREATOR_URL)
+            .header("Accept", "application/json, text/plain, */*")
+            .header("Accept-Language", "zh-CN,zh;q=0.9")
+            .header("x-s", x_s)
+            .header("x-t", x_t)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("POST请求失败: {}", e))?;
+
+        Self::parse_response(response).await
+    }
+
+    /// 解析响应：HTTP 状态异常或 success=false 时返回错误
+    async fn parse_response(response: reqwest::Response) -> Result<Value, String> {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        tracing::debug!("[XHS] 响应: HTTP {}, {}", status, &text[..text.len().min(2000)]);
+
+        if !status.is_success() {
+            return Err(format!("HTTP {}, 响应: {}", status.as_u16(), &text[..text.len().min(500)]));
+        }
+
+        let json: Value = serde_json::from_str(&text)
+            .map_err(|e| format!("解析响应失败: {}", e))?;
+
+        let success = json.get("success").and_then(|v| v.as_bool())
+            .unwrap_or_else(|| json.get("code").and_then(|v| v.as_i64()) == Some(0));
+        if !success {
+            let msg = json.get("msg").and_then(|v| v.as_str()).unwrap_or("未知错误");
+            let code = json.get("code").and_then(|v| v.as_i64()).unwrap_or(-1);
+            return Err(format!("接口返回错误: code={}, msg={}", code, msg));
+        }
+
+        Ok(json)
+    }
+
+    /// 申请上传凭证
+    ///
+    /// 返回文件ID、上传凭证和上传地址
+    pub async fn get_upload_permit(&self, scene: UploadScene) -> Result<UploadPermit, String> {
+        let endpoint = format!(
+            "/api/media/v1/upload/web/permit?biz_name=spectrum&scene={}&file_count=1&version=1&source=web",
+            scene.as_str()
+        );
+        let json = self.request_get(CREATOR_URL, &endpoint).await?;
+
+        let permit = json.pointer("/data/uploadTempPermits/0")
+            .ok_or_else(|| "获取上传凭证失败：uploadTempPermits为空".to_string())?;
+
+        let file_id = permit.pointer("/fileIds/0").and_then(|v| v.as_str()).unwrap_or_default();
+        let token = permit.get("token").and_then(|v| v.as_str()).unwrap_or_default();
+        let upload_addr = permit.get("uploadAddr").and_then(|v| v.as_str()).unwrap_or_default();
+
+        if file_id.is_empty() || token.is_empty() || upload_addr.is_empty() {
+            return Err(format!("获取上传凭证失败：字段不完整 {}", permit));
+        }
+
+        Ok(UploadPermit {
+            file_id: file_id.to_string(),
+            token: token.to_string(),
+            upload_addr: upload_addr.to_string(),
+        })
+    }
+
+    /// 搜索话题（取第一个匹配结果）
+    pub async fn search_topic(&self, keyword: &str) -> Result<Option<Topic>, String> {
+        let data = serde_json::json!({
+            "keyword": keyword,
+            "suggest_topic_request": { "title": "", "desc": "" },
+            "page": { "page_size": 20, "page": 1 },
+        });
+        let json = self.request_post(EDITH_URL, "/web_api/sns/v1/search/topic", &data).await?;
+
+        let topic = json.pointer("/data/topic_info_dtos/0").map(|t| Topic {
+            id: t.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            name: t.get("name").and_then(|v| v.as_str()).unwrap_or(keyword).to_string(),
+            link: t.get("link").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        });
+
+        Ok(topic.filter(|t| !t.id.is_empty()))
+    }
+
+    /// 创建笔记
+    ///
+    /// 返回笔记ID
+    pub async fn create_note(&self, data: &Value) -> Result<String, String> {
+        let json = self.request_post(EDITH_URL, "/web_api/sns/v2/note", data).await?;
+
+        json.pointer("/data/id")
+            .or_else(|| json.pointer("/data/note_id"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("发布成功但未返回笔记ID: {}", json))
+    }
+}
+
+/// 计算创作服务平台接口签名
+///
+/// x-s = 自定义Base64(md5("{x-t}test{uri}{body}"))
+///
+/// # 返回
+///
+/// (x-s, x-t)
+pub fn sign(uri: &str, data: Option<&Value>, timestamp_ms: i64) -> (String, String) {
+    let body = data.map(|d| serde_json::to_string(d).unwrap_or_default()).unwrap_or_default();
+    let raw = format!("{}test{}{}", timestamp_ms, uri, body);
+    let digest = format!("{:x}", md5::compute(raw.as_bytes()));
+    (custom_base64(digest.as_bytes()), timestamp_ms.to_string())
+}
+
+/// 使用签名字母表进行 Base64 编码
+fn custom_base64(input: &[u8]) -> String {
+    let mut output = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let n = (b0 << 16) | (b1 << 8) | b2;
+
+        output.push(SIGN_ALPHABET[(n >> 18) as usize & 63] as char);
+        output.push(SIGN_ALPHABET[(n >> 12) as usize & 63] as char);
+        // 不足3字节时以字母表第65位（'3'）补齐
+        output.push(if chunk.len() > 1 { SIGN_ALPHABET[(n >> 6) as usize & 63] as char } else { SIGN_ALPHABET[64] as char });
+        output.push(if chunk.len() > 2 { SIGN_ALPHABET[n as usize & 63] as char } else { SIGN_ALPHABET[64] as char });
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_is_deterministic() {
+        let data = serde_json::json!({"a": 1});
+        let (xs1, xt1) = sign("/web_api/sns/v2/note", Some(&data), 1700000000000);
+        let (xs2, xt2) = sign("/web_api/sns/v2/note", Some(&data), 1700000000000);
+        assert_eq!(xs1, xs2);
+        assert_eq!(xt1, "1700000000000");
+        assert_eq!(xt1, xt2);
+        // md5 十六进制为32字节，编码后长度为44
+        assert_eq!(xs1.len(), 44);
+    }
+
+    #[test]
+    fn test_custom_base64_padding() {
+        assert_eq!(custom_base64(b"a").len(), 4);
+        assert!(custom_base64(b"a").ends_with("33"));
+        assert!(custom_base64(b"ab").ends_with('3'));
+    }
+}
@@ -0,0 +1,390 @@
+//! 小红书视频发布策略
+//!
+//! 实现策略模式，支持将视频笔记发布到小红书平台
+//!
+//! # 发布流程（6步）
+//!
+//! 1. **参数校验** - 检查视频路径和账号参数
+//! 2. **创建客户端** - 从账号参数解析 Cookie / User-Agent
+//! 3. **上传视频** - 申请上传凭证 → 上传到对象存储
+//! 4. **上传封面** - 有封面时同样流程上传，否则使用视频首帧
+//! 5. **处理文案和话题** - 标题截断、话题搜索并追加到正文
+//! 6. **发布笔记** - 调用 v2/note 接口
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crate::platforms::factory::PublishStrategyFactory;
+//! use crate::core::PlatformType;
+//!
+//! let strategy = PublishStrategyFactory::get_service(PlatformType::Xiaohongshu).await;
+//! if let Some(s) = strategy {
+//!     let result = s.publish(request).await;
+//! }
+//! ```
+
+use crate::core::{PlatformError, PublishResult, PublishRequest as CorePublishRequest, ProgressStatus, PublishProgressEvent, emit_event};
+use crate::platforms::traits::{ModuleChangelogEntry, PublishStrategy};
+use crate::platforms::douyin::account_params::AccountParams;
+use crate::platforms::xiaohongshu::client::{Topic, UploadScene, XiaohongshuClient};
+use crate::platforms::xiaohongshu::uploader::XiaohongshuUploader;
+use serde_json::{json, Value};
+use chrono::Utc;
+
+/// 平台类型标识
+/// 3 = 小红书
+pub const PLATFORM_TYPE_XIAOHONGSHU: i64 = 3;
+
+/// 策略模块版本（小红书接口调整时递增，并在 CHANGELOG 中记录）
+pub const STRATEGY_VERSION: &str = "1.0.0";
+
+/// 策略模块变更记录（新版本在前）
+pub const CHANGELOG: &[ModuleChangelogEntry] = &[
+    ModuleChangelogEntry {
+        version: "1.0.0",
+        notes: "6 步发布流程：上传凭证、分片上传、封面上传、话题搜索、v2/note 发布",
+    },
+];
+
+/// 标题最大长度（字符）
+const MAX_TITLE_CHARS: usize = 20;
+
+/// 正文最大长度（字符）
+const MAX_DESC_CHARS: usize = 1000;
+
+/// 小红书视频发布策略
+///
+/// 实现 `PublishStrategy` 接口，提供小红书视频笔记发布功能
+#[derive(Debug, Clone)]
+pub struct XiaohongshuPublishStrategy {
+    /// 任务ID
+    task_id: String,
+    /// 详情ID
+    detail_id: String,
+    /// 账号ID
+    account_id: String,
+    /// AppHandle 用于发送进度事件
+    app_handle: Option<tauri::AppHandle>,
+}
+
+impl XiaohongshuPublishStrategy {
+    /// 创建新的发布策略实例（无进度跟踪）
+    pub fn new() -> Self {
+        Self {
+            task_id: String::new(),
+            detail_id: String::new(),
+            account_id: String::new(),
+            app_handle: None,
+        }
+    }
+
+    /// 创建带进度跟踪的发布策略实例
+    pub fn with_progress(
+        task_id: &str,
+        detail_id: &str,
+        account_id: &str,
+        app_handle: &tauri::AppHandle,
+    ) -> Self {
+        Self {
+            task_id: task_id.to_string(),
+            detail_id: detail_id.to_string(),
+            account_id: account_id.to_string(),
+            app_handle: Some(app_handle.clone()),
+        }
+    }
+
+    /// 发送进度事件
+    fn emit_progress(&self, status: &ProgressStatus, message: &str, progress: i32) {
+        if let Some(handle) = &self.app_handle {
+            let event = PublishProgressEvent {
+                task_id: self.task_id.clone(),
+                detail_id: self.detail_id.clone(),
+                account_id: self.account_id.clone(),
+                platform: "xiaohongshu".to_string(),
+                status: status.clone(),
+                message: message.to_string(),
+                progress,
+                timestamp: Utc::now().timestamp_millis(),
+            };
+            if let Err(e) = emit_event(handle, &event) {
+                tracing::warn!("[Progress] Failed to emit progress event: {}", e);
+            }
+        }
+    }
+}
+
+impl Default for XiaohongshuPublishStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl PublishStrategy for XiaohongshuPublishStrategy {
+    /// 发布视频笔记到小红书
+    ///
+    /// # 平台数据格式
+    ///
+    /// ```json
+    /// {
+    ///   "params": "数据库中的账号参数JSON",
+    ///   "send_time": 1234567890
+    /// }
+    /// ```
+    async fn publish(&self, request: CorePublishRequest) -> Result<PublishResult, PlatformError> {
+        // 临时性错误的重试由发布流水线统一处理（publish_with_retry），策略内只发布一次
+        let result = self.publish_attempt(&request).await;
+        if let Err(e) = &result {
+            self.emit_progress(&ProgressStatus::Failed, &e.to_string(), 0);
+        }
+        result
+    }
+
+    /// 获取平台类型
+    fn get_platform_type(&self) -> i64 {
+        PLATFORM_TYPE_XIAOHONGSHU
+    }
+
+    /// 获取策略模块版本
+    fn get_version(&self) -> &'static str {
+        STRATEGY_VERSION
+    }
+
+    /// 获取策略模块变更记录
+    fn get_changelog(&self) -> &'static [ModuleChangelogEntry] {
+        CHANGELOG
+    }
+}
+
+impl XiaohongshuPublishStrategy {
+    /// 执行一次完整的发布流程（步骤1-6）
+    async fn publish_attempt(&self, request: &CorePublishRequest) -> Result<PublishResult, PlatformError> {
+        request.check_cancelled()?;
+
+        // ========== 步骤1: 参数校验 ==========
+        tracing::info!("[XHS Publish] ====== 步骤1: 参数校验 ======");
+        self.emit_progress(&ProgressStatus::Starting, "开始发布", 0);
+
+        if request.video_path.as_os_str().is_empty() {
+            return Err(PlatformError::InvalidInput("视频路径不能为空".to_string()));
+        }
+        if !request.video_path.exists() {
+            return Err(PlatformError::InvalidInput(format!(
+                "视频文件不存在: {}",
+                request.video_path.display()
+            )));
+        }
+
+        let platform_data = request.platform_data.as_ref()
+            .ok_or_else(|| PlatformError::InvalidInput("platform_data不能为空".to_string()))?;
+
+        let params = platform_data.get("params")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| PlatformError::InvalidInput("params不能为空".to_string()))?;
+
+        // ========== 步骤2: 创建客户端 ==========
+        tracing::info!("[XHS Publish] ====== 步骤2: 创建客户端 ======");
+
+        let account_params = AccountParams::from_json(params);
+        let cookie = account_params.get_cookie();
+        if cookie.is_empty() {
+            return Err(PlatformError::InvalidCredentials("小红书账号Cookie为空，请重新登录".to_string()));
+        }
+        let client = XiaohongshuClient::new(cookie, account_params.get_user_agent());
+        let uploader = XiaohongshuUploader::new(&client);
+
+        request.check_cancelled()?;
+
+        // ========== 步骤3: 上传视频 ==========
+        tracing::info!("[XHS Publish] ====== 步骤3: 上传视频 ======");
+        self.emit_progress(&ProgressStatus::UploadingVideo, "上传视频中...", 10);
+
+        let video_file_id = uploader
+            .upload_file(&request.video_path, UploadScene::Video)
+            .await
+            .map_err(PlatformError::VideoUploadFailed)?;
+
+        request.check_cancelled()?;
+
+        // ========== 步骤4: 上传封面 ==========
+        tracing::info!("[XHS Publish] ====== 步骤4: 上传封面 ======");
+        self.emit_progress(&ProgressStatus::Uploading, "上传封面中...", 55);
+
+        let cover_file_id = match request.cover_path.as_ref().filter(|p| p.exists()) {
+            Some(cover_path) => Some(
+                uploader
+                    .upload_file(cover_path, UploadScene::Image)
+                    .await
+                    .map_err(PlatformError::VideoUploadFailed)?,
+            ),
+            None => {
+                tracing::info!("[XHS Publish] 未设置封面，使用视频首帧");
+                None
+            }
+        };
+
+        request.check_cancelled()?;
+
+        // ========== 步骤5: 处理文案和话题 ==========
+        tracing::info!("[XHS Publish] ====== 步骤5: 处理文案和话题 ======");
+        self.emit_progress(&ProgressStatus::BuildingData, "处理文案和话题...", 70);
+
+        let mut topics = Vec::new();
+        for name in &request.hashtags {
+            let name = name.trim().trim_start_matches('#');
+            if name.is_empty() {
+                continue;
+            }
+            match client.search_topic(name).await {
+                Ok(Some(topic)) => topics.push(topic),
+                Ok(None) => tracing::info!("[XHS Publish] 未找到话题: {}，以纯文本追加", name),
+                Err(e) => tracing::warn!("[XHS Publish] 搜索话题 {} 失败: {}", name, e),
+            }
+        }
+        let plain_tags: Vec<&str> = request.hashtags.iter()
+            .map(|t| t.trim().trim_start_matches('#'))
+            .filter(|t| !t.is_empty() && !topics.iter().any(|topic| topic.name == *t))
+            .collect();
+
+        let title = truncate_chars(request.title.trim(), MAX_TITLE_CHARS);
+        let desc = build_desc(request.description.as_deref().unwrap_or(""), &topics, &plain_tags);
+
+        request.check_cancelled()?;
+
+        // ========== 步骤6: 发布笔记 ==========
+        tracing::info!("[XHS Publish] ====== 步骤6: 发布笔记 ======");
+        self.emit_progress(&ProgressStatus::Publishing, "发布中...", 80);
+
+        let send_time = platform_data.get("send_time").and_then(|v| v.as_i64())
+            .or(request.send_time);
+        let note_data = build_note_data(
+            &title,
+            &desc,
+            &topics,
+            &video_file_id,
+            cover_file_id.as_deref(),
+            request.visibility_type,
+            send_time,
+        );
+
+        let note_id = client
+            .create_note(&note_data)
+            .await
+            .map_err(PlatformError::PublicationFailed)?;
+
+        tracing::info!("[XHS Publish] 小红书笔记发布成功, noteId: {}", note_id);
+        self.emit_progress(&ProgressStatus::Completed, "发布成功", 100);
+
+        Ok(PublishResult {
+            success: true,
+            publication_id: platform_data.get("record_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            item_id: Some(note_id),
+            error_message: None,
+        })
+    }
+}
+
+/// 按字符截断
+fn truncate_chars(s: &str, max: usize) -> String {
+    s.chars().take(max).collect()
+}
+
+/// 构建正文：原始描述 + 话题（#名称[话题]#）+ 未匹配的纯文本话题
+fn build_desc(description: &str, topics: &[Topic], plain_tags: &[&str]) -> String {
+    let mut desc = description.trim().to_string();
+    for topic in topics {
+        desc.push_str(&format!(" #{}[话题]#", topic.name));
+    }
+    for tag in plain_tags {
+        desc.push_str(&format!(" #{}", tag));
+    }
+    truncate_chars(desc.trim(), MAX_DESC_CHARS)
+}
+
+/// 构建视频笔记请求数据
+fn build_note_data(
+    title: &str,
+    desc: &str,
+    topics: &[Topic],
+    video_file_id: &str,
+    cover_file_id: Option<&str>,
+    visibility_type: i32,
+    send_time: Option<i64>,
+) -> Value {
+    let hash_tag: Vec<Value> = topics.iter()
+        .map(|t| json!({ "id": t.id, "name": t.name, "link": t.link, "type": "topic" }))
+        .collect();
+
+    // 定时发布时间（毫秒），为空表示立即发布
+    let business_binds = json!({
+        "version": 1,
+        "noteId": 0,
+        "noteOrderBind": {},
+        "notePostTiming": { "postTime": send_time.map(|t| (t * 1000).to_string()).unwrap_or_default() },
+        "noteCollectionBind": { "id": "" },
+    });
+
+    let cover = match cover_file_id {
+        Some(file_id) => json!({
+            "fileid": file_id,
+            "file_id": file_id,
+            "frame": { "ts": 0, "is_user_select": false, "is_upload": true },
+        }),
+        None => json!({
+            "frame": { "ts": 0, "is_user_select": false, "is_upload": false },
+        }),
+    };
+
+    json!({
+        "common": {
+            "type": "video",
+            "title": title,
+            "note_id": "",
+            "desc": desc,
+            "source": r#"{"type":"web","ids":"","extraInfo":"{\"subType\":\"official\"}"}"#,
+            "business_binds": business_binds.to_string(),
+            "ats": [],
+            "hash_tag": hash_tag,
+            "post_loc": {},
+            // visibility_type: 0=公开, 其他=仅自己可见
+            "privacy_info": { "op_type": 1, "type": if visibility_type == 0 { 0 } else { 1 } },
+        },
+        "image_info": null,
+        "video_info": {
+            "fileid": video_file_id,
+            "file_id": video_file_id,
+            "video_preview_type": "full_vertical_screen",
+            "timelines": [],
+            "cover": cover,
+            "chapters": [],
+            "chapter_sync_text": false,
+            "entrance": "web",
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_desc_appends_topics_and_truncates() {
+        let topics = vec![Topic { id: "1".into(), name: "旅行".into(), link: String::new() }];
+        let desc = build_desc("周末出游", &topics, &["vlog"]);
+        assert_eq!(desc, "周末出游 #旅行[话题]# #vlog");
+
+        let long = "字".repeat(MAX_DESC_CHARS + 10);
+        assert_eq!(build_desc(&long, &[], &[]).chars().count(), MAX_DESC_CHARS);
+    }
+
+    #[test]
+    fn test_build_note_data_cover_and_timing() {
+        let data = build_note_data("标题", "正文", &[], "vid", None, 0, Some(1700000000));
+        assert_eq!(data["video_info"]["file_id"], "vid");
+        assert_eq!(data["video_info"]["cover"]["frame"]["is_upload"], false);
+        assert!(data["common"]["business_binds"].as_str().unwrap().contains("1700000000000"));
+    }
+}
@@ -0,0 +1,195 @@
+//! 小红书文件上传器
+//!
+//! 负责将视频/封面上传到小红书对象存储
+//!
+//! # 上传流程
+//!
+//! 1. **申请上传凭证** - `XiaohongshuClient::get_upload_permit`，获取 fileId、token、uploadAddr
+//! 2. **上传文件内容**
+//!    - 小文件（<=10MB）：直接 PUT
+//!    - 大文件（>10MB）：分片上传（初始化 → 逐片 PUT → 合并）
+//!
+//! 上传请求通过 `X-Cos-Security-Token` 请求头携带凭证
+//!
+//! # 使用示例
+//!
+//! ```rust
+//! use crate::platforms::xiaohongshu::uploader::XiaohongshuUploader;
+//!
+//! let uploader = XiaohongshuUploader::new(&client);
+//! let file_id = uploader.upload_file(Path::new("/path/to/video.mp4"), UploadScene::Video).await?;
+//! ```
+
+use crate::platforms::xiaohongshu::client::{UploadPermit, UploadScene, XiaohongshuClient};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// 分片大小 (10MB)
+const PART_SIZE: u64 = 10 * 1024 * 1024;
+
+/// 上传请求超时（秒），分片较大需要更长的超时时间
+const UPLOAD_TIMEOUT_SECS: u64 = 300;
+
+/// 小红书文件上传器
+pub struct XiaohongshuUploader<'a> {
+    client: &'a XiaohongshuClient,
+    http: reqwest::Client,
+}
+
+impl<'a> XiaohongshuUploader<'a> {
+    /// 创建上传器
+    pub fn new(client: &'a XiaohongshuClient) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(UPLOAD_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        Self { client, http }
+    }
+
+    /// 上传文件，返回 fileId
+    pub async fn upload_file(&self, path: &Path, scene: UploadScene) -> Result<String, String> {
+        let file_size = std::fs::metadata(path)
+            .map_err(|e| format!("读取文件信息失败: {}", e))?
+            .len();
+        if file_size == 0 {
+            return Err(format!("文件为空: {}", path.display()));
+        }
+
+        let permit = self.client.get_upload_permit(scene).await?;
+        tracing::info!("[XHS Upload] 获取上传凭证成功, fileId: {}, 大小: {} 字节", permit.file_id, file_size);
+
+        let content_type = content_type_for(path, scene);
+        if file_size <= PART_SIZE {
+            self.upload_whole(&permit, path, content_type).await?;
+        } else {
+            self.upload_multipart(&permit, path, file_size, content_type).await?;
+        }
+
+        tracing::info!("[XHS Upload] 上传完成, fileId: {}", permit.file_id);
+        Ok(permit.file_id)
+    }
+
+    /// 直接上传整个文件
+    async fn upload_whole(&self, permit: &UploadPermit, path: &Path, content_type: &str) -> Result<(), String> {
+        let buffer = std::fs::read(path).map_err(|e| format!("读取文件失败: {}", e))?;
+
+        let response = self.http.put(permit.upload_url())
+            .header("X-Cos-Security-Token", &permit.token)
+            .header("Content-Type", content_type)
+            .header("User-Agent", &self.client.user_agent)
+            .body(buffer)
+            .send()
+            .await
+            .map_err(|e| format!("上传文件失败: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("上传文件失败: HTTP {}, 响应: {}", status.as_u16(), text));
+        }
+        Ok(())
+    }
+
+    /// 分片上传
+    async fn upload_multipart(&self, permit: &UploadPermit, path: &Path, file_size: u64, content_type: &str) -> Result<(), String> {
+        let url = permit.upload_url();
+
+        // 初始化分片上传
+        let response = self.http.post(format!("{}?uploads", url))
+            .header("X-Cos-Security-Token", &permit.token)
+            .header("Content-Type", content_type)
+            .header("User-Agent", &self.client.user_agent)
+            .send()
+            .await
+            .map_err(|e| format!("初始化分片上传失败: {}", e))?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(format!("初始化分片上传失败: HTTP {}, 响应: {}", status.as_u16(), text));
+        }
+        let upload_id = extract_xml_tag(&text, "UploadId")
+            .ok_or_else(|| format!("初始化分片上传失败：未返回UploadId {}", text))?;
+
+        let total_parts = file_size.div_ceil(PART_SIZE);
+        tracing::info!("[XHS Upload] 分片上传, uploadId: {}, 分片数: {}", upload_id, total_parts);
+
+        let mut file = File::open(path).map_err(|e| format!("打开文件失败: {}", e))?;
+        let mut etags = Vec::with_capacity(total_parts as usize);
+
+        for part_number in 1..=total_parts {
+            let offset = (part_number - 1) * PART_SIZE;
+            let size = PART_SIZE.min(file_size - offset) as usize;
+            let mut buffer = vec![0u8; size];
+            file.seek(SeekFrom::Start(offset)).map_err(|e| format!("读取文件失败: {}", e))?;
+            file.read_exact(&mut buffer).map_err(|e| format!("读取文件失败: {}", e))?;
+
+            let response = self.http.put(format!("{}?partNumber={}&uploadId={}", url, part_number, upload_id))
+                .header("X-Cos-Security-Token", &permit.token)
+                .header("User-Agent", &self.client.user_agent)
+                .body(buffer)
+                .send()
+                .await
+                .map_err(|e| format!("上传分片{}失败: {}", part_number, e))?;
+
+            let status = response.status();
+            let etag = response.headers().get("ETag")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            if !status.is_success() {
+                let text = response.text().await.unwrap_or_default();
+                return Err(format!("上传分片{}失败: HTTP {}, 响应: {}", part_number, status.as_u16(), text));
+            }
+
+            tracing::debug!("[XHS Upload] 分片 {}/{} 上传成功", part_number, total_parts);
+            etags.push(etag);
+        }
+
+        // 合并分片
+        let parts: String = etags.iter().enumerate()
+            .map(|(i, etag)| format!("<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>", i + 1, etag))
+            .collect();
+        let body = format!("<CompleteMultipartUpload>{}</CompleteMultipartUpload>", parts);
+
+        let response = self.http.post(format!("{}?uploadId={}", url, upload_id))
+            .header("X-Cos-Security-Token", &permit.token)
+            .header("Content-Type", "application/xml")
+            .header("User-Agent", &self.client.user_agent)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("合并分片失败: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("合并分片失败: HTTP {}, 响应: {}", status.as_u16(), text));
+        }
+        Ok(())
+    }
+}
+
+/// 根据扩展名推断 Content-Type
+fn content_type_for(path: &Path, scene: UploadScene) -> &'static str {
+    let ext = path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    match (scene, ext.as_str()) {
+        (UploadScene::Video, "mov") => "video/quicktime",
+        (UploadScene::Video, _) => "video/mp4",
+        (UploadScene::Image, "png") => "image/png",
+        (UploadScene::Image, "webp") => "image/webp",
+        (UploadScene::Image, _) => "image/jpeg",
+    }
+}
+
+/// 读取简单XML中的标签内容
+fn extract_xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let start_tag = format!("<{}>", tag);
+    let end_tag = format!("</{}>", tag);
+    let start = xml.find(&start_tag)? + start_tag.len();
+    let end = xml[start..].find(&end_tag)? + start;
+    Some(xml[start..end].to_string())
+}
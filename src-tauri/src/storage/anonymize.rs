@@ -0,0 +1,175 @@
+// Anonymized snapshot - database copy safe to attach to bug reports
+// 匿名数据快照 - 复制一份数据库并脱敏，便于附在问题反馈中复现与数据结构/状态相关的问题：
+// - 清除账号凭证（params 中的 Cookie 等）以及保存了密钥的设置项
+// - 昵称、用户名等可识别信息替换为稳定的哈希（同一原值得到同一结果，关联关系保持不变）
+// - 素材路径只保留扩展名，不暴露本机目录和文件名
+// 表结构、状态、统计数字和同步记录保持原样
+
+use super::DatabaseManager;
+use serde::Serialize;
+use std::path::Path;
+
+/// 含有密钥或凭证的设置项，快照中直接删除
+const SENSITIVE_SETTINGS: [&str; 2] = ["ai.config", "sync.remote_config"];
+
+/// Counts of what was scrubbed in an anonymized snapshot
+/// 匿名快照的脱敏统计
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AnonymizedSnapshotSummary {
+    pub accounts: usize,
+    pub publication_tasks: usize,
+    pub publication_details: usize,
+    pub comments: usize,
+    pub removed_settings: usize,
+}
+
+/// 可识别信息替换为 `{prefix}_{md5 前 8 位}`，空值保持为空
+pub fn anonymize_value(prefix: &str, value: &str) -> String {
+    if value.is_empty() {
+        return String::new();
+    }
+    let digest = format!("{:x}", md5::compute(value));
+    format!("{}_{}", prefix, &digest[..8])
+}
+
+/// 素材路径替换为 `media_{哈希}.{扩展名}`：去掉目录和文件名，保留扩展名和不同路径之间的区分
+pub fn anonymize_media_path(path: &str) -> String {
+    if path.is_empty() {
+        return String::new();
+    }
+    let name = anonymize_value("media", path);
+    match Path::new(path).extension() {
+        Some(ext) => format!("{}.{}", name, ext.to_string_lossy()),
+        None => name,
+    }
+}
+
+/// 逐行读取 `id` 和需要脱敏的列，在 Rust 中计算新值后写回（未启用 SQLite 自定义函数）
+fn scrub_columns(
+    tx: &rusqlite::Transaction,
+    table: &str,
+    columns: &[&str],
+    scrub: impl Fn(&str, &str) -> String,
+) -> Result<usize, rusqlite::Error> {
+    let select = format!(
+        "SELECT id, {} FROM {}",
+        columns.iter().map(|c| format!("COALESCE({}, '')", c)).collect::<Vec<_>>().join(", "),
+        table,
+    );
+    let rows: Vec<(String, Vec<String>)> = tx.prepare(&select)?
+        .query_map([], |row| {
+            let values = (0..columns.len())
+                .map(|i| row.get(i + 1))
+                .collect::<Result<Vec<String>, _>>()?;
+            Ok((row.get(0)?, values))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let update = format!(
+        "UPDATE {} SET {} WHERE id = ?",
+        table,
+        columns.iter().map(|c| format!("{} = ?", c)).collect::<Vec<_>>().join(", "),
+    );
+    let mut stmt = tx.prepare(&update)?;
+    for (id, values) in &rows {
+        let mut params: Vec<String> = columns.iter().zip(values)
+            .map(|(column, value)| scrub(column, value))
+            .collect();
+        params.push(id.clone());
+        stmt.execute(rusqlite::params_from_iter(params))?;
+    }
+
+    Ok(rows.len())
+}
+
+impl DatabaseManager {
+    /// Write an anonymized copy of the database to `output`
+    /// 导出匿名数据快照：先用 VACUUM INTO 复制数据库，再在副本上脱敏，原数据库不受影响。
+    /// VACUUM INTO 要求 `output` 不存在
+    pub fn export_anonymized_snapshot(&self, output: &Path) -> Result<AnonymizedSnapshotSummary, rusqlite::Error> {
+        {
+            let conn = self.get_connection()?;
+            conn.execute("VACUUM INTO ?", [output.to_string_lossy().to_string()])?;
+        }
+
+        let mut conn = rusqlite::Connection::open(output)?;
+        let tx = conn.transaction()?;
+
+        // 脱敏会触发远程同步触发器，先备份变更记录，完成后还原，保持同步状态与原库一致
+        tx.execute_batch(r#"
+            CREATE TEMP TABLE sync_changes_backup AS SELECT * FROM sync_changes;
+        "#)?;
+
+        let accounts = scrub_columns(&tx, "accounts", &["username", "nickname"], |column, value| {
+            anonymize_value(if column == "username" { "user" } else { "nickname" }, value)
+        })?;
+        tx.execute("UPDATE accounts SET params = '{}', avatar_url = ''", [])?;
+
+        let publication_tasks = scrub_columns(
+            &tx,
+            "publication_tasks",
+            &["video_path", "cover_path", "vertical_video_path", "horizontal_video_path"],
+            |_, value| anonymize_media_path(value),
+        )?;
+        let publication_details = scrub_columns(&tx, "publication_accounts", &["account_name"], |_, value| {
+            anonymize_value("nickname", value)
+        })?;
+        scrub_columns(&tx, "account_reassignments", &["from_account_name", "to_account_name"], |_, value| {
+            anonymize_value("nickname", value)
+        })?;
+        let comments = scrub_columns(&tx, "comments", &["user_id", "user_nickname"], |column, value| {
+            anonymize_value(if column == "user_id" { "uid" } else { "nickname" }, value)
+        })?;
+        tx.execute("UPDATE comments SET user_avatar = ''", [])?;
+
+        let removed_settings = tx.execute(
+            &format!(
+                "DELETE FROM app_settings WHERE key IN ({})",
+                SENSITIVE_SETTINGS.iter().map(|_| "?").collect::<Vec<_>>().join(", "),
+            ),
+            rusqlite::params_from_iter(SENSITIVE_SETTINGS),
+        )?;
+
+        tx.execute_batch(r#"
+            DELETE FROM sync_changes;
+            INSERT INTO sync_changes SELECT * FROM temp.sync_changes_backup;
+            DROP TABLE temp.sync_changes_backup;
+        "#)?;
+        tx.commit()?;
+
+        // 再次 VACUUM，清除空闲页中残留的原始数据
+        conn.execute("VACUUM", [])?;
+
+        Ok(AnonymizedSnapshotSummary {
+            accounts,
+            publication_tasks,
+            publication_details,
+            comments,
+            removed_settings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anonymize_value() {
+        assert_eq!(anonymize_value("nickname", ""), "");
+        assert_eq!(anonymize_value("nickname", "小明"), anonymize_value("nickname", "小明"));
+        assert_ne!(anonymize_value("nickname", "小明"), anonymize_value("nickname", "小红"));
+        assert!(anonymize_value("user", "123456").starts_with("user_"));
+        assert_eq!(anonymize_value("user", "123456").len(), "user_".len() + 8);
+    }
+
+    #[test]
+    fn test_anonymize_media_path() {
+        assert_eq!(anonymize_media_path(""), "");
+        let path = anonymize_media_path("/Users/me/Videos/客户A_新品.mp4");
+        assert!(path.starts_with("media_") && path.ends_with(".mp4"));
+        assert!(!path.contains("客户A"));
+        assert!(!anonymize_media_path("/tmp/cover").contains('.'));
+    }
+}
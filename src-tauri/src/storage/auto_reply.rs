@@ -0,0 +1,219 @@
+// Auto-reply rules and sent reply log
+// 评论自动回复规则及回复记录存储
+
+use super::DatabaseManager;
+use crate::core::{AutoReplyLog, AutoReplyRule, Comment};
+
+const RULE_COLUMNS: &str = "id, account_id, name, keywords, reply_template, enabled, max_replies_per_hour, created_at, updated_at";
+
+const LOG_COLUMNS: &str = "id, rule_id, account_id, aweme_id, comment_id, comment_content, reply_content, success, error, created_at";
+
+fn map_rule(row: &rusqlite::Row) -> rusqlite::Result<AutoReplyRule> {
+    let keywords: String = row.get(3)?;
+    Ok(AutoReplyRule {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        name: row.get(2)?,
+        keywords: serde_json::from_str(&keywords).unwrap_or_default(),
+        reply_template: row.get(4)?,
+        enabled: row.get::<_, i64>(5)? != 0,
+        max_replies_per_hour: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+fn map_log(row: &rusqlite::Row) -> rusqlite::Result<AutoReplyLog> {
+    let error: String = row.get::<_, Option<String>>(8)?.unwrap_or_default();
+    Ok(AutoReplyLog {
+        id: row.get(0)?,
+        rule_id: row.get(1)?,
+        account_id: row.get(2)?,
+        aweme_id: row.get(3)?,
+        comment_id: row.get(4)?,
+        comment_content: row.get(5)?,
+        reply_content: row.get(6)?,
+        success: row.get::<_, i64>(7)? != 0,
+        error: if error.is_empty() { None } else { Some(error) },
+        created_at: row.get(9)?,
+    })
+}
+
+impl DatabaseManager {
+    /// Save (create or update) an auto-reply rule
+    /// 保存自动回复规则
+    pub fn save_auto_reply_rule(&self, rule: &AutoReplyRule) -> Result<(), rusqlite::Error> {
+        self.write("save_auto_reply_rule", |conn| {
+            conn.execute(&format!(
+                "INSERT OR REPLACE INTO auto_reply_rules ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)", RULE_COLUMNS
+            ), rusqlite::params![
+                rule.id,
+                rule.account_id,
+                rule.name,
+                serde_json::to_string(&rule.keywords).unwrap_or_else(|_| "[]".to_string()),
+                rule.reply_template,
+                rule.enabled as i64,
+                rule.max_replies_per_hour,
+                rule.created_at,
+                rule.updated_at,
+            ])?;
+
+            Ok(())
+        })
+    }
+
+    /// Get an auto-reply rule by id
+    /// 根据ID获取自动回复规则
+    pub fn get_auto_reply_rule(&self, rule_id: &str) -> Result<Option<AutoReplyRule>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM auto_reply_rules WHERE id = ?", RULE_COLUMNS))?;
+        match stmt.query_row([rule_id], map_rule) {
+            Ok(rule) => Ok(Some(rule)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get auto-reply rules, optionally for a single account
+    /// 获取自动回复规则（可按账号过滤）
+    pub fn get_auto_reply_rules(&self, account_id: Option<&str>) -> Result<Vec<AutoReplyRule>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let rules = match account_id {
+            Some(account_id) => {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {} FROM auto_reply_rules WHERE account_id = ? ORDER BY created_at", RULE_COLUMNS
+                ))?;
+                let rules = stmt.query_map([account_id], map_rule)?.filter_map(|r| r.ok()).collect();
+                rules
+            }
+            None => {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {} FROM auto_reply_rules ORDER BY account_id, created_at", RULE_COLUMNS
+                ))?;
+                let rules = stmt.query_map([], map_rule)?.filter_map(|r| r.ok()).collect();
+                rules
+            }
+        };
+
+        Ok(rules)
+    }
+
+    /// Get all enabled auto-reply rules
+    /// 获取所有启用的自动回复规则
+    pub fn get_enabled_auto_reply_rules(&self) -> Result<Vec<AutoReplyRule>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM auto_reply_rules WHERE enabled = 1 ORDER BY created_at", RULE_COLUMNS
+        ))?;
+        let rules = stmt.query_map([], map_rule)?.filter_map(|r| r.ok()).collect();
+
+        Ok(rules)
+    }
+
+    /// Delete an auto-reply rule (its reply log is kept)
+    /// 删除自动回复规则（保留历史回复记录）
+    pub fn delete_auto_reply_rule(&self, rule_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("delete_auto_reply_rule", |conn| {
+            let deleted = conn.execute("DELETE FROM auto_reply_rules WHERE id = ?", [rule_id])?;
+            Ok(deleted > 0)
+        })
+    }
+
+    /// Record a sent (or failed) auto-reply
+    /// 记录一次自动回复
+    pub fn save_auto_reply_log(&self, log: &AutoReplyLog) -> Result<(), rusqlite::Error> {
+        self.write("save_auto_reply_log", |conn| {
+            conn.execute(&format!(
+                "INSERT INTO auto_reply_logs ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)", LOG_COLUMNS
+            ), rusqlite::params![
+                log.id,
+                log.rule_id,
+                log.account_id,
+                log.aweme_id,
+                log.comment_id,
+                log.comment_content,
+                log.reply_content,
+                log.success as i64,
+                log.error.as_deref().unwrap_or(""),
+                log.created_at,
+            ])?;
+
+            Ok(())
+        })
+    }
+
+    /// Get auto-reply logs, newest first
+    /// 获取自动回复记录（按时间倒序，可按账号过滤）
+    pub fn get_auto_reply_logs(&self, account_id: Option<&str>, limit: usize) -> Result<Vec<AutoReplyLog>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let logs = match account_id {
+            Some(account_id) => {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {} FROM auto_reply_logs WHERE account_id = ? ORDER BY created_at DESC LIMIT ?", LOG_COLUMNS
+                ))?;
+                let logs = stmt.query_map(rusqlite::params![account_id, limit as i64], map_log)?
+                    .filter_map(|r| r.ok()).collect();
+                logs
+            }
+            None => {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {} FROM auto_reply_logs ORDER BY created_at DESC LIMIT ?", LOG_COLUMNS
+                ))?;
+                let logs = stmt.query_map([limit as i64], map_log)?.filter_map(|r| r.ok()).collect();
+                logs
+            }
+        };
+
+        Ok(logs)
+    }
+
+    /// Number of replies a rule has sent since the given time (for the hourly cap)
+    /// 统计规则在指定时间之后的回复次数（用于每小时上限）
+    pub fn count_auto_replies_since(&self, rule_id: &str, since: &str) -> Result<i64, rusqlite::Error> {
+        let conn = self.get_connection()?;
+        conn.query_row(
+            "SELECT COUNT(*) FROM auto_reply_logs WHERE rule_id = ? AND success = 1 AND created_at >= ?",
+            [rule_id, since],
+            |row| row.get(0),
+        )
+    }
+
+    /// Comments of an item that the auto-reply worker has not handled yet
+    /// 获取作品下尚未被自动回复处理过的评论
+    pub fn get_unreplied_comments(&self, account_id: &str, aweme_id: &str) -> Result<Vec<Comment>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(r#"
+            SELECT c.id, c.account_id, c.aweme_id, c.comment_id, c.user_id, c.user_nickname, c.user_avatar,
+                   c.content, c.like_count, c.reply_count, c.create_time, c.status, c.created_at, c.tags
+            FROM comments c
+            WHERE c.account_id = ? AND c.aweme_id = ?
+              AND NOT EXISTS (SELECT 1 FROM auto_reply_logs l WHERE l.comment_id = c.comment_id)
+            ORDER BY c.create_time
+        "#)?;
+        let comments = stmt.query_map([account_id, aweme_id], |row| {
+            Ok(Comment {
+                id: row.get(0)?,
+                account_id: row.get(1)?,
+                aweme_id: row.get(2)?,
+                comment_id: row.get(3)?,
+                user_id: row.get(4)?,
+                user_nickname: row.get(5)?,
+                user_avatar: row.get(6)?,
+                content: row.get(7)?,
+                like_count: row.get(8)?,
+                reply_count: row.get(9)?,
+                create_time: row.get(10)?,
+                status: Self::parse_comment_status(row.get(11)?),
+                created_at: row.get(12)?,
+                tags: Self::decode_tags(row.get(13)?),
+            })
+        })?.filter_map(|r| r.ok()).collect();
+
+        Ok(comments)
+    }
+}
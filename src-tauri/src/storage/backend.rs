@@ -0,0 +1,175 @@
+// Storage backend abstraction
+// 存储后端抽象：命令层通过 StorageBackend 访问账号、任务、评论和配置，
+// 默认实现为本地 SQLite（DatabaseManager），以后可接入服务端 / Postgres 让团队多台电脑共享同一份数据
+
+use std::fmt::Debug;
+use thiserror::Error;
+use super::{DatabaseManager, ExtractorConfig};
+use crate::core::{
+    Comment, PlatformType, PublicationAccountDetail, PublicationStatus, PublicationTask,
+    PublicationTaskWithAccounts, UserAccount,
+};
+
+/// Storage backend errors
+/// 存储后端错误
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("Backend error: {0}")]
+    Backend(String),
+}
+
+pub type StorageResult<T> = Result<T, StorageError>;
+
+/// Storage backend for the shared dataset
+/// 存储后端：账号、发布任务、评论、配置的读写接口
+pub trait StorageBackend: Send + Sync + Debug {
+    /// 后端名称（用于日志和设置页展示）
+    fn backend_name(&self) -> &'static str;
+
+    // ---- 账号 ----
+    fn save_account(&self, account: &UserAccount) -> StorageResult<()>;
+    fn get_account(&self, account_id: &str) -> StorageResult<Option<UserAccount>>;
+    fn get_all_accounts(&self) -> StorageResult<Vec<UserAccount>>;
+    fn get_accounts_by_platform(&self, platform: PlatformType) -> StorageResult<Vec<UserAccount>>;
+    fn set_account_on_hold(&self, account_id: &str, on_hold: bool) -> StorageResult<bool>;
+    fn delete_account(&self, account_id: &str) -> StorageResult<bool>;
+
+    // ---- 发布任务 ----
+    fn save_publication_with_accounts(&self, task: &PublicationTask, accounts: &[PublicationAccountDetail]) -> StorageResult<()>;
+    fn get_publication_task(&self, task_id: &str) -> StorageResult<Option<PublicationTask>>;
+    fn get_publication_task_with_accounts(&self, task_id: &str) -> StorageResult<Option<PublicationTaskWithAccounts>>;
+    fn get_all_publication_tasks(&self) -> StorageResult<Vec<PublicationTaskWithAccounts>>;
+    fn get_publication_account_detail(&self, detail_id: &str) -> StorageResult<Option<PublicationAccountDetail>>;
+    fn update_publication_account_status(
+        &self,
+        detail_id: &str,
+        status: PublicationStatus,
+        publish_url: Option<String>,
+        message: Option<String>,
+        item_id: Option<String>,
+    ) -> StorageResult<()>;
+    fn delete_publication_task(&self, task_id: &str) -> StorageResult<bool>;
+
+    // ---- 评论 ----
+    fn save_comments_batch(&self, comments: &[Comment]) -> StorageResult<usize>;
+    fn get_comments_by_aweme_id(&self, aweme_id: &str) -> StorageResult<Vec<Comment>>;
+    fn get_comment_count(&self, aweme_id: &str) -> StorageResult<i64>;
+    fn delete_comments_by_aweme_id(&self, aweme_id: &str) -> StorageResult<bool>;
+
+    // ---- 配置 ----
+    fn get_setting(&self, key: &str) -> StorageResult<Option<String>>;
+    fn set_setting(&self, key: &str, value: &str) -> StorageResult<()>;
+    fn save_extractor_config(&self, config: &ExtractorConfig) -> StorageResult<()>;
+    fn get_extractor_config(&self, platform_id: &str) -> StorageResult<Option<ExtractorConfig>>;
+    fn get_all_extractor_configs(&self) -> StorageResult<Vec<ExtractorConfig>>;
+    fn delete_extractor_config(&self, platform_id: &str) -> StorageResult<bool>;
+}
+
+/// 默认后端：本地 SQLite，直接委托给 DatabaseManager 的同名方法
+impl StorageBackend for DatabaseManager {
+    fn backend_name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn save_account(&self, account: &UserAccount) -> StorageResult<()> {
+        Ok(DatabaseManager::save_account(self, account)?)
+    }
+
+    fn get_account(&self, account_id: &str) -> StorageResult<Option<UserAccount>> {
+        Ok(DatabaseManager::get_account(self, account_id)?)
+    }
+
+    fn get_all_accounts(&self) -> StorageResult<Vec<UserAccount>> {
+        Ok(DatabaseManager::get_all_accounts(self)?)
+    }
+
+    fn get_accounts_by_platform(&self, platform: PlatformType) -> StorageResult<Vec<UserAccount>> {
+        Ok(DatabaseManager::get_accounts_by_platform(self, platform)?)
+    }
+
+    fn set_account_on_hold(&self, account_id: &str, on_hold: bool) -> StorageResult<bool> {
+        Ok(DatabaseManager::set_account_on_hold(self, account_id, on_hold)?)
+    }
+
+    fn delete_account(&self, account_id: &str) -> StorageResult<bool> {
+        Ok(DatabaseManager::delete_account(self, account_id)?)
+    }
+
+    fn save_publication_with_accounts(&self, task: &PublicationTask, accounts: &[PublicationAccountDetail]) -> StorageResult<()> {
+        Ok(DatabaseManager::save_publication_with_accounts(self, task, accounts)?)
+    }
+
+    fn get_publication_task(&self, task_id: &str) -> StorageResult<Option<PublicationTask>> {
+        Ok(DatabaseManager::get_publication_task(self, task_id)?)
+    }
+
+    fn get_publication_task_with_accounts(&self, task_id: &str) -> StorageResult<Option<PublicationTaskWithAccounts>> {
+        Ok(DatabaseManager::get_publication_task_with_accounts(self, task_id)?)
+    }
+
+    fn get_all_publication_tasks(&self) -> StorageResult<Vec<PublicationTaskWithAccounts>> {
+        Ok(DatabaseManager::get_all_publication_tasks(self)?)
+    }
+
+    fn get_publication_account_detail(&self, detail_id: &str) -> StorageResult<Option<PublicationAccountDetail>> {
+        Ok(DatabaseManager::get_publication_account_detail(self, detail_id)?)
+    }
+
+    fn update_publication_account_status(
+        &self,
+        detail_id: &str,
+        status: PublicationStatus,
+        publish_url: Option<String>,
+        message: Option<String>,
+        item_id: Option<String>,
+    ) -> StorageResult<()> {
+        Ok(DatabaseManager::update_publication_account_status(self, detail_id, status, publish_url, message, item_id)?)
+    }
+
+    fn delete_publication_task(&self, task_id: &str) -> StorageResult<bool> {
+        Ok(DatabaseManager::delete_publication_task(self, task_id)?)
+    }
+
+    fn save_comments_batch(&self, comments: &[Comment]) -> StorageResult<usize> {
+        Ok(DatabaseManager::save_comments_batch(self, comments)?)
+    }
+
+    fn get_comments_by_aweme_id(&self, aweme_id: &str) -> StorageResult<Vec<Comment>> {
+        Ok(DatabaseManager::get_comments_by_aweme_id(self, aweme_id)?)
+    }
+
+    fn get_comment_count(&self, aweme_id: &str) -> StorageResult<i64> {
+        Ok(DatabaseManager::get_comment_count(self, aweme_id)?)
+    }
+
+    fn delete_comments_by_aweme_id(&self, aweme_id: &str) -> StorageResult<bool> {
+        Ok(DatabaseManager::delete_comments_by_aweme_id(self, aweme_id)?)
+    }
+
+    fn get_setting(&self, key: &str) -> StorageResult<Option<String>> {
+        Ok(DatabaseManager::get_setting(self, key)?)
+    }
+
+    fn set_setting(&self, key: &str, value: &str) -> StorageResult<()> {
+        Ok(DatabaseManager::set_setting(self, key, value)?)
+    }
+
+    fn save_extractor_config(&self, config: &ExtractorConfig) -> StorageResult<()> {
+        Ok(DatabaseManager::save_extractor_config(self, config)?)
+    }
+
+    fn get_extractor_config(&self, platform_id: &str) -> StorageResult<Option<ExtractorConfig>> {
+        Ok(DatabaseManager::get_extractor_config(self, platform_id)?)
+    }
+
+    fn get_all_extractor_configs(&self) -> StorageResult<Vec<ExtractorConfig>> {
+        Ok(DatabaseManager::get_all_extractor_configs(self)?)
+    }
+
+    fn delete_extractor_config(&self, platform_id: &str) -> StorageResult<bool> {
+        Ok(DatabaseManager::delete_extractor_config(self, platform_id)?)
+    }
+}
@@ -0,0 +1,190 @@
+// Backup - dump/restore user data for migrating between machines
+// 备份 - 导出/恢复账号（含凭证）、分组、发布任务与明细、提取引擎配置和评论，
+// 按表逐行导出为 JSON（列名 → 值），恢复时只写入当前表结构中存在的列，
+// 旧版本导出的备份在新增列后仍可导入。加密由 core::backup 负责
+
+use super::DatabaseManager;
+use rusqlite::types::{Value, ValueRef};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// 备份格式版本
+pub const BACKUP_VERSION: u32 = 1;
+
+/// 备份包含的表（按恢复顺序）
+pub const BACKUP_TABLES: [&str; 8] = [
+    "account_groups",
+    "accounts",
+    "extractor_configs",
+    "publication_tasks",
+    "publication_accounts",
+    "task_notes",
+    "account_reassignments",
+    "comments",
+];
+
+/// accounts 表中的凭证列（库中为密文，备份中为明文）
+const CREDENTIAL_COLUMN: &str = "params";
+
+/// 一行数据：列名 → 值
+pub type BackupRow = serde_json::Map<String, serde_json::Value>;
+
+/// Plain (unencrypted) backup contents
+/// 备份内容（明文）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupData {
+    pub version: u32,
+    pub created_at: String,
+    /// 表名 → 行
+    pub tables: BTreeMap<String, Vec<BackupRow>>,
+}
+
+/// Row counts written to / restored from a backup
+/// 备份导出/恢复的行数
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BackupSummary {
+    pub created_at: String,
+    pub accounts: usize,
+    pub account_groups: usize,
+    pub publication_tasks: usize,
+    pub publication_details: usize,
+    pub extractor_configs: usize,
+    pub comments: usize,
+}
+
+impl BackupSummary {
+    pub fn from_counts(created_at: &str, counts: &BTreeMap<String, usize>) -> Self {
+        let count = |table: &str| counts.get(table).copied().unwrap_or(0);
+        Self {
+            created_at: created_at.to_string(),
+            accounts: count("accounts"),
+            account_groups: count("account_groups"),
+            publication_tasks: count("publication_tasks"),
+            publication_details: count("publication_accounts"),
+            extractor_configs: count("extractor_configs"),
+            comments: count("comments"),
+        }
+    }
+}
+
+/// SQLite 值转为 JSON（备份的表均无 BLOB 列，BLOB 按空值处理）
+fn to_json(value: ValueRef) -> serde_json::Value {
+    match value {
+        ValueRef::Null | ValueRef::Blob(_) => serde_json::Value::Null,
+        ValueRef::Integer(i) => i.into(),
+        ValueRef::Real(f) => serde_json::Number::from_f64(f)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned().into(),
+    }
+}
+
+/// JSON 转回 SQLite 值
+fn to_sql(value: &serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Integer(*b as i64),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Real(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => Value::Text(s.clone()),
+        other => Value::Text(other.to_string()),
+    }
+}
+
+impl DatabaseManager {
+    /// Dump all backed-up tables
+    /// 导出备份表的全部数据
+    pub fn dump_backup_data(&self) -> Result<BackupData, rusqlite::Error> {
+        let conn = self.get_connection()?;
+        let mut tables = BTreeMap::new();
+
+        for table in BACKUP_TABLES {
+            let mut stmt = conn.prepare(&format!("SELECT * FROM {}", table))?;
+            let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+            let rows = stmt.query_map([], |row| {
+                let mut map = BackupRow::new();
+                for (i, column) in columns.iter().enumerate() {
+                    map.insert(column.clone(), to_json(row.get_ref(i)?));
+                }
+                // 凭证用本机密钥加密，备份中存明文（整个备份再由备份密码加密）
+                if let Some(serde_json::Value::String(params)) = map.get_mut(CREDENTIAL_COLUMN).filter(|_| table == "accounts") {
+                    *params = self.decrypt_params(params)?;
+                }
+                Ok(map)
+            })?
+                .collect::<Result<Vec<_>, _>>()?;
+            tables.insert(table.to_string(), rows);
+        }
+
+        Ok(BackupData {
+            version: BACKUP_VERSION,
+            created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            tables,
+        })
+    }
+
+    /// Restore backup rows, replacing rows with the same id
+    /// 恢复备份：同 id（或唯一键冲突）的行被覆盖，本机其他数据保留；整体在一个事务中完成。
+    /// 返回各表写入的行数
+    pub fn restore_backup_data(&self, data: &BackupData) -> Result<BTreeMap<String, usize>, rusqlite::Error> {
+        self.write("restore_backup_data", |conn| {
+            let tx = conn.transaction()?;
+            let mut counts = BTreeMap::new();
+
+            for table in BACKUP_TABLES {
+                let Some(rows) = data.tables.get(table) else { continue };
+
+                let existing: HashSet<String> = tx.prepare(&format!("PRAGMA table_info({})", table))?
+                    .query_map([], |row| row.get::<_, String>(1))?
+                    .filter_map(|r| r.ok())
+                    .collect();
+
+                let mut restored = 0;
+                for row in rows {
+                    let (columns, mut values): (Vec<&String>, Vec<Value>) = row.iter()
+                        .filter(|(column, _)| existing.contains(*column))
+                        .map(|(column, value)| (column, to_sql(value)))
+                        .unzip();
+                    if table == "accounts" {
+                        if let Some(i) = columns.iter().position(|c| c.as_str() == CREDENTIAL_COLUMN) {
+                            if let Value::Text(params) = &values[i] {
+                                values[i] = Value::Text(self.encrypt_params(params)?);
+                            }
+                        }
+                    }
+                    if columns.is_empty() {
+                        continue;
+                    }
+
+                    let sql = format!(
+                        "INSERT OR REPLACE INTO {} ({}) VALUES ({})",
+                        table,
+                        columns.iter().map(|c| c.as_str()).collect::<Vec<_>>().join(", "),
+                        vec!["?"; columns.len()].join(", "),
+                    );
+                    restored += tx.execute(&sql, rusqlite::params_from_iter(values))?;
+                }
+                counts.insert(table.to_string(), restored);
+            }
+
+            tx.commit()?;
+            Ok(counts)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_round_trip() {
+        assert_eq!(to_sql(&to_json(ValueRef::Integer(42))), Value::Integer(42));
+        assert_eq!(to_sql(&to_json(ValueRef::Real(1.5))), Value::Real(1.5));
+        assert_eq!(to_sql(&to_json(ValueRef::Text("抖音".as_bytes()))), Value::Text("抖音".to_string()));
+        assert_eq!(to_sql(&to_json(ValueRef::Null)), Value::Null);
+        assert_eq!(to_sql(&serde_json::json!(true)), Value::Integer(1));
+    }
+}
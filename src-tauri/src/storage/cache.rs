@@ -0,0 +1,257 @@
+// Cache manager - temp files produced by transcoding, thumbnails and debug bundles
+// 缓存管理器 - 转码、缩略图、调试包等临时文件统一放在 <app_data>/cache 下
+//
+// 每个分类有独立的容量上限，超出时按最后修改时间从旧到新删除，
+// 避免应用数据目录悄悄占满磁盘。
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// 缓存根目录名（位于应用数据目录下）
+const CACHE_DIR: &str = "cache";
+
+/// 缓存分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheCategory {
+    /// 视频转码产物
+    Transcode,
+    /// 封面 / 缩略图
+    Thumbnails,
+    /// 调试包（日志、请求快照等）
+    Debug,
+}
+
+impl CacheCategory {
+    pub const ALL: [CacheCategory; 3] = [
+        CacheCategory::Transcode,
+        CacheCategory::Thumbnails,
+        CacheCategory::Debug,
+    ];
+
+    /// 分类对应的子目录名
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            CacheCategory::Transcode => "transcode",
+            CacheCategory::Thumbnails => "thumbnails",
+            CacheCategory::Debug => "debug",
+        }
+    }
+
+    /// 分类容量上限（字节）
+    pub fn size_cap(&self) -> u64 {
+        match self {
+            CacheCategory::Transcode => 5 * 1024 * 1024 * 1024,
+            CacheCategory::Thumbnails => 500 * 1024 * 1024,
+            CacheCategory::Debug => 200 * 1024 * 1024,
+        }
+    }
+
+    /// 从字符串解析分类
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.dir_name() == s)
+    }
+}
+
+/// 单个分类的占用情况
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheCategoryUsage {
+    pub category: CacheCategory,
+    pub size_bytes: u64,
+    pub file_count: usize,
+    pub size_cap: u64,
+}
+
+/// 清理结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CacheCleanupResult {
+    /// 释放的空间（字节）
+    pub reclaimed_bytes: u64,
+    /// 删除的文件数
+    pub removed_files: usize,
+}
+
+impl CacheCleanupResult {
+    fn merge(&mut self, other: CacheCleanupResult) {
+        self.reclaimed_bytes += other.reclaimed_bytes;
+        self.removed_files += other.removed_files;
+    }
+}
+
+/// 缓存文件条目
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+/// Cache manager
+/// 缓存管理器
+#[derive(Debug, Clone)]
+pub struct CacheManager {
+    root: PathBuf,
+}
+
+impl CacheManager {
+    /// 基于应用数据目录创建缓存管理器
+    pub fn new(base_path: &Path) -> Self {
+        Self { root: base_path.join(CACHE_DIR) }
+    }
+
+    /// 获取分类目录（不存在时创建），供转码 / 缩略图等模块写入临时文件
+    pub fn dir_for(&self, category: CacheCategory) -> std::io::Result<PathBuf> {
+        let dir = self.root.join(category.dir_name());
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// 各分类占用情况
+    pub fn usage(&self) -> Vec<CacheCategoryUsage> {
+        CacheCategory::ALL.into_iter().map(|category| {
+            let entries = collect_entries(&self.root.join(category.dir_name()));
+            CacheCategoryUsage {
+                category,
+                size_bytes: entries.iter().map(|e| e.size).sum(),
+                file_count: entries.len(),
+                size_cap: category.size_cap(),
+            }
+        }).collect()
+    }
+
+    /// 按容量上限淘汰旧文件（启动时调用）
+    pub fn enforce_limits(&self) -> CacheCleanupResult {
+        let mut result = CacheCleanupResult::default();
+        for category in CacheCategory::ALL {
+            let entries = collect_entries(&self.root.join(category.dir_name()));
+            let victims = select_evictions(entries, category.size_cap());
+            result.merge(remove_entries(&victims));
+        }
+        result
+    }
+
+    /// 清空缓存；`category` 为空时清空全部分类
+    pub fn clear(&self, category: Option<CacheCategory>) -> CacheCleanupResult {
+        let categories = match category {
+            Some(c) => vec![c],
+            None => CacheCategory::ALL.to_vec(),
+        };
+
+        let mut result = CacheCleanupResult::default();
+        for category in categories {
+            let dir = self.root.join(category.dir_name());
+            result.merge(remove_entries(&collect_entries(&dir)));
+            remove_empty_dirs(&dir);
+        }
+        result
+    }
+}
+
+/// 递归收集目录下的所有文件
+fn collect_entries(dir: &Path) -> Vec<CacheEntry> {
+    let mut entries = Vec::new();
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return entries;
+    };
+
+    for entry in read_dir.flatten() {
+        let Ok(metadata) = entry.metadata() else { continue };
+        if metadata.is_dir() {
+            entries.extend(collect_entries(&entry.path()));
+        } else {
+            entries.push(CacheEntry {
+                path: entry.path(),
+                size: metadata.len(),
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
+        }
+    }
+    entries
+}
+
+/// 选出需要淘汰的文件：从最旧的开始删，直到总大小不超过上限
+fn select_evictions(mut entries: Vec<CacheEntry>, cap: u64) -> Vec<CacheEntry> {
+    let mut total: u64 = entries.iter().map(|e| e.size).sum();
+    if total <= cap {
+        return Vec::new();
+    }
+
+    entries.sort_by_key(|e| e.modified);
+    let mut victims = Vec::new();
+    for entry in entries {
+        if total <= cap {
+            break;
+        }
+        total -= entry.size;
+        victims.push(entry);
+    }
+    victims
+}
+
+/// 删除文件，仅统计删除成功的部分
+fn remove_entries(entries: &[CacheEntry]) -> CacheCleanupResult {
+    let mut result = CacheCleanupResult::default();
+    for entry in entries {
+        match std::fs::remove_file(&entry.path) {
+            Ok(_) => {
+                result.reclaimed_bytes += entry.size;
+                result.removed_files += 1;
+            }
+            Err(e) => {
+                tracing::warn!("[Cache] 删除缓存文件失败 {}: {}", entry.path.display(), e);
+            }
+        }
+    }
+    result
+}
+
+/// 删除分类目录下残留的空子目录（保留分类目录本身）
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else { return };
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path);
+            std::fs::remove_dir(&path).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn entry(name: &str, size: u64, age_secs: u64) -> CacheEntry {
+        CacheEntry {
+            path: PathBuf::from(name),
+            size,
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 - age_secs),
+        }
+    }
+
+    #[test]
+    fn test_select_evictions_oldest_first() {
+        let entries = vec![
+            entry("new", 40, 10),
+            entry("old", 30, 300),
+            entry("mid", 50, 100),
+        ];
+        let victims = select_evictions(entries, 60);
+        let names: Vec<_> = victims.iter().map(|e| e.path.to_string_lossy().to_string()).collect();
+        assert_eq!(names, vec!["old", "mid"]);
+    }
+
+    #[test]
+    fn test_select_evictions_under_cap() {
+        let entries = vec![entry("a", 10, 1), entry("b", 10, 2)];
+        assert!(select_evictions(entries, 20).is_empty());
+    }
+
+    #[test]
+    fn test_category_parse() {
+        assert_eq!(CacheCategory::parse("transcode"), Some(CacheCategory::Transcode));
+        assert_eq!(CacheCategory::parse("unknown"), None);
+    }
+}
@@ -0,0 +1,230 @@
+// Comment export - CSV / Excel writers
+// 评论导出：单个作品可导出为 CSV；Excel 统一导出为带汇总表的交付物
+// （汇总表每个作品一行，之后每个作品一个明细表，单个作品时同样如此）
+
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use rust_xlsxwriter::{Color, Format, FormatAlign, FormatBorder, Workbook, Worksheet, XlsxError};
+use crate::core::Comment;
+
+/// 工作表名称最大长度（Excel 限制）
+const SHEET_NAME_MAX_LEN: usize = 31;
+
+/// 每写入多少行回调一次进度
+const PROGRESS_STEP: usize = 500;
+
+/// One exported item with its comments
+/// 单个作品的导出数据
+#[derive(Debug, Clone)]
+pub struct CommentExportItem {
+    pub aweme_id: String,
+    pub title: String,
+    pub account_name: String,
+    pub published_at: String,
+    pub comments: Vec<Comment>,
+}
+
+const SUMMARY_HEADERS: &[(&str, f64)] = &[
+    ("作品ID", 22.0),
+    ("标题", 36.0),
+    ("发布账号", 16.0),
+    ("发布时间", 20.0),
+    ("评论数", 10.0),
+    ("评论点赞总数", 14.0),
+    ("评论回复总数", 14.0),
+    ("最早评论时间", 20.0),
+    ("最新评论时间", 20.0),
+];
+
+const DETAIL_HEADERS: &[(&str, f64)] = &[
+    ("评论时间", 20.0),
+    ("用户昵称", 18.0),
+    ("用户ID", 22.0),
+    ("评论内容", 60.0),
+    ("点赞数", 10.0),
+    ("回复数", 10.0),
+    ("标签", 16.0),
+];
+
+fn header_format() -> Format {
+    Format::new()
+        .set_bold()
+        .set_font_color(Color::White)
+        .set_background_color(Color::RGB(0x4472C4))
+        .set_border(FormatBorder::Thin)
+        .set_align(FormatAlign::Center)
+}
+
+fn write_headers(sheet: &mut Worksheet, headers: &[(&str, f64)], format: &Format) -> Result<(), XlsxError> {
+    for (col, (name, width)) in headers.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *name, format)?;
+        sheet.set_column_width(col as u16, *width)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    Ok(())
+}
+
+/// 生成合法且不重复的工作表名称
+fn sheet_name(aweme_id: &str, used: &mut Vec<String>) -> String {
+    let base: String = aweme_id.chars()
+        .filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\'))
+        .take(SHEET_NAME_MAX_LEN - 4)
+        .collect();
+    let base = if base.is_empty() { "作品".to_string() } else { base };
+
+    let mut name = base.clone();
+    let mut n = 2;
+    while used.contains(&name) {
+        name = format!("{}_{}", base, n);
+        n += 1;
+    }
+    used.push(name.clone());
+    name
+}
+
+/// 写入评论明细表，每 PROGRESS_STEP 行回调一次 `on_row(已写入行数)`
+fn write_detail_sheet(sheet: &mut Worksheet, comments: &[Comment], header: &Format, on_row: &mut dyn FnMut(usize)) -> Result<(), XlsxError> {
+    let wrap = Format::new().set_text_wrap();
+    write_headers(sheet, DETAIL_HEADERS, header)?;
+
+    for (i, comment) in comments.iter().enumerate() {
+        let row = i as u32 + 1;
+        sheet.write_string(row, 0, &comment.create_time)?;
+        sheet.write_string(row, 1, &comment.user_nickname)?;
+        sheet.write_string(row, 2, &comment.user_id)?;
+        sheet.write_string_with_format(row, 3, &comment.content, &wrap)?;
+        sheet.write_number(row, 4, comment.like_count as f64)?;
+        sheet.write_number(row, 5, comment.reply_count as f64)?;
+        sheet.write_string(row, 6, comment.tags.join(","))?;
+
+        if (i + 1) % PROGRESS_STEP == 0 {
+            on_row(i + 1);
+        }
+    }
+    sheet.autofilter(0, 0, comments.len() as u32, DETAIL_HEADERS.len() as u16 - 1)?;
+    on_row(comments.len());
+    Ok(())
+}
+
+fn write_workbook(path: &Path, items: &[CommentExportItem], on_progress: &mut dyn FnMut(&str, usize)) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let header = header_format();
+
+    // 汇总表
+    let summary = workbook.add_worksheet();
+    summary.set_name("汇总")?;
+    write_headers(summary, SUMMARY_HEADERS, &header)?;
+
+    for (i, item) in items.iter().enumerate() {
+        let row = i as u32 + 1;
+        let likes: i64 = item.comments.iter().map(|c| c.like_count).sum();
+        let replies: i64 = item.comments.iter().map(|c| c.reply_count).sum();
+        let earliest = item.comments.iter().map(|c| c.create_time.as_str()).min().unwrap_or("");
+        let latest = item.comments.iter().map(|c| c.create_time.as_str()).max().unwrap_or("");
+
+        summary.write_string(row, 0, &item.aweme_id)?;
+        summary.write_string(row, 1, &item.title)?;
+        summary.write_string(row, 2, &item.account_name)?;
+        summary.write_string(row, 3, &item.published_at)?;
+        summary.write_number(row, 4, item.comments.len() as f64)?;
+        summary.write_number(row, 5, likes as f64)?;
+        summary.write_number(row, 6, replies as f64)?;
+        summary.write_string(row, 7, earliest)?;
+        summary.write_string(row, 8, latest)?;
+    }
+    summary.autofilter(0, 0, items.len() as u32, SUMMARY_HEADERS.len() as u16 - 1)?;
+
+    // 每个作品一个明细表
+    let mut used_names = vec!["汇总".to_string()];
+    for item in items {
+        let sheet = workbook.add_worksheet();
+        sheet.set_name(sheet_name(&item.aweme_id, &mut used_names))?;
+        write_detail_sheet(sheet, &item.comments, &header, &mut |written| on_progress(&item.aweme_id, written))?;
+    }
+
+    workbook.save(path)
+}
+
+/// 将作品的评论写入 .xlsx 文件（汇总表 + 每个作品一个明细表）
+///
+/// 写入明细时回调 `on_progress(作品ID, 该作品已写入行数)`，用于大批量导出时上报进度
+pub fn export_comments_xlsx(path: &Path, items: &[CommentExportItem], mut on_progress: impl FnMut(&str, usize)) -> Result<(), String> {
+    if items.is_empty() {
+        return Err("没有可导出的作品".to_string());
+    }
+    write_workbook(path, items, &mut on_progress).map_err(|e| format!("导出 Excel 失败: {}", e))
+}
+
+/// CSV 字段转义：以 `=`、`+`、`-`、`@`、制表符或回车开头时加 `'` 前缀，
+/// 防止表格软件把评论内容当作公式执行；包含逗号、引号或换行时加引号
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn write_csv(path: &Path, comments: &[Comment], on_row: &mut dyn FnMut(usize)) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(std::fs::File::create(path)?);
+
+    // UTF-8 BOM，Excel 直接打开 CSV 时中文不乱码
+    writer.write_all(b"\xEF\xBB\xBF")?;
+    let headers: Vec<&str> = DETAIL_HEADERS.iter().map(|(name, _)| *name).collect();
+    writeln!(writer, "{}", headers.join(","))?;
+
+    for (i, comment) in comments.iter().enumerate() {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{}",
+            csv_field(&comment.create_time),
+            csv_field(&comment.user_nickname),
+            csv_field(&comment.user_id),
+            csv_field(&comment.content),
+            comment.like_count,
+            comment.reply_count,
+            csv_field(&comment.tags.join(",")),
+        )?;
+
+        if (i + 1) % PROGRESS_STEP == 0 {
+            on_row(i + 1);
+        }
+    }
+
+    writer.flush()?;
+    on_row(comments.len());
+    Ok(())
+}
+
+/// 导出单个作品的评论为 CSV，`on_progress(已写入行数)` 用于大批量导出时上报进度
+pub fn export_comments_csv(path: &Path, comments: &[Comment], mut on_progress: impl FnMut(usize)) -> Result<(), String> {
+    write_csv(path, comments, &mut on_progress).map_err(|e| format!("导出 CSV 失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("好看"), "好看");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("说\"真的\""), "\"说\"\"真的\"\"\"");
+    }
+
+    #[test]
+    fn test_csv_field_formula_injection() {
+        assert_eq!(csv_field("=HYPERLINK(\"http://x\")"), "\"'=HYPERLINK(\"\"http://x\"\")\"");
+        assert_eq!(csv_field("+1"), "'+1");
+        assert_eq!(csv_field("-1"), "'-1");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("\t=1"), "'\t=1");
+        assert_eq!(csv_field("\r=1"), "\"'\r=1\"");
+        assert_eq!(csv_field("1+1"), "1+1");
+    }
+}
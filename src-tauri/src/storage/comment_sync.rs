@@ -0,0 +1,167 @@
+// Comment sync state - incremental comment sync progress
+// 评论增量同步进度存储 - 记录每个作品上次同步到的游标，以及每次同步首次看到的评论（comments.sync_id）
+
+use super::DatabaseManager;
+use crate::core::{Comment, CommentSyncRecord, CommentSyncState};
+
+const STATE_COLUMNS: &str = "aweme_id, detail_id, cursor, has_more, synced_count, last_error, last_synced_at";
+
+fn map_state(row: &rusqlite::Row) -> rusqlite::Result<CommentSyncState> {
+    let last_error: String = row.get::<_, Option<String>>(5)?.unwrap_or_default();
+    Ok(CommentSyncState {
+        aweme_id: row.get(0)?,
+        detail_id: row.get(1)?,
+        cursor: row.get(2)?,
+        has_more: row.get::<_, i64>(3)? != 0,
+        synced_count: row.get(4)?,
+        last_error: if last_error.is_empty() { None } else { Some(last_error) },
+        last_synced_at: row.get(6)?,
+    })
+}
+
+impl DatabaseManager {
+    /// Save comment sync progress for an item
+    /// 保存作品的评论同步进度
+    pub fn save_comment_sync_state(&self, state: &CommentSyncState) -> Result<(), rusqlite::Error> {
+        self.write("save_comment_sync_state", |conn| {
+            conn.execute(&format!(
+                "INSERT OR REPLACE INTO comment_sync_state ({}) VALUES (?, ?, ?, ?, ?, ?, ?)", STATE_COLUMNS
+            ), rusqlite::params![
+                state.aweme_id,
+                state.detail_id,
+                state.cursor,
+                state.has_more as i64,
+                state.synced_count,
+                state.last_error.as_deref().unwrap_or(""),
+                state.last_synced_at,
+            ])
+        })?;
+
+        Ok(())
+    }
+
+    /// Get comment sync progress for an item
+    /// 获取作品的评论同步进度
+    pub fn get_comment_sync_state(&self, aweme_id: &str) -> Result<Option<CommentSyncState>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM comment_sync_state WHERE aweme_id = ?", STATE_COLUMNS))?;
+        match stmt.query_row([aweme_id], map_state) {
+            Ok(state) => Ok(Some(state)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get all comment sync progress (most recently synced first)
+    /// 获取所有作品的评论同步进度
+    pub fn get_comment_sync_states(&self) -> Result<Vec<CommentSyncState>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM comment_sync_state ORDER BY last_synced_at DESC", STATE_COLUMNS
+        ))?;
+        let states = stmt.query_map([], map_state)?.filter_map(|r| r.ok()).collect();
+
+        Ok(states)
+    }
+
+    /// Completed publications and externally published videos to sync comments for, least recently synced first
+    /// 获取待同步评论的作品 (detail_id, aweme_id)，从未同步或最久未同步的优先；
+    /// 未通过本应用发布的平台作品以 remote_videos.id 作为 detail_id
+    pub fn get_comment_sync_targets(&self, limit: usize) -> Result<Vec<(String, String)>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.item_id FROM ( \
+                 SELECT id, item_id, published_at FROM publication_accounts \
+                 WHERE status = 'Completed' COLLATE NOCASE AND item_id != '' \
+                 UNION ALL \
+                 SELECT id, item_id, create_time FROM remote_videos WHERE detail_id = '' \
+             ) t \
+             LEFT JOIN comment_sync_state s ON s.aweme_id = t.item_id \
+             GROUP BY t.item_id \
+             ORDER BY s.last_synced_at IS NOT NULL, s.last_synced_at ASC, t.published_at DESC \
+             LIMIT ?"
+        )?;
+        let targets = stmt.query_map([limit as i64], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<(String, String)>, _>>()?;
+
+        Ok(targets)
+    }
+
+    /// Close a sync batch: comments not yet assigned to a sync belong to this one
+    /// 记录一次评论同步，把尚未归属同步批次的评论（sync_id 为空）归入本次
+    pub fn record_comment_sync(&self, aweme_id: &str) -> Result<CommentSyncRecord, rusqlite::Error> {
+        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+        self.write("record_comment_sync", |conn| {
+            let tx = conn.transaction()?;
+            tx.execute(
+                "INSERT INTO comment_syncs (aweme_id, new_count, synced_at) VALUES (?, 0, ?)",
+                [aweme_id, &now],
+            )?;
+            let id = tx.last_insert_rowid();
+            let new_count = tx.execute(
+                "UPDATE comments SET sync_id = ? WHERE aweme_id = ? AND sync_id IS NULL",
+                rusqlite::params![id, aweme_id],
+            )? as i64;
+            tx.execute("UPDATE comment_syncs SET new_count = ? WHERE id = ?", [new_count, id])?;
+            tx.commit()?;
+
+            Ok(CommentSyncRecord { id, aweme_id: aweme_id.to_string(), new_count, synced_at: now.clone() })
+        })
+    }
+
+    /// Recent syncs of an item, newest first
+    /// 获取作品最近的评论同步记录（最新的在前）
+    pub fn get_comment_sync_history(&self, aweme_id: &str, limit: usize) -> Result<Vec<CommentSyncRecord>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, aweme_id, new_count, synced_at FROM comment_syncs WHERE aweme_id = ? ORDER BY id DESC LIMIT ?"
+        )?;
+        let records = stmt.query_map(rusqlite::params![aweme_id, limit as i64], |row| {
+            Ok(CommentSyncRecord {
+                id: row.get(0)?,
+                aweme_id: row.get(1)?,
+                new_count: row.get(2)?,
+                synced_at: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+            })
+        })?.filter_map(|r| r.ok()).collect();
+
+        Ok(records)
+    }
+
+    /// Comments first seen after the given sync (including ones not yet assigned to a sync)
+    /// 获取指定同步之后首次看到的评论（含尚未归入同步批次的新评论）
+    pub fn get_new_comments(&self, aweme_id: &str, since_sync_id: i64) -> Result<Vec<Comment>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, account_id, aweme_id, comment_id, user_id, user_nickname, user_avatar, content, \
+             like_count, reply_count, create_time, status, created_at, tags FROM comments \
+             WHERE aweme_id = ? AND (sync_id IS NULL OR sync_id > ?) ORDER BY create_time DESC"
+        )?;
+        let comments = stmt.query_map(rusqlite::params![aweme_id, since_sync_id], |row| {
+            Ok(Comment {
+                id: row.get(0)?,
+                account_id: row.get(1)?,
+                aweme_id: row.get(2)?,
+                comment_id: row.get(3)?,
+                user_id: row.get(4)?,
+                user_nickname: row.get(5)?,
+                user_avatar: row.get(6)?,
+                content: row.get(7)?,
+                like_count: row.get(8)?,
+                reply_count: row.get(9)?,
+                create_time: row.get(10)?,
+                status: Self::parse_comment_status(row.get(11)?),
+                created_at: row.get(12)?,
+                tags: Self::decode_tags(row.get(13)?),
+            })
+        })?.filter_map(|r| r.ok()).collect();
+
+        Ok(comments)
+    }
+}
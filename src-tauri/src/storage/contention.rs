@@ -0,0 +1,219 @@
+// Database contention handling - retry on SQLITE_BUSY / SQLITE_LOCKED
+// 数据库锁竞争处理 - 并发发布同时写状态时遇到 "database is locked" 自动退避重试，
+// 并统计竞争频率；短时间内竞争过于频繁时临时切换为串行写入。
+// 退避等待和串行写入锁都会阻塞线程，在异步任务中调用时先让出 tokio 工作线程
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use rusqlite::{Connection, ErrorCode};
+use serde::Serialize;
+use super::DatabaseManager;
+
+/// 单次写操作遇到锁竞争时的最大重试次数
+const BUSY_MAX_RETRIES: u32 = 5;
+
+/// 第一次重试前的等待时间（之后每次翻倍）
+const BUSY_BASE_DELAY: Duration = Duration::from_millis(25);
+
+/// 统计窗口内锁竞争次数达到该值时切换为串行写入
+const SERIALIZE_THRESHOLD: usize = 5;
+
+/// 锁竞争统计窗口
+const CONTENTION_WINDOW: Duration = Duration::from_secs(60);
+
+/// 串行写入持续时间（期间没有新的竞争则恢复并发写入）
+const SERIALIZE_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+/// Database contention counters since app start
+/// 数据库锁竞争统计（应用启动以来）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ContentionStats {
+    /// 遇到 busy/locked 错误的次数
+    pub busy_errors: u64,
+    /// 重试后成功的写操作数
+    pub recovered: u64,
+    /// 重试用尽仍失败的写操作数
+    pub exhausted: u64,
+    /// 串行模式下执行的写操作数
+    pub serialized_writes: u64,
+    /// 当前是否处于串行写入模式
+    pub serializing: bool,
+}
+
+static BUSY_ERRORS: AtomicU64 = AtomicU64::new(0);
+static RECOVERED: AtomicU64 = AtomicU64::new(0);
+static EXHAUSTED: AtomicU64 = AtomicU64::new(0);
+static SERIALIZED_WRITES: AtomicU64 = AtomicU64::new(0);
+
+/// 串行写入锁
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 最近的锁竞争时间点及串行模式截止时间
+struct ContentionState {
+    recent: Vec<Instant>,
+    serialize_until: Option<Instant>,
+}
+
+fn state() -> &'static Mutex<ContentionState> {
+    static STATE: OnceLock<Mutex<ContentionState>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(ContentionState { recent: Vec::new(), serialize_until: None }))
+}
+
+/// 是否为锁竞争错误（SQLITE_BUSY / SQLITE_LOCKED）
+pub fn is_busy_error(error: &rusqlite::Error) -> bool {
+    matches!(
+        error,
+        rusqlite::Error::SqliteFailure(e, _)
+            if matches!(e.code, ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+    )
+}
+
+/// 记录一次锁竞争，窗口内次数达到阈值时进入串行写入模式
+fn record_contention(now: Instant) {
+    BUSY_ERRORS.fetch_add(1, Ordering::Relaxed);
+
+    let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+    state.recent.retain(|t| now.duration_since(*t) < CONTENTION_WINDOW);
+    state.recent.push(now);
+
+    if state.recent.len() >= SERIALIZE_THRESHOLD {
+        if !matches!(state.serialize_until, Some(until) if until > now) {
+            tracing::warn!("[Database] 锁竞争频繁（{} 秒内 {} 次），切换为串行写入", CONTENTION_WINDOW.as_secs(), state.recent.len());
+        }
+        state.serialize_until = Some(now + SERIALIZE_COOLDOWN);
+    }
+}
+
+fn serializing(now: Instant) -> bool {
+    let state = state().lock().unwrap_or_else(|e| e.into_inner());
+    state.serialize_until.is_some_and(|until| until > now)
+}
+
+/// Current contention counters
+/// 获取锁竞争统计
+pub fn contention_stats() -> ContentionStats {
+    ContentionStats {
+        busy_errors: BUSY_ERRORS.load(Ordering::Relaxed),
+        recovered: RECOVERED.load(Ordering::Relaxed),
+        exhausted: EXHAUSTED.load(Ordering::Relaxed),
+        serialized_writes: SERIALIZED_WRITES.load(Ordering::Relaxed),
+        serializing: serializing(Instant::now()),
+    }
+}
+
+/// 执行会阻塞线程的操作：在多线程 tokio 运行时中通过 `block_in_place` 让出工作线程，
+/// 同一工作线程上的其他异步任务会被调度到别的线程继续运行；不在运行时中时直接执行
+fn run_blocking<T>(f: impl FnOnce() -> T) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+/// 执行写操作，遇到锁竞争时指数退避重试
+///
+/// `op` 为操作名，仅用于日志
+pub fn with_busy_retry<T>(op: &str, f: impl FnMut() -> rusqlite::Result<T>) -> rusqlite::Result<T> {
+    run_blocking(|| retry_busy(op, f))
+}
+
+fn retry_busy<T>(op: &str, mut f: impl FnMut() -> rusqlite::Result<T>) -> rusqlite::Result<T> {
+    let _guard = if serializing(Instant::now()) {
+        SERIALIZED_WRITES.fetch_add(1, Ordering::Relaxed);
+        Some(WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner()))
+    } else {
+        None
+    };
+
+    let mut attempt = 0;
+    loop {
+        match f() {
+            Ok(value) => {
+                if attempt > 0 {
+                    RECOVERED.fetch_add(1, Ordering::Relaxed);
+                    tracing::info!("[Database] {} 在第 {} 次重试后成功", op, attempt);
+                }
+                return Ok(value);
+            }
+            Err(e) if is_busy_error(&e) => {
+                record_contention(Instant::now());
+                if attempt >= BUSY_MAX_RETRIES {
+                    EXHAUSTED.fetch_add(1, Ordering::Relaxed);
+                    tracing::error!("[Database] {} 数据库繁忙，重试 {} 次后仍失败: {}", op, attempt, e);
+                    return Err(e);
+                }
+                let delay = BUSY_BASE_DELAY * 2u32.pow(attempt);
+                tracing::warn!("[Database] {} 遇到数据库锁（{}），{} ms 后重试", op, e, delay.as_millis());
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+impl DatabaseManager {
+    /// Run a write against a fresh connection with busy retry; every write to the live database goes through here
+    /// 获取连接并执行写操作（锁竞争时自动重试）。对数据库的写入都应经过这里，以下例外：
+    /// 建表、迁移和旧版明文凭证加密在 get_connection 首次连接时于初始化锁内执行；
+    /// 匿名快照只用 VACUUM INTO 读取本库，脱敏写在导出的副本上。
+    /// `f` 在重试时会被再次调用，需要能重复执行（事务在 `f` 内开启和提交）
+    pub(super) fn write<T>(&self, op: &str, mut f: impl FnMut(&mut Connection) -> rusqlite::Result<T>) -> rusqlite::Result<T> {
+        with_busy_retry(op, || {
+            let mut conn = self.get_connection()?;
+            f(&mut conn)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn busy() -> rusqlite::Error {
+        rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            Some("database is locked".to_string()),
+        )
+    }
+
+    #[test]
+    fn test_is_busy_error() {
+        assert!(is_busy_error(&busy()));
+        assert!(!is_busy_error(&rusqlite::Error::QueryReturnedNoRows));
+    }
+
+    #[test]
+    fn test_retry_recovers_from_busy() {
+        let mut calls = 0;
+        let result = with_busy_retry("test", || {
+            calls += 1;
+            if calls < 3 { Err(busy()) } else { Ok(calls) }
+        });
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_retry_does_not_retry_other_errors() {
+        let mut calls = 0;
+        let result: rusqlite::Result<()> = with_busy_retry("test", || {
+            calls += 1;
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_retry_inside_async_task() {
+        let mut calls = 0;
+        let result = with_busy_retry("test", || {
+            calls += 1;
+            if calls < 2 { Err(busy()) } else { Ok(calls) }
+        });
+        assert_eq!(result.unwrap(), 2);
+    }
+}
@@ -0,0 +1,294 @@
+// Credentials encryption at rest - accounts.params stored as AES-256-GCM ciphertext
+// 账号凭证加密存储 - accounts.params（Cookie、安全 SDK 密钥等）以密文写入数据库：
+// - 密钥为每个数据目录首次使用时随机生成的 32 字节，优先保存在系统钥匙串中
+//   （macOS Keychain / Windows 凭据管理器 / Linux Secret Service），拷走或上传数据目录不会泄露凭证
+// - 没有钥匙串服务的环境（无桌面的 Linux、cron、CLI 服务模式）退回到数据目录下的
+//   credentials.key（权限 0600）；之后钥匙串可用时导入钥匙串并删除该文件
+// - 密文格式为 `enc:v1:` + base64(nonce || tag || ciphertext)，没有前缀的值视为旧版明文，读取时原样返回
+// - 已有的明文凭证在建表迁移完成后加密（不属于迁移，密钥不可用时只影响凭证读写），不产生远程同步变更
+// save_account / get_account 等接口透明加解密，调用方始终拿到明文
+
+use super::DatabaseManager;
+use crate::core::{decrypt_payload, encrypt_payload};
+use std::path::Path;
+
+/// 密文前缀（含格式版本）
+pub const ENCRYPTED_PARAMS_PREFIX: &str = "enc:v1:";
+
+/// 钥匙串中的服务名
+const KEYCHAIN_SERVICE: &str = "com.yzg.matrix";
+
+/// 钥匙串不可用时保存密钥的文件名（旧版本也保存在这里）
+const KEY_FILE: &str = "credentials.key";
+
+/// 钥匙串条目名：每个数据目录一个密钥
+fn keychain_account(dir: &Path) -> String {
+    format!("credentials:{}", dir.display())
+}
+
+#[cfg(not(test))]
+fn keychain_get(account: &str) -> Result<Option<String>, String> {
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, account).map_err(|e| format!("打开系统钥匙串失败: {}", e))?;
+    match entry.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("读取系统钥匙串失败: {}", e)),
+    }
+}
+
+#[cfg(not(test))]
+fn keychain_set(account: &str, secret: &str) -> Result<(), String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, account)
+        .and_then(|entry| entry.set_password(secret))
+        .map_err(|e| format!("写入系统钥匙串失败: {}", e))
+}
+
+// 测试环境（CI）没有钥匙串服务，使用进程内存储代替
+#[cfg(test)]
+static TEST_KEYCHAIN: std::sync::Mutex<Option<std::collections::HashMap<String, String>>> = std::sync::Mutex::new(None);
+
+#[cfg(test)]
+thread_local! {
+    /// 模拟没有钥匙串服务的环境
+    static TEST_KEYCHAIN_UNAVAILABLE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+#[cfg(test)]
+fn keychain_get(account: &str) -> Result<Option<String>, String> {
+    if TEST_KEYCHAIN_UNAVAILABLE.with(|u| u.get()) {
+        return Err("读取系统钥匙串失败: 没有可用的钥匙串服务".to_string());
+    }
+    let store = TEST_KEYCHAIN.lock().unwrap();
+    Ok(store.as_ref().and_then(|m| m.get(&format!("{}/{}", KEYCHAIN_SERVICE, account)).cloned()))
+}
+
+#[cfg(test)]
+fn keychain_set(account: &str, secret: &str) -> Result<(), String> {
+    if TEST_KEYCHAIN_UNAVAILABLE.with(|u| u.get()) {
+        return Err("写入系统钥匙串失败: 没有可用的钥匙串服务".to_string());
+    }
+    TEST_KEYCHAIN.lock().unwrap()
+        .get_or_insert_with(Default::default)
+        .insert(format!("{}/{}", KEYCHAIN_SERVICE, account), secret.to_string());
+    Ok(())
+}
+
+fn decode_key(hex_key: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(hex_key.trim()).map_err(|e| format!("凭证密钥已损坏: {}", e))?;
+    bytes.try_into().map_err(|_| "凭证密钥长度不正确".to_string())
+}
+
+/// 以 0600 权限写入密钥文件（已存在时不覆盖）
+fn write_key_file(path: &Path, key: &[u8; 32]) -> Result<(), String> {
+    use std::io::Write;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)
+        .and_then(|mut file| file.write_all(hex::encode(key).as_bytes()))
+        .map_err(|e| format!("写入凭证密钥文件失败: {}: {}", path.display(), e))
+}
+
+/// 读取密钥：优先钥匙串；钥匙串中没有时导入密钥文件或生成新密钥，钥匙串不可用时使用密钥文件
+fn load_or_create_key(dir: &Path) -> Result<[u8; 32], String> {
+    let account = keychain_account(dir);
+    let key_path = dir.join(KEY_FILE);
+    let keychain_error = match keychain_get(&account) {
+        Ok(Some(secret)) => return decode_key(&secret),
+        Ok(None) => None,
+        Err(e) => Some(e),
+    };
+
+    if let Ok(content) = std::fs::read_to_string(&key_path) {
+        let key = decode_key(&content)?;
+        if keychain_error.is_none() {
+            match keychain_set(&account, &hex::encode(key)) {
+                // 写入钥匙串成功后才删除文件，避免密钥丢失导致已有凭证无法解密
+                Ok(()) => {
+                    if let Err(e) = std::fs::remove_file(&key_path) {
+                        tracing::warn!("[Database] 删除凭证密钥文件失败: {}: {}", key_path.display(), e);
+                    }
+                    tracing::info!("[Database] 已将凭证加密密钥导入系统钥匙串");
+                }
+                Err(e) => tracing::warn!("[Database] {}，继续使用密钥文件", e),
+            }
+        }
+        return Ok(key);
+    }
+
+    let mut key = [0u8; 32];
+    rand::RngCore::try_fill_bytes(&mut rand::rngs::OsRng, &mut key).map_err(|e| format!("生成凭证密钥失败: {}", e))?;
+    let keychain_error = match keychain_error {
+        Some(e) => e,
+        None => match keychain_set(&account, &hex::encode(key)) {
+            Ok(()) => {
+                tracing::info!("[Database] 已生成凭证加密密钥并保存到系统钥匙串");
+                return Ok(key);
+            }
+            Err(e) => e,
+        },
+    };
+
+    std::fs::create_dir_all(dir).map_err(|e| format!("创建数据目录失败: {}", e))?;
+    write_key_file(&key_path, &key)?;
+    tracing::warn!("[Database] {}，凭证加密密钥已保存到 {}", keychain_error, key_path.display());
+    Ok(key)
+}
+
+/// 凭证是否已加密
+pub fn is_encrypted_params(params: &str) -> bool {
+    params.starts_with(ENCRYPTED_PARAMS_PREFIX)
+}
+
+/// 凭证密钥（Debug 输出不包含密钥内容）
+#[derive(Clone, Copy)]
+pub(super) struct CredentialKey([u8; 32]);
+
+impl std::fmt::Debug for CredentialKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CredentialKey(..)")
+    }
+}
+
+fn to_sql_error(message: String) -> rusqlite::Error {
+    rusqlite::Error::ToSqlConversionFailure(message.into())
+}
+
+impl DatabaseManager {
+    /// 凭证密钥（首次使用时读取或生成，克隆之间共享）
+    fn credential_key(&self) -> Result<[u8; 32], rusqlite::Error> {
+        if let Some(key) = self.credential_key.get() {
+            return Ok(key.0);
+        }
+        let key = load_or_create_key(&self.base_path).map_err(to_sql_error)?;
+        Ok(self.credential_key.get_or_init(|| CredentialKey(key)).0)
+    }
+
+    /// Encrypt account params for storage
+    /// 加密账号凭证（已加密的值原样返回）
+    pub fn encrypt_params(&self, params: &str) -> Result<String, rusqlite::Error> {
+        if is_encrypted_params(params) {
+            return Ok(params.to_string());
+        }
+        let encrypted = encrypt_payload(&self.credential_key()?, params).map_err(to_sql_error)?;
+        Ok(format!("{}{}", ENCRYPTED_PARAMS_PREFIX, encrypted))
+    }
+
+    /// Decrypt stored account params
+    /// 解密账号凭证（旧版明文原样返回）
+    pub fn decrypt_params(&self, stored: &str) -> Result<String, rusqlite::Error> {
+        match stored.strip_prefix(ENCRYPTED_PARAMS_PREFIX) {
+            Some(encrypted) => decrypt_payload(&self.credential_key()?, encrypted)
+                .map_err(|e| to_sql_error(format!("账号凭证{}（系统钥匙串中的密钥可能已更换）", e))),
+            None => Ok(stored.to_string()),
+        }
+    }
+
+    /// Encrypt plaintext params left by older versions
+    /// 加密旧版本留下的明文凭证。只是存储格式变化，执行前后保持 sync_changes 不变；
+    /// 在单独的事务中执行，失败时整体回滚（明文凭证仍可正常读取，下次启动再试）
+    pub(super) fn encrypt_plaintext_credentials(&self, conn: &rusqlite::Connection) -> Result<usize, rusqlite::Error> {
+        let tx = conn.unchecked_transaction()?;
+        let rows: Vec<(String, String)> = tx.prepare(
+            "SELECT id, params FROM accounts WHERE params NOT LIKE 'enc:v1:%'"
+        )?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        tracing::info!("[Database] Encrypting plaintext credentials of {} accounts", rows.len());
+        // 同步触发器已存在，更新前备份账号的同步记录，更新后还原
+        tx.execute_batch(r#"
+            DROP TABLE IF EXISTS temp.sync_changes_backup;
+            CREATE TEMP TABLE sync_changes_backup AS SELECT * FROM sync_changes WHERE entity = 'account';
+        "#)?;
+
+        {
+            let mut stmt = tx.prepare("UPDATE accounts SET params = ? WHERE id = ?")?;
+            for (id, params) in &rows {
+                stmt.execute([self.encrypt_params(params)?, id.clone()])?;
+            }
+        }
+
+        tx.execute_batch(r#"
+            DELETE FROM sync_changes WHERE entity = 'account';
+            INSERT INTO sync_changes SELECT * FROM temp.sync_changes_backup;
+            DROP TABLE temp.sync_changes_backup;
+        "#)?;
+        tx.commit()?;
+        Ok(rows.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_round_trip_and_legacy_plaintext() {
+        let dir = std::env::temp_dir().join(format!("amm-credentials-{}", uuid::Uuid::new_v4()));
+        let db = DatabaseManager::new(dir.clone());
+
+        let params = r#"{"cookie":"sessionid=abc"}"#;
+        let encrypted = db.encrypt_params(params).unwrap();
+        assert!(is_encrypted_params(&encrypted));
+        assert!(!encrypted.contains("sessionid"));
+        assert_eq!(db.encrypt_params(&encrypted).unwrap(), encrypted);
+        assert_eq!(db.decrypt_params(&encrypted).unwrap(), params);
+        assert_eq!(db.decrypt_params(params).unwrap(), params);
+
+        // 同一数据目录的新实例读取同一密钥
+        let reopened = DatabaseManager::new(dir.clone());
+        assert_eq!(reopened.decrypt_params(&encrypted).unwrap(), params);
+
+        // 钥匙串可用时密钥只保存在钥匙串中
+        assert!(!dir.join(KEY_FILE).exists());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_legacy_key_file_is_imported() {
+        let dir = std::env::temp_dir().join(format!("amm-credentials-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let key = [7u8; 32];
+        std::fs::write(dir.join(KEY_FILE), hex::encode(key)).unwrap();
+        let encrypted = format!("{}{}", ENCRYPTED_PARAMS_PREFIX, encrypt_payload(&key, "{}").unwrap());
+
+        let db = DatabaseManager::new(dir.clone());
+        assert_eq!(db.decrypt_params(&encrypted).unwrap(), "{}");
+        assert!(!dir.join(KEY_FILE).exists());
+        assert_eq!(load_or_create_key(&dir).unwrap(), key);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_key_file_fallback_without_keychain() {
+        let dir = std::env::temp_dir().join(format!("amm-credentials-{}", uuid::Uuid::new_v4()));
+        TEST_KEYCHAIN_UNAVAILABLE.with(|u| u.set(true));
+
+        let db = DatabaseManager::new(dir.clone());
+        let encrypted = db.encrypt_params("{}").unwrap();
+        let key_path = dir.join(KEY_FILE);
+        assert!(key_path.exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&key_path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        assert_eq!(DatabaseManager::new(dir.clone()).decrypt_params(&encrypted).unwrap(), "{}");
+
+        // 钥匙串恢复后导入密钥文件
+        TEST_KEYCHAIN_UNAVAILABLE.with(|u| u.set(false));
+        assert_eq!(DatabaseManager::new(dir.clone()).decrypt_params(&encrypted).unwrap(), "{}");
+        assert!(!key_path.exists());
+        assert_eq!(DatabaseManager::new(dir.clone()).decrypt_params(&encrypted).unwrap(), "{}");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
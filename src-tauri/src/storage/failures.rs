@@ -0,0 +1,144 @@
+// Failure analytics - normalize publish failure messages into categories
+// 发布失败分析 - 将 publication_accounts.message 归类并按天统计，
+// 便于看出失败主要来自凭证过期、上传超时还是内容审核（归类规则见 core::classify_failure）
+
+use super::DatabaseManager;
+use crate::core::{classify_failure, FailureCategory};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// 单个类别的失败数
+#[derive(Debug, Clone, Serialize)]
+pub struct FailureCategoryCount {
+    pub category: FailureCategory,
+    pub label: &'static str,
+    pub count: i64,
+    /// 占全部失败的比例（0 ~ 1）
+    pub share: f64,
+}
+
+/// 某一天各类别的失败数
+#[derive(Debug, Clone, Serialize)]
+pub struct FailureDailyCount {
+    /// 日期（YYYY-MM-DD）
+    pub date: String,
+    pub counts: BTreeMap<FailureCategory, i64>,
+}
+
+/// 失败分析结果
+#[derive(Debug, Clone, Serialize)]
+pub struct FailureBreakdown {
+    pub start: String,
+    pub end: String,
+    pub total: i64,
+    /// 按失败数降序
+    pub categories: Vec<FailureCategoryCount>,
+    /// 按日期升序
+    pub daily: Vec<FailureDailyCount>,
+}
+
+/// 汇总 (日期, 失败信息) 列表
+fn build_breakdown(start: &str, end: &str, failures: &[(String, String)]) -> FailureBreakdown {
+    let mut totals: BTreeMap<FailureCategory, i64> = BTreeMap::new();
+    let mut daily: BTreeMap<String, BTreeMap<FailureCategory, i64>> = BTreeMap::new();
+
+    for (date, message) in failures {
+        let category = classify_failure(message).category;
+        *totals.entry(category).or_default() += 1;
+        *daily.entry(date.clone()).or_default().entry(category).or_default() += 1;
+    }
+
+    let total = failures.len() as i64;
+    let mut categories: Vec<FailureCategoryCount> = totals.into_iter()
+        .map(|(category, count)| FailureCategoryCount {
+            category,
+            label: category.label(),
+            count,
+            share: count as f64 / total as f64,
+        })
+        .collect();
+    categories.sort_by(|a, b| b.count.cmp(&a.count).then(a.category.cmp(&b.category)));
+
+    FailureBreakdown {
+        start: start.to_string(),
+        end: end.to_string(),
+        total,
+        categories,
+        daily: daily.into_iter().map(|(date, counts)| FailureDailyCount { date, counts }).collect(),
+    }
+}
+
+impl DatabaseManager {
+    /// Break down failed publishes in a date range (inclusive, YYYY-MM-DD) by failure category
+    /// 统计日期区间（含首尾）内的发布失败，按类别和日期汇总
+    pub fn get_failure_breakdown(&self, start: &str, end: &str) -> Result<FailureBreakdown, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        // 失败时间取 published_at（更新状态时写入），缺失时退回 created_at；回收站中的任务和账号不计入
+        let mut stmt = conn.prepare(r#"
+            SELECT substr(COALESCE(NULLIF(pa.published_at, ''), pa.created_at), 1, 10) AS day, COALESCE(pa.message, '')
+            FROM publication_accounts pa
+            JOIN publication_tasks t ON t.id = pa.publication_task_id AND t.deleted_at = ''
+            WHERE pa.status = 'Failed'
+              AND pa.account_id NOT IN (SELECT id FROM accounts WHERE deleted_at != '')
+              AND substr(COALESCE(NULLIF(pa.published_at, ''), pa.created_at), 1, 10) BETWEEN ? AND ?
+        "#)?;
+        let failures: Vec<(String, String)> = stmt.query_map([start, end], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(build_breakdown(start, end, &failures))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_breakdown_shares_and_daily() {
+        let failures = vec![
+            ("2024-03-01".to_string(), "Authentication failed: 未登录".to_string()),
+            ("2024-03-01".to_string(), "Invalid credentials: Cookie为空".to_string()),
+            ("2024-03-02".to_string(), "Network error: 超时".to_string()),
+        ];
+        let breakdown = build_breakdown("2024-03-01", "2024-03-07", &failures);
+
+        assert_eq!(breakdown.total, 3);
+        assert_eq!(breakdown.categories[0].category, FailureCategory::CredentialExpired);
+        assert!((breakdown.categories[0].share - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(breakdown.daily.len(), 2);
+        assert_eq!(breakdown.daily[1].counts.get(&FailureCategory::Timeout), Some(&1));
+    }
+
+    #[test]
+    fn test_trashed_tasks_and_accounts_are_excluded() {
+        let dir = std::env::temp_dir().join(format!("amm-failures-{}", uuid::Uuid::new_v4()));
+        let db = DatabaseManager::new(dir.clone());
+        db.get_connection().unwrap().execute_batch(r#"
+            INSERT INTO accounts (id, username, nickname, platform, params, status) VALUES
+                ('a1', 'u1', '保留', 'Douyin', '{}', 'Active'),
+                ('a2', 'u2', '删除', 'Douyin', '{}', 'Active');
+            INSERT INTO publication_tasks (id, title, video_path, status, created_at) VALUES
+                ('t1', '保留', '/a.mp4', 'Failed', '2024-03-01 10:00:00'),
+                ('t2', '删除', '/b.mp4', 'Failed', '2024-03-01 10:00:00');
+            INSERT INTO publication_accounts (id, publication_task_id, account_id, account_name, platform, status, created_at, message) VALUES
+                ('d1', 't1', 'a1', '保留', 'Douyin', 'Failed', '2024-03-01 10:00:00', 'Network error: 超时'),
+                ('d2', 't2', 'a1', '保留', 'Douyin', 'Failed', '2024-03-01 10:00:00', 'Network error: 超时'),
+                ('d3', 't1', 'a2', '删除', 'Douyin', 'Failed', '2024-03-01 10:00:00', 'Network error: 超时');
+            INSERT INTO comments (id, account_id, aweme_id, comment_id, user_id, user_nickname, user_avatar, content, create_time) VALUES
+                ('c1', 'a1', 'v1', 'c1', 'u', '网友', '', '保留', '2024-03-01 10:00:00'),
+                ('c2', 'a2', 'v2', 'c2', 'u', '网友', '', '删除', '2024-03-01 10:00:00');
+        "#).unwrap();
+        assert!(db.delete_publication_task("t2").unwrap());
+        assert!(db.delete_account("a2").unwrap());
+
+        let breakdown = db.get_failure_breakdown("2024-03-01", "2024-03-07").unwrap();
+        assert_eq!(breakdown.total, 1);
+
+        let query = serde_json::from_value(serde_json::json!({ "entity": "comments" })).unwrap();
+        assert_eq!(db.run_report(&query).unwrap().rows, vec![vec![serde_json::json!(1)]]);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
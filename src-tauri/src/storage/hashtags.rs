@@ -0,0 +1,174 @@
+// Hashtag library - every hashtag ever published, its Douyin id and performance
+// 话题库统计 - 记录发布过的所有话题：
+// - hashtags：话题名、抖音 hashtag_id 缓存（避免每次发布都调用 search_challenge_sug）、使用次数
+// - hashtag_usages：话题与发布明细的关联，表现数据按关联的发布明细实时汇总
+
+use super::DatabaseManager;
+use serde::Serialize;
+
+/// 未指定条数时返回的联想数
+pub const DEFAULT_HASHTAG_SUGGESTIONS: usize = 20;
+
+/// Hashtag suggestion for autocomplete
+/// 话题联想
+#[derive(Debug, Clone, Serialize)]
+pub struct HashtagSuggestion {
+    pub name: String,
+    pub usage_count: i64,
+}
+
+/// Aggregate performance of publications using a hashtag
+/// 话题表现（使用该话题的发布明细数据汇总）
+#[derive(Debug, Clone, Serialize)]
+pub struct HashtagPerformance {
+    pub name: String,
+    /// 抖音话题ID（尚未解析时为 None）
+    pub douyin_hashtag_id: Option<String>,
+    pub usage_count: i64,
+    pub last_used_at: String,
+    pub comments: i64,
+    pub likes: i64,
+    pub favorites: i64,
+    pub shares: i64,
+    /// 平均每次发布的点赞数
+    pub avg_likes: f64,
+}
+
+/// 去掉 # 前缀和空白
+pub fn normalize_hashtag(name: &str) -> String {
+    name.trim().trim_start_matches('#').trim().to_string()
+}
+
+impl DatabaseManager {
+    /// Get the cached Douyin hashtag id of a hashtag
+    /// 获取缓存的抖音话题ID
+    pub fn get_douyin_hashtag_id(&self, name: &str) -> Result<Option<String>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        match conn.query_row(
+            "SELECT douyin_hashtag_id FROM hashtags WHERE name = ? AND douyin_hashtag_id != ''",
+            [normalize_hashtag(name)],
+            |row| row.get(0),
+        ) {
+            Ok(id) => Ok(Some(id)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Cache the Douyin hashtag id resolved through search_challenge_sug
+    /// 缓存通过 search_challenge_sug 解析到的抖音话题ID
+    pub fn cache_douyin_hashtag_id(&self, name: &str, hashtag_id: &str) -> Result<(), rusqlite::Error> {
+        self.write("cache_douyin_hashtag_id", |conn| {
+            let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+            conn.execute(r#"
+                INSERT INTO hashtags (name, douyin_hashtag_id, usage_count, created_at, last_used_at)
+                VALUES (?1, ?2, 0, ?3, '')
+                ON CONFLICT(name) DO UPDATE SET douyin_hashtag_id = excluded.douyin_hashtag_id
+            "#, [&normalize_hashtag(name), hashtag_id, &now])?;
+
+            Ok(())
+        })
+    }
+
+    /// Record the hashtags of a successful publish
+    /// 记录发布成功的明细使用的话题（同一明细重复记录不会重复计数）
+    pub fn record_hashtag_usage(&self, detail_id: &str, hashtags: &[String]) -> Result<(), rusqlite::Error> {
+        self.write("record_hashtag_usage", |conn| {
+            let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+            let tx = conn.transaction()?;
+            for name in hashtags.iter().map(|t| normalize_hashtag(t)).filter(|t| !t.is_empty()) {
+                let inserted = tx.execute(
+                    "INSERT OR IGNORE INTO hashtag_usages (detail_id, hashtag, used_at) VALUES (?, ?, ?)",
+                    [detail_id, &name, &now],
+                )?;
+                if inserted == 0 {
+                    continue;
+                }
+                tx.execute(r#"
+                    INSERT INTO hashtags (name, douyin_hashtag_id, usage_count, created_at, last_used_at)
+                    VALUES (?1, '', 1, ?2, ?2)
+                    ON CONFLICT(name) DO UPDATE SET
+                        usage_count = usage_count + 1,
+                        last_used_at = excluded.last_used_at
+                "#, [&name, &now])?;
+            }
+            tx.commit()?;
+
+            Ok(())
+        })
+    }
+
+    /// Hashtags starting with the prefix, most used first
+    /// 按前缀联想话题（使用次数多的在前）
+    pub fn get_hashtag_suggestions(&self, prefix: &str, limit: usize) -> Result<Vec<HashtagSuggestion>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        // 用 substr 比较前缀，避免话题中的 % _ 被当作 LIKE 通配符
+        let suggestions = conn.prepare(r#"
+            SELECT name, usage_count FROM hashtags
+            WHERE substr(name, 1, length(?1)) = ?1
+            ORDER BY usage_count DESC, last_used_at DESC
+            LIMIT ?2
+        "#)?
+            .query_map(rusqlite::params![normalize_hashtag(prefix), limit as i64], |row| {
+                Ok(HashtagSuggestion { name: row.get(0)?, usage_count: row.get(1)? })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(suggestions)
+    }
+
+    /// Performance of every hashtag, most used first
+    /// 获取所有话题的表现数据（按使用次数倒序）
+    pub fn get_hashtag_performance(&self) -> Result<Vec<HashtagPerformance>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let performance = conn.prepare(r#"
+            SELECT h.name, h.douyin_hashtag_id, h.usage_count, COALESCE(h.last_used_at, ''),
+                COALESCE(SUM(pa.comments), 0), COALESCE(SUM(pa.likes), 0),
+                COALESCE(SUM(pa.favorites), 0), COALESCE(SUM(pa.shares), 0),
+                COUNT(pa.id)
+            FROM hashtags h
+            LEFT JOIN hashtag_usages u ON u.hashtag = h.name
+            LEFT JOIN publication_accounts pa ON pa.id = u.detail_id
+            GROUP BY h.name
+            ORDER BY h.usage_count DESC, h.name
+        "#)?
+            .query_map([], |row| {
+                let douyin_hashtag_id: String = row.get::<_, Option<String>>(1)?.unwrap_or_default();
+                let likes: i64 = row.get(5)?;
+                let publications: i64 = row.get(8)?;
+                Ok(HashtagPerformance {
+                    name: row.get(0)?,
+                    douyin_hashtag_id: if douyin_hashtag_id.is_empty() { None } else { Some(douyin_hashtag_id) },
+                    usage_count: row.get(2)?,
+                    last_used_at: row.get(3)?,
+                    comments: row.get(4)?,
+                    likes,
+                    favorites: row.get(6)?,
+                    shares: row.get(7)?,
+                    avg_likes: if publications > 0 { likes as f64 / publications as f64 } else { 0.0 },
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(performance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_hashtag() {
+        assert_eq!(normalize_hashtag(" #旅行 "), "旅行");
+        assert_eq!(normalize_hashtag("##vlog"), "vlog");
+        assert_eq!(normalize_hashtag("#"), "");
+    }
+}
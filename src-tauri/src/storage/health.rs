@@ -0,0 +1,307 @@
+// Account health - rolling credential/API health score derived from recent publish outcomes
+// 账号健康度 - 根据最近的发布结果计算滚动健康分：
+// 成功记满分，凭证失效 / 风控记零分且加倍权重，限流、超时、网络等接口错误记部分分；
+// 越新的结果权重越高，便于在新建任务时提前提示不稳定的账号。
+// 连续失败、失败率过高或出现风控 / 验证码响应的账号会被标记风险（见 core::account_monitor）
+
+use super::DatabaseManager;
+use crate::core::{classify_failure, FailureCategory, PUBLISH_CANCELLED_MESSAGE};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 参与计算的最近发布结果数
+pub const HEALTH_WINDOW: usize = 20;
+
+/// 每往前一次发布，权重乘以该系数
+const RECENCY_DECAY: f64 = 0.85;
+
+/// 健康分不低于该值为健康
+const HEALTHY_SCORE: u8 = 80;
+
+/// 健康分低于该值为危险
+const CRITICAL_SCORE: u8 = 50;
+
+/// 连续失败达到该次数时标记风险
+pub const RISKY_CONSECUTIVE_FAILURES: usize = 3;
+
+/// 失败率达到该值时标记风险（百分比）
+pub const RISKY_FAILURE_RATE: u8 = 50;
+
+/// 发布数达到该值后才按失败率判断，避免一两次失败就被标记
+const MIN_PUBLISHES_FOR_RATE: usize = 5;
+
+/// 最近的发布中出现风控 / 验证码响应达到该次数时标记风险
+pub const RISKY_RISK_CONTROL_FAILURES: usize = 1;
+
+/// Account health level
+/// 健康等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountHealthLevel {
+    Healthy,
+    Warning,
+    Critical,
+    /// 没有可参考的发布记录
+    Unknown,
+}
+
+/// Account health derived from recent publishes
+/// 账号健康度
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountHealth {
+    pub account_id: String,
+    /// 0 ~ 100，没有发布记录时为 None
+    pub score: Option<u8>,
+    pub level: AccountHealthLevel,
+    /// 参与计算的发布数
+    pub recent_publishes: usize,
+    pub successes: usize,
+    /// 凭证失效 / 风控导致的失败
+    pub auth_failures: usize,
+    /// 限流、超时、网络、上传等接口错误
+    pub api_failures: usize,
+    /// 最近一次失败的原因
+    pub last_failure: Option<String>,
+    /// 从最近一次发布往前连续失败的次数
+    pub consecutive_failures: usize,
+    /// 风控 / 验证码导致的失败（计入 auth_failures）
+    pub risk_control_failures: usize,
+}
+
+/// Reason an account was flagged as risky
+/// 账号被标记风险的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskFlag {
+    /// 连续失败
+    ConsecutiveFailures,
+    /// 失败率过高
+    HighFailureRate,
+    /// 平台返回风控 / 验证码
+    RiskControl,
+}
+
+impl AccountHealth {
+    /// 最近的发布失败率（百分比），没有发布记录时为 None
+    pub fn failure_rate(&self) -> Option<u8> {
+        if self.recent_publishes == 0 {
+            return None;
+        }
+        let failures = self.recent_publishes - self.successes;
+        Some((failures * 100 / self.recent_publishes) as u8)
+    }
+
+    /// Risk flags raised by the recent publish outcomes
+    /// 根据最近的发布结果判断风险，没有风险时返回空
+    pub fn risk_flags(&self) -> Vec<RiskFlag> {
+        let mut flags = Vec::new();
+        if self.consecutive_failures >= RISKY_CONSECUTIVE_FAILURES {
+            flags.push(RiskFlag::ConsecutiveFailures);
+        }
+        if self.recent_publishes >= MIN_PUBLISHES_FOR_RATE
+            && self.failure_rate().is_some_and(|rate| rate >= RISKY_FAILURE_RATE)
+        {
+            flags.push(RiskFlag::HighFailureRate);
+        }
+        if self.risk_control_failures >= RISKY_RISK_CONTROL_FAILURES {
+            flags.push(RiskFlag::RiskControl);
+        }
+        flags
+    }
+}
+
+/// 单次发布结果的得分和权重倍数；与账号无关的结果（文件问题、用户取消）返回 None
+fn outcome_value(status: &str, message: &str) -> Option<(f64, f64)> {
+    match status {
+        "Completed" => Some((1.0, 1.0)),
+        "Failed" if message == PUBLISH_CANCELLED_MESSAGE => None,
+        "Failed" => match classify_failure(message).category {
+            FailureCategory::CredentialExpired | FailureCategory::RiskControl => Some((0.0, 2.0)),
+            FailureCategory::RateLimited => Some((0.3, 1.0)),
+            FailureCategory::Timeout | FailureCategory::Network | FailureCategory::UploadFailed | FailureCategory::Other => {
+                Some((0.5, 1.0))
+            }
+            FailureCategory::ContentRejected => Some((0.6, 1.0)),
+            FailureCategory::InvalidInput => None,
+        },
+        _ => None,
+    }
+}
+
+/// 由最近的发布结果 (状态, 消息)（按时间倒序）计算健康度
+fn compute_health(account_id: &str, outcomes: &[(String, String)]) -> AccountHealth {
+    let mut weighted = 0.0;
+    let mut total_weight = 0.0;
+    let mut weight = 1.0;
+    let mut health = AccountHealth {
+        account_id: account_id.to_string(),
+        score: None,
+        level: AccountHealthLevel::Unknown,
+        recent_publishes: 0,
+        successes: 0,
+        auth_failures: 0,
+        api_failures: 0,
+        last_failure: None,
+        consecutive_failures: 0,
+        risk_control_failures: 0,
+    };
+    let mut failure_streak = true;
+
+    for (status, message) in outcomes {
+        let Some((value, multiplier)) = outcome_value(status, message) else { continue };
+        if health.recent_publishes == HEALTH_WINDOW {
+            break;
+        }
+        health.recent_publishes += 1;
+        weighted += value * weight * multiplier;
+        total_weight += weight * multiplier;
+        weight *= RECENCY_DECAY;
+
+        match (status.as_str(), multiplier > 1.0) {
+            ("Completed", _) => health.successes += 1,
+            (_, true) => health.auth_failures += 1,
+            _ => health.api_failures += 1,
+        }
+        if status == "Completed" {
+            failure_streak = false;
+        } else {
+            if failure_streak {
+                health.consecutive_failures += 1;
+            }
+            if classify_failure(message).category == FailureCategory::RiskControl {
+                health.risk_control_failures += 1;
+            }
+        }
+        if status != "Completed" && health.last_failure.is_none() {
+            health.last_failure = Some(message.clone());
+        }
+    }
+
+    if total_weight > 0.0 {
+        let score = (weighted / total_weight * 100.0).round() as u8;
+        health.score = Some(score);
+        health.level = match score {
+            s if s >= HEALTHY_SCORE => AccountHealthLevel::Healthy,
+            s if s >= CRITICAL_SCORE => AccountHealthLevel::Warning,
+            _ => AccountHealthLevel::Critical,
+        };
+    }
+    health
+}
+
+impl DatabaseManager {
+    /// Compute the health of the given accounts (all accounts when empty)
+    /// 计算账号健康度（account_ids 为空时计算全部账号）
+    pub fn get_account_health(&self, account_ids: &[String]) -> Result<Vec<AccountHealth>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let ids: Vec<String> = if account_ids.is_empty() {
+            conn.prepare("SELECT id FROM accounts WHERE deleted_at = ''")?
+                .query_map([], |row| row.get(0))?
+                .filter_map(|r| r.ok())
+                .collect()
+        } else {
+            account_ids.to_vec()
+        };
+
+        // 结果时间取 published_at（更新状态时写入），缺失时退回 created_at
+        let mut stmt = conn.prepare(r#"
+            SELECT account_id, status, COALESCE(message, '')
+            FROM publication_accounts
+            WHERE status IN ('Completed', 'Failed')
+            ORDER BY COALESCE(NULLIF(published_at, ''), created_at) DESC
+        "#)?;
+        let mut outcomes: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        for (account_id, status, message) in stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+            .filter_map(|r| r.ok())
+        {
+            outcomes.entry(account_id).or_default().push((status, message));
+        }
+
+        Ok(ids.iter()
+            .map(|id| compute_health(id, outcomes.get(id).map(|v| v.as_slice()).unwrap_or_default()))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(status: &str, message: &str) -> (String, String) {
+        (status.to_string(), message.to_string())
+    }
+
+    #[test]
+    fn test_no_history_is_unknown() {
+        let health = compute_health("a", &[]);
+        assert_eq!(health.score, None);
+        assert_eq!(health.level, AccountHealthLevel::Unknown);
+    }
+
+    #[test]
+    fn test_recent_auth_failures_are_critical() {
+        let outcomes = vec![
+            outcome("Failed", "Invalid credentials: Cookie为空，请重新登录"),
+            outcome("Failed", "Authentication failed: 未登录"),
+            outcome("Completed", ""),
+            outcome("Completed", ""),
+        ];
+        let health = compute_health("a", &outcomes);
+        assert_eq!(health.auth_failures, 2);
+        assert_eq!(health.successes, 2);
+        assert_eq!(health.level, AccountHealthLevel::Critical);
+        assert_eq!(health.last_failure.as_deref(), Some("Invalid credentials: Cookie为空，请重新登录"));
+    }
+
+    #[test]
+    fn test_old_failures_weigh_less() {
+        let mut outcomes = vec![outcome("Completed", ""); 5];
+        outcomes.push(outcome("Failed", "Network error: 连接被重置"));
+        let health = compute_health("a", &outcomes);
+        assert_eq!(health.api_failures, 1);
+        assert_eq!(health.level, AccountHealthLevel::Healthy);
+    }
+
+    #[test]
+    fn test_risk_flags() {
+        let outcomes = vec![
+            outcome("Failed", "Network error: 连接被重置"),
+            outcome("Failed", "Publication failed: 发布失败，HTTP 502"),
+            outcome("Failed", "Video upload failed: 所有分片上传失败"),
+            outcome("Completed", ""),
+            outcome("Failed", "Network error: 连接被重置"),
+        ];
+        let health = compute_health("a", &outcomes);
+        assert_eq!(health.consecutive_failures, 3);
+        assert_eq!(health.failure_rate(), Some(80));
+        assert_eq!(health.risk_flags(), vec![RiskFlag::ConsecutiveFailures, RiskFlag::HighFailureRate]);
+
+        let outcomes = vec![
+            outcome("Completed", ""),
+            outcome("Failed", "Publication failed: 请去账号管理列表中解除风控[抖音]"),
+            outcome("Completed", ""),
+        ];
+        let health = compute_health("a", &outcomes);
+        assert_eq!(health.consecutive_failures, 0);
+        assert_eq!(health.risk_control_failures, 1);
+        assert_eq!(health.risk_flags(), vec![RiskFlag::RiskControl]);
+
+        // 发布数不足时不按失败率判断
+        let health = compute_health("a", &[outcome("Failed", "Network error: 连接被重置"), outcome("Completed", "")]);
+        assert!(health.risk_flags().is_empty());
+    }
+
+    #[test]
+    fn test_cancelled_and_input_errors_are_ignored() {
+        let outcomes = vec![
+            outcome("Failed", PUBLISH_CANCELLED_MESSAGE),
+            outcome("Failed", "Invalid input: 视频文件不存在: /tmp/a.mp4"),
+            outcome("Completed", ""),
+        ];
+        let health = compute_health("a", &outcomes);
+        assert_eq!(health.recent_publishes, 1);
+        assert_eq!(health.score, Some(100));
+    }
+}
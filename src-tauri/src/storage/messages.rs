@@ -0,0 +1,82 @@
+// Private messages - creator inbox messages extracted for lead management
+// 私信存储 - 创作者收件箱消息，同一账号下按平台消息ID去重
+
+use super::DatabaseManager;
+use crate::core::PrivateMessage;
+
+const MESSAGE_COLUMNS: &str = "id, account_id, conversation_id, message_id, sender_id, sender_nickname, sender_avatar, content, is_outgoing, create_time, created_at";
+
+fn map_message(row: &rusqlite::Row) -> rusqlite::Result<PrivateMessage> {
+    Ok(PrivateMessage {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        conversation_id: row.get(2)?,
+        message_id: row.get(3)?,
+        sender_id: row.get(4)?,
+        sender_nickname: row.get(5)?,
+        sender_avatar: row.get(6)?,
+        content: row.get(7)?,
+        is_outgoing: row.get::<_, i64>(8)? != 0,
+        create_time: row.get(9)?,
+        created_at: row.get(10)?,
+    })
+}
+
+impl DatabaseManager {
+    /// Save extracted private messages, skipping ones already stored
+    /// 批量保存私信（已存在的消息跳过），返回新保存的条数
+    pub fn save_messages(&self, messages: &[PrivateMessage]) -> Result<usize, rusqlite::Error> {
+        if messages.is_empty() {
+            return Ok(0);
+        }
+
+        self.write("save_messages", |conn| {
+            let tx = conn.transaction()?;
+
+            let mut inserted = 0;
+            for message in messages {
+                inserted += tx.execute(&format!(
+                    "INSERT OR IGNORE INTO messages ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)", MESSAGE_COLUMNS
+                ), rusqlite::params![
+                    message.id,
+                    message.account_id,
+                    message.conversation_id,
+                    message.message_id,
+                    message.sender_id,
+                    message.sender_nickname,
+                    message.sender_avatar,
+                    message.content,
+                    message.is_outgoing as i64,
+                    message.create_time,
+                    message.created_at,
+                ])?;
+            }
+
+            tx.commit()?;
+            Ok(inserted)
+        })
+    }
+
+    /// Get one page of an account's private messages, newest first
+    /// 分页获取账号的私信（按消息时间倒序）
+    pub fn get_messages_paginated(&self, account_id: &str, offset: i64, limit: i64) -> Result<Vec<PrivateMessage>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM messages WHERE account_id = ? ORDER BY create_time DESC, message_id DESC LIMIT ? OFFSET ?",
+            MESSAGE_COLUMNS
+        ))?;
+        let messages = stmt.query_map(rusqlite::params![account_id, limit, offset], map_message)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(messages)
+    }
+
+    /// Count the private messages stored for an account
+    /// 获取账号的私信数量
+    pub fn get_message_count(&self, account_id: &str) -> Result<i64, rusqlite::Error> {
+        let conn = self.get_connection()?;
+        conn.query_row("SELECT COUNT(*) FROM messages WHERE account_id = ?", [account_id], |row| row.get(0))
+    }
+}
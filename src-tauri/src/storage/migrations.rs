@@ -0,0 +1,344 @@
+// Schema migrations - numbered, forward-only migrations tracked in schema_version
+// 数据库迁移 - 按编号顺序执行、只向前不回退，已执行的编号记录在 schema_version 表中。
+//
+// 新增迁移：在 MIGRATIONS 末尾追加一项（编号 +1），不要修改或删除已发布的迁移；
+// 同时更新 initialize_schema 中的建表语句和 schema::EXPECTED_SCHEMA，
+// 因为新数据库直接按最新结构建表，迁移需要能在字段已存在时安全跳过（使用 add_column）。
+// 引入本机制之前的数据库没有版本记录，会从 1 开始依次执行，已存在的字段同样会被跳过
+
+use rusqlite::Connection;
+use super::DatabaseManager;
+
+/// A numbered schema migration
+/// 一次数据库迁移
+pub(super) struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub apply: fn(&DatabaseManager, &Connection) -> rusqlite::Result<()>,
+}
+
+/// 全部迁移，编号从 1 开始连续递增
+pub(super) const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, name: "extractor_configs_login_success", apply: |_, conn| {
+        add_column(conn, "extractor_configs", "login_success_mode", "TEXT DEFAULT 'url_match'")?;
+        for column in ["login_success_api_rule", "login_success_api_operator", "login_success_api_value"] {
+            add_column(conn, "extractor_configs", column, "TEXT")?;
+        }
+        Ok(())
+    }},
+    Migration { version: 2, name: "accounts_on_hold", apply: |_, conn| {
+        add_column(conn, "accounts", "on_hold", "INTEGER DEFAULT 0").map(drop)
+    }},
+    Migration { version: 3, name: "accounts_group_tags", apply: |_, conn| {
+        for column in ["group_id", "tags"] {
+            add_column(conn, "accounts", column, "TEXT DEFAULT ''")?;
+        }
+        Ok(())
+    }},
+    Migration { version: 4, name: "publication_tasks_hashtag_set", apply: |_, conn| {
+        add_column(conn, "publication_tasks", "hashtag_set_id", "TEXT DEFAULT ''").map(drop)
+    }},
+    Migration { version: 5, name: "publication_tasks_video_variants", apply: |_, conn| {
+        for column in ["vertical_video_path", "horizontal_video_path"] {
+            add_column(conn, "publication_tasks", column, "TEXT DEFAULT ''")?;
+        }
+        Ok(())
+    }},
+    Migration { version: 6, name: "publication_tasks_watermark", apply: |_, conn| {
+        add_column(conn, "publication_tasks", "watermark_enabled", "INTEGER DEFAULT 0").map(drop)
+    }},
+    Migration { version: 7, name: "publication_tasks_visibility", apply: |_, conn| {
+        for column in ["visibility_type", "download_allowed"] {
+            add_column(conn, "publication_tasks", column, "INTEGER DEFAULT 0")?;
+        }
+        Ok(())
+    }},
+    Migration { version: 8, name: "publication_tasks_poi_music", apply: |_, conn| {
+        for column in ["poi_id", "poi_name", "music_id", "music_end_time"] {
+            add_column(conn, "publication_tasks", column, "TEXT DEFAULT ''")?;
+        }
+        Ok(())
+    }},
+    Migration { version: 9, name: "publication_tasks_mentions", apply: |_, conn| {
+        add_column(conn, "publication_tasks", "mentions", "TEXT DEFAULT '[]'").map(drop)
+    }},
+    Migration { version: 10, name: "account_groups_watermark", apply: |_, conn| {
+        add_column(conn, "account_groups", "watermark", "TEXT DEFAULT ''").map(drop)
+    }},
+    Migration { version: 11, name: "publication_accounts_strategy_version", apply: |_, conn| {
+        add_column(conn, "publication_accounts", "strategy_version", "TEXT DEFAULT ''").map(drop)
+    }},
+    Migration { version: 12, name: "publication_accounts_overrides", apply: |_, conn| {
+        for column in ["title_override", "description_override", "hashtags_override"] {
+            add_column(conn, "publication_accounts", column, "TEXT DEFAULT ''")?;
+        }
+        Ok(())
+    }},
+    Migration { version: 13, name: "publication_accounts_attempts", apply: |_, conn| {
+        add_column(conn, "publication_accounts", "attempts", "INTEGER DEFAULT 0").map(drop)
+    }},
+    Migration { version: 14, name: "publication_accounts_error_code", apply: |_, conn| {
+        add_column(conn, "publication_accounts", "error_code", "TEXT DEFAULT ''").map(drop)
+    }},
+    Migration { version: 15, name: "publication_accounts_variant", apply: |_, conn| {
+        add_column(conn, "publication_accounts", "variant", "TEXT DEFAULT ''").map(drop)
+    }},
+    Migration { version: 16, name: "account_stats_history_profile", apply: |_, conn| {
+        for column in ["followers", "following", "total_likes", "video_count"] {
+            add_column(conn, "account_stats_history", column, "INTEGER DEFAULT 0")?;
+        }
+        Ok(())
+    }},
+    Migration { version: 17, name: "comments_tags", apply: |_, conn| {
+        add_column(conn, "comments", "tags", "TEXT DEFAULT ''").map(drop)
+    }},
+    Migration { version: 18, name: "comments_sync_id", apply: |_, conn| {
+        // 已有评论视为已同步过，避免升级后把历史评论当作新评论通知
+        if add_column(conn, "comments", "sync_id", "INTEGER")? {
+            conn.execute("UPDATE comments SET sync_id = 0", [])?;
+        }
+        Ok(())
+    }},
+    Migration { version: 19, name: "soft_delete", apply: |_, conn| {
+        for table in ["accounts", "publication_tasks"] {
+            add_column(conn, table, "deleted_at", "TEXT NOT NULL DEFAULT ''")?;
+        }
+        Ok(())
+    }},
+    Migration { version: 20, name: "legacy_publications", apply: |_, conn| {
+        DatabaseManager::migrate_legacy_publications(conn)
+    }},
+    // 明文凭证加密需要凭证密钥，已移出迁移（见 DatabaseManager::get_connection），保留编号为空迁移
+    Migration { version: 21, name: "encrypt_credentials", apply: |_, _| Ok(()) },
+];
+
+/// 字段不存在时添加，返回是否添加了字段；表不存在时跳过（由 initialize_schema 按最新结构建表）
+pub(super) fn add_column(conn: &Connection, table: &str, column: &str, definition: &str) -> rusqlite::Result<bool> {
+    let table_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+        [table],
+        |row| row.get(0),
+    )?;
+    if table_exists == 0 {
+        return Ok(false);
+    }
+
+    let exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?",
+        [table, column],
+        |row| row.get(0),
+    )?;
+    if exists > 0 {
+        return Ok(false);
+    }
+
+    tracing::info!("[Database] Running migration: adding {} column to {}", column, table);
+    conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+    Ok(true)
+}
+
+/// 最新的迁移编号
+pub fn latest_schema_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// 当前版本之后待执行的迁移
+fn pending_migrations(current: u32) -> impl Iterator<Item = &'static Migration> {
+    MIGRATIONS.iter().filter(move |m| m.version > current)
+}
+
+/// 数据库当前的迁移编号（没有执行过迁移时为 0）
+fn current_version(conn: &Connection) -> rusqlite::Result<u32> {
+    conn.query_row("SELECT COALESCE(MAX(version), 0) FROM schema_version", [], |row| row.get(0))
+}
+
+/// Apply pending migrations in order, each in its own transaction
+/// 按编号依次执行未执行的迁移，每个迁移及其版本记录在同一事务中提交，失败时停止并返回错误
+pub(super) fn run_migrations(db: &DatabaseManager, conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(r#"
+        CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )
+    "#, [])?;
+
+    let current = current_version(conn)?;
+    let latest = latest_schema_version();
+    if current > latest {
+        // 数据库由更新版本的应用升级过：只向前迁移，不做任何修改
+        tracing::warn!("[Database] 数据库版本 {} 高于当前应用支持的版本 {}，跳过迁移", current, latest);
+        return Ok(());
+    }
+
+    for migration in pending_migrations(current) {
+        let tx = conn.unchecked_transaction()?;
+        (migration.apply)(db, &tx).map_err(|e| {
+            tracing::error!("[Database] 迁移 {} ({}) 失败: {}", migration.version, migration.name, e);
+            e
+        })?;
+        tx.execute(
+            "INSERT INTO schema_version (version, name) VALUES (?, ?)",
+            rusqlite::params![migration.version, migration.name],
+        )?;
+        tx.commit()?;
+        tracing::info!("[Database] 已执行迁移 {} ({})", migration.version, migration.name);
+    }
+    Ok(())
+}
+
+impl DatabaseManager {
+    /// Current schema migration version of the database
+    /// 数据库当前的迁移编号
+    pub fn get_schema_version(&self) -> rusqlite::Result<u32> {
+        let conn = self.get_connection()?;
+        current_version(&conn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_numbered_consecutively() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, i + 1, "migration {} is out of order", migration.name);
+        }
+        assert_eq!(latest_schema_version() as usize, MIGRATIONS.len());
+    }
+
+    #[test]
+    fn test_pending_migrations() {
+        assert_eq!(pending_migrations(0).count(), MIGRATIONS.len());
+        assert_eq!(pending_migrations(18).map(|m| m.version).collect::<Vec<_>>(), vec![19, 20, 21]);
+        assert_eq!(pending_migrations(latest_schema_version()).count(), 0);
+    }
+
+    fn temp_dir() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("amm-migrations-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn columns(conn: &Connection, table: &str) -> Vec<String> {
+        let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?)").unwrap();
+        let names = stmt.query_map([table], |row| row.get(0)).unwrap()
+            .collect::<rusqlite::Result<Vec<String>>>().unwrap();
+        names
+    }
+
+    #[test]
+    fn test_add_column_skips_missing_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert!(!add_column(&conn, "account_stats_history", "followers", "INTEGER DEFAULT 0").unwrap());
+
+        conn.execute("CREATE TABLE t (id TEXT)", []).unwrap();
+        assert!(add_column(&conn, "t", "name", "TEXT").unwrap());
+        assert!(!add_column(&conn, "t", "name", "TEXT").unwrap());
+    }
+
+    #[test]
+    fn test_new_database_is_at_latest_version() {
+        let dir = temp_dir();
+        let db = DatabaseManager::new(dir.clone());
+
+        assert_eq!(db.get_schema_version().unwrap(), latest_schema_version());
+        assert!(db.get_all_accounts().unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_upgrade_baseline_database() {
+        let dir = temp_dir();
+        let db = DatabaseManager::new(dir.clone());
+
+        // 引入迁移机制之前的结构：没有 schema_version，缺少后来追加的字段，也没有后来新增的表
+        {
+            std::fs::create_dir_all(&dir).unwrap();
+            let conn = Connection::open(db.get_db_path()).unwrap();
+            conn.execute_batch(r#"
+                CREATE TABLE accounts (
+                    id TEXT PRIMARY KEY,
+                    username TEXT NOT NULL,
+                    nickname TEXT NOT NULL,
+                    avatar_url TEXT,
+                    platform TEXT NOT NULL,
+                    params TEXT NOT NULL,
+                    status TEXT NOT NULL DEFAULT 'pending',
+                    created_at TEXT DEFAULT CURRENT_TIMESTAMP
+                );
+                CREATE TABLE comments (
+                    id TEXT PRIMARY KEY,
+                    account_id TEXT NOT NULL,
+                    aweme_id TEXT NOT NULL,
+                    comment_id TEXT NOT NULL,
+                    user_id TEXT NOT NULL,
+                    user_nickname TEXT NOT NULL,
+                    user_avatar TEXT NOT NULL,
+                    content TEXT NOT NULL,
+                    like_count INTEGER DEFAULT 0,
+                    reply_count INTEGER DEFAULT 0,
+                    create_time TEXT NOT NULL,
+                    status TEXT NOT NULL DEFAULT 'pending',
+                    created_at TEXT DEFAULT CURRENT_TIMESTAMP
+                );
+                INSERT INTO accounts (id, username, nickname, platform, params, status)
+                    VALUES ('a1', 'user', '旧账号', 'Douyin', '{}', 'Active');
+                INSERT INTO comments (id, account_id, aweme_id, comment_id, user_id, user_nickname, user_avatar, content, create_time)
+                    VALUES ('c1', 'a1', 'v1', 'c1', 'u1', '网友', '', '旧评论', '2024-01-01 00:00:00');
+            "#).unwrap();
+        }
+
+        assert_eq!(db.get_schema_version().unwrap(), latest_schema_version());
+
+        let conn = Connection::open(db.get_db_path()).unwrap();
+        let account_columns = columns(&conn, "accounts");
+        for column in ["on_hold", "group_id", "tags", "deleted_at"] {
+            assert!(account_columns.iter().any(|c| c == column), "accounts.{} missing", column);
+        }
+        assert!(columns(&conn, "account_stats_history").iter().any(|c| c == "followers"));
+        let sync_id: i64 = conn.query_row("SELECT sync_id FROM comments WHERE id = 'c1'", [], |row| row.get(0)).unwrap();
+        assert_eq!(sync_id, 0);
+        let params: String = conn.query_row("SELECT params FROM accounts WHERE id = 'a1'", [], |row| row.get(0)).unwrap();
+        assert!(crate::storage::credentials::is_encrypted_params(&params));
+
+        let account = db.get_account("a1").unwrap().expect("upgraded account is readable");
+        assert_eq!(account.nickname, "旧账号");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_legacy_publication_with_unknown_platform_is_skipped() {
+        let dir = temp_dir();
+        let db = DatabaseManager::new(dir.clone());
+        {
+            std::fs::create_dir_all(&dir).unwrap();
+            let conn = Connection::open(db.get_db_path()).unwrap();
+            conn.execute_batch(r#"
+                CREATE TABLE publications (
+                    id TEXT PRIMARY KEY,
+                    account_id TEXT,
+                    platform TEXT,
+                    title TEXT,
+                    video_path TEXT,
+                    status TEXT
+                );
+                INSERT INTO publications VALUES ('p1', 'a1', 'douyin', '旧作品', '/v1.mp4', 'completed');
+                INSERT INTO publications VALUES ('p2', 'a1', 'weibo', '未知平台', '/v2.mp4', 'completed');
+            "#).unwrap();
+        }
+
+        assert_eq!(db.get_schema_version().unwrap(), latest_schema_version());
+
+        let task = db.get_publication_task_with_accounts("p1").unwrap().expect("known platform is migrated");
+        assert_eq!(task.accounts.len(), 1);
+        assert!(db.get_publication_task_with_accounts("p2").unwrap().is_none());
+
+        let conn = Connection::open(db.get_db_path()).unwrap();
+        let kept: i64 = conn.query_row("SELECT COUNT(*) FROM publications_legacy", [], |row| row.get(0)).unwrap();
+        assert_eq!(kept, 2);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -1,20 +1,150 @@
 // Storage module - SQLite database operations
 // 存储模块 - SQLite 数据库操作（统一账号表）
 
+pub mod anonymize;
+pub mod auto_reply;
+pub mod backend;
+pub mod backup;
+pub mod cache;
+pub mod comment_export;
+pub mod comment_sync;
+pub mod contention;
+pub mod credentials;
+pub mod failures;
+pub mod hashtags;
+pub mod health;
+pub mod messages;
+pub mod migrations;
+pub mod publish_checkpoints;
+pub mod publish_stats;
+pub mod remote_sync;
+pub mod remote_videos;
+pub mod report;
+pub mod scheduled_jobs;
+pub mod schema;
+pub mod search;
+pub mod task_list;
+pub mod templates;
+pub mod trash;
+pub mod stats;
+pub mod timeline;
+pub mod webhooks;
+
+pub use anonymize::AnonymizedSnapshotSummary;
+pub use backend::{StorageBackend, StorageError, StorageResult};
+pub use backup::{BackupData, BackupSummary, BACKUP_VERSION};
+pub use cache::{CacheManager, CacheCategory, CacheCategoryUsage, CacheCleanupResult};
+pub use comment_export::{CommentExportItem, export_comments_csv, export_comments_xlsx};
+pub use contention::{ContentionStats, contention_stats};
+pub use credentials::{ENCRYPTED_PARAMS_PREFIX, is_encrypted_params};
+pub use failures::{FailureBreakdown, FailureCategoryCount, FailureDailyCount};
+pub use hashtags::{HashtagPerformance, HashtagSuggestion, DEFAULT_HASHTAG_SUGGESTIONS};
+pub use health::{AccountHealth, AccountHealthLevel, RiskFlag, HEALTH_WINDOW};
+pub use publish_checkpoints::{PublishCheckpoint, CheckpointStep, BD_TICKET_TTL_MINUTES};
+pub use publish_stats::{PublishStatistics, StatusCounts, PlatformPublishCounts, AccountPublishCounts, DailyPublishCounts};
+pub use remote_sync::PendingSyncChange;
+pub use report::{ReportQuery, ReportResult, ReportEntitySchema};
+pub use schema::SchemaReport;
+pub use search::{CommentSearchHit, TaskSearchHit, DEFAULT_SEARCH_LIMIT};
+pub use task_list::PublicationTaskFilter;
+pub use stats::{AccountStatsDelta, AccountStatsSnapshot, MetricDelta, StatsPeriod};
+pub use trash::{TrashItem, TrashKind, TrashPurgeResult, DEFAULT_TRASH_RETENTION_DAYS};
+pub use timeline::{AccountTimelineEvent, TimelineEventKind, DEFAULT_TIMELINE_LIMIT};
+
 use rusqlite::{Connection, Result};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
 use crate::core::{
-    UserAccount, PlatformType, AccountStatus,
-    PublicationStatus, PublicationStats, PublicationTask, PublicationAccountDetail,
-    Comment, CommentStatus,
+    UserAccount, AccountGroup, PlatformType, AccountStatus,
+    PublicationStatus, PublicationStats, PublicationTask, PublicationAccountDetail, PublishErrorCode,
+    Comment, CommentStatus, HashtagSet, TaskNote, AccountReassignment, CoverStrategy, CommentTagRules, AiConfig,
+    PublishPacingRules, WatermarkConfig, VideoVariant, NotificationSettings, HttpApiConfig,
 };
 
+/// 发布流水线中每个账号的默认最大尝试次数（含首次发布）
+pub const DEFAULT_MAX_PUBLISH_ATTEMPTS: u32 = 3;
+
+/// 每个账号最大尝试次数上限
+pub const MAX_PUBLISH_ATTEMPTS_LIMIT: u32 = 6;
+
+/// 默认同时发布的账号数
+pub const DEFAULT_MAX_CONCURRENT_PUBLISHES: u32 = 3;
+
+/// 同时发布账号数上限
+pub const MAX_CONCURRENT_PUBLISHES_LIMIT: u32 = 20;
+
+/// SQLite 内置的锁等待时间（毫秒）
+const BUSY_TIMEOUT_MS: u64 = 2000;
+
+// 读取整行时使用的字段列表，与 row_to_* 等按位置读取的代码一一对应。
+// 不使用 SELECT *：补齐字段（迁移、结构校验）都追加在表末尾，字段顺序可能与建表语句不同
+const ACCOUNT_COLUMNS: &str =
+    "id, username, nickname, avatar_url, platform, params, status, created_at, on_hold, group_id, tags";
+const PUBLICATION_TASK_COLUMNS: &str =
+    "id, title, description, video_path, cover_path, hashtags, status, created_at, published_at, \
+     hashtag_set_id, vertical_video_path, horizontal_video_path, watermark_enabled, visibility_type, \
+     download_allowed, poi_id, poi_name, music_id, music_end_time, mentions";
+const PUBLICATION_ACCOUNT_COLUMNS: &str =
+    "id, publication_task_id, account_id, account_name, platform, status, \
+     created_at, published_at, publish_url, comments, likes, favorites, shares, \
+     message, item_id, strategy_version, \
+     title_override, description_override, hashtags_override, attempts, error_code, variant";
+const HASHTAG_SET_COLUMNS: &str = "id, name, tags, platform_tags, created_at, updated_at";
+const COMMENT_COLUMNS: &str =
+    "id, account_id, aweme_id, comment_id, user_id, user_nickname, user_avatar, content, \
+     like_count, reply_count, create_time, status, created_at, tags";
+
+/// Application settings
+/// 应用设置（存储在 app_settings 表中，每个字段一行）
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// 同时发布的账号数（1 ~ MAX_CONCURRENT_PUBLISHES_LIMIT）
+    pub max_concurrent_publishes: u32,
+    /// 重新授权已有账号时默认使用无头浏览器（新账号首次登录始终有界面）
+    pub browser_auth_headless: bool,
+    /// 账号遇到临时性失败（网络、5xx）时的最大尝试次数（1 ~ MAX_PUBLISH_ATTEMPTS_LIMIT，1 表示不自动重试）
+    pub max_publish_attempts: u32,
+    /// 按平台覆盖最大尝试次数（键为平台ID，未设置的平台使用 max_publish_attempts）
+    pub platform_max_publish_attempts: HashMap<String, u32>,
+    /// 发布时为每个账号生成去重变体（裁帧、微调码率、重编码音频），各账号上传的文件互不相同
+    pub video_variants_enabled: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            max_concurrent_publishes: DEFAULT_MAX_CONCURRENT_PUBLISHES,
+            browser_auth_headless: false,
+            max_publish_attempts: DEFAULT_MAX_PUBLISH_ATTEMPTS,
+            platform_max_publish_attempts: HashMap::new(),
+            video_variants_enabled: false,
+        }
+    }
+}
+
+impl AppSettings {
+    /// Maximum publish attempts for a platform, honouring its per-platform override
+    /// 某平台的最大尝试次数（有平台覆盖时使用覆盖值，否则使用全局值）
+    pub fn max_attempts_for(&self, platform: &PlatformType) -> u32 {
+        self.platform_max_publish_attempts.get(platform.as_str())
+            .copied()
+            .unwrap_or(self.max_publish_attempts)
+            .clamp(1, MAX_PUBLISH_ATTEMPTS_LIMIT)
+    }
+}
+
 /// Database manager for SQLite operations
 /// 数据库管理器 - 统一存储所有平台账号
 #[derive(Clone, Debug)]
 pub struct DatabaseManager {
     /// Base path for database files
     pub base_path: PathBuf,
+    /// 数据库结构是否已初始化（克隆之间共享，避免每次连接都重复建表和迁移）
+    schema_initialized: Arc<Mutex<bool>>,
+    /// 账号凭证加密密钥（首次使用时从数据目录读取或生成，克隆之间共享）
+    credential_key: Arc<OnceLock<credentials::CredentialKey>>,
 }
 
 /// Platform extractor configuration struct
@@ -41,7 +171,11 @@ impl DatabaseManager {
     /// Create a new database manager
     /// 创建新的数据库管理器
     pub fn new(base_path: PathBuf) -> Self {
-        Self { base_path }
+        Self {
+            base_path,
+            schema_initialized: Arc::new(Mutex::new(false)),
+            credential_key: Arc::new(OnceLock::new()),
+        }
     }
 
     /// Get unified database path
@@ -59,7 +193,22 @@ impl DatabaseManager {
             std::fs::create_dir_all(parent).ok();
         }
         let conn = Connection::open(&db_path)?;
-        self.initialize_schema(&conn)?;
+        // 遇到写锁时先由 SQLite 自身等待一段时间，仍失败再由 with_busy_retry 退避重试
+        conn.busy_timeout(std::time::Duration::from_millis(BUSY_TIMEOUT_MS))?;
+
+        // 只在首次连接时建表和迁移；加锁防止并发连接同时执行迁移
+        let mut initialized = self.schema_initialized.lock().unwrap_or_else(|e| e.into_inner());
+        if !*initialized {
+            // WAL 模式允许读写并发，减少并发发布时的锁竞争
+            conn.pragma_update(None, "journal_mode", "WAL")?;
+            self.initialize_schema(&conn)?;
+            *initialized = true;
+            // 加密旧版明文凭证需要凭证密钥：不放在迁移中，密钥不可用时只影响凭证读写，不影响其他数据访问
+            if let Err(e) = self.encrypt_plaintext_credentials(&conn) {
+                tracing::warn!("[Database] 加密旧版明文凭证失败，下次启动时重试: {}", e);
+            }
+        }
+
         Ok(conn)
     }
 
@@ -76,7 +225,22 @@ impl DatabaseManager {
                 platform TEXT NOT NULL,
                 params TEXT NOT NULL,
                 status TEXT NOT NULL DEFAULT 'pending',
-                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                on_hold INTEGER DEFAULT 0,
+                group_id TEXT DEFAULT '',
+                tags TEXT DEFAULT '',
+                deleted_at TEXT NOT NULL DEFAULT ''
+            )
+        "#, [])?;
+
+        // Account groups table - 账号分组（任务可直接以分组为目标）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS account_groups (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                description TEXT DEFAULT '',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                watermark TEXT DEFAULT ''
             )
         "#, [])?;
 
@@ -91,12 +255,24 @@ impl DatabaseManager {
                 hashtags TEXT DEFAULT '[]',
                 status TEXT NOT NULL DEFAULT 'draft',
                 created_at TEXT DEFAULT CURRENT_TIMESTAMP,
-                published_at TEXT
+                published_at TEXT,
+                hashtag_set_id TEXT DEFAULT '',
+                vertical_video_path TEXT DEFAULT '',
+                horizontal_video_path TEXT DEFAULT '',
+                watermark_enabled INTEGER DEFAULT 0,
+                visibility_type INTEGER DEFAULT 0,
+                download_allowed INTEGER DEFAULT 0,
+                poi_id TEXT DEFAULT '',
+                poi_name TEXT DEFAULT '',
+                music_id TEXT DEFAULT '',
+                music_end_time TEXT DEFAULT '',
+                mentions TEXT DEFAULT '[]',
+                deleted_at TEXT NOT NULL DEFAULT ''
             )
         "#, [])?;
 
         // Publication accounts table - 账号发布详情子表
-        // 注意：title/description/hashtags 只在主表存储，子表只存储关联信息
+        // 注意：title/description/hashtags 在主表存储，子表只存储关联信息和账号级覆盖
         // 冗余 account_name 字段便于直接显示
         conn.execute(r#"
             CREATE TABLE IF NOT EXISTS publication_accounts (
@@ -114,7 +290,14 @@ impl DatabaseManager {
                 favorites INTEGER DEFAULT 0,
                 shares INTEGER DEFAULT 0,
                 message TEXT DEFAULT '',
-                item_id TEXT DEFAULT ''
+                item_id TEXT DEFAULT '',
+                strategy_version TEXT DEFAULT '',
+                title_override TEXT DEFAULT '',
+                description_override TEXT DEFAULT '',
+                hashtags_override TEXT DEFAULT '',
+                attempts INTEGER DEFAULT 0,
+                error_code TEXT DEFAULT '',
+                variant TEXT DEFAULT ''
             )
         "#, [])?;
 
@@ -124,6 +307,48 @@ impl DatabaseManager {
             ON publication_accounts(publication_task_id)
         "#, [])?;
 
+        // 任务列表按创建时间倒序分页
+        conn.execute(r#"
+            CREATE INDEX IF NOT EXISTS idx_publication_tasks_created_at
+            ON publication_tasks(created_at)
+        "#, [])?;
+
+        // Task notes table - 任务备注
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS task_notes (
+                id TEXT PRIMARY KEY,
+                task_id TEXT NOT NULL,
+                author TEXT NOT NULL DEFAULT '',
+                content TEXT NOT NULL,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        conn.execute(r#"
+            CREATE INDEX IF NOT EXISTS idx_task_notes_task_id
+            ON task_notes(task_id)
+        "#, [])?;
+
+        // Account reassignment history - 发布账号改派记录
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS account_reassignments (
+                id TEXT PRIMARY KEY,
+                detail_id TEXT NOT NULL,
+                task_id TEXT NOT NULL,
+                from_account_id TEXT NOT NULL,
+                from_account_name TEXT NOT NULL DEFAULT '',
+                to_account_id TEXT NOT NULL,
+                to_account_name TEXT NOT NULL DEFAULT '',
+                reason TEXT NOT NULL DEFAULT '',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        conn.execute(r#"
+            CREATE INDEX IF NOT EXISTS idx_account_reassignments_task_id
+            ON account_reassignments(task_id)
+        "#, [])?;
+
         // Platform extractor configs table - 平台数据提取引擎配置
         conn.execute(r#"
             CREATE TABLE IF NOT EXISTS extractor_configs (
@@ -144,12 +369,6 @@ impl DatabaseManager {
             )
         "#, [])?;
 
-        // Run migrations for existing tables
-        self.run_migrations(conn)?;
-
-        // Initialize default configurations for supported platforms
-        Self::initialize_default_configs(conn)?;
-
         // Comments table - 评论表
         conn.execute(r#"
             CREATE TABLE IF NOT EXISTS comments (
@@ -165,7 +384,9 @@ impl DatabaseManager {
                 reply_count INTEGER DEFAULT 0,
                 create_time TEXT NOT NULL,
                 status TEXT NOT NULL DEFAULT 'pending',
-                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                tags TEXT DEFAULT '',
+                sync_id INTEGER
             )
         "#, [])?;
 
@@ -185,6 +406,279 @@ impl DatabaseManager {
             ON comments(comment_id)
         "#, [])?;
 
+        // Hashtag sets table - 话题库（有序话题 + 各平台变体，均为 JSON）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS hashtag_sets (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                tags TEXT NOT NULL DEFAULT '[]',
+                platform_tags TEXT NOT NULL DEFAULT '{}',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        // Hashtags table - 发布过的话题（抖音话题ID缓存 + 使用次数）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS hashtags (
+                name TEXT PRIMARY KEY,
+                douyin_hashtag_id TEXT NOT NULL DEFAULT '',
+                usage_count INTEGER DEFAULT 0,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                last_used_at TEXT DEFAULT ''
+            )
+        "#, [])?;
+
+        // Hashtag usages table - 话题与发布成功的明细的关联
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS hashtag_usages (
+                detail_id TEXT NOT NULL,
+                hashtag TEXT NOT NULL,
+                used_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (detail_id, hashtag)
+            )
+        "#, [])?;
+
+        conn.execute(r#"
+            CREATE INDEX IF NOT EXISTS idx_hashtag_usages_hashtag
+            ON hashtag_usages(hashtag)
+        "#, [])?;
+
+        // Publication templates table - 发布模板（固定的标题/描述/话题/账号，每次只换视频）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS publication_templates (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                title TEXT NOT NULL DEFAULT '',
+                description TEXT NOT NULL DEFAULT '',
+                hashtags TEXT NOT NULL DEFAULT '[]',
+                hashtag_set_id TEXT NOT NULL DEFAULT '',
+                accounts TEXT NOT NULL DEFAULT '[]',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        // Account stats history table - 账号数据每日快照（累计值）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS account_stats_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                account_id TEXT NOT NULL,
+                snapshot_date TEXT NOT NULL,
+                works INTEGER DEFAULT 0,
+                comments INTEGER DEFAULT 0,
+                likes INTEGER DEFAULT 0,
+                favorites INTEGER DEFAULT 0,
+                shares INTEGER DEFAULT 0,
+                followers INTEGER DEFAULT 0,
+                following INTEGER DEFAULT 0,
+                total_likes INTEGER DEFAULT 0,
+                video_count INTEGER DEFAULT 0,
+                UNIQUE(account_id, snapshot_date)
+            )
+        "#, [])?;
+
+        // Scheduled jobs table - 定时发布任务
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS scheduled_jobs (
+                id TEXT PRIMARY KEY,
+                task_id TEXT NOT NULL,
+                send_at TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                message TEXT DEFAULT '',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        conn.execute(r#"
+            CREATE INDEX IF NOT EXISTS idx_scheduled_jobs_status_send_at
+            ON scheduled_jobs(status, send_at)
+        "#, [])?;
+
+        // Auto-reply rules table - 评论自动回复规则
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS auto_reply_rules (
+                id TEXT PRIMARY KEY,
+                account_id TEXT NOT NULL,
+                name TEXT NOT NULL DEFAULT '',
+                keywords TEXT NOT NULL DEFAULT '[]',
+                reply_template TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                max_replies_per_hour INTEGER NOT NULL DEFAULT 20,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        // Auto-reply log table - 自动回复记录
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS auto_reply_logs (
+                id TEXT PRIMARY KEY,
+                rule_id TEXT NOT NULL,
+                account_id TEXT NOT NULL,
+                aweme_id TEXT NOT NULL,
+                comment_id TEXT NOT NULL,
+                comment_content TEXT NOT NULL DEFAULT '',
+                reply_content TEXT NOT NULL DEFAULT '',
+                success INTEGER NOT NULL DEFAULT 0,
+                error TEXT DEFAULT '',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        conn.execute(r#"
+            CREATE INDEX IF NOT EXISTS idx_auto_reply_logs_comment_id
+            ON auto_reply_logs(comment_id)
+        "#, [])?;
+
+        conn.execute(r#"
+            CREATE INDEX IF NOT EXISTS idx_auto_reply_logs_rule_id_created_at
+            ON auto_reply_logs(rule_id, created_at)
+        "#, [])?;
+
+        // Comment sync state table - 评论增量同步进度（每个作品一行）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS comment_sync_state (
+                aweme_id TEXT PRIMARY KEY,
+                detail_id TEXT NOT NULL,
+                cursor INTEGER NOT NULL DEFAULT 0,
+                has_more INTEGER NOT NULL DEFAULT 1,
+                synced_count INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT DEFAULT '',
+                last_synced_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        // Comment sync history - 每次评论同步一行，comments.sync_id 指向首次看到该评论的同步
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS comment_syncs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                aweme_id TEXT NOT NULL,
+                new_count INTEGER NOT NULL DEFAULT 0,
+                synced_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        conn.execute(r#"
+            CREATE INDEX IF NOT EXISTS idx_comment_syncs_aweme_id
+            ON comment_syncs(aweme_id)
+        "#, [])?;
+
+        // Messages table - 私信（创作者收件箱，同一账号下消息ID唯一）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS messages (
+                id TEXT PRIMARY KEY,
+                account_id TEXT NOT NULL,
+                conversation_id TEXT NOT NULL DEFAULT '',
+                message_id TEXT NOT NULL,
+                sender_id TEXT NOT NULL DEFAULT '',
+                sender_nickname TEXT NOT NULL DEFAULT '',
+                sender_avatar TEXT NOT NULL DEFAULT '',
+                content TEXT NOT NULL DEFAULT '',
+                is_outgoing INTEGER NOT NULL DEFAULT 0,
+                create_time TEXT NOT NULL DEFAULT '',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                UNIQUE(account_id, message_id)
+            )
+        "#, [])?;
+
+        conn.execute(r#"
+            CREATE INDEX IF NOT EXISTS idx_messages_account_id_create_time
+            ON messages(account_id, create_time)
+        "#, [])?;
+
+        // Remote videos table - 平台作品（账号在平台上的作品列表，同一账号下作品ID唯一）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS remote_videos (
+                id TEXT PRIMARY KEY,
+                account_id TEXT NOT NULL,
+                item_id TEXT NOT NULL,
+                title TEXT NOT NULL DEFAULT '',
+                cover_url TEXT NOT NULL DEFAULT '',
+                share_url TEXT NOT NULL DEFAULT '',
+                create_time TEXT NOT NULL DEFAULT '',
+                plays INTEGER NOT NULL DEFAULT 0,
+                likes INTEGER NOT NULL DEFAULT 0,
+                comments INTEGER NOT NULL DEFAULT 0,
+                favorites INTEGER NOT NULL DEFAULT 0,
+                shares INTEGER NOT NULL DEFAULT 0,
+                detail_id TEXT NOT NULL DEFAULT '',
+                synced_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                UNIQUE(account_id, item_id)
+            )
+        "#, [])?;
+
+        // Webhook delivery log table - 发布回调投递记录（每条通知每个地址一行）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                id TEXT PRIMARY KEY,
+                url TEXT NOT NULL,
+                event TEXT NOT NULL,
+                task_id TEXT NOT NULL DEFAULT '',
+                detail_id TEXT NOT NULL DEFAULT '',
+                payload TEXT NOT NULL DEFAULT '',
+                success INTEGER NOT NULL DEFAULT 0,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                response_code INTEGER,
+                error TEXT DEFAULT '',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        conn.execute(r#"
+            CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_created_at
+            ON webhook_deliveries(created_at)
+        "#, [])?;
+
+        // Publish checkpoint table - 发布断点（每个账号发布一行，已完成步骤的中间结果，发布成功后删除）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS publish_checkpoints (
+                detail_id TEXT PRIMARY KEY,
+                step INTEGER NOT NULL DEFAULT 0,
+                video_path TEXT NOT NULL DEFAULT '',
+                video_size INTEGER NOT NULL DEFAULT 0,
+                video_id TEXT NOT NULL DEFAULT '',
+                poster_uri TEXT NOT NULL DEFAULT '',
+                bd_ticket TEXT NOT NULL DEFAULT '',
+                bd_ticket_at TEXT NOT NULL DEFAULT '',
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        // App settings table - 应用设置（键值对）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS app_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+        "#, [])?;
+
+        // Sync changes table - 远程同步变更记录（由触发器维护，updated_at 为 UTC）
+        conn.execute(r#"
+            CREATE TABLE IF NOT EXISTS sync_changes (
+                entity TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                deleted INTEGER NOT NULL DEFAULT 0,
+                pushed INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (entity, entity_id)
+            )
+        "#, [])?;
+
+        // Run migrations after all tables exist (migrations may alter any of them)
+        // 所有表建好后再执行迁移；在同步触发器和全文索引之前执行，迁移写入的数据不会产生同步记录
+        self.run_migrations(conn)?;
+
+        // Initialize default configurations for supported platforms
+        Self::initialize_default_configs(conn)?;
+
+        remote_sync::create_sync_triggers(&conn)?;
+
+        // Full-text search indexes - 任务标题 / 描述和评论内容的全文索引（由触发器维护）
+        search::create_search_indexes(conn)?;
+
         Ok(())
     }
 
@@ -195,24 +689,65 @@ impl DatabaseManager {
     /// Save account to database
     /// 保存账号到数据库
     pub fn save_account(&self, account: &UserAccount) -> Result<(), rusqlite::Error> {
-        let conn = self.get_connection()?;
+        let params = self.encrypt_params(&account.params)?;
+
+        self.write("save_account", |conn| {
+            // on_hold / 分组 / 标签只在新建时写入，重新授权不会清除；重新授权回收站中的账号时恢复该账号
+            conn.execute(r#"
+                INSERT INTO accounts (
+                    id, username, nickname, avatar_url, platform, params, status, created_at, on_hold, group_id, tags
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO UPDATE SET
+                    username = excluded.username,
+                    nickname = excluded.nickname,
+                    avatar_url = excluded.avatar_url,
+                    platform = excluded.platform,
+                    params = excluded.params,
+                    status = excluded.status,
+                    created_at = excluded.created_at,
+                    deleted_at = ''
+            "#, rusqlite::params![
+                account.id,
+                account.username,
+                account.nickname,
+                account.avatar_url,
+                format!("{:?}", account.platform),
+                params,
+                format!("{:?}", account.status),
+                account.created_at,
+                account.on_hold,
+                account.group_id.as_deref().unwrap_or(""),
+                serde_json::to_string(&account.tags).unwrap_or_else(|_| "[]".to_string()),
+            ])?;
 
-        conn.execute(r#"
-            INSERT OR REPLACE INTO accounts (
-                id, username, nickname, avatar_url, platform, params, status, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-        "#, &[
-            &account.id,
-            &account.username,
-            &account.nickname,
-            &account.avatar_url,
-            &format!("{:?}", account.platform),
-            &account.params,
-            &format!("{:?}", account.status),
-            &account.created_at,
-        ])?;
+            Ok(())
+        })
+    }
 
-        Ok(())
+    /// Set or clear the maintenance flag of an account
+    /// 设置账号维护状态
+    pub fn set_account_on_hold(&self, account_id: &str, on_hold: bool) -> Result<bool, rusqlite::Error> {
+        self.write("set_account_on_hold", |conn| {
+            let rows = conn.execute(
+                "UPDATE accounts SET on_hold = ? WHERE id = ?",
+                rusqlite::params![on_hold, account_id],
+            )?;
+
+            Ok(rows > 0)
+        })
+    }
+
+    /// Update the credential status of an account
+    /// 更新账号凭证状态（如检测到凭证失效时标记为 Expired）
+    pub fn set_account_status(&self, account_id: &str, status: &AccountStatus) -> Result<bool, rusqlite::Error> {
+        self.write("set_account_status", |conn| {
+            let rows = conn.execute(
+                "UPDATE accounts SET status = ? WHERE id = ?",
+                rusqlite::params![format!("{:?}", status), account_id],
+            )?;
+
+            Ok(rows > 0)
+        })
     }
 
     /// Get account by ID
@@ -220,20 +755,9 @@ impl DatabaseManager {
     pub fn get_account(&self, account_id: &str) -> Result<Option<UserAccount>, rusqlite::Error> {
         let conn = self.get_connection()?;
 
-        let mut stmt = conn.prepare("SELECT * FROM accounts WHERE id = ?")?;
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM accounts WHERE id = ? AND deleted_at = ''", ACCOUNT_COLUMNS))?;
 
-        match stmt.query_row([account_id], |row| {
-            Ok(UserAccount {
-                id: row.get(0)?,
-                username: row.get(1)?,
-                nickname: row.get(2)?,
-                avatar_url: row.get(3)?,
-                platform: Self::parse_platform(row.get::<_, String>(4)?),
-                params: row.get(5)?,
-                status: Self::parse_status(row.get::<_, String>(6)?),
-                created_at: row.get(7)?,
-            })
-        }) {
+        match stmt.query_row([account_id], |row| self.row_to_account(row)) {
             Ok(account) => Ok(Some(account)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e),
@@ -245,19 +769,8 @@ impl DatabaseManager {
     pub fn get_all_accounts(&self) -> Result<Vec<UserAccount>, rusqlite::Error> {
         let conn = self.get_connection()?;
 
-        let mut stmt = conn.prepare("SELECT * FROM accounts ORDER BY created_at DESC")?;
-        let accounts = stmt.query_map([], |row| {
-            Ok(UserAccount {
-                id: row.get(0)?,
-                username: row.get(1)?,
-                nickname: row.get(2)?,
-                avatar_url: row.get(3)?,
-                platform: Self::parse_platform(row.get::<_, String>(4)?),
-                params: row.get(5)?,
-                status: Self::parse_status(row.get::<_, String>(6)?),
-                created_at: row.get(7)?,
-            })
-        })?.filter_map(|r| r.ok()).collect();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM accounts WHERE deleted_at = '' ORDER BY created_at DESC", ACCOUNT_COLUMNS))?;
+        let accounts = stmt.query_map([], |row| self.row_to_account(row))?.filter_map(|r| r.ok()).collect();
 
         Ok(accounts)
     }
@@ -267,35 +780,188 @@ impl DatabaseManager {
     pub fn get_accounts_by_platform(&self, platform: PlatformType) -> Result<Vec<UserAccount>, rusqlite::Error> {
         let conn = self.get_connection()?;
 
-        let mut stmt = conn.prepare("SELECT * FROM accounts WHERE platform = ? ORDER BY created_at DESC")?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM accounts WHERE platform = ? AND deleted_at = '' ORDER BY created_at DESC", ACCOUNT_COLUMNS
+        ))?;
         let platform_str = format!("{:?}", platform);
-        let accounts = stmt.query_map([platform_str], |row| {
-            Ok(UserAccount {
-                id: row.get(0)?,
-                username: row.get(1)?,
-                nickname: row.get(2)?,
-                avatar_url: row.get(3)?,
-                platform: Self::parse_platform(row.get::<_, String>(4)?),
-                params: row.get(5)?,
-                status: Self::parse_status(row.get::<_, String>(6)?),
-                created_at: row.get(7)?,
-            })
-        })?.filter_map(|r| r.ok()).collect();
+        let accounts = stmt.query_map([platform_str], |row| self.row_to_account(row))?.filter_map(|r| r.ok()).collect();
 
         Ok(accounts)
     }
 
-    /// Delete account
-    /// 删除账号（已移除外键约束，可直接删除）
-    pub fn delete_account(&self, account_id: &str) -> Result<bool, rusqlite::Error> {
+    /// Permanently delete an account
+    /// 彻底删除账号（已移除外键约束，可直接删除）；普通删除只移入回收站，见 trash 模块
+    pub(super) fn purge_account(&self, account_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("purge_account", |conn| {
+            let rows = conn.execute(
+                "DELETE FROM accounts WHERE id = ?",
+                [account_id],
+            )?;
+
+            Ok(rows > 0)
+        })
+    }
+
+    fn row_to_account(&self, row: &rusqlite::Row) -> Result<UserAccount, rusqlite::Error> {
+        let group_id: Option<String> = row.get(9)?;
+        Ok(UserAccount {
+            id: row.get(0)?,
+            username: row.get(1)?,
+            nickname: row.get(2)?,
+            avatar_url: row.get(3)?,
+            platform: Self::parse_platform(row, 4)?,
+            params: self.decrypt_params(&row.get::<_, String>(5)?)?,
+            status: Self::parse_status(row.get::<_, String>(6)?),
+            created_at: row.get(7)?,
+            on_hold: row.get::<_, Option<bool>>(8)?.unwrap_or(false),
+            group_id: group_id.filter(|id| !id.is_empty()),
+            tags: Self::decode_tags(row.get(10)?),
+        })
+    }
+
+    // ============================================================================
+    // 账号分组
+    // ============================================================================
+
+    /// Save (create or update) an account group
+    /// 保存账号分组
+    pub fn save_account_group(&self, group: &AccountGroup) -> Result<(), rusqlite::Error> {
+        self.write("save_account_group", |conn| {
+            conn.execute(r#"
+                INSERT INTO account_groups (id, name, description, created_at)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT(id) DO UPDATE SET
+                    name = excluded.name,
+                    description = excluded.description
+            "#, [
+                &group.id,
+                &group.name,
+                &group.description,
+                &group.created_at,
+            ])?;
+
+            Ok(())
+        })
+    }
+
+    /// Get an account group by ID
+    /// 根据ID获取账号分组
+    pub fn get_account_group(&self, group_id: &str) -> Result<Option<AccountGroup>, rusqlite::Error> {
         let conn = self.get_connection()?;
 
-        let rows = conn.execute(
-            "DELETE FROM accounts WHERE id = ?",
-            [account_id],
-        )?;
+        let mut stmt = conn.prepare("SELECT id, name, description, created_at FROM account_groups WHERE id = ?")?;
 
-        Ok(rows > 0)
+        match stmt.query_row([group_id], Self::row_to_account_group) {
+            Ok(group) => Ok(Some(group)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get all account groups
+    /// 获取所有账号分组
+    pub fn get_account_groups(&self) -> Result<Vec<AccountGroup>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare("SELECT id, name, description, created_at FROM account_groups ORDER BY name")?;
+        let groups = stmt.query_map([], Self::row_to_account_group)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(groups)
+    }
+
+    /// Get the watermark configuration of an account group
+    /// 获取账号分组的水印配置（未配置或分组不存在时为 None）
+    pub fn get_group_watermark(&self, group_id: &str) -> Result<Option<WatermarkConfig>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let watermark: Option<String> = match conn.query_row(
+            "SELECT watermark FROM account_groups WHERE id = ?",
+            [group_id],
+            |row| row.get(0),
+        ) {
+            Ok(value) => value,
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(e),
+        };
+
+        Ok(watermark.filter(|v| !v.is_empty()).and_then(|v| serde_json::from_str(&v).ok()))
+    }
+
+    /// Set or clear the watermark configuration of an account group
+    /// 设置账号分组的水印配置（None 为清除）
+    pub fn set_group_watermark(&self, group_id: &str, config: Option<&WatermarkConfig>) -> Result<bool, rusqlite::Error> {
+        self.write("set_group_watermark", |conn| {
+            let value = config.and_then(|c| serde_json::to_string(c).ok()).unwrap_or_default();
+            let rows = conn.execute(
+                "UPDATE account_groups SET watermark = ? WHERE id = ?",
+                [value.as_str(), group_id],
+            )?;
+
+            Ok(rows > 0)
+        })
+    }
+
+    /// Delete an account group and move its accounts out of it
+    /// 删除账号分组，组内账号变为未分组
+    pub fn delete_account_group(&self, group_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("delete_account_group", |conn| {
+            let tx = conn.transaction()?;
+            tx.execute("UPDATE accounts SET group_id = '' WHERE group_id = ?", [group_id])?;
+            let rows = tx.execute("DELETE FROM account_groups WHERE id = ?", [group_id])?;
+            tx.commit()?;
+
+            Ok(rows > 0)
+        })
+    }
+
+    /// Move an account into a group (None to ungroup)
+    /// 设置账号所属分组（None 为移出分组）
+    pub fn assign_account_to_group(&self, account_id: &str, group_id: Option<&str>) -> Result<bool, rusqlite::Error> {
+        self.write("assign_account_to_group", |conn| {
+            let rows = conn.execute(
+                "UPDATE accounts SET group_id = ? WHERE id = ?",
+                [group_id.unwrap_or(""), account_id],
+            )?;
+
+            Ok(rows > 0)
+        })
+    }
+
+    /// Replace the tags of an account
+    /// 设置账号标签
+    pub fn set_account_tags(&self, account_id: &str, tags: &[String]) -> Result<bool, rusqlite::Error> {
+        self.write("set_account_tags", |conn| {
+            let rows = conn.execute(
+                "UPDATE accounts SET tags = ? WHERE id = ?",
+                [&serde_json::to_string(tags).unwrap_or_else(|_| "[]".to_string()), account_id],
+            )?;
+
+            Ok(rows > 0)
+        })
+    }
+
+    /// Get accounts in a group
+    /// 获取分组内的所有账号
+    pub fn get_accounts_by_group(&self, group_id: &str) -> Result<Vec<UserAccount>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM accounts WHERE group_id = ? AND deleted_at = '' ORDER BY created_at DESC", ACCOUNT_COLUMNS
+        ))?;
+        let accounts = stmt.query_map([group_id], |row| self.row_to_account(row))?.filter_map(|r| r.ok()).collect();
+
+        Ok(accounts)
+    }
+
+    fn row_to_account_group(row: &rusqlite::Row) -> Result<AccountGroup, rusqlite::Error> {
+        Ok(AccountGroup {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            description: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+            created_at: row.get(3)?,
+        })
     }
 
     // ============================================================================
@@ -305,98 +971,52 @@ impl DatabaseManager {
     /// Save a publication task (main table)
     /// 保存作品发布任务主表
     pub fn save_publication_task(&self, task: &PublicationTask) -> Result<(), rusqlite::Error> {
-        let conn = self.get_connection()?;
-
-        conn.execute(r#"
-            INSERT OR REPLACE INTO publication_tasks (
-                id, title, description, video_path, cover_path, hashtags, status, created_at, published_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
-        "#, &[
-            &task.id,
-            &task.title,
-            task.description.as_ref().unwrap_or(&String::new()),
-            &task.video_path,
-            task.cover_path.as_ref().unwrap_or(&String::new()),
-            &serde_json::to_string(&task.hashtags).unwrap_or("[]".to_string()),
-            &format!("{:?}", task.status),
-            &task.created_at,
-            task.published_at.as_ref().unwrap_or(&String::new()),
-        ])?;
+        self.write("save_publication_task", |conn| {
+            conn.execute(r#"
+                INSERT OR REPLACE INTO publication_tasks (
+                    id, title, description, video_path, cover_path, hashtags, status, created_at, published_at,
+                    hashtag_set_id, vertical_video_path, horizontal_video_path, watermark_enabled,
+                    visibility_type, download_allowed, poi_id, poi_name, music_id, music_end_time,
+                    mentions
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#, &[
+                &task.id,
+                &task.title,
+                task.description.as_ref().unwrap_or(&String::new()),
+                &task.video_path,
+                task.cover_path.as_ref().unwrap_or(&String::new()),
+                &serde_json::to_string(&task.hashtags).unwrap_or("[]".to_string()),
+                &format!("{:?}", task.status),
+                &task.created_at,
+                task.published_at.as_ref().unwrap_or(&String::new()),
+                task.hashtag_set_id.as_ref().unwrap_or(&String::new()),
+                task.vertical_video_path.as_ref().unwrap_or(&String::new()),
+                task.horizontal_video_path.as_ref().unwrap_or(&String::new()),
+                &(task.watermark_enabled as i32).to_string(),
+                &task.visibility_type.to_string(),
+                &(task.download_allowed as i32).to_string(),
+                task.poi_id.as_ref().unwrap_or(&String::new()),
+                task.poi_name.as_ref().unwrap_or(&String::new()),
+                task.music_id.as_ref().unwrap_or(&String::new()),
+                task.music_end_time.as_ref().unwrap_or(&String::new()),
+                &serde_json::to_string(&task.mentions).unwrap_or("[]".to_string()),
+            ])?;
 
-        Ok(())
+            Ok(())
+        })
     }
 
     /// Save a publication account detail (sub table)
     /// 保存作品账号详情子表
     pub fn save_publication_account_detail(&self, detail: &PublicationAccountDetail) -> Result<(), rusqlite::Error> {
-        let conn = self.get_connection()?;
-
-        conn.execute(r#"
-            INSERT OR REPLACE INTO publication_accounts (
-                id, publication_task_id, account_id, account_name, platform, status,
-                created_at, published_at, publish_url,
-                comments, likes, favorites, shares,
-                message, item_id
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-        "#, &[
-            &detail.id,
-            &detail.publication_task_id,
-            &detail.account_id,
-            &detail.account_name,
-            &format!("{:?}", detail.platform),
-            &format!("{:?}", detail.status),
-            &detail.created_at,
-            detail.published_at.as_ref().unwrap_or(&String::new()),
-            detail.publish_url.as_ref().unwrap_or(&String::new()),
-            &detail.stats.comments.to_string(),
-            &detail.stats.likes.to_string(),
-            &detail.stats.favorites.to_string(),
-            &detail.stats.shares.to_string(),
-            detail.message.as_ref().unwrap_or(&String::new()),
-            detail.item_id.as_ref().unwrap_or(&String::new()),
-        ])?;
-
-        Ok(())
-    }
-
-    /// Save publication task with all account details (transaction)
-    /// 保存任务和所有账号详情（事务）
-    pub fn save_publication_with_accounts(
-        &self,
-        task: &PublicationTask,
-        accounts: &[PublicationAccountDetail],
-    ) -> Result<(), rusqlite::Error> {
-        let mut conn = self.get_connection()?;
-
-        // Start transaction
-        let tx = conn.transaction()?;
-
-        // Save main task (with hashtags)
-        tx.execute(r#"
-            INSERT OR REPLACE INTO publication_tasks (
-                id, title, description, video_path, cover_path, hashtags, status, created_at, published_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
-        "#, &[
-            &task.id,
-            &task.title,
-            task.description.as_ref().unwrap_or(&String::new()),
-            &task.video_path,
-            task.cover_path.as_ref().unwrap_or(&String::new()),
-            &serde_json::to_string(&task.hashtags).unwrap_or("[]".to_string()),
-            &format!("{:?}", task.status),
-            &task.created_at,
-            task.published_at.as_ref().unwrap_or(&String::new()),
-        ])?;
-
-        // Save all account details (only store account info, no title/description/hashtags)
-        for detail in accounts {
-            tx.execute(r#"
+        self.write("save_publication_account_detail", |conn| {
+            conn.execute(r#"
                 INSERT OR REPLACE INTO publication_accounts (
                     id, publication_task_id, account_id, account_name, platform, status,
                     created_at, published_at, publish_url,
                     comments, likes, favorites, shares,
-                    message, item_id
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    message, item_id, title_override, description_override, hashtags_override, attempts, error_code, variant
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#, &[
                 &detail.id,
                 &detail.publication_task_id,
@@ -413,11 +1033,97 @@ impl DatabaseManager {
                 &detail.stats.shares.to_string(),
                 detail.message.as_ref().unwrap_or(&String::new()),
                 detail.item_id.as_ref().unwrap_or(&String::new()),
+                detail.title_override.as_ref().unwrap_or(&String::new()),
+                detail.description_override.as_ref().unwrap_or(&String::new()),
+                &Self::encode_hashtags_override(&detail.hashtags_override),
+                &detail.attempts.to_string(),
+                &detail.error_code.map(|c| c.as_str().to_string()).unwrap_or_default(),
+                &Self::encode_variant(&detail.variant),
             ])?;
-        }
 
-        tx.commit()?;
-        Ok(())
+            Ok(())
+        })
+    }
+
+    /// Save publication task with all account details (transaction)
+    /// 保存任务和所有账号详情（事务）
+    pub fn save_publication_with_accounts(
+        &self,
+        task: &PublicationTask,
+        accounts: &[PublicationAccountDetail],
+    ) -> Result<(), rusqlite::Error> {
+        self.write("save_publication_with_accounts", |conn| {
+            // Start transaction
+            let tx = conn.transaction()?;
+
+            // Save main task (with hashtags)
+            tx.execute(r#"
+                INSERT OR REPLACE INTO publication_tasks (
+                    id, title, description, video_path, cover_path, hashtags, status, created_at, published_at,
+                    hashtag_set_id, vertical_video_path, horizontal_video_path, watermark_enabled,
+                    visibility_type, download_allowed, poi_id, poi_name, music_id, music_end_time,
+                    mentions
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#, &[
+                &task.id,
+                &task.title,
+                task.description.as_ref().unwrap_or(&String::new()),
+                &task.video_path,
+                task.cover_path.as_ref().unwrap_or(&String::new()),
+                &serde_json::to_string(&task.hashtags).unwrap_or("[]".to_string()),
+                &format!("{:?}", task.status),
+                &task.created_at,
+                task.published_at.as_ref().unwrap_or(&String::new()),
+                task.hashtag_set_id.as_ref().unwrap_or(&String::new()),
+                task.vertical_video_path.as_ref().unwrap_or(&String::new()),
+                task.horizontal_video_path.as_ref().unwrap_or(&String::new()),
+                &(task.watermark_enabled as i32).to_string(),
+                &task.visibility_type.to_string(),
+                &(task.download_allowed as i32).to_string(),
+                task.poi_id.as_ref().unwrap_or(&String::new()),
+                task.poi_name.as_ref().unwrap_or(&String::new()),
+                task.music_id.as_ref().unwrap_or(&String::new()),
+                task.music_end_time.as_ref().unwrap_or(&String::new()),
+                &serde_json::to_string(&task.mentions).unwrap_or("[]".to_string()),
+            ])?;
+
+            // Save all account details (only store account info, no title/description/hashtags)
+            for detail in accounts {
+                tx.execute(r#"
+                    INSERT OR REPLACE INTO publication_accounts (
+                        id, publication_task_id, account_id, account_name, platform, status,
+                        created_at, published_at, publish_url,
+                        comments, likes, favorites, shares,
+                        message, item_id, title_override, description_override, hashtags_override, attempts, error_code, variant
+                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#, &[
+                    &detail.id,
+                    &detail.publication_task_id,
+                    &detail.account_id,
+                    &detail.account_name,
+                    &format!("{:?}", detail.platform),
+                    &format!("{:?}", detail.status),
+                    &detail.created_at,
+                    detail.published_at.as_ref().unwrap_or(&String::new()),
+                    detail.publish_url.as_ref().unwrap_or(&String::new()),
+                    &detail.stats.comments.to_string(),
+                    &detail.stats.likes.to_string(),
+                    &detail.stats.favorites.to_string(),
+                    &detail.stats.shares.to_string(),
+                    detail.message.as_ref().unwrap_or(&String::new()),
+                    detail.item_id.as_ref().unwrap_or(&String::new()),
+                    detail.title_override.as_ref().unwrap_or(&String::new()),
+                    detail.description_override.as_ref().unwrap_or(&String::new()),
+                    &Self::encode_hashtags_override(&detail.hashtags_override),
+                    &detail.attempts.to_string(),
+                    &detail.error_code.map(|c| c.as_str().to_string()).unwrap_or_default(),
+                    &Self::encode_variant(&detail.variant),
+                ])?;
+            }
+
+            tx.commit()?;
+            Ok(())
+        })
     }
 
     /// Get publication task by ID
@@ -425,7 +1131,9 @@ impl DatabaseManager {
     pub fn get_publication_task(&self, task_id: &str) -> Result<Option<PublicationTask>, rusqlite::Error> {
         let conn = self.get_connection()?;
 
-        let mut stmt = conn.prepare("SELECT * FROM publication_tasks WHERE id = ?")?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM publication_tasks WHERE id = ? AND deleted_at = ''", PUBLICATION_TASK_COLUMNS
+        ))?;
 
         match stmt.query_row([task_id], |row| {
             let hashtags_str: String = row.get(5)?;
@@ -440,6 +1148,19 @@ impl DatabaseManager {
                 status: Self::parse_publication_status(row.get::<_, String>(6)?),
                 created_at: row.get(7)?,
                 published_at: Some(row.get(8)?),
+                hashtag_set_id: row.get::<_, Option<String>>(9)?.filter(|v| !v.is_empty()),
+                vertical_video_path: row.get::<_, Option<String>>(10)?.filter(|v| !v.is_empty()),
+                horizontal_video_path: row.get::<_, Option<String>>(11)?.filter(|v| !v.is_empty()),
+                watermark_enabled: row.get::<_, Option<bool>>(12)?.unwrap_or(false),
+                visibility_type: row.get::<_, Option<i32>>(13)?.unwrap_or(0),
+                download_allowed: row.get::<_, Option<bool>>(14)?.unwrap_or(false),
+                poi_id: row.get::<_, Option<String>>(15)?.filter(|v| !v.is_empty()),
+                poi_name: row.get::<_, Option<String>>(16)?.filter(|v| !v.is_empty()),
+                music_id: row.get::<_, Option<String>>(17)?.filter(|v| !v.is_empty()),
+                music_end_time: row.get::<_, Option<String>>(18)?.filter(|v| !v.is_empty()),
+                mentions: row.get::<_, Option<String>>(19)?
+                    .and_then(|v| serde_json::from_str(&v).ok())
+                    .unwrap_or_default(),
             })
         }) {
             Ok(task) => Ok(Some(task)),
@@ -454,7 +1175,9 @@ impl DatabaseManager {
         let conn = self.get_connection()?;
 
         // Get all tasks with hashtags
-        let mut task_stmt = conn.prepare("SELECT * FROM publication_tasks ORDER BY created_at DESC")?;
+        let mut task_stmt = conn.prepare(&format!(
+            "SELECT {} FROM publication_tasks WHERE deleted_at = '' ORDER BY created_at DESC", PUBLICATION_TASK_COLUMNS
+        ))?;
         let tasks: Vec<(PublicationTask, String)> = task_stmt.query_map([], |row| {
             let hashtags_str: String = row.get(5)?;
             let hashtags: Vec<String> = serde_json::from_str(&hashtags_str).unwrap_or_default();
@@ -468,11 +1191,24 @@ impl DatabaseManager {
                 status: Self::parse_publication_status(row.get::<_, String>(6)?),
                 created_at: row.get(7)?,
                 published_at: Some(row.get(8)?),
+                hashtag_set_id: row.get::<_, Option<String>>(9)?.filter(|v| !v.is_empty()),
+                vertical_video_path: row.get::<_, Option<String>>(10)?.filter(|v| !v.is_empty()),
+                horizontal_video_path: row.get::<_, Option<String>>(11)?.filter(|v| !v.is_empty()),
+                watermark_enabled: row.get::<_, Option<bool>>(12)?.unwrap_or(false),
+                visibility_type: row.get::<_, Option<i32>>(13)?.unwrap_or(0),
+                download_allowed: row.get::<_, Option<bool>>(14)?.unwrap_or(false),
+                poi_id: row.get::<_, Option<String>>(15)?.filter(|v| !v.is_empty()),
+                poi_name: row.get::<_, Option<String>>(16)?.filter(|v| !v.is_empty()),
+                music_id: row.get::<_, Option<String>>(17)?.filter(|v| !v.is_empty()),
+                music_end_time: row.get::<_, Option<String>>(18)?.filter(|v| !v.is_empty()),
+                mentions: row.get::<_, Option<String>>(19)?
+                    .and_then(|v| serde_json::from_str(&v).ok())
+                    .unwrap_or_default(),
             }, hashtags_str))
         })?.filter_map(|r| r.ok()).collect();
 
         // Get all account details (without title/description/hashtags)
-        let mut acc_stmt = conn.prepare("SELECT * FROM publication_accounts")?;
+        let mut acc_stmt = conn.prepare(&format!("SELECT {} FROM publication_accounts", PUBLICATION_ACCOUNT_COLUMNS))?;
         let accounts: Vec<PublicationAccountDetail> = acc_stmt.query_map([], |row| {
             let message: String = row.get(13)?;
             let item_id: String = row.get(14)?;
@@ -481,7 +1217,7 @@ impl DatabaseManager {
                 publication_task_id: row.get(1)?,
                 account_id: row.get(2)?,
                 account_name: row.get(3)?,  // 冗余的账号名称
-                platform: Self::parse_platform(row.get::<_, String>(4)?),
+                platform: Self::parse_platform(row, 4)?,
                 status: Self::parse_publication_status(row.get::<_, String>(5)?),
                 created_at: row.get(6)?,
                 published_at: Some(row.get(7)?),
@@ -494,6 +1230,13 @@ impl DatabaseManager {
                 },
                 message: if message.is_empty() { None } else { Some(message) },
                 item_id: if item_id.is_empty() { None } else { Some(item_id) },
+                strategy_version: row.get::<_, Option<String>>(15)?.filter(|v| !v.is_empty()),
+                title_override: row.get::<_, Option<String>>(16)?.filter(|v| !v.is_empty()),
+                description_override: row.get::<_, Option<String>>(17)?.filter(|v| !v.is_empty()),
+                hashtags_override: Self::decode_hashtags_override(row.get::<_, Option<String>>(18)?),
+                attempts: row.get::<_, Option<u32>>(19)?.unwrap_or(0),
+                error_code: row.get::<_, Option<String>>(20)?.as_deref().and_then(PublishErrorCode::parse),
+                variant: Self::decode_variant(row.get::<_, Option<String>>(21)?),
             })
         })?.filter_map(|r| r.ok()).collect();
 
@@ -517,6 +1260,17 @@ impl DatabaseManager {
                 status: t.status,
                 created_at: t.created_at,
                 published_at: t.published_at.unwrap_or_default(),
+                hashtag_set_id: t.hashtag_set_id,
+                vertical_video_path: t.vertical_video_path,
+                horizontal_video_path: t.horizontal_video_path,
+                watermark_enabled: t.watermark_enabled,
+                visibility_type: t.visibility_type,
+                download_allowed: t.download_allowed,
+                poi_id: t.poi_id,
+                poi_name: t.poi_name,
+                music_id: t.music_id,
+                music_end_time: t.music_end_time,
+                mentions: t.mentions,
                 accounts: task_accounts,
             });
         }
@@ -529,7 +1283,7 @@ impl DatabaseManager {
     pub fn get_publication_account_detail(&self, detail_id: &str) -> Result<Option<PublicationAccountDetail>, rusqlite::Error> {
         let conn = self.get_connection()?;
 
-        let mut stmt = conn.prepare("SELECT * FROM publication_accounts WHERE id = ?")?;
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM publication_accounts WHERE id = ?", PUBLICATION_ACCOUNT_COLUMNS))?;
 
         match stmt.query_row([detail_id], |row| {
             let message: String = row.get(13)?;
@@ -539,7 +1293,7 @@ impl DatabaseManager {
                 publication_task_id: row.get(1)?,
                 account_id: row.get(2)?,
                 account_name: row.get(3)?,  // 冗余的账号名称
-                platform: Self::parse_platform(row.get::<_, String>(4)?),
+                platform: Self::parse_platform(row, 4)?,
                 status: Self::parse_publication_status(row.get::<_, String>(5)?),
                 created_at: row.get(6)?,
                 published_at: Some(row.get(7)?),
@@ -552,6 +1306,13 @@ impl DatabaseManager {
                 },
                 message: if message.is_empty() { None } else { Some(message) },
                 item_id: if item_id.is_empty() { None } else { Some(item_id) },
+                strategy_version: row.get::<_, Option<String>>(15)?.filter(|v| !v.is_empty()),
+                title_override: row.get::<_, Option<String>>(16)?.filter(|v| !v.is_empty()),
+                description_override: row.get::<_, Option<String>>(17)?.filter(|v| !v.is_empty()),
+                hashtags_override: Self::decode_hashtags_override(row.get::<_, Option<String>>(18)?),
+                attempts: row.get::<_, Option<u32>>(19)?.unwrap_or(0),
+                error_code: row.get::<_, Option<String>>(20)?.as_deref().and_then(PublishErrorCode::parse),
+                variant: Self::decode_variant(row.get::<_, Option<String>>(21)?),
             })
         }) {
             Ok(detail) => Ok(Some(detail)),
@@ -560,24 +1321,45 @@ impl DatabaseManager {
         }
     }
 
-    /// Delete publication task and all its accounts
-    /// 删除作品任务及其所有账号详情
-    pub fn delete_publication_task(&self, task_id: &str) -> Result<bool, rusqlite::Error> {
-        let conn = self.get_connection()?;
+    /// Permanently delete a publication task and all its accounts
+    /// 彻底删除作品任务及其所有账号详情；普通删除只移入回收站，见 trash 模块
+    pub(super) fn purge_publication_task(&self, task_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("purge_publication_task", |conn| {
+            // 发布断点按账号详情关联，需在删除账号详情之前删除
+            conn.execute(
+                "DELETE FROM publish_checkpoints WHERE detail_id IN (SELECT id FROM publication_accounts WHERE publication_task_id = ?)",
+                [task_id],
+            )?;
 
-        // Delete account details first (due to FK constraint, but CASCADE should handle it)
-        conn.execute(
-            "DELETE FROM publication_accounts WHERE publication_task_id = ?",
-            [task_id],
-        )?;
+            // Delete account details first (due to FK constraint, but CASCADE should handle it)
+            conn.execute(
+                "DELETE FROM publication_accounts WHERE publication_task_id = ?",
+                [task_id],
+            )?;
 
-        // Delete task
-        let rows = conn.execute(
-            "DELETE FROM publication_tasks WHERE id = ?",
-            [task_id],
-        )?;
+            conn.execute(
+                "DELETE FROM account_reassignments WHERE task_id = ?",
+                [task_id],
+            )?;
+
+            conn.execute(
+                "DELETE FROM task_notes WHERE task_id = ?",
+                [task_id],
+            )?;
 
-        Ok(rows > 0)
+            conn.execute(
+                "DELETE FROM scheduled_jobs WHERE task_id = ?",
+                [task_id],
+            )?;
+
+            // Delete task
+            let rows = conn.execute(
+                "DELETE FROM publication_tasks WHERE id = ?",
+                [task_id],
+            )?;
+
+            Ok(rows > 0)
+        })
     }
 
     /// Update publication account detail status
@@ -590,21 +1372,136 @@ impl DatabaseManager {
         message: Option<String>,
         item_id: Option<String>,
     ) -> Result<(), rusqlite::Error> {
-        let conn = self.get_connection()?;
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
-        conn.execute(r#"
-            UPDATE publication_accounts
-            SET status = ?, published_at = ?, publish_url = ?, message = ?, item_id = ?
-            WHERE id = ?
-        "#, &[
-            &format!("{:?}", status),
-            &now,
-            publish_url.as_ref().unwrap_or(&String::new()),
-            message.as_ref().unwrap_or(&String::new()),
-            item_id.as_ref().unwrap_or(&String::new()),
-            detail_id,
-        ])?;
+        self.write("update_publication_account_status", |conn| {
+            conn.execute(r#"
+                UPDATE publication_accounts
+                SET status = ?, published_at = ?, publish_url = ?, message = ?, item_id = ?, error_code = ''
+                WHERE id = ?
+            "#, &[
+                &format!("{:?}", status),
+                &now,
+                publish_url.as_ref().unwrap_or(&String::new()),
+                message.as_ref().unwrap_or(&String::new()),
+                item_id.as_ref().unwrap_or(&String::new()),
+                detail_id,
+            ])
+        })?;
+
+        Ok(())
+    }
+
+    /// Mark an account publication as removed from the platform; the synced copy of the video is dropped too
+    /// 将账号发布标记为已从平台删除（保留作品ID和发布时间），并删除同步到的平台作品记录
+    pub fn mark_publication_account_removed(&self, detail_id: &str, message: &str) -> Result<(), rusqlite::Error> {
+        self.write("mark_publication_account_removed", |conn| {
+            let tx = conn.transaction()?;
+            tx.execute(
+                "UPDATE publication_accounts SET status = ?, message = ? WHERE id = ?",
+                [&format!("{:?}", PublicationStatus::Removed), message, detail_id],
+            )?;
+            tx.execute(
+                "DELETE FROM remote_videos WHERE detail_id = ? \
+                 OR (account_id, item_id) IN (SELECT account_id, item_id FROM publication_accounts WHERE id = ?)",
+                [detail_id, detail_id],
+            )?;
+            tx.commit()
+        })
+    }
+
+    /// Update the engagement stats of one account publication
+    /// 更新账号发布的互动数据（点赞、评论、收藏、分享）
+    pub fn update_publication_account_stats(&self, detail_id: &str, stats: &PublicationStats) -> Result<(), rusqlite::Error> {
+        self.write("update_publication_account_stats", |conn| {
+            conn.execute(
+                "UPDATE publication_accounts SET comments = ?, likes = ?, favorites = ?, shares = ? WHERE id = ?",
+                rusqlite::params![stats.comments, stats.likes, stats.favorites, stats.shares, detail_id],
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Get recently published account details that have a platform item id, newest first
+    /// 获取最近发布成功且有作品ID的账号发布（用于后台刷新互动数据）
+    pub fn get_recently_published_detail_ids(&self, since: &str, limit: usize) -> Result<Vec<String>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id FROM publication_accounts \
+             WHERE status = 'Completed' COLLATE NOCASE AND item_id != '' AND published_at >= ? \
+             ORDER BY published_at DESC LIMIT ?"
+        )?;
+        let ids = stmt.query_map(rusqlite::params![since, limit as i64], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// Get publish times of an account since the given time
+    /// 获取账号在指定时间之后成功发布的时间（用于发布节奏限制）
+    pub fn get_account_publish_times(&self, account_id: &str, since: &str) -> Result<Vec<String>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT published_at FROM publication_accounts \
+             WHERE account_id = ? AND status = 'Completed' COLLATE NOCASE AND published_at >= ?"
+        )?;
+        let times = stmt.query_map([account_id, since], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(times)
+    }
+
+    /// Record which strategy module version performed the publish
+    /// 记录执行发布的策略模块版本
+    pub fn set_publication_account_strategy_version(&self, detail_id: &str, version: &str) -> Result<(), rusqlite::Error> {
+        self.write("set_publication_account_strategy_version", |conn| {
+            conn.execute(
+                "UPDATE publication_accounts SET strategy_version = ? WHERE id = ?",
+                [version, detail_id],
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Increment the publish attempt count of one account publication
+    /// 记录一次发布尝试（累计次数，手动重发时清零）
+    pub fn record_publication_attempt(&self, detail_id: &str) -> Result<(), rusqlite::Error> {
+        self.write("record_publication_attempt", |conn| {
+            conn.execute(
+                "UPDATE publication_accounts SET attempts = COALESCE(attempts, 0) + 1 WHERE id = ?",
+                [detail_id],
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Record the video variant uploaded for an account publication
+    /// 记录账号发布使用的去重变体
+    pub fn set_publication_account_variant(&self, detail_id: &str, variant: &VideoVariant) -> Result<(), rusqlite::Error> {
+        self.write("set_publication_account_variant", |conn| {
+            conn.execute(
+                "UPDATE publication_accounts SET variant = ? WHERE id = ?",
+                [Self::encode_variant(&Some(variant.clone())).as_str(), detail_id],
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Record the structured error code of a failed account publication
+    /// 记录账号发布失败的错误码（更新状态时会清空，需在更新状态之后调用）
+    pub fn set_publication_account_error_code(&self, detail_id: &str, code: PublishErrorCode) -> Result<(), rusqlite::Error> {
+        self.write("set_publication_account_error_code", |conn| {
+            conn.execute(
+                "UPDATE publication_accounts SET error_code = ? WHERE id = ?",
+                [code.as_str(), detail_id],
+            )
+        })?;
 
         Ok(())
     }
@@ -624,7 +1521,8 @@ impl DatabaseManager {
         let mut acc_stmt = conn.prepare("
             SELECT id, publication_task_id, account_id, account_name, platform, status,
                    created_at, published_at, publish_url, comments, likes, favorites, shares,
-                   message, item_id
+                   message, item_id, strategy_version,
+                   title_override, description_override, hashtags_override, attempts, error_code, variant
             FROM publication_accounts WHERE publication_task_id = ?
         ")?;
         let accounts: Vec<PublicationAccountDetail> = acc_stmt.query_map([task_id], |row| {
@@ -635,7 +1533,7 @@ impl DatabaseManager {
                 publication_task_id: row.get(1)?,
                 account_id: row.get(2)?,
                 account_name: row.get(3)?,  // 冗余的账号名称
-                platform: Self::parse_platform(row.get::<_, String>(4)?),
+                platform: Self::parse_platform(row, 4)?,
                 status: Self::parse_publication_status(row.get::<_, String>(5)?),
                 created_at: row.get(6)?,
                 published_at: Some(row.get(7)?),
@@ -648,6 +1546,13 @@ impl DatabaseManager {
                 },
                 message: if message.is_empty() { None } else { Some(message) },
                 item_id: if item_id.is_empty() { None } else { Some(item_id) },
+                strategy_version: row.get::<_, Option<String>>(15)?.filter(|v| !v.is_empty()),
+                title_override: row.get::<_, Option<String>>(16)?.filter(|v| !v.is_empty()),
+                description_override: row.get::<_, Option<String>>(17)?.filter(|v| !v.is_empty()),
+                hashtags_override: Self::decode_hashtags_override(row.get::<_, Option<String>>(18)?),
+                attempts: row.get::<_, Option<u32>>(19)?.unwrap_or(0),
+                error_code: row.get::<_, Option<String>>(20)?.as_deref().and_then(PublishErrorCode::parse),
+                variant: Self::decode_variant(row.get::<_, Option<String>>(21)?),
             })
         })?.filter_map(|r| r.ok()).collect();
 
@@ -661,6 +1566,17 @@ impl DatabaseManager {
             status: task.status,
             created_at: task.created_at,
             published_at: task.published_at.unwrap_or_default(),
+            hashtag_set_id: task.hashtag_set_id,
+            vertical_video_path: task.vertical_video_path,
+            horizontal_video_path: task.horizontal_video_path,
+            watermark_enabled: task.watermark_enabled,
+            visibility_type: task.visibility_type,
+            download_allowed: task.download_allowed,
+            poi_id: task.poi_id,
+            poi_name: task.poi_name,
+            music_id: task.music_id,
+            music_end_time: task.music_end_time,
+            mentions: task.mentions,
             accounts,
         }))
     }
@@ -673,26 +1589,27 @@ impl DatabaseManager {
     /// - 如果所有子表都完成（Completed 或 Failed）：
     ///   - 如果至少有一个 Completed -> 主表 Completed
     ///   - 如果全部 Failed -> 主表 Failed
+    /// - 已发布的作品全部从平台删除（没有 Completed，至少一个 Removed）-> 主表 Removed
     pub fn update_task_status_from_accounts(&self, task_id: &str) -> Result<(), rusqlite::Error> {
-        let conn = self.get_connection()?;
-
-        let mut stmt = conn.prepare("SELECT status FROM publication_accounts WHERE publication_task_id = ?")?;
-        let statuses: Vec<String> = stmt.query_map([task_id], |row| Ok(row.get(0)?))?
-            .filter_map(|r| r.ok())
-            .collect();
+        self.write("update_task_status_from_accounts", |conn| {
+            let mut stmt = conn.prepare("SELECT status FROM publication_accounts WHERE publication_task_id = ?")?;
+            let statuses: Vec<String> = stmt.query_map([task_id], |row| row.get(0))?
+                .filter_map(|r| r.ok())
+                .collect();
 
-        if statuses.is_empty() {
-            return Ok(());
-        }
+            if statuses.is_empty() {
+                return Ok(());
+            }
 
-        let new_status = Self::calculate_task_status(&statuses);
+            let new_status = Self::calculate_task_status(&statuses);
 
-        conn.execute(
-            "UPDATE publication_tasks SET status = ? WHERE id = ?",
-            [&format!("{:?}", new_status), task_id],
-        )?;
+            conn.execute(
+                "UPDATE publication_tasks SET status = ? WHERE id = ?",
+                [&format!("{:?}", new_status), task_id],
+            )?;
 
-        Ok(())
+            Ok(())
+        })
     }
 
     /// Calculate task status from account statuses
@@ -701,6 +1618,8 @@ impl DatabaseManager {
         let mut has_publishing = false;
         let mut has_completed = false;
         let mut has_failed = false;
+        let mut has_skipped = false;
+        let mut has_removed = false;
 
         for status in account_statuses {
             match status.to_lowercase().as_str() {
@@ -708,6 +1627,8 @@ impl DatabaseManager {
                 "publishing" => has_publishing = true,
                 "completed" => has_completed = true,
                 "failed" => has_failed = true,
+                "skipped" => has_skipped = true,
+                "removed" => has_removed = true,
                 _ => {}
             }
         }
@@ -716,8 +1637,12 @@ impl DatabaseManager {
             PublicationStatus::Publishing
         } else if has_completed {
             PublicationStatus::Completed
+        } else if has_removed {
+            PublicationStatus::Removed
         } else if has_failed {
             PublicationStatus::Failed
+        } else if has_skipped {
+            PublicationStatus::Skipped
         } else {
             PublicationStatus::Draft
         }
@@ -726,15 +1651,37 @@ impl DatabaseManager {
     /// Reset account status for retry (set Draft and clear message)
     /// 重置账号状态用于重发
     pub fn reset_account_for_retry(&self, detail_id: &str) -> Result<(), rusqlite::Error> {
-        let conn = self.get_connection()?;
+        self.write("reset_account_for_retry", |conn| {
+            conn.execute(r#"
+                UPDATE publication_accounts
+                SET status = 'draft', message = '', item_id = '', published_at = '', publish_url = '', attempts = 0, error_code = ''
+                WHERE id = ?
+            "#, [detail_id])?;
+
+            Ok(())
+        })
+    }
 
-        conn.execute(r#"
-            UPDATE publication_accounts
-            SET status = 'draft', message = '', item_id = '', published_at = '', publish_url = ''
-            WHERE id = ?
-        "#, [detail_id])?;
+    /// Replace a task's main video and reset the given accounts to Draft if they failed or were skipped
+    /// 替换任务主视频，并把指定账号中发布失败 / 跳过的重置为草稿（已发布的保持不变），返回重置的账号数
+    pub fn replace_task_video(&self, task_id: &str, video_path: &str, detail_ids: &[String]) -> Result<usize, rusqlite::Error> {
+        self.write("replace_task_video", |conn| {
+            let tx = conn.transaction()?;
+
+            tx.execute("UPDATE publication_tasks SET video_path = ? WHERE id = ?", [video_path, task_id])?;
+
+            let mut reset = 0;
+            for detail_id in detail_ids {
+                reset += tx.execute(r#"
+                    UPDATE publication_accounts
+                    SET status = 'draft', message = '', item_id = '', published_at = '', publish_url = '', attempts = 0, error_code = '', variant = ''
+                    WHERE id = ? AND publication_task_id = ? AND lower(status) IN ('failed', 'skipped')
+                "#, [detail_id.as_str(), task_id])?;
+            }
+            tx.commit()?;
 
-        Ok(())
+            Ok(reset)
+        })
     }
 
     /// Get accounts that need retry (Draft or Failed status)
@@ -745,9 +1692,10 @@ impl DatabaseManager {
         let mut stmt = conn.prepare("
             SELECT id, publication_task_id, account_id, account_name, platform, status,
                    created_at, published_at, publish_url, comments, likes, favorites, shares,
-                   message, item_id
+                   message, item_id, strategy_version,
+                   title_override, description_override, hashtags_override, attempts, error_code, variant
             FROM publication_accounts
-            WHERE publication_task_id = ? AND status IN ('draft', 'failed')
+            WHERE publication_task_id = ? AND status IN ('draft', 'failed', 'skipped')
         ")?;
 
         let accounts: Vec<PublicationAccountDetail> = stmt.query_map([task_id], |row| {
@@ -758,7 +1706,7 @@ impl DatabaseManager {
                 publication_task_id: row.get(1)?,
                 account_id: row.get(2)?,
                 account_name: row.get(3)?,
-                platform: Self::parse_platform(row.get::<_, String>(4)?),
+                platform: Self::parse_platform(row, 4)?,
                 status: Self::parse_publication_status(row.get::<_, String>(5)?),
                 created_at: row.get(6)?,
                 published_at: Some(row.get(7)?),
@@ -771,6 +1719,13 @@ impl DatabaseManager {
                 },
                 message: if message.is_empty() { None } else { Some(message) },
                 item_id: if item_id.is_empty() { None } else { Some(item_id) },
+                strategy_version: row.get::<_, Option<String>>(15)?.filter(|v| !v.is_empty()),
+                title_override: row.get::<_, Option<String>>(16)?.filter(|v| !v.is_empty()),
+                description_override: row.get::<_, Option<String>>(17)?.filter(|v| !v.is_empty()),
+                hashtags_override: Self::decode_hashtags_override(row.get::<_, Option<String>>(18)?),
+                attempts: row.get::<_, Option<u32>>(19)?.unwrap_or(0),
+                error_code: row.get::<_, Option<String>>(20)?.as_deref().and_then(PublishErrorCode::parse),
+                variant: Self::decode_variant(row.get::<_, Option<String>>(21)?),
             })
         })?.filter_map(|r| r.ok()).collect();
 
@@ -780,30 +1735,466 @@ impl DatabaseManager {
     /// Update main task status
     /// 直接更新主表状态
     pub fn update_publication_task_status(&self, task_id: &str, status: PublicationStatus) -> Result<(), rusqlite::Error> {
+        self.write("update_publication_task_status", |conn| {
+            conn.execute(
+                "UPDATE publication_tasks SET status = ? WHERE id = ?",
+                [&format!("{:?}", status), task_id],
+            )?;
+
+            Ok(())
+        })
+    }
+
+    // ============================================================================
+    // 任务备注操作
+    // ============================================================================
+
+    /// Add a note to a publication task
+    /// 添加任务备注
+    pub fn add_task_note(&self, note: &TaskNote) -> Result<(), rusqlite::Error> {
+        self.write("add_task_note", |conn| {
+            conn.execute(
+                "INSERT INTO task_notes (id, task_id, author, content, created_at) VALUES (?, ?, ?, ?, ?)",
+                [&note.id, &note.task_id, &note.author, &note.content, &note.created_at],
+            )?;
+
+            Ok(())
+        })
+    }
+
+    /// Get all notes of a publication task (oldest first)
+    /// 获取任务的所有备注（按时间正序）
+    pub fn get_task_notes(&self, task_id: &str) -> Result<Vec<TaskNote>, rusqlite::Error> {
         let conn = self.get_connection()?;
 
-        conn.execute(
-            "UPDATE publication_tasks SET status = ? WHERE id = ?",
-            [&format!("{:?}", status), task_id],
+        let mut stmt = conn.prepare(
+            "SELECT id, task_id, author, content, created_at FROM task_notes WHERE task_id = ? ORDER BY created_at, rowid"
         )?;
+        let notes = stmt.query_map([task_id], |row| {
+            Ok(TaskNote {
+                id: row.get(0)?,
+                task_id: row.get(1)?,
+                author: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?.filter_map(|r| r.ok()).collect();
 
-        Ok(())
+        Ok(notes)
     }
 
     // ============================================================================
-    // 辅助方法
+    // 发布账号改派
     // ============================================================================
 
-    /// Parse platform string
-    /// 解析平台字符串
-    fn parse_platform(s: String) -> PlatformType {
-        match s.to_lowercase().as_str() {
-            "douyin" => PlatformType::Douyin,
-            "xiaohongshu" => PlatformType::Xiaohongshu,
-            "kuaishou" => PlatformType::Kuaishou,
-            "bilibili" => PlatformType::Bilibili,
-            _ => PlatformType::Douyin,
+    /// Move a not-yet-published account detail to another account, keeping a history record
+    /// 将未发布的账号详情改派到其他账号（同一事务内更新子表并写入改派记录）
+    ///
+    /// 已发布成功或正在发布的详情不会被修改，此时返回 false
+    pub fn reassign_publication_account(&self, record: &AccountReassignment) -> Result<bool, rusqlite::Error> {
+        self.write("reassign_publication_account", |conn| {
+            let tx = conn.transaction()?;
+
+            let updated = tx.execute(r#"
+                UPDATE publication_accounts
+                SET account_id = ?, account_name = ?,
+                    status = 'Draft', message = '', item_id = '', published_at = '', publish_url = ''
+                WHERE id = ? AND LOWER(status) NOT IN ('completed', 'publishing')
+            "#, [&record.to_account_id, &record.to_account_name, &record.detail_id])?;
+
+            if updated == 0 {
+                return Ok(false);
+            }
+
+            tx.execute(r#"
+                INSERT INTO account_reassignments (
+                    id, detail_id, task_id, from_account_id, from_account_name,
+                    to_account_id, to_account_name, reason, created_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#, [
+                &record.id,
+                &record.detail_id,
+                &record.task_id,
+                &record.from_account_id,
+                &record.from_account_name,
+                &record.to_account_id,
+                &record.to_account_name,
+                &record.reason,
+                &record.created_at,
+            ])?;
+
+            tx.commit()?;
+            Ok(true)
+        })
+    }
+
+    /// Get the reassignment history of a publication task (oldest first)
+    /// 获取任务的账号改派记录（按时间正序）
+    pub fn get_account_reassignments(&self, task_id: &str) -> Result<Vec<AccountReassignment>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(r#"
+            SELECT id, detail_id, task_id, from_account_id, from_account_name,
+                   to_account_id, to_account_name, reason, created_at
+            FROM account_reassignments WHERE task_id = ? ORDER BY created_at, rowid
+        "#)?;
+        let records = stmt.query_map([task_id], |row| {
+            Ok(AccountReassignment {
+                id: row.get(0)?,
+                detail_id: row.get(1)?,
+                task_id: row.get(2)?,
+                from_account_id: row.get(3)?,
+                from_account_name: row.get(4)?,
+                to_account_id: row.get(5)?,
+                to_account_name: row.get(6)?,
+                reason: row.get(7)?,
+                created_at: row.get(8)?,
+            })
+        })?.filter_map(|r| r.ok()).collect();
+
+        Ok(records)
+    }
+
+    // ============================================================================
+    // 话题库操作
+    // ============================================================================
+
+    /// Save (create or update) a hashtag set
+    /// 保存话题库（新建或更新）
+    pub fn save_hashtag_set(&self, set: &HashtagSet) -> Result<(), rusqlite::Error> {
+        self.write("save_hashtag_set", |conn| {
+            conn.execute(r#"
+                INSERT INTO hashtag_sets (id, name, tags, platform_tags, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO UPDATE SET
+                    name = excluded.name,
+                    tags = excluded.tags,
+                    platform_tags = excluded.platform_tags,
+                    updated_at = excluded.updated_at
+            "#, [
+                &set.id,
+                &set.name,
+                &serde_json::to_string(&set.tags).unwrap_or("[]".to_string()),
+                &serde_json::to_string(&set.platform_tags).unwrap_or("{}".to_string()),
+                &set.created_at,
+                &set.updated_at,
+            ])?;
+
+            Ok(())
+        })
+    }
+
+    /// Get a hashtag set by ID
+    /// 根据ID获取话题库
+    pub fn get_hashtag_set(&self, set_id: &str) -> Result<Option<HashtagSet>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM hashtag_sets WHERE id = ?", HASHTAG_SET_COLUMNS))?;
+
+        match stmt.query_row([set_id], Self::row_to_hashtag_set) {
+            Ok(set) => Ok(Some(set)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get all hashtag sets
+    /// 获取所有话题库
+    pub fn get_all_hashtag_sets(&self) -> Result<Vec<HashtagSet>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM hashtag_sets ORDER BY name", HASHTAG_SET_COLUMNS))?;
+        let sets = stmt.query_map([], Self::row_to_hashtag_set)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(sets)
+    }
+
+    /// Delete a hashtag set and detach it from tasks
+    /// 删除话题库，并解除任务上的关联（任务保留原有话题）
+    pub fn delete_hashtag_set(&self, set_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("delete_hashtag_set", |conn| {
+            let tx = conn.transaction()?;
+            tx.execute("UPDATE publication_tasks SET hashtag_set_id = '' WHERE hashtag_set_id = ?", [set_id])?;
+            let rows = tx.execute("DELETE FROM hashtag_sets WHERE id = ?", [set_id])?;
+            tx.commit()?;
+
+            Ok(rows > 0)
+        })
+    }
+
+    /// Attach a hashtag set to a task (None to detach)
+    /// 将话题库关联到任务，同时用话题库的默认话题覆盖任务话题
+    pub fn attach_hashtag_set_to_task(&self, task_id: &str, set: Option<&HashtagSet>) -> Result<bool, rusqlite::Error> {
+        self.write("attach_hashtag_set_to_task", |conn| {
+            let rows = match set {
+                Some(set) => conn.execute(
+                    "UPDATE publication_tasks SET hashtag_set_id = ?, hashtags = ? WHERE id = ?",
+                    [
+                        set.id.as_str(),
+                        &serde_json::to_string(&set.tags).unwrap_or("[]".to_string()),
+                        task_id,
+                    ],
+                )?,
+                None => conn.execute(
+                    "UPDATE publication_tasks SET hashtag_set_id = '' WHERE id = ?",
+                    [task_id],
+                )?,
+            };
+
+            Ok(rows > 0)
+        })
+    }
+
+    fn row_to_hashtag_set(row: &rusqlite::Row) -> Result<HashtagSet, rusqlite::Error> {
+        let tags_str: String = row.get(2)?;
+        let platform_tags_str: String = row.get(3)?;
+        Ok(HashtagSet {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            tags: serde_json::from_str(&tags_str).unwrap_or_default(),
+            platform_tags: serde_json::from_str(&platform_tags_str).unwrap_or_default(),
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        })
+    }
+
+    // ============================================================================
+    // 应用设置
+    // ============================================================================
+
+    /// Get a setting value
+    /// 获取设置项
+    pub fn get_setting(&self, key: &str) -> Result<Option<String>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare("SELECT value FROM app_settings WHERE key = ?")?;
+        let mut rows = stmt.query([key])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(row.get(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Save a setting value
+    /// 保存设置项
+    pub fn set_setting(&self, key: &str, value: &str) -> Result<(), rusqlite::Error> {
+        self.write("set_setting", |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO app_settings (key, value, updated_at) VALUES (?, ?, CURRENT_TIMESTAMP)",
+                [key, value],
+            )?;
+
+            Ok(())
+        })
+    }
+
+    /// Get application settings, falling back to defaults for unset keys
+    /// 获取应用设置（未设置的项使用默认值）
+    pub fn get_app_settings(&self) -> Result<AppSettings, rusqlite::Error> {
+        let defaults = AppSettings::default();
+        Ok(AppSettings {
+            max_concurrent_publishes: self.get_setting("max_concurrent_publishes")?
+                .and_then(|v| v.parse::<u32>().ok())
+                .map(|v| v.clamp(1, MAX_CONCURRENT_PUBLISHES_LIMIT))
+                .unwrap_or(defaults.max_concurrent_publishes),
+            browser_auth_headless: self.get_setting("browser_auth_headless")?
+                .map(|v| v == "true")
+                .unwrap_or(defaults.browser_auth_headless),
+            max_publish_attempts: self.get_setting("max_publish_attempts")?
+                .and_then(|v| v.parse::<u32>().ok())
+                .map(|v| v.clamp(1, MAX_PUBLISH_ATTEMPTS_LIMIT))
+                .unwrap_or(defaults.max_publish_attempts),
+            platform_max_publish_attempts: self.get_platform_max_publish_attempts()?,
+            video_variants_enabled: self.get_setting("video_variants_enabled")?
+                .map(|v| v == "true")
+                .unwrap_or(defaults.video_variants_enabled),
+        })
+    }
+
+    /// Save application settings; out-of-range values are clamped and the stored settings returned
+    /// 保存应用设置，超出范围的值会被修正，返回实际生效的设置
+    pub fn save_app_settings(&self, settings: &AppSettings) -> Result<AppSettings, rusqlite::Error> {
+        let max_concurrent = settings.max_concurrent_publishes.clamp(1, MAX_CONCURRENT_PUBLISHES_LIMIT);
+        self.set_setting("max_concurrent_publishes", &max_concurrent.to_string())?;
+        self.set_setting("browser_auth_headless", &settings.browser_auth_headless.to_string())?;
+        let max_attempts = settings.max_publish_attempts.clamp(1, MAX_PUBLISH_ATTEMPTS_LIMIT);
+        self.set_setting("max_publish_attempts", &max_attempts.to_string())?;
+        for platform in PlatformType::ALL.iter() {
+            // 空值表示该平台不覆盖，沿用全局尝试次数
+            let value = settings.platform_max_publish_attempts.get(platform.as_str())
+                .map(|v| (*v).clamp(1, MAX_PUBLISH_ATTEMPTS_LIMIT).to_string())
+                .unwrap_or_default();
+            self.set_setting(&format!("max_publish_attempts.{}", platform.as_str()), &value)?;
+        }
+        self.set_setting("video_variants_enabled", &settings.video_variants_enabled.to_string())?;
+        self.get_app_settings()
+    }
+
+    /// Per-platform attempt overrides; legacy `publish_retries.{platform}` values count as retries + 1
+    /// 读取各平台的尝试次数覆盖（兼容旧的 publish_retries.{platform} 重试次数设置，尝试次数 = 重试次数 + 1）
+    fn get_platform_max_publish_attempts(&self) -> Result<HashMap<String, u32>, rusqlite::Error> {
+        let mut overrides = HashMap::new();
+        for platform in PlatformType::ALL.iter() {
+            let attempts = match self.get_setting(&format!("max_publish_attempts.{}", platform.as_str()))? {
+                Some(value) => value.parse::<u32>().ok(),
+                None => self.get_setting(&format!("publish_retries.{}", platform.as_str()))?
+                    .and_then(|v| v.parse::<u32>().ok())
+                    .map(|retries| retries.saturating_add(1)),
+            };
+            if let Some(attempts) = attempts {
+                overrides.insert(platform.as_str().to_string(), attempts.clamp(1, MAX_PUBLISH_ATTEMPTS_LIMIT));
+            }
         }
+        Ok(overrides)
+    }
+
+    /// Get the default cover strategy used when a task has no cover
+    /// 获取任务未指定封面时的默认封面策略
+    pub fn get_cover_strategy(&self) -> Result<CoverStrategy, rusqlite::Error> {
+        Ok(self.get_setting("publish.cover_strategy")?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    /// Set the default cover strategy
+    /// 设置默认封面策略
+    pub fn set_cover_strategy(&self, strategy: &CoverStrategy) -> Result<(), rusqlite::Error> {
+        let value = serde_json::to_string(strategy).unwrap_or_default();
+        self.set_setting("publish.cover_strategy", &value)
+    }
+
+    /// Get the HTTP API configuration
+    /// 获取 HTTP API 配置
+    pub fn get_http_api_config(&self) -> Result<HttpApiConfig, rusqlite::Error> {
+        Ok(self.get_setting("http_api.config")?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    /// Save the HTTP API configuration
+    /// 保存 HTTP API 配置
+    pub fn set_http_api_config(&self, config: &HttpApiConfig) -> Result<(), rusqlite::Error> {
+        let value = serde_json::to_string(config).unwrap_or_default();
+        self.set_setting("http_api.config", &value)
+    }
+
+    /// Get the desktop notification settings
+    /// 获取系统通知设置
+    pub fn get_notification_settings(&self) -> Result<NotificationSettings, rusqlite::Error> {
+        Ok(self.get_setting("notification.settings")?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    /// Set the desktop notification settings
+    /// 设置系统通知开关
+    pub fn set_notification_settings(&self, settings: &NotificationSettings) -> Result<(), rusqlite::Error> {
+        let value = serde_json::to_string(settings).unwrap_or_default();
+        self.set_setting("notification.settings", &value)
+    }
+
+    /// Get the publish pacing rules
+    /// 获取发布节奏规则
+    pub fn get_publish_pacing_rules(&self) -> Result<PublishPacingRules, rusqlite::Error> {
+        Ok(self.get_setting("publish.pacing_rules")?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    /// Set the publish pacing rules
+    /// 设置发布节奏规则
+    pub fn set_publish_pacing_rules(&self, rules: &PublishPacingRules) -> Result<(), rusqlite::Error> {
+        let value = serde_json::to_string(rules).unwrap_or_default();
+        self.set_setting("publish.pacing_rules", &value)
+    }
+
+    /// Get the keyword lists used to tag comments
+    /// 获取评论分类关键词配置
+    pub fn get_comment_tag_rules(&self) -> Result<CommentTagRules, rusqlite::Error> {
+        Ok(self.get_setting("comments.tag_rules")?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    /// Set the keyword lists used to tag comments
+    /// 设置评论分类关键词配置
+    pub fn set_comment_tag_rules(&self, rules: &CommentTagRules) -> Result<(), rusqlite::Error> {
+        let value = serde_json::to_string(rules).unwrap_or_default();
+        self.set_setting("comments.tag_rules", &value)
+    }
+
+    /// Get the AI caption generation configuration
+    /// 获取 AI 文案生成配置
+    pub fn get_ai_config(&self) -> Result<AiConfig, rusqlite::Error> {
+        Ok(self.get_setting("ai.config")?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    /// Save the AI caption generation configuration
+    /// 保存 AI 文案生成配置
+    pub fn set_ai_config(&self, config: &AiConfig) -> Result<(), rusqlite::Error> {
+        let value = serde_json::to_string(config).unwrap_or_default();
+        self.set_setting("ai.config", &value)
+    }
+
+    /// Get the filename parsing regex used by batch import (None when not configured)
+    /// 获取批量导入的文件名解析规则（未配置时为 None）
+    pub fn get_filename_pattern(&self) -> Result<Option<String>, rusqlite::Error> {
+        Ok(self.get_setting("import.filename_pattern")?.filter(|v| !v.trim().is_empty()))
+    }
+
+    /// Set the filename parsing regex (empty string disables parsing)
+    /// 设置文件名解析规则（空字符串表示不解析）
+    pub fn set_filename_pattern(&self, pattern: &str) -> Result<(), rusqlite::Error> {
+        self.set_setting("import.filename_pattern", pattern.trim())
+    }
+
+    // ============================================================================
+    // 辅助方法
+    // ============================================================================
+
+    /// 账号话题覆盖存为 JSON 数组，未覆盖时存空字符串
+    fn encode_hashtags_override(tags: &Option<Vec<String>>) -> String {
+        tags.as_ref()
+            .map(|t| serde_json::to_string(t).unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    /// 标签（评论 / 账号）存为 JSON 数组，未设置时为空字符串
+    fn decode_tags(value: Option<String>) -> Vec<String> {
+        value.filter(|v| !v.is_empty())
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default()
+    }
+
+    fn decode_hashtags_override(value: Option<String>) -> Option<Vec<String>> {
+        value.filter(|v| !v.is_empty())
+            .and_then(|v| serde_json::from_str(&v).ok())
+    }
+
+    /// 去重变体存为 JSON，未生成时为空字符串
+    fn encode_variant(variant: &Option<VideoVariant>) -> String {
+        variant.as_ref()
+            .map(|v| serde_json::to_string(v).unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    fn decode_variant(value: Option<String>) -> Option<VideoVariant> {
+        value.filter(|v| !v.is_empty())
+            .and_then(|v| serde_json::from_str(&v).ok())
+    }
+
+    /// Parse the platform column; unknown values are a conversion error rather than Douyin
+    /// 解析平台字段（无法识别的平台返回错误，不再默认当作抖音）
+    fn parse_platform(row: &rusqlite::Row, idx: usize) -> Result<PlatformType, rusqlite::Error> {
+        row.get::<_, String>(idx)?
+            .parse()
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, Box::new(e)))
     }
 
     /// Parse status string
@@ -812,6 +2203,7 @@ impl DatabaseManager {
         match s.to_lowercase().as_str() {
             "active" => AccountStatus::Active,
             "expired" => AccountStatus::Expired,
+            "risky" => AccountStatus::Risky,
             _ => AccountStatus::Pending,
         }
     }
@@ -824,6 +2216,8 @@ impl DatabaseManager {
             "publishing" => PublicationStatus::Publishing,
             "completed" => PublicationStatus::Completed,
             "failed" => PublicationStatus::Failed,
+            "skipped" => PublicationStatus::Skipped,
+            "removed" => PublicationStatus::Removed,
             _ => PublicationStatus::Draft,
         }
     }
@@ -833,71 +2227,169 @@ impl DatabaseManager {
     // ============================================================================
 
     /// Run database migrations for schema updates
-    /// 运行数据库迁移以更新架构
+    /// 运行数据库迁移以更新架构（编号迁移见 migrations 模块）
     fn run_migrations(&self, conn: &Connection) -> Result<()> {
-        // Migration: Add login_success_mode and related columns to extractor_configs
-        // Check if login_success_mode column exists
-        let has_login_success_mode: i32 = conn.query_row(
-            "SELECT COUNT(*) FROM pragma_table_info('extractor_configs') WHERE name = 'login_success_mode'",
+        migrations::run_migrations(self, conn)
+    }
+
+    /// Convert rows of the legacy single-table `publications` layout into the main + sub table structure
+    /// 将旧版单表 publications 数据转换为主表+子表结构
+    ///
+    /// 旧表每行对应一个账号的一次发布：主表沿用原 ID，子表按账号生成一条详情。
+    /// 转换完成后旧表重命名为 publications_legacy 保留备份，不会重复迁移。
+    /// 作为编号迁移执行，整个转换在迁移事务中提交。
+    fn migrate_legacy_publications(conn: &Connection) -> Result<()> {
+        let has_legacy: i32 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'publications'",
             [],
             |row| row.get(0)
         ).unwrap_or(0);
 
-        if has_login_success_mode == 0 {
-            tracing::info!("[Database] Running migration: adding login_success_mode columns to extractor_configs");
-            // Add new columns to extractor_configs table
-            conn.execute("ALTER TABLE extractor_configs ADD COLUMN login_success_mode TEXT DEFAULT 'url_match'", [])?;
-            conn.execute("ALTER TABLE extractor_configs ADD COLUMN login_success_api_rule TEXT", [])?;
-            conn.execute("ALTER TABLE extractor_configs ADD COLUMN login_success_api_operator TEXT", [])?;
-            conn.execute("ALTER TABLE extractor_configs ADD COLUMN login_success_api_value TEXT", [])?;
-            tracing::info!("[Database] Migration completed: added new columns to extractor_configs");
+        if has_legacy == 0 {
+            return Ok(());
+        }
+
+        tracing::info!("[Database] Running migration: converting legacy publications table");
+
+        // 旧表结构在不同版本间不一致，按实际存在的字段取值，缺失字段使用默认值
+        let legacy_columns: Vec<String> = {
+            let mut stmt = conn.prepare("SELECT name FROM pragma_table_info('publications')")?;
+            let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+            rows.collect::<Result<Vec<_>>>()?
+        };
+        let text = |name: &str, fallback: &str| -> String {
+            if legacy_columns.iter().any(|c| c == name) {
+                format!("CAST({} AS TEXT)", name)
+            } else {
+                fallback.to_string()
+            }
+        };
+        let number = |name: &str| -> String {
+            if legacy_columns.iter().any(|c| c == name) {
+                format!("COALESCE(CAST({} AS INTEGER), 0)", name)
+            } else {
+                "0".to_string()
+            }
+        };
+
+        let sql = format!(
+            "SELECT {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {} FROM publications",
+            text("id", "CAST(rowid AS TEXT)"),
+            text("account_id", "''"),
+            text("platform", "'douyin'"),
+            text("title", "''"),
+            text("description", "NULL"),
+            text("video_path", "''"),
+            text("cover_path", "NULL"),
+            text("hashtags", "'[]'"),
+            text("status", "'draft'"),
+            text("created_at", "CURRENT_TIMESTAMP"),
+            text("published_at", "NULL"),
+            text("publish_url", "NULL"),
+            text("message", "NULL"),
+            text("item_id", "NULL"),
+            number("comments"),
+            number("likes"),
+            number("favorites"),
+            number("shares"),
+        );
+
+        let mut migrated = 0;
+        let mut skipped = 0;
+        {
+            let mut stmt = conn.prepare(&sql)?;
+            let mut rows = stmt.query([])?;
+
+            while let Some(row) = rows.next()? {
+                let id: String = row.get::<_, Option<String>>(0)?.unwrap_or_default();
+                let account_id: String = row.get::<_, Option<String>>(1)?.unwrap_or_default();
+                // 旧版 publications 表只支持抖音，平台为空时按抖音迁移；无法识别的平台跳过该行
+                // （原数据保留在 publications_legacy 中），不影响其他记录和数据库初始化
+                let platform_raw = row.get::<_, Option<String>>(2)?
+                    .filter(|p| !p.is_empty())
+                    .unwrap_or_else(|| PlatformType::Douyin.to_string());
+                let platform: PlatformType = match platform_raw.parse() {
+                    Ok(platform) => platform,
+                    Err(e) => {
+                        tracing::warn!("[Database] Skipped legacy publication {}: unknown platform {:?} ({})", id, platform_raw, e);
+                        skipped += 1;
+                        continue;
+                    }
+                };
+                let status = Self::parse_publication_status(row.get::<_, Option<String>>(8)?.unwrap_or_default());
+                let created_at: String = row.get::<_, Option<String>>(9)?.unwrap_or_default();
+                let published_at: String = row.get::<_, Option<String>>(10)?.unwrap_or_default();
+
+                // 话题可能以 JSON 数组或逗号分隔字符串存储
+                let hashtags_raw: String = row.get::<_, Option<String>>(7)?.unwrap_or_default();
+                let hashtags: Vec<String> = serde_json::from_str(&hashtags_raw).unwrap_or_else(|_| {
+                    hashtags_raw.split(',')
+                        .map(|t| t.trim().trim_start_matches('#').to_string())
+                        .filter(|t| !t.is_empty())
+                        .collect()
+                });
+
+                conn.execute(r#"
+                    INSERT OR IGNORE INTO publication_tasks (
+                        id, title, description, video_path, cover_path, hashtags, status, created_at, published_at
+                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#, rusqlite::params![
+                    id,
+                    row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+                    row.get::<_, Option<String>>(6)?,
+                    serde_json::to_string(&hashtags).unwrap_or("[]".to_string()),
+                    format!("{:?}", status),
+                    created_at,
+                    published_at,
+                ])?;
+
+                if !account_id.is_empty() {
+                    conn.execute(r#"
+                        INSERT OR IGNORE INTO publication_accounts (
+                            id, publication_task_id, account_id, account_name, platform, status,
+                            created_at, published_at, publish_url,
+                            comments, likes, favorites, shares,
+                            message, item_id
+                        ) VALUES (?, ?, ?, COALESCE((SELECT nickname FROM accounts WHERE id = ?), ?), ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    "#, rusqlite::params![
+                        format!("{}_legacy", id),
+                        id,
+                        account_id,
+                        account_id,
+                        account_id,
+                        format!("{:?}", platform),
+                        format!("{:?}", status),
+                        created_at,
+                        published_at,
+                        row.get::<_, Option<String>>(11)?.unwrap_or_default(),
+                        row.get::<_, i64>(14)?,
+                        row.get::<_, i64>(15)?,
+                        row.get::<_, i64>(16)?,
+                        row.get::<_, i64>(17)?,
+                        row.get::<_, Option<String>>(12)?.unwrap_or_default(),
+                        row.get::<_, Option<String>>(13)?.unwrap_or_default(),
+                    ])?;
+                }
+
+                migrated += 1;
+            }
         }
 
+        conn.execute("ALTER TABLE publications RENAME TO publications_legacy", [])?;
+
+        tracing::info!("[Database] Migration completed: converted {} legacy publications, skipped {}", migrated, skipped);
         Ok(())
     }
 
     /// Initialize default configurations for supported platforms
     /// 初始化支持的平台的默认配置
     fn initialize_default_configs(conn: &Connection) -> Result<()> {
-        // Default Douyin configuration with proper user_info structure
-        let douyin_rules = serde_json::json!({
-            "user_info": {
-                "nickname": "${api:/web/api/media/user/info:response:body:user:nickname}",
-                "avatar_url": "${api:/web/api/media/user/info:response:body:user:avatar_thumb:url_list:0}",
-                "third_id": "${api:/account/api/v1/user/account/info:response:body:user:uid}",
-                "sec_uid": "${api:/web/api/media/user/info:response:body:user:sec_uid}"
-            },
-            "request_headers": {
-                "cookie": "${api:/account/api/v1/user/account/info:request:headers:cookie}"
-            },
-            "local_storage": [
-                "security-sdk/s_sdk_cert_key",
-                "security-sdk/s_sdk_crypt_sdk",
-                "security-sdk/s_sdk_pri_key",
-                "security-sdk/s_sdk_pub_key"
-            ],
-            "cookie": {
-                "source": "from_api",
-                "api_path": "/account/api/v1/user/account/info",
-                "header_name": "cookie"
-            }
-        });
-
-        let configs: Vec<(&str, &str, &str, &str, &str, Option<&str>, Option<&str>, Option<&str>, Option<&str>, &serde_json::Value)> = vec![
-            (
-                "douyin",
-                "抖音",
-                "https://creator.douyin.com/",
-                "url_match",
-                r#"**/creator-micro/**"#,
-                None, None, None,
-                Some("https://creator.douyin.com/creator-micro/content/post"),
-                &douyin_rules,
-            ),
-            // Add more platform defaults as needed
-        ];
-
-        for (platform_id, platform_name, login_url, login_mode, pattern, api_rule, api_op, api_value, redirect_url, rules) in configs {
+        // 各平台浏览器提供的默认配置
+        let platform_defaults = crate::browser::platforms::default_extractor_configs();
+
+        for d in &platform_defaults {
             conn.execute(
                 r#"INSERT OR IGNORE INTO extractor_configs
                     (id, platform_id, platform_name, login_url, login_success_mode, login_success_pattern,
@@ -905,17 +2397,17 @@ impl DatabaseManager {
                      redirect_url, extract_rules, is_default)
                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 1)"#,
                 &[
-                    &format!("config_{}", platform_id),
-                    platform_id,
-                    platform_name,
-                    login_url,
-                    login_mode,
-                    pattern,
-                    api_rule.unwrap_or(""),
-                    api_op.unwrap_or(""),
-                    api_value.unwrap_or(""),
-                    redirect_url.unwrap_or(""),
-                    &rules.to_string(),
+                    &format!("config_{}", d.platform_id),
+                    d.platform_id,
+                    d.platform_name,
+                    d.login_url,
+                    d.login_success_mode,
+                    d.login_success_pattern,
+                    "",
+                    "",
+                    "",
+                    d.redirect_url.unwrap_or(""),
+                    &d.extract_rules.to_string(),
                 ],
             )?;
         }
@@ -926,30 +2418,30 @@ impl DatabaseManager {
     /// Save extractor configuration
     /// 保存提取引擎配置
     pub fn save_extractor_config(&self, config: &ExtractorConfig) -> Result<(), rusqlite::Error> {
-        let conn = self.get_connection()?;
-
-        conn.execute(r#"
-            INSERT OR REPLACE INTO extractor_configs (
-                id, platform_id, platform_name, login_url, login_success_mode, login_success_pattern,
-                login_success_api_rule, login_success_api_operator, login_success_api_value,
-                redirect_url, extract_rules, is_default, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
-        "#, &[
-            &config.id,
-            &config.platform_id,
-            &config.platform_name,
-            &config.login_url,
-            &config.login_success_mode,
-            &config.login_success_pattern,
-            &config.login_success_api_rule.as_ref().unwrap_or(&String::new()),
-            &config.login_success_api_operator.as_ref().unwrap_or(&String::new()),
-            &config.login_success_api_value.as_ref().unwrap_or(&String::new()),
-            &config.redirect_url.as_ref().unwrap_or(&String::new()),
-            &config.extract_rules.to_string(),
-            &if config.is_default { "1".to_string() } else { "0".to_string() },
-        ])?;
+        self.write("save_extractor_config", |conn| {
+            conn.execute(r#"
+                INSERT OR REPLACE INTO extractor_configs (
+                    id, platform_id, platform_name, login_url, login_success_mode, login_success_pattern,
+                    login_success_api_rule, login_success_api_operator, login_success_api_value,
+                    redirect_url, extract_rules, is_default, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+            "#, &[
+                &config.id,
+                &config.platform_id,
+                &config.platform_name,
+                &config.login_url,
+                &config.login_success_mode,
+                &config.login_success_pattern,
+                &config.login_success_api_rule.as_ref().unwrap_or(&String::new()),
+                &config.login_success_api_operator.as_ref().unwrap_or(&String::new()),
+                &config.login_success_api_value.as_ref().unwrap_or(&String::new()),
+                &config.redirect_url.as_ref().unwrap_or(&String::new()),
+                &config.extract_rules.to_string(),
+                &if config.is_default { "1".to_string() } else { "0".to_string() },
+            ])?;
 
-        Ok(())
+            Ok(())
+        })
     }
 
     /// Get extractor configuration by platform ID
@@ -1017,28 +2509,28 @@ impl DatabaseManager {
     /// Delete extractor configuration
     /// 删除提取引擎配置
     pub fn delete_extractor_config(&self, platform_id: &str) -> Result<bool, rusqlite::Error> {
-        let conn = self.get_connection()?;
-
-        // Prevent deleting default configurations
-        let is_default = conn.query_row(
-            "SELECT is_default FROM extractor_configs WHERE platform_id = ?",
-            [platform_id],
-            |row| row.get::<_, i32>(0)
-        ).unwrap_or(0) == 1;
-
-        if is_default {
-            return Err(rusqlite::Error::IntegralValueOutOfRange(
-                0,
-                0
-            ));
-        }
+        self.write("delete_extractor_config", |conn| {
+            // Prevent deleting default configurations
+            let is_default = conn.query_row(
+                "SELECT is_default FROM extractor_configs WHERE platform_id = ?",
+                [platform_id],
+                |row| row.get::<_, i32>(0)
+            ).unwrap_or(0) == 1;
+
+            if is_default {
+                return Err(rusqlite::Error::IntegralValueOutOfRange(
+                    0,
+                    0
+                ));
+            }
 
-        let rows = conn.execute(
-            "DELETE FROM extractor_configs WHERE platform_id = ?",
-            [platform_id],
-        )?;
+            let rows = conn.execute(
+                "DELETE FROM extractor_configs WHERE platform_id = ?",
+                [platform_id],
+            )?;
 
-        Ok(rows > 0)
+            Ok(rows > 0)
+        })
     }
 
     // ============================================================================
@@ -1048,48 +2540,9 @@ impl DatabaseManager {
     /// Save a comment to database
     /// 保存评论到数据库
     pub fn save_comment(&self, comment: &Comment) -> Result<(), rusqlite::Error> {
-        let conn = self.get_connection()?;
-
-        conn.execute(r#"
-            INSERT OR REPLACE INTO comments (
-                id, account_id, aweme_id, comment_id, user_id,
-                user_nickname, user_avatar, content,
-                like_count, reply_count, create_time, status, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-        "#, &[
-            &comment.id,
-            &comment.account_id,
-            &comment.aweme_id,
-            &comment.comment_id,
-            &comment.user_id,
-            &comment.user_nickname,
-            &comment.user_avatar,
-            &comment.content,
-            &comment.like_count.to_string(),
-            &comment.reply_count.to_string(),
-            &comment.create_time,
-            &format!("{:?}", comment.status),
-            &comment.created_at,
-        ])?;
-
-        Ok(())
-    }
-
-    /// Save multiple comments in batch
-    /// 批量保存评论
-    pub fn save_comments_batch(&self, comments: &[Comment]) -> Result<(), rusqlite::Error> {
-        if comments.is_empty() {
-            return Ok(());
-        }
-
-        let mut conn = self.get_connection()?;
-
-        // Start transaction for batch insert
-        let tx = conn.transaction()?;
-
-        for comment in comments {
-            tx.execute(r#"
-                INSERT OR IGNORE INTO comments (
+        self.write("save_comment", |conn| {
+            conn.execute(r#"
+                INSERT OR REPLACE INTO comments (
                     id, account_id, aweme_id, comment_id, user_id,
                     user_nickname, user_avatar, content,
                     like_count, reply_count, create_time, status, created_at
@@ -1109,10 +2562,52 @@ impl DatabaseManager {
                 &format!("{:?}", comment.status),
                 &comment.created_at,
             ])?;
+
+            Ok(())
+        })
+    }
+
+    /// Save multiple comments in batch, skipping comments already stored for the same item
+    /// 批量保存评论（同一作品下已存在的 comment_id 跳过），返回新增条数
+    pub fn save_comments_batch(&self, comments: &[Comment]) -> Result<usize, rusqlite::Error> {
+        if comments.is_empty() {
+            return Ok(0);
         }
 
-        tx.commit()?;
-        Ok(())
+        self.write("save_comments_batch", |conn| {
+            // Start transaction for batch insert
+            let tx = conn.transaction()?;
+
+            let mut inserted = 0;
+            for comment in comments {
+                inserted += tx.execute(r#"
+                    INSERT OR IGNORE INTO comments (
+                        id, account_id, aweme_id, comment_id, user_id,
+                        user_nickname, user_avatar, content,
+                        like_count, reply_count, create_time, status, created_at
+                    )
+                    SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13
+                    WHERE NOT EXISTS (SELECT 1 FROM comments WHERE aweme_id = ?3 AND comment_id = ?4)
+                "#, &[
+                    &comment.id,
+                    &comment.account_id,
+                    &comment.aweme_id,
+                    &comment.comment_id,
+                    &comment.user_id,
+                    &comment.user_nickname,
+                    &comment.user_avatar,
+                    &comment.content,
+                    &comment.like_count.to_string(),
+                    &comment.reply_count.to_string(),
+                    &comment.create_time,
+                    &format!("{:?}", comment.status),
+                    &comment.created_at,
+                ])?;
+            }
+
+            tx.commit()?;
+            Ok(inserted)
+        })
     }
 
     /// Get comments by aweme_id
@@ -1120,7 +2615,7 @@ impl DatabaseManager {
     pub fn get_comments_by_aweme_id(&self, aweme_id: &str) -> Result<Vec<Comment>, rusqlite::Error> {
         let conn = self.get_connection()?;
 
-        let mut stmt = conn.prepare("SELECT * FROM comments WHERE aweme_id = ? ORDER BY create_time DESC")?;
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM comments WHERE aweme_id = ? ORDER BY create_time DESC", COMMENT_COLUMNS))?;
         let comments = stmt.query_map([aweme_id], |row| {
             Ok(Comment {
                 id: row.get(0)?,
@@ -1131,23 +2626,43 @@ impl DatabaseManager {
                 user_nickname: row.get(5)?,
                 user_avatar: row.get(6)?,
                 content: row.get(7)?,
-                like_count: row.get::<_, String>(8)?.parse().unwrap_or(0),
-                reply_count: row.get::<_, String>(9)?.parse().unwrap_or(0),
+                // 计数列是 INTEGER 亲和性，写入的字符串会被转换为整数
+                like_count: row.get(8)?,
+                reply_count: row.get(9)?,
                 create_time: row.get(10)?,
                 status: Self::parse_comment_status(row.get::<_, String>(11)?),
                 created_at: row.get(12)?,
+                tags: Self::decode_tags(row.get(13)?),
             })
         })?.filter_map(|r| r.ok()).collect();
 
         Ok(comments)
     }
 
+    /// Publication info of an item for comment export (task title, account name, publish time)
+    /// 获取作品对应的发布信息（用于评论导出）
+    pub fn get_comment_export_item(&self, aweme_id: &str) -> Result<Option<(String, String, String)>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        match conn.query_row(r#"
+            SELECT COALESCE(NULLIF(p.title_override, ''), t.title), p.account_name, p.published_at
+            FROM publication_accounts p
+            JOIN publication_tasks t ON t.id = p.publication_task_id AND t.deleted_at = ''
+            WHERE p.item_id = ?
+            LIMIT 1
+        "#, [aweme_id], |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, Option<String>>(2)?.unwrap_or_default()))) {
+            Ok(info) => Ok(Some(info)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Get comments by account_id
     /// 根据账号ID获取评论
     pub fn get_comments_by_account_id(&self, account_id: &str) -> Result<Vec<Comment>, rusqlite::Error> {
         let conn = self.get_connection()?;
 
-        let mut stmt = conn.prepare("SELECT * FROM comments WHERE account_id = ? ORDER BY create_time DESC")?;
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM comments WHERE account_id = ? ORDER BY create_time DESC", COMMENT_COLUMNS))?;
         let comments = stmt.query_map([account_id], |row| {
             Ok(Comment {
                 id: row.get(0)?,
@@ -1158,11 +2673,12 @@ impl DatabaseManager {
                 user_nickname: row.get(5)?,
                 user_avatar: row.get(6)?,
                 content: row.get(7)?,
-                like_count: row.get::<_, String>(8)?.parse().unwrap_or(0),
-                reply_count: row.get::<_, String>(9)?.parse().unwrap_or(0),
+                like_count: row.get(8)?,
+                reply_count: row.get(9)?,
                 create_time: row.get(10)?,
                 status: Self::parse_comment_status(row.get::<_, String>(11)?),
                 created_at: row.get(12)?,
+                tags: Self::decode_tags(row.get(13)?),
             })
         })?.filter_map(|r| r.ok()).collect();
 
@@ -1181,7 +2697,7 @@ impl DatabaseManager {
 
         // 直接构建 SQL（避免 LIMIT/OFFSET 参数绑定问题）
         let sql = format!(
-            "SELECT id, account_id, aweme_id, comment_id, user_id, user_nickname, user_avatar, content, like_count, reply_count, create_time, status, created_at FROM comments WHERE aweme_id = '{}' ORDER BY create_time DESC LIMIT {} OFFSET {}",
+            "SELECT id, account_id, aweme_id, comment_id, user_id, user_nickname, user_avatar, content, like_count, reply_count, create_time, status, created_at, tags FROM comments WHERE aweme_id = '{}' ORDER BY create_time DESC LIMIT {} OFFSET {}",
             aweme_id.replace("'", "''"), // 转义单引号
             limit,
             offset
@@ -1206,12 +2722,67 @@ impl DatabaseManager {
                 create_time: row.get(10)?,
                 status: Self::parse_comment_status(row.get(11)?),
                 created_at: row.get(12)?,
+                tags: Self::decode_tags(row.get(13)?),
             });
         }
 
         Ok(comments)
     }
 
+    /// Overwrite the tags of comments in one transaction
+    /// 批量写入评论标签
+    pub fn set_comment_tags(&self, tags: &[(String, Vec<String>)]) -> Result<(), rusqlite::Error> {
+        self.write("set_comment_tags", |conn| {
+            let tx = conn.transaction()?;
+
+            for (id, comment_tags) in tags {
+                let value = if comment_tags.is_empty() {
+                    String::new()
+                } else {
+                    serde_json::to_string(comment_tags).unwrap_or_default()
+                };
+                tx.execute("UPDATE comments SET tags = ? WHERE id = ?", [&value, id])?;
+            }
+
+            tx.commit()?;
+            Ok(())
+        })
+    }
+
+    /// Get comments carrying a tag, optionally within one item
+    /// 按标签查询评论（可限定作品）
+    pub fn get_comments_by_tag(&self, tag: &str, aweme_id: Option<&str>) -> Result<Vec<Comment>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        // tags 为 JSON 数组，按带引号的标签名匹配
+        let pattern = format!("%{}%", serde_json::to_string(tag).unwrap_or_default());
+        let mut stmt = conn.prepare(&format!(r#"
+            SELECT {} FROM comments
+            WHERE tags LIKE ? AND (? IS NULL OR aweme_id = ?)
+            ORDER BY create_time DESC
+        "#, COMMENT_COLUMNS))?;
+        let comments = stmt.query_map(rusqlite::params![pattern, aweme_id, aweme_id], |row| {
+            Ok(Comment {
+                id: row.get(0)?,
+                account_id: row.get(1)?,
+                aweme_id: row.get(2)?,
+                comment_id: row.get(3)?,
+                user_id: row.get(4)?,
+                user_nickname: row.get(5)?,
+                user_avatar: row.get(6)?,
+                content: row.get(7)?,
+                like_count: row.get(8)?,
+                reply_count: row.get(9)?,
+                create_time: row.get(10)?,
+                status: Self::parse_comment_status(row.get::<_, String>(11)?),
+                created_at: row.get(12)?,
+                tags: Self::decode_tags(row.get(13)?),
+            })
+        })?.filter_map(|r| r.ok()).collect();
+
+        Ok(comments)
+    }
+
     /// Get comment count by aweme_id
     /// 根据作品ID获取评论数量
     pub fn get_comment_count(&self, aweme_id: &str) -> Result<i64, rusqlite::Error> {
@@ -1229,42 +2800,42 @@ impl DatabaseManager {
     /// Delete comments by aweme_id and update publication_accounts comment count
     /// 根据作品ID删除评论并更新发布账号表的评论数
     pub fn delete_comments_by_aweme_id(&self, aweme_id: &str) -> Result<bool, rusqlite::Error> {
-        let conn = self.get_connection()?;
-
-        // Delete comments
-        let rows = conn.execute(
-            "DELETE FROM comments WHERE aweme_id = ?",
-            [aweme_id],
-        )?;
+        self.write("delete_comments_by_aweme_id", |conn| {
+            // Delete comments
+            let rows = conn.execute(
+                "DELETE FROM comments WHERE aweme_id = ?",
+                [aweme_id],
+            )?;
 
-        // Update publication_accounts comment count to 0
-        conn.execute(
-            "UPDATE publication_accounts SET comments = 0 WHERE item_id = ?",
-            [aweme_id.to_string()],
-        )?;
+            // Update publication_accounts comment count to 0
+            conn.execute(
+                "UPDATE publication_accounts SET comments = 0 WHERE item_id = ?",
+                [aweme_id.to_string()],
+            )?;
 
-        Ok(rows > 0)
+            Ok(rows > 0)
+        })
     }
 
     /// Update publication_accounts comment count based on aweme_id
     /// 根据作品ID更新发布账号表的评论数
     pub fn update_publication_account_comment_count(&self, aweme_id: &str) -> Result<(), rusqlite::Error> {
-        let conn = self.get_connection()?;
-
-        // Count comments for this aweme_id
-        let count: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM comments WHERE aweme_id = ?",
-            [aweme_id],
-            |row| row.get(0)
-        )?;
+        self.write("update_publication_account_comment_count", |conn| {
+            // Count comments for this aweme_id
+            let count: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM comments WHERE aweme_id = ?",
+                [aweme_id],
+                |row| row.get(0)
+            )?;
 
-        // Update the publication_accounts
-        conn.execute(
-            "UPDATE publication_accounts SET comments = ? WHERE item_id = ?",
-            [count.to_string(), aweme_id.to_string()],
-        )?;
+            // Update the publication_accounts
+            conn.execute(
+                "UPDATE publication_accounts SET comments = ? WHERE item_id = ?",
+                [count.to_string(), aweme_id.to_string()],
+            )?;
 
-        Ok(())
+            Ok(())
+        })
     }
 
     /// Parse comment status string
@@ -0,0 +1,197 @@
+// Publish checkpoints - intermediate results of completed publish steps
+// 发布断点 - 记录账号发布中已完成步骤的中间结果（已上传的视频ID、封面、BD 凭证），
+// 步骤4-9 之间崩溃或断网后重新发布时从最后完成的步骤继续，不必重新上传大视频；发布成功后删除
+
+use std::collections::HashMap;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use super::DatabaseManager;
+
+/// BD 凭证的复用有效期（分钟），超过后重新获取
+pub const BD_TICKET_TTL_MINUTES: i64 = 30;
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+const CHECKPOINT_COLUMNS: &str = "detail_id, step, video_path, video_size, video_id, poster_uri, bd_ticket, bd_ticket_at, updated_at";
+
+/// Last completed publish step
+/// 最后完成的发布步骤
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointStep {
+    /// 步骤4：视频（和封面）已上传
+    VideoUploaded,
+    /// 步骤5：BD 凭证已获取
+    TicketObtained,
+}
+
+impl CheckpointStep {
+    fn as_i64(&self) -> i64 {
+        match self {
+            CheckpointStep::VideoUploaded => 4,
+            CheckpointStep::TicketObtained => 5,
+        }
+    }
+
+    fn from_i64(step: i64) -> Self {
+        if step >= 5 { CheckpointStep::TicketObtained } else { CheckpointStep::VideoUploaded }
+    }
+}
+
+/// Checkpoint of one account publish
+/// 一个账号发布的断点
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishCheckpoint {
+    pub detail_id: String,
+    pub step: CheckpointStep,
+    pub video_path: String,
+    pub video_size: u64,                               // 上传时的文件大小，文件被替换后不再复用
+    pub video_id: String,
+    pub poster_uri: Option<String>,
+    pub bd_ticket: Option<HashMap<String, String>>,
+    pub bd_ticket_at: Option<String>,
+    pub updated_at: String,
+}
+
+impl PublishCheckpoint {
+    /// 已上传视频的断点
+    pub fn video_uploaded(detail_id: &str, video_path: &str, video_size: u64, video_id: &str, poster_uri: Option<&str>) -> Self {
+        Self {
+            detail_id: detail_id.to_string(),
+            step: CheckpointStep::VideoUploaded,
+            video_path: video_path.to_string(),
+            video_size,
+            video_id: video_id.to_string(),
+            poster_uri: poster_uri.map(|uri| uri.to_string()),
+            bd_ticket: None,
+            bd_ticket_at: None,
+            updated_at: now(),
+        }
+    }
+
+    /// 记录已获取的 BD 凭证
+    pub fn with_bd_ticket(mut self, bd_ticket: &HashMap<String, String>) -> Self {
+        self.step = CheckpointStep::TicketObtained;
+        self.bd_ticket = Some(bd_ticket.clone());
+        self.bd_ticket_at = Some(now());
+        self.updated_at = now();
+        self
+    }
+
+    /// 视频文件未变化（路径和大小一致）时才能复用已上传的视频
+    pub fn matches_video(&self, video_path: &str, video_size: u64) -> bool {
+        !self.video_id.is_empty() && self.video_path == video_path && self.video_size == video_size
+    }
+
+    /// 仍在有效期内的 BD 凭证
+    pub fn fresh_bd_ticket(&self, now: NaiveDateTime) -> Option<&HashMap<String, String>> {
+        let obtained_at = NaiveDateTime::parse_from_str(self.bd_ticket_at.as_deref()?, TIME_FORMAT).ok()?;
+        let age = now.signed_duration_since(obtained_at).num_minutes();
+        if (0..BD_TICKET_TTL_MINUTES).contains(&age) {
+            self.bd_ticket.as_ref().filter(|ticket| !ticket.is_empty())
+        } else {
+            None
+        }
+    }
+}
+
+fn now() -> String {
+    chrono::Local::now().format(TIME_FORMAT).to_string()
+}
+
+fn map_checkpoint(row: &rusqlite::Row) -> rusqlite::Result<PublishCheckpoint> {
+    let non_empty = |s: String| if s.is_empty() { None } else { Some(s) };
+    let bd_ticket: String = row.get(6)?;
+    Ok(PublishCheckpoint {
+        detail_id: row.get(0)?,
+        step: CheckpointStep::from_i64(row.get(1)?),
+        video_path: row.get(2)?,
+        video_size: row.get::<_, i64>(3)?.max(0) as u64,
+        video_id: row.get(4)?,
+        poster_uri: non_empty(row.get(5)?),
+        bd_ticket: serde_json::from_str(&bd_ticket).ok(),
+        bd_ticket_at: non_empty(row.get(7)?),
+        updated_at: row.get::<_, Option<String>>(8)?.unwrap_or_default(),
+    })
+}
+
+impl DatabaseManager {
+    /// Get the checkpoint of an account publish
+    /// 获取账号发布的断点
+    pub fn get_publish_checkpoint(&self, detail_id: &str) -> Result<Option<PublishCheckpoint>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM publish_checkpoints WHERE detail_id = ?", CHECKPOINT_COLUMNS))?;
+        match stmt.query_row([detail_id], map_checkpoint) {
+            Ok(checkpoint) => Ok(Some(checkpoint)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Save (replace) the checkpoint of an account publish
+    /// 保存账号发布的断点（覆盖旧断点）
+    pub fn save_publish_checkpoint(&self, checkpoint: &PublishCheckpoint) -> Result<(), rusqlite::Error> {
+        let bd_ticket = checkpoint.bd_ticket.as_ref()
+            .map(|ticket| serde_json::to_string(ticket).unwrap_or_default())
+            .unwrap_or_default();
+        self.write("save_publish_checkpoint", |conn| {
+            conn.execute(&format!(
+                "INSERT OR REPLACE INTO publish_checkpoints ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)", CHECKPOINT_COLUMNS
+            ), rusqlite::params![
+                checkpoint.detail_id,
+                checkpoint.step.as_i64(),
+                checkpoint.video_path,
+                checkpoint.video_size as i64,
+                checkpoint.video_id,
+                checkpoint.poster_uri.as_deref().unwrap_or(""),
+                bd_ticket,
+                checkpoint.bd_ticket_at.as_deref().unwrap_or(""),
+                checkpoint.updated_at,
+            ])?;
+            Ok(())
+        })
+    }
+
+    /// Delete the checkpoint once the publish has finished
+    /// 发布完成后删除断点
+    pub fn delete_publish_checkpoint(&self, detail_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("delete_publish_checkpoint", |conn| {
+            Ok(conn.execute("DELETE FROM publish_checkpoints WHERE detail_id = ?", [detail_id])? > 0)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(time, TIME_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn test_matches_video() {
+        let checkpoint = PublishCheckpoint::video_uploaded("d1", "/videos/a.mp4", 1024, "v0d00f", None);
+        assert!(checkpoint.matches_video("/videos/a.mp4", 1024));
+        assert!(!checkpoint.matches_video("/videos/a.mp4", 2048));
+        assert!(!checkpoint.matches_video("/videos/b.mp4", 1024));
+
+        let empty = PublishCheckpoint::video_uploaded("d1", "/videos/a.mp4", 1024, "", None);
+        assert!(!empty.matches_video("/videos/a.mp4", 1024));
+    }
+
+    #[test]
+    fn test_fresh_bd_ticket() {
+        let ticket = HashMap::from([("bd-ticket-guard-client-data".to_string(), "x".to_string())]);
+        let mut checkpoint = PublishCheckpoint::video_uploaded("d1", "/videos/a.mp4", 1024, "v0d00f", None)
+            .with_bd_ticket(&ticket);
+        assert_eq!(checkpoint.step, CheckpointStep::TicketObtained);
+
+        checkpoint.bd_ticket_at = Some("2026-01-01 10:00:00".to_string());
+        assert_eq!(checkpoint.fresh_bd_ticket(at("2026-01-01 10:10:00")), Some(&ticket));
+        assert_eq!(checkpoint.fresh_bd_ticket(at("2026-01-01 10:30:00")), None);
+        // 时钟回拨时不复用
+        assert_eq!(checkpoint.fresh_bd_ticket(at("2026-01-01 09:59:00")), None);
+    }
+}
@@ -0,0 +1,243 @@
+// Publish statistics - aggregate counts for the publication dashboard
+// 发布统计 - 按状态、平台、账号、日期汇总任务和发布明细数量，
+// 仪表盘一次查询即可渲染，无需拉取全部任务在前端分组
+
+use super::DatabaseManager;
+use super::stats::StatsPeriod;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// 各状态的数量
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StatusCounts {
+    pub total: i64,
+    pub draft: i64,
+    pub publishing: i64,
+    pub completed: i64,
+    pub failed: i64,
+    pub skipped: i64,
+}
+
+impl StatusCounts {
+    /// 累加某个状态的数量（状态名不区分大小写，未知状态只计入 total）
+    fn add(&mut self, status: &str, count: i64) {
+        self.total += count;
+        match status.to_lowercase().as_str() {
+            "draft" => self.draft += count,
+            "publishing" => self.publishing += count,
+            "completed" => self.completed += count,
+            "failed" => self.failed += count,
+            "skipped" => self.skipped += count,
+            _ => {}
+        }
+    }
+
+    /// 成功率：completed / (completed + failed)，没有结果时为空
+    pub fn success_rate(&self) -> Option<f64> {
+        let finished = self.completed + self.failed;
+        if finished > 0 { Some(self.completed as f64 / finished as f64) } else { None }
+    }
+}
+
+/// 某个平台的发布明细数量
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformPublishCounts {
+    pub platform: String,
+    pub counts: StatusCounts,
+    pub success_rate: Option<f64>,
+}
+
+/// 某个账号的发布明细数量
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountPublishCounts {
+    pub account_id: String,
+    pub account_name: String,
+    pub platform: String,
+    pub counts: StatusCounts,
+    pub success_rate: Option<f64>,
+}
+
+/// 某一天的发布明细数量
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyPublishCounts {
+    /// 日期（YYYY-MM-DD）
+    pub date: String,
+    pub counts: StatusCounts,
+}
+
+/// Publication dashboard statistics for a date range
+/// 发布统计结果
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishStatistics {
+    pub start: String,
+    pub end: String,
+    /// 区间内创建的任务，按任务状态
+    pub tasks: StatusCounts,
+    /// 区间内的发布明细（每个账号一条），按明细状态
+    pub publications: StatusCounts,
+    pub publication_success_rate: Option<f64>,
+    /// 按发布明细总数降序
+    pub by_platform: Vec<PlatformPublishCounts>,
+    /// 按发布明细总数降序
+    pub by_account: Vec<AccountPublishCounts>,
+    /// 按日期升序
+    pub daily: Vec<DailyPublishCounts>,
+}
+
+/// 把 (分组键, 状态, 数量) 行汇总为每个分组的状态数量
+fn group_counts(rows: &[(String, String, i64)]) -> BTreeMap<String, StatusCounts> {
+    let mut groups: BTreeMap<String, StatusCounts> = BTreeMap::new();
+    for (key, status, count) in rows {
+        groups.entry(key.clone()).or_default().add(status, *count);
+    }
+    groups
+}
+
+/// 发布明细的统计时间：发布时间（更新状态时写入），缺失时退回创建时间
+const PUBLICATION_DAY: &str = "substr(COALESCE(NULLIF(pa.published_at, ''), pa.created_at), 1, 10)";
+
+impl DatabaseManager {
+    /// Aggregate task and publication counts in a date range (inclusive, YYYY-MM-DD)
+    /// 统计日期区间（含首尾）内的任务和发布明细：按状态、平台、账号、日期汇总
+    pub fn get_publish_statistics(&self, range: &StatsPeriod) -> Result<PublishStatistics, rusqlite::Error> {
+        let conn = self.get_connection()?;
+        let params = [&range.start, &range.end];
+
+        let mut tasks = StatusCounts::default();
+        conn.prepare(r#"
+            SELECT status, COUNT(*) FROM publication_tasks
+            WHERE substr(created_at, 1, 10) BETWEEN ? AND ? AND deleted_at = ''
+            GROUP BY status
+        "#)?
+            .query_map(params, |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?
+            .filter_map(|r| r.ok())
+            .for_each(|(status, count)| tasks.add(&status, count));
+
+        // 按 (平台, 状态)、(账号, 状态)、(日期, 状态) 分组的明细数量，回收站中的任务不计入
+        let grouped = |key: &str| -> Result<Vec<(String, String, i64)>, rusqlite::Error> {
+            let rows = conn.prepare(&format!(
+                "SELECT {key}, pa.status, COUNT(*) FROM publication_accounts pa \
+                 JOIN publication_tasks t ON t.id = pa.publication_task_id AND t.deleted_at = '' \
+                 WHERE {day} BETWEEN ? AND ? GROUP BY 1, 2",
+                key = key, day = PUBLICATION_DAY,
+            ))?
+                .query_map(params, |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+                .filter_map(|r| r.ok())
+                .collect();
+            Ok(rows)
+        };
+        let platform_rows = grouped("pa.platform")?;
+        let account_rows = grouped("pa.account_id")?;
+        let daily_rows = grouped(PUBLICATION_DAY)?;
+
+        let mut publications = StatusCounts::default();
+        for (_, status, count) in &platform_rows {
+            publications.add(status, *count);
+        }
+
+        let mut by_platform: Vec<PlatformPublishCounts> = group_counts(&platform_rows).into_iter()
+            .map(|(platform, counts)| PlatformPublishCounts {
+                platform: platform.to_lowercase(),
+                success_rate: counts.success_rate(),
+                counts,
+            })
+            .collect();
+        by_platform.sort_by(|a, b| b.counts.total.cmp(&a.counts.total));
+
+        // 账号名和平台取账号表的当前值，账号已删除（含移入回收站）时退回最近一条明细中的冗余名称
+        let mut account_info = conn.prepare(r#"
+            SELECT COALESCE(a.nickname, pa.account_name), COALESCE(a.platform, pa.platform)
+            FROM publication_accounts pa
+            LEFT JOIN accounts a ON a.id = pa.account_id AND a.deleted_at = ''
+            WHERE pa.account_id = ?
+            ORDER BY pa.created_at DESC
+            LIMIT 1
+        "#)?;
+        let mut by_account = Vec::new();
+        for (account_id, counts) in group_counts(&account_rows) {
+            let (account_name, platform): (String, String) = account_info
+                .query_row([&account_id], |row| Ok((row.get(0)?, row.get(1)?)))
+                .unwrap_or_default();
+            by_account.push(AccountPublishCounts {
+                account_id,
+                account_name,
+                platform: platform.to_lowercase(),
+                success_rate: counts.success_rate(),
+                counts,
+            });
+        }
+        by_account.sort_by(|a, b| b.counts.total.cmp(&a.counts.total));
+
+        let daily = group_counts(&daily_rows).into_iter()
+            .map(|(date, counts)| DailyPublishCounts { date, counts })
+            .collect();
+
+        Ok(PublishStatistics {
+            start: range.start.clone(),
+            end: range.end.clone(),
+            tasks,
+            publication_success_rate: publications.success_rate(),
+            publications,
+            by_platform,
+            by_account,
+            daily,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_counts_and_success_rate() {
+        let rows = vec![
+            ("Douyin".to_string(), "Completed".to_string(), 3),
+            ("Douyin".to_string(), "failed".to_string(), 1),
+            ("Douyin".to_string(), "Unknown".to_string(), 2),
+            ("Kuaishou".to_string(), "Draft".to_string(), 5),
+        ];
+        let groups = group_counts(&rows);
+
+        let douyin = &groups["Douyin"];
+        assert_eq!(douyin.total, 6);
+        assert_eq!(douyin.completed, 3);
+        assert_eq!(douyin.failed, 1);
+        assert_eq!(douyin.success_rate(), Some(0.75));
+        assert_eq!(groups["Kuaishou"].draft, 5);
+        assert_eq!(groups["Kuaishou"].success_rate(), None);
+    }
+
+    #[test]
+    fn test_trashed_tasks_are_excluded() {
+        let dir = std::env::temp_dir().join(format!("amm-publish-stats-{}", uuid::Uuid::new_v4()));
+        let db = DatabaseManager::new(dir.clone());
+        db.get_connection().unwrap().execute_batch(r#"
+            INSERT INTO publication_tasks (id, title, video_path, status, created_at) VALUES
+                ('t1', '保留', '/a.mp4', 'Completed', '2024-01-02 10:00:00'),
+                ('t2', '删除', '/b.mp4', 'Completed', '2024-01-02 10:00:00');
+            INSERT INTO publication_accounts (id, publication_task_id, account_id, account_name, platform, status, created_at, published_at, item_id) VALUES
+                ('d1', 't1', 'a1', '账号', 'Douyin', 'Completed', '2024-01-02 10:00:00', '2024-01-02 10:05:00', 'v1'),
+                ('d2', 't2', 'a1', '账号', 'Douyin', 'Completed', '2024-01-02 10:00:00', '2024-01-02 10:05:00', 'v2');
+        "#).unwrap();
+        assert!(db.delete_publication_task("t2").unwrap());
+
+        let range = StatsPeriod { start: "2024-01-01".to_string(), end: "2024-01-31".to_string() };
+        let stats = db.get_publish_statistics(&range).unwrap();
+        assert_eq!(stats.tasks.total, 1);
+        assert_eq!(stats.publications.total, 1);
+        assert_eq!(stats.by_account[0].counts.total, 1);
+
+        let query = serde_json::from_value(serde_json::json!({ "entity": "publication_accounts" })).unwrap();
+        assert_eq!(db.run_report(&query).unwrap().rows, vec![vec![serde_json::json!(1)]]);
+
+        let timeline = db.get_account_timeline("a1", 50).unwrap();
+        assert!(timeline.iter().any(|e| e.reference_id.as_deref() == Some("d1")));
+        assert!(timeline.iter().all(|e| e.reference_id.as_deref() != Some("d2")));
+
+        assert!(db.get_comment_export_item("v1").unwrap().is_some());
+        assert!(db.get_comment_export_item("v2").unwrap().is_none());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
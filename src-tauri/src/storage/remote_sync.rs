@@ -0,0 +1,147 @@
+// Remote sync bookkeeping - change log for the optional sync server
+// 远程同步记录 - accounts / publication_tasks / publication_accounts 的增删改由触发器写入 sync_changes，
+// 同步时推送未推送的本地变更，并按 updated_at（UTC）判断远端变更是否更新
+
+use super::DatabaseManager;
+use crate::core::{RemoteSyncConfig, SyncEntity};
+
+/// 参与同步的表及其实体类型
+pub(super) const SYNC_TRACKED_TABLES: &[(&str, SyncEntity)] = &[
+    ("accounts", SyncEntity::Account),
+    ("publication_tasks", SyncEntity::Task),
+    ("publication_accounts", SyncEntity::Detail),
+];
+
+/// A pending local change
+/// 待推送的本地变更
+#[derive(Debug, Clone)]
+pub struct PendingSyncChange {
+    pub entity: SyncEntity,
+    pub entity_id: String,
+    pub updated_at: String,
+    pub deleted: bool,
+}
+
+/// 创建变更记录触发器，并为已有数据补齐变更记录（首次同步时推送全部数据）
+pub(super) fn create_sync_triggers(conn: &rusqlite::Connection) -> Result<(), rusqlite::Error> {
+    for (table, entity) in SYNC_TRACKED_TABLES {
+        for (event, row, deleted) in [("INSERT", "NEW", 0), ("UPDATE", "NEW", 0), ("DELETE", "OLD", 1)] {
+            conn.execute(&format!(
+                "CREATE TRIGGER IF NOT EXISTS sync_{table}_{name} AFTER {event} ON {table} BEGIN \
+                 INSERT OR REPLACE INTO sync_changes (entity, entity_id, updated_at, deleted, pushed) \
+                 VALUES ('{entity}', {row}.id, strftime('%Y-%m-%d %H:%M:%S', 'now'), {deleted}, 0); \
+                 END",
+                table = table, name = event.to_lowercase(), event = event, entity = entity.as_str(), row = row, deleted = deleted,
+            ), [])?;
+        }
+
+        conn.execute(&format!(
+            "INSERT OR IGNORE INTO sync_changes (entity, entity_id, updated_at, deleted, pushed) \
+             SELECT '{}', id, strftime('%Y-%m-%d %H:%M:%S', 'now'), 0, 0 FROM {}",
+            entity.as_str(), table,
+        ), [])?;
+    }
+    Ok(())
+}
+
+impl DatabaseManager {
+    /// Local changes not yet pushed to the sync server
+    /// 获取尚未推送的本地变更
+    pub fn get_pending_sync_changes(&self) -> Result<Vec<PendingSyncChange>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT entity, entity_id, updated_at, deleted FROM sync_changes WHERE pushed = 0 ORDER BY updated_at ASC"
+        )?;
+        let changes = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get(1)?, row.get(2)?, row.get::<_, i64>(3)? != 0))
+        })?
+            .filter_map(|r| r.ok())
+            .filter_map(|(entity, entity_id, updated_at, deleted)| {
+                SyncEntity::parse(&entity).map(|entity| PendingSyncChange { entity, entity_id, updated_at, deleted })
+            })
+            .collect();
+
+        Ok(changes)
+    }
+
+    /// Last change time of an entity (None if never changed locally)
+    /// 获取实体最近一次变更时间（UTC）
+    pub fn get_sync_updated_at(&self, entity: SyncEntity, entity_id: &str) -> Result<Option<String>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        match conn.query_row(
+            "SELECT updated_at FROM sync_changes WHERE entity = ? AND entity_id = ?",
+            [entity.as_str(), entity_id],
+            |row| row.get(0),
+        ) {
+            Ok(updated_at) => Ok(Some(updated_at)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Mark pushed changes; a change modified again after the push stays pending
+    /// 标记已推送的变更（推送后又被修改的保持待推送）
+    pub fn mark_sync_changes_pushed(&self, changes: &[PendingSyncChange]) -> Result<(), rusqlite::Error> {
+        self.write("mark_sync_changes_pushed", |conn| {
+            let tx = conn.transaction()?;
+            for change in changes {
+                tx.execute(
+                    "UPDATE sync_changes SET pushed = 1 WHERE entity = ? AND entity_id = ? AND updated_at = ?",
+                    [change.entity.as_str(), &change.entity_id, &change.updated_at],
+                )?;
+            }
+            tx.commit()
+        })
+    }
+
+    /// Record a change applied from the server with the server's timestamp (not pushed back)
+    /// 记录从服务端应用的变更，保留服务端的时间戳，不再推送回去
+    pub fn record_remote_sync_change(&self, entity: SyncEntity, entity_id: &str, updated_at: &str, deleted: bool) -> Result<(), rusqlite::Error> {
+        self.write("record_remote_sync_change", |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO sync_changes (entity, entity_id, updated_at, deleted, pushed) VALUES (?, ?, ?, ?, 1)",
+                rusqlite::params![entity.as_str(), entity_id, updated_at, deleted as i64],
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Delete a publication account detail row (remote deletion)
+    /// 删除账号发布详情（应用远端删除）
+    pub fn delete_publication_account_detail(&self, detail_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("delete_publication_account_detail", |conn| {
+            let rows = conn.execute("DELETE FROM publication_accounts WHERE id = ?", [detail_id])?;
+            Ok(rows > 0)
+        })
+    }
+
+    /// Get the remote sync configuration
+    /// 获取远程同步配置
+    pub fn get_remote_sync_config(&self) -> Result<RemoteSyncConfig, rusqlite::Error> {
+        Ok(self.get_setting("sync.remote_config")?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    /// Save the remote sync configuration
+    /// 保存远程同步配置
+    pub fn set_remote_sync_config(&self, config: &RemoteSyncConfig) -> Result<(), rusqlite::Error> {
+        let value = serde_json::to_string(config).unwrap_or_default();
+        self.set_setting("sync.remote_config", &value)
+    }
+
+    /// Get the opaque pull cursor returned by the server
+    /// 获取服务端返回的拉取游标
+    pub fn get_remote_sync_cursor(&self) -> Result<String, rusqlite::Error> {
+        Ok(self.get_setting("sync.remote_cursor")?.unwrap_or_default())
+    }
+
+    /// Save the pull cursor
+    /// 保存拉取游标
+    pub fn set_remote_sync_cursor(&self, cursor: &str) -> Result<(), rusqlite::Error> {
+        self.set_setting("sync.remote_cursor", cursor)
+    }
+}
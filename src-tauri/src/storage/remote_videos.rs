@@ -0,0 +1,148 @@
+// Remote videos - accounts' published video lists pulled from the platforms
+// 平台作品 - 账号在平台上的作品列表（含未通过本应用发布的作品），同一账号下按作品ID合并，
+// 作品ID与本应用的发布记录一致时关联到 publication_accounts
+
+use super::DatabaseManager;
+use crate::core::RemoteVideo;
+
+const REMOTE_VIDEO_COLUMNS: &str = "id, account_id, item_id, title, cover_url, share_url, create_time, plays, likes, comments, favorites, shares, detail_id, synced_at";
+
+fn map_remote_video(row: &rusqlite::Row) -> rusqlite::Result<RemoteVideo> {
+    let detail_id: String = row.get(12)?;
+    Ok(RemoteVideo {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        item_id: row.get(2)?,
+        title: row.get(3)?,
+        cover_url: row.get(4)?,
+        share_url: row.get(5)?,
+        create_time: row.get(6)?,
+        plays: row.get(7)?,
+        likes: row.get(8)?,
+        comments: row.get(9)?,
+        favorites: row.get(10)?,
+        shares: row.get(11)?,
+        detail_id: if detail_id.is_empty() { None } else { Some(detail_id) },
+        synced_at: row.get(13)?,
+    })
+}
+
+impl DatabaseManager {
+    /// Save an account's synced videos, updating the ones already stored; returns the number of new videos
+    /// 保存同步到的作品（已存在的作品更新标题、封面和互动数据），返回新入库的作品数
+    pub fn save_remote_videos(&self, videos: &[RemoteVideo]) -> Result<usize, rusqlite::Error> {
+        if videos.is_empty() {
+            return Ok(0);
+        }
+
+        self.write("save_remote_videos", |conn| {
+            let tx = conn.transaction()?;
+
+            let mut inserted = 0;
+            for video in videos {
+                let exists: bool = tx.query_row(
+                    "SELECT COUNT(*) > 0 FROM remote_videos WHERE account_id = ? AND item_id = ?",
+                    [&video.account_id, &video.item_id],
+                    |row| row.get(0),
+                )?;
+                if !exists {
+                    inserted += 1;
+                }
+
+                tx.execute(&format!(
+                    "INSERT INTO remote_videos ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+                     ON CONFLICT(account_id, item_id) DO UPDATE SET \
+                     title = excluded.title, cover_url = excluded.cover_url, share_url = excluded.share_url, \
+                     create_time = excluded.create_time, plays = excluded.plays, likes = excluded.likes, \
+                     comments = excluded.comments, favorites = excluded.favorites, shares = excluded.shares, \
+                     synced_at = excluded.synced_at",
+                    REMOTE_VIDEO_COLUMNS
+                ), rusqlite::params![
+                    video.id,
+                    video.account_id,
+                    video.item_id,
+                    video.title,
+                    video.cover_url,
+                    video.share_url,
+                    video.create_time,
+                    video.plays,
+                    video.likes,
+                    video.comments,
+                    video.favorites,
+                    video.shares,
+                    video.detail_id.as_deref().unwrap_or_default(),
+                    video.synced_at,
+                ])?;
+            }
+
+            tx.commit()?;
+            Ok(inserted)
+        })
+    }
+
+    /// Link an account's synced videos to this app's publications with the same item id and copy their stats over
+    /// 把账号的作品关联到作品ID相同的发布记录，并用作品列表中的互动数据更新发布记录；返回已关联的作品数
+    pub fn link_remote_videos(&self, account_id: &str) -> Result<usize, rusqlite::Error> {
+        self.write("link_remote_videos", |conn| {
+            let tx = conn.transaction()?;
+
+            tx.execute(
+                "UPDATE remote_videos SET detail_id = COALESCE(( \
+                     SELECT pa.id FROM publication_accounts pa \
+                     WHERE pa.account_id = remote_videos.account_id AND pa.item_id = remote_videos.item_id \
+                     ORDER BY pa.published_at DESC LIMIT 1 \
+                 ), '') \
+                 WHERE account_id = ?",
+                [account_id],
+            )?;
+            tx.execute(
+                "UPDATE publication_accounts SET \
+                     likes = rv.likes, comments = rv.comments, favorites = rv.favorites, shares = rv.shares \
+                 FROM remote_videos rv \
+                 WHERE rv.detail_id = publication_accounts.id AND rv.account_id = ?",
+                [account_id],
+            )?;
+            let linked = tx.query_row(
+                "SELECT COUNT(*) FROM remote_videos WHERE account_id = ? AND detail_id != ''",
+                [account_id],
+                |row| row.get::<_, i64>(0),
+            )?;
+
+            tx.commit()?;
+            Ok(linked as usize)
+        })
+    }
+
+    /// Get one synced video by id
+    /// 根据ID获取作品
+    pub fn get_remote_video(&self, id: &str) -> Result<Option<RemoteVideo>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM remote_videos WHERE id = ?", REMOTE_VIDEO_COLUMNS))?;
+        let mut rows = stmt.query_map([id], map_remote_video)?;
+        rows.next().transpose()
+    }
+
+    /// Get one page of an account's synced videos, newest first
+    /// 分页获取账号的作品（按发布时间倒序）
+    pub fn get_remote_videos_paginated(&self, account_id: &str, offset: i64, limit: i64) -> Result<Vec<RemoteVideo>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM remote_videos WHERE account_id = ? ORDER BY create_time DESC, item_id DESC LIMIT ? OFFSET ?",
+            REMOTE_VIDEO_COLUMNS
+        ))?;
+        let videos = stmt.query_map(rusqlite::params![account_id, limit, offset], map_remote_video)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(videos)
+    }
+
+    /// Count the videos synced for an account
+    /// 获取账号已同步的作品数量
+    pub fn get_remote_video_count(&self, account_id: &str) -> Result<i64, rusqlite::Error> {
+        let conn = self.get_connection()?;
+        conn.query_row("SELECT COUNT(*) FROM remote_videos WHERE account_id = ?", [account_id], |row| row.get(0))
+    }
+}
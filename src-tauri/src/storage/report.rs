@@ -0,0 +1,436 @@
+// Report builder - constrained query API translated into parameterized SQL
+// 报表构建器 - 受限的查询描述，由 DatabaseManager 转换为参数化 SQL
+//
+// 前端只能选择白名单内的实体/字段/聚合函数，所有用户输入的值均通过参数绑定，
+// 不会拼接进 SQL 文本。
+
+use super::DatabaseManager;
+use super::task_list::like_pattern;
+use rusqlite::types::Value as SqlValue;
+use serde::{Deserialize, Serialize};
+
+/// 单个报表返回的最大行数
+const MAX_REPORT_ROWS: i64 = 1000;
+
+/// 可查询的实体（数据表）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportEntity {
+    Accounts,
+    PublicationTasks,
+    PublicationAccounts,
+    Comments,
+}
+
+/// 字段类型
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFieldKind {
+    Text,
+    Number,
+    Time,
+}
+
+/// 实体字段描述
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportField {
+    pub name: &'static str,
+    pub kind: ReportFieldKind,
+}
+
+impl ReportEntity {
+    /// 所有实体
+    pub const ALL: [ReportEntity; 4] = [
+        ReportEntity::Accounts,
+        ReportEntity::PublicationTasks,
+        ReportEntity::PublicationAccounts,
+        ReportEntity::Comments,
+    ];
+
+    /// 对应的数据表
+    fn table(&self) -> &'static str {
+        match self {
+            ReportEntity::Accounts => "accounts",
+            ReportEntity::PublicationTasks => "publication_tasks",
+            ReportEntity::PublicationAccounts => "publication_accounts",
+            ReportEntity::Comments => "comments",
+        }
+    }
+
+    /// 排除回收站数据的固定条件（发布明细随所属任务、评论随所属账号一起排除）
+    fn live_condition(&self) -> Option<&'static str> {
+        match self {
+            ReportEntity::Accounts | ReportEntity::PublicationTasks => Some("deleted_at = ''"),
+            ReportEntity::PublicationAccounts => {
+                Some("publication_task_id IN (SELECT id FROM publication_tasks WHERE deleted_at = '')")
+            }
+            ReportEntity::Comments => Some("account_id IN (SELECT id FROM accounts WHERE deleted_at = '')"),
+        }
+    }
+
+    /// 可用于过滤/分组/聚合的字段白名单（不包含凭证等敏感字段）
+    pub fn fields(&self) -> &'static [ReportField] {
+        use ReportFieldKind::*;
+        match self {
+            ReportEntity::Accounts => &[
+                ReportField { name: "platform", kind: Text },
+                ReportField { name: "status", kind: Text },
+                ReportField { name: "created_at", kind: Time },
+            ],
+            ReportEntity::PublicationTasks => &[
+                ReportField { name: "status", kind: Text },
+                ReportField { name: "created_at", kind: Time },
+                ReportField { name: "published_at", kind: Time },
+            ],
+            ReportEntity::PublicationAccounts => &[
+                ReportField { name: "publication_task_id", kind: Text },
+                ReportField { name: "account_id", kind: Text },
+                ReportField { name: "account_name", kind: Text },
+                ReportField { name: "platform", kind: Text },
+                ReportField { name: "status", kind: Text },
+                ReportField { name: "created_at", kind: Time },
+                ReportField { name: "published_at", kind: Time },
+                ReportField { name: "comments", kind: Number },
+                ReportField { name: "likes", kind: Number },
+                ReportField { name: "favorites", kind: Number },
+                ReportField { name: "shares", kind: Number },
+            ],
+            ReportEntity::Comments => &[
+                ReportField { name: "account_id", kind: Text },
+                ReportField { name: "aweme_id", kind: Text },
+                ReportField { name: "status", kind: Text },
+                ReportField { name: "like_count", kind: Number },
+                ReportField { name: "reply_count", kind: Number },
+                ReportField { name: "create_time", kind: Time },
+                ReportField { name: "created_at", kind: Time },
+            ],
+        }
+    }
+
+    fn field(&self, name: &str) -> Result<&'static ReportField, String> {
+        self.fields().iter()
+            .find(|f| f.name == name)
+            .ok_or_else(|| format!("{} 不支持字段: {}", self.table(), name))
+    }
+}
+
+/// 过滤操作符
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportOperator {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+    In,
+}
+
+/// 过滤条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportFilter {
+    pub field: String,
+    pub op: ReportOperator,
+    pub value: serde_json::Value,
+}
+
+/// 时间分桶粒度
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeBucket {
+    Day,
+    Week,
+    Month,
+}
+
+/// 分组字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportGroupBy {
+    pub field: String,
+    /// 仅时间字段可用
+    #[serde(default)]
+    pub bucket: Option<TimeBucket>,
+}
+
+/// 聚合函数
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricFunc {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+/// 聚合指标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportMetric {
+    pub func: MetricFunc,
+    /// Count 可省略（即 COUNT(*)），其余函数必须是数值字段
+    #[serde(default)]
+    pub field: Option<String>,
+}
+
+/// 报表查询描述
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportQuery {
+    pub entity: ReportEntity,
+    #[serde(default)]
+    pub filters: Vec<ReportFilter>,
+    #[serde(default)]
+    pub group_by: Vec<ReportGroupBy>,
+    #[serde(default)]
+    pub metrics: Vec<ReportMetric>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+/// 报表结果（按列名 + 行数据返回，便于前端直接渲染表格）
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// 报表可用实体及字段（供前端构建查询界面）
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportEntitySchema {
+    pub entity: ReportEntity,
+    pub fields: &'static [ReportField],
+}
+
+/// 获取所有实体的字段白名单
+pub fn report_schema() -> Vec<ReportEntitySchema> {
+    ReportEntity::ALL.iter()
+        .map(|entity| ReportEntitySchema { entity: *entity, fields: entity.fields() })
+        .collect()
+}
+
+/// 将报表查询编译为 SQL 文本 + 绑定参数 + 列名
+pub fn build_report_sql(query: &ReportQuery) -> Result<(String, Vec<SqlValue>, Vec<String>), String> {
+    let entity = query.entity;
+    let mut select = Vec::new();
+    let mut groups = Vec::new();
+    let mut columns = Vec::new();
+    let mut params = Vec::new();
+
+    for group in &query.group_by {
+        let field = entity.field(&group.field)?;
+        let expr = match group.bucket {
+            None => group.field.clone(),
+            Some(bucket) => {
+                if field.kind != ReportFieldKind::Time {
+                    return Err(format!("字段 {} 不是时间字段，无法按时间分组", field.name));
+                }
+                let fmt = match bucket {
+                    TimeBucket::Day => "%Y-%m-%d",
+                    TimeBucket::Week => "%Y-W%W",
+                    TimeBucket::Month => "%Y-%m",
+                };
+                format!("strftime('{}', {})", fmt, field.name)
+            }
+        };
+        select.push(expr.clone());
+        groups.push(expr);
+        columns.push(match group.bucket {
+            Some(bucket) => format!("{}_{:?}", field.name, bucket).to_lowercase(),
+            None => field.name.to_string(),
+        });
+    }
+
+    let metrics: Vec<ReportMetric> = if query.metrics.is_empty() {
+        vec![ReportMetric { func: MetricFunc::Count, field: None }]
+    } else {
+        query.metrics.clone()
+    };
+
+    for metric in &metrics {
+        let func = format!("{:?}", metric.func).to_uppercase();
+        match (&metric.field, metric.func) {
+            (None, MetricFunc::Count) => {
+                select.push("COUNT(*)".to_string());
+                columns.push("count".to_string());
+            }
+            (None, _) => return Err(format!("{} 需要指定字段", func)),
+            (Some(name), func_kind) => {
+                let field = entity.field(name)?;
+                if func_kind != MetricFunc::Count && field.kind != ReportFieldKind::Number {
+                    return Err(format!("{} 只能用于数值字段: {}", func, field.name));
+                }
+                select.push(format!("{}({})", func, field.name));
+                columns.push(format!("{}_{}", func.to_lowercase(), field.name));
+            }
+        }
+    }
+
+    let mut conditions: Vec<String> = entity.live_condition().map(|c| c.to_string()).into_iter().collect();
+    for filter in &query.filters {
+        let field = entity.field(&filter.field)?;
+        // 文本字段忽略大小写（枚举以 Debug 格式存储，历史数据存在小写值）
+        let collate = if field.kind == ReportFieldKind::Text { " COLLATE NOCASE" } else { "" };
+        let condition = match filter.op {
+            ReportOperator::In => {
+                let values = filter.value.as_array()
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| format!("字段 {} 的 in 条件需要非空数组", field.name))?;
+                for v in values {
+                    params.push(json_to_sql(v)?);
+                }
+                let placeholders = vec!["?"; values.len()].join(", ");
+                format!("{}{} IN ({})", field.name, collate, placeholders)
+            }
+            ReportOperator::Contains => {
+                let text = filter.value.as_str()
+                    .ok_or_else(|| format!("字段 {} 的 contains 条件需要字符串", field.name))?;
+                // 关键字中的 % _ \ 按字面匹配
+                params.push(SqlValue::Text(like_pattern(text)));
+                format!("{} LIKE ? ESCAPE '\\'", field.name)
+            }
+            op => {
+                params.push(json_to_sql(&filter.value)?);
+                let sym = match op {
+                    ReportOperator::Eq => "=",
+                    ReportOperator::Ne => "!=",
+                    ReportOperator::Gt => ">",
+                    ReportOperator::Gte => ">=",
+                    ReportOperator::Lt => "<",
+                    _ => "<=",
+                };
+                format!("{} {} ?{}", field.name, sym, collate)
+            }
+        };
+        conditions.push(condition);
+    }
+
+    let mut sql = format!("SELECT {} FROM {}", select.join(", "), entity.table());
+    if !conditions.is_empty() {
+        sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+    }
+    if !groups.is_empty() {
+        sql.push_str(&format!(" GROUP BY {0} ORDER BY {0}", groups.join(", ")));
+    }
+    let limit = query.limit.unwrap_or(MAX_REPORT_ROWS).clamp(1, MAX_REPORT_ROWS);
+    sql.push_str(" LIMIT ?");
+    params.push(SqlValue::Integer(limit));
+
+    Ok((sql, params, columns))
+}
+
+/// JSON 值转换为 SQLite 绑定参数
+fn json_to_sql(value: &serde_json::Value) -> Result<SqlValue, String> {
+    match value {
+        serde_json::Value::Null => Ok(SqlValue::Null),
+        serde_json::Value::Bool(b) => Ok(SqlValue::Integer(*b as i64)),
+        serde_json::Value::Number(n) => Ok(n.as_i64()
+            .map(SqlValue::Integer)
+            .unwrap_or_else(|| SqlValue::Real(n.as_f64().unwrap_or(0.0)))),
+        serde_json::Value::String(s) => Ok(SqlValue::Text(s.clone())),
+        _ => Err("过滤值只能是字符串、数字或布尔值".to_string()),
+    }
+}
+
+/// SQLite 值转换为 JSON
+fn sql_to_json(value: SqlValue) -> serde_json::Value {
+    match value {
+        SqlValue::Null => serde_json::Value::Null,
+        SqlValue::Integer(i) => serde_json::json!(i),
+        SqlValue::Real(f) => serde_json::json!(f),
+        SqlValue::Text(s) => serde_json::Value::String(s),
+        SqlValue::Blob(_) => serde_json::Value::Null,
+    }
+}
+
+impl DatabaseManager {
+    /// Run a constrained report query
+    /// 执行报表查询
+    pub fn run_report(&self, query: &ReportQuery) -> Result<ReportResult, String> {
+        let (sql, params, columns) = build_report_sql(query)?;
+        tracing::debug!("[Report] {}", sql);
+
+        let conn = self.get_connection().map_err(|e| e.to_string())?;
+        let mut stmt = conn.prepare(&sql).map_err(|e| e.to_string())?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+            (0..columns.len())
+                .map(|i| row.get::<_, SqlValue>(i).map(sql_to_json))
+                .collect::<Result<Vec<_>, _>>()
+        }).map_err(|e| e.to_string())?;
+
+        let rows = rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())?;
+        Ok(ReportResult { columns, rows })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(json: serde_json::Value) -> ReportQuery {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_failures_by_platform_by_week() {
+        let q = query(serde_json::json!({
+            "entity": "publication_accounts",
+            "filters": [{ "field": "status", "op": "eq", "value": "Failed" }],
+            "group_by": [{ "field": "platform" }, { "field": "created_at", "bucket": "week" }],
+        }));
+        let (sql, params, columns) = build_report_sql(&q).unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT platform, strftime('%Y-W%W', created_at), COUNT(*) FROM publication_accounts \
+             WHERE publication_task_id IN (SELECT id FROM publication_tasks WHERE deleted_at = '') AND status = ? COLLATE NOCASE \
+             GROUP BY platform, strftime('%Y-W%W', created_at) ORDER BY platform, strftime('%Y-W%W', created_at) LIMIT ?"
+        );
+        assert_eq!(params, vec![SqlValue::Text("Failed".into()), SqlValue::Integer(MAX_REPORT_ROWS)]);
+        assert_eq!(columns, vec!["platform", "created_at_week", "count"]);
+    }
+
+    #[test]
+    fn test_contains_matches_wildcards_literally() {
+        let q = query(serde_json::json!({
+            "entity": "publication_accounts",
+            "filters": [{ "field": "account_name", "op": "contains", "value": "100%_a\\b" }],
+        }));
+        let (sql, params, _) = build_report_sql(&q).unwrap();
+        assert!(sql.contains("account_name LIKE ? ESCAPE '\\'"));
+        assert_eq!(params[0], SqlValue::Text("%100\\%\\_a\\\\b%".to_string()));
+    }
+
+    #[test]
+    fn test_rejects_unknown_field() {
+        let q = query(serde_json::json!({
+            "entity": "accounts",
+            "filters": [{ "field": "params; DROP TABLE accounts", "op": "eq", "value": 1 }],
+        }));
+        assert!(build_report_sql(&q).is_err());
+    }
+
+    #[test]
+    fn test_sum_requires_numeric_field() {
+        let q = query(serde_json::json!({
+            "entity": "publication_accounts",
+            "metrics": [{ "func": "sum", "field": "platform" }],
+        }));
+        assert!(build_report_sql(&q).is_err());
+
+        let q = query(serde_json::json!({
+            "entity": "publication_accounts",
+            "metrics": [{ "func": "sum", "field": "likes" }],
+            "filters": [{ "field": "platform", "op": "in", "value": ["Douyin", "Kuaishou"] }],
+            "limit": 10,
+        }));
+        let (sql, params, columns) = build_report_sql(&q).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT SUM(likes) FROM publication_accounts \
+             WHERE publication_task_id IN (SELECT id FROM publication_tasks WHERE deleted_at = '') AND platform COLLATE NOCASE IN (?, ?) LIMIT ?"
+        );
+        assert_eq!(params.len(), 3);
+        assert_eq!(columns, vec!["sum_likes"]);
+    }
+}
@@ -0,0 +1,135 @@
+// Scheduled jobs - persisted publish schedule
+// 定时发布任务存储 - 调度器轮询此表，应用重启后任务不会丢失
+
+use super::DatabaseManager;
+use crate::core::{ScheduledJob, ScheduledJobStatus};
+
+const JOB_COLUMNS: &str = "id, task_id, send_at, status, message, created_at, updated_at";
+
+fn map_job(row: &rusqlite::Row) -> rusqlite::Result<ScheduledJob> {
+    let message: String = row.get::<_, Option<String>>(4)?.unwrap_or_default();
+    Ok(ScheduledJob {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        send_at: row.get(2)?,
+        status: ScheduledJobStatus::parse(&row.get::<_, String>(3)?),
+        message: if message.is_empty() { None } else { Some(message) },
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+impl DatabaseManager {
+    /// Save a scheduled job
+    /// 保存定时发布任务
+    pub fn save_scheduled_job(&self, job: &ScheduledJob) -> Result<(), rusqlite::Error> {
+        self.write("save_scheduled_job", |conn| {
+            conn.execute(r#"
+                INSERT OR REPLACE INTO scheduled_jobs (
+                    id, task_id, send_at, status, message, created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#, [
+                &job.id,
+                &job.task_id,
+                &job.send_at,
+                job.status.as_str(),
+                job.message.as_deref().unwrap_or(""),
+                &job.created_at,
+                &job.updated_at,
+            ])?;
+
+            Ok(())
+        })
+    }
+
+    /// Get scheduled jobs, optionally filtered by task (latest send time first)
+    /// 获取定时任务（可按发布任务过滤）
+    pub fn get_scheduled_jobs(&self, task_id: Option<&str>) -> Result<Vec<ScheduledJob>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let jobs = match task_id {
+            Some(task_id) => {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {} FROM scheduled_jobs WHERE task_id = ? ORDER BY send_at DESC", JOB_COLUMNS
+                ))?;
+                let jobs = stmt.query_map([task_id], map_job)?.filter_map(|r| r.ok()).collect();
+                jobs
+            }
+            None => {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {} FROM scheduled_jobs ORDER BY send_at DESC", JOB_COLUMNS
+                ))?;
+                let jobs = stmt.query_map([], map_job)?.filter_map(|r| r.ok()).collect();
+                jobs
+            }
+        };
+
+        Ok(jobs)
+    }
+
+    /// Get pending jobs whose send time has arrived
+    /// 获取已到发布时间的待执行任务
+    pub fn get_due_scheduled_jobs(&self, now: &str) -> Result<Vec<ScheduledJob>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM scheduled_jobs WHERE status = 'pending' AND send_at <= ? ORDER BY send_at", JOB_COLUMNS
+        ))?;
+        let jobs = stmt.query_map([now], map_job)?.filter_map(|r| r.ok()).collect();
+
+        Ok(jobs)
+    }
+
+    /// Atomically move a pending job to running; false if another runner already took it
+    /// 将待执行任务标记为执行中（返回 false 表示已被取消或已被执行）
+    pub fn claim_scheduled_job(&self, job_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("claim_scheduled_job", |conn| {
+            let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+            let rows = conn.execute(
+                "UPDATE scheduled_jobs SET status = 'running', updated_at = ? WHERE id = ? AND status = 'pending'",
+                [&now, job_id],
+            )?;
+
+            Ok(rows > 0)
+        })
+    }
+
+    /// Record the outcome of a fired job
+    /// 记录定时任务执行结果
+    pub fn finish_scheduled_job(&self, job_id: &str, status: ScheduledJobStatus, message: &str) -> Result<(), rusqlite::Error> {
+        self.write("finish_scheduled_job", |conn| {
+            let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+            conn.execute(
+                "UPDATE scheduled_jobs SET status = ?, message = ?, updated_at = ? WHERE id = ?",
+                [status.as_str(), message, &now, job_id],
+            )?;
+
+            Ok(())
+        })
+    }
+
+    /// Cancel a pending job
+    /// 取消尚未执行的定时任务
+    pub fn cancel_scheduled_job(&self, job_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("cancel_scheduled_job", |conn| {
+            let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+            let rows = conn.execute(
+                "UPDATE scheduled_jobs SET status = 'cancelled', updated_at = ? WHERE id = ? AND status = 'pending'",
+                [&now, job_id],
+            )?;
+
+            Ok(rows > 0)
+        })
+    }
+
+    /// Put jobs interrupted by an app exit back into the queue
+    /// 将上次退出时仍在执行的任务恢复为待执行
+    pub fn requeue_running_scheduled_jobs(&self) -> Result<usize, rusqlite::Error> {
+        self.write("requeue_running_scheduled_jobs", |conn| {
+            conn.execute("UPDATE scheduled_jobs SET status = 'pending' WHERE status = 'running'", [])
+        })
+    }
+}
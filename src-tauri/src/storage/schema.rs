@@ -0,0 +1,527 @@
+// Schema verification - compare live schema with the expected definition
+// 数据库结构校验 - 比对实际表结构与期望定义，自动补齐缺失字段
+//
+// 老版本数据库或被手动修改过的数据库可能缺少字段，读取时会出现
+// row.get(n) 列索引错误。启动时先用哈希快速比对，不一致时逐列检查：
+// - 缺失字段：ALTER TABLE ADD COLUMN 补齐（仅做加法修复）
+// - 类型不一致 / 缺失表：无法自动修复，记录到报告中
+
+use super::DatabaseManager;
+use rusqlite::Connection;
+use serde::Serialize;
+
+/// 字段定义：(字段名, 类型, ADD COLUMN 时的字段定义)
+type ColumnDef = (&'static str, &'static str, &'static str);
+
+/// 期望的表结构：(表名, 字段定义列表)
+///
+/// 修改 initialize_schema 中的建表语句时需要同步更新此处
+pub(crate) const EXPECTED_SCHEMA: &[(&str, &[ColumnDef])] = &[
+    ("accounts", &[
+        ("id", "TEXT", "TEXT"),
+        ("username", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("nickname", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("avatar_url", "TEXT", "TEXT"),
+        ("platform", "TEXT", "TEXT NOT NULL DEFAULT 'douyin'"),
+        ("params", "TEXT", "TEXT NOT NULL DEFAULT '{}'"),
+        ("status", "TEXT", "TEXT NOT NULL DEFAULT 'pending'"),
+        ("created_at", "TEXT", "TEXT"),
+        ("on_hold", "INTEGER", "INTEGER DEFAULT 0"),
+        ("group_id", "TEXT", "TEXT DEFAULT ''"),
+        ("tags", "TEXT", "TEXT DEFAULT ''"),
+        ("deleted_at", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+    ]),
+    ("account_groups", &[
+        ("id", "TEXT", "TEXT"),
+        ("name", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("description", "TEXT", "TEXT DEFAULT ''"),
+        ("created_at", "TEXT", "TEXT"),
+        ("watermark", "TEXT", "TEXT DEFAULT ''"),
+    ]),
+    ("publication_tasks", &[
+        ("id", "TEXT", "TEXT"),
+        ("title", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("description", "TEXT", "TEXT"),
+        ("video_path", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("cover_path", "TEXT", "TEXT"),
+        ("hashtags", "TEXT", "TEXT DEFAULT '[]'"),
+        ("status", "TEXT", "TEXT NOT NULL DEFAULT 'draft'"),
+        ("created_at", "TEXT", "TEXT"),
+        ("published_at", "TEXT", "TEXT"),
+        ("hashtag_set_id", "TEXT", "TEXT DEFAULT ''"),
+        ("vertical_video_path", "TEXT", "TEXT DEFAULT ''"),
+        ("horizontal_video_path", "TEXT", "TEXT DEFAULT ''"),
+        ("watermark_enabled", "INTEGER", "INTEGER DEFAULT 0"),
+        ("visibility_type", "INTEGER", "INTEGER DEFAULT 0"),
+        ("download_allowed", "INTEGER", "INTEGER DEFAULT 0"),
+        ("poi_id", "TEXT", "TEXT DEFAULT ''"),
+        ("poi_name", "TEXT", "TEXT DEFAULT ''"),
+        ("music_id", "TEXT", "TEXT DEFAULT ''"),
+        ("music_end_time", "TEXT", "TEXT DEFAULT ''"),
+        ("mentions", "TEXT", "TEXT DEFAULT '[]'"),
+        ("deleted_at", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+    ]),
+    ("publication_accounts", &[
+        ("id", "TEXT", "TEXT"),
+        ("publication_task_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("account_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("account_name", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("platform", "TEXT", "TEXT NOT NULL DEFAULT 'douyin'"),
+        ("status", "TEXT", "TEXT NOT NULL DEFAULT 'draft'"),
+        ("created_at", "TEXT", "TEXT"),
+        ("published_at", "TEXT", "TEXT"),
+        ("publish_url", "TEXT", "TEXT"),
+        ("comments", "INTEGER", "INTEGER DEFAULT 0"),
+        ("likes", "INTEGER", "INTEGER DEFAULT 0"),
+        ("favorites", "INTEGER", "INTEGER DEFAULT 0"),
+        ("shares", "INTEGER", "INTEGER DEFAULT 0"),
+        ("message", "TEXT", "TEXT DEFAULT ''"),
+        ("item_id", "TEXT", "TEXT DEFAULT ''"),
+        ("strategy_version", "TEXT", "TEXT DEFAULT ''"),
+        ("title_override", "TEXT", "TEXT DEFAULT ''"),
+        ("description_override", "TEXT", "TEXT DEFAULT ''"),
+        ("hashtags_override", "TEXT", "TEXT DEFAULT ''"),
+        ("attempts", "INTEGER", "INTEGER DEFAULT 0"),
+        ("error_code", "TEXT", "TEXT DEFAULT ''"),
+        ("variant", "TEXT", "TEXT DEFAULT ''"),
+    ]),
+    ("task_notes", &[
+        ("id", "TEXT", "TEXT"),
+        ("task_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("author", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("content", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("created_at", "TEXT", "TEXT"),
+    ]),
+    ("account_reassignments", &[
+        ("id", "TEXT", "TEXT"),
+        ("detail_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("task_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("from_account_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("from_account_name", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("to_account_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("to_account_name", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("reason", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("created_at", "TEXT", "TEXT"),
+    ]),
+    ("extractor_configs", &[
+        ("id", "TEXT", "TEXT"),
+        ("platform_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("platform_name", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("login_url", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("login_success_mode", "TEXT", "TEXT DEFAULT 'url_match'"),
+        ("login_success_pattern", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("login_success_api_rule", "TEXT", "TEXT"),
+        ("login_success_api_operator", "TEXT", "TEXT"),
+        ("login_success_api_value", "TEXT", "TEXT"),
+        ("redirect_url", "TEXT", "TEXT"),
+        ("extract_rules", "TEXT", "TEXT NOT NULL DEFAULT '{}'"),
+        ("is_default", "INTEGER", "INTEGER DEFAULT 0"),
+        ("created_at", "TEXT", "TEXT"),
+        ("updated_at", "TEXT", "TEXT"),
+    ]),
+    ("comments", &[
+        ("id", "TEXT", "TEXT"),
+        ("account_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("aweme_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("comment_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("user_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("user_nickname", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("user_avatar", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("content", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("like_count", "INTEGER", "INTEGER DEFAULT 0"),
+        ("reply_count", "INTEGER", "INTEGER DEFAULT 0"),
+        ("create_time", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("status", "TEXT", "TEXT NOT NULL DEFAULT 'pending'"),
+        ("created_at", "TEXT", "TEXT"),
+        ("tags", "TEXT", "TEXT DEFAULT ''"),
+        ("sync_id", "INTEGER", "INTEGER"),
+    ]),
+    ("hashtag_sets", &[
+        ("id", "TEXT", "TEXT"),
+        ("name", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("tags", "TEXT", "TEXT NOT NULL DEFAULT '[]'"),
+        ("platform_tags", "TEXT", "TEXT NOT NULL DEFAULT '{}'"),
+        ("created_at", "TEXT", "TEXT"),
+        ("updated_at", "TEXT", "TEXT"),
+    ]),
+    ("hashtags", &[
+        ("name", "TEXT", "TEXT"),
+        ("douyin_hashtag_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("usage_count", "INTEGER", "INTEGER DEFAULT 0"),
+        ("created_at", "TEXT", "TEXT"),
+        ("last_used_at", "TEXT", "TEXT DEFAULT ''"),
+    ]),
+    ("hashtag_usages", &[
+        ("detail_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("hashtag", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("used_at", "TEXT", "TEXT"),
+    ]),
+    ("publication_templates", &[
+        ("id", "TEXT", "TEXT"),
+        ("name", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("title", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("description", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("hashtags", "TEXT", "TEXT NOT NULL DEFAULT '[]'"),
+        ("hashtag_set_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("accounts", "TEXT", "TEXT NOT NULL DEFAULT '[]'"),
+        ("created_at", "TEXT", "TEXT"),
+        ("updated_at", "TEXT", "TEXT"),
+    ]),
+    ("account_stats_history", &[
+        ("id", "INTEGER", "INTEGER"),
+        ("account_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("snapshot_date", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("works", "INTEGER", "INTEGER DEFAULT 0"),
+        ("comments", "INTEGER", "INTEGER DEFAULT 0"),
+        ("likes", "INTEGER", "INTEGER DEFAULT 0"),
+        ("favorites", "INTEGER", "INTEGER DEFAULT 0"),
+        ("shares", "INTEGER", "INTEGER DEFAULT 0"),
+        ("followers", "INTEGER", "INTEGER DEFAULT 0"),
+        ("following", "INTEGER", "INTEGER DEFAULT 0"),
+        ("total_likes", "INTEGER", "INTEGER DEFAULT 0"),
+        ("video_count", "INTEGER", "INTEGER DEFAULT 0"),
+    ]),
+    ("scheduled_jobs", &[
+        ("id", "TEXT", "TEXT"),
+        ("task_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("send_at", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("status", "TEXT", "TEXT NOT NULL DEFAULT 'pending'"),
+        ("message", "TEXT", "TEXT DEFAULT ''"),
+        ("created_at", "TEXT", "TEXT"),
+        ("updated_at", "TEXT", "TEXT"),
+    ]),
+    ("auto_reply_rules", &[
+        ("id", "TEXT", "TEXT"),
+        ("account_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("name", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("keywords", "TEXT", "TEXT NOT NULL DEFAULT '[]'"),
+        ("reply_template", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("enabled", "INTEGER", "INTEGER NOT NULL DEFAULT 1"),
+        ("max_replies_per_hour", "INTEGER", "INTEGER NOT NULL DEFAULT 20"),
+        ("created_at", "TEXT", "TEXT"),
+        ("updated_at", "TEXT", "TEXT"),
+    ]),
+    ("auto_reply_logs", &[
+        ("id", "TEXT", "TEXT"),
+        ("rule_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("account_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("aweme_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("comment_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("comment_content", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("reply_content", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("success", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("error", "TEXT", "TEXT DEFAULT ''"),
+        ("created_at", "TEXT", "TEXT"),
+    ]),
+    ("comment_sync_state", &[
+        ("aweme_id", "TEXT", "TEXT"),
+        ("detail_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("cursor", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("has_more", "INTEGER", "INTEGER NOT NULL DEFAULT 1"),
+        ("synced_count", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("last_error", "TEXT", "TEXT DEFAULT ''"),
+        ("last_synced_at", "TEXT", "TEXT"),
+    ]),
+    ("comment_syncs", &[
+        ("id", "INTEGER", "INTEGER"),
+        ("aweme_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("new_count", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("synced_at", "TEXT", "TEXT"),
+    ]),
+    ("messages", &[
+        ("id", "TEXT", "TEXT"),
+        ("account_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("conversation_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("message_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("sender_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("sender_nickname", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("sender_avatar", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("content", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("is_outgoing", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("create_time", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("created_at", "TEXT", "TEXT"),
+    ]),
+    ("remote_videos", &[
+        ("id", "TEXT", "TEXT"),
+        ("account_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("item_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("title", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("cover_url", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("share_url", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("create_time", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("plays", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("likes", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("comments", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("favorites", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("shares", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("detail_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("synced_at", "TEXT", "TEXT"),
+    ]),
+    ("webhook_deliveries", &[
+        ("id", "TEXT", "TEXT"),
+        ("url", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("event", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("task_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("detail_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("payload", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("success", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("attempts", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("response_code", "INTEGER", "INTEGER"),
+        ("error", "TEXT", "TEXT DEFAULT ''"),
+        ("created_at", "TEXT", "TEXT"),
+    ]),
+    ("publish_checkpoints", &[
+        ("detail_id", "TEXT", "TEXT"),
+        ("step", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("video_path", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("video_size", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("video_id", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("poster_uri", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("bd_ticket", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("bd_ticket_at", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("updated_at", "TEXT", "TEXT"),
+    ]),
+    ("schema_version", &[
+        ("version", "INTEGER", "INTEGER"),
+        ("name", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("applied_at", "TEXT", "TEXT"),
+    ]),
+    ("app_settings", &[
+        ("key", "TEXT", "TEXT"),
+        ("value", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("updated_at", "TEXT", "TEXT"),
+    ]),
+    ("sync_changes", &[
+        ("entity", "TEXT", "TEXT"),
+        ("entity_id", "TEXT", "TEXT"),
+        ("updated_at", "TEXT", "TEXT NOT NULL DEFAULT ''"),
+        ("deleted", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+        ("pushed", "INTEGER", "INTEGER NOT NULL DEFAULT 0"),
+    ]),
+];
+
+/// 结构问题类型
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaIssueKind {
+    /// 表不存在
+    MissingTable,
+    /// 字段缺失（已尝试补齐）
+    MissingColumn,
+    /// 字段类型不一致
+    TypeMismatch,
+}
+
+/// 单个结构问题
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaIssue {
+    pub kind: SchemaIssueKind,
+    pub table: String,
+    pub column: Option<String>,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    /// 是否已自动修复
+    pub repaired: bool,
+}
+
+/// 结构校验报告
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaReport {
+    pub expected_hash: String,
+    pub live_hash: String,
+    pub issues: Vec<SchemaIssue>,
+}
+
+impl SchemaReport {
+    /// 所有问题均已修复（或没有问题）
+    pub fn is_healthy(&self) -> bool {
+        self.issues.iter().all(|i| i.repaired)
+    }
+}
+
+/// 计算结构哈希：按 表名.字段名:类型 排序后取 MD5
+fn schema_hash<'a>(columns: impl Iterator<Item = (&'a str, &'a str, &'a str)>) -> String {
+    let mut entries: Vec<String> = columns
+        .map(|(table, column, ty)| format!("{}.{}:{}", table, column, ty.to_uppercase()))
+        .collect();
+    entries.sort();
+    format!("{:x}", md5::compute(entries.join("\n")))
+}
+
+/// 期望结构的哈希
+pub fn expected_schema_hash() -> String {
+    schema_hash(EXPECTED_SCHEMA.iter()
+        .flat_map(|(table, cols)| cols.iter().map(move |(col, ty, _)| (*table, *col, *ty))))
+}
+
+/// 读取实际表结构中与期望定义相关的字段：(表名, 字段名, 类型)
+fn live_columns(conn: &Connection) -> rusqlite::Result<Vec<(String, String, String)>> {
+    let mut columns = Vec::new();
+    for (table, _) in EXPECTED_SCHEMA {
+        let mut stmt = conn.prepare("SELECT name, type FROM pragma_table_info(?)")?;
+        let rows = stmt.query_map([table], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        for row in rows {
+            let (name, ty) = row?;
+            columns.push((table.to_string(), name, ty));
+        }
+    }
+    Ok(columns)
+}
+
+fn hash_live_columns(columns: &[(String, String, String)]) -> String {
+    // 只统计期望定义中存在的字段，用户额外添加的字段不视为漂移
+    schema_hash(columns.iter()
+        .filter(|(table, col, _)| EXPECTED_SCHEMA.iter()
+            .any(|(t, cols)| t == table && cols.iter().any(|(c, _, _)| c == col)))
+        .map(|(t, c, ty)| (t.as_str(), c.as_str(), ty.as_str())))
+}
+
+impl DatabaseManager {
+    /// Verify live schema against the expected definition and repair additively
+    /// 校验数据库结构，缺失字段自动补齐，其余问题记录到报告
+    pub fn verify_schema(&self) -> rusqlite::Result<SchemaReport> {
+        let conn = self.get_connection()?;
+        Self::verify_schema_with(&conn)
+    }
+
+    pub(crate) fn verify_schema_with(conn: &Connection) -> rusqlite::Result<SchemaReport> {
+        let expected_hash = expected_schema_hash();
+        let live = live_columns(conn)?;
+        let live_hash = hash_live_columns(&live);
+
+        if live_hash == expected_hash {
+            return Ok(SchemaReport { expected_hash, live_hash, issues: Vec::new() });
+        }
+
+        tracing::warn!("[Database] 数据库结构与期望定义不一致，开始逐列检查 (expected={}, live={})", expected_hash, live_hash);
+
+        let mut issues = Vec::new();
+        for (table, columns) in EXPECTED_SCHEMA {
+            let table_columns: Vec<&(String, String, String)> = live.iter().filter(|(t, _, _)| t == table).collect();
+            if table_columns.is_empty() {
+                issues.push(SchemaIssue {
+                    kind: SchemaIssueKind::MissingTable,
+                    table: table.to_string(),
+                    column: None,
+                    expected: None,
+                    actual: None,
+                    repaired: false,
+                });
+                continue;
+            }
+
+            for (column, ty, definition) in columns.iter() {
+                match table_columns.iter().find(|(_, c, _)| c == column) {
+                    None => {
+                        let sql = format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition);
+                        let repaired = match conn.execute(&sql, []) {
+                            Ok(_) => {
+                                tracing::info!("[Database] 已补齐缺失字段: {}.{}", table, column);
+                                true
+                            }
+                            Err(e) => {
+                                tracing::error!("[Database] 补齐字段 {}.{} 失败: {}", table, column, e);
+                                false
+                            }
+                        };
+                        issues.push(SchemaIssue {
+                            kind: SchemaIssueKind::MissingColumn,
+                            table: table.to_string(),
+                            column: Some(column.to_string()),
+                            expected: Some(ty.to_string()),
+                            actual: None,
+                            repaired,
+                        });
+                    }
+                    Some((_, _, actual)) if !actual.eq_ignore_ascii_case(ty) => {
+                        tracing::warn!("[Database] 字段类型不一致: {}.{} 期望 {}，实际 {}", table, column, ty, actual);
+                        issues.push(SchemaIssue {
+                            kind: SchemaIssueKind::TypeMismatch,
+                            table: table.to_string(),
+                            column: Some(column.to_string()),
+                            expected: Some(ty.to_string()),
+                            actual: Some(actual.clone()),
+                            repaired: false,
+                        });
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        let live_hash = hash_live_columns(&live_columns(conn)?);
+        Ok(SchemaReport { expected_hash, live_hash, issues })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{AccountStatus, PlatformType};
+
+    #[test]
+    fn test_read_rows_with_out_of_order_columns() {
+        let dir = std::env::temp_dir().join(format!("amm-schema-{}", uuid::Uuid::new_v4()));
+        let db = DatabaseManager::new(dir.clone());
+
+        // 被手动修改过的数据库：字段顺序与建表语句不同，且缺少后来追加的字段
+        {
+            std::fs::create_dir_all(&dir).unwrap();
+            let conn = Connection::open(db.get_db_path()).unwrap();
+            conn.execute_batch(r#"
+                CREATE TABLE accounts (
+                    id TEXT PRIMARY KEY,
+                    tags TEXT DEFAULT '',
+                    group_id TEXT DEFAULT '',
+                    platform TEXT NOT NULL,
+                    nickname TEXT NOT NULL,
+                    username TEXT NOT NULL,
+                    params TEXT NOT NULL,
+                    status TEXT NOT NULL DEFAULT 'pending',
+                    deleted_at TEXT NOT NULL DEFAULT '',
+                    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                    avatar_url TEXT
+                );
+                CREATE TABLE publication_tasks (
+                    id TEXT PRIMARY KEY,
+                    mentions TEXT DEFAULT '[]',
+                    status TEXT NOT NULL DEFAULT 'draft',
+                    title TEXT NOT NULL,
+                    hashtags TEXT DEFAULT '[]',
+                    video_path TEXT NOT NULL,
+                    description TEXT,
+                    cover_path TEXT,
+                    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                    published_at TEXT,
+                    deleted_at TEXT NOT NULL DEFAULT ''
+                );
+                INSERT INTO accounts (id, tags, group_id, platform, nickname, username, params, status, created_at, avatar_url)
+                    VALUES ('a1', '["美食"]', 'g1', 'Douyin', '昵称', 'user', '{}', 'Active', '2024-01-01 00:00:00', 'avatar.png');
+                INSERT INTO publication_tasks (id, mentions, status, title, hashtags, video_path, description, cover_path, created_at, published_at)
+                    VALUES ('t1', '[]', 'Completed', '标题', '["话题"]', 'video.mp4', '描述', 'cover.png', '2024-01-01 00:00:00', '');
+            "#).unwrap();
+        }
+
+        // 打开时迁移把缺失字段追加到表末尾，之后结构校验不应再有缺失字段
+        let report = db.verify_schema().unwrap();
+        assert!(!report.issues.iter().any(|i| i.kind == SchemaIssueKind::MissingColumn));
+
+        let account = db.get_account("a1").unwrap().expect("account is readable");
+        assert_eq!(account.username, "user");
+        assert_eq!(account.nickname, "昵称");
+        assert_eq!(account.avatar_url, "avatar.png");
+        assert_eq!(account.platform, PlatformType::Douyin);
+        assert_eq!(account.status, AccountStatus::Active);
+        assert_eq!(account.group_id.as_deref(), Some("g1"));
+        assert_eq!(account.tags, vec!["美食".to_string()]);
+        assert!(!account.on_hold);
+
+        let task = db.get_publication_task("t1").unwrap().expect("task is readable");
+        assert_eq!(task.title, "标题");
+        assert_eq!(task.video_path, "video.mp4");
+        assert_eq!(task.description.as_deref(), Some("描述"));
+        assert_eq!(task.hashtags, vec!["话题".to_string()]);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,247 @@
+// Full-text search - FTS5 index over task titles/descriptions and comment text
+// 全文搜索 - 用 FTS5 虚拟表索引任务标题、描述和评论内容，由触发器与源表保持同步。
+// 使用 trigram 分词器以支持中文子串匹配；少于 3 个字的关键字无法用 trigram 索引，改为 LIKE 查询源表。
+// 虚拟表不在 EXPECTED_SCHEMA 中校验（不能 ALTER TABLE 补列），结构变化时删表重建即可
+
+use rusqlite::types::Value as SqlValue;
+use serde::Serialize;
+use super::DatabaseManager;
+use crate::core::PublicationStatus;
+
+/// 搜索结果条数上限
+pub const DEFAULT_SEARCH_LIMIT: i64 = 50;
+
+/// trigram 分词器可索引的最短关键字长度（字符数）
+const MIN_TRIGRAM_CHARS: usize = 3;
+
+/// (索引表, 源表, 索引字段)
+const SEARCH_INDEXES: &[(&str, &str, &[&str])] = &[
+    ("task_search", "publication_tasks", &["title", "description"]),
+    ("comment_search", "comments", &["content"]),
+];
+
+/// A task matching a search
+/// 搜索命中的任务
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskSearchHit {
+    pub task_id: String,
+    pub title: String,
+    pub description: String,
+    pub status: PublicationStatus,
+    pub created_at: String,
+}
+
+/// A comment matching a search, with the task that published the video
+/// 搜索命中的评论，附带发布该作品的任务（作品不是本应用发布时为空）
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentSearchHit {
+    pub comment_id: String,
+    pub account_id: String,
+    pub aweme_id: String,
+    pub user_nickname: String,
+    pub content: String,
+    pub create_time: String,
+    pub task_id: Option<String>,
+    pub task_title: Option<String>,
+}
+
+/// 创建全文索引和同步触发器；索引表首次创建时为已有数据建立索引
+///
+/// 索引行的 rowid 与源表行的 rowid 一致。源表使用 INSERT OR REPLACE 写入时，
+/// 冲突行的删除不会触发 DELETE 触发器，因此在 BEFORE INSERT 中先删除旧行的索引
+pub(super) fn create_search_indexes(conn: &rusqlite::Connection) -> Result<(), rusqlite::Error> {
+    for (index, table, columns) in SEARCH_INDEXES {
+        let exists: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+            [index],
+            |row| row.get(0),
+        )?;
+
+        let column_list = columns.join(", ");
+        let new_values = columns.iter().map(|c| format!("NEW.{}", c)).collect::<Vec<_>>().join(", ");
+        let assignments = columns.iter().map(|c| format!("{0} = NEW.{0}", c)).collect::<Vec<_>>().join(", ");
+
+        conn.execute(&format!(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS {} USING fts5({}, tokenize = 'trigram')",
+            index, column_list
+        ), [])?;
+
+        conn.execute(&format!(
+            "CREATE TRIGGER IF NOT EXISTS {index}_before_insert BEFORE INSERT ON {table} BEGIN \
+             DELETE FROM {index} WHERE rowid = (SELECT rowid FROM {table} WHERE id = NEW.id); \
+             END",
+            index = index, table = table,
+        ), [])?;
+        conn.execute(&format!(
+            "CREATE TRIGGER IF NOT EXISTS {index}_insert AFTER INSERT ON {table} BEGIN \
+             INSERT OR REPLACE INTO {index} (rowid, {columns}) VALUES (NEW.rowid, {values}); \
+             END",
+            index = index, table = table, columns = column_list, values = new_values,
+        ), [])?;
+        conn.execute(&format!(
+            "CREATE TRIGGER IF NOT EXISTS {index}_update AFTER UPDATE OF {columns} ON {table} BEGIN \
+             UPDATE {index} SET {assignments} WHERE rowid = NEW.rowid; \
+             END",
+            index = index, table = table, columns = column_list, assignments = assignments,
+        ), [])?;
+        conn.execute(&format!(
+            "CREATE TRIGGER IF NOT EXISTS {index}_delete AFTER DELETE ON {table} BEGIN \
+             DELETE FROM {index} WHERE rowid = OLD.rowid; \
+             END",
+            index = index, table = table,
+        ), [])?;
+
+        if exists == 0 {
+            let indexed = conn.execute(&format!(
+                "INSERT INTO {index} (rowid, {columns}) SELECT rowid, {columns} FROM {table}",
+                index = index, table = table, columns = column_list,
+            ), [])?;
+            tracing::info!("[Database] 已为 {} 建立全文索引: {} 条", table, indexed);
+        }
+    }
+    Ok(())
+}
+
+/// 拆分搜索关键字（空白分隔，多个关键字需同时命中）
+fn search_terms(query: &str) -> Vec<&str> {
+    query.split_whitespace().collect()
+}
+
+/// 生成 FTS5 MATCH 表达式：每个关键字按短语匹配；有关键字短于 3 个字时返回 None（改用 LIKE）
+fn fts_match_expr(terms: &[&str]) -> Option<String> {
+    if terms.is_empty() || terms.iter().any(|t| t.chars().count() < MIN_TRIGRAM_CHARS) {
+        return None;
+    }
+    Some(terms.iter()
+        .map(|t| format!("\"{}\"", t.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" AND "))
+}
+
+/// 转义 LIKE 通配符
+fn like_pattern(term: &str) -> String {
+    let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// 生成搜索条件和参数：关键字足够长时走全文索引，否则对源表字段做 LIKE 匹配
+///
+/// `source` 为源表别名，`index` 为索引表名
+fn search_condition(terms: &[&str], source: &str, index: &str, columns: &[&str]) -> (String, Vec<SqlValue>) {
+    match fts_match_expr(terms) {
+        Some(expr) => (
+            format!("{}.rowid IN (SELECT rowid FROM {} WHERE {} MATCH ?)", source, index, index),
+            vec![SqlValue::Text(expr)],
+        ),
+        None => {
+            let mut params = Vec::new();
+            let conditions: Vec<String> = terms.iter().map(|term| {
+                let any_column: Vec<String> = columns.iter()
+                    .map(|c| {
+                        params.push(SqlValue::Text(like_pattern(term)));
+                        format!("{}.{} LIKE ? ESCAPE '\\'", source, c)
+                    })
+                    .collect();
+                format!("({})", any_column.join(" OR "))
+            }).collect();
+            (conditions.join(" AND "), params)
+        }
+    }
+}
+
+impl DatabaseManager {
+    /// Search tasks by title and description
+    /// 按标题、描述搜索发布任务（按创建时间倒序）
+    pub fn search_tasks(&self, query: &str, limit: i64) -> Result<Vec<TaskSearchHit>, rusqlite::Error> {
+        let terms = search_terms(query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self.get_connection()?;
+        let (condition, mut params) = search_condition(&terms, "t", "task_search", &["title", "description"]);
+        params.push(SqlValue::Integer(limit));
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT t.id, t.title, t.description, t.status, t.created_at FROM publication_tasks t \
+             WHERE t.deleted_at = '' AND {} ORDER BY t.created_at DESC LIMIT ?",
+            condition
+        ))?;
+        let hits = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+            Ok(TaskSearchHit {
+                task_id: row.get(0)?,
+                title: row.get(1)?,
+                description: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                status: Self::parse_publication_status(row.get(3)?),
+                created_at: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+            })
+        })?.filter_map(|r| r.ok()).collect();
+
+        Ok(hits)
+    }
+
+    /// Search comments by content
+    /// 按内容搜索评论（按评论时间倒序），附带发布该作品的任务
+    pub fn search_comments(&self, query: &str, limit: i64) -> Result<Vec<CommentSearchHit>, rusqlite::Error> {
+        let terms = search_terms(query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self.get_connection()?;
+        let (condition, mut params) = search_condition(&terms, "c", "comment_search", &["content"]);
+        params.push(SqlValue::Integer(limit));
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT c.comment_id, c.account_id, c.aweme_id, c.user_nickname, c.content, c.create_time, t.id, t.title \
+             FROM comments c \
+             LEFT JOIN publication_tasks t ON t.deleted_at = '' AND t.id = ( \
+                 SELECT pa.publication_task_id FROM publication_accounts pa \
+                 WHERE pa.item_id = c.aweme_id AND pa.item_id != '' LIMIT 1) \
+             WHERE {} ORDER BY c.create_time DESC LIMIT ?",
+            condition
+        ))?;
+        let hits = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+            Ok(CommentSearchHit {
+                comment_id: row.get(0)?,
+                account_id: row.get(1)?,
+                aweme_id: row.get(2)?,
+                user_nickname: row.get(3)?,
+                content: row.get(4)?,
+                create_time: row.get(5)?,
+                task_id: row.get(6)?,
+                task_title: row.get(7)?,
+            })
+        })?.filter_map(|r| r.ok()).collect();
+
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_match_expr() {
+        assert_eq!(fts_match_expr(&["新品上市"]), Some("\"新品上市\"".to_string()));
+        assert_eq!(fts_match_expr(&["新品上市", "优惠券"]), Some("\"新品上市\" AND \"优惠券\"".to_string()));
+        assert_eq!(fts_match_expr(&["say\"hi"]), Some("\"say\"\"hi\"".to_string()));
+        // 短于 3 个字无法用 trigram 索引
+        assert_eq!(fts_match_expr(&["新品"]), None);
+        assert_eq!(fts_match_expr(&["新品上市", "好"]), None);
+        assert_eq!(fts_match_expr(&[]), None);
+    }
+
+    #[test]
+    fn test_search_condition_falls_back_to_like() {
+        let (condition, params) = search_condition(&["新品", "5%"], "t", "task_search", &["title", "description"]);
+        assert_eq!(condition.matches('?').count(), params.len());
+        assert_eq!(params.len(), 4);
+        assert_eq!(params[2], SqlValue::Text("%5\\%%".to_string()));
+
+        let (condition, params) = search_condition(&["新品上市"], "c", "comment_search", &["content"]);
+        assert_eq!(condition, "c.rowid IN (SELECT rowid FROM comment_search WHERE comment_search MATCH ?)");
+        assert_eq!(params.len(), 1);
+    }
+}
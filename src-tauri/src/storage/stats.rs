@@ -0,0 +1,299 @@
+// Account stats history - daily snapshots of per-account engagement totals
+// 账号数据历史 - 按天记录每个账号的互动数据快照，用于计算周期增长
+//
+// 快照为累计值（该账号所有已发布作品的评论/点赞/收藏/分享之和），
+// 某个周期的增长 = 周期结束时的快照 - 周期开始前最后一次快照。
+// 刷新账号数据时还会写入平台返回的粉丝数、获赞数、作品数（未刷新过的快照为 0）。
+
+use super::DatabaseManager;
+use crate::core::AccountProfileStats;
+use serde::{Deserialize, Serialize};
+
+/// 统计的互动指标
+pub(super) const METRICS: [&str; 7] = ["works", "comments", "likes", "favorites", "shares", "followers", "total_likes"];
+
+/// 快照查询字段（与 map_snapshot 对应）
+pub(super) const SNAPSHOT_COLUMNS: &str = "account_id, snapshot_date, works, comments, likes, favorites, shares, followers, following, total_likes, video_count";
+
+/// 账号数据快照（累计值）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountStatsSnapshot {
+    pub account_id: String,
+    /// 快照日期（YYYY-MM-DD）
+    pub snapshot_date: String,
+    /// 已发布作品数
+    pub works: i64,
+    pub comments: i64,
+    pub likes: i64,
+    pub favorites: i64,
+    pub shares: i64,
+    /// 以下为平台返回的账号主页数据
+    #[serde(default)]
+    pub followers: i64,
+    #[serde(default)]
+    pub following: i64,
+    /// 账号累计获赞数
+    #[serde(default)]
+    pub total_likes: i64,
+    /// 平台上的作品数
+    #[serde(default)]
+    pub video_count: i64,
+}
+
+pub(super) fn map_snapshot(row: &rusqlite::Row) -> rusqlite::Result<AccountStatsSnapshot> {
+    Ok(AccountStatsSnapshot {
+        account_id: row.get(0)?,
+        snapshot_date: row.get(1)?,
+        works: row.get(2)?,
+        comments: row.get(3)?,
+        likes: row.get(4)?,
+        favorites: row.get(5)?,
+        shares: row.get(6)?,
+        followers: row.get::<_, Option<i64>>(7)?.unwrap_or(0),
+        following: row.get::<_, Option<i64>>(8)?.unwrap_or(0),
+        total_likes: row.get::<_, Option<i64>>(9)?.unwrap_or(0),
+        video_count: row.get::<_, Option<i64>>(10)?.unwrap_or(0),
+    })
+}
+
+impl AccountStatsSnapshot {
+    pub(super) fn metric(&self, name: &str) -> i64 {
+        match name {
+            "works" => self.works,
+            "comments" => self.comments,
+            "likes" => self.likes,
+            "favorites" => self.favorites,
+            "shares" => self.shares,
+            "followers" => self.followers,
+            "total_likes" => self.total_likes,
+            _ => 0,
+        }
+    }
+}
+
+/// 统计周期（闭区间，日期格式 YYYY-MM-DD）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsPeriod {
+    pub start: String,
+    pub end: String,
+}
+
+/// 单个指标在两个周期的增长对比
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricDelta {
+    pub metric: &'static str,
+    /// 周期A内的增长
+    pub period_a: i64,
+    /// 周期B内的增长
+    pub period_b: i64,
+    /// B - A
+    pub change: i64,
+    /// (B - A) / A，A 为 0 时为空
+    pub change_rate: Option<f64>,
+}
+
+/// 账号两个周期的数据对比
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountStatsDelta {
+    pub account_id: String,
+    pub period_a: StatsPeriod,
+    pub period_b: StatsPeriod,
+    pub metrics: Vec<MetricDelta>,
+}
+
+impl DatabaseManager {
+    /// Record today's stats snapshot for every account (one row per account per day)
+    /// 为所有账号记录当天的数据快照（同一天重复调用会覆盖）
+    pub fn record_account_stats_snapshots(&self) -> Result<usize, rusqlite::Error> {
+        self.write("record_account_stats_snapshots", |conn| {
+            let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+            let rows = conn.execute(r#"
+                INSERT INTO account_stats_history (account_id, snapshot_date, works, comments, likes, favorites, shares)
+                SELECT a.id, ?1,
+                       COUNT(p.id),
+                       COALESCE(SUM(p.comments), 0),
+                       COALESCE(SUM(p.likes), 0),
+                       COALESCE(SUM(p.favorites), 0),
+                       COALESCE(SUM(p.shares), 0)
+                FROM accounts a
+                LEFT JOIN publication_accounts p ON p.account_id = a.id AND p.status = 'Completed'
+                WHERE a.deleted_at = ''
+                GROUP BY a.id
+                ON CONFLICT(account_id, snapshot_date) DO UPDATE SET
+                    works = excluded.works,
+                    comments = excluded.comments,
+                    likes = excluded.likes,
+                    favorites = excluded.favorites,
+                    shares = excluded.shares
+            "#, [&today])?;
+
+            Ok(rows)
+        })
+    }
+
+    /// Record the platform-reported profile stats into today's snapshot of an account
+    /// 把平台返回的粉丝数、获赞数、作品数写入账号当天的快照（需先记录当天的互动快照）
+    pub fn record_account_profile_stats(&self, account_id: &str, stats: &AccountProfileStats) -> Result<(), rusqlite::Error> {
+        self.write("record_account_profile_stats", |conn| {
+            let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+            conn.execute(r#"
+                INSERT INTO account_stats_history (account_id, snapshot_date, followers, following, total_likes, video_count)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                ON CONFLICT(account_id, snapshot_date) DO UPDATE SET
+                    followers = excluded.followers,
+                    following = excluded.following,
+                    total_likes = excluded.total_likes,
+                    video_count = excluded.video_count
+            "#, rusqlite::params![account_id, today, stats.followers, stats.following, stats.total_likes, stats.video_count])?;
+
+            Ok(())
+        })
+    }
+
+    /// Get an account's snapshots within the last `days` days, oldest first (for trend charts)
+    /// 获取账号最近 `days` 天的数据快照（按日期升序，用于趋势图）
+    pub fn get_account_stats_history(&self, account_id: &str, days: u32) -> Result<Vec<AccountStatsSnapshot>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+        let since = (chrono::Local::now() - chrono::Duration::days(days as i64)).format("%Y-%m-%d").to_string();
+
+        let mut stmt = conn.prepare(&format!(r#"
+            SELECT {}
+            FROM account_stats_history
+            WHERE account_id = ? AND snapshot_date > ?
+            ORDER BY snapshot_date
+        "#, SNAPSHOT_COLUMNS))?;
+        let snapshots = stmt.query_map([account_id, since.as_str()], map_snapshot)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(snapshots)
+    }
+
+    /// Get the latest snapshot on or before the given date
+    /// 获取指定日期（含）之前最近的一次快照
+    pub fn get_account_stats_snapshot_at(&self, account_id: &str, date: &str) -> Result<Option<AccountStatsSnapshot>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!(r#"
+            SELECT {}
+            FROM account_stats_history
+            WHERE account_id = ? AND snapshot_date <= ?
+            ORDER BY snapshot_date DESC
+            LIMIT 1
+        "#, SNAPSHOT_COLUMNS))?;
+
+        match stmt.query_row([account_id, date], map_snapshot) {
+            Ok(snapshot) => Ok(Some(snapshot)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Compare an account's engagement growth between two periods
+    /// 对比账号在两个周期内的互动增长
+    pub fn get_account_stats_delta(&self, account_id: &str, period_a: &StatsPeriod, period_b: &StatsPeriod) -> Result<AccountStatsDelta, String> {
+        let growth_a = self.period_growth(account_id, period_a)?;
+        let growth_b = self.period_growth(account_id, period_b)?;
+
+        Ok(AccountStatsDelta {
+            account_id: account_id.to_string(),
+            period_a: period_a.clone(),
+            period_b: period_b.clone(),
+            metrics: compute_stats_delta(&growth_a, &growth_b),
+        })
+    }
+
+    /// 周期内的增长（结束快照 - 开始前快照）
+    fn period_growth(&self, account_id: &str, period: &StatsPeriod) -> Result<AccountStatsSnapshot, String> {
+        let start = chrono::NaiveDate::parse_from_str(&period.start, "%Y-%m-%d")
+            .map_err(|_| format!("无效的开始日期: {}", period.start))?;
+        let end = chrono::NaiveDate::parse_from_str(&period.end, "%Y-%m-%d")
+            .map_err(|_| format!("无效的结束日期: {}", period.end))?;
+        if start > end {
+            return Err(format!("开始日期晚于结束日期: {} > {}", period.start, period.end));
+        }
+
+        let before_start = start.pred_opt().unwrap_or(start).format("%Y-%m-%d").to_string();
+        let baseline = self.get_account_stats_snapshot_at(account_id, &before_start)
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default();
+        let closing = self.get_account_stats_snapshot_at(account_id, &period.end)
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default();
+
+        Ok(snapshot_growth(&baseline, &closing))
+    }
+}
+
+/// 两次快照之间的增长
+fn snapshot_growth(baseline: &AccountStatsSnapshot, closing: &AccountStatsSnapshot) -> AccountStatsSnapshot {
+    AccountStatsSnapshot {
+        account_id: closing.account_id.clone(),
+        snapshot_date: closing.snapshot_date.clone(),
+        works: closing.works - baseline.works,
+        comments: closing.comments - baseline.comments,
+        likes: closing.likes - baseline.likes,
+        favorites: closing.favorites - baseline.favorites,
+        shares: closing.shares - baseline.shares,
+        followers: closing.followers - baseline.followers,
+        following: closing.following - baseline.following,
+        total_likes: closing.total_likes - baseline.total_likes,
+        video_count: closing.video_count - baseline.video_count,
+    }
+}
+
+/// 按指标计算两个周期增长的差值和变化率
+fn compute_stats_delta(growth_a: &AccountStatsSnapshot, growth_b: &AccountStatsSnapshot) -> Vec<MetricDelta> {
+    METRICS.iter().map(|&metric| {
+        let a = growth_a.metric(metric);
+        let b = growth_b.metric(metric);
+        MetricDelta {
+            metric,
+            period_a: a,
+            period_b: b,
+            change: b - a,
+            change_rate: if a == 0 { None } else { Some((b - a) as f64 / a as f64) },
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(likes: i64, comments: i64) -> AccountStatsSnapshot {
+        AccountStatsSnapshot { likes, comments, ..Default::default() }
+    }
+
+    #[test]
+    fn test_snapshot_growth() {
+        let growth = snapshot_growth(&snapshot(100, 10), &snapshot(150, 12));
+        assert_eq!(growth.likes, 50);
+        assert_eq!(growth.comments, 2);
+    }
+
+    #[test]
+    fn test_compute_stats_delta() {
+        let metrics = compute_stats_delta(&snapshot(50, 0), &snapshot(75, 4));
+
+        let likes = metrics.iter().find(|m| m.metric == "likes").unwrap();
+        assert_eq!(likes.change, 25);
+        assert_eq!(likes.change_rate, Some(0.5));
+
+        // 周期A为 0 时不计算变化率
+        let comments = metrics.iter().find(|m| m.metric == "comments").unwrap();
+        assert_eq!(comments.change, 4);
+        assert_eq!(comments.change_rate, None);
+    }
+
+    #[test]
+    fn test_snapshot_growth_includes_profile_stats() {
+        let baseline = AccountStatsSnapshot { followers: 1200, total_likes: 8000, ..Default::default() };
+        let closing = AccountStatsSnapshot { followers: 1350, total_likes: 9100, ..Default::default() };
+        let growth = snapshot_growth(&baseline, &closing);
+        assert_eq!(growth.followers, 150);
+        assert_eq!(growth.metric("total_likes"), 1100);
+    }
+}
@@ -0,0 +1,141 @@
+// Publication task list - paginated and filtered task queries
+// 发布任务列表 - 分页、筛选在 SQL 中完成，只加载当前页任务的账号明细，
+// 避免任务很多时一次性读出所有任务和所有账号再在内存中分组
+
+use rusqlite::types::Value as SqlValue;
+use super::DatabaseManager;
+use crate::core::{PlatformType, PublicationStatus, PublicationTaskWithAccounts};
+
+/// Filter of the publication task list
+/// 任务列表筛选条件，未设置的条件不参与筛选
+#[derive(Debug, Clone, Default)]
+pub struct PublicationTaskFilter {
+    /// 任务状态
+    pub status: Option<PublicationStatus>,
+    /// 包含该平台账号的任务
+    pub platform: Option<PlatformType>,
+    /// 标题、描述或账号名称包含的关键字
+    pub search: Option<String>,
+}
+
+/// 转义 LIKE 通配符，关键字按字面匹配（配合 `ESCAPE '\'` 使用）
+pub(super) fn like_pattern(keyword: &str) -> String {
+    let escaped = keyword
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+impl PublicationTaskFilter {
+    /// 生成 WHERE 子句和绑定参数；任务表别名为 t
+    fn where_clause(&self) -> (String, Vec<SqlValue>) {
+        // 回收站中的任务不出现在列表中
+        let mut conditions = vec!["t.deleted_at = ''".to_string()];
+        let mut params = Vec::new();
+
+        // 状态以 Debug 格式存储，历史数据存在小写值
+        if let Some(status) = &self.status {
+            conditions.push("t.status = ? COLLATE NOCASE".to_string());
+            params.push(SqlValue::Text(format!("{:?}", status)));
+        }
+        if let Some(platform) = &self.platform {
+            conditions.push(
+                "EXISTS (SELECT 1 FROM publication_accounts a WHERE a.publication_task_id = t.id AND a.platform = ? COLLATE NOCASE)"
+                    .to_string(),
+            );
+            params.push(SqlValue::Text(platform.as_str().to_string()));
+        }
+        if let Some(keyword) = self.search.as_deref().map(str::trim).filter(|k| !k.is_empty()) {
+            conditions.push(
+                "(t.title LIKE ? ESCAPE '\\' OR t.description LIKE ? ESCAPE '\\' \
+                 OR EXISTS (SELECT 1 FROM publication_accounts a WHERE a.publication_task_id = t.id AND a.account_name LIKE ? ESCAPE '\\'))"
+                    .to_string(),
+            );
+            params.extend(vec![SqlValue::Text(like_pattern(keyword)); 3]);
+        }
+
+        (format!(" WHERE {}", conditions.join(" AND ")), params)
+    }
+}
+
+impl DatabaseManager {
+    /// Get one page of publication tasks matching the filter, newest first
+    /// 分页获取符合条件的发布任务（按创建时间倒序），只加载当前页任务的账号明细
+    pub fn get_publication_tasks_paginated(
+        &self,
+        filter: &PublicationTaskFilter,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<PublicationTaskWithAccounts>, rusqlite::Error> {
+        let task_ids: Vec<String> = {
+            let conn = self.get_connection()?;
+            let (where_clause, mut params) = filter.where_clause();
+            params.push(SqlValue::Integer(limit));
+            params.push(SqlValue::Integer(offset));
+
+            let sql = format!(
+                "SELECT t.id FROM publication_tasks t{} ORDER BY t.created_at DESC LIMIT ? OFFSET ?",
+                where_clause
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            let ids = stmt.query_map(rusqlite::params_from_iter(params), |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            ids
+        };
+
+        let mut tasks = Vec::with_capacity(task_ids.len());
+        for task_id in &task_ids {
+            if let Some(task) = self.get_publication_task_with_accounts(task_id)? {
+                tasks.push(task);
+            }
+        }
+        Ok(tasks)
+    }
+
+    /// Count the publication tasks matching the filter
+    /// 统计符合条件的发布任务数
+    pub fn count_publication_tasks(&self, filter: &PublicationTaskFilter) -> Result<i64, rusqlite::Error> {
+        let conn = self.get_connection()?;
+        let (where_clause, params) = filter.where_clause();
+
+        conn.query_row(
+            &format!("SELECT COUNT(*) FROM publication_tasks t{}", where_clause),
+            rusqlite::params_from_iter(params),
+            |row| row.get(0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("新品"), "%新品%");
+        assert_eq!(like_pattern("100%_off"), "%100\\%\\_off%");
+    }
+
+    #[test]
+    fn test_where_clause() {
+        let (clause, params) = PublicationTaskFilter::default().where_clause();
+        assert_eq!(clause, " WHERE t.deleted_at = ''");
+        assert!(params.is_empty());
+
+        let filter = PublicationTaskFilter {
+            status: Some(PublicationStatus::Failed),
+            platform: Some(PlatformType::Douyin),
+            search: Some("  新品 ".to_string()),
+        };
+        let (clause, params) = filter.where_clause();
+        assert!(clause.starts_with(" WHERE t.deleted_at = '' AND t.status = ? COLLATE NOCASE AND EXISTS"));
+        assert_eq!(clause.matches('?').count(), params.len());
+        assert_eq!(params[0], SqlValue::Text("Failed".to_string()));
+        assert_eq!(params[1], SqlValue::Text("douyin".to_string()));
+        assert_eq!(params[2], SqlValue::Text("%新品%".to_string()));
+
+        let blank = PublicationTaskFilter { search: Some("  ".to_string()), ..Default::default() };
+        assert_eq!(blank.where_clause().0, " WHERE t.deleted_at = ''");
+    }
+}
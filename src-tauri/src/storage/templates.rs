@@ -0,0 +1,97 @@
+// Publication templates - reusable title/description/hashtags/account selection
+// 发布模板存储 - 话题和目标账号以 JSON 保存，按模板生成任务时只需提供视频
+
+use super::DatabaseManager;
+use crate::core::PublicationTemplate;
+
+const TEMPLATE_COLUMNS: &str = "id, name, title, description, hashtags, hashtag_set_id, accounts, created_at, updated_at";
+
+fn map_template(row: &rusqlite::Row) -> rusqlite::Result<PublicationTemplate> {
+    let hashtag_set_id: String = row.get(5)?;
+    Ok(PublicationTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        title: row.get(2)?,
+        description: row.get(3)?,
+        hashtags: serde_json::from_str(&row.get::<_, String>(4)?).unwrap_or_default(),
+        hashtag_set_id: if hashtag_set_id.is_empty() { None } else { Some(hashtag_set_id) },
+        accounts: serde_json::from_str(&row.get::<_, String>(6)?).unwrap_or_default(),
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+impl DatabaseManager {
+    /// Save (create or update) a publication template
+    /// 保存发布模板（新建或更新）
+    pub fn save_publication_template(&self, template: &PublicationTemplate) -> Result<(), rusqlite::Error> {
+        self.write("save_publication_template", |conn| {
+            conn.execute(r#"
+                INSERT INTO publication_templates (
+                    id, name, title, description, hashtags, hashtag_set_id, accounts, created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO UPDATE SET
+                    name = excluded.name,
+                    title = excluded.title,
+                    description = excluded.description,
+                    hashtags = excluded.hashtags,
+                    hashtag_set_id = excluded.hashtag_set_id,
+                    accounts = excluded.accounts,
+                    updated_at = excluded.updated_at
+            "#, [
+                &template.id,
+                &template.name,
+                &template.title,
+                &template.description,
+                &serde_json::to_string(&template.hashtags).unwrap_or("[]".to_string()),
+                template.hashtag_set_id.as_deref().unwrap_or(""),
+                &serde_json::to_string(&template.accounts).unwrap_or("[]".to_string()),
+                &template.created_at,
+                &template.updated_at,
+            ])?;
+
+            Ok(())
+        })
+    }
+
+    /// Get a publication template by ID
+    /// 根据ID获取发布模板
+    pub fn get_publication_template(&self, template_id: &str) -> Result<Option<PublicationTemplate>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM publication_templates WHERE id = ?", TEMPLATE_COLUMNS
+        ))?;
+
+        match stmt.query_row([template_id], map_template) {
+            Ok(template) => Ok(Some(template)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get all publication templates
+    /// 获取所有发布模板
+    pub fn get_publication_templates(&self) -> Result<Vec<PublicationTemplate>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM publication_templates ORDER BY name", TEMPLATE_COLUMNS
+        ))?;
+        let templates = stmt.query_map([], map_template)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(templates)
+    }
+
+    /// Delete a publication template (tasks created from it are kept)
+    /// 删除发布模板（已生成的任务不受影响）
+    pub fn delete_publication_template(&self, template_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("delete_publication_template", |conn| {
+            let rows = conn.execute("DELETE FROM publication_templates WHERE id = ?", [template_id])?;
+
+            Ok(rows > 0)
+        })
+    }
+}
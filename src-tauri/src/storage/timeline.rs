@@ -0,0 +1,203 @@
+// Account timeline - per-account activity feed assembled from existing tables
+// 账号动态时间线 - 由发布记录、授权时间和数据快照拼出账号的活动流
+//
+// 不单独存储事件：
+// - 发布：publication_accounts 的创建时间
+// - 发布结果（状态变化）：publication_accounts 的 published_at + status
+// - 授权刷新：accounts.created_at（重新授权时会更新）
+// - 数据里程碑：相邻两次 account_stats_history 快照之间跨过 10/100/1000... 的指标
+
+use super::stats::{AccountStatsSnapshot, METRICS, SNAPSHOT_COLUMNS, map_snapshot};
+use super::DatabaseManager;
+use serde::Serialize;
+
+/// 未指定条数时的默认值
+pub const DEFAULT_TIMELINE_LIMIT: usize = 50;
+
+/// 数据里程碑阈值
+const MILESTONES: [i64; 6] = [10, 100, 1_000, 10_000, 100_000, 1_000_000];
+
+/// 时间线事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventKind {
+    /// 创建发布
+    Publish,
+    /// 发布完成 / 失败 / 跳过
+    PublishResult,
+    /// 授权刷新
+    AuthRefresh,
+    /// 数据里程碑
+    StatsMilestone,
+}
+
+/// 时间线事件
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountTimelineEvent {
+    pub kind: TimelineEventKind,
+    /// 发生时间（YYYY-MM-DD HH:MM:SS，里程碑为快照当天 00:00:00）
+    pub occurred_at: String,
+    pub title: String,
+    pub detail: Option<String>,
+    /// 关联记录ID（发布明细ID等）
+    pub reference_id: Option<String>,
+}
+
+impl DatabaseManager {
+    /// Get an account's activity feed, newest first
+    /// 获取账号动态时间线（按时间倒序）
+    pub fn get_account_timeline(&self, account_id: &str, limit: usize) -> Result<Vec<AccountTimelineEvent>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+        let mut events = Vec::new();
+
+        // 授权刷新
+        let mut stmt = conn.prepare("SELECT created_at, status FROM accounts WHERE id = ? AND deleted_at = ''")?;
+        let auth_rows = stmt.query_map([account_id], |row| {
+            Ok((row.get::<_, Option<String>>(0)?.unwrap_or_default(), row.get::<_, String>(1)?))
+        })?;
+        for (created_at, status) in auth_rows.filter_map(|r| r.ok()) {
+            if created_at.is_empty() {
+                continue;
+            }
+            events.push(AccountTimelineEvent {
+                kind: TimelineEventKind::AuthRefresh,
+                occurred_at: created_at,
+                title: "账号授权".to_string(),
+                detail: Some(format!("账号状态: {}", status)),
+                reference_id: None,
+            });
+        }
+
+        // 发布与发布结果
+        let mut stmt = conn.prepare(r#"
+            SELECT pa.id, pa.created_at, pa.published_at, pa.status, pa.message, pa.publish_url, pt.title
+            FROM publication_accounts pa
+            LEFT JOIN publication_tasks pt ON pt.id = pa.publication_task_id
+            WHERE pa.account_id = ? AND COALESCE(pt.deleted_at, '') = ''
+        "#)?;
+        let publish_rows = stmt.query_map([account_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+                row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+                row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+            ))
+        })?;
+        for (detail_id, created_at, published_at, status, message, publish_url, task_title) in publish_rows.filter_map(|r| r.ok()) {
+            if !created_at.is_empty() {
+                events.push(AccountTimelineEvent {
+                    kind: TimelineEventKind::Publish,
+                    occurred_at: created_at,
+                    title: format!("创建发布: {}", task_title),
+                    detail: None,
+                    reference_id: Some(detail_id.clone()),
+                });
+            }
+
+            let finished = matches!(status.as_str(), "Completed" | "Failed" | "Skipped" | "Removed");
+            if finished && !published_at.is_empty() {
+                let detail = [message, publish_url].into_iter().find(|s| !s.is_empty());
+                events.push(AccountTimelineEvent {
+                    kind: TimelineEventKind::PublishResult,
+                    occurred_at: published_at,
+                    title: format!("发布{}: {}", publish_status_label(&status), task_title),
+                    detail,
+                    reference_id: Some(detail_id),
+                });
+            }
+        }
+
+        // 数据里程碑
+        let mut stmt = conn.prepare(&format!(r#"
+            SELECT {}
+            FROM account_stats_history
+            WHERE account_id = ?
+            ORDER BY snapshot_date
+        "#, SNAPSHOT_COLUMNS))?;
+        let snapshots: Vec<AccountStatsSnapshot> = stmt.query_map([account_id], map_snapshot)?
+            .filter_map(|r| r.ok())
+            .collect();
+        events.extend(stats_milestones(&snapshots));
+
+        events.sort_by(|a, b| b.occurred_at.cmp(&a.occurred_at));
+        events.truncate(limit);
+        Ok(events)
+    }
+}
+
+fn publish_status_label(status: &str) -> &'static str {
+    match status {
+        "Completed" => "成功",
+        "Failed" => "失败",
+        "Skipped" => "跳过",
+        "Removed" => "已删除",
+        _ => "进行中",
+    }
+}
+
+fn metric_label(metric: &str) -> &'static str {
+    match metric {
+        "works" => "作品数",
+        "comments" => "评论数",
+        "likes" => "点赞数",
+        "favorites" => "收藏数",
+        "shares" => "分享数",
+        "followers" => "粉丝数",
+        "total_likes" => "获赞数",
+        _ => "数据",
+    }
+}
+
+/// 相邻快照之间跨过里程碑阈值的指标（快照需按日期升序）
+fn stats_milestones(snapshots: &[AccountStatsSnapshot]) -> Vec<AccountTimelineEvent> {
+    let mut events = Vec::new();
+
+    for pair in snapshots.windows(2) {
+        let (prev, cur) = (&pair[0], &pair[1]);
+        for metric in METRICS {
+            let (before, after) = (prev.metric(metric), cur.metric(metric));
+            for milestone in MILESTONES.iter().filter(|&&m| before < m && after >= m) {
+                events.push(AccountTimelineEvent {
+                    kind: TimelineEventKind::StatsMilestone,
+                    occurred_at: format!("{} 00:00:00", cur.snapshot_date),
+                    title: format!("{}突破 {}", metric_label(metric), milestone),
+                    detail: Some(format!("{} → {}", before, after)),
+                    reference_id: None,
+                });
+            }
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(date: &str, likes: i64) -> AccountStatsSnapshot {
+        AccountStatsSnapshot { snapshot_date: date.to_string(), likes, ..Default::default() }
+    }
+
+    #[test]
+    fn test_stats_milestones_crossed_between_snapshots() {
+        let events = stats_milestones(&[
+            snapshot("2024-01-01", 5),
+            snapshot("2024-01-02", 150),
+            snapshot("2024-01-03", 180),
+        ]);
+
+        // 5 → 150 跨过 10 和 100；150 → 180 没有新里程碑
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| e.occurred_at == "2024-01-02 00:00:00"));
+        assert_eq!(events[1].title, "点赞数突破 100");
+    }
+
+    #[test]
+    fn test_stats_milestones_ignore_single_snapshot() {
+        assert!(stats_milestones(&[snapshot("2024-01-01", 5_000)]).is_empty());
+    }
+}
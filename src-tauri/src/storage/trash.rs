@@ -0,0 +1,157 @@
+// Trash - soft delete and restore of accounts and publication tasks
+// 回收站 - 删除账号和发布任务时只写入 deleted_at，数据（含账号凭证、发布明细、备注）原样保留，
+// 可随时恢复；清空回收站时才彻底删除。查询账号 / 任务的方法都会排除回收站中的数据
+
+use serde::Serialize;
+use super::DatabaseManager;
+
+/// 清空回收站时默认保留最近多少天内删除的数据
+pub const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Kind of a trashed item
+/// 回收站条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashKind {
+    Account,
+    Task,
+}
+
+/// A deleted account or task waiting in the trash
+/// 回收站中的账号或任务
+#[derive(Debug, Clone, Serialize)]
+pub struct TrashItem {
+    pub kind: TrashKind,
+    pub id: String,
+    pub name: String,        // 账号昵称 / 任务标题
+    pub platform: String,    // 账号平台，任务为空
+    pub deleted_at: String,
+}
+
+/// Result of emptying the trash
+/// 清空回收站的结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TrashPurgeResult {
+    /// 彻底删除的账号ID（调用方据此清理浏览器资料目录和头像缓存）
+    pub account_ids: Vec<String>,
+    pub tasks: usize,
+}
+
+fn now() -> String {
+    chrono::Local::now().format(TIME_FORMAT).to_string()
+}
+
+impl DatabaseManager {
+    /// Move an account to the trash
+    /// 删除账号（移入回收站，凭证保留可恢复）
+    pub fn delete_account(&self, account_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("delete_account", |conn| {
+            Ok(conn.execute(
+                "UPDATE accounts SET deleted_at = ? WHERE id = ? AND deleted_at = ''",
+                [now().as_str(), account_id],
+            )? > 0)
+        })
+    }
+
+    /// Move a publication task to the trash
+    /// 删除发布任务（移入回收站）；任务的定时发布一并取消，恢复后需重新设置
+    pub fn delete_publication_task(&self, task_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("delete_publication_task", |conn| {
+            let tx = conn.transaction()?;
+            let rows = tx.execute(
+                "UPDATE publication_tasks SET deleted_at = ? WHERE id = ? AND deleted_at = ''",
+                [now().as_str(), task_id],
+            )?;
+            if rows > 0 {
+                tx.execute("DELETE FROM scheduled_jobs WHERE task_id = ? AND status = 'pending'", [task_id])?;
+            }
+            tx.commit()?;
+            Ok(rows > 0)
+        })
+    }
+
+    /// Restore an account from the trash
+    /// 从回收站恢复账号
+    pub fn restore_account(&self, account_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("restore_account", |conn| {
+            Ok(conn.execute(
+                "UPDATE accounts SET deleted_at = '' WHERE id = ? AND deleted_at != ''",
+                [account_id],
+            )? > 0)
+        })
+    }
+
+    /// Restore a publication task from the trash
+    /// 从回收站恢复发布任务
+    pub fn restore_publication_task(&self, task_id: &str) -> Result<bool, rusqlite::Error> {
+        self.write("restore_publication_task", |conn| {
+            Ok(conn.execute(
+                "UPDATE publication_tasks SET deleted_at = '' WHERE id = ? AND deleted_at != ''",
+                [task_id],
+            )? > 0)
+        })
+    }
+
+    /// List the accounts and tasks in the trash, most recently deleted first
+    /// 获取回收站中的账号和任务（最近删除的在前）
+    pub fn get_trash(&self) -> Result<Vec<TrashItem>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(r#"
+            SELECT 'account', id, nickname, platform, deleted_at FROM accounts WHERE deleted_at != ''
+            UNION ALL
+            SELECT 'task', id, title, '', deleted_at FROM publication_tasks WHERE deleted_at != ''
+            ORDER BY 5 DESC
+        "#)?;
+        let items = stmt.query_map([], |row| {
+            let kind = if row.get::<_, String>(0)? == "account" { TrashKind::Account } else { TrashKind::Task };
+            Ok(TrashItem {
+                kind,
+                id: row.get(1)?,
+                name: row.get(2)?,
+                platform: row.get::<_, String>(3)?.to_lowercase(),
+                deleted_at: row.get(4)?,
+            })
+        })?.filter_map(|r| r.ok()).collect();
+
+        Ok(items)
+    }
+
+    /// Permanently delete trashed items deleted more than `older_than_days` days ago (0 empties the trash)
+    /// 彻底删除回收站中超过指定天数的账号和任务（0 表示清空回收站）
+    pub fn purge_trash(&self, older_than_days: i64) -> Result<TrashPurgeResult, rusqlite::Error> {
+        let cutoff = (chrono::Local::now() - chrono::Duration::days(older_than_days.max(0)))
+            .format(TIME_FORMAT)
+            .to_string();
+
+        let (account_ids, task_ids) = {
+            let conn = self.get_connection()?;
+            let select_ids = |table: &str| -> Result<Vec<String>, rusqlite::Error> {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT id FROM {} WHERE deleted_at != '' AND deleted_at <= ?", table
+                ))?;
+                let ids = stmt.query_map([&cutoff], |row| row.get(0))?
+                    .collect::<Result<Vec<String>, _>>()?;
+                Ok(ids)
+            };
+            (select_ids("accounts")?, select_ids("publication_tasks")?)
+        };
+
+        let mut result = TrashPurgeResult::default();
+        for task_id in &task_ids {
+            if self.purge_publication_task(task_id)? {
+                result.tasks += 1;
+            }
+        }
+        for account_id in account_ids {
+            if self.purge_account(&account_id)? {
+                result.account_ids.push(account_id);
+            }
+        }
+
+        tracing::info!("[Trash] 已清理回收站: {} 个账号, {} 个任务", result.account_ids.len(), result.tasks);
+        Ok(result)
+    }
+}
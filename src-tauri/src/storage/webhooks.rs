@@ -0,0 +1,92 @@
+// Webhooks - publish callback configuration and delivery log
+// 发布回调 - 回调配置保存在 app_settings，每条通知投递到每个地址的最终结果记录在 webhook_deliveries，
+// 便于排查对方服务没有收到通知的原因
+
+use super::DatabaseManager;
+use crate::core::{WebhookConfig, WebhookDelivery};
+
+const DELIVERY_COLUMNS: &str = "id, url, event, task_id, detail_id, payload, success, attempts, response_code, error, created_at";
+
+fn map_delivery(row: &rusqlite::Row) -> rusqlite::Result<WebhookDelivery> {
+    let error: String = row.get(9)?;
+    Ok(WebhookDelivery {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        event: row.get(2)?,
+        task_id: row.get(3)?,
+        detail_id: row.get(4)?,
+        payload: row.get(5)?,
+        success: row.get::<_, i64>(6)? != 0,
+        attempts: row.get(7)?,
+        response_code: row.get(8)?,
+        error: if error.is_empty() { None } else { Some(error) },
+        created_at: row.get(10)?,
+    })
+}
+
+impl DatabaseManager {
+    /// Get the webhook configuration
+    /// 获取发布回调配置
+    pub fn get_webhook_config(&self) -> Result<WebhookConfig, rusqlite::Error> {
+        Ok(self.get_setting("webhook.config")?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    /// Save the webhook configuration
+    /// 保存发布回调配置
+    pub fn set_webhook_config(&self, config: &WebhookConfig) -> Result<(), rusqlite::Error> {
+        let value = serde_json::to_string(config).unwrap_or_default();
+        self.set_setting("webhook.config", &value)
+    }
+
+    /// Record the result of delivering a notification to one URL
+    /// 记录一条通知投递到一个地址的结果
+    pub fn save_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), rusqlite::Error> {
+        self.write("save_webhook_delivery", |conn| {
+            conn.execute(&format!(
+                "INSERT INTO webhook_deliveries ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)", DELIVERY_COLUMNS
+            ), rusqlite::params![
+                delivery.id,
+                delivery.url,
+                delivery.event,
+                delivery.task_id,
+                delivery.detail_id,
+                delivery.payload,
+                delivery.success as i64,
+                delivery.attempts,
+                delivery.response_code,
+                delivery.error.as_deref().unwrap_or(""),
+                delivery.created_at,
+            ])?;
+            Ok(())
+        })
+    }
+
+    /// Get one page of webhook deliveries, newest first (optionally only failed ones)
+    /// 分页获取回调投递记录（按时间倒序，可只看失败的记录）
+    pub fn get_webhook_deliveries_paginated(&self, failed_only: bool, offset: i64, limit: i64) -> Result<Vec<WebhookDelivery>, rusqlite::Error> {
+        let conn = self.get_connection()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM webhook_deliveries WHERE (? = 0 OR success = 0) ORDER BY created_at DESC, rowid DESC LIMIT ? OFFSET ?",
+            DELIVERY_COLUMNS
+        ))?;
+        let deliveries = stmt.query_map(rusqlite::params![failed_only as i64, limit, offset], map_delivery)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(deliveries)
+    }
+
+    /// Count webhook deliveries (optionally only failed ones)
+    /// 获取回调投递记录数量
+    pub fn get_webhook_delivery_count(&self, failed_only: bool) -> Result<i64, rusqlite::Error> {
+        let conn = self.get_connection()?;
+        conn.query_row(
+            "SELECT COUNT(*) FROM webhook_deliveries WHERE (? = 0 OR success = 0)",
+            [failed_only as i64],
+            |row| row.get(0),
+        )
+    }
+}
@@ -34,6 +34,8 @@ export function useBrowserAuth() {
 
   const isAuthenticating = ref(false)
   const currentPlatform = ref('')
+  // 当前授权会话ID（每个 useBrowserAuth 实例一个会话，可同时授权多个账号）
+  const sessionId = ref('')
 
   // Polling timer
   let pollTimer: number | null = null
@@ -65,6 +67,7 @@ export function useBrowserAuth() {
 
     try {
       const result = await startBrowserAuth(platform, accountId)
+      sessionId.value = result.session_id
       state.value.step = getAuthStep(result.step)
       state.value.message = result.message
       state.value.currentUrl = result.currentUrl || ''
@@ -100,7 +103,7 @@ export function useBrowserAuth() {
       }
 
       try {
-        const result = await checkBrowserAuthStatus()
+        const result = await checkBrowserAuthStatus(sessionId.value)
         state.value.step = getAuthStep(result.step)
         state.value.message = result.message
         state.value.currentUrl = result.currentUrl
@@ -136,7 +139,9 @@ export function useBrowserAuth() {
     isAuthenticating.value = false
 
     try {
-      await cancelBrowserAuth()
+      if (sessionId.value) {
+        await cancelBrowserAuth(sessionId.value)
+      }
     } catch (error) {
       console.error('Error canceling auth:', error)
     }
@@ -155,6 +160,7 @@ export function useBrowserAuth() {
     state,
     isAuthenticating,
     currentPlatform,
+    sessionId,
     startAuth,
     cancelAuth,
   }
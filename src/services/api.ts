@@ -2,6 +2,7 @@
 // 使用 Tauri invoke 调用后端命令
 
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 
 // Types matching the backend UserAccount
 export interface UserAccount {
@@ -13,10 +14,13 @@ export interface UserAccount {
   params: string
   status: AccountStatus
   created_at: string
+  on_hold: boolean  // 维护中（暂停发布）
+  group_id: string | null  // 所属分组
+  tags: string[]
 }
 
 export type PlatformType = 'Douyin' | 'Xiaohongshu' | 'Kuaishou' | 'Bilibili'
-export type AccountStatus = 'Active' | 'Expired' | 'Pending'
+export type AccountStatus = 'Active' | 'Expired' | 'Pending' | 'Risky'
 
 export interface PlatformInfo {
   id: string
@@ -25,6 +29,33 @@ export interface PlatformInfo {
   color: string
 }
 
+// 可见范围：0 公开，1 仅自己可见（可先发布私密作品测试），2 好友可见
+export type PublishVisibility = 0 | 1 | 2
+
+// 位置（POI），通过 searchPoi 搜索后设置到发布任务上
+export interface PoiInfo {
+  poi_id: string
+  poi_name: string
+  address: string
+  city: string
+}
+
+// 音乐搜索结果，通过 searchMusic 搜索后作为发布任务的配乐
+export interface MusicSearchItem {
+  music_id: string
+  title: string
+  author: string
+  duration: number  // 时长（秒）
+  cover_url: string
+  play_url: string  // 试听地址
+}
+
+// 作品配乐，music_end_time 为配乐截止时间（毫秒）
+export interface MusicInfo {
+  music_id: string
+  music_end_time: string
+}
+
 // Publication Task types (main + sub table structure)
 export interface PublicationTaskWithAccounts {
   id: string
@@ -36,6 +67,17 @@ export interface PublicationTaskWithAccounts {
   status: PublicationStatus
   createdAt: string
   publishedAt: string
+  hashtagSetId: string | null  // 关联的话题库
+  verticalVideoPath: string | null    // 竖版（9:16）视频，抖音/快手/小红书优先使用
+  horizontalVideoPath: string | null  // 横版（16:9）视频，B站优先使用
+  watermarkEnabled: boolean  // 发布前按账号所在分组的配置叠加水印
+  visibilityType: PublishVisibility
+  downloadAllowed: boolean  // 是否允许下载
+  poiId: string | null    // 定位（POI）ID
+  poiName: string | null
+  musicId: string | null  // 作品配乐
+  musicEndTime: string | null
+  mentions: string[]  // 发布时追加到描述的 @ 昵称（抖音解析为 @ 提及）
   accounts: PublicationAccountDetail[]
 }
 
@@ -45,7 +87,10 @@ export interface PublicationAccountDetail {
   accountId: string
   accountName: string  // 冗余的账号名称
   platform: PlatformType
-  // Note: title/description/hashtags are only in the main table now
+  // 账号级别覆盖，为空时使用主表的 title/description/hashtags
+  titleOverride?: string | null
+  descriptionOverride?: string | null
+  hashtagsOverride?: string[] | null
   status: PublicationStatus
   createdAt: string
   publishedAt: string | null
@@ -58,8 +103,30 @@ export interface PublicationAccountDetail {
   }
   message: string | null  // 发布失败原因
   itemId: string | null   // 发布成功的视频ID
+  strategyVersion: string | null  // 执行发布的策略模块版本
+  attempts: number  // 发布尝试次数（含临时性失败后的自动重试）
+  errorCode: PublishErrorCode | null  // 发布失败错误码
+  variant: VideoVariant | null  // 该账号上传的去重变体
 }
 
+// 为单个账号生成的去重视频变体
+export interface VideoVariant {
+  seed: number
+  trim_ms: number  // 裁掉开头的毫秒数
+  video_kbps: number
+  audio_kbps: number
+  md5: string
+}
+
+// 发布失败错误码，用于给出针对性的处理建议
+export type PublishErrorCode =
+  | 'cookie_expired'  // 凭证过期，需重新授权账号
+  | 'upload_failed'
+  | 'risk_control'
+  | 'rate_limited'
+  | 'network'
+  | 'unknown'
+
 export interface PublicationTaskDetail {
   id: string
   title: string
@@ -70,10 +137,20 @@ export interface PublicationTaskDetail {
   status: PublicationStatus
   createdAt: string
   publishedAt: string
+  hashtagSetId: string | null  // 关联的话题库
   accounts: PublicationAccountDetail[]
 }
 
-export type PublicationStatus = 'Draft' | 'Publishing' | 'Completed' | 'Failed'
+export type PublicationStatus = 'Draft' | 'Publishing' | 'Completed' | 'Failed' | 'Skipped' | 'Removed'
+
+/** Pagination envelope returned by all list commands */
+export interface Paginated<T> {
+  items: T[]
+  total: number
+  page: number
+  page_size: number
+  has_more: boolean
+}
 
 // Platform list (can be fetched from backend in the future)
 export const PLATFORMS: PlatformInfo[] = [
@@ -100,9 +177,13 @@ export async function getSupportedPlatforms(): Promise<PlatformInfo[]> {
 /**
  * Get accounts for a platform
  */
-export async function getAccounts(platform: string): Promise<UserAccount[]> {
+export async function getAccounts(
+  platform: string,
+  page: number | null = null,
+  pageSize: number | null = null
+): Promise<Paginated<UserAccount>> {
   try {
-    return await invoke<UserAccount[]>('get_accounts', { platform })
+    return await invoke<Paginated<UserAccount>>('get_accounts', { platform, page, pageSize })
   } catch (error) {
     console.error('Failed to get accounts:', error)
     throw error
@@ -112,9 +193,12 @@ export async function getAccounts(platform: string): Promise<UserAccount[]> {
 /**
  * Get all accounts across all platforms
  */
-export async function getAllAccounts(): Promise<UserAccount[]> {
+export async function getAllAccounts(
+  page: number | null = null,
+  pageSize: number | null = null
+): Promise<Paginated<UserAccount>> {
   try {
-    return await invoke<UserAccount[]>('get_all_accounts')
+    return await invoke<Paginated<UserAccount>>('get_all_accounts', { page, pageSize })
   } catch (error) {
     console.error('Failed to get all accounts:', error)
     throw error
@@ -158,16 +242,164 @@ export async function deleteAccount(accountId: string): Promise<boolean> {
 }
 
 /**
- * Get publications for an account
+ * Restore an account from the trash
  */
-export async function getPublications(
-  platform: string,
-  accountId: string
-): Promise<any[]> {
+export async function restoreAccount(accountId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('restore_account', { accountId })
+  } catch (error) {
+    console.error('Failed to restore account:', error)
+    throw error
+  }
+}
+
+// 回收站中的账号或任务
+export interface TrashItem {
+  kind: 'account' | 'task'
+  id: string
+  name: string       // 账号昵称 / 任务标题
+  platform: string   // 账号平台，任务为空
+  deleted_at: string
+}
+
+export interface TrashPurgeResult {
+  account_ids: string[]
+  tasks: number
+}
+
+/**
+ * List the accounts and tasks in the trash
+ */
+export async function getTrash(): Promise<TrashItem[]> {
+  try {
+    return await invoke<TrashItem[]>('get_trash')
+  } catch (error) {
+    console.error('Failed to get trash:', error)
+    throw error
+  }
+}
+
+/**
+ * Permanently delete trashed items older than the given days (default 30, 0 empties the trash)
+ */
+export async function purgeTrash(olderThanDays: number | null = null): Promise<TrashPurgeResult> {
+  try {
+    return await invoke<TrashPurgeResult>('purge_trash', { olderThanDays })
+  } catch (error) {
+    console.error('Failed to purge trash:', error)
+    throw error
+  }
+}
+
+/**
+ * Set or clear the maintenance (do-not-publish) flag of an account
+ */
+export async function setAccountOnHold(accountId: string, onHold: boolean): Promise<boolean> {
+  try {
+    return await invoke<boolean>('set_account_on_hold', { accountId, onHold })
+  } catch (error) {
+    console.error('Failed to set account on hold:', error)
+    throw error
+  }
+}
+
+export interface AccountGroup {
+  id: string
+  name: string
+  description: string
+  created_at: string
+}
+
+/**
+ * Create an account group
+ */
+export async function createAccountGroup(name: string, description: string | null = null): Promise<AccountGroup> {
+  try {
+    return await invoke<AccountGroup>('create_account_group', { name, description })
+  } catch (error) {
+    console.error('Failed to create account group:', error)
+    throw error
+  }
+}
+
+export async function getAccountGroups(): Promise<AccountGroup[]> {
+  try {
+    return await invoke<AccountGroup[]>('get_account_groups')
+  } catch (error) {
+    console.error('Failed to get account groups:', error)
+    throw error
+  }
+}
+
+export async function deleteAccountGroup(groupId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('delete_account_group', { groupId })
+  } catch (error) {
+    console.error('Failed to delete account group:', error)
+    throw error
+  }
+}
+
+/**
+ * Move an account into a group (null to ungroup)
+ */
+export async function assignAccountToGroup(accountId: string, groupId: string | null): Promise<boolean> {
+  try {
+    return await invoke<boolean>('assign_account_to_group', { accountId, groupId })
+  } catch (error) {
+    console.error('Failed to assign account to group:', error)
+    throw error
+  }
+}
+
+export type WatermarkContent =
+  | { kind: 'text'; text: string; font_size?: number; color?: string; font_path?: string | null }
+  | { kind: 'image'; path: string; scale?: number }  // scale: 水印宽度占视频宽度的比例
+
+export type WatermarkPosition = 'top_left' | 'top_right' | 'bottom_left' | 'bottom_right' | 'center'
+
+export interface WatermarkConfig {
+  content: WatermarkContent
+  position?: WatermarkPosition  // 默认 bottom_right
+  opacity?: number  // 0 ~ 1，默认 0.8
+  margin?: number   // 距画面边缘的像素，默认 24
+}
+
+export async function getGroupWatermark(groupId: string): Promise<WatermarkConfig | null> {
+  try {
+    return await invoke<WatermarkConfig | null>('get_group_watermark', { groupId })
+  } catch (error) {
+    console.error('Failed to get group watermark:', error)
+    throw error
+  }
+}
+
+/**
+ * Set or clear (null) the watermark of an account group
+ */
+export async function setGroupWatermark(groupId: string, config: WatermarkConfig | null): Promise<boolean> {
+  try {
+    return await invoke<boolean>('set_group_watermark', { groupId, config })
+  } catch (error) {
+    console.error('Failed to set group watermark:', error)
+    throw error
+  }
+}
+
+export async function setAccountTags(accountId: string, tags: string[]): Promise<boolean> {
+  try {
+    return await invoke<boolean>('set_account_tags', { accountId, tags })
+  } catch (error) {
+    console.error('Failed to set account tags:', error)
+    throw error
+  }
+}
+
+export async function getAccountsByGroup(groupId: string): Promise<UserAccount[]> {
   try {
-    return await invoke<any[]>('get_publications', { platform, accountId })
+    return await invoke<UserAccount[]>('get_accounts_by_group', { groupId })
   } catch (error) {
-    console.error('Failed to get publications:', error)
+    console.error('Failed to get accounts by group:', error)
     throw error
   }
 }
@@ -175,15 +407,47 @@ export async function getPublications(
 /**
  * Get all publication tasks with their account details
  */
-export async function getPublicationTasks(): Promise<PublicationTaskWithAccounts[]> {
+export async function getPublicationTasks(
+  page: number | null = null,
+  pageSize: number | null = null
+): Promise<Paginated<PublicationTaskWithAccounts>> {
   try {
-    return await invoke<PublicationTaskWithAccounts[]>('get_publication_tasks')
+    return await invoke<Paginated<PublicationTaskWithAccounts>>('get_publication_tasks', { page, pageSize })
   } catch (error) {
     console.error('Failed to get publication tasks:', error)
     throw error
   }
 }
 
+// 任务列表筛选条件（未设置的条件不参与筛选）
+export interface PublicationTaskFilter {
+  status?: PublicationStatus | null
+  platform?: PlatformType | null
+  search?: string | null  // 标题、描述或账号名称包含的关键字
+}
+
+/**
+ * Get one page of publication tasks, filtered and counted in the database
+ */
+export async function getPublicationTasksPaginated(
+  page: number,
+  pageSize: number,
+  filter: PublicationTaskFilter = {}
+): Promise<Paginated<PublicationTaskWithAccounts>> {
+  try {
+    return await invoke<Paginated<PublicationTaskWithAccounts>>('get_publication_tasks_paginated', {
+      page,
+      pageSize,
+      statusFilter: filter.status ?? null,
+      platformFilter: filter.platform ?? null,
+      search: filter.search ?? null,
+    })
+  } catch (error) {
+    console.error('Failed to get paginated publication tasks:', error)
+    throw error
+  }
+}
+
 /**
  * Get a single publication task with its account details
  */
@@ -196,30 +460,146 @@ export async function getPublicationTask(taskId: string): Promise<PublicationTas
   }
 }
 
+/** Account targeted by a publication task */
+export interface TaskAccountInput {
+  account_id: string
+  platform: string  // 'douyin' | 'xiaohongshu' | 'kuaishou' | 'bilibili'
+  overrides?: {
+    title?: string        // 该账号专属标题
+    description?: string  // 该账号专属描述
+    hashtags?: string[]  // 该账号额外的话题（与任务话题合并去重）
+  }
+}
+
+/**
+ * Request of createPublicationTask
+ * 创建任务的请求参数（字段与后端 CreatePublicationTaskRequest 一致）
+ */
+export interface CreatePublicationTaskRequest {
+  title: string
+  description?: string
+  video_path?: string
+  cover_path?: string | null
+  accounts: TaskAccountInput[]
+  hashtags?: string[]
+  hashtag_set_id?: string | null
+  vertical_video_path?: string | null
+  horizontal_video_path?: string | null
+  group_id?: string | null  // 分组内所有账号都加入任务
+  watermark_enabled?: boolean  // 发布前按账号所在分组的配置叠加水印
+  visibility_type?: PublishVisibility
+  download_allowed?: boolean
+  poi?: PoiInfo | null  // 视频定位
+  music?: MusicInfo | null  // 作品配乐
+  mentions?: string[]  // 要 @ 的用户昵称
+}
+
 /**
  * Create a publication task with account details (main + sub tables)
  */
 export async function createPublicationTask(
-  title: string,
-  description: string,
-  videoPath: string,
-  coverPath: string | null,
-  accountIds: string[],
-  platforms: string[],
-  hashtags: string[][]
+  request: CreatePublicationTaskRequest
 ): Promise<PublicationTaskWithAccounts> {
   try {
-    return await invoke<PublicationTaskWithAccounts>('create_publication_task', {
-      title,
+    return await invoke<PublicationTaskWithAccounts>('create_publication_task', { request })
+  } catch (error) {
+    console.error('Failed to create publication task:', error)
+    throw error
+  }
+}
+
+/**
+ * Search the platform's locations (POI) with the given account (Douyin only)
+ */
+export async function searchPoi(keyword: string, accountId: string): Promise<PoiInfo[]> {
+  try {
+    return await invoke<PoiInfo[]>('search_poi', { keyword, accountId })
+  } catch (error) {
+    console.error('Failed to search POI:', error)
+    throw error
+  }
+}
+
+/**
+ * Search the platform's music library with the given account (Douyin only)
+ */
+export async function searchMusic(keyword: string, accountId: string): Promise<MusicSearchItem[]> {
+  try {
+    return await invoke<MusicSearchItem[]>('search_music', { keyword, accountId })
+  } catch (error) {
+    console.error('Failed to search music:', error)
+    throw error
+  }
+}
+
+export interface BulkPublicationTasksResult {
+  created: PublicationTaskWithAccounts[]
+  errors: string[]
+}
+
+/**
+ * Create one publication task per video found in a directory
+ * 扫描目录中的视频，每个视频创建一个任务（标题取自文件名）
+ */
+export async function createBulkPublicationTasks(
+  directory: string,
+  accounts: TaskAccountInput[],
+  description: string | null = null,
+  hashtags: string[] | null = null,
+  hashtagSetId: string | null = null,
+  groupId: string | null = null
+): Promise<BulkPublicationTasksResult> {
+  try {
+    return await invoke<BulkPublicationTasksResult>('create_bulk_publication_tasks', {
+      directory,
       description,
-      videoPath,
-      coverPath,
-      accountIds,
-      platforms,
+      accounts,
       hashtags,
+      hashtagSetId,
+      groupId
     })
   } catch (error) {
-    console.error('Failed to create publication task:', error)
+    console.error('Failed to create bulk publication tasks:', error)
+    throw error
+  }
+}
+
+export interface FilenameMetadata {
+  title: string | null
+  date: string | null  // YYYY-MM-DD（无法识别时为原文）
+  tags: string[]
+}
+
+/**
+ * Filename parsing rule used by batch import (regex with named groups title / date / tags)
+ * 批量导入的文件名解析规则
+ */
+export async function getFilenameParseRule(): Promise<string | null> {
+  try {
+    return await invoke<string | null>('get_filename_parse_rule')
+  } catch (error) {
+    console.error('Failed to get filename parse rule:', error)
+    throw error
+  }
+}
+
+export async function setFilenameParseRule(pattern: string | null): Promise<string | null> {
+  try {
+    return await invoke<string | null>('set_filename_parse_rule', { pattern })
+  } catch (error) {
+    console.error('Failed to set filename parse rule:', error)
+    throw error
+  }
+}
+
+/**
+ * Preview what a rule extracts from a filename (saved rule when pattern is omitted)
+ */
+export async function previewFilenameMetadata(fileName: string, pattern: string | null = null): Promise<FilenameMetadata | null> {
+  try {
+    return await invoke<FilenameMetadata | null>('preview_filename_metadata', { fileName, pattern })
+  } catch (error) {
+    console.error('Failed to preview filename metadata:', error)
     throw error
   }
 }
@@ -236,6 +616,39 @@ export async function deletePublicationTask(taskId: string): Promise<boolean> {
   }
 }
 
+/**
+ * Restore a publication task from the trash
+ */
+export async function restoreTask(taskId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('restore_task', { taskId })
+  } catch (error) {
+    console.error('Failed to restore task:', error)
+    throw error
+  }
+}
+
+export interface ReplaceTaskVideoResult {
+  task_id: string
+  video_path: string
+  info: VideoInfo
+  reset_accounts: number           // 重置为草稿的账号数
+  cleared_upload_sessions: number  // 清除的上传断点数
+  warnings: string[]               // 新视频超出平台限制的提示
+}
+
+/**
+ * Replace a task's main video (e.g. a fixed cut); failed/skipped accounts using it are reset to draft
+ */
+export async function replaceTaskVideo(taskId: string, newPath: string): Promise<ReplaceTaskVideoResult> {
+  try {
+    return await invoke<ReplaceTaskVideoResult>('replace_task_video', { taskId, newPath })
+  } catch (error) {
+    console.error('Failed to replace task video:', error)
+    throw error
+  }
+}
+
 /**
  * Get a publication task with all account details by ID
  */
@@ -261,6 +674,39 @@ export async function getPublicationAccountDetail(detailId: string): Promise<Pub
   }
 }
 
+/**
+ * Refresh likes/comments/favorites/shares of one publication from the platform (Douyin only)
+ * 从平台刷新单个账号发布的互动数据
+ */
+export async function refreshPublicationStats(detailId: string): Promise<PublicationAccountDetail> {
+  try {
+    return await invoke<PublicationAccountDetail>('refresh_publication_stats', { detailId })
+  } catch (error) {
+    console.error('Failed to refresh publication stats:', error)
+    throw error
+  }
+}
+
+export interface PublicationStatsRefreshResult {
+  refreshed: number
+  failed: number
+  skipped: number  // 未完成发布、无作品ID或平台暂不支持
+  errors: string[]
+}
+
+/**
+ * Refresh the stats of every completed publication in a task
+ * 批量刷新任务下已发布作品的互动数据
+ */
+export async function refreshTaskPublicationStats(taskId: string): Promise<PublicationStatsRefreshResult> {
+  try {
+    return await invoke<PublicationStatsRefreshResult>('refresh_task_publication_stats', { taskId })
+  } catch (error) {
+    console.error('Failed to refresh task publication stats:', error)
+    throw error
+  }
+}
+
 /**
  * Result of publishing a task
  */
@@ -269,6 +715,9 @@ export interface PublishTaskResult {
   detailId: string
   publishUrl: string | null
   error: string | null
+  errorCode: PublishErrorCode | null  // 失败时的结构化错误码
+  skipped: boolean
+  deferredUntil: string | null  // 受发布节奏限制推迟到的时间，到时自动发布
 }
 
 /**
@@ -279,6 +728,8 @@ export interface PublishProgressResult {
   completedAccounts: number
   successCount: number
   failedCount: number
+  skippedCount: number
+  deferredCount: number  // 受发布节奏限制推迟的账号数
   results: PublishTaskResult[]
 }
 
@@ -290,7 +741,8 @@ export async function publishPublicationTask(
   title: string,
   description: string,
   videoPath: string,
-  hashtags: string[]
+  hashtags: string[],
+  skipDetailIds: string[] = []
 ): Promise<PublishProgressResult> {
   try {
     return await invoke<PublishProgressResult>('publish_publication_task', {
@@ -299,6 +751,7 @@ export async function publishPublicationTask(
       description,
       videoPath,
       hashtags,
+      skipDetailIds,
     })
   } catch (error) {
     console.error('Failed to publish task:', error)
@@ -307,67 +760,284 @@ export async function publishPublicationTask(
 }
 
 /**
- * Retry publishing for failed or pending accounts
+ * Upload the task's video to the given accounts and save it to the platform drafts instead of publishing (Douyin only).
+ * An empty `accountIds` covers every unpublished account of the task.
  */
-export async function retryPublicationTask(
-  taskId: string
-): Promise<PublishProgressResult> {
+export async function saveAsPlatformDraft(taskId: string, accountIds: string[] = []): Promise<PublishProgressResult> {
   try {
-    return await invoke<PublishProgressResult>('retry_publication_task', { taskId })
+    return await invoke<PublishProgressResult>('save_as_platform_draft', { taskId, accountIds })
   } catch (error) {
-    console.error('Failed to retry task:', error)
+    console.error('Failed to save as platform draft:', error)
     throw error
   }
 }
 
+export interface CredentialCheckResult {
+  detail_id: string
+  account_id: string
+  account_name: string
+  platform: string
+  valid: boolean
+  error: string | null
+}
+
 /**
- * Legacy: Get all publications across all accounts (uses old structure)
+ * Check credentials of every account a publish would target
+ * 发布前凭证预检；失效账号可跳过（传给 publishPublicationTask 的 skipDetailIds）或放弃发布
  */
-export async function getAllPublications(): Promise<any[]> {
+export async function verifyPublicationCredentials(taskId: string): Promise<CredentialCheckResult[]> {
   try {
-    return await invoke<any[]>('get_publication_tasks')
+    return await invoke<CredentialCheckResult[]>('verify_publication_credentials', { taskId })
   } catch (error) {
-    console.error('Failed to get all publications:', error)
+    console.error('Failed to verify publication credentials:', error)
     throw error
   }
 }
 
+export interface PlatformLimits {
+  platform: string
+  max_duration_secs: number
+  max_size_bytes: number
+}
+
 /**
- * Legacy: Save a publication draft (uses old structure)
+ * Known video duration / size limits of every platform
+ * 创建任务时超出限制的账号会被拒绝
  */
-export async function savePublication(
-  title: string,
-  description: string,
-  videoPath: string,
-  coverPath: string | null,
-  accountIds: string[],
-  platforms: string[],
-  hashtags: string[][]
-): Promise<PublicationTaskWithAccounts> {
+export async function getPlatformLimits(): Promise<PlatformLimits[]> {
   try {
-    return await invoke<PublicationTaskWithAccounts>('create_publication_task', {
-      title,
-      description,
-      videoPath,
-      coverPath,
-      accountIds,
-      platforms,
-      hashtags,
-    })
+    return await invoke<PlatformLimits[]>('get_platform_limits')
   } catch (error) {
-    console.error('Failed to save publication:', error)
+    console.error('Failed to get platform limits:', error)
     throw error
   }
 }
 
+export interface VideoInfo {
+  size_bytes: number
+  duration_secs: number | null
+  width: number | null
+  height: number | null
+  video_codec: string | null
+  audio_codec: string | null
+}
+
+export interface VideoValidation {
+  info: VideoInfo
+  checks: { platform: string; violations: string[] }[]
+  transcode_reason: string | null  // 不为空时建议先调用 transcodeVideo
+}
+
 /**
- * Publish a video
+ * Probe a video and check it against platform limits before creating a task
+ * 读取视频时长、分辨率、编码和大小，并列出各平台的超限项
  */
-export async function publishVideo(
-  platform: string,
-  accountId: string,
-  videoPath: string,
-  title: string,
+export async function validateVideo(path: string, platforms?: string[]): Promise<VideoValidation> {
+  try {
+    return await invoke<VideoValidation>('validate_video', { path, platforms })
+  } catch (error) {
+    console.error('Failed to validate video:', error)
+    throw error
+  }
+}
+
+export interface TranscodeResult {
+  output_path: string
+  transcoded: boolean
+  reason: string | null
+}
+
+/**
+ * Transcode an incompatible video to H.264 MP4
+ * 进度通过 video-transcode-progress 事件（{ source_path, progress }）推送
+ */
+export async function transcodeVideo(path: string, force = false): Promise<TranscodeResult> {
+  try {
+    return await invoke<TranscodeResult>('transcode_video', { path, force })
+  } catch (error) {
+    console.error('Failed to transcode video:', error)
+    throw error
+  }
+}
+
+/**
+ * Upload a cover image to Douyin over HTTP and return its image URI
+ * 传入 frameSeconds 时 imagePath 为视频，先截取该时间点的画面
+ */
+export async function uploadDouyinCover(accountId: string, imagePath: string, frameSeconds?: number): Promise<string> {
+  try {
+    return await invoke<string>('upload_douyin_cover', { accountId, imagePath, frameSeconds })
+  } catch (error) {
+    console.error('Failed to upload Douyin cover:', error)
+    throw error
+  }
+}
+
+export interface PreviewHashtag {
+  name: string
+  hashtag_id: string
+  matched: boolean
+}
+
+export interface PublishPreview {
+  item_title: string
+  caption: string
+  text: string
+  hashtags: PreviewHashtag[]
+  text_extra: unknown[]
+  errors: string[]
+}
+
+export interface PublicationPreviewResult {
+  detail_id: string
+  account_id: string
+  account_name: string
+  platform: string
+  preview: PublishPreview
+}
+
+/**
+ * Dry-run a publication task without uploading or publishing
+ * 发布预演：返回各账号解析后的文案、话题ID和校验错误
+ */
+export async function previewPublicationTask(taskId: string): Promise<PublicationPreviewResult[]> {
+  try {
+    return await invoke<PublicationPreviewResult[]>('preview_publication_task', { taskId })
+  } catch (error) {
+    console.error('Failed to preview publication task:', error)
+    throw error
+  }
+}
+
+/**
+ * Retry publishing for failed or pending accounts
+ */
+export async function retryPublicationTask(
+  taskId: string
+): Promise<PublishProgressResult> {
+  try {
+    return await invoke<PublishProgressResult>('retry_publication_task', { taskId })
+  } catch (error) {
+    console.error('Failed to retry task:', error)
+    throw error
+  }
+}
+
+/**
+ * Cancel an in-flight publish; queued accounts are aborted, running ones stop after the current step
+ * 取消正在执行的发布，被取消的账号标记为失败；任务没有在发布时返回 false
+ */
+export async function cancelPublicationTask(taskId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('cancel_publication_task', { taskId })
+  } catch (error) {
+    console.error('Failed to cancel publication task:', error)
+    throw error
+  }
+}
+
+export interface PublishCaptchaEvent {
+  task_id: string
+  detail_id: string
+  account_id: string
+  platform: string
+  verify_url: string  // 需要完成验证的页面
+  message: string
+  timestamp: number
+}
+
+/**
+ * Listen for publishes paused for a captcha / security verification; returns the unlisten function
+ * 监听发布等待验证事件
+ */
+export async function onPublishCaptchaRequired(handler: (event: PublishCaptchaEvent) => void): Promise<() => void> {
+  return await listen<PublishCaptchaEvent>('publish-captcha-required', (event) => handler(event.payload))
+}
+
+/**
+ * List the publishes currently waiting for verification
+ * 获取正在等待验证的账号
+ */
+export async function getPendingPublishVerifications(): Promise<PublishCaptchaEvent[]> {
+  try {
+    return await invoke<PublishCaptchaEvent[]>('get_pending_publish_verifications')
+  } catch (error) {
+    console.error('Failed to get pending publish verifications:', error)
+    throw error
+  }
+}
+
+/**
+ * Open the verification page in the account's browser profile
+ * 在账号的浏览器中打开验证页
+ */
+export async function openPublishVerification(detailId: string): Promise<BrowserAuthStatus> {
+  try {
+    return await invoke<BrowserAuthStatus>('open_publish_verification', { detailId })
+  } catch (error) {
+    console.error('Failed to open publish verification:', error)
+    throw error
+  }
+}
+
+/**
+ * Resume a publish after the user has passed the verification
+ * 完成验证后继续发布
+ */
+export async function resumePublishAfterVerification(detailId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('resume_publish_after_verification', { detailId })
+  } catch (error) {
+    console.error('Failed to resume publish after verification:', error)
+    throw error
+  }
+}
+
+// 发布断点：已上传的视频和 BD 凭证，中断后从最后完成的步骤继续
+export interface PublishCheckpoint {
+  detail_id: string
+  step: 'video_uploaded' | 'ticket_obtained'
+  video_path: string
+  video_size: number
+  video_id: string
+  poster_uri: string | null
+  bd_ticket: Record<string, string> | null
+  bd_ticket_at: string | null
+  updated_at: string
+}
+
+/**
+ * 获取账号发布的断点
+ */
+export async function getPublishCheckpoint(detailId: string): Promise<PublishCheckpoint | null> {
+  try {
+    return await invoke<PublishCheckpoint | null>('get_publish_checkpoint', { detailId })
+  } catch (error) {
+    console.error('Failed to get publish checkpoint:', error)
+    throw error
+  }
+}
+
+/**
+ * 从断点继续发布一个账号（跳过已完成的视频上传）
+ */
+export async function resumePublication(detailId: string): Promise<PublishProgressResult> {
+  try {
+    return await invoke<PublishProgressResult>('resume_publication', { detailId })
+  } catch (error) {
+    console.error('Failed to resume publication:', error)
+    throw error
+  }
+}
+
+/**
+ * Publish a video
+ */
+export async function publishVideo(
+  platform: string,
+  accountId: string,
+  videoPath: string,
+  title: string,
   description: string,
   hashtags: string[]
 ): Promise<any> {
@@ -387,15 +1057,25 @@ export async function publishVideo(
 }
 
 /**
- * Publish a saved publication
+ * Publish an image post (图文) to a Douyin account; images are shown in the given order
  */
-export async function publishSavedVideo(
-  publicationId: string
+export async function publishImagePost(
+  accountId: string,
+  imagePaths: string[],
+  title: string,
+  description: string | null,
+  hashtags: string[]
 ): Promise<any> {
   try {
-    return await invoke('publish_saved_video', { publicationId })
+    return await invoke('publish_image_post', {
+      accountId,
+      imagePaths,
+      title,
+      description,
+      hashtags,
+    })
   } catch (error) {
-    console.error('Failed to publish saved video:', error)
+    console.error('Failed to publish image post:', error)
     throw error
   }
 }
@@ -412,6 +1092,7 @@ export function toFrontendAccount(account: UserAccount): {
   avatar: string
   status: 'active' | 'expired' | 'pending'
   authorizedAt: string
+  onHold: boolean
 } {
   return {
     id: account.id,
@@ -420,6 +1101,7 @@ export function toFrontendAccount(account: UserAccount): {
     avatar: account.avatar_url,
     status: account.status.toLowerCase() as 'active' | 'expired' | 'pending',
     authorizedAt: account.created_at,
+    onHold: account.on_hold,
   }
 }
 
@@ -439,6 +1121,9 @@ export function toPlatformString(platform: PlatformType): string {
  * Browser authentication status
  */
 export interface BrowserAuthStatus {
+  session_id: string          // 授权会话ID（查询/取消时传入）
+  platform_id: string
+  account_id: string | null   // 重新授权的账号，完成后为保存的账号
   step: string
   message: string
   currentUrl: string
@@ -453,17 +1138,30 @@ export interface BrowserAuthStatus {
   error: string | null
 }
 
+export interface BrowserFingerprint {
+  userAgent?: string
+  viewportWidth?: number
+  viewportHeight?: number
+}
+
 /**
  * Start browser authentication flow for a platform
- * This launches a headless browser and navigates to the platform's login page
+ * This launches a browser in the background and returns the session id to poll with checkBrowserAuthStatus
  * If accountId is provided, it will update the existing account instead of creating a new one
+ * The optional fingerprint (user-agent / viewport) is stored with the account and reused for publishing
+ * headless runs the browser without a window (credential refresh of an existing login only);
+ * when omitted, re-authorizing an existing account follows the browser_auth_headless setting
  */
-export async function startBrowserAuth(platform: string, accountId?: string): Promise<BrowserAuthStatus> {
+export async function startBrowserAuth(platform: string, accountId?: string, fingerprint: BrowserFingerprint = {}, headless?: boolean): Promise<BrowserAuthStatus> {
   console.log('[API] Starting browser auth for platform:', platform, 'accountId:', accountId)
   try {
     const result = await invoke<BrowserAuthStatus>('start_browser_auth', {
       platform,
-      accountId: accountId || null
+      accountId: accountId || null,
+      userAgent: fingerprint.userAgent || null,
+      viewportWidth: fingerprint.viewportWidth ?? null,
+      viewportHeight: fingerprint.viewportHeight ?? null,
+      headless: headless ?? null
     })
     console.log('[API] Browser auth started successfully:', result)
     return result
@@ -475,12 +1173,12 @@ export async function startBrowserAuth(platform: string, accountId?: string): Pr
 }
 
 /**
- * Check browser authentication status and extract credentials if logged in
+ * Check the status of a browser authentication session
  * This should be called periodically to poll for login completion
  */
-export async function checkBrowserAuthStatus(): Promise<BrowserAuthStatus> {
+export async function checkBrowserAuthStatus(sessionId: string): Promise<BrowserAuthStatus> {
   try {
-    return await invoke<BrowserAuthStatus>('check_browser_auth_status')
+    return await invoke<BrowserAuthStatus>('check_browser_auth_status', { sessionId })
   } catch (error) {
     console.error('Failed to check auth status:', error)
     throw error
@@ -488,122 +1186,2028 @@ export async function checkBrowserAuthStatus(): Promise<BrowserAuthStatus> {
 }
 
 /**
- * Cancel browser authentication
+ * List browser authentication sessions (active ones first)
+ * 列出所有授权会话（多个账号可同时授权）
+ */
+export async function getBrowserAuthSessions(): Promise<BrowserAuthStatus[]> {
+  try {
+    return await invoke<BrowserAuthStatus[]>('get_browser_auth_sessions')
+  } catch (error) {
+    console.error('Failed to get browser auth sessions:', error)
+    throw error
+  }
+}
+
+/**
+ * Cancel a browser authentication session
+ * Returns whether an active session was cancelled
  */
-export async function cancelBrowserAuth(): Promise<void> {
+export async function cancelBrowserAuth(sessionId: string): Promise<boolean> {
   try {
-    await invoke('cancel_browser_auth')
+    return await invoke<boolean>('cancel_browser_auth', { sessionId })
   } catch (error) {
     console.error('Failed to cancel auth:', error)
     throw error
   }
 }
 
-// ============================================================================
-// Comment Extraction Functions
-// 评论提取功能
-// ============================================================================
+export interface AccountBrowserProfile {
+  account_id: string
+  path: string
+  size_bytes: number
+  last_used: string | null
+}
+
+export interface ProfileCleanupResult {
+  removed_profiles: number
+  reclaimed_bytes: number
+}
 
 /**
- * Comment interface
+ * List the persistent browser profiles kept per account (re-authorization reuses their login session)
+ * 列出账号的浏览器资料目录
  */
-export interface Comment {
-  id: string
-  accountId: string
-  awemeId: string
-  commentId: string
-  userId: string
-  userNickname: string
-  userAvatar: string
-  content: string
-  likeCount: number
-  replyCount: number
-  createTime: string
-  status: 'Pending' | 'Completed' | 'Failed'
-  createdAt: string
+export async function getAccountBrowserProfiles(): Promise<AccountBrowserProfile[]> {
+  try {
+    return await invoke<AccountBrowserProfile[]>('get_account_browser_profiles')
+  } catch (error) {
+    console.error('Failed to get account browser profiles:', error)
+    throw error
+  }
 }
 
 /**
- * Comment extraction result
- * Note: Tauri returns snake_case field names, not camelCase
+ * Delete an account's browser profile; returns the bytes freed
+ * 删除账号的浏览器资料目录（下次授权需重新扫码）
  */
-export interface CommentExtractResult {
-  success: boolean
-  total_extracted: number
-  total_in_aweme: number
-  comments: Comment[]
-  error_message: string | null
+export async function deleteAccountBrowserProfile(accountId: string): Promise<number> {
+  try {
+    return await invoke<number>('delete_account_browser_profile', { accountId })
+  } catch (error) {
+    console.error('Failed to delete account browser profile:', error)
+    throw error
+  }
 }
 
 /**
- * Extract comments from a video
- * @param detailId - Publication account detail ID (publication_accounts table id)
- * @param awemeId - Video ID (item_id)
- * @param maxCount - Maximum number of comments to extract (default: 500)
- * @param cursor - Pagination cursor (default: 0, start from beginning)
+ * Remove browser profiles of deleted accounts and interrupted authorizations
+ * 清理无主的浏览器资料目录
  */
-export async function extractComments(
-  detailId: string,
-  awemeId: string,
-  maxCount: number = 500,
-  cursor: number = 0
-): Promise<CommentExtractResult> {
+export async function cleanupAccountBrowserProfiles(): Promise<ProfileCleanupResult> {
   try {
-    return await invoke<CommentExtractResult>('extract_comments', {
-      detailId,
-      awemeId,
-      maxCount,
-      cursor,
-    })
+    return await invoke<ProfileCleanupResult>('cleanup_account_browser_profiles')
   } catch (error) {
-    console.error('Failed to extract comments:', error)
+    console.error('Failed to clean up account browser profiles:', error)
     throw error
   }
 }
 
+export type ReauthItemStatus = 'pending' | 'in_progress' | 'completed' | 'failed' | 'skipped'
+
+export interface ReauthQueueItem {
+  account_id: string
+  nickname: string
+  platform: PlatformType
+  status: ReauthItemStatus
+  message: string | null
+}
+
+export interface ReauthQueue {
+  items: ReauthQueueItem[]
+  cancelled: boolean
+}
+
 /**
- * Get comments by aweme_id with pagination
- * @param awemeId - Video ID (item_id)
- * @param page - Page number (1-indexed)
- * @param pageSize - Number of items per page
+ * Re-authorize expired accounts one after another (progress via the 'reauth-queue-progress' event)
+ * 批量重新授权：不传 accountIds 时自动校验并收集凭证失效的账号
  */
-export async function getCommentsByAwemeId(
-  awemeId: string,
-  page: number = 1,
-  pageSize: number = 50
-): Promise<{ comments: Comment[]; total: number }> {
+export async function startReauthQueue(platform?: PlatformType, accountIds?: string[]): Promise<ReauthQueue> {
   try {
-    return await invoke<{ comments: Comment[]; total: number }>('get_comments_by_aweme_id', {
-      awemeId,
-      page,
-      pageSize,
-    })
+    return await invoke<ReauthQueue>('start_reauth_queue', { platform, accountIds })
   } catch (error) {
-    console.error('Failed to get comments:', error)
+    console.error('Failed to start reauth queue:', error)
     throw error
   }
 }
 
 /**
- * Delete comments by aweme_id
+ * Get the current re-authorization queue
  */
-export async function deleteComments(awemeId: string): Promise<boolean> {
+export async function getReauthQueue(): Promise<ReauthQueue> {
   try {
-    return await invoke<boolean>('delete_comments', { awemeId })
+    return await invoke<ReauthQueue>('get_reauth_queue')
   } catch (error) {
-    console.error('Failed to delete comments:', error)
+    console.error('Failed to get reauth queue:', error)
     throw error
   }
 }
 
 /**
- * Get actual comment count from database
+ * Cancel the re-authorization queue after the current account
  */
-export async function getCommentCount(awemeId: string): Promise<number> {
+export async function cancelReauthQueue(): Promise<ReauthQueue> {
   try {
-    return await invoke<number>('get_comment_count', { awemeId })
+    return await invoke<ReauthQueue>('cancel_reauth_queue')
   } catch (error) {
-    console.error('Failed to get comment count:', error)
+    console.error('Failed to cancel reauth queue:', error)
+    throw error
+  }
+}
+
+/**
+ * Import an account by pasting its login cookie (no browser needed, works on mobile)
+ * 粘贴 Cookie 导入账号；已存在相同用户 ID 的账号会更新凭证
+ */
+export async function importAccountFromCookie(
+  platform: string,
+  cookie: string,
+  userAgent: string | null = null,
+  nickname: string | null = null
+): Promise<UserAccount> {
+  try {
+    return await invoke<UserAccount>('import_account_from_cookie', { platform, cookie, userAgent, nickname })
+  } catch (error) {
+    console.error('Failed to import account from cookie:', error)
+    throw error
+  }
+}
+
+export interface AccountImportItem {
+  index: number              // 在文件中的序号
+  platform: PlatformType
+  nickname: string
+  account_id: string | null  // 导入失败时为 null
+  valid: boolean             // 凭证是否校验通过（未通过时以 Expired 状态导入）
+  error: string | null
+}
+
+/**
+ * Import accounts from an account JSON file or a Netscape cookies.txt
+ * 批量导入账号（账号 JSON 文件或 Netscape cookies.txt）；未指定路径时弹出文件选择对话框，取消返回空列表
+ */
+export async function importAccounts(path?: string): Promise<AccountImportItem[]> {
+  try {
+    return await invoke<AccountImportItem[]>('import_accounts', { path })
+  } catch (error) {
+    console.error('Failed to import accounts:', error)
+    throw error
+  }
+}
+
+/**
+ * Export accounts (with plaintext credentials) to a JSON file; empty accountIds exports all
+ * 导出账号到 JSON 文件（含明文凭证），accountIds 为空时导出全部；取消保存返回 null，否则返回导出数量
+ */
+export async function exportAccounts(accountIds: string[] = [], path?: string): Promise<number | null> {
+  try {
+    return await invoke<number | null>('export_accounts', { path, accountIds })
+  } catch (error) {
+    console.error('Failed to export accounts:', error)
+    throw error
+  }
+}
+
+export interface AuthCapabilities {
+  browser_auth: boolean
+  browser_import: boolean
+  cookie_paste: boolean
+  mobile: boolean
+}
+
+/**
+ * Get the authorization modes available on this device
+ * 获取当前设备可用的授权方式（移动端仅支持粘贴 Cookie）
+ */
+export async function getAuthCapabilities(): Promise<AuthCapabilities> {
+  try {
+    return await invoke<AuthCapabilities>('get_auth_capabilities')
+  } catch (error) {
+    console.error('Failed to get auth capabilities:', error)
+    throw error
+  }
+}
+
+/**
+ * Get an account's avatar from the local cache as a data URL (null when unavailable)
+ * 获取账号头像（本地缓存的 base64 data URL），取不到时返回 null
+ */
+export async function getAccountAvatar(accountId: string): Promise<string | null> {
+  try {
+    return await invoke<string | null>('get_account_avatar', { accountId })
+  } catch (error) {
+    console.error('Failed to get account avatar:', error)
+    throw error
+  }
+}
+
+export type ExtractorRuleStatus = 'matched' | 'empty' | 'not_captured' | 'invalid' | 'fixed'
+
+export interface ExtractorRuleCheck {
+  section: string
+  key: string
+  rule: string
+  status: ExtractorRuleStatus
+  value: string
+  message: string
+}
+
+export interface ExtractorTestReport {
+  platform_id: string
+  current_url: string
+  captured_urls: string[]
+  rules: ExtractorRuleCheck[]
+  matched: number
+  failed: number
+}
+
+/**
+ * Test an extractor config in a sandbox login session and report each rule's result
+ * 测试提取引擎配置：打开登录页，登录后返回每条规则的提取结果（不保存账号）
+ */
+export async function testExtractorConfig(platformId: string): Promise<ExtractorTestReport> {
+  try {
+    return await invoke<ExtractorTestReport>('test_extractor_config', { platformId })
+  } catch (error) {
+    console.error('Failed to test extractor config:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Comment Extraction Functions
+// 评论提取功能
+// ============================================================================
+
+/**
+ * Comment interface
+ */
+export interface Comment {
+  id: string
+  accountId: string
+  awemeId: string
+  commentId: string
+  userId: string
+  userNickname: string
+  userAvatar: string
+  content: string
+  likeCount: number
+  replyCount: number
+  createTime: string
+  status: 'Pending' | 'Completed' | 'Failed'
+  createdAt: string
+  tags?: string[]  // 分类标签（negative / lead / spam 等）
+}
+
+/**
+ * Comment extraction result
+ * Note: Tauri returns snake_case field names, not camelCase
+ */
+export interface CommentExtractResult {
+  success: boolean
+  total_extracted: number
+  total_in_aweme: number
+  comments: Comment[]
+  error_message: string | null
+  next_cursor: number
+  has_more: boolean
+}
+
+/**
+ * Extract comments from a video
+ * @param detailId - Publication account detail ID (publication_accounts table id)
+ * @param awemeId - Video ID (item_id)
+ * @param maxCount - Maximum number of comments to extract (default: 500)
+ * @param cursor - Pagination cursor (default: 0, start from beginning)
+ */
+export async function extractComments(
+  detailId: string,
+  awemeId: string,
+  maxCount: number = 500,
+  cursor: number = 0
+): Promise<CommentExtractResult> {
+  try {
+    return await invoke<CommentExtractResult>('extract_comments', {
+      detailId,
+      awemeId,
+      maxCount,
+      cursor,
+    })
+  } catch (error) {
+    console.error('Failed to extract comments:', error)
+    throw error
+  }
+}
+
+/**
+ * Get comments by aweme_id with pagination
+ * @param awemeId - Video ID (item_id)
+ * @param page - Page number (1-indexed)
+ * @param pageSize - Number of items per page
+ */
+export async function getCommentsByAwemeId(
+  awemeId: string,
+  page: number = 1,
+  pageSize: number = 50
+): Promise<Paginated<Comment>> {
+  try {
+    return await invoke<Paginated<Comment>>('get_comments_by_aweme_id', {
+      awemeId,
+      page,
+      pageSize,
+    })
+  } catch (error) {
+    console.error('Failed to get comments:', error)
+    throw error
+  }
+}
+
+// 全文搜索命中的任务
+export interface TaskSearchHit {
+  task_id: string
+  title: string
+  description: string
+  status: PublicationStatus
+  created_at: string
+}
+
+// 全文搜索命中的评论（task_id 为发布该作品的任务，非本应用发布的作品为空）
+export interface CommentSearchHit {
+  comment_id: string
+  account_id: string
+  aweme_id: string
+  user_nickname: string
+  content: string
+  create_time: string
+  task_id: string | null
+  task_title: string | null
+}
+
+/**
+ * Full-text search over task titles and descriptions (space-separated keywords)
+ */
+export async function searchTasks(query: string, limit: number | null = null): Promise<TaskSearchHit[]> {
+  try {
+    return await invoke<TaskSearchHit[]>('search_tasks', { query, limit })
+  } catch (error) {
+    console.error('Failed to search tasks:', error)
+    throw error
+  }
+}
+
+/**
+ * Full-text search over comment text
+ */
+export async function searchComments(query: string, limit: number | null = null): Promise<CommentSearchHit[]> {
+  try {
+    return await invoke<CommentSearchHit[]>('search_comments', { query, limit })
+  } catch (error) {
+    console.error('Failed to search comments:', error)
+    throw error
+  }
+}
+
+// Private message (私信) types
+export interface PrivateMessage {
+  id: string
+  account_id: string
+  conversation_id: string
+  message_id: string
+  sender_id: string
+  sender_nickname: string
+  sender_avatar: string
+  content: string
+  is_outgoing: boolean  // 本账号发出的消息
+  create_time: string
+  created_at: string
+}
+
+export interface MessageExtractResult {
+  success: boolean
+  total_extracted: number
+  new_messages: number  // 新入库的消息数
+  messages: PrivateMessage[]
+  error_message: string | null
+}
+
+/**
+ * Extract private messages from an account's creator inbox (Douyin only)
+ */
+export async function extractMessages(accountId: string, maxCount: number | null = null): Promise<MessageExtractResult> {
+  try {
+    return await invoke<MessageExtractResult>('extract_messages', { accountId, maxCount })
+  } catch (error) {
+    console.error('Failed to extract messages:', error)
+    throw error
+  }
+}
+
+/**
+ * Get an account's stored private messages with pagination, newest first
+ */
+export async function getMessages(
+  accountId: string,
+  page: number = 1,
+  pageSize: number = 50
+): Promise<Paginated<PrivateMessage>> {
+  try {
+    return await invoke<Paginated<PrivateMessage>>('get_messages', { accountId, page, pageSize })
+  } catch (error) {
+    console.error('Failed to get messages:', error)
+    throw error
+  }
+}
+
+export interface RemoteVideo {
+  id: string
+  account_id: string
+  item_id: string  // 平台作品ID
+  title: string
+  cover_url: string
+  share_url: string
+  create_time: string
+  plays: number
+  likes: number
+  comments: number
+  favorites: number
+  shares: number
+  detail_id: string | null  // 通过本应用发布的作品关联的发布详情ID
+  synced_at: string
+}
+
+export interface VideoSyncResult {
+  total: number
+  new_videos: number
+  linked: number
+}
+
+/**
+ * Sync an account's published video list from the platform, including videos not published through this app (Douyin only)
+ */
+export async function syncAccountVideos(accountId: string, maxCount: number | null = null): Promise<VideoSyncResult> {
+  try {
+    return await invoke<VideoSyncResult>('sync_account_videos', { accountId, maxCount })
+  } catch (error) {
+    console.error('Failed to sync account videos:', error)
+    throw error
+  }
+}
+
+/**
+ * Get an account's synced videos with pagination, newest first
+ */
+export async function getRemoteVideos(
+  accountId: string,
+  page: number = 1,
+  pageSize: number = 20
+): Promise<Paginated<RemoteVideo>> {
+  try {
+    return await invoke<Paginated<RemoteVideo>>('get_remote_videos', { accountId, page, pageSize })
+  } catch (error) {
+    console.error('Failed to get remote videos:', error)
+    throw error
+  }
+}
+
+/**
+ * Delete a published video from the platform and mark the publication as Removed (Douyin only)
+ */
+export async function deleteRemotePublication(detailId: string, deleteComments: boolean = false): Promise<void> {
+  try {
+    await invoke('delete_remote_publication', { detailId, deleteComments })
+  } catch (error) {
+    console.error('Failed to delete remote publication:', error)
+    throw error
+  }
+}
+
+/**
+ * Delete comments by aweme_id
+ */
+export async function deleteComments(awemeId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('delete_comments', { awemeId })
+  } catch (error) {
+    console.error('Failed to delete comments:', error)
+    throw error
+  }
+}
+
+/**
+ * Get actual comment count from database
+ */
+export async function getCommentCount(awemeId: string): Promise<number> {
+  try {
+    return await invoke<number>('get_comment_count', { awemeId })
+  } catch (error) {
+    console.error('Failed to get comment count:', error)
+    throw error
+  }
+}
+
+// 标签 → 关键词列表
+export type CommentTagRules = Record<string, string[]>
+
+export interface CommentClassifyResult {
+  total: number
+  tagged: number
+  tag_counts: Record<string, number>
+}
+
+export async function getCommentTagRules(): Promise<CommentTagRules> {
+  try {
+    return await invoke<CommentTagRules>('get_comment_tag_rules')
+  } catch (error) {
+    console.error('Failed to get comment tag rules:', error)
+    throw error
+  }
+}
+
+export async function setCommentTagRules(rules: CommentTagRules): Promise<CommentTagRules> {
+  try {
+    return await invoke<CommentTagRules>('set_comment_tag_rules', { rules })
+  } catch (error) {
+    console.error('Failed to set comment tag rules:', error)
+    throw error
+  }
+}
+
+/**
+ * Tag every comment of an item by the configured keyword lists
+ * 按关键词配置给作品下的评论打标签
+ */
+export async function classifyComments(awemeId: string): Promise<CommentClassifyResult> {
+  try {
+    return await invoke<CommentClassifyResult>('classify_comments', { awemeId })
+  } catch (error) {
+    console.error('Failed to classify comments:', error)
+    throw error
+  }
+}
+
+export async function getCommentsByTag(
+  tag: string,
+  awemeId: string | null = null,
+  page: number = 1,
+  pageSize: number = 20
+): Promise<Paginated<Comment>> {
+  try {
+    return await invoke<Paginated<Comment>>('get_comments_by_tag', { tag, awemeId, page, pageSize })
+  } catch (error) {
+    console.error('Failed to get comments by tag:', error)
+    throw error
+  }
+}
+
+export interface CommentExportProgress {
+  aweme_id: string
+  written: number
+  total: number
+}
+
+/**
+ * Export comments to an Excel workbook (summary sheet + one sheet per item)
+ * 导出评论为 Excel；未指定 outputPath 时弹出保存对话框，取消返回 null。
+ * 只导出一个作品且 outputPath 以 .csv 结尾时写为 CSV；onProgress 接收写入进度
+ */
+export async function exportCommentsToXlsx(
+  options: { awemeIds?: string[]; taskId?: string; outputPath?: string },
+  onProgress?: (progress: CommentExportProgress) => void
+): Promise<string | null> {
+  const unlisten = onProgress
+    ? await listen<CommentExportProgress>('comment-export-progress', (event) => onProgress(event.payload))
+    : null
+
+  try {
+    return await invoke<string | null>('export_comments_to_xlsx', {
+      awemeIds: options.awemeIds ?? null,
+      taskId: options.taskId ?? null,
+      outputPath: options.outputPath ?? null
+    })
+  } catch (error) {
+    console.error('Failed to export comments:', error)
+    throw error
+  } finally {
+    unlisten?.()
+  }
+}
+
+/**
+ * Reply to a comment as the publishing account
+ * 以账号身份回复评论
+ */
+export async function replyComment(accountId: string, awemeId: string, commentId: string, text: string): Promise<void> {
+  try {
+    await invoke('reply_comment', { accountId, awemeId, commentId, text })
+  } catch (error) {
+    console.error('Failed to reply comment:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Auto-Reply Functions
+// 评论自动回复
+// ============================================================================
+
+export interface AutoReplyRule {
+  id: string
+  account_id: string
+  name: string
+  keywords: string[]       // 评论包含任一关键词即命中
+  reply_template: string   // 支持 {nickname}、{content} 占位符
+  enabled: boolean
+  max_replies_per_hour: number
+  created_at: string
+  updated_at: string
+}
+
+export interface AutoReplyLog {
+  id: string
+  rule_id: string
+  account_id: string
+  aweme_id: string
+  comment_id: string
+  comment_content: string
+  reply_content: string
+  success: boolean
+  error: string | null
+  created_at: string
+}
+
+export async function getAutoReplyRules(accountId: string | null = null): Promise<AutoReplyRule[]> {
+  try {
+    return await invoke<AutoReplyRule[]>('get_auto_reply_rules', { accountId })
+  } catch (error) {
+    console.error('Failed to get auto-reply rules:', error)
+    throw error
+  }
+}
+
+/**
+ * Create or update an auto-reply rule (no id creates a new rule)
+ */
+export async function saveAutoReplyRule(rule: {
+  id?: string | null
+  accountId: string
+  name: string
+  keywords: string[]
+  replyTemplate: string
+  enabled?: boolean
+  maxRepliesPerHour?: number
+}): Promise<AutoReplyRule> {
+  try {
+    return await invoke<AutoReplyRule>('save_auto_reply_rule', {
+      id: rule.id ?? null,
+      accountId: rule.accountId,
+      name: rule.name,
+      keywords: rule.keywords,
+      replyTemplate: rule.replyTemplate,
+      enabled: rule.enabled ?? null,
+      maxRepliesPerHour: rule.maxRepliesPerHour ?? null
+    })
+  } catch (error) {
+    console.error('Failed to save auto-reply rule:', error)
+    throw error
+  }
+}
+
+export async function deleteAutoReplyRule(ruleId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('delete_auto_reply_rule', { ruleId })
+  } catch (error) {
+    console.error('Failed to delete auto-reply rule:', error)
+    throw error
+  }
+}
+
+export async function getAutoReplyLogs(accountId: string | null = null, limit: number | null = null): Promise<AutoReplyLog[]> {
+  try {
+    return await invoke<AutoReplyLog[]>('get_auto_reply_logs', { accountId, limit })
+  } catch (error) {
+    console.error('Failed to get auto-reply logs:', error)
+    throw error
+  }
+}
+
+export interface CommentSyncState {
+  aweme_id: string
+  detail_id: string
+  cursor: number
+  has_more: boolean
+  synced_count: number
+  last_error: string | null
+  last_synced_at: string
+}
+
+/**
+ * Get incremental comment sync progress of all items
+ * 获取各作品的评论增量同步进度
+ */
+export async function getCommentSyncStates(): Promise<CommentSyncState[]> {
+  try {
+    return await invoke<CommentSyncState[]>('get_comment_sync_states')
+  } catch (error) {
+    console.error('Failed to get comment sync states:', error)
+    throw error
+  }
+}
+
+export interface CommentSyncRecord {
+  id: number
+  aweme_id: string
+  new_count: number   // 本次同步首次看到的评论数
+  synced_at: string
+}
+
+/**
+ * Recent comment syncs of an item, newest first
+ */
+export async function getCommentSyncHistory(awemeId: string, limit?: number): Promise<CommentSyncRecord[]> {
+  try {
+    return await invoke<CommentSyncRecord[]>('get_comment_sync_history', { awemeId, limit })
+  } catch (error) {
+    console.error('Failed to get comment sync history:', error)
+    throw error
+  }
+}
+
+/**
+ * Comments first seen after the given sync
+ * 传入上次查看时最新的同步ID，返回之后的新评论（用于未读角标）
+ */
+export async function getNewComments(awemeId: string, sinceSyncId: number): Promise<Comment[]> {
+  try {
+    return await invoke<Comment[]>('get_new_comments', { awemeId, sinceSyncId })
+  } catch (error) {
+    console.error('Failed to get new comments:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Remote Sync Functions
+// 远程同步功能
+// ============================================================================
+
+export interface RemoteSyncConfig {
+  enabled: boolean
+  endpoint: string
+  token: string
+  passphrase: string
+  interval_minutes: number
+  device_id: string
+}
+
+export interface RemoteSyncResult {
+  pulled: number
+  applied: number
+  skipped: number
+  pushed: number
+  finished_at: string
+}
+
+/**
+ * Get the remote sync configuration
+ */
+export async function getRemoteSyncConfig(): Promise<RemoteSyncConfig> {
+  try {
+    return await invoke<RemoteSyncConfig>('get_remote_sync_config')
+  } catch (error) {
+    console.error('Failed to get remote sync config:', error)
+    throw error
+  }
+}
+
+/**
+ * Save the remote sync configuration (returns the stored config with device_id)
+ * 保存远程同步配置；各台电脑的同步密码需一致，账号凭证用它加密
+ */
+export async function saveRemoteSyncConfig(config: RemoteSyncConfig): Promise<RemoteSyncConfig> {
+  try {
+    return await invoke<RemoteSyncConfig>('save_remote_sync_config', { config })
+  } catch (error) {
+    console.error('Failed to save remote sync config:', error)
+    throw error
+  }
+}
+
+/**
+ * Pull remote changes and push local ones now
+ */
+export async function runRemoteSyncNow(): Promise<RemoteSyncResult> {
+  try {
+    return await invoke<RemoteSyncResult>('run_remote_sync_now')
+  } catch (error) {
+    console.error('Failed to run remote sync:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// HTTP API Functions
+// HTTP API 功能（需以 --features http-api 编译）
+// ============================================================================
+
+// HTTP API 配置：外部脚本 / CI 通过 `Authorization: Bearer {token}` 调用本机接口
+export interface HttpApiConfig {
+  enabled: boolean
+  bind_address: string  // 默认 127.0.0.1，局域网调用时改为 0.0.0.0
+  port: number
+  token: string  // 留空保存时自动生成
+}
+
+/**
+ * Get the HTTP API configuration
+ */
+export async function getHttpApiConfig(): Promise<HttpApiConfig> {
+  try {
+    return await invoke<HttpApiConfig>('get_http_api_config')
+  } catch (error) {
+    console.error('Failed to get HTTP API config:', error)
+    throw error
+  }
+}
+
+/**
+ * Save the HTTP API configuration and restart the server (returns the stored config with token)
+ */
+export async function saveHttpApiConfig(config: HttpApiConfig): Promise<HttpApiConfig> {
+  try {
+    return await invoke<HttpApiConfig>('save_http_api_config', { config })
+  } catch (error) {
+    console.error('Failed to save HTTP API config:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Notification Functions
+// 系统通知功能
+// ============================================================================
+
+// 系统通知设置：发布、定时发布、后台评论同步结束后发送系统通知，可分别关闭
+export interface NotificationSettings {
+  enabled: boolean  // 总开关
+  publish: boolean
+  scheduled_job: boolean
+  comment_sync: boolean
+}
+
+/**
+ * Get the desktop notification settings
+ */
+export async function getNotificationSettings(): Promise<NotificationSettings> {
+  try {
+    return await invoke<NotificationSettings>('get_notification_settings')
+  } catch (error) {
+    console.error('Failed to get notification settings:', error)
+    throw error
+  }
+}
+
+/**
+ * Save the desktop notification settings
+ */
+export async function saveNotificationSettings(settings: NotificationSettings): Promise<NotificationSettings> {
+  try {
+    return await invoke<NotificationSettings>('save_notification_settings', { settings })
+  } catch (error) {
+    console.error('Failed to save notification settings:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Webhook Functions
+// 发布回调功能
+// ============================================================================
+
+// 发布回调配置：账号发布成功 / 失败后向每个地址 POST 一条 JSON 通知
+export interface WebhookConfig {
+  enabled: boolean
+  urls: string[]
+  max_attempts: number  // 每个地址的最大尝试次数（1 ~ 10）
+}
+
+export interface WebhookDelivery {
+  id: string
+  url: string
+  event: string  // publish.completed / publish.failed / webhook.test
+  task_id: string
+  detail_id: string
+  payload: string
+  success: boolean
+  attempts: number
+  response_code: number | null
+  error: string | null
+  created_at: string
+}
+
+/**
+ * Get the publish webhook configuration
+ */
+export async function getWebhookConfig(): Promise<WebhookConfig> {
+  try {
+    return await invoke<WebhookConfig>('get_webhook_config')
+  } catch (error) {
+    console.error('Failed to get webhook config:', error)
+    throw error
+  }
+}
+
+/**
+ * Save the publish webhook configuration (returns the stored config)
+ */
+export async function saveWebhookConfig(config: WebhookConfig): Promise<WebhookConfig> {
+  try {
+    return await invoke<WebhookConfig>('save_webhook_config', { config })
+  } catch (error) {
+    console.error('Failed to save webhook config:', error)
+    throw error
+  }
+}
+
+/**
+ * Send a test notification to every configured webhook URL
+ */
+export async function testWebhook(): Promise<WebhookDelivery[]> {
+  try {
+    return await invoke<WebhookDelivery[]>('test_webhook')
+  } catch (error) {
+    console.error('Failed to test webhook:', error)
+    throw error
+  }
+}
+
+/**
+ * Get webhook delivery records with pagination
+ */
+export async function getWebhookDeliveries(
+  failedOnly: boolean = false,
+  page: number = 1,
+  pageSize: number = 20
+): Promise<Paginated<WebhookDelivery>> {
+  try {
+    return await invoke<Paginated<WebhookDelivery>>('get_webhook_deliveries', { failedOnly, page, pageSize })
+  } catch (error) {
+    console.error('Failed to get webhook deliveries:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// AI Caption Functions
+// AI 文案生成功能
+// ============================================================================
+
+export interface AiConfig {
+  enabled: boolean
+  endpoint: string  // 兼容 OpenAI 的接口地址，如 https://api.openai.com/v1
+  api_key: string
+  model: string
+}
+
+export interface CaptionCandidate {
+  title: string
+  description: string
+  hashtags: string[]
+}
+
+export async function getAiConfig(): Promise<AiConfig> {
+  try {
+    return await invoke<AiConfig>('get_ai_config')
+  } catch (error) {
+    console.error('Failed to get AI config:', error)
+    throw error
+  }
+}
+
+export async function saveAiConfig(config: AiConfig): Promise<AiConfig> {
+  try {
+    return await invoke<AiConfig>('save_ai_config', { config })
+  } catch (error) {
+    console.error('Failed to save AI config:', error)
+    throw error
+  }
+}
+
+export async function generateCaptions(
+  videoPathOrTopic: string,
+  platform: string,
+  count: number | null = null
+): Promise<CaptionCandidate[]> {
+  try {
+    return await invoke<CaptionCandidate[]>('generate_captions', { videoPathOrTopic, platform, count })
+  } catch (error) {
+    console.error('Failed to generate captions:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Report Builder Functions
+// 自定义报表功能
+// ============================================================================
+
+export type ReportEntity = 'accounts' | 'publication_tasks' | 'publication_accounts' | 'comments'
+export type ReportOperator = 'eq' | 'ne' | 'gt' | 'gte' | 'lt' | 'lte' | 'contains' | 'in'
+export type MetricFunc = 'count' | 'sum' | 'avg' | 'min' | 'max'
+
+export interface ReportQuery {
+  entity: ReportEntity
+  filters?: { field: string; op: ReportOperator; value: string | number | boolean | (string | number)[] }[]
+  group_by?: { field: string; bucket?: 'day' | 'week' | 'month' }[]
+  metrics?: { func: MetricFunc; field?: string }[]
+  limit?: number
+}
+
+export interface ReportResult {
+  columns: string[]
+  rows: (string | number | null)[][]
+}
+
+export interface ReportEntitySchema {
+  entity: ReportEntity
+  fields: { name: string; kind: 'text' | 'number' | 'time' }[]
+}
+
+/**
+ * Get entities and fields available to the report builder
+ */
+export async function getReportSchema(): Promise<ReportEntitySchema[]> {
+  try {
+    return await invoke<ReportEntitySchema[]>('get_report_schema')
+  } catch (error) {
+    console.error('Failed to get report schema:', error)
+    throw error
+  }
+}
+
+/**
+ * Run a custom report, e.g. failures by platform by week:
+ * { entity: 'publication_accounts', filters: [{ field: 'status', op: 'eq', value: 'Failed' }],
+ *   group_by: [{ field: 'platform' }, { field: 'created_at', bucket: 'week' }] }
+ */
+export async function runReport(query: ReportQuery): Promise<ReportResult> {
+  try {
+    return await invoke<ReportResult>('run_report', { query })
+  } catch (error) {
+    console.error('Failed to run report:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// App Settings Functions
+// 应用设置功能
+// ============================================================================
+
+export interface AppSettings {
+  max_concurrent_publishes: number  // 同时发布的账号数（1 ~ 20）
+  browser_auth_headless: boolean  // 重新授权已有账号时默认无头运行
+  max_publish_attempts: number  // 账号遇到网络/5xx 等临时性失败时的最大尝试次数（1 ~ 6）
+  platform_max_publish_attempts: Record<string, number>  // 按平台覆盖最大尝试次数（键为平台ID，未设置的平台使用 max_publish_attempts）
+  video_variants_enabled: boolean  // 发布时为每个账号生成去重变体（裁帧、微调码率、重编码音频）
+}
+
+export async function getAppSettings(): Promise<AppSettings> {
+  try {
+    return await invoke<AppSettings>('get_app_settings')
+  } catch (error) {
+    console.error('Failed to get app settings:', error)
+    throw error
+  }
+}
+
+/**
+ * Save app settings, returns the effective values (out-of-range values are clamped)
+ */
+export async function setAppSettings(settings: AppSettings): Promise<AppSettings> {
+  try {
+    return await invoke<AppSettings>('set_app_settings', { settings })
+  } catch (error) {
+    console.error('Failed to set app settings:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Cover Strategy Functions
+// 默认封面策略功能
+// ============================================================================
+
+/** 任务未指定封面时的默认封面策略 */
+export type CoverStrategy =
+  | { mode: 'platform_auto' }
+  | { mode: 'first_frame' }
+  | { mode: 'frame_at'; seconds: number }
+
+export async function getCoverStrategy(): Promise<CoverStrategy> {
+  try {
+    return await invoke<CoverStrategy>('get_cover_strategy')
+  } catch (error) {
+    console.error('Failed to get cover strategy:', error)
+    throw error
+  }
+}
+
+export async function setCoverStrategy(strategy: CoverStrategy): Promise<void> {
+  try {
+    await invoke('set_cover_strategy', { strategy })
+  } catch (error) {
+    console.error('Failed to set cover strategy:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Publish Pacing Functions
+// 发布节奏限制功能
+// ============================================================================
+
+/** 单个账号的发布节奏限制（0 表示不限） */
+export interface PacingRule {
+  max_per_day: number  // 24 小时内最多发布次数
+  min_interval_minutes: number  // 两次发布的最小间隔（分钟）
+}
+
+/** 发布节奏规则：默认规则作用于所有账号，平台规则覆盖该平台账号的默认规则 */
+export interface PublishPacingRules {
+  account: PacingRule
+  platforms: Record<string, PacingRule>  // 平台ID → 规则
+}
+
+export async function getPublishPacingRules(): Promise<PublishPacingRules> {
+  try {
+    return await invoke<PublishPacingRules>('get_publish_pacing_rules')
+  } catch (error) {
+    console.error('Failed to get publish pacing rules:', error)
+    throw error
+  }
+}
+
+export async function setPublishPacingRules(rules: PublishPacingRules): Promise<void> {
+  try {
+    await invoke('set_publish_pacing_rules', { rules })
+  } catch (error) {
+    console.error('Failed to set publish pacing rules:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Scheduled Publishing Functions
+// 定时发布功能
+// ============================================================================
+
+export type ScheduledJobStatus = 'pending' | 'running' | 'completed' | 'failed' | 'cancelled'
+
+export interface ScheduledJob {
+  id: string
+  task_id: string
+  send_at: string  // 本地时间 YYYY-MM-DD HH:MM:SS
+  status: ScheduledJobStatus
+  message: string | null
+  created_at: string
+  updated_at: string
+}
+
+/**
+ * Schedule a publication task for a future time (local time, YYYY-MM-DD HH:MM:SS)
+ */
+export async function schedulePublicationTask(taskId: string, sendAt: string): Promise<ScheduledJob> {
+  try {
+    return await invoke<ScheduledJob>('schedule_publication_task', { taskId, sendAt })
+  } catch (error) {
+    console.error('Failed to schedule publication task:', error)
+    throw error
+  }
+}
+
+export async function getScheduledJobs(
+  taskId: string | null = null,
+  page: number | null = null,
+  pageSize: number | null = null
+): Promise<Paginated<ScheduledJob>> {
+  try {
+    return await invoke<Paginated<ScheduledJob>>('get_scheduled_jobs', { taskId, page, pageSize })
+  } catch (error) {
+    console.error('Failed to get scheduled jobs:', error)
+    throw error
+  }
+}
+
+export async function cancelScheduledJob(jobId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('cancel_scheduled_job', { jobId })
+  } catch (error) {
+    console.error('Failed to cancel scheduled job:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Publish Log Functions
+// 发布日志实时查看功能
+// ============================================================================
+
+export interface PublishLogEntry {
+  detail_id: string
+  level: string  // TRACE / DEBUG / INFO / WARN / ERROR
+  message: string
+  timestamp: number  // 毫秒时间戳
+}
+
+/**
+ * Tail the logs of one account publish; returns a function that stops the tail
+ */
+export async function tailPublishLogs(
+  detailId: string,
+  onLog: (entry: PublishLogEntry) => void
+): Promise<() => Promise<void>> {
+  const unlisten = await listen<PublishLogEntry>('publish-log', (event) => {
+    if (event.payload.detail_id === detailId) {
+      onLog(event.payload)
+    }
+  })
+
+  try {
+    await invoke('subscribe_publish_logs', { detailId })
+  } catch (error) {
+    unlisten()
+    console.error('Failed to subscribe publish logs:', error)
+    throw error
+  }
+
+  return async () => {
+    unlisten()
+    try {
+      await invoke<boolean>('unsubscribe_publish_logs', { detailId })
+    } catch (error) {
+      console.error('Failed to unsubscribe publish logs:', error)
+    }
+  }
+}
+
+// ============================================================================
+// Task Note Functions
+// 任务备注功能
+// ============================================================================
+
+export interface TaskNote {
+  id: string
+  task_id: string
+  author: string
+  content: string
+  created_at: string
+}
+
+export async function addTaskNote(taskId: string, content: string, author: string | null = null): Promise<TaskNote> {
+  try {
+    return await invoke<TaskNote>('add_task_note', { taskId, content, author })
+  } catch (error) {
+    console.error('Failed to add task note:', error)
+    throw error
+  }
+}
+
+export async function getTaskNotes(taskId: string): Promise<TaskNote[]> {
+  try {
+    return await invoke<TaskNote[]>('get_task_notes', { taskId })
+  } catch (error) {
+    console.error('Failed to get task notes:', error)
+    throw error
+  }
+}
+
+export interface AccountReassignment {
+  id: string
+  detail_id: string
+  task_id: string
+  from_account_id: string
+  from_account_name: string
+  to_account_id: string
+  to_account_name: string
+  reason: string
+  created_at: string
+}
+
+/**
+ * Reassign a pending account publication to another account of the same platform
+ * 将未发布的账号详情改派到同平台的其他账号
+ */
+export async function reassignPublicationAccount(
+  detailId: string,
+  accountId: string,
+  reason: string | null = null
+): Promise<PublicationAccountDetail> {
+  try {
+    return await invoke<PublicationAccountDetail>('reassign_publication_account', { detailId, accountId, reason })
+  } catch (error) {
+    console.error('Failed to reassign publication account:', error)
+    throw error
+  }
+}
+
+export async function getAccountReassignments(taskId: string): Promise<AccountReassignment[]> {
+  try {
+    return await invoke<AccountReassignment[]>('get_account_reassignments', { taskId })
+  } catch (error) {
+    console.error('Failed to get account reassignments:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Hashtag Set Functions
+// 话题库功能
+// ============================================================================
+
+export interface HashtagSet {
+  id: string
+  name: string
+  tags: string[]
+  platform_tags: Record<string, string[]>  // 各平台变体，键为小写平台名
+  created_at: string
+  updated_at: string
+}
+
+export async function getHashtagSets(): Promise<HashtagSet[]> {
+  try {
+    return await invoke<HashtagSet[]>('get_hashtag_sets')
+  } catch (error) {
+    console.error('Failed to get hashtag sets:', error)
+    throw error
+  }
+}
+
+export async function saveHashtagSet(
+  id: string | null,
+  name: string,
+  tags: string[],
+  platformTags: Record<string, string[]> = {}
+): Promise<HashtagSet> {
+  try {
+    return await invoke<HashtagSet>('save_hashtag_set', { id, name, tags, platformTags })
+  } catch (error) {
+    console.error('Failed to save hashtag set:', error)
+    throw error
+  }
+}
+
+export async function deleteHashtagSet(setId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('delete_hashtag_set', { setId })
+  } catch (error) {
+    console.error('Failed to delete hashtag set:', error)
+    throw error
+  }
+}
+
+export async function attachHashtagSetToTask(taskId: string, setId: string | null): Promise<boolean> {
+  try {
+    return await invoke<boolean>('attach_hashtag_set_to_task', { taskId, setId })
+  } catch (error) {
+    console.error('Failed to attach hashtag set to task:', error)
+    throw error
+  }
+}
+
+export interface HashtagSuggestion {
+  name: string
+  usage_count: number
+}
+
+export interface HashtagPerformance {
+  name: string
+  douyin_hashtag_id: string | null
+  usage_count: number
+  last_used_at: string
+  comments: number
+  likes: number
+  favorites: number
+  shares: number
+  avg_likes: number
+}
+
+export async function getHashtagSuggestions(prefix: string, limit: number | null = null): Promise<HashtagSuggestion[]> {
+  try {
+    return await invoke<HashtagSuggestion[]>('get_hashtag_suggestions', { prefix, limit })
+  } catch (error) {
+    console.error('Failed to get hashtag suggestions:', error)
+    throw error
+  }
+}
+
+export async function getHashtagPerformance(): Promise<HashtagPerformance[]> {
+  try {
+    return await invoke<HashtagPerformance[]>('get_hashtag_performance')
+  } catch (error) {
+    console.error('Failed to get hashtag performance:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Publication Template Functions
+// 发布模板功能
+// ============================================================================
+
+export interface TemplateAccount {
+  account_id: string
+  platform: PlatformType
+  title_override: string | null
+  description_override: string | null
+  hashtags_override: string[] | null
+}
+
+export interface PublicationTemplate {
+  id: string
+  name: string
+  title: string
+  description: string
+  hashtags: string[]
+  hashtag_set_id: string | null
+  accounts: TemplateAccount[]
+  created_at: string
+  updated_at: string
+}
+
+export async function getPublicationTemplates(): Promise<PublicationTemplate[]> {
+  try {
+    return await invoke<PublicationTemplate[]>('get_publication_templates')
+  } catch (error) {
+    console.error('Failed to get publication templates:', error)
+    throw error
+  }
+}
+
+export async function saveTaskAsTemplate(
+  taskId: string,
+  name: string,
+  templateId: string | null = null
+): Promise<PublicationTemplate> {
+  try {
+    return await invoke<PublicationTemplate>('save_task_as_template', { taskId, name, templateId })
+  } catch (error) {
+    console.error('Failed to save task as template:', error)
+    throw error
+  }
+}
+
+export async function deletePublicationTemplate(templateId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('delete_publication_template', { templateId })
+  } catch (error) {
+    console.error('Failed to delete publication template:', error)
+    throw error
+  }
+}
+
+export async function createTaskFromTemplate(
+  templateId: string,
+  videoPath: string
+): Promise<PublicationTaskWithAccounts> {
+  try {
+    return await invoke<PublicationTaskWithAccounts>('create_task_from_template', { templateId, videoPath })
+  } catch (error) {
+    console.error('Failed to create task from template:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Platform Module Version Functions
+// 平台模块版本功能
+// ============================================================================
+
+export interface ModuleChangelogEntry {
+  version: string
+  notes: string
+}
+
+export interface PlatformModuleVersion {
+  platform: PlatformType
+  module: string
+  version: string
+  changelog: ModuleChangelogEntry[]
+}
+
+export async function getPlatformModuleVersions(): Promise<PlatformModuleVersion[]> {
+  try {
+    return await invoke<PlatformModuleVersion[]>('get_platform_module_versions')
+  } catch (error) {
+    console.error('Failed to get platform module versions:', error)
+    throw error
+  }
+}
+
+export interface EventSchema {
+  name: string
+  version: number
+  legacy_name: string | null
+  description: string
+  fields: string[]
+}
+
+/**
+ * Get the versioned events the backend emits (e.g. 'amm://publish/progress/v2')
+ * 获取后端推送的事件清单
+ */
+export async function getEventSchema(): Promise<EventSchema[]> {
+  try {
+    return await invoke<EventSchema[]>('get_event_schema')
+  } catch (error) {
+    console.error('Failed to get event schema:', error)
+    throw error
+  }
+}
+
+export interface CommandMetric {
+  command: string
+  calls: number
+  total_ms: number
+  avg_ms: number
+  max_ms: number
+  last_ms: number
+  slow_calls: number  // 超过 1 秒的调用次数
+  last_args: string
+  last_called_at: string
+  asynchronous: boolean
+}
+
+export async function getCommandMetrics(): Promise<CommandMetric[]> {
+  try {
+    return await invoke<CommandMetric[]>('get_command_metrics')
+  } catch (error) {
+    console.error('Failed to get command metrics:', error)
+    throw error
+  }
+}
+
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace'
+
+export interface LogEntry {
+  timestamp: string  // 本地时间 YYYY-MM-DD HH:mm:ss.SSS
+  level: string      // ERROR / WARN / INFO / DEBUG / TRACE
+  message: string
+}
+
+/**
+ * Recent entries from the log files at or above `level` (default info), oldest first
+ * 获取最近的日志，反馈发布失败时可附带
+ */
+export async function getRecentLogs(level: LogLevel | null = null, limit: number | null = null): Promise<LogEntry[]> {
+  try {
+    return await invoke<LogEntry[]>('get_recent_logs', { level, limit })
+  } catch (error) {
+    console.error('Failed to get recent logs:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Database Maintenance Functions
+// 数据库维护功能
+// ============================================================================
+
+export interface SchemaIssue {
+  kind: 'missing_table' | 'missing_column' | 'type_mismatch'
+  table: string
+  column: string | null
+  expected: string | null
+  actual: string | null
+  repaired: boolean
+}
+
+export interface SchemaReport {
+  expected_hash: string
+  live_hash: string
+  issues: SchemaIssue[]
+}
+
+/**
+ * Verify the database schema, repairing missing columns where possible
+ */
+export async function checkDatabaseSchema(): Promise<SchemaReport> {
+  try {
+    return await invoke<SchemaReport>('check_database_schema')
+  } catch (error) {
+    console.error('Failed to check database schema:', error)
+    throw error
+  }
+}
+
+export interface ContentionStats {
+  busy_errors: number        // 遇到数据库锁的次数
+  recovered: number          // 重试后成功的写操作数
+  exhausted: number          // 重试用尽仍失败的写操作数
+  serialized_writes: number  // 串行模式下执行的写操作数
+  serializing: boolean       // 当前是否处于串行写入模式
+}
+
+/**
+ * Database lock contention counters since app start
+ */
+export async function getDbContentionStats(): Promise<ContentionStats> {
+  try {
+    return await invoke<ContentionStats>('get_db_contention_stats')
+  } catch (error) {
+    console.error('Failed to get database contention stats:', error)
+    throw error
+  }
+}
+
+/**
+ * Export an anonymized database snapshot for bug reports
+ * 导出匿名数据快照（凭证已清除、昵称已哈希、素材路径已脱敏）；未指定路径时弹出保存对话框，取消返回 null
+ */
+export async function exportAnonymizedSnapshot(outputPath?: string): Promise<string | null> {
+  try {
+    return await invoke<string | null>('export_anonymized_snapshot', { outputPath })
+  } catch (error) {
+    console.error('Failed to export anonymized snapshot:', error)
+    throw error
+  }
+}
+
+export interface BackupSummary {
+  created_at: string        // 备份导出时间
+  accounts: number
+  account_groups: number
+  publication_tasks: number
+  publication_details: number
+  extractor_configs: number
+  comments: number
+}
+
+/**
+ * Export an encrypted backup (accounts with credentials, tasks, details, extractor configs, comments)
+ * 导出加密备份，用于迁移到其他电脑；未指定路径时弹出保存对话框，取消返回 null
+ */
+export async function exportBackup(passphrase: string, path?: string): Promise<BackupSummary | null> {
+  try {
+    return await invoke<BackupSummary | null>('export_backup', { path, passphrase })
+  } catch (error) {
+    console.error('Failed to export backup:', error)
+    throw error
+  }
+}
+
+/**
+ * Import an encrypted backup; rows with the same id are overwritten
+ * 导入加密备份（同 id 数据被覆盖，其余保留）；未指定路径时弹出文件选择对话框，取消返回 null
+ */
+export async function importBackup(passphrase: string, path?: string): Promise<BackupSummary | null> {
+  try {
+    return await invoke<BackupSummary | null>('import_backup', { path, passphrase })
+  } catch (error) {
+    console.error('Failed to import backup:', error)
+    throw error
+  }
+}
+
+export interface LoadTestConfig {
+  accounts: number
+  max_concurrent: number
+  min_latency_ms: number
+  max_latency_ms: number
+  failure_rate: number       // 0.0 ~ 1.0
+}
+
+export interface LoadTestReport {
+  accounts: number
+  max_concurrent: number
+  success_count: number
+  failed_count: number
+  write_errors: number
+  elapsed_ms: number
+  throughput_per_sec: number
+  latency_p50_ms: number
+  latency_p95_ms: number
+  latency_max_ms: number
+  busy_errors: number
+  recovered: number
+  exhausted: number
+  serialized_writes: number
+}
+
+/**
+ * Run the publish load test against a mock platform
+ * 开发用发布压测，仅在以 load-test feature 编译的版本中可用
+ */
+export async function runPublishLoadTest(config: Partial<LoadTestConfig> = {}): Promise<LoadTestReport> {
+  try {
+    return await invoke<LoadTestReport>('run_publish_load_test', { config })
+  } catch (error) {
+    console.error('Failed to run publish load test:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Account Stats Functions
+// 账号数据统计功能
+// ============================================================================
+
+/** Inclusive date range, dates formatted as YYYY-MM-DD */
+export interface StatsPeriod {
+  start: string
+  end: string
+}
+
+export interface MetricDelta {
+  metric: 'works' | 'comments' | 'likes' | 'favorites' | 'shares'
+  period_a: number
+  period_b: number
+  change: number
+  change_rate: number | null
+}
+
+export interface AccountStatsDelta {
+  account_id: string
+  period_a: StatsPeriod
+  period_b: StatsPeriod
+  metrics: MetricDelta[]
+}
+
+/**
+ * Compare an account's engagement growth between two periods
+ */
+export async function getAccountStatsDelta(accountId: string, periodA: StatsPeriod, periodB: StatsPeriod): Promise<AccountStatsDelta> {
+  try {
+    return await invoke<AccountStatsDelta>('get_account_stats_delta', { accountId, periodA, periodB })
+  } catch (error) {
+    console.error('Failed to get account stats delta:', error)
+    throw error
+  }
+}
+
+export interface AccountProfileStats {
+  followers: number
+  following: number
+  total_likes: number  // 账号累计获赞数
+  video_count: number  // 平台上的作品数
+}
+
+export interface AccountStatsSnapshot {
+  account_id: string
+  snapshot_date: string  // YYYY-MM-DD
+  works: number
+  comments: number
+  likes: number
+  favorites: number
+  shares: number
+  followers: number
+  following: number
+  total_likes: number
+  video_count: number
+}
+
+/**
+ * Refresh an account's follower count, total likes and video count from the platform (Douyin only)
+ */
+export async function refreshAccountStats(accountId: string): Promise<AccountProfileStats> {
+  try {
+    return await invoke<AccountProfileStats>('refresh_account_stats', { accountId })
+  } catch (error) {
+    console.error('Failed to refresh account stats:', error)
+    throw error
+  }
+}
+
+/**
+ * Daily stats snapshots of the last `days` days (default 30), oldest first, for trend charts
+ */
+export async function getAccountStatsHistory(accountId: string, days: number | null = null): Promise<AccountStatsSnapshot[]> {
+  try {
+    return await invoke<AccountStatsSnapshot[]>('get_account_stats_history', { accountId, days })
+  } catch (error) {
+    console.error('Failed to get account stats history:', error)
+    throw error
+  }
+}
+
+export type TimelineEventKind = 'publish' | 'publish_result' | 'auth_refresh' | 'stats_milestone'
+
+export interface AccountTimelineEvent {
+  kind: TimelineEventKind
+  occurred_at: string  // YYYY-MM-DD HH:MM:SS
+  title: string
+  detail: string | null
+  reference_id: string | null  // 发布明细ID等
+}
+
+/**
+ * Get an account's activity timeline (newest first)
+ */
+export async function getAccountTimeline(accountId: string, limit: number | null = null): Promise<AccountTimelineEvent[]> {
+  try {
+    return await invoke<AccountTimelineEvent[]>('get_account_timeline', { accountId, limit })
+  } catch (error) {
+    console.error('Failed to get account timeline:', error)
+    throw error
+  }
+}
+
+export type AccountHealthLevel = 'healthy' | 'warning' | 'critical' | 'unknown'
+
+export interface AccountHealth {
+  account_id: string
+  score: number | null
+  level: AccountHealthLevel
+  recent_publishes: number
+  successes: number
+  auth_failures: number
+  api_failures: number
+  last_failure: string | null
+  consecutive_failures: number
+  risk_control_failures: number
+}
+
+export interface AccountOverview {
+  account: UserAccount
+  health: AccountHealth
+}
+
+/**
+ * List accounts with a health score derived from recent publish outcomes
+ * 账号概览（含健康分）
+ */
+export async function getAccountOverview(page: number | null = null, pageSize: number | null = null): Promise<Paginated<AccountOverview>> {
+  try {
+    return await invoke<Paginated<AccountOverview>>('get_account_overview', { page, pageSize })
+  } catch (error) {
+    console.error('Failed to get account overview:', error)
+    throw error
+  }
+}
+
+/**
+ * Get health warnings for the accounts selected for a new task (only warning / critical accounts)
+ * 新建任务前检查账号健康度
+ */
+export async function checkAccountHealth(accountIds: string[]): Promise<AccountHealth[]> {
+  try {
+    return await invoke<AccountHealth[]>('check_account_health', { accountIds })
+  } catch (error) {
+    console.error('Failed to check account health:', error)
+    throw error
+  }
+}
+
+export type RiskFlag = 'consecutive_failures' | 'high_failure_rate' | 'risk_control'
+
+export interface AccountHealthReport {
+  account: UserAccount
+  health: AccountHealth
+  failure_rate: number | null  // 百分比
+  risk_flags: RiskFlag[]
+  risk_reasons: string[]
+}
+
+/**
+ * Get the health report of all accounts, risky accounts first
+ * 账号健康报告（风险账号在前）
+ */
+export async function getAccountHealthReport(): Promise<AccountHealthReport[]> {
+  try {
+    return await invoke<AccountHealthReport[]>('get_account_health_report')
+  } catch (error) {
+    console.error('Failed to get account health report:', error)
+    throw error
+  }
+}
+
+/**
+ * Clear the risky flag of an account
+ * 解除账号的风险标记
+ */
+export async function clearAccountRisk(accountId: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>('clear_account_risk', { accountId })
+  } catch (error) {
+    console.error('Failed to clear account risk:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Publish Statistics Functions
+// 发布统计功能
+// ============================================================================
+
+export interface StatusCounts {
+  total: number
+  draft: number
+  publishing: number
+  completed: number
+  failed: number
+  skipped: number
+}
+
+export interface PublishStatistics {
+  start: string
+  end: string
+  tasks: StatusCounts
+  publications: StatusCounts
+  publication_success_rate: number | null  // completed / (completed + failed)
+  by_platform: { platform: string; counts: StatusCounts; success_rate: number | null }[]
+  by_account: { account_id: string; account_name: string; platform: string; counts: StatusCounts; success_rate: number | null }[]
+  daily: { date: string; counts: StatusCounts }[]
+}
+
+/**
+ * Task/publication counts by status, platform, account and day (dates YYYY-MM-DD, defaults to the last 30 days)
+ */
+export async function getPublishStatistics(range: StatsPeriod | null = null): Promise<PublishStatistics> {
+  try {
+    return await invoke<PublishStatistics>('get_publish_statistics', { range })
+  } catch (error) {
+    console.error('Failed to get publish statistics:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Failure Analytics Functions
+// 发布失败分析功能
+// ============================================================================
+
+export type FailureCategory =
+  | 'credential_expired'
+  | 'risk_control'
+  | 'timeout'
+  | 'network'
+  | 'upload_failed'
+  | 'content_rejected'
+  | 'rate_limited'
+  | 'invalid_input'
+  | 'other'
+
+export interface FailureCategoryCount {
+  category: FailureCategory
+  label: string
+  count: number
+  share: number  // 0 ~ 1
+}
+
+export interface FailureBreakdown {
+  start: string
+  end: string
+  total: number
+  categories: FailureCategoryCount[]
+  daily: { date: string; counts: Partial<Record<FailureCategory, number>> }[]
+}
+
+/**
+ * Failed publishes grouped by failure category (dates YYYY-MM-DD, defaults to the last 7 days)
+ */
+export async function getFailureBreakdown(start: string | null = null, end: string | null = null): Promise<FailureBreakdown> {
+  try {
+    return await invoke<FailureBreakdown>('get_failure_breakdown', { start, end })
+  } catch (error) {
+    console.error('Failed to get failure breakdown:', error)
+    throw error
+  }
+}
+
+// ============================================================================
+// Cache Functions
+// 缓存管理功能
+// ============================================================================
+
+export type CacheCategory = 'transcode' | 'thumbnails' | 'debug'
+
+export interface CacheCategoryUsage {
+  category: CacheCategory
+  size_bytes: number
+  file_count: number
+  size_cap: number
+}
+
+export interface CacheCleanupResult {
+  reclaimed_bytes: number
+  removed_files: number
+}
+
+export async function getCacheUsage(): Promise<CacheCategoryUsage[]> {
+  try {
+    return await invoke<CacheCategoryUsage[]>('get_cache_usage')
+  } catch (error) {
+    console.error('Failed to get cache usage:', error)
+    throw error
+  }
+}
+
+/**
+ * Clear cached temp files (all categories when none given)
+ */
+export async function clearCache(category: CacheCategory | null = null): Promise<CacheCleanupResult> {
+  try {
+    return await invoke<CacheCleanupResult>('clear_cache', { category })
+  } catch (error) {
+    console.error('Failed to clear cache:', error)
     throw error
   }
 }